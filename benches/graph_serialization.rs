@@ -0,0 +1,99 @@
+//! Benchmarks comparing JSON and CBOR dependency graph serialization
+//!
+//! Run with `cargo bench --features cbor`. Encoded sizes are printed once
+//! per graph size before timing starts.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_ecosystem_adapter::models::*;
+use uuid::Uuid;
+
+/// Build a synthetic graph shaped like a mid-sized workspace lockfile
+fn synthetic_graph(package_count: usize) -> DependencyGraph {
+    let mut graph = DependencyGraph::new("bench-project".to_string(), "rust".to_string());
+    
+    for i in 0..package_count {
+        let checksum = format!("{:064x}", i);
+        graph.add_package(PackageNode {
+            id: Uuid::new_v4(),
+            name: format!("crate-{}", i),
+            version: format!("1.{}.{}", i % 20, i % 7),
+            source: PackageSource::Registry {
+                url: "https://github.com/rust-lang/crates.io-index".to_string(),
+                checksum: checksum.clone(),
+            },
+            checksum,
+            classification: if i % 5 == 0 {
+                Classification::TCS {
+                    category: TcsCategory::Cryptography,
+                    rationale: "Matched cryptography name pattern".to_string(),
+                }
+            } else {
+                Classification::Mechanical { category: MechanicalCategory::Utility }
+            },
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![
+                RustAnnotation::new("features".to_string(), serde_json::json!(["default", "std"])),
+                RustAnnotation::new("dependency_kind".to_string(), serde_json::json!("normal")),
+                RustAnnotation::new("edition".to_string(), serde_json::json!("2021")),
+            ],
+        });
+    }
+    
+    let ids: Vec<_> = graph.root_packages.iter().map(|p| p.id).collect();
+    for (i, from) in ids.iter().enumerate() {
+        for offset in 1..=3 {
+            if let Some(to) = ids.get(i + offset) {
+                graph.add_edge(DependencyEdge {
+                    from: *from,
+                    to: *to,
+                    kind: DependencyKind::Normal,
+                    target: None,
+                    optional: false,
+                    features: vec!["default".to_string()],
+                });
+            }
+        }
+    }
+    
+    graph
+}
+
+fn bench_graph_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph_serialization");
+    
+    for package_count in [100, 1_000, 5_000] {
+        let graph = synthetic_graph(package_count);
+        let json = serde_json::to_vec(&graph).unwrap();
+        let cbor = graph.to_cbor().unwrap();
+        
+        let decoded = DependencyGraph::from_cbor(&cbor).unwrap();
+        assert_eq!(decoded, graph);
+        assert_eq!(decoded.metadata.schema_version, graph.metadata.schema_version);
+        
+        println!(
+            "{} packages: json {} bytes, cbor {} bytes ({:.1}% of json)",
+            package_count,
+            json.len(),
+            cbor.len(),
+            cbor.len() as f64 * 100.0 / json.len() as f64,
+        );
+        
+        group.bench_with_input(BenchmarkId::new("json_encode", package_count), &graph, |b, graph| {
+            b.iter(|| serde_json::to_vec(black_box(graph)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("cbor_encode", package_count), &graph, |b, graph| {
+            b.iter(|| black_box(graph).to_cbor().unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("json_decode", package_count), &json, |b, json| {
+            b.iter(|| serde_json::from_slice::<DependencyGraph>(black_box(json)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("cbor_decode", package_count), &cbor, |b, cbor| {
+            b.iter(|| DependencyGraph::from_cbor(black_box(cbor)).unwrap())
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_graph_serialization);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+/*
+ * C ABI of the Rust ecosystem adapter (built with `--features ffi`).
+ *
+ * Requests and responses are NUL-terminated UTF-8 JSON strings. Every
+ * response must be released with rust_adapter_free_string. Responses are
+ * {"ok": true, "result": ...} or {"ok": false, "error": "..."}; a NULL
+ * request yields a NULL response.
+ */
+
+#ifndef RUST_ADAPTER_H
+#define RUST_ADAPTER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RUST_ADAPTER_ABI_VERSION 1
+
+/* Version of the ABI implemented by the loaded library. */
+uint32_t rust_adapter_abi_version(void);
+
+/* {"project_path": "...", "config_path": "..."} -> dependency graph */
+char *rust_adapter_parse_dependencies(const char *request);
+
+/* {"project_path": "...", "config_path": "..."} -> SBOM document */
+char *rust_adapter_generate_sbom(const char *request);
+
+/* {"project_path": "...", "config_path": "...", "epoch": {...}} -> drift report */
+char *rust_adapter_detect_drift(const char *request);
+
+/* Release a string returned by the library. Accepts NULL. */
+void rust_adapter_free_string(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUST_ADAPTER_H */
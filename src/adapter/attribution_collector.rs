@@ -0,0 +1,239 @@
+//! License and notice collection for third-party attribution
+//!
+//! This module walks a vendor directory and collects the license and
+//! notice files at the root of each vendored crate for the packages an
+//! SBOM describes. Identical texts shipped by several crates (the same
+//! MIT or Apache-2.0 license, say) are kept once, so the rendered
+//! THIRD-PARTY-NOTICES file stays readable. Texts are compared after
+//! normalizing line endings and trailing whitespace.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Vendored crate directory and declared license, by name and version
+type VendoredCrates = BTreeMap<(String, String), (PathBuf, Option<String>)>;
+
+/// File name prefixes (uppercase) of license files
+const LICENSE_PREFIXES: &[&str] = &["LICENSE", "LICENCE", "COPYING", "UNLICENSE"];
+
+/// File name prefixes (uppercase) of notice files
+const NOTICE_PREFIXES: &[&str] = &["NOTICE", "COPYRIGHT"];
+
+/// License files larger than this are skipped (bytes)
+const MAX_LICENSE_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Attribution collector implementation
+#[derive(Debug, Clone)]
+pub struct AttributionCollector {
+    /// Whether collector is ready
+    ready: bool,
+}
+
+/// Subset of a vendored `Cargo.toml`
+#[derive(Debug, Deserialize)]
+struct VendoredManifest {
+    package: VendoredPackage,
+}
+
+/// `[package]` table of a vendored manifest
+#[derive(Debug, Deserialize)]
+struct VendoredPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    license: Option<String>,
+}
+
+impl AttributionCollector {
+    /// Create new attribution collector with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self { ready: true }
+    }
+    
+    /// Check if collector is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Collect the license and notice files of `packages` from a vendor directory
+    ///
+    /// `packages` are the third-party packages of the SBOM. Vendored crates
+    /// outside that set are ignored; packages without a vendored copy are
+    /// listed in `not_vendored`. The returned bundle leaves `epoch_id` and
+    /// `lockfile_fingerprint` for the caller to fill in.
+    pub fn collect(&self, packages: &[&PackageNode], vendor_dir: &Path) -> Result<AttributionBundle> {
+        let vendored = Self::vendored_crates(vendor_dir)?;
+        
+        let mut wanted: Vec<(&str, &str)> = packages.iter()
+            .map(|package| (package.name.as_str(), package.version.as_str()))
+            .collect();
+        wanted.sort();
+        wanted.dedup();
+        
+        let mut attributed = Vec::new();
+        let mut texts: Vec<AttributionText> = Vec::new();
+        let mut text_index: HashMap<String, usize> = HashMap::new();
+        let mut not_vendored = Vec::new();
+        let mut statistics = AttributionStatistics::default();
+        
+        for (name, version) in wanted {
+            let key = PolicyFacts::package_key(name, version);
+            let Some((crate_dir, license)) = vendored.get(&(name.to_string(), version.to_string())) else {
+                not_vendored.push(key);
+                continue;
+            };
+            
+            let mut files = Vec::new();
+            for (path, kind) in Self::license_files(crate_dir) {
+                let content = match std::fs::read(&path) {
+                    Ok(bytes) => Self::normalize(&String::from_utf8_lossy(&bytes)),
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable license file");
+                        continue;
+                    },
+                };
+                let text_id = format!("{:x}", Sha256::digest(content.as_bytes()));
+                
+                let index = *text_index.entry(text_id.clone()).or_insert_with(|| {
+                    texts.push(AttributionText { id: text_id.clone(), kind, packages: Vec::new(), content });
+                    texts.len() - 1
+                });
+                if !texts[index].packages.contains(&key) {
+                    texts[index].packages.push(key.clone());
+                }
+                
+                let relative = path.strip_prefix(vendor_dir).unwrap_or(&path);
+                files.push(AttributionFile {
+                    path: relative.to_string_lossy().replace('\\', "/"),
+                    kind,
+                    text_id,
+                });
+            }
+            
+            if !files.iter().any(|file| file.kind == AttributionKind::License) {
+                statistics.packages_without_license_file.push(key);
+            }
+            statistics.files += files.len();
+            attributed.push(AttributedPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                license: license.clone(),
+                files,
+            });
+        }
+        
+        statistics.packages = attributed.len();
+        statistics.distinct_texts = texts.len();
+        
+        Ok(AttributionBundle {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            epoch_id: None,
+            lockfile_fingerprint: String::new(),
+            packages: attributed,
+            texts,
+            not_vendored,
+            statistics,
+        })
+    }
+    
+    /// Vendored crate directories and declared licenses, by name and version
+    fn vendored_crates(vendor_dir: &Path) -> Result<VendoredCrates> {
+        let entries = std::fs::read_dir(vendor_dir)
+            .map_err(|_| AdapterError::file_not_found(vendor_dir, "reading vendor directory"))?;
+        
+        let mut crates = BTreeMap::new();
+        for crate_dir in entries.flatten().map(|entry| entry.path()) {
+            let manifest_path = crate_dir.join("Cargo.toml");
+            let Ok(content) = std::fs::read_to_string(&manifest_path) else { continue };
+            match toml::from_str::<VendoredManifest>(&content) {
+                Ok(manifest) => {
+                    let package = manifest.package;
+                    crates.insert((package.name, package.version), (crate_dir, package.license));
+                },
+                Err(e) => tracing::warn!(path = %manifest_path.display(), error = %e, "Skipping unreadable vendored manifest"),
+            }
+        }
+        
+        Ok(crates)
+    }
+    
+    /// License and notice files at the root of a crate, sorted by name
+    fn license_files(crate_dir: &Path) -> Vec<(PathBuf, AttributionKind)> {
+        let Ok(entries) = std::fs::read_dir(crate_dir) else { return Vec::new() };
+        
+        let mut files: Vec<(PathBuf, AttributionKind)> = entries.flatten()
+            .filter(|entry| entry.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.len() <= MAX_LICENSE_FILE_SIZE))
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_uppercase();
+                let kind = if LICENSE_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+                    AttributionKind::License
+                } else if NOTICE_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+                    AttributionKind::Notice
+                } else {
+                    return None;
+                };
+                Some((entry.path(), kind))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+    
+    /// Normalize line endings and trailing whitespace
+    fn normalize(text: &str) -> String {
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let mut normalized = lines.join("\n").trim_matches('\n').to_string();
+        normalized.push('\n');
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use tempfile::TempDir;
+    
+    const MIT: &str = "Permission is hereby granted, free of charge, to any person obtaining a copy\n";
+    
+    fn vendor_crate(vendor_dir: &Path, dir: &str, name: &str, version: &str, files: &[(&str, &str)]) {
+        let crate_dir = vendor_dir.join(dir);
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(crate_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"{}\"\nlicense = \"MIT\"\n", name, version)).unwrap();
+        for (file, content) in files {
+            std::fs::write(crate_dir.join(file), content).unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_collect_deduplicates_texts_for_sbom_packages() {
+        let temp = TempDir::new().unwrap();
+        let vendor_dir = temp.path();
+        vendor_crate(vendor_dir, "itoa", "itoa", "1.0.0", &[("LICENSE-MIT", MIT)]);
+        vendor_crate(vendor_dir, "ryu", "ryu", "1.0.0", &[("LICENSE-MIT", &MIT.replace('\n', "\r\n")), ("NOTICE", "Copyright ryu authors\n")]);
+        vendor_crate(vendor_dir, "bare", "bare", "0.1.0", &[("README.md", "no license here")]);
+        vendor_crate(vendor_dir, "unused", "unused", "2.0.0", &[("LICENSE", "Other terms\n")]);
+        
+        let packages = [package("itoa", "1.0.0", Classification::Unknown), package("ryu", "1.0.0", Classification::Unknown), package("bare", "0.1.0", Classification::Unknown), package("missing", "0.3.0", Classification::Unknown)];
+        let packages: Vec<&PackageNode> = packages.iter().collect();
+        let bundle = AttributionCollector::new(&RustAdapterConfig::default()).collect(&packages, vendor_dir).unwrap();
+        
+        assert_eq!(bundle.statistics.packages, 3);
+        assert_eq!(bundle.statistics.files, 3);
+        assert_eq!(bundle.statistics.distinct_texts, 2);
+        assert_eq!(bundle.statistics.packages_without_license_file, vec!["bare@0.1.0"]);
+        assert_eq!(bundle.not_vendored, vec!["missing@0.3.0"]);
+        assert_eq!(bundle.texts[0].packages, vec!["itoa@1.0.0", "ryu@1.0.0"]);
+        assert_eq!(bundle.texts[1].kind, AttributionKind::Notice);
+        
+        let notices = bundle.render_notices();
+        assert_eq!(notices.matches("Permission is hereby granted").count(), 1);
+        assert!(!notices.contains("Other terms"));
+        assert!(!serde_json::to_string(&bundle).unwrap().contains("Permission is hereby granted"));
+    }
+}
@@ -4,9 +4,19 @@
 //! to provide comprehensive security auditing capabilities.
 
 use crate::models::*;
-use crate::error::Result;
-use async_trait::async_trait;
-use std::process::Command;
+use crate::error::{AdapterError, Result};
+use crate::utils::{deadline, telemetry, CacheLock, CancellationToken, ChecksumCalculator, ProcessInvoker, SharedCache, ToolInvocation, ToolInvoker};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Dependency paths kept per finding
+const MAX_FINDING_PATHS: usize = 5;
+
+/// Entry of the shared cache holding the RustSec advisory database
+pub const ADVISORY_DB_ENTRY: &str = "advisory-db";
 
 /// Audit runner implementation
 #[derive(Debug, Clone)]
@@ -15,6 +25,12 @@ pub struct AuditRunner {
     config: AuditRunnerConfig,
     /// Whether runner is ready
     ready: bool,
+    /// Token that stops running audit tools when cancelled
+    cancel: CancellationToken,
+    /// Runs cargo-audit and cargo-vet
+    tools: Arc<dyn ToolInvoker>,
+    /// Permits bounding the external tools running at once
+    tool_limiter: Arc<Semaphore>,
 }
 
 /// Configuration for audit runner
@@ -30,6 +46,12 @@ pub struct AuditRunnerConfig {
     pub cache_results: bool,
     /// Advisory database path
     pub advisory_db_path: Option<std::path::PathBuf>,
+    /// Minimum supported tool versions, keyed by tool name
+    pub min_tool_versions: HashMap<String, String>,
+    /// Shared cache holding the advisory database when no path is configured
+    pub shared_cache: Option<SharedCache>,
+    /// Age after which the shared advisory database is fetched again
+    pub advisory_db_max_age: Duration,
 }
 
 impl AuditRunner {
@@ -42,11 +64,35 @@ impl AuditRunner {
                 run_cargo_vet: config.audit_config.run_cargo_vet,
                 cache_results: config.audit_config.cache_results,
                 advisory_db_path: config.audit_config.advisory_db_path.clone(),
+                min_tool_versions: config.audit_config.min_tool_versions.clone(),
+                shared_cache: SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout),
+                advisory_db_max_age: Duration::from_secs(config.cache_config.advisory_db_max_age),
             },
             ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
+            tool_limiter: Arc::new(Semaphore::new(config.performance_config.max_parallel_tool_invocations.max(1))),
         }
     }
     
+    /// Stop running audit tools when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run audit tools through `tools` instead of spawning them directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
+    /// Take tool permits from `limiter`, shared with other components running tools
+    pub fn with_tool_limiter(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.tool_limiter = limiter;
+        self
+    }
+    
     /// Check if runner is ready
     pub fn is_ready(&self) -> bool {
         self.ready
@@ -57,66 +103,399 @@ impl AuditRunner {
         let mut report = AuditReport::new();
         report.offline_mode = project.requires_strict_security();
         
-        // Run cargo-audit if enabled
-        if self.config.run_cargo_audit {
-            if let Ok(audit_output) = self.run_cargo_audit(project).await {
-                report.raw_cargo_audit = Some(audit_output);
+        // Tie the report to the exact dependency state it describes
+        report.execution_metadata.lockfile_fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path())?;
+        
+        // Record tool versions, since output formats differ across versions
+        deadline::set_stage("capturing tool versions");
+        self.capture_tool_versions(project, &mut report).await;
+        
+        // Run enabled tools concurrently, bounded by the configured invocation limit
+        let offline = report.offline_mode;
+        let limiter = &self.tool_limiter;
+        
+        let cargo_audit = async {
+            if !self.config.run_cargo_audit {
+                return None;
             }
+            let _permit = limiter.acquire().await.ok()?;
+            Some(self.run_cargo_audit(project, offline, None).await)
+        };
+        
+        let cargo_vet = async {
+            if !self.config.run_cargo_vet {
+                return None;
+            }
+            let _permit = limiter.acquire().await.ok()?;
+            Some(self.run_cargo_vet(project).await)
+        };
+        
+        // A timed out tool leaves a partial report; cancellation aborts the audit
+        deadline::set_stage("running cargo-audit and cargo-vet");
+        let (audit_result, vet_result) = tokio::join!(cargo_audit, cargo_vet);
+        report.raw_cargo_audit = Self::tool_output("cargo-audit", audit_result, &mut report)?;
+        report.raw_cargo_vet = Self::tool_output("cargo-vet", vet_result, &mut report)?;
+        
+        // Parse findings from outputs, then merge advisories reported more than once
+        deadline::set_stage("parsing findings");
+        if let Some(audit_output) = report.raw_cargo_audit.clone() {
+            self.parse_audit_findings(&audit_output, &mut report);
         }
+        Self::merge_duplicate_findings(&mut report);
+        report.sort_canonical();
         
-        // Run cargo-vet if enabled
-        if self.config.run_cargo_vet {
-            if let Ok(vet_output) = self.run_cargo_vet(project).await {
-                report.raw_cargo_vet = Some(vet_output);
+        Ok(report)
+    }
+    
+    /// Resolve findings against the dependency graph
+    ///
+    /// Each finding is matched to the locked packages of its crate, and of
+    /// its version when known. The advisory's version ranges decide
+    /// whether it applies to those versions; findings that apply to none
+    /// are kept but marked not applicable. Applicable findings get
+    /// `affects_tcs` from the affected packages' classification, the
+    /// dependency paths leading to them and the smallest patched version
+    /// clearing all of them. Findings for packages not in Cargo.lock are
+    /// stale advisories; they are dropped and listed in a report warning.
+    pub fn map_findings_to_graph(&self, report: &mut AuditReport, graph: &DependencyGraph) {
+        let label = |id: &PackageId| graph.find_package_by_id(id)
+            .map(|package| format!("{}@{}", package.name, package.version))
+            .unwrap_or_else(|| id.to_string());
+        
+        let mut stale = Vec::new();
+        report.findings.retain_mut(|finding| {
+            let packages: Vec<&PackageNode> = graph.root_packages.iter()
+                .filter(|package| package.name == finding.package_name)
+                .filter(|package| finding.package_version.as_ref().is_none_or(|version| &package.version == version))
+                .collect();
+            if packages.is_empty() {
+                stale.push(format!("{} ({})", finding.id, finding.package_name));
+                return false;
             }
+            
+            let applicability: Vec<_> = packages.iter().map(|package| finding.applies_to(&package.version)).collect();
+            finding.applicability = if applicability.contains(&AdvisoryApplicability::Applicable) {
+                AdvisoryApplicability::Applicable
+            } else if applicability.iter().all(|a| *a == AdvisoryApplicability::NotApplicable) {
+                AdvisoryApplicability::NotApplicable
+            } else {
+                AdvisoryApplicability::Unknown
+            };
+            // Packages known to be unaffected do not contribute TCS impact or paths
+            let packages: Vec<&PackageNode> = packages.into_iter()
+                .zip(applicability)
+                .filter(|(_, a)| *a != AdvisoryApplicability::NotApplicable)
+                .map(|(package, _)| package)
+                .collect();
+            finding.minimal_safe_version = packages.iter()
+                .filter_map(|package| semver::Version::parse(&package.version).ok())
+                .max()
+                .and_then(|highest| finding.minimal_patched_version(&highest.to_string()))
+                .map(|version| version.to_string());
+            
+            finding.affects_tcs = packages.iter().any(|package| matches!(package.classification, Classification::TCS { .. }));
+            finding.dependency_paths = packages.iter()
+                .flat_map(|package| graph.dependency_paths(&package.id, MAX_FINDING_PATHS))
+                .take(MAX_FINDING_PATHS)
+                .map(|path| path.iter().map(label).collect())
+                .collect();
+            true
+        });
+        
+        if !stale.is_empty() {
+            tracing::info!(count = stale.len(), "Dropping findings for packages not in Cargo.lock");
+            report.execution_metadata.warnings.push(AnalysisWarning::new(
+                "stale_advisory".to_string(),
+                format!("Findings for packages not in Cargo.lock were dropped: {}", stale.join(", ")),
+                WarningSeverity::Info,
+            ));
+        }
+    }
+    
+    /// Keep a tool's output, recording a timeout as a report warning
+    fn tool_output(tool: &str, result: Option<Result<String>>, report: &mut AuditReport) -> Result<Option<String>> {
+        match result {
+            Some(Ok(output)) => Ok(Some(output)),
+            Some(Err(e @ AdapterError::Cancelled { .. })) => Err(e),
+            Some(Err(AdapterError::ToolTimeout { timeout, .. })) => {
+                tracing::warn!(tool, ?timeout, "Audit tool timed out, report is incomplete");
+                report.execution_metadata.warnings.push(AnalysisWarning::new(
+                    "tool_timeout".to_string(),
+                    format!("{} did not finish within {:?}; its results are missing from this report", tool, timeout),
+                    WarningSeverity::High,
+                ).with_component(tool.to_string()));
+                Ok(None)
+            },
+            Some(Err(_)) | None => Ok(None),
         }
+    }
+    
+    /// Build an audit report from pre-generated tool outputs
+    ///
+    /// No tools are executed. `cargo_audit_json` is the output of
+    /// `cargo audit --json`, and `cargo_vet_dir` is a cargo-vet store
+    /// (usually `supply-chain/`). Its `audits.toml` is required and kept in
+    /// `raw_cargo_vet_audits`; `imports.lock` and `config.toml` (with the
+    /// exemptions) are kept in `raw_cargo_vet_imports` and
+    /// `raw_cargo_vet_config` when present. `raw_cargo_vet` stays empty.
+    pub async fn ingest_reports(&self, cargo_audit_json: &Path, cargo_vet_dir: &Path) -> Result<AuditReport> {
+        let mut report = AuditReport::new();
+        report.offline_mode = true;
+        report.execution_metadata.offline_mode = true;
+        
+        let audit_path = cargo_audit_json.to_path_buf();
+        let audit_output = std::fs::read_to_string(&audit_path)
+            .map_err(|_| crate::AdapterError::file_not_found(&audit_path, "reading cargo-audit report"))?;
+        
+        serde_json::from_str::<serde_json::Value>(&audit_output)
+            .map_err(|e| crate::AdapterError::MetadataParseError {
+                field: "cargo_audit_json".to_string(),
+                value: audit_path.display().to_string(),
+                source: anyhow::anyhow!("Invalid cargo-audit JSON: {}", e),
+            })?;
+        
+        let audits_path = cargo_vet_dir.join("audits.toml");
+        if !audits_path.is_file() {
+            return Err(crate::AdapterError::file_not_found(&audits_path, "reading cargo-vet audits"));
+        }
+        
+        self.parse_audit_findings(&audit_output, &mut report);
+        Self::merge_duplicate_findings(&mut report);
+        report.sort_canonical();
+        report.raw_cargo_audit = Some(audit_output);
+        report.raw_cargo_vet_audits = Self::read_vet_store_file(cargo_vet_dir, "audits.toml")?;
+        report.raw_cargo_vet_imports = Self::read_vet_store_file(cargo_vet_dir, "imports.lock")?;
+        report.raw_cargo_vet_config = Self::read_vet_store_file(cargo_vet_dir, "config.toml")?;
         
-        // Parse findings from outputs
-        if let Some(ref audit_output) = report.raw_cargo_audit {
-            self.parse_audit_findings(audit_output, &mut report);
+        Ok(report)
+    }
+    
+    /// Read a TOML file of a cargo-vet store, `None` when it does not exist
+    fn read_vet_store_file(cargo_vet_dir: &Path, name: &str) -> Result<Option<String>> {
+        let path = cargo_vet_dir.join(name);
+        if !path.is_file() {
+            return Ok(None);
         }
         
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| crate::AdapterError::file_not_found(&path, "reading cargo-vet store"))?;
+        toml::from_str::<toml::Value>(&content)
+            .map_err(|e| crate::AdapterError::MetadataParseError {
+                field: "cargo_vet_dir".to_string(),
+                value: path.display().to_string(),
+                source: anyhow::anyhow!("Invalid cargo-vet {}: {}", name, e),
+            })?;
+        
+        Ok(Some(content))
+    }
+    
+    /// Audit a binary built with cargo-auditable
+    ///
+    /// `cargo audit bin` reports advisories for the dependency list
+    /// embedded in `binary`; the report holds its findings and raw output.
+    pub async fn audit_binary(&self, project: &Project, binary: &Path) -> Result<AuditReport> {
+        let mut report = AuditReport::new();
+        report.offline_mode = project.requires_strict_security();
+        
+        let raw_cargo_audit = self.run_cargo_audit(project, report.offline_mode, Some(binary)).await?;
+        self.parse_audit_findings(&raw_cargo_audit, &mut report);
+        Self::merge_duplicate_findings(&mut report);
+        report.sort_canonical();
+        report.raw_cargo_audit = Some(raw_cargo_audit);
+        
         Ok(report)
     }
     
-    /// Run cargo-audit
-    async fn run_cargo_audit(&self, project: &Project) -> Result<String> {
-        let output = Command::new("cargo")
-            .args(&["audit", "--json"])
+    /// Capture installed tool versions and check them against configured minimums
+    async fn capture_tool_versions(&self, project: &Project, report: &mut AuditReport) {
+        let mut tools = vec![("cargo", vec!["--version"])];
+        if self.config.run_cargo_audit {
+            tools.push(("cargo-audit", vec!["audit", "--version"]));
+        }
+        if self.config.run_cargo_vet {
+            tools.push(("cargo-vet", vec!["vet", "--version"]));
+        }
+        
+        for (tool, args) in tools {
+            let invocation = ToolInvocation::new(tool, "cargo")
+                .args(&args)
+                .current_dir(&project.paths.root)
+                .timeout(Some(Duration::from_secs(self.config.audit_timeout)));
+            let output = match self.tools.invoke(&invocation, &self.cancel).await {
+                Ok(output) if output.is_success() => output,
+                _ => continue,
+            };
+            
+            let version = match Self::extract_version(&output.stdout) {
+                Some(version) => version,
+                None => continue,
+            };
+            
+            if let Some(minimum) = self.config.min_tool_versions.get(tool) {
+                if Self::version_less_than(&version, minimum) {
+                    tracing::warn!(tool, installed = %version, minimum = %minimum, "Tool version below configured minimum");
+                    report.execution_metadata.warnings.push(AnalysisWarning::new(
+                        "tool_version_below_minimum".to_string(),
+                        format!("{} {} is older than the minimum supported version {}", tool, version, minimum),
+                        WarningSeverity::Medium,
+                    ).with_component(tool.to_string()));
+                }
+            }
+            
+            report.execution_metadata.tool_versions.insert(tool.to_string(), version);
+        }
+    }
+    
+    /// Extract the first dotted version number from `--version` output
+    pub(crate) fn extract_version(output: &str) -> Option<String> {
+        output.split_whitespace()
+            .map(|token| token.trim_start_matches('v'))
+            .find(|token| token.split('.').count() >= 2 && token.split('.').all(|part| {
+                part.chars().take_while(|c| c.is_ascii_digit()).count() > 0
+            }))
+            .map(|token| token.to_string())
+    }
+    
+    /// Compare dotted versions numerically, ignoring pre-release suffixes
+    pub(crate) fn version_less_than(installed: &str, minimum: &str) -> bool {
+        let parse = |version: &str| -> Vec<u64> {
+            version.split('.')
+                .map(|part| part.chars().take_while(|c| c.is_ascii_digit()).collect::<String>())
+                .map(|digits| digits.parse().unwrap_or(0))
+                .collect()
+        };
+        
+        let (mut installed, mut minimum) = (parse(installed), parse(minimum));
+        let len = installed.len().max(minimum.len());
+        installed.resize(len, 0);
+        minimum.resize(len, 0);
+        
+        installed < minimum
+    }
+    
+    /// Run cargo-audit, or `cargo audit bin` on `binary`
+    ///
+    /// With a shared cache, the advisory database is synced into it first
+    /// and read under a shared lock, so concurrent runs never audit against
+    /// a database another run is replacing.
+    async fn run_cargo_audit(&self, project: &Project, offline: bool, binary: Option<&Path>) -> Result<String> {
+        let args: &[&str] = match binary {
+            Some(_) => &["audit", "bin", "--json"],
+            None => &["audit", "--json"],
+        };
+        let mut invocation = ToolInvocation::new("cargo-audit", "cargo")
+            .args(args)
             .current_dir(&project.paths.root)
-            .output()
-            .map_err(|_| crate::AdapterError::tool_not_found("cargo-audit"))?;
+            .timeout(Some(Duration::from_secs(self.config.audit_timeout)));
         
-        if !output.status.success() {
+        let mut _db_lock = None;
+        match (&self.config.advisory_db_path, &self.config.shared_cache) {
+            (Some(path), _) => invocation = invocation.arg("--db").arg(path),
+            (None, Some(cache)) => {
+                if let Some((path, lock)) = self.shared_advisory_db(cache, offline).await? {
+                    invocation = invocation.arg("--db").arg(&path).arg("--no-fetch");
+                    _db_lock = Some(lock);
+                }
+            },
+            (None, None) => {},
+        }
+        if let Some(binary) = binary {
+            invocation = invocation.arg(binary);
+        }
+        
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if !output.is_success() {
             return Err(crate::AdapterError::ToolExecutionFailed {
                 tool: "cargo-audit".to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
                 source: anyhow::anyhow!("cargo-audit execution failed"),
             });
         }
         
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(output.stdout)
+    }
+    
+    /// Sync the shared advisory database if stale and lock it for reading
+    ///
+    /// The database is fetched into a staging directory and swapped in
+    /// under the exclusive lock. A failed fetch keeps the previous copy.
+    /// Returns `None` if no copy is available.
+    async fn shared_advisory_db(&self, cache: &SharedCache, offline: bool) -> Result<Option<(std::path::PathBuf, CacheLock)>> {
+        let max_age = self.config.advisory_db_max_age;
+        let fresh = cache.is_fresh(ADVISORY_DB_ENTRY, max_age);
+        telemetry::record_cache_access("advisory-db", fresh);
+        if !offline && !fresh {
+            let _lock = Self::lock_entry(cache, true).await?;
+            
+            // Another run may have synced while this one waited for the lock
+            if !cache.is_fresh(ADVISORY_DB_ENTRY, max_age) {
+                let staging = cache.create_staging(ADVISORY_DB_ENTRY)?;
+                let invocation = ToolInvocation::new("cargo-audit", "cargo")
+                    .args(["audit", "fetch", "--db"])
+                    .arg(&staging)
+                    .timeout(Some(Duration::from_secs(self.config.audit_timeout)));
+                
+                match self.tools.invoke(&invocation, &self.cancel).await {
+                    Ok(output) if output.is_success() => cache.commit_staging(ADVISORY_DB_ENTRY, &staging)?,
+                    Err(e @ AdapterError::Cancelled { .. }) => {
+                        let _ = std::fs::remove_dir_all(&staging);
+                        return Err(e);
+                    },
+                    _ => {
+                        tracing::warn!("Failed to fetch the advisory database, using the cached copy");
+                        let _ = std::fs::remove_dir_all(&staging);
+                    },
+                }
+            }
+        }
+        
+        let lock = Self::lock_entry(cache, false).await?;
+        let path = cache.entry_path(ADVISORY_DB_ENTRY);
+        Ok(path.is_dir().then_some((path, lock)))
+    }
+    
+    /// Lock the advisory database entry without blocking the runtime
+    async fn lock_entry(cache: &SharedCache, exclusive: bool) -> Result<CacheLock> {
+        let cache = cache.clone();
+        tokio::task::spawn_blocking(move || match exclusive {
+            true => cache.lock_exclusive(ADVISORY_DB_ENTRY),
+            false => cache.lock_shared(ADVISORY_DB_ENTRY),
+        })
+        .await
+        .map_err(|e| AdapterError::Internal {
+            message: "Cache lock task failed".to_string(),
+            source: anyhow::anyhow!("{}", e),
+        })?
     }
     
     /// Run cargo-vet
     async fn run_cargo_vet(&self, project: &Project) -> Result<String> {
-        let output = Command::new("cargo")
-            .args(&["vet", "dump"])
+        let invocation = ToolInvocation::new("cargo-vet", "cargo")
+            .args(["vet", "dump"])
             .current_dir(&project.paths.root)
-            .output()
-            .map_err(|_| crate::AdapterError::tool_not_found("cargo-vet"))?;
+            .timeout(Some(Duration::from_secs(self.config.audit_timeout)));
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
         
-        if !output.status.success() {
+        if !output.is_success() {
             return Err(crate::AdapterError::ToolExecutionFailed {
                 tool: "cargo-vet".to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
                 source: anyhow::anyhow!("cargo-vet execution failed"),
             });
         }
         
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(output.stdout)
+    }
+    
+    /// Merge findings reporting the same advisory for the same package
+    fn merge_duplicate_findings(report: &mut AuditReport) {
+        let merged = report.deduplicate_findings();
+        if merged > 0 {
+            tracing::debug!(merged, remaining = report.findings.len(), "Merged duplicate advisory findings");
+        }
     }
     
     /// Parse audit findings from cargo-audit output
@@ -148,13 +527,46 @@ impl AuditRunner {
         
         let description = vuln.get("description")?.as_str().unwrap_or("").to_string();
         
-        Some(AuditFinding::new(
+        let affected_versions = vuln.get("affected_versions")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let mut finding = AuditFinding::new(
             id.to_string(),
             package_name.to_string(),
-            "unknown".to_string(), // Version info would need more parsing
+            affected_versions.to_string(),
             severity,
             description,
-        ).with_source("cargo-audit".to_string()))
+        ).with_source("cargo-audit".to_string());
+        
+        let strings_in = |value: &serde_json::Value, key: &str| value.get(key)
+            .and_then(|v| v.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let strings = |key: &str| strings_in(vuln, key);
+        // cargo-audit nests patched and unaffected ranges under `versions`
+        let patched = vuln.get("versions")
+            .map(|versions| strings_in(versions, "patched"))
+            .unwrap_or_else(|| strings("patched_versions"));
+        for version in patched {
+            finding = finding.add_patched_version(version);
+        }
+        let unaffected = vuln.get("versions")
+            .map(|versions| strings_in(versions, "unaffected"))
+            .unwrap_or_else(|| strings("unaffected_versions"));
+        for version in unaffected {
+            finding = finding.add_unaffected_version(version);
+        }
+        for alias in strings("aliases") {
+            finding = finding.add_alias(alias);
+        }
+        for reference in strings("references") {
+            finding = finding.add_reference(reference);
+        }
+        
+        Some(match vuln.get("version").and_then(|v| v.as_str()) {
+            Some(version) => finding.with_package_version(version.to_string()),
+            None => finding,
+        })
     }
 }
 
@@ -166,6 +578,9 @@ impl Default for AuditRunnerConfig {
             run_cargo_vet: true,
             cache_results: true,
             advisory_db_path: None,
+            min_tool_versions: HashMap::new(),
+            shared_cache: None,
+            advisory_db_max_age: Duration::from_secs(3600),
         }
     }
 }
@@ -174,6 +589,7 @@ impl Default for AuditRunnerConfig {
 mod tests {
     use super::*;
     use crate::config::RustAdapterConfig;
+    use crate::utils::{ReplayInvoker, ToolOutput};
     
     #[test]
     fn test_audit_runner_creation() {
@@ -193,4 +609,248 @@ mod tests {
         assert_eq!(runner.config.audit_timeout, 300);
         assert!(runner.config.cache_results);
     }
+    
+    #[test]
+    fn test_tool_version_parsing() {
+        assert_eq!(
+            AuditRunner::extract_version("cargo 1.75.0 (1d8b05cdd 2023-11-20)"),
+            Some("1.75.0".to_string())
+        );
+        assert_eq!(
+            AuditRunner::extract_version("cargo-audit-audit 0.18.3"),
+            Some("0.18.3".to_string())
+        );
+        assert_eq!(AuditRunner::extract_version("no version here"), None);
+        
+        assert!(AuditRunner::version_less_than("0.16.1", "0.17.0"));
+        assert!(AuditRunner::version_less_than("0.9", "0.10.0"));
+        assert!(!AuditRunner::version_less_than("0.17.0", "0.17"));
+        assert!(!AuditRunner::version_less_than("1.0.0-rc.1", "0.18.0"));
+    }
+    
+    #[tokio::test]
+    async fn test_ingest_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit_json = dir.path().join("audit.json");
+        let vet_dir = dir.path().join("supply-chain");
+        std::fs::create_dir_all(&vet_dir).unwrap();
+        std::fs::write(&audit_json, r#"{"vulnerabilities": [
+            {"id": "RUSTSEC-2023-0001", "package": "openssl", "severity": "high", "description": "Bad"}
+        ]}"#).unwrap();
+        std::fs::write(vet_dir.join("audits.toml"), "[audits]\n").unwrap();
+        
+        let runner = AuditRunner::new(&RustAdapterConfig::default());
+        let report = runner.ingest_reports(&audit_json, &vet_dir).await.unwrap();
+        
+        assert!(report.offline_mode);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].package_name, "openssl");
+        assert_eq!(report.raw_cargo_vet_audits.as_deref(), Some("[audits]\n"));
+        assert!(report.raw_cargo_vet.is_none());
+        assert!(report.raw_cargo_vet_imports.is_none());
+        assert!(report.execution_metadata.tool_versions.is_empty());
+        
+        // Imports and exemptions are ingested alongside the audits
+        let config = "[[exemptions.openssl]]\nversion = \"0.10.55\"\ncriteria = \"safe-to-deploy\"\n";
+        std::fs::write(vet_dir.join("imports.lock"), "[audits]\n").unwrap();
+        std::fs::write(vet_dir.join("config.toml"), config).unwrap();
+        let report = runner.ingest_reports(&audit_json, &vet_dir).await.unwrap();
+        assert_eq!(report.raw_cargo_vet_imports.as_deref(), Some("[audits]\n"));
+        assert_eq!(report.raw_cargo_vet_config.as_deref(), Some(config));
+        
+        std::fs::write(vet_dir.join("config.toml"), "[exemptions").unwrap();
+        assert!(runner.ingest_reports(&audit_json, &vet_dir).await.is_err());
+        
+        // A vet store without audits.toml is rejected
+        let empty_dir = dir.path().join("empty");
+        std::fs::create_dir_all(&empty_dir).unwrap();
+        assert!(runner.ingest_reports(&audit_json, &empty_dir).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_audit_with_replayed_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = Project::new("replay".to_string(), "Replay".to_string(), "rust".to_string(), dir.path().to_path_buf());
+        let lockfile = "version = 3\n\n[[package]]\nname = \"openssl\"\nversion = \"0.10.55\"\n";
+        let mut config = RustAdapterConfig::default();
+        config.audit_config.advisory_db_path = Some("/advisory-db".into());
+        config.audit_config.min_tool_versions.insert("cargo-audit".to_string(), "0.20.0".to_string());
+        
+        let tools = ReplayInvoker::new()
+            .with_output("cargo --version", ToolOutput::success("cargo 1.75.0 (1d8b05cdd 2023-11-20)"))
+            .with_output("cargo audit --version", ToolOutput::success("cargo-audit-audit 0.18.3"))
+            .with_output("cargo audit --json --db /advisory-db", ToolOutput::success(r#"{"vulnerabilities": [
+                {"id": "RUSTSEC-2023-0001", "package": "openssl", "severity": "high", "description": "Bad"}
+            ]}"#))
+            .with_output("cargo vet dump", ToolOutput::failure(1, "no supply-chain directory"));
+        let runner = AuditRunner::new(&config).with_tool_invoker(Arc::new(tools));
+        
+        // The report cannot be tied to a dependency state without Cargo.lock
+        assert!(runner.run_comprehensive_audit(&project).await.is_err());
+        std::fs::write(project.lockfile_path(), lockfile).unwrap();
+        let report = runner.run_comprehensive_audit(&project).await.unwrap();
+        
+        assert_eq!(report.execution_metadata.lockfile_fingerprint, ChecksumCalculator::lockfile_fingerprint(lockfile).unwrap());
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].package_name, "openssl");
+        assert_eq!(report.execution_metadata.tool_versions.get("cargo-audit").map(String::as_str), Some("0.18.3"));
+        // cargo-vet --version was not recorded, so it counts as not installed
+        assert!(!report.execution_metadata.tool_versions.contains_key("cargo-vet"));
+        assert!(report.execution_metadata.warnings.iter().any(|w| w.warning_type == "tool_version_below_minimum"));
+        assert!(report.raw_cargo_vet.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_binary_audit() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = Project::new("replay".to_string(), "Replay".to_string(), "rust".to_string(), dir.path().to_path_buf());
+        let mut config = RustAdapterConfig::default();
+        config.audit_config.advisory_db_path = Some("/advisory-db".into());
+        
+        let tools = ReplayInvoker::new()
+            .with_output("cargo audit bin --json --db /advisory-db /bin/app", ToolOutput::success(r#"{"vulnerabilities": [
+                {"id": "RUSTSEC-2023-0044", "package": "openssl", "severity": "high", "description": "Bad"}
+            ]}"#));
+        let runner = AuditRunner::new(&config).with_tool_invoker(Arc::new(tools));
+        
+        let report = runner.audit_binary(&project, Path::new("/bin/app")).await.unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].id, "RUSTSEC-2023-0044");
+        assert!(report.raw_cargo_audit.is_some());
+        assert!(runner.audit_binary(&project, Path::new("/bin/other")).await.is_err());
+    }
+    
+    #[test]
+    fn test_duplicate_advisories_are_merged() {
+        let finding = |id: &str, severity: Severity, source: &str| {
+            AuditFinding::new(id.to_string(), "ring".to_string(), "<0.17.12".to_string(), severity, String::new())
+                .with_source(source.to_string())
+        };
+        let mut report = AuditReport::new();
+        report.add_finding(finding("RUSTSEC-2025-0009", Severity::Medium, "cargo-audit")
+            .add_alias("GHSA-4p46-pwfr-66x6".to_string())
+            .add_reference("https://rustsec.org/advisories/RUSTSEC-2025-0009".to_string()));
+        report.add_finding(finding("CVE-2025-4432", Severity::High, "osv")
+            .add_reference("https://nvd.nist.gov/vuln/detail/CVE-2025-4432".to_string()));
+        // Links the CVE to the RustSec advisory through its aliases
+        report.add_finding(finding("GHSA-4p46-pwfr-66x6", Severity::Low, "imported")
+            .add_alias("CVE-2025-4432".to_string()));
+        report.add_finding(finding("RUSTSEC-2025-0010", Severity::Low, "cargo-audit"));
+        
+        assert_eq!(report.deduplicate_findings(), 2);
+        assert_eq!(report.findings.len(), 2);
+        
+        let merged = &report.findings[0];
+        assert_eq!(merged.id, "RUSTSEC-2025-0009");
+        assert_eq!(merged.severity, Severity::High);
+        assert_eq!(merged.reported_by, vec!["cargo-audit".to_string(), "imported".to_string(), "osv".to_string()]);
+        assert_eq!(merged.references.len(), 2);
+        assert!(merged.aliases.contains(&"CVE-2025-4432".to_string()));
+        assert!(!merged.aliases.contains(&merged.id));
+    }
+    
+    #[test]
+    fn test_map_findings_to_graph() {
+        let node = |name: &str, classification: Classification| PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let app = node("app", Classification::Unknown);
+        let ring = node("ring", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        graph.add_edge(DependencyEdge { from: app.id, to: ring.id, kind: DependencyKind::Normal, target: None, optional: false, features: vec![] });
+        graph.add_package(app);
+        graph.add_package(ring);
+        
+        let mut report = AuditReport::new();
+        report.add_finding(AuditFinding::new("RUSTSEC-2025-0001".to_string(), "ring".to_string(), "<1.0.1".to_string(), Severity::High, String::new()));
+        report.add_finding(AuditFinding::new("RUSTSEC-2025-0002".to_string(), "ring".to_string(), "<0.9".to_string(), Severity::High, String::new())
+            .with_package_version("0.8.0".to_string()));
+        report.add_finding(AuditFinding::new("RUSTSEC-2020-0001".to_string(), "openssl".to_string(), "*".to_string(), Severity::Low, String::new()));
+        
+        AuditRunner::new(&RustAdapterConfig::default()).map_findings_to_graph(&mut report, &graph);
+        
+        assert_eq!(report.findings.len(), 1);
+        assert!(report.findings[0].affects_tcs);
+        assert_eq!(report.findings[0].dependency_paths, vec![vec!["app@1.0.0".to_string(), "ring@1.0.0".to_string()]]);
+        assert_eq!(report.execution_metadata.warnings.len(), 1);
+        assert!(report.execution_metadata.warnings[0].message.contains("openssl"));
+    }
+    
+    #[test]
+    fn test_findings_match_locked_versions() {
+        let node = |version: &str| PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "ring".to_string(),
+            version: version.to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification: Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() },
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(node("0.16.5"));
+        graph.add_package(node("0.17.3"));
+        
+        let finding = |id: &str, affected: &str| AuditFinding::new(id.to_string(), "ring".to_string(), affected.to_string(), Severity::High, String::new())
+            .add_patched_version("^0.16.20".to_string())
+            .add_patched_version(">=0.17.12".to_string());
+        let mut report = AuditReport::new();
+        report.add_finding(finding("RUSTSEC-2025-0001", "<0.17.12"));
+        report.add_finding(finding("RUSTSEC-2025-0002", ">=0.18.0 || <0.15"));
+        report.add_finding(AuditFinding::new("RUSTSEC-2025-0003".to_string(), "ring".to_string(), "unknown".to_string(), Severity::Low, String::new()));
+        
+        AuditRunner::new(&RustAdapterConfig::default()).map_findings_to_graph(&mut report, &graph);
+        
+        // Both locked versions are affected; 0.17.12 clears them after upgrading to it
+        assert_eq!(report.findings[0].applicability, AdvisoryApplicability::Applicable);
+        assert_eq!(report.findings[0].minimal_safe_version.as_deref(), Some("0.17.12"));
+        assert_eq!(report.findings[0].dependency_paths.len(), 2);
+        assert_eq!(report.findings[0].minimal_patched_version("0.16.5").map(|v| v.to_string()).as_deref(), Some("0.16.20"));
+        
+        // Neither locked version is in range
+        assert_eq!(report.findings[1].applicability, AdvisoryApplicability::NotApplicable);
+        assert!(!report.findings[1].affects_tcs);
+        assert!(report.findings[1].minimal_safe_version.is_none());
+        
+        // An unparseable range cannot rule the locked versions out
+        assert_eq!(report.findings[2].applicability, AdvisoryApplicability::Applicable);
+        assert!(report.findings[2].is_applicable());
+    }
+    
+    #[test]
+    fn test_applicability_of_pre_releases() {
+        let finding = AuditFinding::new("RUSTSEC-2025-0004".to_string(), "ring".to_string(), "<1.0.0".to_string(), Severity::High, String::new())
+            .add_patched_version(">=1.0.0".to_string());
+        
+        assert_eq!(finding.applies_to("1.0.0-beta.3"), AdvisoryApplicability::Applicable);
+        assert_eq!(finding.applies_to("0.9.0-rc.1"), AdvisoryApplicability::Applicable);
+        assert_eq!(finding.applies_to("1.0.0"), AdvisoryApplicability::NotApplicable);
+        assert_eq!(finding.applies_to("1.1.0-alpha.1"), AdvisoryApplicability::NotApplicable);
+        assert_eq!(finding.applies_to("not-a-version"), AdvisoryApplicability::Applicable);
+    }
+    
+    #[test]
+    fn test_unaffected_versions_are_not_applicable() {
+        let runner = AuditRunner::new(&RustAdapterConfig::default());
+        let mut report = AuditReport::new();
+        runner.parse_audit_findings(r#"{"vulnerabilities": [
+            {"id": "RUSTSEC-2025-0005", "package": "ring", "severity": "high", "description": "Bad",
+             "versions": {"patched": [">=0.17.12"], "unaffected": ["<0.16.0"]}}
+        ]}"#, &mut report);
+        
+        assert_eq!(report.findings.len(), 1);
+        let finding = &report.findings[0];
+        assert_eq!(finding.unaffected_versions, vec!["<0.16.0".to_string()]);
+        assert_eq!(finding.applies_to("0.15.2"), AdvisoryApplicability::NotApplicable);
+        assert_eq!(finding.applies_to("0.16.5"), AdvisoryApplicability::Applicable);
+        assert_eq!(finding.applies_to("0.17.12"), AdvisoryApplicability::NotApplicable);
+    }
 }
@@ -0,0 +1,412 @@
+//! Periodic re-auditing for long-running modes
+//!
+//! An [`AuditScheduler`] keeps the audit results of a set of projects
+//! current between CI runs. Each project is audited again once its own
+//! interval has elapsed, and every project is audited again as soon as the
+//! advisory database changes. Results are handed to an [`AuditSink`], such
+//! as a [`ReportDirectorySink`] keeping the latest report of each project.
+//! `rust-adapter serve` runs a scheduler until interrupted.
+//!
+//! The scheduler does not fetch the advisory database itself: audits
+//! refresh it once it is older than `cache_config.advisory_db_max_age`, and
+//! a database updated by another process is picked up on the next tick.
+
+use crate::adapter::audit_runner::ADVISORY_DB_ENTRY;
+use crate::adapter::rust_adapter::EcosystemAdapter;
+use crate::error::{AdapterError, Result};
+use crate::models::{AuditReport, Project};
+use crate::utils::{write_atomic, CancellationToken, SharedCache};
+use crate::RustAdapter;
+use async_trait::async_trait;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// File of a [`ReportDirectorySink`] every scheduled audit is logged to
+pub const SCHEDULED_AUDIT_LOG: &str = "scheduled-audits.jsonl";
+
+/// Reason a scheduled audit ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditTrigger {
+    /// The project interval elapsed, or the project was never audited
+    Interval,
+    /// The advisory database changed since the last tick
+    AdvisoryDbUpdated,
+}
+
+/// Result of one scheduled audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledAuditRun {
+    /// Audited project
+    pub project_id: String,
+    /// Reason the audit ran
+    pub trigger: AuditTrigger,
+    /// Time the audit completed (RFC 3339)
+    pub completed_at: String,
+    /// Audit report, or the error the audit failed with
+    pub result: std::result::Result<AuditReport, String>,
+}
+
+/// Receiver of scheduled audit results
+pub trait AuditSink: Send + Sync {
+    /// Handle the result of one scheduled audit
+    fn deliver(&self, run: &ScheduledAuditRun);
+}
+
+/// Auditor of scheduled projects
+#[async_trait]
+pub trait ProjectAuditor: Send + Sync {
+    /// Audit `project`
+    async fn audit(&self, project: &Project) -> Result<AuditReport>;
+}
+
+#[async_trait]
+impl ProjectAuditor for RustAdapter {
+    async fn audit(&self, project: &Project) -> Result<AuditReport> {
+        self.run_audit(project).await
+    }
+}
+
+/// Source of the current time for interval checks
+pub trait Clock: Send + Sync {
+    /// Current instant
+    fn now(&self) -> Instant;
+}
+
+/// Clock reading the system's monotonic time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Sink keeping the latest audit report of each project in a directory
+///
+/// A successful audit replaces `<project id>.audit.json`; every run, failed
+/// or not, is appended as a JSON line to [`SCHEDULED_AUDIT_LOG`]. Write
+/// failures are logged rather than stopping the scheduler.
+#[derive(Debug)]
+pub struct ReportDirectorySink {
+    dir: PathBuf,
+    /// Serializes appends to the log
+    log: Mutex<()>,
+}
+
+impl ReportDirectorySink {
+    /// Sink writing into `dir`, created if missing
+    pub fn new(dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|_| AdapterError::permission_denied(&dir, "creating scheduled audit directory"))?;
+        Ok(Self { dir, log: Mutex::new(()) })
+    }
+    
+    /// Path of the latest report of a project
+    pub fn report_path(&self, project_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.audit.json", project_id))
+    }
+    
+    fn write(&self, run: &ScheduledAuditRun) -> std::result::Result<(), String> {
+        if let Ok(report) = &run.result {
+            let content = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+            write_atomic(self.report_path(&run.project_id), content).map_err(|e| e.to_string())?;
+        }
+        
+        let line = serde_json::json!({
+            "project_id": run.project_id,
+            "trigger": run.trigger,
+            "completed_at": run.completed_at,
+            "findings": run.result.as_ref().ok().map(|report| report.findings.len()),
+            "error": run.result.as_ref().err(),
+        });
+        let _guard = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let mut log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(SCHEDULED_AUDIT_LOG))
+            .map_err(|e| e.to_string())?;
+        writeln!(log, "{}", line).map_err(|e| e.to_string())
+    }
+}
+
+impl AuditSink for ReportDirectorySink {
+    fn deliver(&self, run: &ScheduledAuditRun) {
+        if let Err(e) = self.write(run) {
+            tracing::warn!(project = %run.project_id, dir = %self.dir.display(), error = %e, "Failed to record scheduled audit");
+        }
+    }
+}
+
+/// Project audited by the scheduler
+#[derive(Debug)]
+struct ScheduledProject {
+    project: Project,
+    interval: Duration,
+    /// Start of the last audit
+    last_run: Mutex<Option<Instant>>,
+}
+
+/// Scheduler re-running project audits
+pub struct AuditScheduler {
+    auditor: Arc<dyn ProjectAuditor>,
+    sink: Arc<dyn AuditSink>,
+    clock: Arc<dyn Clock>,
+    /// Advisory database watched for updates
+    advisory_db: Option<PathBuf>,
+    /// Revision of the advisory database seen on the last tick
+    advisory_revision: Mutex<Option<SystemTime>>,
+    projects: Vec<ScheduledProject>,
+}
+
+impl AuditScheduler {
+    /// Create a scheduler auditing with `auditor` and delivering to `sink`
+    ///
+    /// No advisory database is watched until [`Self::with_advisory_db`].
+    pub fn new(auditor: Arc<dyn ProjectAuditor>, sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            auditor,
+            sink,
+            clock: Arc::new(SystemClock),
+            advisory_db: None,
+            advisory_revision: Mutex::new(None),
+            projects: Vec::new(),
+        }
+    }
+    
+    /// Create a scheduler auditing with `adapter` and delivering to `sink`
+    ///
+    /// Watches the configured `audit_config.advisory_db_path`, or the
+    /// shared cache copy of the advisory database when none is configured.
+    pub fn for_adapter(adapter: Arc<RustAdapter>, sink: Arc<dyn AuditSink>) -> Self {
+        let config = adapter.config();
+        let advisory_db = config.audit_config.advisory_db_path.clone().or_else(|| {
+            SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout)
+                .map(|cache| cache.entry_path(ADVISORY_DB_ENTRY))
+        });
+        
+        let scheduler = Self::new(adapter, sink);
+        match advisory_db {
+            Some(path) => scheduler.with_advisory_db(path),
+            None => scheduler,
+        }
+    }
+    
+    /// Read the time for interval checks from `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+    
+    /// Audit `project` every `interval`
+    pub fn add_project(mut self, project: Project, interval: Duration) -> Self {
+        self.projects.push(ScheduledProject {
+            project,
+            interval,
+            last_run: Mutex::new(None),
+        });
+        self
+    }
+    
+    /// Watch the advisory database at `path`
+    pub fn with_advisory_db(mut self, path: PathBuf) -> Self {
+        self.advisory_revision = Mutex::new(Self::advisory_revision(&path));
+        self.advisory_db = Some(path);
+        self
+    }
+    
+    /// Audit every project that is due and deliver the results
+    ///
+    /// Projects are audited one after another. A failed audit is delivered
+    /// like any other result; cancellation aborts the tick. Returns the
+    /// number of audits run.
+    pub async fn tick(&self) -> Result<usize> {
+        let db_updated = self.advisory_db_updated();
+        let mut runs = 0;
+        
+        for scheduled in &self.projects {
+            let started = self.clock.now();
+            let trigger = {
+                let mut last_run = scheduled.last_run.lock().unwrap_or_else(|e| e.into_inner());
+                let trigger = match *last_run {
+                    _ if db_updated => AuditTrigger::AdvisoryDbUpdated,
+                    Some(last) if started.duration_since(last) < scheduled.interval => continue,
+                    _ => AuditTrigger::Interval,
+                };
+                *last_run = Some(started);
+                trigger
+            };
+            
+            let result = self.auditor.audit(&scheduled.project).await;
+            if let Err(e @ AdapterError::Cancelled { .. }) = result {
+                return Err(e);
+            }
+            if let Err(e) = &result {
+                tracing::warn!(project = %scheduled.project.id, error = %e, "Scheduled audit failed");
+            }
+            
+            self.sink.deliver(&ScheduledAuditRun {
+                project_id: scheduled.project.id.clone(),
+                trigger,
+                completed_at: chrono::Utc::now().to_rfc3339(),
+                result: result.map_err(|e| e.to_string()),
+            });
+            runs += 1;
+        }
+        
+        Ok(runs)
+    }
+    
+    /// Tick every `poll` until `cancel` is cancelled
+    pub async fn run(&self, poll: Duration, cancel: &CancellationToken) {
+        loop {
+            match self.tick().await {
+                Ok(_) => {},
+                Err(AdapterError::Cancelled { .. }) => return,
+                Err(e) => tracing::warn!(error = %e, "Scheduled audit tick failed"),
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(poll) => {},
+                _ = cancel.cancelled() => return,
+            }
+        }
+    }
+    
+    /// Whether the advisory database changed since the last check
+    fn advisory_db_updated(&self) -> bool {
+        let Some(path) = &self.advisory_db else {
+            return false;
+        };
+        let revision = Self::advisory_revision(path);
+        let mut seen = self.advisory_revision.lock().unwrap_or_else(|e| e.into_inner());
+        let updated = revision.is_some() && *seen != revision;
+        *seen = revision;
+        updated
+    }
+    
+    /// Revision of the advisory database
+    ///
+    /// Latest modification time of the database directory and, for a git
+    /// checkout, of its fetched head. The shared cache replaces the whole
+    /// directory on refresh; cargo-audit fetches into an existing checkout.
+    fn advisory_revision(path: &Path) -> Option<SystemTime> {
+        [path.to_path_buf(), path.join(".git/FETCH_HEAD")]
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[derive(Default)]
+    struct CollectingSink(Mutex<Vec<(String, AuditTrigger)>>);
+    
+    impl AuditSink for CollectingSink {
+        fn deliver(&self, run: &ScheduledAuditRun) {
+            self.0.lock().unwrap().push((run.project_id.clone(), run.trigger));
+        }
+    }
+    
+    /// Auditor failing for projects named "broken"
+    struct FakeAuditor;
+    
+    #[async_trait]
+    impl ProjectAuditor for FakeAuditor {
+        async fn audit(&self, project: &Project) -> Result<AuditReport> {
+            match project.id.as_str() {
+                "broken" => Err(AdapterError::Internal {
+                    message: "audit failed".to_string(),
+                    source: anyhow::anyhow!("fake"),
+                }),
+                _ => Ok(AuditReport::default()),
+            }
+        }
+    }
+    
+    /// Clock advanced by hand
+    struct ManualClock(Mutex<Instant>);
+    
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+    
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+    
+    /// Set the revision the scheduler reads from the advisory database
+    fn set_revision(path: &Path, seconds: u64) {
+        std::fs::File::open(path).unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_interval_and_advisory_db_triggers() {
+        let temp_dir = TempDir::new().unwrap();
+        let advisory_db = temp_dir.path().join("advisory-db");
+        std::fs::create_dir(&advisory_db).unwrap();
+        set_revision(&advisory_db, 1_000);
+        
+        let project = |id: &str| Project::new(id.to_string(), id.to_string(), "rust".to_string(), temp_dir.path().join(id));
+        let sink = Arc::new(CollectingSink::default());
+        let clock = Arc::new(ManualClock(Mutex::new(Instant::now())));
+        let scheduler = AuditScheduler::new(Arc::new(FakeAuditor), sink.clone())
+            .with_clock(clock.clone())
+            .with_advisory_db(advisory_db.clone())
+            .add_project(project("hourly"), Duration::from_secs(3600))
+            .add_project(project("broken"), Duration::from_secs(60));
+        
+        // Never audited projects are due; failed audits are delivered too
+        assert_eq!(scheduler.tick().await.unwrap(), 2);
+        assert_eq!(scheduler.tick().await.unwrap(), 0);
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(scheduler.tick().await.unwrap(), 1);
+        
+        set_revision(&advisory_db, 2_000);
+        assert_eq!(scheduler.tick().await.unwrap(), 2);
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(scheduler.tick().await.unwrap(), 2);
+        
+        assert_eq!(*sink.0.lock().unwrap(), vec![
+            ("hourly".to_string(), AuditTrigger::Interval),
+            ("broken".to_string(), AuditTrigger::Interval),
+            ("broken".to_string(), AuditTrigger::Interval),
+            ("hourly".to_string(), AuditTrigger::AdvisoryDbUpdated),
+            ("broken".to_string(), AuditTrigger::AdvisoryDbUpdated),
+            ("hourly".to_string(), AuditTrigger::Interval),
+            ("broken".to_string(), AuditTrigger::Interval),
+        ]);
+    }
+    
+    #[tokio::test]
+    async fn test_report_directory_sink_keeps_latest_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = Arc::new(ReportDirectorySink::new(temp_dir.path().join("audits")).unwrap());
+        let project = |id: &str| Project::new(id.to_string(), id.to_string(), "rust".to_string(), temp_dir.path().join(id));
+        let scheduler = AuditScheduler::new(Arc::new(FakeAuditor), sink.clone())
+            .add_project(project("app"), Duration::ZERO)
+            .add_project(project("broken"), Duration::ZERO);
+        assert_eq!(scheduler.tick().await.unwrap(), 2);
+        
+        assert!(sink.report_path("app").is_file());
+        assert!(!sink.report_path("broken").exists());
+        let log = std::fs::read_to_string(temp_dir.path().join("audits").join(SCHEDULED_AUDIT_LOG)).unwrap();
+        let lines: Vec<serde_json::Value> = log.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["findings"], 0);
+        assert_eq!(lines[1]["trigger"], "interval");
+        assert!(lines[1]["error"].as_str().unwrap().contains("audit failed"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Status badges from the latest cached reports
+//!
+//! Every audit, drift detection and SBOM generation run records a
+//! severity summary under the project's report directory. Badges are
+//! rendered from those summaries, so producing one never re-runs an
+//! analysis.
+
+use crate::models::*;
+use crate::error::{AdapterError, Result};
+use std::path::PathBuf;
+
+/// Report summary directory, relative to the project root
+const REPORTS_DIR: &str = ".rust-adapter/reports";
+
+/// Badge generator implementation
+#[derive(Debug, Clone)]
+pub struct BadgeGenerator {
+    /// Whether generator is ready
+    ready: bool,
+}
+
+impl BadgeGenerator {
+    /// Create new badge generator with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self { ready: true }
+    }
+    
+    /// Check if generator is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Record a summary as the latest report of its kind
+    pub fn record(&self, project: &Project, summary: &ReportSummary) -> Result<()> {
+        let path = Self::summary_path(project, summary.kind);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating report directory"))?;
+        }
+        
+        let content = serde_json::to_string_pretty(summary)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize report summary".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        crate::utils::write_atomic(&path, content)
+    }
+    
+    /// Latest recorded summary of a kind
+    pub fn latest(&self, project: &Project, kind: ReportKind) -> Result<ReportSummary> {
+        let path = Self::summary_path(project, kind);
+        crate::utils::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| AdapterError::file_not_found(&path, &format!("reading latest {} report summary", kind)))?;
+        
+        serde_json::from_str(&content)
+            .map_err(|e| AdapterError::MetadataParseError {
+                field: format!("{}_summary", kind),
+                value: path.display().to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })
+    }
+    
+    /// Badge for the latest report of a kind
+    pub fn badge(&self, project: &Project, kind: ReportKind, label: Option<&str>) -> Result<Badge> {
+        Ok(self.latest(project, kind)?.badge(label))
+    }
+    
+    /// Summary file of a report kind
+    fn summary_path(project: &Project, kind: ReportKind) -> PathBuf {
+        project.paths.root.join(REPORTS_DIR).join(format!("latest-{}.json", kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_record_and_badge() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            temp_dir.path().to_path_buf(),
+        );
+        let generator = BadgeGenerator::new(&RustAdapterConfig::default());
+        
+        assert!(matches!(generator.latest(&project, ReportKind::Drift), Err(AdapterError::FileNotFound { .. })));
+        
+        let mut summary = ReportSummary::from_sbom(3, String::new());
+        generator.record(&project, &summary).unwrap();
+        summary.total = 4;
+        generator.record(&project, &summary).unwrap();
+        
+        assert_eq!(generator.latest(&project, ReportKind::Sbom).unwrap(), summary);
+        assert_eq!(generator.badge(&project, ReportKind::Sbom, None).unwrap().message, "4 components");
+    }
+}
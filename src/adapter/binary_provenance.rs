@@ -0,0 +1,203 @@
+//! Provenance verification of binaries built with cargo-auditable
+//!
+//! cargo-auditable embeds the dependency list of a binary as
+//! zlib-compressed JSON in a `.dep-v0` section (`__DATA,.dep-v0` on
+//! Mach-O). This module reads that section from ELF, PE and Mach-O files
+//! without running any tool, and compares the list with the packages an
+//! epoch pins. A release binary embedding a package the epoch does not
+//! pin, or pinned from another kind of source, was built from a
+//! dependency set that was never approved.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use crate::utils::lockfile::CRATES_IO_SOURCE;
+use crate::utils::normalize_source;
+use object::{Object, ObjectSection};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Section holding the embedded dependency list
+const AUDITABLE_SECTION: &str = ".dep-v0";
+
+/// Embedded dependency lists larger than this are rejected (bytes, decompressed)
+const MAX_AUDITABLE_DATA_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Binary provenance verifier implementation
+#[derive(Debug, Clone)]
+pub struct BinaryProvenanceVerifier {
+    /// Whether verifier is ready
+    ready: bool,
+}
+
+/// Dependency list embedded by cargo-auditable
+#[derive(Debug, Deserialize)]
+struct AuditableDependencies {
+    packages: Vec<EmbeddedPackage>,
+}
+
+/// Package of an embedded dependency list
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct EmbeddedPackage {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// `crates.io`, `git`, `local`, `registry` or `other`
+    #[serde(default)]
+    pub source: String,
+}
+
+impl BinaryProvenanceVerifier {
+    /// Create new verifier with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self { ready: true }
+    }
+    
+    /// Check if verifier is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Compare the dependencies embedded in `binary` with `expected`
+    ///
+    /// `expected` holds the packages pinned by `epoch_id`, or by Cargo.lock
+    /// without an epoch. Workspace and path packages are not compared.
+    pub fn verify(
+        &self,
+        binary: &Path,
+        epoch_id: Option<&str>,
+        expected: &BTreeMap<String, VendorManifestEntry>,
+    ) -> Result<BinaryProvenance> {
+        let embedded = self.embedded_packages(binary)?;
+        let mut provenance = Self::compare(&embedded, expected);
+        provenance.binary = binary.display().to_string();
+        provenance.epoch_id = epoch_id.map(str::to_string);
+        Ok(provenance)
+    }
+    
+    /// Read the dependency list embedded in an ELF, PE or Mach-O binary
+    pub fn embedded_packages(&self, binary: &Path) -> Result<Vec<EmbeddedPackage>> {
+        let data = std::fs::read(binary)
+            .map_err(|_| AdapterError::file_not_found(binary, "reading binary"))?;
+        let invalid = |reason: String| AdapterError::MetadataParseError {
+            field: AUDITABLE_SECTION.to_string(),
+            value: binary.display().to_string(),
+            source: anyhow::anyhow!(reason),
+        };
+        
+        let file = object::File::parse(&*data)
+            .map_err(|e| invalid(format!("Not an ELF, PE or Mach-O binary: {}", e)))?;
+        let section = file.section_by_name(AUDITABLE_SECTION)
+            .ok_or_else(|| invalid("No cargo-auditable dependency data; build with `cargo auditable build`".to_string()))?;
+        let compressed = section.data()
+            .map_err(|e| invalid(format!("Unreadable dependency data: {}", e)))?;
+        Self::parse_section(compressed).map_err(invalid)
+    }
+    
+    /// Decompress and parse the contents of a `.dep-v0` section
+    fn parse_section(compressed: &[u8]) -> std::result::Result<Vec<EmbeddedPackage>, String> {
+        let mut json = Vec::new();
+        flate2::read::ZlibDecoder::new(compressed)
+            .take(MAX_AUDITABLE_DATA_SIZE + 1)
+            .read_to_end(&mut json)
+            .map_err(|e| format!("Corrupt dependency data: {}", e))?;
+        if json.len() as u64 > MAX_AUDITABLE_DATA_SIZE {
+            return Err(format!("Dependency data exceeds {} bytes", MAX_AUDITABLE_DATA_SIZE));
+        }
+        
+        serde_json::from_slice::<AuditableDependencies>(&json)
+            .map(|dependencies| dependencies.packages)
+            .map_err(|e| format!("Invalid dependency data: {}", e))
+    }
+    
+    /// Provenance of an embedded dependency list against pinned packages
+    fn compare(embedded: &[EmbeddedPackage], expected: &BTreeMap<String, VendorManifestEntry>) -> BinaryProvenance {
+        let mut provenance = BinaryProvenance {
+            binary: String::new(),
+            epoch_id: None,
+            embedded_packages: 0,
+            unexpected_packages: Vec::new(),
+            source_mismatches: Vec::new(),
+            verified: true,
+        };
+        
+        for package in embedded.iter().filter(|package| package.source != "local") {
+            provenance.embedded_packages += 1;
+            let key = format!("{}@{}", package.name, package.version);
+            match expected.get(&key) {
+                None => provenance.unexpected_packages.push(key),
+                Some(entry) => {
+                    let pinned = entry.source.as_deref().map(Self::source_kind).unwrap_or("local");
+                    if pinned != package.source {
+                        provenance.source_mismatches.push(key);
+                    }
+                },
+            }
+        }
+        
+        for list in [&mut provenance.unexpected_packages, &mut provenance.source_mismatches] {
+            list.sort();
+            list.dedup();
+        }
+        provenance.verified = provenance.unexpected_packages.is_empty() && provenance.source_mismatches.is_empty();
+        provenance
+    }
+    
+    /// Source kind cargo-auditable records for a Cargo.lock source
+    fn source_kind(source: &str) -> &'static str {
+        match source.split_once('+') {
+            Some(("git", _)) => "git",
+            Some(("registry" | "sparse", _)) if normalize_source(source) == CRATES_IO_SOURCE => "crates.io",
+            Some(("registry" | "sparse", _)) => "registry",
+            _ => "other",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    
+    #[test]
+    fn test_embedded_dependencies_compared_with_epoch() {
+        let json = r#"{"packages": [
+            {"name": "app", "version": "0.1.0", "source": "local", "kind": "runtime", "root": true},
+            {"name": "serde", "version": "1.0.190", "source": "crates.io", "kind": "runtime"},
+            {"name": "log", "version": "0.4.21", "source": "crates.io", "kind": "runtime"},
+            {"name": "ring", "version": "0.17.8", "source": "crates.io", "kind": "runtime"}
+        ]}"#;
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let embedded = BinaryProvenanceVerifier::parse_section(&encoder.finish().unwrap()).unwrap();
+        assert_eq!(embedded.len(), 4);
+        
+        let entry = |name: &str, version: &str, source: &str| (format!("{}@{}", name, version), VendorManifestEntry {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: Some(source.to_string()),
+            checksum: None,
+            path: name.into(),
+        });
+        let expected: BTreeMap<_, _> = [
+            entry("serde", "1.0.190", "sparse+https://index.crates.io/"),
+            entry("log", "0.4.20", CRATES_IO_SOURCE),
+            entry("ring", "0.17.8", "git+https://github.com/briansmith/ring?rev=abc#abc"),
+        ].into_iter().collect();
+        
+        let provenance = BinaryProvenanceVerifier::compare(&embedded, &expected);
+        assert_eq!(provenance.embedded_packages, 3);
+        assert_eq!(provenance.unexpected_packages, vec!["log@0.4.21".to_string()]);
+        assert_eq!(provenance.source_mismatches, vec!["ring@0.17.8".to_string()]);
+        assert!(!provenance.verified);
+        
+        // Files that are not binaries are rejected
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, "not a binary").unwrap();
+        let verifier = BinaryProvenanceVerifier::new(&RustAdapterConfig::default());
+        assert!(matches!(verifier.verify(&text, None, &expected), Err(AdapterError::MetadataParseError { .. })));
+    }
+}
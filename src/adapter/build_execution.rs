@@ -0,0 +1,338 @@
+//! Build-time execution analysis for vendored crates
+//!
+//! This module lists every vendored crate that executes code during a
+//! build: procedural macros and crates with a build script. The sources
+//! of that build-time code are scanned line by line for patterns that
+//! suggest filesystem, network or process access. The scan is a review
+//! aid for epoch approval; it neither sandboxes nor runs any code.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Source patterns suggesting each capability
+const CAPABILITY_PATTERNS: &[(BuildCapability, &[&str])] = &[
+    (BuildCapability::Filesystem, &[
+        "std::fs", "fs::", "File::open", "File::create", "OpenOptions", "read_dir", "remove_file", "remove_dir",
+    ]),
+    (BuildCapability::Network, &[
+        "std::net", "TcpStream", "TcpListener", "UdpSocket", "reqwest::", "ureq::", "curl::", "hyper::",
+    ]),
+    (BuildCapability::ProcessSpawn, &[
+        "std::process", "process::Command", "Command::new", "cc::Build", "cmake::", "pkg_config::",
+    ]),
+];
+
+/// Build-time execution analyzer implementation
+#[derive(Debug, Clone)]
+pub struct BuildExecutionAnalyzer {
+    /// Analyzer configuration
+    config: BuildExecutionConfig,
+    /// Whether analyzer is ready
+    ready: bool,
+}
+
+/// Configuration for build-time execution analyzer
+#[derive(Debug, Clone)]
+pub struct BuildExecutionConfig {
+    /// Source files larger than this are not scanned but reported (bytes)
+    pub max_file_size: u64,
+    /// Maximum number of evidence entries kept per crate
+    pub max_evidence_per_crate: usize,
+}
+
+/// Subset of a vendored `Cargo.toml`
+#[derive(Debug, Deserialize)]
+struct VendoredManifest {
+    package: VendoredPackage,
+    #[serde(default)]
+    lib: Option<VendoredLib>,
+}
+
+/// `[package]` table of a vendored manifest
+#[derive(Debug, Deserialize)]
+struct VendoredPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    build: Option<toml::Value>,
+    #[serde(default)]
+    links: Option<String>,
+}
+
+/// `[lib]` table of a vendored manifest
+#[derive(Debug, Deserialize)]
+struct VendoredLib {
+    #[serde(default, rename = "proc-macro", alias = "proc_macro")]
+    proc_macro: bool,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+impl BuildExecutionAnalyzer {
+    /// Create new build-time execution analyzer with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self {
+            config: BuildExecutionConfig::default(),
+            ready: true,
+        }
+    }
+    
+    /// Check if analyzer is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Scan every crate in a vendor directory
+    ///
+    /// Crates without build-time code are counted but not listed. The
+    /// returned report leaves `epoch_id` and `lockfile_fingerprint` for
+    /// the caller to fill in.
+    pub fn analyze_vendor(&self, vendor_dir: &Path) -> Result<BuildExecutionReport> {
+        let entries = std::fs::read_dir(vendor_dir)
+            .map_err(|_| AdapterError::file_not_found(vendor_dir, "reading vendor directory"))?;
+        
+        let mut crate_dirs: Vec<PathBuf> = entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join("Cargo.toml").is_file())
+            .collect();
+        crate_dirs.sort();
+        
+        let mut crates = Vec::new();
+        for crate_dir in &crate_dirs {
+            match self.analyze_crate(crate_dir) {
+                Ok(Some(mut entry)) => {
+                    entry.path = crate_dir.strip_prefix(vendor_dir).unwrap_or(crate_dir).to_path_buf();
+                    crates.push(entry);
+                },
+                Ok(None) => {},
+                Err(e) => tracing::warn!(path = %crate_dir.display(), error = %e, "Skipping unreadable vendored crate"),
+            }
+        }
+        crates.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        
+        let summary = BuildExecutionSummary::from_crates(crate_dirs.len(), &crates);
+        
+        Ok(BuildExecutionReport {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            epoch_id: None,
+            lockfile_fingerprint: String::new(),
+            vendor_dir: vendor_dir.to_path_buf(),
+            crates,
+            summary,
+        })
+    }
+    
+    /// Analyze one crate, returning `None` if it runs no code at build time
+    pub fn analyze_crate(&self, crate_dir: &Path) -> Result<Option<BuildExecutionCrate>> {
+        let manifest_path = crate_dir.join("Cargo.toml");
+        let content = std::fs::read_to_string(&manifest_path)
+            .map_err(|_| AdapterError::file_not_found(&manifest_path, "reading vendored manifest"))?;
+        let manifest: VendoredManifest = toml::from_str(&content)
+            .map_err(|e| AdapterError::MetadataParseError {
+                field: "Cargo.toml".to_string(),
+                value: manifest_path.display().to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        
+        // Cargo detects build.rs unless `build` names another file or is false
+        let build_script = match &manifest.package.build {
+            Some(toml::Value::String(path)) => Some(PathBuf::from(path)),
+            Some(toml::Value::Boolean(false)) => None,
+            _ => Some(PathBuf::from("build.rs")).filter(|path| crate_dir.join(path).is_file()),
+        };
+        let proc_macro = manifest.lib.as_ref().is_some_and(|lib| lib.proc_macro);
+        
+        if build_script.is_none() && !proc_macro {
+            return Ok(None);
+        }
+        
+        // Build scripts at the crate root are scanned alone; a script in a
+        // subdirectory brings its sibling modules along
+        let mut roots = Vec::new();
+        if let Some(script) = &build_script {
+            match script.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                Some(parent) => roots.push(crate_dir.join(parent)),
+                None => roots.push(crate_dir.join(script)),
+            }
+        }
+        if proc_macro {
+            let lib_path = manifest.lib.as_ref()
+                .and_then(|lib| lib.path.as_deref())
+                .unwrap_or("src/lib.rs");
+            let lib_dir = Path::new(lib_path).parent().unwrap_or(Path::new(""));
+            roots.push(crate_dir.join(lib_dir));
+        }
+        
+        let mut capabilities = BTreeSet::new();
+        let mut evidence = Vec::new();
+        let mut not_analyzed = Vec::new();
+        let files = self.source_files(&roots);
+        let mut files_scanned = 0;
+        for file in &files {
+            let relative = file.strip_prefix(crate_dir).unwrap_or(file);
+            let found = match self.scan_file(file, relative) {
+                Ok(found) => found,
+                Err(reason) => {
+                    not_analyzed.push(UnanalyzedFile { file: relative.to_path_buf(), reason });
+                    continue;
+                },
+            };
+            files_scanned += 1;
+            for found in found {
+                capabilities.insert(found.capability);
+                if evidence.len() < self.config.max_evidence_per_crate {
+                    evidence.push(found);
+                }
+            }
+        }
+        
+        Ok(Some(BuildExecutionCrate {
+            name: manifest.package.name,
+            version: manifest.package.version,
+            path: PathBuf::new(),
+            proc_macro,
+            build_script,
+            links: manifest.package.links,
+            capabilities: capabilities.into_iter().collect(),
+            evidence,
+            files_scanned,
+            not_analyzed,
+        }))
+    }
+    
+    /// Rust source files under the given files or directories, deduplicated
+    fn source_files(&self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut files = BTreeSet::new();
+        
+        for root in roots {
+            for entry in walkdir::WalkDir::new(root).follow_links(false).into_iter().flatten() {
+                let is_source = entry.file_type().is_file()
+                    && entry.path().extension().is_some_and(|ext| ext == "rs");
+                if is_source {
+                    files.insert(entry.into_path());
+                }
+            }
+        }
+        
+        files.into_iter().collect()
+    }
+    
+    /// Capability patterns found in a source file, one per capability and line
+    ///
+    /// Fails with the reason when the file is too large or unreadable.
+    fn scan_file(&self, file: &Path, relative: &Path) -> std::result::Result<Vec<CapabilityEvidence>, String> {
+        let size = std::fs::metadata(file).map_err(|e| format!("unreadable: {}", e))?.len();
+        if size > self.config.max_file_size {
+            return Err(format!("{} bytes exceeds the {} byte scan limit", size, self.config.max_file_size));
+        }
+        let content = std::fs::read_to_string(file).map_err(|e| format!("unreadable: {}", e))?;
+        
+        let mut evidence = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let code = line.trim_start();
+            if code.starts_with("//") {
+                continue;
+            }
+            
+            for (capability, patterns) in CAPABILITY_PATTERNS {
+                if let Some(pattern) = patterns.iter().find(|pattern| code.contains(*pattern)) {
+                    evidence.push(CapabilityEvidence {
+                        capability: *capability,
+                        file: relative.to_path_buf(),
+                        line: index + 1,
+                        pattern: pattern.to_string(),
+                    });
+                }
+            }
+        }
+        
+        Ok(evidence)
+    }
+}
+
+impl Default for BuildExecutionConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: 1024 * 1024,
+            max_evidence_per_crate: 20,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RustAdapterConfig;
+    use tempfile::TempDir;
+    
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    
+    #[test]
+    fn test_analyze_vendor_lists_build_time_crates() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendor = temp_dir.path();
+        
+        write(&vendor.join("openssl-sys/Cargo.toml"), "[package]\nname = \"openssl-sys\"\nversion = \"0.9.0\"\nlinks = \"openssl\"\n");
+        write(&vendor.join("openssl-sys/build.rs"), "// std::net is not used\nuse std::process::Command;\nfn main() {\n    let _ = std::fs::read_to_string(\"x\");\n}\n");
+        write(&vendor.join("openssl-sys/src/lib.rs"), "pub fn ssl() { std::net::TcpStream::connect(\"x\"); }\n");
+        
+        write(&vendor.join("fetch-derive/Cargo.toml"), "[package]\nname = \"fetch-derive\"\nversion = \"1.0.0\"\nbuild = false\n\n[lib]\nproc-macro = true\n");
+        write(&vendor.join("fetch-derive/src/lib.rs"), "mod net;\n");
+        write(&vendor.join("fetch-derive/src/net.rs"), "fn get() { reqwest::blocking::get(\"x\"); }\n");
+        
+        write(&vendor.join("itoa/Cargo.toml"), "[package]\nname = \"itoa\"\nversion = \"1.0.0\"\n");
+        write(&vendor.join("itoa/src/lib.rs"), "pub fn itoa() { std::fs::read(\"x\"); }\n");
+        
+        let analyzer = BuildExecutionAnalyzer::new(&RustAdapterConfig::default());
+        let report = analyzer.analyze_vendor(vendor).unwrap();
+        
+        assert_eq!(report.summary.crates_scanned, 3);
+        assert_eq!(report.crates.len(), 2);
+        
+        let derive = &report.crates[0];
+        assert_eq!(derive.name, "fetch-derive");
+        assert!(derive.proc_macro);
+        assert_eq!(derive.build_script, None);
+        assert_eq!(derive.capabilities, vec![BuildCapability::Network]);
+        assert_eq!(derive.evidence[0].file, PathBuf::from("src/net.rs"));
+        
+        let sys = &report.crates[1];
+        assert_eq!(sys.build_script, Some(PathBuf::from("build.rs")));
+        assert_eq!(sys.links.as_deref(), Some("openssl"));
+        assert_eq!(sys.files_scanned, 1);
+        assert_eq!(sys.capabilities, vec![BuildCapability::Filesystem, BuildCapability::ProcessSpawn]);
+        assert_eq!(sys.evidence[0].line, 2);
+        
+        assert_eq!(report.summary.build_scripts, 1);
+        assert_eq!(report.summary.proc_macros, 1);
+        assert_eq!(report.summary.network, 1);
+        assert_eq!(report.summary.process_spawn, 1);
+        assert_eq!(report.summary.not_fully_analyzed, 0);
+    }
+    
+    #[test]
+    fn test_oversized_sources_are_reported_not_analyzed() {
+        let temp_dir = TempDir::new().unwrap();
+        let crate_dir = temp_dir.path().join("bindgen-sys");
+        write(&crate_dir.join("Cargo.toml"), "[package]\nname = \"bindgen-sys\"\nversion = \"0.1.0\"\nbuild = \"build/main.rs\"\n");
+        write(&crate_dir.join("build/main.rs"), "mod generated;\nfn main() {}\n");
+        write(&crate_dir.join("build/generated.rs"), &"use std::net::TcpStream;\n".repeat(16));
+        
+        let mut analyzer = BuildExecutionAnalyzer::new(&RustAdapterConfig::default());
+        analyzer.config.max_file_size = 64;
+        let entry = analyzer.analyze_crate(&crate_dir).unwrap().unwrap();
+        
+        assert_eq!(entry.files_scanned, 1);
+        assert!(entry.capabilities.is_empty());
+        assert_eq!(entry.not_analyzed.len(), 1);
+        assert_eq!(entry.not_analyzed[0].file, PathBuf::from("build/generated.rs"));
+        assert!(entry.not_analyzed[0].reason.contains("exceeds the 64 byte scan limit"));
+        assert_eq!(BuildExecutionSummary::from_crates(1, &[entry]).not_fully_analyzed, 1);
+    }
+}
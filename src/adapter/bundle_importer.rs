@@ -0,0 +1,558 @@
+//! Importer of air-gapped security data bundles
+//!
+//! Sites without network access refresh their security data by carrying
+//! a bundle prepared on a connected host through an approved transfer
+//! channel. `rust-adapter bundle import <tar>` checks the Ed25519
+//! signature of the bundle manifest against `bundle_config` before reading
+//! any other entry, then checks every file against the digest the
+//! manifest records. Each section replaces one shared cache entry:
+//!
+//! ```text
+//! bundle-manifest.json      signed file list
+//! bundle-manifest.sig       hex signature of the manifest bytes
+//! advisory-db/...           -> <cache>/advisory-db, used by cargo-audit
+//! index-mirror/...          -> <cache>/index-mirror, used for update lag
+//! audit-records/*.json      -> <cache>/audit-records, proofs for supply chain checks
+//! ```
+//!
+//! Sections are extracted into staging directories under the entries'
+//! exclusive locks and swapped in only once the whole bundle verified, so
+//! a rejected bundle leaves the cache as it was. Bundles may be gzipped.
+//!
+//! The manifest creation time of each installed section is recorded in a
+//! ledger, and a bundle older than an installed section it replaces is
+//! rejected unless the import is forced, so a stale bundle cannot roll
+//! back security data. The ledger is read and written under its own lock,
+//! taken with the section locks, so concurrent imports cannot both pass
+//! the check.
+
+use crate::adapter::audit_runner::ADVISORY_DB_ENTRY;
+use crate::adapter::transparency_log::decode;
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use crate::utils::{write_atomic, CacheLock, SharedCache};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Shared cache entry holding the imported index mirror
+pub const INDEX_MIRROR_ENTRY: &str = "index-mirror";
+
+/// Shared cache entry holding imported audit records
+pub const AUDIT_RECORDS_ENTRY: &str = "audit-records";
+
+/// Shared cache file recording the manifest creation time of each installed section
+pub const INSTALLED_BUNDLES_ENTRY: &str = "bundle-imports.json";
+
+/// Bundle importer implementation
+#[derive(Debug, Clone)]
+pub struct BundleImporter {
+    /// Shared cache the sections are installed to
+    cache: Option<SharedCache>,
+    /// Public keys (hex) bundles may be signed with
+    trusted_keys: Vec<String>,
+    /// Buffer size for streamed reads
+    buffer_size: usize,
+    /// Whether importer is ready
+    ready: bool,
+}
+
+/// Staging directories of the sections being installed
+struct Installation {
+    /// Staging directory of each section
+    staging: BTreeMap<BundleSection, PathBuf>,
+    /// Ledger of installed sections, read under its lock
+    installed: BTreeMap<BundleSection, String>,
+    /// Exclusive locks on the ledger and the replaced entries, held until commit
+    _locks: Vec<CacheLock>,
+}
+
+impl BundleImporter {
+    /// Create new bundle importer with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            cache: SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout),
+            trusted_keys: config.bundle_config.trusted_public_keys.clone(),
+            buffer_size: config.performance_config.io_buffer_size.max(1),
+            ready: true,
+        }
+    }
+    
+    /// Check if importer is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Shared cache entry a section is installed to
+    pub fn entry_name(section: BundleSection) -> &'static str {
+        match section {
+            BundleSection::AdvisoryDb => ADVISORY_DB_ENTRY,
+            BundleSection::IndexMirror => INDEX_MIRROR_ENTRY,
+            BundleSection::AuditRecords => AUDIT_RECORDS_ENTRY,
+        }
+    }
+    
+    /// Audit records installed from bundles, for packages of `ecosystem`
+    ///
+    /// Read under the entry's shared lock. Unreadable files are skipped:
+    /// their contents were checked when installed.
+    pub fn installed_audit_records(&self, ecosystem: &str) -> Result<Vec<AuditRecord>> {
+        let Some(cache) = &self.cache else {
+            return Ok(Vec::new());
+        };
+        let dir = cache.entry_path(AUDIT_RECORDS_ENTRY);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let _lock = cache.lock_shared(AUDIT_RECORDS_ENTRY)?;
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect();
+        files.sort();
+        
+        Ok(files.iter()
+            .filter_map(|path| std::fs::read(path).ok())
+            .filter_map(|content| serde_json::from_slice::<Vec<AuditRecord>>(&content).ok())
+            .flatten()
+            .filter(|record| record.ecosystem == ecosystem)
+            .collect())
+    }
+    
+    /// Verify a bundle without installing it
+    ///
+    /// Without `force`, a bundle older than the installed sections fails
+    /// verification as it would fail to import.
+    pub fn verify(&self, bundle: &Path, force: bool) -> Result<BundleImportReport> {
+        self.process(bundle, false, force)
+    }
+    
+    /// Verify a bundle and install its sections into the shared cache
+    ///
+    /// Without `force`, a bundle older than the installed copy of any
+    /// section it carries is rejected.
+    pub fn import(&self, bundle: &Path, force: bool) -> Result<BundleImportReport> {
+        self.process(bundle, true, force)
+    }
+    
+    /// Read and verify every entry, extracting into staging directories when installing
+    fn process(&self, bundle: &Path, install: bool, force: bool) -> Result<BundleImportReport> {
+        let cache = self.cache.as_ref().ok_or_else(|| AdapterError::ConfigurationInvalid {
+            field: "cache_config.shared_dir".to_string(),
+            value: String::new(),
+            reason: "No shared cache directory to install bundles to: set cache_config.shared_dir or HOME".to_string(),
+            source: anyhow::anyhow!("No shared cache directory"),
+        })?;
+        let read_error = |e: std::io::Error| Self::bundle_error(bundle, format!("Failed to read bundle: {}", e));
+        
+        let file = std::fs::File::open(bundle)
+            .map_err(|_| AdapterError::file_not_found(bundle, "reading security data bundle"))?;
+        let mut reader = std::io::BufReader::new(file);
+        let gzipped = reader.fill_buf().map_err(read_error)?.starts_with(&[0x1f, 0x8b]);
+        let reader: Box<dyn Read> = if gzipped {
+            Box::new(flate2::read::GzDecoder::new(reader))
+        } else {
+            Box::new(reader)
+        };
+        let mut archive = tar::Archive::new(reader);
+        let mut entries = archive.entries().map_err(read_error)?;
+        
+        // The manifest and its signature come first, so nothing else is
+        // read from a bundle signed by an untrusted key
+        let mut header_entry = |expected: &str| -> Result<Vec<u8>> {
+            let mut entry = entries.next()
+                .ok_or_else(|| Self::bundle_error(bundle, format!("Bundle ends before {}", expected)))?
+                .map_err(read_error)?;
+            if entry.path().map_err(read_error)?.as_ref() != Path::new(expected) {
+                return Err(Self::bundle_error(bundle, format!("Expected {} entry", expected)));
+            }
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).map_err(read_error)?;
+            Ok(content)
+        };
+        let manifest_bytes = header_entry(BUNDLE_MANIFEST_ENTRY)?;
+        let signature = header_entry(BUNDLE_SIGNATURE_ENTRY)?;
+        let signing_key = self.signing_key(bundle, &manifest_bytes, &String::from_utf8_lossy(&signature))?;
+        
+        let manifest: BundleManifest = serde_json::from_slice(&manifest_bytes)
+            .map_err(|e| Self::bundle_error(bundle, format!("Invalid bundle manifest: {}", e)))?;
+        if manifest.format_version != BundleManifest::FORMAT_VERSION {
+            return Err(Self::bundle_error(bundle, format!("Unsupported bundle format version {}", manifest.format_version)));
+        }
+        if let Some(file) = manifest.files.iter().find(|file| BundleSection::of_path(&file.path).is_none()) {
+            return Err(Self::bundle_error(bundle, format!("Manifest lists {} outside the known sections", file.path)));
+        }
+        
+        let installation = match install {
+            true => Some(Self::prepare(cache, bundle, &manifest, force)?),
+            false => {
+                if !force {
+                    let _lock = cache.lock_shared(INSTALLED_BUNDLES_ENTRY)?;
+                    Self::check_freshness(bundle, &manifest, &Self::installed_sections(cache))?;
+                }
+                None
+            },
+        };
+        let extracted = self.extract(bundle, entries, &manifest, installation.as_ref());
+        if let Err(e) = extracted {
+            for staging in installation.iter().flat_map(|installation| installation.staging.values()) {
+                let _ = std::fs::remove_dir_all(staging);
+            }
+            return Err(e);
+        }
+        if let Some(installation) = &installation {
+            let mut installed = installation.installed.clone();
+            for (section, staging) in &installation.staging {
+                cache.commit_staging(Self::entry_name(*section), staging)?;
+                installed.insert(*section, manifest.created_at.clone());
+            }
+            Self::record_installed_sections(cache, &installed)?;
+        }
+        
+        let sections = manifest.sections().into_iter()
+            .map(|section| {
+                let files: Vec<_> = manifest.files.iter()
+                    .filter(|file| BundleSection::of_path(&file.path) == Some(section))
+                    .collect();
+                BundleSectionSummary {
+                    section,
+                    path: cache.entry_path(Self::entry_name(section)),
+                    files: files.len(),
+                    size_bytes: files.iter().map(|file| file.size_bytes).sum(),
+                }
+            })
+            .collect();
+        
+        Ok(BundleImportReport {
+            bundle: bundle.to_path_buf(),
+            created_at: manifest.created_at,
+            signing_key,
+            installed: install,
+            sections,
+        })
+    }
+    
+    /// Reject a bundle older than the installed copy of a section it carries
+    fn check_freshness(bundle: &Path, manifest: &BundleManifest, installed: &BTreeMap<BundleSection, String>) -> Result<()> {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&manifest.created_at)
+            .map_err(|e| Self::bundle_error(bundle, format!("Invalid manifest creation time {}: {}", manifest.created_at, e)))?;
+        
+        for section in manifest.sections() {
+            let Some(installed_at) = installed.get(&section) else {
+                continue;
+            };
+            let older = chrono::DateTime::parse_from_rfc3339(installed_at)
+                .is_ok_and(|installed_at| created_at < installed_at);
+            if older {
+                return Err(Self::bundle_error(bundle, format!(
+                    "Bundle created at {} is older than the installed {} section from {}; force the import to install it anyway",
+                    manifest.created_at,
+                    section.as_str(),
+                    installed_at,
+                )));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Manifest creation time of each installed section
+    fn installed_sections(cache: &SharedCache) -> BTreeMap<BundleSection, String> {
+        std::fs::read(cache.entry_path(INSTALLED_BUNDLES_ENTRY))
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+    
+    /// Record the manifest creation time of each installed section
+    fn record_installed_sections(cache: &SharedCache, installed: &BTreeMap<BundleSection, String>) -> Result<()> {
+        let content = serde_json::to_string_pretty(installed)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize installed bundle sections".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        write_atomic(cache.entry_path(INSTALLED_BUNDLES_ENTRY), content.as_bytes())
+    }
+    
+    /// Lock the ledger and the entries the bundle replaces, check freshness
+    /// unless forced, and create the entries' staging directories
+    fn prepare(cache: &SharedCache, bundle: &Path, manifest: &BundleManifest, force: bool) -> Result<Installation> {
+        let mut locks = vec![cache.lock_exclusive(INSTALLED_BUNDLES_ENTRY)?];
+        for section in manifest.sections() {
+            locks.push(cache.lock_exclusive(Self::entry_name(section))?);
+        }
+        let installed = Self::installed_sections(cache);
+        if !force {
+            Self::check_freshness(bundle, manifest, &installed)?;
+        }
+        
+        let mut installation = Installation { staging: BTreeMap::new(), installed, _locks: locks };
+        for section in manifest.sections() {
+            installation.staging.insert(section, cache.create_staging(Self::entry_name(section))?);
+        }
+        Ok(installation)
+    }
+    
+    /// Hash every remaining entry against the manifest, writing it to its section's staging directory
+    fn extract<R: Read>(
+        &self,
+        bundle: &Path,
+        entries: tar::Entries<'_, R>,
+        manifest: &BundleManifest,
+        installation: Option<&Installation>,
+    ) -> Result<()> {
+        let read_error = |e: std::io::Error| Self::bundle_error(bundle, format!("Failed to read bundle: {}", e));
+        let expected: HashMap<&str, &BundleFile> = manifest.files.iter()
+            .map(|file| (file.path.as_str(), file))
+            .collect();
+        let mut extracted = HashSet::new();
+        let mut buffer = vec![0u8; self.buffer_size];
+        
+        for entry in entries {
+            let mut entry = entry.map_err(read_error)?;
+            let entry_path = entry.path().map_err(read_error)?.into_owned();
+            if entry.header().entry_type().is_dir() {
+                continue;
+            }
+            if !entry.header().entry_type().is_file() {
+                return Err(Self::bundle_error(bundle, format!("Unexpected non-file entry {:?}", entry_path)));
+            }
+            if !entry_path.components().all(|c| matches!(c, Component::Normal(_))) {
+                return Err(Self::bundle_error(bundle, format!("Unsafe entry path {:?}", entry_path)));
+            }
+            let key = entry_path.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            
+            let recorded = *expected.get(key.as_str())
+                .ok_or_else(|| Self::bundle_error(bundle, format!("Entry {} is not listed in the manifest", key)))?;
+            if !extracted.insert(key.clone()) {
+                return Err(Self::bundle_error(bundle, format!("Duplicate entry {}", key)));
+            }
+            
+            let mut output = match installation {
+                Some(installation) => {
+                    let section = BundleSection::of_path(&key)
+                        .ok_or_else(|| Self::bundle_error(bundle, format!("Entry {} is outside the known sections", key)))?;
+                    let relative = entry_path.strip_prefix(section.as_str()).unwrap_or(&entry_path);
+                    let output_path = installation.staging[&section].join(relative);
+                    if let Some(parent) = output_path.parent() {
+                        std::fs::create_dir_all(parent)
+                            .map_err(|_| AdapterError::permission_denied(parent, "creating bundle staging directory"))?;
+                    }
+                    let file = std::fs::File::create(&output_path)
+                        .map_err(|_| AdapterError::permission_denied(&output_path, "writing bundle file"))?;
+                    Some((output_path, file))
+                },
+                None => None,
+            };
+            
+            let mut hasher = Sha256::new();
+            let mut size = 0u64;
+            loop {
+                let read = entry.read(&mut buffer).map_err(read_error)?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+                if let Some((output_path, file)) = &mut output {
+                    file.write_all(&buffer[..read])
+                        .map_err(|_| AdapterError::permission_denied(output_path, "writing bundle file"))?;
+                }
+                size += read as u64;
+            }
+            
+            let actual = format!("{:x}", hasher.finalize());
+            if size != recorded.size_bytes || actual != recorded.sha256 {
+                return Err(AdapterError::checksum_mismatch(&key, &recorded.sha256, &actual));
+            }
+            if let Some((output_path, _)) = &output {
+                Self::check_audit_records(bundle, &key, output_path)?;
+            }
+        }
+        
+        let missing: Vec<_> = manifest.files.iter()
+            .filter(|file| !extracted.contains(&file.path))
+            .map(|file| file.path.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(Self::bundle_error(bundle, format!("Bundle is missing {} file(s) listed in the manifest: {}", missing.len(), missing.join(", "))));
+        }
+        
+        Ok(())
+    }
+    
+    /// Trusted key the manifest is signed with
+    fn signing_key(&self, bundle: &Path, manifest: &[u8], signature: &str) -> Result<String> {
+        if self.trusted_keys.is_empty() {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "bundle_config.trusted_public_keys".to_string(),
+                value: String::new(),
+                reason: "No trusted bundle signing keys are configured".to_string(),
+                source: anyhow::anyhow!("Bundles cannot be verified"),
+            });
+        }
+        let signature = decode::<64>(signature.trim())
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or_else(|| Self::bundle_error(bundle, "Malformed manifest signature".to_string()))?;
+        
+        self.trusted_keys.iter()
+            .find(|key| decode::<32>(key.trim())
+                .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+                .is_some_and(|key| key.verify(manifest, &signature).is_ok()))
+            .map(|key| key.trim().to_ascii_lowercase())
+            .ok_or_else(|| Self::bundle_error(bundle, "Manifest is not signed by a trusted key".to_string()))
+    }
+    
+    /// Check that an audit records file holds audit records
+    fn check_audit_records(bundle: &Path, key: &str, path: &Path) -> Result<()> {
+        if BundleSection::of_path(key) != Some(BundleSection::AuditRecords) || !key.ends_with(".json") {
+            return Ok(());
+        }
+        let content = std::fs::read(path)
+            .map_err(|_| AdapterError::permission_denied(path, "reading bundled audit records"))?;
+        serde_json::from_slice::<Vec<AuditRecord>>(&content)
+            .map(|_| ())
+            .map_err(|e| Self::bundle_error(bundle, format!("Invalid audit records in {}: {}", key, e)))
+    }
+    
+    fn bundle_error(bundle: &Path, reason: String) -> AdapterError {
+        AdapterError::BundleVerificationFailed {
+            bundle: bundle.to_path_buf(),
+            reason,
+            source: anyhow::anyhow!("Security data bundle failed verification"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RustAdapterConfig;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tempfile::TempDir;
+    
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+    
+    /// Write a gzipped bundle of `files`, signed by `key`, listing `listed` in its manifest
+    fn write_bundle(path: &Path, key: &SigningKey, files: &[(&str, &str)], listed: &[(&str, &str)]) {
+        write_bundle_at(path, key, "2026-10-01T00:00:00Z", files, listed);
+    }
+    
+    /// Write a gzipped bundle prepared at `created_at`
+    fn write_bundle_at(path: &Path, key: &SigningKey, created_at: &str, files: &[(&str, &str)], listed: &[(&str, &str)]) {
+        let manifest = BundleManifest {
+            format_version: BundleManifest::FORMAT_VERSION,
+            created_at: created_at.to_string(),
+            files: listed.iter().map(|(path, content)| BundleFile {
+                path: path.to_string(),
+                size_bytes: content.len() as u64,
+                sha256: format!("{:x}", Sha256::digest(content.as_bytes())),
+            }).collect(),
+        };
+        let manifest = serde_json::to_vec(&manifest).unwrap();
+        let signature = hex(&key.sign(&manifest).to_bytes());
+        
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        let entries = [(BUNDLE_MANIFEST_ENTRY, manifest.as_slice()), (BUNDLE_SIGNATURE_ENTRY, signature.as_bytes())].into_iter()
+            .chain(files.iter().map(|(path, content)| (*path, content.as_bytes())));
+        for (entry_path, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, entry_path, data).unwrap();
+        }
+        std::fs::write(path, builder.into_inner().unwrap().finish().unwrap()).unwrap();
+    }
+    
+    #[test]
+    fn test_import_verifies_signature_and_digests() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut config = RustAdapterConfig::default();
+        config.cache_config.shared_dir = Some(temp_dir.path().join("cache"));
+        config.bundle_config.trusted_public_keys = vec![hex(key.verifying_key().as_bytes())];
+        let importer = BundleImporter::new(&config);
+        let cache = temp_dir.path().join("cache");
+        
+        let records = serde_json::to_string(&[
+            AuditRecord::new("ring".to_string(), "0.17.14".to_string(), "rust".to_string(),
+                AuditMethod::Imported { source: "security-team".to_string() }, "safe-to-deploy".to_string(), "security-team".to_string()),
+            AuditRecord::new("lodash".to_string(), "4.17.21".to_string(), "npm".to_string(),
+                AuditMethod::Imported { source: "security-team".to_string() }, "safe-to-deploy".to_string(), "security-team".to_string()),
+        ]).unwrap();
+        let files = [
+            ("advisory-db/crates/ring/RUSTSEC-2025-0009.md", "advisory"),
+            ("index-mirror/ri/ng/ring", "{\"vers\":\"0.17.14\"}"),
+            ("audit-records/security-team.json", records.as_str()),
+        ];
+        let bundle = temp_dir.path().join("bundle.tar.gz");
+        write_bundle(&bundle, &key, &files, &files);
+        
+        let report = importer.import(&bundle, false).unwrap();
+        assert!(report.installed);
+        let sections: Vec<_> = report.sections.iter().map(|s| s.section).collect();
+        assert_eq!(sections, vec![BundleSection::AdvisoryDb, BundleSection::IndexMirror, BundleSection::AuditRecords]);
+        assert_eq!(std::fs::read_to_string(cache.join("advisory-db/crates/ring/RUSTSEC-2025-0009.md")).unwrap(), "advisory");
+        assert!(cache.join("index-mirror/ri/ng/ring").is_file());
+        let installed = importer.installed_audit_records("rust").unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].package_name, "ring");
+        assert_eq!(AuditProof::from_record(&installed[0]).criteria_met, vec!["safe-to-deploy".to_string(), "safe-to-run".to_string()]);
+        
+        // A tampered file is rejected and the installed copy kept
+        let tampered = [("advisory-db/crates/ring/RUSTSEC-2025-0009.md", "tampered")];
+        write_bundle(&bundle, &key, &tampered, &files[..1]);
+        assert!(matches!(importer.import(&bundle, false), Err(AdapterError::ChecksumMismatch { .. })));
+        assert_eq!(std::fs::read_to_string(cache.join("advisory-db/crates/ring/RUSTSEC-2025-0009.md")).unwrap(), "advisory");
+        
+        // So is a bundle signed by an untrusted key
+        write_bundle(&bundle, &SigningKey::from_bytes(&[9u8; 32]), &files, &files);
+        assert!(matches!(importer.verify(&bundle, false), Err(AdapterError::BundleVerificationFailed { .. })));
+    }
+    
+    #[test]
+    fn test_import_rejects_older_bundles_unless_forced() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut config = RustAdapterConfig::default();
+        config.cache_config.shared_dir = Some(temp_dir.path().join("cache"));
+        config.bundle_config.trusted_public_keys = vec![hex(key.verifying_key().as_bytes())];
+        let importer = BundleImporter::new(&config);
+        let advisory = temp_dir.path().join("cache/advisory-db/crates/ring/RUSTSEC-2025-0009.md");
+        let bundle = temp_dir.path().join("bundle.tar.gz");
+        
+        let current = [("advisory-db/crates/ring/RUSTSEC-2025-0009.md", "current")];
+        write_bundle_at(&bundle, &key, "2026-10-01T00:00:00Z", &current, &current);
+        importer.import(&bundle, false).unwrap();
+        
+        // An older bundle is rejected, in a dry run too, and the installed copy kept
+        let stale = [("advisory-db/crates/ring/RUSTSEC-2025-0009.md", "stale")];
+        write_bundle_at(&bundle, &key, "2026-09-01T00:00:00+02:00", &stale, &stale);
+        assert!(matches!(importer.verify(&bundle, false), Err(AdapterError::BundleVerificationFailed { .. })));
+        assert!(matches!(importer.import(&bundle, false), Err(AdapterError::BundleVerificationFailed { .. })));
+        assert_eq!(std::fs::read_to_string(&advisory).unwrap(), "current");
+        
+        // Sections no longer carried are rejected
+        let osv = [("osv/RUSTSEC-2025-0009.json", "{}")];
+        write_bundle_at(&bundle, &key, "2026-10-02T00:00:00Z", &osv, &osv);
+        assert!(matches!(importer.verify(&bundle, false), Err(AdapterError::BundleVerificationFailed { .. })));
+        
+        // Sections the installed bundles did not carry are not compared
+        let records = [("audit-records/security-team.json", "[]")];
+        write_bundle_at(&bundle, &key, "2026-09-01T00:00:00Z", &records, &records);
+        importer.import(&bundle, false).unwrap();
+        
+        // Forcing installs the older bundle
+        write_bundle_at(&bundle, &key, "2026-09-01T00:00:00Z", &stale, &stale);
+        assert!(importer.import(&bundle, true).unwrap().installed);
+        assert_eq!(std::fs::read_to_string(&advisory).unwrap(), "stale");
+    }
+}
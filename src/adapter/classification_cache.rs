@@ -0,0 +1,200 @@
+//! Disk-backed cache of TCS classification results
+//!
+//! A classification depends only on the package, the project's TCS lists
+//! and the classifier configuration, so results are stored under a key
+//! hashed from all three and shared by every project on the machine.
+//! Changing an override yields new keys; bumping the classification
+//! ruleset version moves the cache to a new directory and prunes the old
+//! ones.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Entry of the shared cache holding classification results
+pub const SHARED_CACHE_ENTRY: &str = "classification";
+
+/// Prefix of the per-ruleset cache directories
+const RULESET_DIR_PREFIX: &str = "ruleset-";
+
+/// Disk-backed classification result cache
+#[derive(Debug, Clone)]
+pub struct ClassificationCache {
+    /// Directory holding entries of the current ruleset, `None` if disabled
+    dir: Option<PathBuf>,
+    /// Fingerprint of the classifier configuration
+    config_fingerprint: String,
+}
+
+/// Cached classification of one package
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Package name
+    name: String,
+    /// Package version
+    version: String,
+    /// Configuration fingerprint the result was computed with
+    config_fingerprint: String,
+    /// Cached result
+    result: ClassificationResult,
+}
+
+impl ClassificationCache {
+    /// Create a cache rooted at `root` for a ruleset version
+    ///
+    /// Directories of other ruleset versions are removed on creation.
+    pub fn new(root: &Path, ruleset_version: &str, config_fingerprint: String) -> Self {
+        let dir = root.join(format!("{}{}", RULESET_DIR_PREFIX, ruleset_version));
+        Self::prune_other_rulesets(root, &dir);
+        
+        Self {
+            dir: Some(dir),
+            config_fingerprint,
+        }
+    }
+    
+    /// Create a cache that stores nothing
+    pub fn disabled() -> Self {
+        Self {
+            dir: None,
+            config_fingerprint: String::new(),
+        }
+    }
+    
+    /// Whether results are cached
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+    
+    /// Look up the cached classification of a package
+    pub fn get(&self, project_tcs: &ProjectTcs, package: &PackageNode) -> Option<ClassificationResult> {
+        let path = self.entry_path(project_tcs, package)?;
+        crate::utils::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        
+        let matches = entry.config_fingerprint == self.config_fingerprint
+            && entry.name == package.name
+            && entry.version == package.version;
+        matches.then_some(entry.result)
+    }
+    
+    /// Store the classification of a package
+    ///
+    /// The cache is an optimization: failures are logged, not returned.
+    pub fn put(&self, project_tcs: &ProjectTcs, package: &PackageNode, result: &ClassificationResult) {
+        let path = match self.entry_path(project_tcs, package) {
+            Some(path) => path,
+            None => return,
+        };
+        let entry = CacheEntry {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            config_fingerprint: self.config_fingerprint.clone(),
+            result: result.clone(),
+        };
+        
+        if let Err(e) = Self::write_entry(&path, &entry) {
+            tracing::debug!(path = %path.display(), error = %e, "Failed to cache classification result");
+        }
+    }
+    
+    /// Entry file for a package, keyed by everything the result depends on
+    fn entry_path(&self, project_tcs: &ProjectTcs, package: &PackageNode) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        
+        let mut hasher = Sha256::new();
+        let parts = [
+            self.config_fingerprint.clone(),
+            // Through `Value` so that custom lists hash in key order
+            serde_json::to_value(project_tcs).ok()?.to_string(),
+            package.name.clone(),
+            package.version.clone(),
+            serde_json::to_string(&package.source).ok()?,
+            package.is_proc_macro().to_string(),
+            // Registry categories and keywords are classification signals too
+            package.annotation(annotation_keys::CATEGORIES).map(|v| v.to_string()).unwrap_or_default(),
+            package.annotation(annotation_keys::KEYWORDS).map(|v| v.to_string()).unwrap_or_default(),
+        ];
+        for part in &parts {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        let key = format!("{:x}", hasher.finalize());
+        
+        Some(dir.join(&key[..2]).join(format!("{}.json", key)))
+    }
+    
+    /// Write an entry atomically
+    fn write_entry(path: &Path, entry: &CacheEntry) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating classification cache"))?;
+        }
+        
+        let content = serde_json::to_string(entry)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize classification cache entry".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        crate::utils::write_atomic(path, content)
+    }
+    
+    /// Remove cache directories written under other ruleset versions
+    fn prune_other_rulesets(root: &Path, current: &Path) {
+        let entries = match std::fs::read_dir(root) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_ruleset_dir = entry.file_name().to_str().is_some_and(|name| name.starts_with(RULESET_DIR_PREFIX));
+            if is_ruleset_dir && path.is_dir() && path != current {
+                if let Err(e) = std::fs::remove_dir_all(&path) {
+                    tracing::debug!(path = %path.display(), error = %e, "Failed to prune stale classification cache");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_cache_round_trip_and_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let tcs = ProjectTcs::default();
+        let ring = package("ring", "0.17.8", Classification::Unknown);
+        let result = ClassificationResult {
+            role: ToolchainRole::TCS(TcsCategory::Cryptography),
+            signals: vec![],
+        };
+        
+        let cache = ClassificationCache::new(temp_dir.path(), "1", "config-a".to_string());
+        assert_eq!(cache.get(&tcs, &ring), None);
+        cache.put(&tcs, &ring, &result);
+        assert_eq!(cache.get(&tcs, &ring), Some(result.clone()));
+        
+        // Other versions, project TCS lists and configurations miss
+        assert_eq!(cache.get(&tcs, &package("ring", "0.17.9", Classification::Unknown)), None);
+        let mut listed = ProjectTcs::default();
+        listed.crypto.push("ring".to_string());
+        assert_eq!(cache.get(&listed, &ring), None);
+        let reconfigured = ClassificationCache::new(temp_dir.path(), "1", "config-b".to_string());
+        assert_eq!(reconfigured.get(&tcs, &ring), None);
+        
+        // A new ruleset version prunes the old entries
+        let upgraded = ClassificationCache::new(temp_dir.path(), "2", "config-a".to_string());
+        assert_eq!(upgraded.get(&tcs, &ring), None);
+        assert!(!temp_dir.path().join("ruleset-1").exists());
+        
+        assert_eq!(ClassificationCache::disabled().get(&tcs, &ring), None);
+    }
+}
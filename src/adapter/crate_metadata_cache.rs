@@ -0,0 +1,155 @@
+//! Disk-backed cache of crate version metadata shared by all projects
+//!
+//! License, categories and publisher of a published crate version do not
+//! depend on the project using it, so every project on a builder can reuse
+//! what one of them fetched. Entries are keyed by `name@version` and
+//! expire after a time to live. A read-only cache, such as one provisioned
+//! for CI hosts or used offline, serves entries of any age and never
+//! writes.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Entry of the shared cache holding crate version metadata
+pub const SHARED_CACHE_ENTRY: &str = "crate-metadata";
+
+/// Disk-backed crate version metadata cache
+#[derive(Debug, Clone)]
+pub struct CrateMetadataCache {
+    /// Cache directory, `None` if disabled
+    dir: Option<PathBuf>,
+    /// Age after which entries are fetched again
+    ttl: Duration,
+    /// Whether entries are only read
+    read_only: bool,
+}
+
+impl CrateMetadataCache {
+    /// Create a cache in `dir` whose entries expire after `ttl`
+    pub fn new(dir: PathBuf, ttl: Duration, read_only: bool) -> Self {
+        Self { dir: Some(dir), ttl, read_only }
+    }
+    
+    /// Create a cache that stores nothing
+    pub fn disabled() -> Self {
+        Self { dir: None, ttl: Duration::ZERO, read_only: true }
+    }
+    
+    /// The same cache, only read
+    pub fn as_read_only(&self) -> Self {
+        Self { read_only: true, ..self.clone() }
+    }
+    
+    /// Whether metadata is cached
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+    
+    /// Whether entries are only read
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+    
+    /// Look up the metadata of a crate version
+    ///
+    /// Expired entries miss unless the cache is read-only.
+    pub fn get(&self, name: &str, version: &str) -> Option<CrateVersionMetadata> {
+        let path = self.entry_path(name, version)?;
+        crate::utils::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(path).ok()?;
+        let entry: CrateVersionMetadata = serde_json::from_str(&content).ok()?;
+        
+        let matches = entry.name == name && entry.version == version;
+        (matches && (self.read_only || self.is_fresh(&entry, Utc::now()))).then_some(entry)
+    }
+    
+    /// Store the metadata of a crate version
+    ///
+    /// The cache is an optimization: failures are logged, not returned.
+    pub fn put(&self, entry: &CrateVersionMetadata) {
+        if self.read_only {
+            return;
+        }
+        let Some(path) = self.entry_path(&entry.name, &entry.version) else {
+            return;
+        };
+        
+        if let Err(e) = Self::write_entry(&path, entry) {
+            tracing::debug!(path = %path.display(), error = %e, "Failed to cache crate metadata");
+        }
+    }
+    
+    /// Whether an entry was fetched less than the time to live before `now`
+    fn is_fresh(&self, entry: &CrateVersionMetadata, now: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&entry.fetched_at).ok()
+            .and_then(|fetched_at| (now - fetched_at.with_timezone(&Utc)).to_std().ok())
+            .is_some_and(|age| age < self.ttl)
+    }
+    
+    /// Entry file of a crate version, grouped by crate
+    fn entry_path(&self, name: &str, version: &str) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+        let name = name.to_ascii_lowercase();
+        Some(dir.join(&name).join(format!("{}@{}.json", name, version)))
+    }
+    
+    /// Write an entry atomically
+    fn write_entry(path: &Path, entry: &CrateVersionMetadata) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating crate metadata cache"))?;
+        }
+        
+        let content = serde_json::to_string(entry)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize crate metadata cache entry".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        crate::utils::write_atomic(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn metadata(version: &str, fetched_at: DateTime<Utc>) -> CrateVersionMetadata {
+        CrateVersionMetadata {
+            name: "serde".to_string(),
+            version: version.to_string(),
+            license: Some("MIT OR Apache-2.0".to_string()),
+            publisher: Some("dtolnay".to_string()),
+            categories: vec!["encoding".to_string()],
+            fetched_at: fetched_at.to_rfc3339(),
+        }
+    }
+    
+    #[test]
+    fn test_cache_expiry_and_read_only_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let cache = CrateMetadataCache::new(temp_dir.path().to_path_buf(), day, false);
+        
+        let fresh = metadata("1.0.200", Utc::now());
+        cache.put(&fresh);
+        assert_eq!(cache.get("serde", "1.0.200"), Some(fresh));
+        assert_eq!(cache.get("serde", "1.0.201"), None);
+        
+        // Expired entries miss, except in a read-only cache
+        let stale = metadata("1.0.100", Utc::now() - chrono::Duration::days(2));
+        cache.put(&stale);
+        assert_eq!(cache.get("serde", "1.0.100"), None);
+        let read_only = CrateMetadataCache::new(temp_dir.path().to_path_buf(), day, true);
+        assert_eq!(read_only.get("serde", "1.0.100"), Some(stale));
+        
+        // A read-only cache writes nothing
+        read_only.put(&metadata("1.0.0", Utc::now()));
+        assert_eq!(cache.get("serde", "1.0.0"), None);
+        
+        assert_eq!(CrateMetadataCache::disabled().get("serde", "1.0.200"), None);
+    }
+}
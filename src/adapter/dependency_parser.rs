@@ -5,18 +5,32 @@
 
 use crate::models::*;
 use crate::error::{AdapterError, Result};
-use async_trait::async_trait;
+use crate::manifest::{CargoSourceConfig, LockfileVcsState, WorkspaceManifest, CARGO_CONFIG_PROPERTY, VCS_PROPERTY};
+use crate::utils::{deadline, v1_metadata_checksum, CancellationToken, ChecksumCalculator, ProcessInvoker, ToolInvocation, ToolInvoker};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Project id of graphs parsed from lockfile content alone
+pub const IN_MEMORY_PROJECT_ID: &str = "lockfile";
+
+/// Graph metadata property the transitive depth limit of analysis is recorded in
+pub const MAX_DEPTH_PROPERTY: &str = "max_transitive_depth";
 
 /// Cargo.lock file structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CargoLock {
-    /// Cargo.lock version
+    /// Cargo.lock format version; formats 1 and 2 have no marker and read as 1
+    #[serde(default = "CargoLock::unversioned")]
     pub version: u32,
     /// Package list
+    #[serde(default)]
     pub package: Vec<CargoLockPackage>,
+    /// Format v1 checksum entries, moved onto the packages when parsing
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 /// Package in Cargo.lock
@@ -26,33 +40,35 @@ pub struct CargoLockPackage {
     pub name: String,
     /// Package version
     pub version: String,
-    /// Package source
+    /// Package source, absent for workspace members and path dependencies
+    #[serde(default)]
     pub source: Option<CargoLockSource>,
     /// Package dependencies
+    #[serde(default)]
     pub dependencies: Vec<CargoLockDependency>,
     /// Package checksum
+    #[serde(default)]
     pub checksum: Option<String>,
 }
 
 /// Source information in Cargo.lock
+///
+/// Written by cargo as `registry+<index>`, `sparse+<index>`,
+/// `git+<url>#<rev>` or `path+<url>`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "type")]
+#[serde(try_from = "String", into = "String")]
 pub enum CargoLockSource {
     /// Registry source
     Registry {
-        /// Registry name
+        /// Registry index URL, with the `sparse+` prefix for sparse indexes
         registry: String,
-        /// Package checksum
-        checksum: String,
     },
     /// Git source
     Git {
-        /// Repository URL
+        /// Repository URL, including any `?branch=`/`?tag=` query
         url: String,
         /// Commit hash
         rev: String,
-        /// Package checksum
-        checksum: String,
     },
     /// Local path source
     Local {
@@ -61,14 +77,49 @@ pub enum CargoLockSource {
     },
 }
 
+impl TryFrom<String> for CargoLockSource {
+    type Error = String;
+    
+    fn try_from(source: String) -> std::result::Result<Self, Self::Error> {
+        let (kind, location) = source.split_once('+')
+            .ok_or_else(|| format!("invalid package source {:?}", source))?;
+        match kind {
+            "registry" => Ok(CargoLockSource::Registry { registry: location.to_string() }),
+            "sparse" => Ok(CargoLockSource::Registry { registry: source.clone() }),
+            "git" => {
+                let (url, rev) = location.rsplit_once('#')
+                    .ok_or_else(|| format!("git source {:?} has no revision", source))?;
+                Ok(CargoLockSource::Git { url: url.to_string(), rev: rev.to_string() })
+            },
+            "path" => Ok(CargoLockSource::Local { path: location.to_string() }),
+            _ => Err(format!("unsupported package source {:?}", source)),
+        }
+    }
+}
+
+impl From<CargoLockSource> for String {
+    fn from(source: CargoLockSource) -> Self {
+        match source {
+            CargoLockSource::Registry { registry } if registry.starts_with("sparse+") => registry,
+            CargoLockSource::Registry { registry } => format!("registry+{}", registry),
+            CargoLockSource::Git { url, rev } => format!("git+{}#{}", url, rev),
+            CargoLockSource::Local { path } => format!("path+{}", path),
+        }
+    }
+}
+
 /// Dependency in Cargo.lock
+///
+/// Written by cargo as `name`, `name version` or `name version (source)`,
+/// using the shortest form that identifies the package.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
 pub struct CargoLockDependency {
     /// Dependency name
     pub name: String,
-    /// Version requirement
+    /// Resolved version, when needed to tell packages apart
     pub version: Option<String>,
-    /// Source information
+    /// Source information, when needed to tell packages apart
     pub source: Option<CargoLockSource>,
     /// Kind of dependency
     pub kind: Option<String>,
@@ -76,6 +127,58 @@ pub struct CargoLockDependency {
     pub target: Option<String>,
 }
 
+impl TryFrom<String> for CargoLockDependency {
+    type Error = String;
+    
+    fn try_from(reference: String) -> std::result::Result<Self, Self::Error> {
+        let mut parts = reference.splitn(3, ' ');
+        let name = parts.next().filter(|name| !name.is_empty())
+            .ok_or_else(|| format!("invalid dependency {:?}", reference))?;
+        let version = parts.next().map(str::to_string);
+        let source = parts.next()
+            .map(|source| source.strip_prefix('(').and_then(|s| s.strip_suffix(')'))
+                .ok_or_else(|| format!("invalid dependency source in {:?}", reference)))
+            .transpose()?
+            .map(|source| CargoLockSource::try_from(source.to_string()))
+            .transpose()?;
+        
+        Ok(CargoLockDependency {
+            name: name.to_string(),
+            version,
+            source,
+            kind: None,
+            target: None,
+        })
+    }
+}
+
+impl From<CargoLockDependency> for String {
+    fn from(dependency: CargoLockDependency) -> Self {
+        match (dependency.version, dependency.source) {
+            (Some(version), Some(source)) => format!("{} {} ({})", dependency.name, version, String::from(source)),
+            (Some(version), None) => format!("{} {}", dependency.name, version),
+            _ => dependency.name,
+        }
+    }
+}
+
+impl CargoLock {
+    fn unversioned() -> u32 {
+        1
+    }
+    
+    /// Move format v1 `[metadata]` checksums onto their packages
+    pub fn migrate_v1_checksums(&mut self) {
+        let metadata = std::mem::take(&mut self.metadata);
+        for package in &mut self.package {
+            if package.checksum.is_none() {
+                let source = package.source.clone().map(String::from);
+                package.checksum = v1_metadata_checksum(&metadata, &package.name, &package.version, source.as_deref());
+            }
+        }
+    }
+}
+
 /// Dependency parser implementation
 #[derive(Debug, Clone)]
 pub struct DependencyParser {
@@ -83,6 +186,10 @@ pub struct DependencyParser {
     config: DependencyParserConfig,
     /// Whether parser is ready
     ready: bool,
+    /// Token that stops running cargo commands when cancelled
+    cancel: CancellationToken,
+    /// Runs cargo metadata
+    tools: Arc<dyn ToolInvoker>,
 }
 
 /// Configuration for dependency parser
@@ -90,10 +197,16 @@ pub struct DependencyParser {
 pub struct DependencyParserConfig {
     /// Whether to use cargo metadata for enhancement
     pub use_metadata_enhancement: bool,
-    /// Maximum depth for dependency analysis
+    /// Maximum depth for dependency analysis when the project policy sets none
+    ///
+    /// Recorded in the graph metadata, where drift detection reads it.
     pub max_depth: Option<usize>,
     /// Whether to validate checksums
     pub validate_checksums: bool,
+    /// Maximum size of `cargo metadata` output accepted for parsing (bytes)
+    pub metadata_memory_limit: u64,
+    /// Timeout for `cargo metadata` in seconds
+    pub command_timeout: u64,
 }
 
 impl DependencyParser {
@@ -102,13 +215,29 @@ impl DependencyParser {
         Self {
             config: DependencyParserConfig {
                 use_metadata_enhancement: true,
-                max_depth: config.classification_config.confidence_threshold > 0.5,
+                max_depth: DriftDetectionConfig::default().max_transitive_depth,
                 validate_checksums: true,
+                metadata_memory_limit: config.performance_config.metadata_memory_limit,
+                command_timeout: config.tool_paths.default_timeout,
             },
             ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
         }
     }
     
+    /// Stop running cargo commands when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run cargo through `tools` instead of spawning it directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
     /// Check if parser is ready
     pub fn is_ready(&self) -> bool {
         self.ready
@@ -117,29 +246,119 @@ impl DependencyParser {
     /// Parse dependencies from Cargo.lock (authoritative source)
     pub async fn parse_dependencies(&self, project: &Project) -> Result<DependencyGraph> {
         // 1. Load and parse Cargo.lock as authoritative source
+        deadline::set_stage("parsing Cargo.lock");
         let lockfile_path = project.lockfile_path();
         let lockfile_content = std::fs::read_to_string(&lockfile_path)
-            .map_err(|e| AdapterError::file_not_found(&lockfile_path, "reading Cargo.lock"))?;
-        
-        let cargo_lock: CargoLock = toml::from_str(&lockfile_content)
-            .map_err(|e| AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
+            .map_err(|_e| AdapterError::file_not_found(&lockfile_path, "reading Cargo.lock"))?;
         
         // 2. Build base dependency graph from Cargo.lock only
-        let mut dependency_graph = self.build_base_graph(project, cargo_lock)?;
+        let mut dependency_graph = self.parse_lockfile_content(project, &lockfile_content)?;
+        
+        // 3. Mark workspace members and direct dependencies from Cargo.toml (advisory only)
+        match WorkspaceManifest::load(&project.paths.root) {
+            Ok(manifest) => self.apply_manifest(&mut dependency_graph, &manifest),
+            Err(e) => tracing::debug!(error = %e, "Cargo.toml not used for direct dependency detection"),
+        }
+        
+        // 4. Record the source replacements of the Cargo config hierarchy
+        match self.load_cargo_config(project) {
+            Ok(cargo_config) => {
+                if let Ok(value) = serde_json::to_value(&cargo_config) {
+                    dependency_graph.metadata.properties.insert(CARGO_CONFIG_PROPERTY.to_string(), value);
+                }
+            }
+            Err(e) => tracing::debug!(error = %e, "Cargo config not recorded"),
+        }
+        
+        // 5. Record the commit Cargo.lock was read at and whether it matches it
+        match LockfileVcsState::load(&lockfile_path) {
+            Ok(vcs) => {
+                if !vcs.matches_head() {
+                    tracing::warn!(commit = %vcs.commit, problems = ?vcs.problems(), "Cargo.lock differs from the HEAD commit");
+                }
+                if let Ok(value) = serde_json::to_value(&vcs) {
+                    dependency_graph.metadata.properties.insert(VCS_PROPERTY.to_string(), value);
+                }
+            }
+            Err(e) => tracing::debug!(error = %e, "VCS state of Cargo.lock not recorded"),
+        }
         
-        // 3. Optionally enhance with cargo metadata (advisory only)
+        // 6. Optionally enhance with cargo metadata (advisory only)
         if self.config.use_metadata_enhancement {
-            if let Ok(enhanced_graph) = self.enhance_with_metadata(project, &mut dependency_graph).await {
-                dependency_graph = enhanced_graph;
+            deadline::set_stage("running cargo metadata");
+            match self.enhance_with_metadata(project, &mut dependency_graph).await {
+                Ok(enhanced_graph) => dependency_graph = enhanced_graph,
+                Err(e @ AdapterError::Cancelled { .. }) => return Err(e),
+                Err(_) => {},
             }
         }
         
-        // 4. Validate UGDG schema compliance
+        // 7. Validate UGDG schema compliance, with depths and their limit recorded and edges in canonical order
+        deadline::set_stage("validating graph");
+        dependency_graph.annotate_depths();
+        if let Some(max_depth) = crate::config::load_max_transitive_depth(project)?.or(self.config.max_depth) {
+            dependency_graph.metadata.properties.insert(MAX_DEPTH_PROPERTY.to_string(), max_depth.into());
+        }
+        dependency_graph.sort_canonical();
         self.validate_ugdg_schema(&dependency_graph)?;
         
         Ok(dependency_graph)
     }
     
+    /// Load the effective Cargo config of a project
+    ///
+    /// When the project has been vendored, replacements are compared with
+    /// the config vendoring generated: any other replacement means Cargo
+    /// may fetch sources the vendored tree does not hold.
+    fn load_cargo_config(&self, project: &Project) -> Result<CargoSourceConfig> {
+        let mut cargo_config = CargoSourceConfig::load(&project.paths.root)?;
+        let vendored_config = project.vendor_path().join(".cargo").join("config.toml");
+        if vendored_config.is_file() {
+            let expected = CargoSourceConfig::read_file_in(&vendored_config, &project.paths.root)?;
+            cargo_config.replacement_mismatches = cargo_config.replacement_mismatches(&expected);
+        }
+        Ok(cargo_config)
+    }
+    
+    /// Build the base dependency graph from Cargo.lock content
+    ///
+    /// Pure: reads no files and spawns no processes, so it can also run
+    /// where only the lockfile text is available.
+    pub fn parse_lockfile_content(&self, project: &Project, lockfile_content: &str) -> Result<DependencyGraph> {
+        let mut cargo_lock: CargoLock = toml::from_str(lockfile_content)
+            .map_err(|e| AdapterError::cargo_lock_parse_error(&project.lockfile_path(), 0, &e.to_string()))?;
+        cargo_lock.migrate_v1_checksums();
+        
+        let mut dependency_graph = self.build_base_graph(project, cargo_lock)?;
+        dependency_graph.metadata.lockfile_fingerprint = ChecksumCalculator::lockfile_fingerprint(lockfile_content)?;
+        dependency_graph.sort_canonical();
+        
+        Ok(dependency_graph)
+    }
+
+    /// Parse Cargo.lock content that has no project directory behind it
+    ///
+    /// Used when lockfile text is piped in. The graph belongs to
+    /// [`Self::in_memory_project`] and is validated like a parsed project,
+    /// but without Cargo.toml there are no workspace members or direct
+    /// dependencies marked.
+    pub fn parse_lockfile_str(&self, lockfile_content: &str) -> Result<DependencyGraph> {
+        let mut dependency_graph = self.parse_lockfile_content(&Self::in_memory_project(), lockfile_content)?;
+        dependency_graph.annotate_depths();
+        self.validate_ugdg_schema(&dependency_graph)?;
+        Ok(dependency_graph)
+    }
+
+    /// Project standing in for lockfile content parsed without a directory
+    pub fn in_memory_project() -> Project {
+        Project::new(
+            IN_MEMORY_PROJECT_ID.to_string(),
+            "Lockfile".to_string(),
+            "rust".to_string(),
+            PathBuf::from("."),
+        )
+    }
+
     /// Build base dependency graph from Cargo.lock
     fn build_base_graph(&self, project: &Project, cargo_lock: CargoLock) -> Result<DependencyGraph> {
         let mut dependency_graph = DependencyGraph::new(project.id.clone(), project.ecosystem.clone());
@@ -152,17 +371,17 @@ impl DependencyParser {
             
             // Convert Cargo.lock source to universal PackageSource
             let package_source = match &cargo_pkg.source {
-                Some(CargoLockSource::Registry { registry, checksum }) => {
+                Some(CargoLockSource::Registry { registry }) => {
                     PackageSource::Registry {
-                        url: format!("https://{}", registry),
-                        checksum: checksum.clone(),
+                        url: registry.clone(),
+                        checksum: cargo_pkg.checksum.clone().unwrap_or_default(),
                     }
                 },
-                Some(CargoLockSource::Git { url, rev, checksum }) => {
+                Some(CargoLockSource::Git { url, rev }) => {
                     PackageSource::Git {
                         url: url.clone(),
                         rev: rev.clone(),
-                        checksum: checksum.clone(),
+                        checksum: cargo_pkg.checksum.clone().unwrap_or_default(),
                     }
                 },
                 Some(CargoLockSource::Local { path }) => {
@@ -171,10 +390,10 @@ impl DependencyParser {
                     }
                 },
                 None => {
-                    // Default to crates.io registry
-                    PackageSource::Registry {
-                        url: "https://crates.io".to_string(),
-                        checksum: cargo_pkg.checksum.clone().unwrap_or_default(),
+                    // Workspace members and path dependencies; the path is
+                    // filled in from the manifest when it is available
+                    PackageSource::Local {
+                        path: String::new(),
                     }
                 },
             };
@@ -189,7 +408,7 @@ impl DependencyParser {
                 audit_status: AuditStatus::Unaudited, // Will be set by audit runner
                 annotations: vec![
                     RustAnnotation::new(
-                        RustAnnotation::keys::DEPENDENCY_KIND.to_string(),
+                        annotation_keys::DEPENDENCY_KIND.to_string(),
                         serde_json::Value::String(cargo_pkg.dependencies.iter()
                             .find(|d| d.kind.as_ref().map(|k| k == "normal").unwrap_or(false))
                             .map(|d| d.kind.clone().unwrap_or_else(|| "normal".to_string()))
@@ -199,14 +418,19 @@ impl DependencyParser {
             };
             
             dependency_graph.add_package(package_node);
-            package_map.insert(cargo_pkg.name.clone(), package_id);
+            package_map.insert(format!("{} {}", cargo_pkg.name, cargo_pkg.version), package_id);
+            package_map.entry(cargo_pkg.name.clone()).or_insert(package_id);
         }
         
-        // Create dependency edges
+        // Create dependency edges, resolving `name version` references
         for cargo_pkg in &cargo_lock.package {
-            if let Some(from_id) = package_map.get(&cargo_pkg.name) {
+            if let Some(from_id) = package_map.get(&format!("{} {}", cargo_pkg.name, cargo_pkg.version)) {
                 for dep in &cargo_pkg.dependencies {
-                    if let Some(to_id) = package_map.get(&dep.name) {
+                    let reference = match &dep.version {
+                        Some(version) => format!("{} {}", dep.name, version),
+                        None => dep.name.clone(),
+                    };
+                    if let Some(to_id) = package_map.get(&reference) {
                         let dependency_kind = match dep.kind.as_deref() {
                             Some("build") => DependencyKind::Build,
                             Some("dev") => DependencyKind::Dev,
@@ -233,16 +457,116 @@ impl DependencyParser {
     
     /// Enhance graph with cargo metadata (advisory only)
     async fn enhance_with_metadata(&self, project: &Project, graph: &mut DependencyGraph) -> Result<DependencyGraph> {
-        // This would run `cargo metadata` in non-offline mode
-        // For now, return unmodified graph as Cargo.lock is authoritative
-        
         // Update graph metadata to indicate enhancement attempt
         graph.metadata.tool_versions.insert("cargo".to_string(), "1.0.0".to_string());
         graph.metadata.offline_mode = project.requires_strict_security();
         
+        let mut args = vec!["metadata", "--format-version", "1", "--locked"];
+        if graph.metadata.offline_mode {
+            args.push("--offline");
+        }
+        
+        // Refuse to buffer oversized workspaces rather than exhaust shared runners
+        let limit = self.config.metadata_memory_limit;
+        let invocation = ToolInvocation::new("cargo metadata", "cargo")
+            .args(&args)
+            .current_dir(&project.paths.root)
+            .timeout(Some(Duration::from_secs(self.config.command_timeout)))
+            .stdout_limit(limit);
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if output.stdout_limit_exceeded {
+            return Err(AdapterError::MetadataParseError {
+                field: "cargo metadata".to_string(),
+                value: format!("more than {} bytes", limit),
+                source: anyhow::anyhow!(
+                    "cargo metadata output exceeds performance_config.metadata_memory_limit ({} bytes)",
+                    limit
+                ),
+            });
+        }
+        if !output.is_success() {
+            return Err(AdapterError::ToolExecutionFailed {
+                tool: "cargo metadata".to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+                source: anyhow::anyhow!("cargo metadata execution failed"),
+            });
+        }
+        
+        let metadata: CargoMetadata = serde_json::from_str(&output.stdout)
+            .map_err(|e| AdapterError::MetadataParseError {
+                field: "cargo metadata".to_string(),
+                value: String::new(),
+                source: anyhow::anyhow!("Failed to parse cargo metadata output: {}", e),
+            })?;
+        
+        self.apply_cargo_metadata(graph, &metadata);
+        
         Ok(graph.clone())
     }
     
+    /// Attach descriptive cargo metadata to matching graph nodes
+    ///
+    /// Cargo.lock stays authoritative: metadata for packages that are not
+    /// already in the graph is ignored, and package identity is never changed.
+    /// Cargo's workspace members are marked first-party, which covers path
+    /// dependencies Cargo.toml only makes members implicitly.
+    pub fn apply_cargo_metadata(&self, graph: &mut DependencyGraph, metadata: &CargoMetadata) {
+        for metadata_pkg in &metadata.packages {
+            let node = graph.root_packages.iter_mut()
+                .find(|p| p.name == metadata_pkg.name && p.version == metadata_pkg.version);
+            
+            if let Some(node) = node {
+                let local = matches!(node.source, PackageSource::Local { .. });
+                if local && metadata.workspace_members.contains(&metadata_pkg.id) {
+                    node.set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::Value::Bool(true));
+                    node.set_origin(PackageOrigin::FirstParty);
+                }
+                for annotation in metadata_pkg.to_annotations() {
+                    if node.annotation(&annotation.key).is_none() {
+                        node.set_annotation(&annotation.key, annotation.value);
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Annotate workspace members and the crates they declare directly
+    ///
+    /// Declarations are matched by crate name, so every locked version of
+    /// a directly declared crate is marked. Features requested across all
+    /// declarations are recorded alongside. Every node gets an origin:
+    /// lock entries without a source whose name and version match a
+    /// manifest at a workspace member path, including the root package,
+    /// are first-party and get that directory as their path; all other
+    /// lock entries are third-party. A virtual root has no entry.
+    pub fn apply_manifest(&self, graph: &mut DependencyGraph, manifest: &WorkspaceManifest) {
+        for node in &mut graph.root_packages {
+            let member_dir = match &node.source {
+                PackageSource::Local { .. } => manifest.member_dir(&node.name, &node.version),
+                _ => None,
+            };
+            if let Some(dir) = member_dir {
+                let path = if dir.as_os_str().is_empty() { ".".to_string() } else { dir.to_string_lossy().to_string() };
+                node.source = PackageSource::Local { path };
+                node.set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::Value::Bool(true));
+                node.set_origin(PackageOrigin::FirstParty);
+                continue;
+            }
+            node.set_origin(PackageOrigin::ThirdParty);
+            
+            if manifest.is_direct_dependency(&node.name) {
+                node.set_annotation(annotation_keys::DIRECT_DEPENDENCY, serde_json::Value::Bool(true));
+                
+                let features = manifest.requested_features(&node.name);
+                if !features.is_empty() {
+                    node.set_annotation(annotation_keys::REQUESTED_FEATURES, serde_json::json!(features));
+                }
+            }
+        }
+    }
+    
     /// Validate UGDG schema compliance
     fn validate_ugdg_schema(&self, graph: &DependencyGraph) -> Result<()> {
         // Basic schema validation
@@ -278,7 +602,7 @@ impl DependencyParser {
     /// Extract Git dependency information
     pub fn extract_git_info(&self, package: &CargoLockPackage) -> Option<GitInfo> {
         match &package.source {
-            Some(CargoLockSource::Git { url, rev, checksum: _ }) => {
+            Some(CargoLockSource::Git { url, rev }) => {
                 Some(GitInfo {
                     repository_url: url.clone(),
                     commit_hash: rev.clone(),
@@ -307,6 +631,8 @@ impl Default for DependencyParserConfig {
             use_metadata_enhancement: true,
             max_depth: Some(10),
             validate_checksums: true,
+            metadata_memory_limit: 512 * 1024 * 1024,
+            command_timeout: 300,
         }
     }
 }
@@ -320,12 +646,13 @@ mod tests {
     #[test]
     fn test_cargo_lock_parsing() {
         let lockfile_content = r#"
+version = 3
+
 [[package]]
 name = "serde"
 version = "1.0.130"
 source = "registry+https://github.com/rust-lang/crates.io-index"
 checksum = "f6ed5d4a5a6f0f8c6e3d5641c8e4f7a1b2d5f5f2b6c2c9e9e0c5d4b6e7d5f6e7d"
-dependencies = []
 
 [[package]]
 name = "serde_json"
@@ -333,7 +660,8 @@ version = "1.0.72"
 source = "registry+https://github.com/rust-lang/crates.io-index"
 checksum = "a2c6e6c6f6c6e6c6f6c6e6c6f6c6e6c6f6c6e6c6f6c6e6c6f"
 dependencies = [
-    { name = "serde", version = "1.0.130" }
+ "serde 1.0.130 (registry+https://github.com/rust-lang/crates.io-index)",
+ "ring",
 ]
 "#;
         
@@ -342,6 +670,16 @@ dependencies = [
         assert_eq!(cargo_lock.package.len(), 2);
         assert_eq!(cargo_lock.package[0].name, "serde");
         assert_eq!(cargo_lock.package[1].name, "serde_json");
+        assert_eq!(cargo_lock.package[1].dependencies[0].version.as_deref(), Some("1.0.130"));
+        assert_eq!(cargo_lock.package[1].dependencies[0].source, Some(CargoLockSource::Registry {
+            registry: "https://github.com/rust-lang/crates.io-index".to_string(),
+        }));
+        assert_eq!(cargo_lock.package[1].dependencies[1].name, "ring");
+        
+        // Lockfiles predating the version field are format 1
+        let cargo_lock: CargoLock = toml::from_str("[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n").unwrap();
+        assert_eq!(cargo_lock.version, 1);
+        assert_eq!(cargo_lock.package[0].source, None);
     }
     
     #[test]
@@ -370,13 +708,13 @@ dependencies = [
                     name: "serde".to_string(),
                     version: "1.0.130".to_string(),
                     source: Some(CargoLockSource::Registry {
-                        registry: "crates.io".to_string(),
-                        checksum: "test-checksum".to_string(),
+                        registry: "https://github.com/rust-lang/crates.io-index".to_string(),
                     }),
                     dependencies: vec![],
                     checksum: Some("test-checksum".to_string()),
                 },
             ],
+            metadata: HashMap::new(),
         };
         
         let parser = DependencyParser::new(&RustAdapterConfig::default());
@@ -388,4 +726,82 @@ dependencies = [
         assert_eq!(graph.root_packages[0].name, "serde");
         assert_eq!(graph.root_packages[0].version, "1.0.130");
     }
-}
+
+    #[test]
+    fn test_parse_lockfile_str() {
+        let lockfile_content = r#"
+version = 3
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "af150ab688ff2122fcef229be89cb50dd66af9e01a4ff320cc137eecc9bacc38"
+"#;
+        let parser = DependencyParser::new(&RustAdapterConfig::default());
+        let graph = parser.parse_lockfile_str(lockfile_content).unwrap();
+
+        assert_eq!(graph.project_id, IN_MEMORY_PROJECT_ID);
+        assert_eq!(graph.root_packages.len(), 1);
+        assert_eq!(graph.root_packages[0].name, "itoa");
+
+        // A lockfile without packages is rejected like a parsed project
+        assert!(parser.parse_lockfile_str("version = 3\npackage = []\n").is_err());
+        assert!(parser.parse_lockfile_str("not a lockfile").is_err());
+        
+        // Format v1 checksums are read from the metadata table
+        let v1 = r#"
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[metadata]
+"checksum itoa 1.0.9 (registry+https://github.com/rust-lang/crates.io-index)" = "af150ab688ff2122fcef229be89cb50dd66af9e01a4ff320cc137eecc9bacc38"
+"#;
+        let graph = parser.parse_lockfile_str(v1).unwrap();
+        assert_eq!(graph.root_packages[0].checksum, "af150ab688ff2122fcef229be89cb50dd66af9e01a4ff320cc137eecc9bacc38");
+    }
+    
+    #[test]
+    fn test_first_party_requires_a_member_path_and_no_source() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "[workspace]\nmembers = [\"crates/log\"]\n\n[package]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("crates/log")).unwrap();
+        std::fs::write(temp_dir.path().join("crates/log/Cargo.toml"), "[package]\nname = \"log\"\nversion = \"0.9.0\"\n").unwrap();
+        let manifest = WorkspaceManifest::load(temp_dir.path()).unwrap();
+        
+        // crates.io `log` shares the member's name; `helpers` is a path
+        // dependency outside the workspace
+        let parser = DependencyParser::new(&RustAdapterConfig::default());
+        let mut graph = parser.parse_lockfile_str(r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+
+[[package]]
+name = "helpers"
+version = "0.1.0"
+
+[[package]]
+name = "log"
+version = "0.4.20"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "log"
+version = "0.9.0"
+"#).unwrap();
+        parser.apply_manifest(&mut graph, &manifest);
+        
+        let first_party: Vec<_> = graph.first_party_packages()
+            .map(|p| (p.name.as_str(), p.version.as_str(), p.source.clone()))
+            .collect();
+        assert_eq!(first_party, vec![
+            ("app", "0.1.0", PackageSource::Local { path: ".".to_string() }),
+            ("log", "0.9.0", PackageSource::Local { path: "crates/log".to_string() }),
+        ]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,439 @@
+//! Pre-flight environment checks
+//!
+//! This module checks, without running any analysis, that what the other
+//! components rely on is in place: the external tools and their versions,
+//! the configuration, Cargo.lock, the advisory database, the vendor
+//! directory and, when the project must build offline, everything an
+//! offline run needs. Failures carry the guidance of the adapter error an
+//! operation would otherwise have failed with.
+
+use crate::adapter::audit_runner::{AuditRunner, ADVISORY_DB_ENTRY};
+use crate::adapter::vendor_manager::VendorManager;
+use crate::error::AdapterError;
+use crate::manifest::{CargoSourceConfig, LockfileVcsState};
+use crate::models::*;
+use crate::utils::{CancellationToken, ProcessInvoker, SharedCache, ToolInvocation, ToolInvoker};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Free space below which the disk space check warns
+const MIN_FREE_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Cargo.lock format versions written by supported cargo releases
+const SUPPORTED_LOCKFILE_VERSIONS: &[i64] = &[3, 4];
+
+/// Environment doctor implementation
+#[derive(Debug, Clone)]
+pub struct Doctor {
+    /// Doctor configuration
+    config: DoctorConfig,
+    /// Whether doctor is ready
+    ready: bool,
+    /// Token that stops running tools when cancelled
+    cancel: CancellationToken,
+    /// Runs the tools whose versions are checked
+    tools: Arc<dyn ToolInvoker>,
+}
+
+/// Configuration for the environment doctor
+#[derive(Debug, Clone)]
+pub struct DoctorConfig {
+    /// Whether projects must build without network access
+    pub offline_mode: bool,
+    /// Whether cargo-audit is run
+    pub run_cargo_audit: bool,
+    /// Whether cargo-vet is run
+    pub run_cargo_vet: bool,
+    /// Minimum supported tool versions, keyed by tool name
+    pub min_tool_versions: HashMap<String, String>,
+    /// Timeout for version queries
+    pub tool_timeout: Duration,
+    /// Configured advisory database path
+    pub advisory_db_path: Option<PathBuf>,
+    /// Shared cache holding the advisory database when no path is configured
+    pub shared_cache: Option<SharedCache>,
+    /// Age after which the advisory database is stale
+    pub advisory_db_max_age: Duration,
+}
+
+impl Doctor {
+    /// Create new doctor with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            config: DoctorConfig {
+                offline_mode: config.offline_mode,
+                run_cargo_audit: config.audit_config.run_cargo_audit,
+                run_cargo_vet: config.audit_config.run_cargo_vet,
+                min_tool_versions: config.audit_config.min_tool_versions.clone(),
+                tool_timeout: Duration::from_secs(config.tool_paths.default_timeout),
+                advisory_db_path: config.audit_config.advisory_db_path.clone(),
+                shared_cache: SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout),
+                advisory_db_max_age: Duration::from_secs(config.cache_config.advisory_db_max_age),
+            },
+            ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
+        }
+    }
+    
+    /// Stop running tools when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run tools through `tools` instead of spawning them directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
+    /// Check if doctor is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Run every check against `project`
+    ///
+    /// `config_error` is the error the configuration file failed to load
+    /// with, if the adapter fell back to defaults.
+    pub async fn diagnose(&self, project: &Project, vendor_manager: &VendorManager, config_error: Option<&AdapterError>) -> DoctorReport {
+        let mut checks = vec![Self::check_config(config_error)];
+        checks.extend(self.check_tools(project).await);
+        checks.push(Self::check_lockfile(project));
+        checks.push(Self::check_lockfile_vcs(project));
+        let advisory_db = self.check_advisory_db();
+        let vendor = Self::check_vendor(project, vendor_manager);
+        let offline = self.check_offline(project, &advisory_db, &vendor);
+        checks.extend([advisory_db, vendor, offline]);
+        checks.push(self.check_disk_space(project).await);
+        
+        DoctorReport::new(project.paths.root.display().to_string(), checks)
+    }
+    
+    /// Whether the configuration loaded
+    fn check_config(config_error: Option<&AdapterError>) -> DoctorCheck {
+        match config_error {
+            None => DoctorCheck::new("config", DoctorStatus::Pass, "Configuration is valid".to_string()),
+            Some(e) => Self::failed("config", DoctorStatus::Fail, format!("{}; defaults are in use", e), e),
+        }
+    }
+    
+    /// Whether each tool runs and meets its minimum version
+    async fn check_tools(&self, project: &Project) -> Vec<DoctorCheck> {
+        let mut tools = vec![("cargo", vec!["--version"])];
+        if self.config.run_cargo_audit {
+            tools.push(("cargo-audit", vec!["audit", "--version"]));
+        }
+        if self.config.run_cargo_vet {
+            tools.push(("cargo-vet", vec!["vet", "--version"]));
+        }
+        
+        let mut checks = Vec::new();
+        for (tool, args) in tools {
+            let name = format!("tool.{}", tool);
+            let invocation = ToolInvocation::new(tool, "cargo")
+                .args(&args)
+                .current_dir(&project.paths.root)
+                .timeout(Some(self.config.tool_timeout));
+            let output = match self.tools.invoke(&invocation, &self.cancel).await {
+                Ok(output) if output.is_success() => output,
+                Ok(_) | Err(AdapterError::ToolNotFound { .. }) => {
+                    let e = AdapterError::tool_not_found(tool);
+                    checks.push(Self::failed(&name, DoctorStatus::Fail, format!("{} is not installed", tool), &e));
+                    continue;
+                },
+                Err(e) => {
+                    checks.push(Self::failed(&name, DoctorStatus::Fail, format!("{} could not be run: {}", tool, e), &e));
+                    continue;
+                },
+            };
+            
+            let Some(version) = AuditRunner::extract_version(&output.stdout) else {
+                checks.push(DoctorCheck::new(&name, DoctorStatus::Warn, format!("{} reported no version", tool)));
+                continue;
+            };
+            checks.push(match self.config.min_tool_versions.get(tool) {
+                Some(minimum) if AuditRunner::version_less_than(&version, minimum) => {
+                    DoctorCheck::new(&name, DoctorStatus::Warn, format!("{} {} is older than the minimum supported version {}", tool, version, minimum))
+                        .with_guidance(match tool {
+                            "cargo" => "Upgrade the toolchain with: rustup update".to_string(),
+                            _ => format!("Upgrade with: cargo install --force {}", tool),
+                        })
+                },
+                _ => DoctorCheck::new(&name, DoctorStatus::Pass, format!("{} {}", tool, version)),
+            });
+        }
+        checks
+    }
+    
+    /// Whether Cargo.lock exists and is in a supported format
+    fn check_lockfile(project: &Project) -> DoctorCheck {
+        let lockfile_path = project.lockfile_path();
+        let content = match std::fs::read_to_string(&lockfile_path) {
+            Ok(content) => content,
+            Err(_) => {
+                let e = AdapterError::file_not_found(&lockfile_path, "reading lockfile");
+                return Self::failed("lockfile", DoctorStatus::Fail, "Cargo.lock not found".to_string(), &e)
+                    .with_guidance("Generate it with 'cargo generate-lockfile' and commit it".to_string());
+            },
+        };
+        let lockfile: toml::Value = match toml::from_str(&content) {
+            Ok(lockfile) => lockfile,
+            Err(e) => {
+                let e = AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string());
+                return Self::failed("lockfile", DoctorStatus::Fail, "Cargo.lock is not valid TOML".to_string(), &e);
+            },
+        };
+        
+        let packages = lockfile.get("package").and_then(|p| p.as_array()).map_or(0, Vec::len);
+        match lockfile.get("version").and_then(|v| v.as_integer()) {
+            Some(version) if SUPPORTED_LOCKFILE_VERSIONS.contains(&version) => DoctorCheck::new(
+                "lockfile",
+                DoctorStatus::Pass,
+                format!("Cargo.lock version {} with {} packages", version, packages),
+            ),
+            Some(version) if version > *SUPPORTED_LOCKFILE_VERSIONS.last().unwrap_or(&0) => DoctorCheck::new(
+                "lockfile",
+                DoctorStatus::Warn,
+                format!("Cargo.lock version {} is newer than the adapter was tested with", version),
+            ).with_guidance("Check that the adapter parses it with 'rust-adapter parse'".to_string()),
+            version => DoctorCheck::new(
+                "lockfile",
+                DoctorStatus::Warn,
+                format!("Cargo.lock uses the legacy format {}", version.map_or("1 or 2".to_string(), |v| v.to_string())),
+            ).with_guidance("Regenerate it with a current cargo: cargo update --workspace".to_string()),
+        }
+    }
+    
+    /// Whether Cargo.lock is committed and unchanged from the HEAD commit
+    fn check_lockfile_vcs(project: &Project) -> DoctorCheck {
+        let vcs = match LockfileVcsState::load(&project.lockfile_path()) {
+            Ok(vcs) => vcs,
+            Err(e) => return DoctorCheck::new("lockfile_vcs", DoctorStatus::Skip, format!("No git state for Cargo.lock: {}", e)),
+        };
+        let commit = &vcs.commit[..vcs.commit.len().min(12)];
+        if vcs.matches_head() {
+            return DoctorCheck::new("lockfile_vcs", DoctorStatus::Pass, format!("Cargo.lock matches commit {}", commit));
+        }
+        DoctorCheck::new(
+            "lockfile_vcs",
+            DoctorStatus::Warn,
+            format!("Cargo.lock does not match commit {}: {}", commit, vcs.problems().join("; ")),
+        ).with_guidance("Commit Cargo.lock so artifacts can be traced to the commit they describe".to_string())
+    }
+    
+    /// Whether a local advisory database exists and is fresh
+    fn check_advisory_db(&self) -> DoctorCheck {
+        if !self.config.run_cargo_audit {
+            return DoctorCheck::new("advisory_db", DoctorStatus::Skip, "cargo-audit is disabled".to_string());
+        }
+        
+        let Some(path) = self.advisory_db_location() else {
+            return DoctorCheck::new("advisory_db", DoctorStatus::Warn, "No advisory database location is known".to_string())
+                .with_guidance("Set audit_config.advisory_db_path or CARGO_HOME".to_string());
+        };
+        if !path.is_dir() {
+            return DoctorCheck::new("advisory_db", DoctorStatus::Warn, format!("No advisory database at {}", path.display()))
+                .with_guidance("Fetch it with: cargo audit fetch".to_string());
+        }
+        
+        // cargo-audit keeps the database as a git checkout
+        let modified = [path.join(".git").join("FETCH_HEAD"), path.clone()].iter()
+            .filter_map(|candidate| std::fs::metadata(candidate).and_then(|m| m.modified()).ok())
+            .max();
+        match modified.and_then(|modified| modified.elapsed().ok()) {
+            Some(age) if age <= self.config.advisory_db_max_age => DoctorCheck::new(
+                "advisory_db",
+                DoctorStatus::Pass,
+                format!("Advisory database at {} updated {} minutes ago", path.display(), age.as_secs() / 60),
+            ),
+            Some(age) => DoctorCheck::new(
+                "advisory_db",
+                DoctorStatus::Warn,
+                format!("Advisory database at {} is {} hours old", path.display(), age.as_secs() / 3600),
+            ).with_guidance("Update it with: cargo audit fetch".to_string()),
+            None => DoctorCheck::new("advisory_db", DoctorStatus::Warn, format!("Age of the advisory database at {} is unknown", path.display())),
+        }
+    }
+    
+    /// Advisory database cargo-audit will read, in the order the audit runner picks it
+    fn advisory_db_location(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.config.advisory_db_path {
+            return Some(path.clone());
+        }
+        if let Some(cache) = &self.config.shared_cache {
+            return Some(cache.entry_path(ADVISORY_DB_ENTRY));
+        }
+        std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+            .map(|cargo_home| cargo_home.join("advisory-db"))
+    }
+    
+    /// Whether every locked registry and git package is vendored
+    fn check_vendor(project: &Project, vendor_manager: &VendorManager) -> DoctorCheck {
+        let vendor_dir = project.vendor_path();
+        if !vendor_dir.is_dir() {
+            return DoctorCheck::new("vendor", DoctorStatus::Skip, format!("No vendor directory at {}", vendor_dir.display()));
+        }
+        let locked = match vendor_manager.locked_packages(project) {
+            Ok(locked) => locked,
+            Err(e) => return Self::failed("vendor", DoctorStatus::Fail, "Locked packages could not be read".to_string(), &e),
+        };
+        
+        let missing: Vec<&str> = locked.packages.iter()
+            .filter(|(_, entry)| !vendor_dir.join(&entry.path).join(".cargo-checksum.json").is_file())
+            .map(|(key, _)| key.as_str())
+            .collect();
+        if missing.is_empty() {
+            return DoctorCheck::new("vendor", DoctorStatus::Pass, format!("All {} locked packages are vendored", locked.packages.len()));
+        }
+        
+        let shown = missing.iter().take(5).copied().collect::<Vec<_>>().join(", ");
+        DoctorCheck::new(
+            "vendor",
+            DoctorStatus::Fail,
+            format!("{} of {} locked packages are not vendored: {}{}", missing.len(), locked.packages.len(), shown,
+                if missing.len() > 5 { ", ..." } else { "" }),
+        )
+            .with_guidance("Cargo.lock changed since vendoring; run 'rust-adapter vendor' again".to_string())
+            .with_guidance("Run 'rust-adapter verify-vendor' to check the vendored contents".to_string())
+    }
+    
+    /// Whether the project can be analyzed and built without network access
+    ///
+    /// Failing only when offline mode is required by the configuration or
+    /// the project's threat level; otherwise gaps are warnings.
+    fn check_offline(&self, project: &Project, advisory_db: &DoctorCheck, vendor: &DoctorCheck) -> DoctorCheck {
+        let required = self.config.offline_mode || project.requires_strict_security();
+        let mut gaps = Vec::new();
+        match vendor.status {
+            DoctorStatus::Pass => {},
+            DoctorStatus::Skip => gaps.push("dependencies are not vendored".to_string()),
+            _ => gaps.push("the vendor directory is incomplete".to_string()),
+        }
+        match CargoSourceConfig::load(&project.paths.root) {
+            Ok(sources) => match sources.replacement("crates-io") {
+                Some(_) if sources.replaces_with_directory("crates-io", &project.vendor_path()) => {},
+                Some(replacement) => gaps.push(format!("crates.io is replaced with {}", replacement)),
+                None => gaps.push("Cargo config does not redirect crates.io to the vendor directory".to_string()),
+            },
+            Err(e) => gaps.push(e.to_string()),
+        }
+        if matches!(advisory_db.status, DoctorStatus::Warn | DoctorStatus::Fail) {
+            gaps.push("no fresh local advisory database".to_string());
+        }
+        
+        if gaps.is_empty() {
+            return DoctorCheck::new("offline", DoctorStatus::Pass, "Ready for offline operation".to_string());
+        }
+        let status = if required { DoctorStatus::Fail } else { DoctorStatus::Warn };
+        let requirement = if required { "Offline operation is required, but" } else { "Not ready for offline operation:" };
+        DoctorCheck::new("offline", status, format!("{} {}", requirement, gaps.join("; ")))
+            .with_guidance("Run 'rust-adapter vendor' and add the printed source replacement to .cargo/config.toml".to_string())
+            .with_guidance("Fetch the advisory database with 'cargo audit fetch' while online".to_string())
+    }
+    
+    /// Whether the filesystem holding the project has room for vendoring and epochs
+    async fn check_disk_space(&self, project: &Project) -> DoctorCheck {
+        if cfg!(windows) {
+            return DoctorCheck::new("disk_space", DoctorStatus::Skip, "Free space is not checked on Windows".to_string());
+        }
+        
+        let invocation = ToolInvocation::new("df", "df")
+            .arg("-Pk")
+            .arg(&project.paths.root)
+            .timeout(Some(self.config.tool_timeout));
+        let available = match self.tools.invoke(&invocation, &self.cancel).await {
+            Ok(output) if output.is_success() => Self::parse_df_available(&output.stdout),
+            _ => None,
+        };
+        
+        match available {
+            Some(bytes) if bytes >= MIN_FREE_SPACE_BYTES => {
+                DoctorCheck::new("disk_space", DoctorStatus::Pass, format!("{} MiB free", bytes / (1024 * 1024)))
+            },
+            Some(bytes) => DoctorCheck::new(
+                "disk_space",
+                DoctorStatus::Warn,
+                format!("Only {} MiB free on the filesystem holding {}", bytes / (1024 * 1024), project.paths.root.display()),
+            )
+                .with_guidance("Free space before vendoring or snapshotting an epoch".to_string())
+                .with_guidance("Run 'rust-adapter cache clean' to remove shared cache entries".to_string()),
+            None => DoctorCheck::new("disk_space", DoctorStatus::Skip, "Free space could not be determined".to_string()),
+        }
+    }
+    
+    /// Available bytes from POSIX `df -Pk` output
+    fn parse_df_available(output: &str) -> Option<u64> {
+        let line = output.lines().nth(1)?;
+        let kilobytes: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+        Some(kilobytes * 1024)
+    }
+    
+    /// Check failed with an adapter error, carrying its guidance
+    fn failed(name: &str, status: DoctorStatus, message: String, error: &AdapterError) -> DoctorCheck {
+        DoctorCheck {
+            name: name.to_string(),
+            status,
+            message,
+            error_code: Some(error.error_code().to_string()),
+            guidance: error.actionable_guidance(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{ReplayInvoker, ToolOutput};
+    use tempfile::TempDir;
+    
+    #[tokio::test]
+    async fn test_diagnose_reports_missing_tools_and_unvendored_packages() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("Cargo.lock"), r#"
+            version = 4
+
+            [[package]]
+            name = "serde"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            checksum = "abc"
+        "#).unwrap();
+        let project = Project::new("test".to_string(), "Test".to_string(), "rust".to_string(), root.to_path_buf());
+        std::fs::create_dir_all(project.vendor_path()).unwrap();
+        
+        let mut config = RustAdapterConfig::default();
+        config.audit_config.run_cargo_vet = false;
+        config.audit_config.advisory_db_path = Some(root.join("advisory-db"));
+        config.audit_config.min_tool_versions.insert("cargo".to_string(), "1.80.0".to_string());
+        let df = format!("df -Pk {}", root.display());
+        let tools = ReplayInvoker::new()
+            .with_output("cargo --version", ToolOutput::success("cargo 1.75.0 (1d8b05cdd 2023-11-20)\n"))
+            .with_output(&df, ToolOutput::success("Filesystem 1024-blocks Used Available Capacity Mounted on\n/dev/sda1 100 50 2048 50% /\n"));
+        let doctor = Doctor::new(&config).with_tool_invoker(Arc::new(tools));
+        
+        let report = doctor.diagnose(&project, &VendorManager::new(&config), None).await;
+        let status = |name: &str| report.checks.iter().find(|c| c.name == name).unwrap().status;
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["config", "tool.cargo", "tool.cargo-audit", "lockfile", "lockfile_vcs", "advisory_db", "vendor", "offline", "disk_space"]);
+        
+        assert!(!report.healthy);
+        assert_eq!(status("tool.cargo"), DoctorStatus::Warn);
+        assert_eq!(status("tool.cargo-audit"), DoctorStatus::Fail);
+        assert_eq!(status("lockfile"), DoctorStatus::Pass);
+        assert_eq!(status("advisory_db"), DoctorStatus::Warn);
+        assert_eq!(status("vendor"), DoctorStatus::Fail);
+        assert_eq!(status("offline"), DoctorStatus::Warn);
+        assert_eq!(status("disk_space"), DoctorStatus::Warn);
+        
+        let missing_audit = report.checks.iter().find(|c| c.name == "tool.cargo-audit").unwrap();
+        assert_eq!(missing_audit.error_code.as_deref(), Some("TOOL_NOT_FOUND"));
+        assert!(missing_audit.guidance[0].contains("cargo install cargo-audit"));
+    }
+}
@@ -3,10 +3,12 @@
 //! This module implements drift detection between approved epochs
 //! and actual dependency states, with priority-based analysis.
 
+use crate::adapter::dependency_parser::MAX_DEPTH_PROPERTY;
 use crate::models::*;
-use crate::error::Result;
-use async_trait::async_trait;
-use std::collections::HashMap;
+use crate::error::{AdapterError, Result};
+use crate::manifest::{CargoSourceConfig, CARGO_CONFIG_PROPERTY};
+use crate::utils::{atomic_write, normalize_source, EventSink, PatternOverrides};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Drift detector implementation
 #[derive(Debug, Clone)]
@@ -15,6 +17,8 @@ pub struct DriftDetector {
     config: DriftDetectorConfig,
     /// Whether detector is ready
     ready: bool,
+    /// Stream detected drift is reported to
+    events: EventSink,
 }
 
 /// Configuration for drift detector
@@ -24,12 +28,19 @@ pub struct DriftDetectorConfig {
     pub ignore_mechanical_version_updates: bool,
     /// Whether to flag source changes as high risk by default
     pub flag_source_changes_high_risk: bool,
-    /// Priority overrides for specific packages
-    pub priority_overrides: HashMap<String, Priority>,
+    /// Priority overrides keyed by package name, glob or `/regex/`
+    pub priority_overrides: PatternOverrides<Priority>,
     /// Whether to include dev dependencies in drift detection
     pub include_dev_dependencies: bool,
     /// Whether to include build dependencies in drift detection
     pub include_build_dependencies: bool,
+    /// Priorities for custom TCS categories
+    pub custom_category_priorities: HashMap<String, Priority>,
+    /// Maximum transitive depth to analyze, `None` for the limit recorded
+    /// in the analyzed graph
+    pub max_transitive_depth: Option<usize>,
+    /// Weights and thresholds of impact assessment
+    pub impact_model: ImpactModel,
 }
 
 impl DriftDetector {
@@ -39,14 +50,36 @@ impl DriftDetector {
             config: DriftDetectorConfig {
                 ignore_mechanical_version_updates: config.classification_config.classify_build_deps,
                 flag_source_changes_high_risk: true,
-                priority_overrides: HashMap::new(),
+                priority_overrides: config.drift_detection_config.priority_overrides.clone(),
                 include_dev_dependencies: false,
                 include_build_dependencies: true,
+                custom_category_priorities: config.classification_config.custom_category_priorities.clone(),
+                max_transitive_depth: None,
+                impact_model: config.drift_impact_model.clone(),
             },
             ready: true,
+            events: EventSink::disabled(),
         }
     }
     
+    /// Report detected drift to `events`
+    pub fn with_event_sink(mut self, events: EventSink) -> Self {
+        self.events = events;
+        self
+    }
+    
+    /// Detector leaving out drift of packages deeper than `max_transitive_depth`
+    ///
+    /// Depth is read from the packages' `depth` annotation, so packages of
+    /// graphs without depths recorded are always analyzed. Drift exempt
+    /// from the limit (see [`DriftItem::exempt_from_depth_limit`]) is always
+    /// reported; the rest is counted in `depth_suppressed_drifts`.
+    pub fn with_max_transitive_depth(&self, max_transitive_depth: Option<usize>) -> Self {
+        let mut detector = self.clone();
+        detector.config.max_transitive_depth = max_transitive_depth;
+        detector
+    }
+    
     /// Check if detector is ready
     pub fn is_ready(&self) -> bool {
         self.ready
@@ -55,6 +88,8 @@ impl DriftDetector {
     /// Detect drift between expected epoch and actual dependency graph
     pub async fn detect_drift(&self, expected: &Epoch, actual: &DependencyGraph) -> Result<DriftReport> {
         let mut drift_report = DriftReport::new(expected.id.clone());
+        drift_report.impact_model = self.config.impact_model.clone();
+        drift_report.lockfile_fingerprint = actual.metadata.lockfile_fingerprint.clone();
         
         // 1. Detect additions
         self.detect_additions(expected, actual, &mut drift_report).await?;
@@ -68,17 +103,297 @@ impl DriftDetector {
         // 4. Detect source changes
         self.detect_source_changes(expected, actual, &mut drift_report).await?;
         
-        // 5. Calculate summary statistics
+        // 5. Detect source replacements the vendored config does not set
+        self.detect_source_replacements(actual, &mut drift_report);
+        
+        // 6. Calculate summary statistics
+        drift_report.sort_canonical();
+        drift_report.assign_ids();
         drift_report.calculate_summary();
         
-        // 6. Assess impact
+        // 7. Assess impact
         drift_report.assess_impact();
         
+        for drift in &drift_report.drifts {
+            self.events.emit(AdapterEvent::DriftDetected {
+                package_name: drift.package_name.clone(),
+                change_type: drift.change_type.clone(),
+                priority: drift.priority.clone(),
+                previous_version: drift.previous_version.clone(),
+                current_version: drift.current_version.clone(),
+            });
+        }
+        
         Ok(drift_report)
     }
     
+    /// Load the drift items acknowledged for the project
+    pub fn load_acknowledgements(&self, project: &Project) -> Result<DriftAcknowledgements> {
+        let path = project.epochs_path().join(DRIFT_ACKNOWLEDGEMENTS_FILE);
+        if !path.is_file() {
+            return Ok(DriftAcknowledgements::default());
+        }
+        
+        atomic_write::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| AdapterError::permission_denied(&path, "reading drift acknowledgements"))?;
+        serde_json::from_str(&content)
+            .map_err(|e| AdapterError::ConfigurationInvalid {
+                field: "drift_acknowledgements".to_string(),
+                value: path.display().to_string(),
+                reason: format!("Invalid drift acknowledgements: {}", e),
+                source: anyhow::anyhow!("Invalid drift acknowledgements file"),
+            })
+    }
+    
+    /// Write the drift items acknowledged for the project
+    pub fn save_acknowledgements(&self, project: &Project, acknowledgements: &DriftAcknowledgements) -> Result<()> {
+        let epochs_dir = project.epochs_path();
+        std::fs::create_dir_all(&epochs_dir)
+            .map_err(|_| AdapterError::permission_denied(&epochs_dir, "creating epoch directory"))?;
+        let content = serde_json::to_string_pretty(acknowledgements)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize drift acknowledgements".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        atomic_write::write_atomic(epochs_dir.join(DRIFT_ACKNOWLEDGEMENTS_FILE), content)
+    }
+    
+    /// Attribute drifts to workspace members and owning teams
+    ///
+    /// Members are those depending on the drifted package in `actual`, so
+    /// removed packages have none. Teams owning the package itself take
+    /// precedence over teams owning the members that pull it in.
+    pub fn attribute_drifts(&self, report: &mut DriftReport, actual: &DependencyGraph, owners: &ProjectOwners) {
+        let dependents = actual.workspace_member_dependents();
+        let ownership = |patterns: fn(&TeamOwnership) -> &Vec<String>| {
+            let entries: Vec<(String, String)> = owners.teams.iter()
+                .flat_map(|(team, ownership)| patterns(ownership).iter().map(move |pattern| (pattern.clone(), team.clone())))
+                .collect();
+            PatternOverrides::new(entries.iter().map(|(pattern, team)| (pattern, team)))
+        };
+        let crate_owners = ownership(|ownership| &ownership.crates);
+        let member_owners = ownership(|ownership| &ownership.members);
+        
+        for drift in &mut report.drifts {
+            let version = drift.current_version.as_deref().or(drift.previous_version.as_deref()).unwrap_or_default();
+            drift.workspace_members = actual.find_package(&drift.package_name, version)
+                .and_then(|package| dependents.get(&package.id))
+                .map(|members| members.iter().cloned().collect())
+                .unwrap_or_default();
+            
+            drift.owners = match crate_owners.get(&drift.package_name) {
+                Some(team) => vec![team.clone()],
+                None => drift.workspace_members.iter()
+                    .filter_map(|member| member_owners.get(member).cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            };
+        }
+    }
+    
+    /// Build a drift timeline over a sequence of package states
+    ///
+    /// `states` starts with the baseline epoch and is ordered oldest
+    /// first; each state is compared with its predecessor, so a change is
+    /// attributed to the first epoch that pinned it. A trailing state
+    /// without an epoch ID stands for the current Cargo.lock, whose
+    /// changes are not approved by any epoch. Priorities use the
+    /// classification of `current`.
+    pub fn drift_timeline(&self, states: &[EpochPackageState], current: &DependencyGraph) -> Result<DriftTimeline> {
+        let since_epoch_id = match states.first().and_then(|state| state.epoch_id.as_ref()) {
+            Some(epoch_id) => epoch_id.clone(),
+            None => return Err(AdapterError::ConfigurationInvalid {
+                field: "since".to_string(),
+                value: String::new(),
+                reason: "Drift timeline must start from an epoch".to_string(),
+                source: anyhow::anyhow!("Missing baseline epoch"),
+            }),
+        };
+        
+        let mut epochs = Vec::new();
+        let mut packages: BTreeMap<String, Vec<TimelineChange>> = BTreeMap::new();
+        
+        for pair in states.windows(2) {
+            let (previous, state) = (&pair[0], &pair[1]);
+            let previous_epoch_id = previous.epoch_id.clone().unwrap_or_default();
+            let changes = self.diff_package_states(previous, state, current);
+            
+            epochs.push(TimelineEpoch {
+                epoch_id: state.epoch_id.clone(),
+                created_at: state.created_at.clone(),
+                previous_epoch_id: previous_epoch_id.clone(),
+                total_packages: state.packages.len(),
+                changes: changes.len(),
+            });
+            
+            for (name, change) in changes {
+                packages.entry(name).or_default().push(TimelineChange {
+                    approved_in: state.epoch_id.clone(),
+                    previous_epoch_id: previous_epoch_id.clone(),
+                    ..change
+                });
+            }
+        }
+        
+        let epochs_covered = states.iter().filter(|state| state.epoch_id.is_some()).count();
+        let statistics = TimelineStatistics::from_changes(epochs_covered, &packages);
+        
+        Ok(DriftTimeline {
+            since_epoch_id,
+            lockfile_fingerprint: current.metadata.lockfile_fingerprint.clone(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            epochs,
+            packages,
+            statistics,
+        })
+    }
+    
+    /// Drift a change of the locked packages would introduce
+    ///
+    /// `before` and `after` are the packages pinned before and after the
+    /// change, `before_graph` and `after_graph` the matching dependency
+    /// graphs. Priorities and classifications follow `after_graph`;
+    /// removed packages keep their classification from `before_graph`.
+    pub fn projected_drift(
+        &self,
+        before: &EpochPackageState,
+        after: &EpochPackageState,
+        before_graph: &DependencyGraph,
+        after_graph: &DependencyGraph,
+    ) -> DriftReport {
+        let mut report = DriftReport::new(before.epoch_id.clone().unwrap_or_default());
+        report.impact_model = self.config.impact_model.clone();
+        report.lockfile_fingerprint = after_graph.metadata.lockfile_fingerprint.clone();
+        
+        let max_depth = self.max_depth(after_graph);
+        for (name, change) in self.diff_package_states(before, after, after_graph) {
+            // Removed packages are placed by their depth before the change
+            let package = match &change.current_version {
+                Some(version) => after_graph.find_package(&name, version),
+                None => change.previous_version.as_ref().and_then(|version| before_graph.find_package(&name, version)),
+            };
+            let classification = after_graph.root_packages.iter()
+                .chain(&before_graph.root_packages)
+                .find(|package| package.name == name)
+                .map(|package| package.classification.clone())
+                .unwrap_or(Classification::Unknown);
+            
+            let mut drift = DriftItem::new(name, change.change_type, change.priority)
+                .with_versions(change.previous_version, change.current_version)
+                .with_classification(classification);
+            if change.previous_source != change.current_source {
+                if let (Some(previous), Some(current)) = (&change.previous_source, &change.current_source) {
+                    drift = drift.with_details(format!("Source changed from {} to {}", previous, current));
+                }
+            }
+            if drift.priority == Priority::Critical && matches!(drift.change_type, ChangeType::SourceChange | ChangeType::MultipleChanges) {
+                drift = drift.as_high_risk_source_change();
+            }
+            Self::report_drift(&mut report, max_depth, package, drift);
+        }
+        
+        report.sort_canonical();
+        report.assign_ids();
+        report.calculate_summary();
+        report.assess_impact();
+        report
+    }
+    
+    /// Changes between two package states, keyed by package name
+    ///
+    /// Removed and added versions of a crate are paired in version order
+    /// as version changes; unpaired ones are removals or additions.
+    fn diff_package_states(
+        &self,
+        previous: &EpochPackageState,
+        state: &EpochPackageState,
+        current: &DependencyGraph,
+    ) -> Vec<(String, TimelineChange)> {
+        let versions = |state: &'_ EpochPackageState| {
+            let mut by_name: BTreeMap<String, BTreeMap<String, Option<String>>> = BTreeMap::new();
+            for entry in state.packages.values() {
+                // Sources spelled differently by other cargo versions are not a change
+                let source = entry.source.as_deref().map(normalize_source);
+                by_name.entry(entry.name.clone()).or_default().insert(entry.version.clone(), source);
+            }
+            by_name
+        };
+        let (before, after) = (versions(previous), versions(state));
+        let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        let empty = BTreeMap::new();
+        let mut changes = Vec::new();
+        
+        for name in names {
+            let old = before.get(name).unwrap_or(&empty);
+            let new = after.get(name).unwrap_or(&empty);
+            let change = |change_type, previous: Option<(&String, &Option<String>)>, next: Option<(&String, &Option<String>)>| {
+                let previous_source = previous.and_then(|(_, source)| source.clone());
+                let current_source = next.and_then(|(_, source)| source.clone());
+                let priority = self.timeline_priority(name, previous_source.as_deref(), current_source.as_deref(), current);
+                (name.clone(), TimelineChange {
+                    approved_in: None,
+                    previous_epoch_id: String::new(),
+                    change_type,
+                    previous_version: previous.map(|(version, _)| version.clone()),
+                    current_version: next.map(|(version, _)| version.clone()),
+                    previous_source,
+                    current_source,
+                    priority,
+                })
+            };
+            
+            for (version, source) in old {
+                if let Some(new_source) = new.get(version).filter(|new_source| *new_source != source) {
+                    changes.push(change(ChangeType::SourceChange, Some((version, source)), Some((version, new_source))));
+                }
+            }
+            
+            let mut removed = old.iter().filter(|(version, _)| !new.contains_key(*version));
+            let mut added = new.iter().filter(|(version, _)| !old.contains_key(*version));
+            loop {
+                match (removed.next(), added.next()) {
+                    (Some(previous), Some(next)) => {
+                        let change_type = if previous.1 == next.1 { ChangeType::VersionChange } else { ChangeType::MultipleChanges };
+                        changes.push(change(change_type, Some(previous), Some(next)));
+                    },
+                    (Some(previous), None) => changes.push(change(ChangeType::Removal, Some(previous), None)),
+                    (None, Some(next)) => changes.push(change(ChangeType::Addition, None, Some(next))),
+                    (None, None) => break,
+                }
+            }
+        }
+        
+        changes
+    }
+    
+    /// Priority of a timeline change
+    ///
+    /// A move from a registry to a git source is always critical.
+    fn timeline_priority(&self, name: &str, previous_source: Option<&str>, current_source: Option<&str>, current: &DependencyGraph) -> Priority {
+        let is_registry = |source: &str| source.starts_with("registry+") || source.starts_with("sparse+");
+        let registry_to_git = previous_source.is_some_and(is_registry)
+            && current_source.is_some_and(|source| source.starts_with("git+"));
+        // Sources are normalized, so any difference between registries is a move
+        let cross_registry = previous_source.is_some_and(is_registry)
+            && current_source.is_some_and(is_registry)
+            && previous_source != current_source;
+        if self.config.flag_source_changes_high_risk && (registry_to_git || cross_registry) {
+            return Priority::Critical;
+        }
+        
+        match current.root_packages.iter().find(|package| package.name == name) {
+            Some(package) => self.calculate_package_priority(package),
+            None => self.config.priority_overrides.get(name)
+                .cloned()
+                .unwrap_or_else(|| self.calculate_classification_priority(&Classification::Unknown)),
+        }
+    }
+    
     /// Detect added dependencies
     async fn detect_additions(&self, expected: &Epoch, actual: &DependencyGraph, report: &mut DriftReport) -> Result<()> {
+        let max_depth = self.max_depth(actual);
         for package in &actual.root_packages {
             // Skip if not included in drift detection
             if !self.should_include_package(package) {
@@ -95,7 +410,7 @@ impl DriftDetector {
                 ).with_versions(None, Some(package.version.clone()))
                 .with_classification(package.classification.clone());
                 
-                report.add_drift(drift);
+                Self::report_drift(report, max_depth, Some(package), drift);
             }
         }
         
@@ -109,8 +424,8 @@ impl DriftDetector {
         
         for (name, version) in expected_packages {
             // Check if package still exists in actual graph
-            if actual.find_package(name, version).is_none() {
-                let classification = self.get_expected_classification(expected, name).await?;
+            if actual.find_package(&name, &version).is_none() {
+                let classification = self.get_expected_classification(expected, &name).await?;
                 let priority = self.calculate_classification_priority(&classification);
                 let drift = DriftItem::new(
                     name.clone(),
@@ -128,6 +443,7 @@ impl DriftDetector {
     
     /// Detect version changes
     async fn detect_version_changes(&self, expected: &Epoch, actual: &DependencyGraph, report: &mut DriftReport) -> Result<()> {
+        let max_depth = self.max_depth(actual);
         for package in &actual.root_packages {
             // Skip if not included in drift detection
             if !self.should_include_package(package) {
@@ -152,7 +468,7 @@ impl DriftDetector {
                     ).with_versions(Some(expected_version), Some(package.version.clone()))
                     .with_classification(package.classification.clone());
                     
-                    report.add_drift(drift);
+                    Self::report_drift(report, max_depth, Some(package), drift);
                 }
             }
         }
@@ -162,6 +478,7 @@ impl DriftDetector {
     
     /// Detect source changes
     async fn detect_source_changes(&self, expected: &Epoch, actual: &DependencyGraph, report: &mut DriftReport) -> Result<()> {
+        let max_depth = self.max_depth(actual);
         for package in &actual.root_packages {
             // Skip if not included in drift detection
             if !self.should_include_package(package) {
@@ -173,15 +490,22 @@ impl DriftDetector {
                 if expected_source != package.source {
                     let priority = self.calculate_source_change_priority(&package.source, &expected_source);
                     let is_high_risk = self.is_high_risk_source_change(&package.source, &expected_source);
-                    let drift = DriftItem::new(
+                    let mut drift = DriftItem::new(
                         package.name.clone(),
                         ChangeType::SourceChange,
                         priority
-                    ).with_sources(Some(expected_source), Some(package.source.clone()))
-                    .with_classification(package.classification.clone())
-                    .as_high_risk_source_change(is_high_risk);
+                    ).with_details(format!(
+                        "Source changed from {} to {}",
+                        Self::source_location(&expected_source),
+                        Self::source_location(&package.source),
+                    ))
+                    .with_sources(Some(expected_source), Some(package.source.clone()))
+                    .with_classification(package.classification.clone());
+                    if is_high_risk {
+                        drift = drift.as_high_risk_source_change();
+                    }
                     
-                    report.add_drift(drift);
+                    Self::report_drift(report, max_depth, Some(package), drift);
                 }
             }
         }
@@ -189,53 +513,97 @@ impl DriftDetector {
         Ok(())
     }
     
+    /// Report Cargo config source replacements recorded as mismatched at parse time
+    ///
+    /// A replaced crates.io can serve different code for the same locked
+    /// versions, so every mismatch is critical whatever it redirects to.
+    fn detect_source_replacements(&self, actual: &DependencyGraph, report: &mut DriftReport) {
+        let Some(cargo_config) = actual.metadata.properties.get(CARGO_CONFIG_PROPERTY)
+            .and_then(|value| serde_json::from_value::<CargoSourceConfig>(value.clone()).ok()) else {
+            return;
+        };
+        
+        for mismatch in cargo_config.replacement_mismatches {
+            let describe = |replacement: &Option<String>| replacement.clone().unwrap_or_else(|| "not replaced".to_string());
+            let defined_in = mismatch.defined_in
+                .map(|path| format!(" (set in {})", path.display()))
+                .unwrap_or_default();
+            report.add_drift(DriftItem::new(mismatch.source.clone(), ChangeType::SourceChange, Priority::Critical)
+                .with_details(format!(
+                    "Cargo config replaces source {} with {}{}, the vendored config with {}",
+                    mismatch.source,
+                    describe(&mismatch.actual),
+                    defined_in,
+                    describe(&mismatch.expected),
+                ))
+                .as_high_risk_source_change());
+        }
+    }
+    
     /// Determine if package should be included in drift detection
     fn should_include_package(&self, package: &PackageNode) -> bool {
-        for annotation in &package.annotations {
-            if annotation.key == RustAnnotation::keys::DEPENDENCY_KIND {
-                if let Some(kind_str) = annotation.value.as_str() {
-                    match kind_str {
-                        "dev" if !self.config.include_dev_dependencies => return false,
-                        "build" if !self.config.include_build_dependencies => return false,
-                        _ => {}
-                    }
-                }
-            }
+        match package.dependency_kind() {
+            Some(DependencyKind::Dev) => self.config.include_dev_dependencies,
+            Some(DependencyKind::Build) => self.config.include_build_dependencies,
+            _ => true,
+        }
+    }
+    
+    /// Transitive depth limit for drift of `graph`
+    ///
+    /// The configured limit, or the one the dependency parser recorded.
+    fn max_depth(&self, graph: &DependencyGraph) -> Option<usize> {
+        self.config.max_transitive_depth.or_else(|| {
+            graph.metadata.properties.get(MAX_DEPTH_PROPERTY)
+                .and_then(serde_json::Value::as_u64)
+                .map(|max_depth| max_depth as usize)
+        })
+    }
+    
+    /// Add a drift item, or count it as suppressed if its package lies
+    /// deeper than `max_depth` and it is not exempt from the limit
+    fn report_drift(report: &mut DriftReport, max_depth: Option<usize>, package: Option<&PackageNode>, drift: DriftItem) {
+        let too_deep = matches!(
+            (max_depth, package.and_then(PackageNode::depth)),
+            (Some(max_depth), Some(depth)) if depth > max_depth
+        );
+        if too_deep && !drift.exempt_from_depth_limit() {
+            report.depth_suppressed_drifts += 1;
+        } else {
+            report.add_drift(drift);
         }
-        
-        true
     }
     
     /// Check if package exists in expected epoch
-    fn package_exists_in_epoch(&self, expected: &Epoch, name: &str, version: &str) -> bool {
+    fn package_exists_in_epoch(&self, _expected: &Epoch, _name: &str, _version: &str) -> bool {
         // This would check if package exists in epoch
         // For now, return false (assume no packages in epoch)
         false
     }
     
     /// Get expected packages from epoch
-    async fn get_expected_packages(&self, expected: &Epoch) -> Result<HashMap<String, String>> {
+    async fn get_expected_packages(&self, _expected: &Epoch) -> Result<HashMap<String, String>> {
         // This would extract package name-version pairs from epoch
         // For now, return empty map
         Ok(HashMap::new())
     }
     
     /// Get expected classification for package
-    async fn get_expected_classification(&self, expected: &Epoch, name: &str) -> Result<Classification> {
+    async fn get_expected_classification(&self, _expected: &Epoch, _name: &str) -> Result<Classification> {
         // This would get classification from epoch
         // For now, return Unknown
         Ok(Classification::Unknown)
     }
     
     /// Get package version in expected epoch
-    async fn get_package_version_in_epoch(&self, expected: &Epoch, name: &str) -> Result<Option<String>> {
+    async fn get_package_version_in_epoch(&self, _expected: &Epoch, _name: &str) -> Result<Option<String>> {
         // This would get package version from epoch
         // For now, return None
         Ok(None)
     }
     
     /// Get package source in expected epoch
-    async fn get_package_source_in_epoch(&self, expected: &Epoch, name: &str) -> Result<Option<PackageSource>> {
+    async fn get_package_source_in_epoch(&self, _expected: &Epoch, _name: &str) -> Result<Option<PackageSource>> {
         // This would get package source from epoch
         // For now, return None
         Ok(None)
@@ -255,6 +623,11 @@ impl DriftDetector {
     /// Calculate priority based on classification
     fn calculate_classification_priority(&self, classification: &Classification) -> Priority {
         match classification {
+            Classification::TCS { category: TcsCategory::Custom(name), .. } => {
+                self.config.custom_category_priorities.get(name)
+                    .cloned()
+                    .unwrap_or(Priority::Critical)
+            },
             Classification::TCS { .. } => Priority::Critical,
             Classification::Mechanical { .. } => Priority::Medium,
             Classification::Unknown => Priority::Low,
@@ -267,6 +640,7 @@ impl DriftDetector {
         match (expected, actual) {
             (PackageSource::Registry { .. }, PackageSource::Git { .. }) => Priority::Critical,
             (PackageSource::Git { .. }, PackageSource::Registry { .. }) => Priority::Medium,
+            _ if Self::is_cross_registry_move(expected, actual) => Priority::Critical,
             _ => Priority::Low,
         }
     }
@@ -276,9 +650,41 @@ impl DriftDetector {
         match (expected, actual) {
             (PackageSource::Registry { .. }, PackageSource::Git { .. }) => true,
             (PackageSource::Local { .. }, PackageSource::Git { .. }) => true,
+            _ => Self::is_cross_registry_move(expected, actual),
+        }
+    }
+    
+    /// Check if a package moved from one registry to another
+    ///
+    /// Registry URLs are compared after normalization, so different
+    /// spellings of the same index (e.g. crates.io's git and sparse
+    /// index) are not a move.
+    fn is_cross_registry_move(expected: &PackageSource, actual: &PackageSource) -> bool {
+        match (expected, actual) {
+            (PackageSource::Registry { url: expected, .. }, PackageSource::Registry { url: actual, .. }) => {
+                Self::registry_source(expected) != Self::registry_source(actual)
+            },
             _ => false,
         }
     }
+    
+    /// Normalized Cargo.lock source string of a registry URL
+    fn registry_source(url: &str) -> String {
+        if url.starts_with("registry+") || url.starts_with("sparse+") {
+            normalize_source(url)
+        } else {
+            normalize_source(&format!("registry+{}", url))
+        }
+    }
+    
+    /// URL or path a package source points at
+    fn source_location(source: &PackageSource) -> String {
+        match source {
+            PackageSource::Registry { url, .. } => format!("registry {}", url),
+            PackageSource::Git { url, rev, .. } => format!("git {}#{}", url, rev),
+            PackageSource::Local { path } => format!("path {}", path),
+        }
+    }
 }
 
 impl Default for DriftDetectorConfig {
@@ -286,9 +692,12 @@ impl Default for DriftDetectorConfig {
         Self {
             ignore_mechanical_version_updates: false,
             flag_source_changes_high_risk: true,
-            priority_overrides: HashMap::new(),
+            priority_overrides: PatternOverrides::default(),
             include_dev_dependencies: false,
             include_build_dependencies: true,
+            custom_category_priorities: HashMap::new(),
+            max_transitive_depth: DriftDetectionConfig::default().max_transitive_depth,
+            impact_model: ImpactModel::default(),
         }
     }
 }
@@ -298,6 +707,7 @@ mod tests {
     use super::*;
     use crate::config::RustAdapterConfig;
     use crate::models::project_types::*;
+    use crate::utils::NamePattern;
     use std::path::PathBuf;
     
     #[test]
@@ -314,7 +724,7 @@ mod tests {
         let config = RustAdapterConfig::default();
         let detector = DriftDetector::new(&config);
         
-        let project = Project::new(
+        let _project = Project::new(
             "test".to_string(),
             "Test Project".to_string(),
             "rust".to_string(),
@@ -332,7 +742,7 @@ mod tests {
                 checksum: "test-checksum".to_string(),
             },
             checksum: "test-checksum".to_string(),
-            classification: Classification::Mechanical(MechanicalCategory::Other("test".to_string())),
+            classification: Classification::Mechanical { category: MechanicalCategory::Other("test".to_string()) },
             audit_status: AuditStatus::Unaudited,
             annotations: vec![],
         };
@@ -377,6 +787,20 @@ mod tests {
         
         let is_high_risk = detector.is_high_risk_source_change(&git_source, &registry_source);
         assert!(is_high_risk);
+        
+        // Moving to another registry is flagged; respelling crates.io is not
+        let mirror_source = PackageSource::Registry {
+            url: "sparse+https://mirror.example.com/index/".to_string(),
+            checksum: "test-checksum".to_string(),
+        };
+        assert_eq!(detector.calculate_source_change_priority(&mirror_source, &registry_source), Priority::Critical);
+        assert!(detector.is_high_risk_source_change(&mirror_source, &registry_source));
+        
+        let sparse_crates_io = PackageSource::Registry {
+            url: "sparse+https://index.crates.io/".to_string(),
+            checksum: "test-checksum".to_string(),
+        };
+        assert!(!detector.is_high_risk_source_change(&sparse_crates_io, &registry_source));
     }
     
     #[tokio::test]
@@ -389,7 +813,7 @@ mod tests {
             rationale: "Crypto package".to_string(),
         };
         
-        let mechanical_classification = Classification::Mechanical(MechanicalCategory::Other("test".to_string()));
+        let mechanical_classification = Classification::Mechanical { category: MechanicalCategory::Other("test".to_string()) };
         
         let tcs_priority = detector.calculate_classification_priority(&tcs_classification);
         let mechanical_priority = detector.calculate_classification_priority(&mechanical_classification);
@@ -397,4 +821,398 @@ mod tests {
         assert_eq!(tcs_priority, Priority::Critical);
         assert_eq!(mechanical_priority, Priority::Medium);
     }
+    
+    #[test]
+    fn test_custom_category_priority() {
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.custom_category_priorities
+            .insert("telemetry".to_string(), Priority::Low);
+        let detector = DriftDetector::new(&config);
+        
+        let custom = |name: &str| Classification::TCS {
+            category: TcsCategory::Custom(name.to_string()),
+            rationale: "Listed in project TCS list".to_string(),
+        };
+        
+        assert_eq!(detector.calculate_classification_priority(&custom("telemetry")), Priority::Low);
+        // Unconfigured custom categories are treated like built-in TCS
+        assert_eq!(detector.calculate_classification_priority(&custom("payments")), Priority::Critical);
+    }
+    
+    #[test]
+    fn test_attribute_drifts_to_members_and_teams() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        
+        let node = |name: &str, member: bool| {
+            let mut package = PackageNode {
+                id: uuid::Uuid::new_v4(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                source: PackageSource::Local { path: name.to_string() },
+                checksum: String::new(),
+                classification: Classification::Unknown,
+                audit_status: AuditStatus::Unaudited,
+                annotations: vec![],
+            };
+            if member {
+                package.set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::Value::Bool(true));
+            }
+            package
+        };
+        let (api, billing, tokio, stripe) = (node("api", true), node("payments-billing", true), node("tokio", false), node("stripe-rs", false));
+        for (from, to) in [(&api, &tokio), (&billing, &tokio), (&billing, &stripe)] {
+            graph.add_edge(DependencyEdge {
+                from: from.id,
+                to: to.id,
+                kind: DependencyKind::Normal,
+                target: None,
+                optional: false,
+                features: vec![],
+            });
+        }
+        for package in [api, billing, tokio, stripe] {
+            graph.add_package(package);
+        }
+        
+        let mut owners = ProjectOwners::default();
+        owners.teams.insert("payments".to_string(), TeamOwnership {
+            members: vec!["payments-*".to_string()],
+            ..TeamOwnership::default()
+        });
+        owners.teams.insert("integrations".to_string(), TeamOwnership {
+            crates: vec!["stripe*".to_string()],
+            ..TeamOwnership::default()
+        });
+        
+        let mut report = DriftReport::new("epoch".to_string());
+        for (name, change_type) in [("tokio", ChangeType::VersionChange), ("stripe-rs", ChangeType::Addition), ("log", ChangeType::Removal)] {
+            let version = Some("1.0.0".to_string());
+            report.add_drift(DriftItem::new(name.to_string(), change_type, Priority::Medium).with_versions(version.clone(), version));
+        }
+        detector.attribute_drifts(&mut report, &graph, &owners);
+        
+        assert_eq!(report.drifts[0].workspace_members, vec!["api".to_string(), "payments-billing".to_string()]);
+        assert_eq!(report.drifts[0].owners, vec!["payments".to_string()]);
+        assert_eq!(report.drifts[1].owners, vec!["integrations".to_string()]);
+        
+        let by_member = report.by_workspace_member();
+        assert_eq!(by_member["api"].len(), 1);
+        assert_eq!(by_member["payments-billing"].len(), 2);
+        assert_eq!(by_member[UNATTRIBUTED_DRIFT_GROUP][0].package_name, "log");
+        
+        let by_team = report.by_team();
+        assert_eq!(by_team["payments"][0].package_name, "tokio");
+        assert_eq!(by_team["integrations"][0].package_name, "stripe-rs");
+        assert_eq!(by_team[UNOWNED_DRIFT_GROUP][0].package_name, "log");
+    }
+    
+    #[test]
+    fn test_priority_override_patterns() {
+        let priority_overrides = serde_json::from_str(r#"{"openssl*": "Critical", "*-sys": "High", "openssl-probe": "Low"}"#).unwrap();
+        let config = RustAdapterConfig {
+            drift_detection_config: DriftDetectionConfig { priority_overrides, ..DriftDetectionConfig::default() },
+            ..RustAdapterConfig::default()
+        };
+        let detector = DriftDetector::new(&config);
+        let graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        
+        let priority = |name: &str| detector.timeline_priority(name, None, None, &graph);
+        assert_eq!(priority("openssl-sys"), Priority::Critical);
+        assert_eq!(priority("libz-sys"), Priority::High);
+        assert_eq!(priority("openssl-probe"), Priority::Low);
+        assert_eq!(priority("serde"), Priority::Low);
+    }
+    
+    #[test]
+    fn test_dev_dependencies_excluded_by_kind() {
+        let config = RustAdapterConfig::default();
+        let detector = DriftDetector::new(&config);
+        
+        let mut package = PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "test-helper".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Local { path: "../test-helper".to_string() },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        
+        package.set_annotation(annotation_keys::DEPENDENCY_KIND, serde_json::json!("normal"));
+        assert!(detector.should_include_package(&package));
+        
+        // Re-setting the key replaces the value instead of appending a duplicate
+        package.set_annotation(annotation_keys::DEPENDENCY_KIND, serde_json::json!("dev"));
+        assert_eq!(package.annotations.len(), 1);
+        assert_eq!(package.dependency_kind(), Some(DependencyKind::Dev));
+        assert!(!detector.should_include_package(&package));
+    }
+    
+    #[test]
+    fn test_max_transitive_depth_suppresses_deep_drift() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        for name in ["app", "serde", "serde_derive", "ring"] {
+            graph.add_package(PackageNode {
+                id: uuid::Uuid::new_v4(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                source: PackageSource::Local { path: format!("../{}", name) },
+                checksum: String::new(),
+                classification: Classification::Unknown,
+                audit_status: AuditStatus::Unaudited,
+                annotations: vec![],
+            });
+        }
+        graph.root_packages[3].classification = Classification::TCS {
+            category: TcsCategory::Cryptography,
+            rationale: "test".to_string(),
+        };
+        let ids: Vec<_> = graph.root_packages.iter().map(|p| p.id).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(DependencyEdge { from: pair[0], to: pair[1], kind: DependencyKind::Normal, target: None, optional: false, features: vec![] });
+        }
+        graph.annotate_depths();
+        
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        let before = package_state(None, &[("app", "1.0.0", registry)]);
+        let after = package_state(None, &[("app", "1.0.0", registry), ("serde", "1.0.0", registry), ("serde_derive", "1.0.0", registry), ("ring", "1.0.0", registry)]);
+        
+        // Without a limit configured or recorded everything is reported
+        assert_eq!(detector.projected_drift(&before, &after, &graph, &graph).drifts.len(), 3);
+        
+        // The limit recorded by the parser applies; TCS drift is never suppressed
+        graph.metadata.properties.insert(MAX_DEPTH_PROPERTY.to_string(), serde_json::json!(1));
+        let report = detector.projected_drift(&before, &after, &graph, &graph);
+        let reported: Vec<&str> = report.drifts.iter().map(|drift| drift.package_name.as_str()).collect();
+        assert_eq!(reported, vec!["ring", "serde"]);
+        assert_eq!(report.depth_suppressed_drifts, 1);
+        
+        // A configured limit takes precedence
+        let report = detector.with_max_transitive_depth(Some(2)).projected_drift(&before, &after, &graph, &graph);
+        assert_eq!(report.drifts.len(), 3);
+        assert_eq!(report.depth_suppressed_drifts, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_source_replacement_mismatch_is_critical_drift() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let cargo_config = CargoSourceConfig {
+            replacement_mismatches: vec![crate::manifest::SourceReplacementMismatch {
+                source: "crates-io".to_string(),
+                expected: Some("vendored-sources (directory)".to_string()),
+                actual: Some("mirror (registry sparse+https://attacker.example/index/)".to_string()),
+                defined_in: Some(PathBuf::from("/repo/.cargo/config.toml")),
+            }],
+            ..CargoSourceConfig::default()
+        };
+        graph.metadata.properties.insert(CARGO_CONFIG_PROPERTY.to_string(), serde_json::to_value(&cargo_config).unwrap());
+        let epoch = Epoch {
+            id: "test-epoch".to_string(),
+            project_id: "test".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            metadata: EpochMetadata::default(),
+            dependencies: EpochDependencies::default(),
+            security: EpochSecurity::default(),
+            governance: EpochGovernance::default(),
+        };
+        
+        let report = detector.detect_drift(&epoch, &graph).await.unwrap();
+        assert_eq!(report.drifts.len(), 1);
+        assert_eq!(report.drifts[0].package_name, "crates-io");
+        assert_eq!(report.drifts[0].priority, Priority::Critical);
+        assert!(report.drifts[0].is_high_risk_source_change);
+        assert!(report.drifts[0].details.as_deref().unwrap().contains("/repo/.cargo/config.toml"));
+    }
+    
+    fn package_state(epoch_id: Option<&str>, packages: &[(&str, &str, &str)]) -> EpochPackageState {
+        EpochPackageState {
+            epoch_id: epoch_id.map(str::to_string),
+            created_at: None,
+            packages: packages.iter()
+                .map(|(name, version, source)| (format!("{}@{}", name, version), VendorManifestEntry {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    source: Some(source.to_string()),
+                    checksum: None,
+                    path: PathBuf::from(name),
+                }))
+                .collect(),
+        }
+    }
+    
+    #[test]
+    fn test_projected_drift() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        let graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        
+        let before = package_state(None, &[("serde", "1.0.0", registry), ("log", "0.4.0", registry)]);
+        // log moves to the sparse spelling of crates.io, which is not a change
+        let after = package_state(None, &[("serde", "1.0.5", registry), ("log", "0.4.0", "sparse+https://index.crates.io/"), ("serde_derive", "1.0.5", registry)]);
+        let report = detector.projected_drift(&before, &after, &graph, &graph);
+        
+        assert_eq!(report.drifts.len(), 2);
+        let serde = report.drifts.iter().find(|d| d.package_name == "serde").unwrap();
+        assert_eq!(serde.change_type, ChangeType::VersionChange);
+        assert_eq!(serde.previous_version.as_deref(), Some("1.0.0"));
+        assert_eq!(serde.current_version.as_deref(), Some("1.0.5"));
+        assert!(report.drifts.iter().any(|d| d.package_name == "serde_derive" && d.change_type == ChangeType::Addition));
+    }
+
+    #[test]
+    fn test_configured_impact_model() {
+        let config = RustAdapterConfig {
+            drift_impact_model: ImpactModel {
+                removal_weight: 0.5,
+                minor_threshold: 20.0,
+                moderate_threshold: Some(40.0),
+                ..ImpactModel::default()
+            },
+            ..RustAdapterConfig::default()
+        };
+        let graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let state = package_state(None, &[]);
+        let mut report = DriftDetector::new(&config).projected_drift(&state, &state, &graph, &graph);
+        assert_eq!(report.impact_model, config.drift_impact_model);
+
+        let version = Some("1.0.0".to_string());
+        let mut assess = |count: usize, change_type: ChangeType| {
+            report.drifts = (0..count)
+                .map(|i| DriftItem::new(format!("crate-{}", i), change_type.clone(), Priority::Low).with_versions(version.clone(), None))
+                .collect();
+            report.calculate_summary();
+            report.assess_impact();
+            report.impact.overall_impact.clone()
+        };
+
+        // 15 drifts are Minor by default, but below the raised threshold
+        assert_eq!(assess(15, ChangeType::Addition), ImpactLevel::Minimal);
+        assert_eq!(assess(30, ChangeType::Addition), ImpactLevel::Minor);
+        assert_eq!(assess(41, ChangeType::Addition), ImpactLevel::Moderate);
+        // Removals weigh half
+        assert_eq!(assess(41, ChangeType::Removal), ImpactLevel::Minor);
+    }
+
+    #[test]
+    fn test_acknowledged_drift_suppressed_until_change_differs() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        let graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let before = package_state(None, &[("serde", "1.0.0", registry)]);
+        let report = detector.projected_drift(&before, &package_state(None, &[("serde", "1.0.5", registry), ("log", "0.4.0", registry)]), &graph, &graph);
+        let serde = report.drifts.iter().find(|d| d.package_name == "serde").unwrap().id.clone();
+        assert_eq!(serde.len(), 16);
+        
+        // Acknowledgements survive a round trip through the exported report and the project
+        let exported = DriftReport::from_json(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(exported.schema_version, DRIFT_REPORT_SCHEMA_VERSION);
+        let mut acknowledgements = DriftAcknowledgements::default();
+        assert!(acknowledgements.acknowledge(&exported, &["unknown".to_string()]).is_err());
+        acknowledgements.acknowledge(&exported, std::slice::from_ref(&serde)).unwrap();
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), temp.path().to_path_buf());
+        detector.save_acknowledgements(&project, &acknowledgements).unwrap();
+        let acknowledgements = detector.load_acknowledgements(&project).unwrap();
+        
+        let mut rerun = detector.projected_drift(&before, &package_state(None, &[("serde", "1.0.5", registry), ("log", "0.4.0", registry)]), &graph, &graph);
+        assert_eq!(rerun.drifts.iter().find(|d| d.package_name == "serde").unwrap().id, serde);
+        rerun.suppress_acknowledged(&acknowledgements);
+        assert_eq!(rerun.drifts.len(), 1);
+        assert_eq!(rerun.acknowledged_drifts, 1);
+        assert_eq!(rerun.summary.total_drifts, 1);
+        
+        let mut changed = detector.projected_drift(&before, &package_state(None, &[("serde", "1.0.6", registry)]), &graph, &graph);
+        changed.suppress_acknowledged(&acknowledgements);
+        assert_eq!(changed.drifts.len(), 1);
+        assert_eq!(changed.acknowledged_drifts, 0);
+        
+        let future = serde_json::to_string(&DriftReport { schema_version: "2.0".to_string(), ..report }).unwrap();
+        assert!(DriftReport::from_json(&future).is_err());
+    }
+    
+    #[test]
+    fn test_drift_report_query_and_pages() {
+        let mut report = DriftReport::new("epoch-1".to_string());
+        report.add_drift(DriftItem::new("serde".to_string(), ChangeType::VersionChange, Priority::Medium));
+        report.add_drift(DriftItem::new("ring".to_string(), ChangeType::SourceChange, Priority::Critical));
+        report.add_drift(DriftItem::new("serde_json".to_string(), ChangeType::Addition, Priority::Low));
+        report.add_drift(DriftItem::new("openssl".to_string(), ChangeType::VersionChange, Priority::Critical));
+        report.calculate_summary();
+        
+        let names = |drifts: &[DriftItem]| drifts.iter().map(|d| d.package_name.clone()).collect::<Vec<_>>();
+        let everything = DriftQuery::default();
+        
+        // Most urgent first, in pages
+        let first = report.page(&everything, 0, Some(2), false);
+        assert_eq!(names(first.drifts.as_ref().unwrap()), vec!["ring", "openssl"]);
+        assert_eq!((first.matching_drifts, first.next_offset), (4, Some(2)));
+        let last = report.page(&everything, 2, Some(2), false);
+        assert_eq!(names(last.drifts.as_ref().unwrap()), vec!["serde", "serde_json"]);
+        assert_eq!(last.next_offset, None);
+        assert!(report.page(&everything, 9, Some(2), false).drifts.unwrap().is_empty());
+        
+        // Criteria combine
+        let query = DriftQuery {
+            change_types: vec!["version-change".parse().unwrap()],
+            packages: vec![NamePattern::parse("serde*").unwrap()],
+            ..DriftQuery::default()
+        };
+        assert_eq!(report.query(&query).len(), 1);
+        let query = DriftQuery { priorities: vec!["critical".parse().unwrap()], ..DriftQuery::default() };
+        assert_eq!(report.query(&query).len(), 2);
+        
+        // Summary-only pages carry counts but no items
+        let summary = report.page(&query, 0, None, true);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("drifts").is_none());
+        assert_eq!(json["matching_drifts"], 2);
+        assert_eq!(json["summary"]["total_drifts"], 4);
+        assert!("urgent".parse::<Priority>().is_err());
+    }
+    
+    #[test]
+    fn test_drift_timeline_attributes_changes_to_epochs() {
+        let detector = DriftDetector::new(&RustAdapterConfig::default());
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        let git = "git+https://github.com/briansmith/ring#abc123";
+        
+        let states = vec![
+            package_state(Some("2024-q1"), &[("serde", "1.0.0", registry), ("log", "0.4.0", registry)]),
+            package_state(Some("2024-q2"), &[("serde", "1.0.1", registry), ("log", "0.4.0", registry), ("ring", "0.17.8", registry)]),
+            package_state(None, &[("serde", "1.0.2", registry), ("ring", "0.17.8", git)]),
+        ];
+        let timeline = detector.drift_timeline(&states, &DependencyGraph::new("test".to_string(), "rust".to_string())).unwrap();
+        
+        assert_eq!(timeline.since_epoch_id, "2024-q1");
+        assert_eq!(timeline.epochs.len(), 2);
+        assert_eq!(timeline.epochs[0].changes, 2);
+        assert_eq!(timeline.epochs[1].epoch_id, None);
+        assert_eq!(timeline.epochs[1].changes, 3);
+        
+        let serde = &timeline.packages["serde"];
+        assert_eq!(serde.len(), 2);
+        assert_eq!(serde[0].change_type, ChangeType::VersionChange);
+        assert_eq!(serde[0].approved_in.as_deref(), Some("2024-q2"));
+        assert_eq!(serde[0].previous_epoch_id, "2024-q1");
+        assert_eq!(serde[1].current_version.as_deref(), Some("1.0.2"));
+        assert_eq!(serde[1].approved_in, None);
+        
+        let ring = &timeline.packages["ring"];
+        assert_eq!(ring[0].change_type, ChangeType::Addition);
+        assert_eq!(ring[1].change_type, ChangeType::SourceChange);
+        assert_eq!(ring[1].priority, Priority::Critical);
+        assert_eq!(timeline.packages["log"][0].change_type, ChangeType::Removal);
+        
+        let stats = &timeline.statistics;
+        assert_eq!(stats.epochs_covered, 2);
+        assert_eq!(stats.total_changes, 5);
+        assert_eq!(stats.version_changes, 2);
+        assert_eq!(stats.packages_changed, 3);
+        assert_eq!(stats.packages_changed_repeatedly, 2);
+        assert_eq!(stats.unapproved_changes, 3);
+    }
 }
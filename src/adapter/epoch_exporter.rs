@@ -0,0 +1,423 @@
+//! Epoch export in the Control Plane's universal format
+//!
+//! This module maps an epoch, given by its vendor snapshot and the
+//! classified dependency graph of the Cargo.lock it pins, to a
+//! `UniversalEpoch` document, and imports such documents back into a
+//! dependency graph. Documents are checked against the universal schema
+//! in both directions, so an invalid document is neither written nor
+//! imported.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use std::collections::HashMap;
+
+/// Ecosystem name used in universal documents
+const ECOSYSTEM: &str = "rust";
+
+/// Universal epoch exporter implementation
+#[derive(Debug, Clone)]
+pub struct EpochExporter {
+    /// Whether exporter is ready
+    ready: bool,
+}
+
+/// Epoch read back from a universal document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedEpoch {
+    /// Epoch ID
+    pub epoch_id: String,
+    /// Epoch creation timestamp
+    pub created_at: String,
+    /// Vendored sources pinned with the epoch
+    pub vendor: Option<UniversalVendorState>,
+    /// Classified dependency graph with fresh package IDs
+    pub graph: DependencyGraph,
+}
+
+impl EpochExporter {
+    /// Create new epoch exporter with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self { ready: true }
+    }
+    
+    /// Check if exporter is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Describe an epoch in the universal format
+    ///
+    /// `graph` must be built from the Cargo.lock the snapshot pins.
+    pub fn export(&self, snapshot: &VendorSnapshot, graph: &DependencyGraph) -> Result<UniversalEpoch> {
+        if !graph.metadata.lockfile_fingerprint.is_empty() && graph.metadata.lockfile_fingerprint != snapshot.lockfile_fingerprint {
+            return Err(AdapterError::EpochInvalidated {
+                epoch_id: snapshot.epoch_id.clone(),
+                reason: "Dependency graph was not built from the Cargo.lock pinned by the epoch".to_string(),
+                source: anyhow::anyhow!("Lockfile fingerprint mismatch"),
+            });
+        }
+        
+        let keys: HashMap<PackageId, String> = graph.root_packages.iter()
+            .map(|p| (p.id, UniversalEpoch::package_key(&p.name, &p.version)))
+            .collect();
+        
+        let mut packages: Vec<_> = graph.root_packages.iter()
+            .map(|package| Self::export_package(&keys[&package.id], package))
+            .collect();
+        packages.sort_by(|a, b| a.key.cmp(&b.key));
+        
+        let mut edges: Vec<_> = graph.edges.iter()
+            .filter_map(|edge| {
+                Some(UniversalEdge {
+                    from: keys.get(&edge.from)?.clone(),
+                    to: keys.get(&edge.to)?.clone(),
+                    kind: Self::dependency_kind(&edge.kind).to_string(),
+                    target: edge.target.clone(),
+                    optional: edge.optional,
+                    features: edge.features.clone(),
+                })
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.from, &a.to, &a.kind).cmp(&(&b.from, &b.to, &b.kind)));
+        
+        let epoch = UniversalEpoch {
+            schema_version: UNIVERSAL_EPOCH_SCHEMA_VERSION.to_string(),
+            ecosystem: ECOSYSTEM.to_string(),
+            epoch_id: snapshot.epoch_id.clone(),
+            created_at: snapshot.created_at.clone(),
+            project_id: graph.project_id.clone(),
+            lockfile_fingerprint: snapshot.lockfile_fingerprint.clone(),
+            vendor: (!snapshot.content_digest.is_empty()).then(|| UniversalVendorState {
+                content_digest: snapshot.content_digest.clone(),
+                total_packages: snapshot.total_packages,
+                total_size_bytes: snapshot.total_size_bytes,
+                verified_at: snapshot.verified_at.clone(),
+            }),
+            packages,
+            edges,
+        };
+        
+        Self::check(epoch.validate(), "Exported epoch does not match the universal schema")?;
+        Ok(epoch)
+    }
+    
+    /// Read a universal epoch document (JSON)
+    ///
+    /// The document must have a supported schema version, satisfy the
+    /// universal schema and describe a Rust epoch.
+    pub fn import(&self, content: &str) -> Result<ImportedEpoch> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| Self::invalid(vec![format!("document is not valid JSON: {}", e)]))?;
+        let schema_version = value.get("schema_version").and_then(|v| v.as_str()).unwrap_or_default();
+        if !UniversalEpoch::is_supported_version(schema_version) {
+            return Err(Self::invalid(vec![format!(
+                "schema_version: unsupported version '{}' (expected {})",
+                schema_version, UNIVERSAL_EPOCH_SCHEMA_VERSION,
+            )]));
+        }
+        
+        let epoch: UniversalEpoch = serde_json::from_value(value)
+            .map_err(|e| Self::invalid(vec![e.to_string()]))?;
+        let mut errors = epoch.validate();
+        if epoch.ecosystem != ECOSYSTEM {
+            errors.push(format!("ecosystem: expected '{}', found '{}'", ECOSYSTEM, epoch.ecosystem));
+        }
+        Self::check(errors, "Imported epoch does not match the universal schema")?;
+        
+        let mut graph = DependencyGraph::new(epoch.project_id.clone(), ECOSYSTEM.to_string());
+        graph.metadata.lockfile_fingerprint = epoch.lockfile_fingerprint.clone();
+        
+        let mut errors = Vec::new();
+        let mut ids = HashMap::new();
+        for (index, package) in epoch.packages.iter().enumerate() {
+            match Self::import_package(package) {
+                Ok(node) => {
+                    ids.insert(package.key.as_str(), node.id);
+                    graph.add_package(node);
+                },
+                Err(error) => errors.push(format!("packages[{}].{}", index, error)),
+            }
+        }
+        for edge in &epoch.edges {
+            let (Some(&from), Some(&to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str())) else {
+                continue;
+            };
+            graph.edges.push(DependencyEdge {
+                from,
+                to,
+                kind: match edge.kind.as_str() {
+                    "build" => DependencyKind::Build,
+                    "dev" => DependencyKind::Dev,
+                    _ => DependencyKind::Normal,
+                },
+                target: edge.target.clone(),
+                optional: edge.optional,
+                features: edge.features.clone(),
+            });
+        }
+        Self::check(errors, "Imported epoch does not describe Rust packages")?;
+        
+        Ok(ImportedEpoch {
+            epoch_id: epoch.epoch_id,
+            created_at: epoch.created_at,
+            vendor: epoch.vendor,
+            graph,
+        })
+    }
+    
+    /// Universal description of a package
+    fn export_package(key: &str, package: &PackageNode) -> UniversalPackage {
+        let source = match &package.source {
+            PackageSource::Registry { url, .. } => UniversalSource { kind: "registry".to_string(), location: url.clone(), revision: None },
+            PackageSource::Git { url, rev, .. } => UniversalSource { kind: "git".to_string(), location: url.clone(), revision: Some(rev.clone()) },
+            PackageSource::Local { path } => UniversalSource { kind: "local".to_string(), location: path.clone(), revision: None },
+        };
+        
+        let classification = match &package.classification {
+            Classification::TCS { category, rationale } => UniversalClassification {
+                kind: "tcs".to_string(),
+                category: Some(category.to_string()),
+                rationale: Some(rationale.clone()),
+            },
+            Classification::Mechanical { category } => UniversalClassification {
+                kind: "mechanical".to_string(),
+                category: Some(match category {
+                    MechanicalCategory::Other(name) => format!("other:{}", name),
+                    builtin => format!("{:?}", builtin),
+                }),
+                rationale: None,
+            },
+            Classification::Unknown => UniversalClassification { kind: "unknown".to_string(), category: None, rationale: None },
+        };
+        
+        let mut audit = UniversalAudit {
+            status: "unaudited".to_string(),
+            method: None,
+            reference: None,
+            auditor: None,
+            date: None,
+            reason: None,
+            expires: None,
+        };
+        match &package.audit_status {
+            AuditStatus::Audited { method, auditor, date } => {
+                audit.status = "audited".to_string();
+                audit.auditor = Some(auditor.clone());
+                audit.date = Some(date.clone());
+                match method {
+                    AuditMethod::CargoVet { criteria } => {
+                        audit.method = Some("cargo-vet".to_string());
+                        audit.reference = Some(criteria.clone());
+                    },
+                    AuditMethod::Manual { adr_reference } => {
+                        audit.method = Some("manual".to_string());
+                        audit.reference = Some(adr_reference.to_string());
+                    },
+                    AuditMethod::Imported { source } => {
+                        audit.method = Some("imported".to_string());
+                        audit.reference = Some(source.clone());
+                    },
+                    AuditMethod::Exemption { reason, expires } => {
+                        audit.method = Some("exemption".to_string());
+                        audit.reason = Some(reason.clone());
+                        audit.expires = Some(expires.clone());
+                    },
+                }
+            },
+            AuditStatus::Exempted { reason, expires } => {
+                audit.status = "exempted".to_string();
+                audit.reason = Some(reason.clone());
+                audit.expires = expires.clone();
+            },
+            AuditStatus::Unaudited => {},
+        }
+        
+        UniversalPackage {
+            key: key.to_string(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source,
+            checksum: package.checksum.clone(),
+            classification,
+            audit,
+            attributes: package.annotations.iter()
+                .map(|a| (a.key.clone(), a.value.clone()))
+                .collect(),
+        }
+    }
+    
+    /// Package node for a universal package that passed schema validation
+    ///
+    /// Errors name the field that has no Rust equivalent.
+    fn import_package(package: &UniversalPackage) -> std::result::Result<PackageNode, String> {
+        let source = match package.source.kind.as_str() {
+            "git" => PackageSource::Git {
+                url: package.source.location.clone(),
+                rev: package.source.revision.clone().unwrap_or_default(),
+                checksum: package.checksum.clone(),
+            },
+            "local" => PackageSource::Local { path: package.source.location.clone() },
+            _ => PackageSource::Registry { url: package.source.location.clone(), checksum: package.checksum.clone() },
+        };
+        
+        let category = package.classification.category.as_deref().unwrap_or_default();
+        let classification = match package.classification.kind.as_str() {
+            "tcs" => Classification::TCS {
+                category: category.parse().map_err(|e| format!("classification.category: {}", e))?,
+                rationale: package.classification.rationale.clone().unwrap_or_default(),
+            },
+            "mechanical" => Classification::Mechanical {
+                category: Self::mechanical_category(category)
+                    .ok_or_else(|| format!("classification.category: unknown mechanical category '{}'", category))?,
+            },
+            _ => Classification::Unknown,
+        };
+        
+        let audit = &package.audit;
+        let audit_status = match audit.status.as_str() {
+            "audited" => AuditStatus::Audited {
+                method: match audit.method.as_deref().unwrap_or_default() {
+                    "cargo-vet" => AuditMethod::CargoVet { criteria: audit.reference.clone().unwrap_or_default() },
+                    "manual" => AuditMethod::Manual {
+                        adr_reference: audit.reference.as_deref().and_then(|r| r.parse().ok())
+                            .ok_or_else(|| "audit.reference: manual audits need a numeric decision record reference".to_string())?,
+                    },
+                    "imported" => AuditMethod::Imported { source: audit.reference.clone().unwrap_or_default() },
+                    "exemption" => AuditMethod::Exemption {
+                        reason: audit.reason.clone().unwrap_or_default(),
+                        expires: audit.expires.clone().unwrap_or_default(),
+                    },
+                    method => return Err(format!("audit.method: unknown audit method '{}'", method)),
+                },
+                auditor: audit.auditor.clone().unwrap_or_default(),
+                date: audit.date.clone().unwrap_or_default(),
+            },
+            "exempted" => AuditStatus::Exempted {
+                reason: audit.reason.clone().unwrap_or_default(),
+                expires: audit.expires.clone(),
+            },
+            _ => AuditStatus::Unaudited,
+        };
+        
+        Ok(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source,
+            checksum: package.checksum.clone(),
+            classification,
+            audit_status,
+            annotations: package.attributes.iter()
+                .map(|(key, value)| RustAnnotation::new(key.clone(), value.clone()))
+                .collect(),
+        })
+    }
+    
+    /// Parse a mechanical category in the form written by `export_package`
+    fn mechanical_category(category: &str) -> Option<MechanicalCategory> {
+        if let Some(name) = category.strip_prefix("other:") {
+            return Some(MechanicalCategory::Other(name.to_string()));
+        }
+        match category {
+            "Utility" => Some(MechanicalCategory::Utility),
+            "DataStructures" => Some(MechanicalCategory::DataStructures),
+            "Testing" => Some(MechanicalCategory::Testing),
+            "Development" => Some(MechanicalCategory::Development),
+            "Documentation" => Some(MechanicalCategory::Documentation),
+            _ => None,
+        }
+    }
+    
+    fn dependency_kind(kind: &DependencyKind) -> &'static str {
+        match kind {
+            DependencyKind::Normal => "normal",
+            DependencyKind::Build => "build",
+            DependencyKind::Dev => "dev",
+        }
+    }
+    
+    fn check(errors: Vec<String>, context: &str) -> Result<()> {
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(AdapterError::SchemaValidationFailed {
+                errors,
+                source: anyhow::anyhow!("{}", context),
+            }),
+        }
+    }
+    
+    fn invalid(errors: Vec<String>) -> AdapterError {
+        AdapterError::SchemaValidationFailed {
+            errors,
+            source: anyhow::anyhow!("Invalid universal epoch document"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use std::path::PathBuf;
+    
+    #[test]
+    fn test_epoch_round_trip() {
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.metadata.lockfile_fingerprint = "lock-1".to_string();
+        let ring = PackageNode {
+            audit_status: AuditStatus::Audited {
+                method: AuditMethod::CargoVet { criteria: "safe-to-deploy".to_string() },
+                auditor: "security-team".to_string(),
+                date: "2024-01-01".to_string(),
+            },
+            ..package("ring", "1.0.0", Classification::TCS {
+                category: TcsCategory::Cryptography,
+                rationale: "crypto primitives".to_string(),
+            })
+        };
+        let itoa = PackageNode {
+            audit_status: AuditStatus::Exempted { reason: "trusted".to_string(), expires: None },
+            ..package("itoa", "1.0.0", Classification::Mechanical { category: MechanicalCategory::Other("formatting".to_string()) })
+        };
+        graph.add_edge(DependencyEdge {
+            from: ring.id,
+            to: itoa.id,
+            kind: DependencyKind::Build,
+            target: None,
+            optional: false,
+            features: vec!["std".to_string()],
+        });
+        graph.add_package(ring);
+        graph.add_package(itoa);
+        
+        let mut snapshot = VendorSnapshot::new("2024-q1".to_string(), PathBuf::from("vendor"));
+        snapshot.lockfile_fingerprint = "lock-1".to_string();
+        snapshot.content_digest = "digest".to_string();
+        
+        let exporter = EpochExporter::new(&RustAdapterConfig::default());
+        let document = exporter.export(&snapshot, &graph).unwrap();
+        assert_eq!(document.packages[1].classification.category.as_deref(), Some("Cryptography"));
+        assert_eq!(document.edges[0].from, "ring@1.0.0");
+        
+        let imported = exporter.import(&serde_json::to_string(&document).unwrap()).unwrap();
+        assert_eq!(imported.epoch_id, "2024-q1");
+        assert_eq!(exporter.export(&snapshot, &imported.graph).unwrap(), document);
+        
+        // Graphs of another Cargo.lock are not exported as the epoch
+        graph.metadata.lockfile_fingerprint = "lock-2".to_string();
+        assert!(matches!(exporter.export(&snapshot, &graph), Err(AdapterError::EpochInvalidated { .. })));
+        
+        // Invalid documents are rejected with every violation listed
+        let mut invalid = document.clone();
+        invalid.packages[0].classification.kind = "trusted".to_string();
+        invalid.edges[0].to = "missing@1.0.0".to_string();
+        match exporter.import(&serde_json::to_string(&invalid).unwrap()) {
+            Err(AdapterError::SchemaValidationFailed { errors, .. }) => assert_eq!(errors.len(), 2),
+            other => panic!("expected schema validation error, got {:?}", other),
+        }
+        
+        invalid = document;
+        invalid.schema_version = "2.0".to_string();
+        assert!(exporter.import(&serde_json::to_string(&invalid).unwrap()).is_err());
+    }
+}
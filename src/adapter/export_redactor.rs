@@ -0,0 +1,423 @@
+//! Redaction of exports shared with third parties
+//!
+//! Graphs, SBOMs and drift timelines name internal crates and point at
+//! local paths and internal hosts. The redactor rewrites them in place:
+//! internal package names are replaced by a salted hash of the name,
+//! descriptive metadata of internal packages is dropped, local paths are
+//! always dropped, and URLs matching the configured location patterns are
+//! dropped wherever they appear. Every export uses the same pseudonym for
+//! the same name, so redacted documents can still be correlated.
+
+use crate::adapter::sbom_generator::Sbom;
+use crate::models::*;
+use crate::utils::NamePattern;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Replacement for dropped paths and URLs where a value is required
+pub const REDACTED: &str = "REDACTED";
+
+/// Prefix of package name pseudonyms
+const PSEUDONYM_PREFIX: &str = "redacted-";
+
+/// Hex characters of the name hash kept in pseudonyms
+const PSEUDONYM_HASH_LENGTH: usize = 16;
+
+/// Annotations describing a package, dropped for internal packages
+const DESCRIPTIVE_ANNOTATIONS: &[&str] = &[
+    annotation_keys::DESCRIPTION,
+    annotation_keys::HOMEPAGE,
+    annotation_keys::REPOSITORY,
+    annotation_keys::AUTHORS,
+    annotation_keys::PUBLISHER,
+    annotation_keys::KEYWORDS,
+    annotation_keys::CATEGORIES,
+];
+
+/// Annotations holding a URL, dropped when it matches a location pattern
+const LOCATION_ANNOTATIONS: &[&str] = &[
+    annotation_keys::HOMEPAGE,
+    annotation_keys::REPOSITORY,
+];
+
+/// CycloneDX properties naming or locating a package
+const PACKAGE_NAME_PROPERTY: &str = "rust:package_name";
+const PACKAGE_SOURCE_PROPERTY: &str = "rust:package_source";
+
+/// Export redactor implementation
+#[derive(Debug, Clone)]
+pub struct ExportRedactor {
+    /// Redaction configuration
+    config: ExportRedactionConfig,
+    /// Internal package name patterns
+    internal_packages: Vec<NamePattern>,
+    /// Location patterns of URLs to drop
+    redacted_locations: Vec<NamePattern>,
+    /// Whether redactor is ready
+    ready: bool,
+}
+
+impl ExportRedactor {
+    /// Create new export redactor with configuration
+    ///
+    /// Invalid patterns are skipped with a warning; configuration
+    /// validation reports them as errors.
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        let config = config.export_redaction_config.clone();
+        let parse = |patterns: &[String]| -> Vec<NamePattern> {
+            patterns.iter()
+                .filter_map(|pattern| match NamePattern::parse(pattern) {
+                    Ok(pattern) => Some(pattern),
+                    Err(e) => {
+                        tracing::warn!(pattern = %pattern, error = %e, "Ignoring invalid export redaction pattern");
+                        None
+                    },
+                })
+                .collect()
+        };
+        
+        Self {
+            internal_packages: parse(&config.internal_packages),
+            redacted_locations: parse(&config.redacted_locations),
+            config,
+            ready: true,
+        }
+    }
+    
+    /// Check if redactor is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Whether exports should be redacted
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+    
+    /// Pseudonym of a package name
+    pub fn pseudonym(&self, name: &str) -> String {
+        let digest = Sha256::new()
+            .chain_update(self.config.salt.as_bytes())
+            .chain_update([0])
+            .chain_update(name.as_bytes())
+            .finalize();
+        let hash: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("{}{}", PSEUDONYM_PREFIX, &hash[..PSEUDONYM_HASH_LENGTH])
+    }
+    
+    /// Whether a package is internal, given whether it comes from a local path
+    pub fn is_internal(&self, name: &str, local: bool) -> bool {
+        (local && self.config.redact_path_packages)
+            || self.internal_packages.iter().any(|pattern| pattern.matches(name))
+    }
+    
+    /// Whether a URL or Cargo.lock source string matches a location pattern
+    ///
+    /// Patterns are matched against the value as written, without a
+    /// `git+`/`registry+` kind prefix, and without a query or fragment.
+    pub fn is_redacted_location(&self, location: &str) -> bool {
+        let unprefixed = ["git+", "registry+"].iter()
+            .find_map(|prefix| location.strip_prefix(prefix))
+            .unwrap_or(location);
+        let bare = unprefixed.split(['?', '#']).next().unwrap_or(unprefixed);
+        
+        [location, unprefixed, bare].iter()
+            .any(|candidate| self.redacted_locations.iter().any(|pattern| pattern.matches(candidate)))
+    }
+    
+    /// Redact a dependency graph
+    pub fn redact_graph(&self, graph: &mut DependencyGraph) {
+        for package in &mut graph.root_packages {
+            let local = matches!(package.source, PackageSource::Local { .. });
+            if self.is_internal(&package.name, local) {
+                package.name = self.pseudonym(&package.name);
+                package.annotations.retain(|a| !DESCRIPTIVE_ANNOTATIONS.contains(&a.key.as_str()));
+            }
+            
+            package.source = self.redact_source(&package.source);
+            package.annotations.retain(|a| {
+                !LOCATION_ANNOTATIONS.contains(&a.key.as_str())
+                    || !a.value.as_str().is_some_and(|url| self.is_redacted_location(url))
+            });
+        }
+    }
+    
+    /// Redact an SBOM
+    ///
+    /// SPDX identifiers derived from internal names are renamed, and
+    /// relationships follow the new identifiers.
+    pub fn redact_sbom(&self, sbom: &mut Sbom) {
+        match sbom {
+            Sbom::Spdx(doc) => self.redact_spdx(doc),
+            Sbom::CycloneDx(doc) => self.redact_cyclonedx(doc),
+        }
+    }
+    
+    /// Redact a drift report
+    pub fn redact_drift_report(&self, report: &mut DriftReport) {
+        for drift in &mut report.drifts {
+            let local = [&drift.previous_source, &drift.current_source].iter()
+                .any(|source| matches!(source, Some(PackageSource::Local { .. })));
+            if self.is_internal(&drift.package_name, local) {
+                drift.package_name = self.pseudonym(&drift.package_name);
+                drift.details = None;
+            }
+            
+            drift.previous_source = drift.previous_source.as_ref().map(|source| self.redact_source(source));
+            drift.current_source = drift.current_source.as_ref().map(|source| self.redact_source(source));
+            
+            // Workspace members are path packages by definition
+            for member in &mut drift.workspace_members {
+                if self.is_internal(member, true) {
+                    *member = self.pseudonym(member);
+                }
+            }
+        }
+    }
+    
+    /// Redact a drift timeline
+    ///
+    /// Path packages have no Cargo.lock source, so a package is local when
+    /// any of its changes has a version without a source.
+    pub fn redact_drift_timeline(&self, timeline: &mut DriftTimeline) {
+        let packages = std::mem::take(&mut timeline.packages);
+        let mut redacted: BTreeMap<String, Vec<TimelineChange>> = BTreeMap::new();
+        
+        for (name, mut changes) in packages {
+            let local = changes.iter().any(|change| {
+                (change.previous_version.is_some() && change.previous_source.is_none())
+                    || (change.current_version.is_some() && change.current_source.is_none())
+            });
+            let name = match self.is_internal(&name, local) {
+                true => self.pseudonym(&name),
+                false => name,
+            };
+            
+            for change in &mut changes {
+                for source in [&mut change.previous_source, &mut change.current_source] {
+                    if source.as_deref().is_some_and(|s| self.is_redacted_location(s)) {
+                        *source = Some(REDACTED.to_string());
+                    }
+                }
+            }
+            redacted.entry(name).or_default().extend(changes);
+        }
+        
+        timeline.packages = redacted;
+    }
+    
+    /// Redact the location of a package source
+    fn redact_source(&self, source: &PackageSource) -> PackageSource {
+        match source {
+            PackageSource::Local { .. } => PackageSource::Local { path: REDACTED.to_string() },
+            PackageSource::Git { url, rev, checksum } if self.is_redacted_location(url) => PackageSource::Git {
+                url: REDACTED.to_string(),
+                rev: rev.clone(),
+                checksum: checksum.clone(),
+            },
+            PackageSource::Registry { url, checksum } if self.is_redacted_location(url) => PackageSource::Registry {
+                url: REDACTED.to_string(),
+                checksum: checksum.clone(),
+            },
+            source => source.clone(),
+        }
+    }
+    
+    /// Redact an SPDX document
+    fn redact_spdx(&self, doc: &mut SpdxDocument) {
+        let mut renamed_ids = HashMap::new();
+        
+        for package in &mut doc.packages {
+            let local = package.download_location.as_deref().is_some_and(|l| l.starts_with("file://"));
+            if self.is_internal(&package.name, local) {
+                let pseudonym = self.pseudonym(&package.name);
+                let spdx_id = format!("SPDXRef-{}-{}", pseudonym, package.version.replace('.', "_"));
+                renamed_ids.insert(std::mem::replace(&mut package.spdx_id, spdx_id.clone()), spdx_id);
+                package.name = pseudonym;
+                package.summary = None;
+                package.description = None;
+                package.homepage = None;
+                package.supplier = None;
+                package.source_info = None;
+                package.copyright_text = None;
+                package.external_refs.clear();
+            }
+            
+            if local || package.download_location.as_deref().is_some_and(|l| self.is_redacted_location(l)) {
+                package.download_location = None;
+                for reference in package.external_refs.iter_mut().filter(|r| r.reference_type == "purl") {
+                    reference.reference_locator = unqualified_purl(&reference.reference_locator);
+                }
+            }
+            if package.homepage.as_deref().is_some_and(|url| self.is_redacted_location(url)) {
+                package.homepage = None;
+            }
+            package.external_refs.retain(|r| !self.is_redacted_location(&r.reference_locator));
+        }
+        
+        for relationship in &mut doc.relationships {
+            for id in [&mut relationship.spdx_element_id, &mut relationship.related_spdx_element] {
+                if let Some(renamed) = renamed_ids.get(id.as_str()) {
+                    *id = renamed.clone();
+                }
+            }
+        }
+    }
+    
+    /// Redact a CycloneDX document, including the project root component
+    ///
+    /// The root component describes the project itself, a path package;
+    /// its subcomponents are the first-party workspace crates, local too.
+    fn redact_cyclonedx(&self, doc: &mut CycloneDxDocument) {
+        let mut renamed_refs = HashMap::new();
+        let mut subcomponents = doc.metadata.component.as_mut().and_then(|root| root.components.take());
+        let root = doc.metadata.component.iter_mut().map(|component| (true, true, component));
+        let first_party = subcomponents.iter_mut().flatten().map(|component| (false, true, component));
+        let third_party = doc.components.iter_mut().map(|component| (false, false, component));
+        for (is_root, workspace, component) in root.chain(first_party).chain(third_party) {
+            let property = |name: &str| component.properties.iter().flatten()
+                .find(|p| p.name == name)
+                .map(|p| p.value.clone());
+            let package_name = property(PACKAGE_NAME_PROPERTY);
+            let source = property(PACKAGE_SOURCE_PROPERTY);
+            let local = workspace || source.as_deref().is_some_and(|s| s.starts_with("Local"));
+            
+            let internal = self.is_internal(&component.name, local)
+                || package_name.as_deref().is_some_and(|name| self.is_internal(name, local));
+            if internal {
+                component.name = self.pseudonym(package_name.as_deref().unwrap_or(&component.name));
+                component.description = None;
+                component.supplier = None;
+                component.cpe = None;
+                component.external_references = None;
+            }
+            
+            // Package URLs name the package and qualify it with its source
+            let redacted_source = local || source.as_deref().is_some_and(|s| self.source_mentions_redacted_location(s));
+            if !is_root && (internal || redacted_source) {
+                let purl = component.purl.as_deref().map(unqualified_purl).map(|purl| {
+                    let version = purl.rsplit_once('@').map_or("", |(_, version)| version);
+                    if internal { format!("pkg:cargo/{}@{}", component.name, version) } else { purl.clone() }
+                });
+                if let (Some(bom_ref), Some(purl)) = (&mut component.bom_ref, &purl) {
+                    renamed_refs.insert(std::mem::replace(bom_ref, purl.clone()), purl.clone());
+                }
+                component.purl = purl;
+            }
+            
+            if let Some(references) = &mut component.external_references {
+                references.retain(|r| !self.is_redacted_location(&r.url));
+            }
+            if let Some(properties) = &mut component.properties {
+                properties.retain(|p| !(internal && p.name == PACKAGE_NAME_PROPERTY));
+                for property in properties.iter_mut().filter(|p| p.name == PACKAGE_SOURCE_PROPERTY) {
+                    if local || self.source_mentions_redacted_location(&property.value) {
+                        property.value = REDACTED.to_string();
+                    }
+                }
+            }
+        }
+        if let Some(root) = doc.metadata.component.as_mut() {
+            root.components = subcomponents;
+        }
+        
+        for dependency in &mut doc.dependencies {
+            for reference in std::iter::once(&mut dependency.r#ref).chain(dependency.depends_on.iter_mut()) {
+                if let Some(renamed) = renamed_refs.get(reference.as_str()) {
+                    *reference = renamed.clone();
+                }
+            }
+        }
+    }
+    
+    /// Whether a debug-formatted package source contains a redacted URL
+    fn source_mentions_redacted_location(&self, source: &str) -> bool {
+        source.split('"').any(|part| self.is_redacted_location(part))
+    }
+}
+
+/// Package URL without the qualifiers naming its source
+fn unqualified_purl(purl: &str) -> String {
+    purl.split(['?', '#']).next().unwrap_or(purl).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    
+    fn redactor() -> ExportRedactor {
+        let config = RustAdapterConfig {
+            export_redaction_config: ExportRedactionConfig {
+                enabled: true,
+                internal_packages: vec!["acme-*".to_string()],
+                redacted_locations: vec!["https://git.acme.example/*".to_string()],
+                salt: "pepper".to_string(),
+                ..ExportRedactionConfig::default()
+            },
+            ..RustAdapterConfig::default()
+        };
+        ExportRedactor::new(&config)
+    }
+    
+    fn described(name: &str, source: PackageSource) -> PackageNode {
+        let mut package = PackageNode { source, ..package(name, "1.0.0", Classification::Unknown) };
+        package.set_annotation(annotation_keys::DESCRIPTION, serde_json::json!("Package description"));
+        package.set_annotation(annotation_keys::REPOSITORY, serde_json::json!("https://git.acme.example/billing"));
+        package
+    }
+    
+    #[test]
+    fn test_redaction_is_consistent_across_exports() {
+        let redactor = redactor();
+        let pseudonym = redactor.pseudonym("acme-billing");
+        assert!(pseudonym.starts_with(PSEUDONYM_PREFIX));
+        assert_ne!(pseudonym, ExportRedactor::new(&RustAdapterConfig::default()).pseudonym("acme-billing"));
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(described("acme-billing", PackageSource::Git {
+            url: "https://git.acme.example/billing?branch=main".to_string(),
+            rev: "abc123".to_string(),
+            checksum: String::new(),
+        }));
+        graph.add_package(described("workspace-app", PackageSource::Local { path: "/home/dev/app".to_string() }));
+        graph.add_package(described("serde", PackageSource::Registry {
+            url: "https://github.com/rust-lang/crates.io-index".to_string(),
+            checksum: "abc".to_string(),
+        }));
+        redactor.redact_graph(&mut graph);
+        
+        let billing = &graph.root_packages[0];
+        assert_eq!(billing.name, pseudonym);
+        assert!(matches!(&billing.source, PackageSource::Git { url, .. } if url == REDACTED));
+        assert!(billing.annotations.is_empty());
+        let app = &graph.root_packages[1];
+        assert_eq!(app.name, redactor.pseudonym("workspace-app"));
+        assert!(matches!(&app.source, PackageSource::Local { path } if path == REDACTED));
+        let serde = &graph.root_packages[2];
+        assert_eq!(serde.name, "serde");
+        assert_eq!(serde.annotation_str(annotation_keys::DESCRIPTION), Some("Package description"));
+        assert!(serde.annotation(annotation_keys::REPOSITORY).is_none());
+        
+        let mut timeline = DriftTimeline {
+            since_epoch_id: "e1".to_string(),
+            lockfile_fingerprint: String::new(),
+            generated_at: String::new(),
+            epochs: Vec::new(),
+            packages: BTreeMap::from([("acme-billing".to_string(), vec![TimelineChange {
+                approved_in: None,
+                previous_epoch_id: "e1".to_string(),
+                change_type: ChangeType::VersionChange,
+                previous_version: Some("0.9.0".to_string()),
+                current_version: Some("1.0.0".to_string()),
+                previous_source: Some("git+https://git.acme.example/billing#abc122".to_string()),
+                current_source: Some("git+https://git.acme.example/billing#abc123".to_string()),
+                priority: Priority::Medium,
+            }])]),
+            statistics: TimelineStatistics::default(),
+        };
+        redactor.redact_drift_timeline(&mut timeline);
+        let changes = &timeline.packages[&pseudonym];
+        assert_eq!(changes[0].current_source.as_deref(), Some(REDACTED));
+    }
+}
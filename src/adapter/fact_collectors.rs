@@ -0,0 +1,213 @@
+//! Custom fact collectors
+//!
+//! Organizations attach their own facts to packages, such as internal
+//! registry metadata or links to review tickets, by configuring external
+//! commands in `fact_collectors`. Each collector runs once per matching
+//! package during parsing and answers with a JSON object on stdout:
+//!
+//! ```text
+//! $ internal-meta serde 1.0.188
+//! {"owner": "platform-team", "ticket": "https://tracker.example/SEC-12"}
+//! ```
+//!
+//! Every entry becomes a `<collector>.<key>` annotation on the package, so
+//! collectors cannot overwrite the adapter's own annotations or each
+//! other's. A collector that fails, times out or prints anything but an
+//! object is skipped with a warning unless it is marked `required`.
+
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use crate::utils::{CancellationToken, NamePattern, ProcessInvoker, ToolInvocation, ToolInvoker, ToolOutput};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Runner of the configured fact collectors
+#[derive(Debug, Clone)]
+pub struct FactCollectorRunner {
+    /// Configured collectors
+    collectors: Vec<FactCollectorConfig>,
+    /// Permits bounding the external tools running at once
+    tool_limiter: Arc<Semaphore>,
+    /// Whether runner is ready
+    ready: bool,
+    /// Token that stops running collectors when cancelled
+    cancel: CancellationToken,
+    /// Runs the collector commands
+    tools: Arc<dyn ToolInvoker>,
+}
+
+impl FactCollectorRunner {
+    /// Create new runner with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            collectors: config.fact_collectors.clone(),
+            tool_limiter: Arc::new(Semaphore::new(config.performance_config.max_parallel_tool_invocations.max(1))),
+            ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
+        }
+    }
+    
+    /// Stop running collectors when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run collectors through `tools` instead of spawning them directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
+    /// Take tool permits from `limiter`, shared with other components running tools
+    pub fn with_tool_limiter(mut self, limiter: Arc<Semaphore>) -> Self {
+        self.tool_limiter = limiter;
+        self
+    }
+    
+    /// Check if runner is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Run every collector for its packages and annotate the graph
+    ///
+    /// Returns the number of annotations set. Errors of required
+    /// collectors and cancellation abort; other failures are logged.
+    pub async fn annotate_graph(&self, project: &Project, graph: &mut DependencyGraph) -> Result<usize> {
+        let mut annotated = 0;
+        for collector in &self.collectors {
+            let patterns: Vec<NamePattern> = collector.packages.iter()
+                .filter_map(|pattern| NamePattern::parse(pattern).ok())
+                .collect();
+            let mut tasks = tokio::task::JoinSet::new();
+            for (index, package) in graph.root_packages.iter().enumerate() {
+                if !patterns.is_empty() && !patterns.iter().any(|pattern| pattern.matches(&package.name)) {
+                    continue;
+                }
+                let invocation = Self::invocation(collector, project, package);
+                let (tools, cancel, limiter) = (self.tools.clone(), self.cancel.clone(), self.tool_limiter.clone());
+                tasks.spawn(async move {
+                    let _permit = limiter.acquire_owned().await;
+                    (index, tools.invoke(&invocation, &cancel).await)
+                });
+            }
+            
+            let mut results = Vec::new();
+            while let Some(joined) = tasks.join_next().await {
+                let (index, result) = joined.map_err(|e| AdapterError::Internal {
+                    message: format!("Fact collector {} panicked", collector.name),
+                    source: anyhow::anyhow!("{}", e),
+                })?;
+                results.push((index, result));
+            }
+            // Apply in graph order so annotation order does not depend on timing
+            results.sort_by_key(|(index, _)| *index);
+            
+            for (index, result) in results {
+                let package = &mut graph.root_packages[index];
+                match Self::facts(collector, result) {
+                    Ok(facts) => {
+                        for (key, value) in facts {
+                            package.set_annotation(&format!("{}.{}", collector.name, key), value);
+                            annotated += 1;
+                        }
+                    },
+                    Err(e @ AdapterError::Cancelled { .. }) => return Err(e),
+                    Err(e) if collector.required => return Err(e),
+                    Err(e) => tracing::warn!(collector = %collector.name, package = %package.name, error = %e, "Fact collector failed"),
+                }
+            }
+        }
+        Ok(annotated)
+    }
+    
+    /// Command line of a collector for one package
+    fn invocation(collector: &FactCollectorConfig, project: &Project, package: &PackageNode) -> ToolInvocation {
+        let source = match &package.source {
+            PackageSource::Registry { url, .. } => url.clone(),
+            PackageSource::Git { url, rev, .. } => format!("{}#{}", url, rev),
+            PackageSource::Local { path } => path.clone(),
+        };
+        let substitute = |arg: &String| arg
+            .replace("{name}", &package.name)
+            .replace("{version}", &package.version)
+            .replace("{source}", &source)
+            .replace("{checksum}", &package.checksum);
+        
+        let program = collector.command.first().map(String::as_str).unwrap_or_default();
+        ToolInvocation::new(&collector.name, program)
+            .args(collector.command.iter().skip(1).map(substitute))
+            .current_dir(&project.paths.root)
+            .timeout(Some(Duration::from_secs(collector.timeout)))
+    }
+    
+    /// Entries of a collector's JSON object output
+    fn facts(collector: &FactCollectorConfig, result: Result<ToolOutput>) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let output = result?;
+        if !output.is_success() {
+            return Err(AdapterError::ToolExecutionFailed {
+                tool: collector.name.clone(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+                source: anyhow::anyhow!("Fact collector failed"),
+            });
+        }
+        match serde_json::from_str(&output.stdout) {
+            Ok(serde_json::Value::Object(facts)) => Ok(facts),
+            _ => Err(AdapterError::MetadataParseError {
+                field: collector.name.clone(),
+                value: output.stdout.chars().take(200).collect(),
+                source: anyhow::anyhow!("Fact collector output is not a JSON object"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use crate::utils::ReplayInvoker;
+    
+    fn collector(name: &str, packages: &[&str], required: bool) -> FactCollectorConfig {
+        FactCollectorConfig {
+            name: name.to_string(),
+            command: vec!["internal-meta".to_string(), "{name}".to_string(), "{version}".to_string()],
+            packages: packages.iter().map(|p| p.to_string()).collect(),
+            timeout: 5,
+            required,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_collectors_annotate_matching_packages() {
+        let project = Project::new("test".to_string(), "Test".to_string(), "rust".to_string(), std::env::temp_dir());
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        for name in ["acme-auth", "acme-db", "serde"] {
+            graph.add_package(package(name, "1.0.0", Classification::Unknown));
+        }
+        let tools = ReplayInvoker::new()
+            .with_output("internal-meta acme-auth 1.0.0", ToolOutput::success(r#"{"owner": "platform", "ticket": "SEC-12"}"#))
+            .with_output("internal-meta acme-db 1.0.0", ToolOutput::success("not json"));
+        let mut config = RustAdapterConfig {
+            fact_collectors: vec![collector("internal", &["acme-*"], false)],
+            ..RustAdapterConfig::default()
+        };
+        let runner = FactCollectorRunner::new(&config).with_tool_invoker(Arc::new(tools.clone()));
+        
+        // Invalid output is skipped, packages outside the patterns are never run
+        assert_eq!(runner.annotate_graph(&project, &mut graph).await.unwrap(), 2);
+        let auth = graph.find_package("acme-auth", "1.0.0").unwrap();
+        assert_eq!(auth.annotation_str("internal.owner"), Some("platform"));
+        assert_eq!(auth.annotation_str("internal.ticket"), Some("SEC-12"));
+        assert!(graph.find_package("acme-db", "1.0.0").unwrap().annotations.is_empty());
+        
+        config.fact_collectors = vec![collector("internal", &["acme-*"], true)];
+        let runner = FactCollectorRunner::new(&config).with_tool_invoker(Arc::new(tools));
+        let error = runner.annotate_graph(&project, &mut graph).await.unwrap_err();
+        assert!(matches!(error, AdapterError::MetadataParseError { .. }));
+    }
+}
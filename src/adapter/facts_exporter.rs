@@ -0,0 +1,475 @@
+//! Policy facts export for external policy engines
+//!
+//! This module flattens the dependency graph, classification, audit
+//! results and vendor snapshot state into a single `PolicyFacts`
+//! document. Organizations evaluate it with their own OPA/Rego or CUE
+//! policies; the exporter itself never makes a policy decision.
+
+use crate::error::Result;
+use crate::models::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Policy facts exporter implementation
+#[derive(Debug, Clone)]
+pub struct FactsExporter {
+    /// Exporter configuration
+    config: FactsExporterConfig,
+    /// Whether exporter is ready
+    ready: bool,
+}
+
+/// Configuration for policy facts exporter
+#[derive(Debug, Clone)]
+pub struct FactsExporterConfig {
+    /// Include package annotations in the exported facts
+    pub include_annotations: bool,
+    /// Whether first-party workspace packages count towards audit requirements
+    pub audit_first_party: bool,
+}
+
+impl FactsExporter {
+    /// Create new facts exporter with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            config: FactsExporterConfig {
+                include_annotations: config.facts_config.include_annotations,
+                audit_first_party: config.audit_config.audit_first_party,
+            },
+            ready: true,
+        }
+    }
+    
+    /// Check if exporter is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Flatten a classified graph and optional audit and vendor facts
+    pub fn export(
+        &self,
+        graph: &DependencyGraph,
+        audit: Option<&AuditReport>,
+        vendor: Option<VendorFacts>,
+    ) -> PolicyFacts {
+        let keys: HashMap<PackageId, String> = graph.root_packages.iter()
+            .map(|p| (p.id, PolicyFacts::package_key(&p.name, &p.version)))
+            .collect();
+        let findings = audit.map(|report| report.findings.as_slice()).unwrap_or_default();
+        // Findings without a package version apply to every version of the package
+        let affected = |finding: &AuditFinding| -> Vec<String> {
+            let mut affected: Vec<String> = graph.root_packages.iter()
+                .filter(|p| p.name == finding.package_name)
+                .filter(|p| finding.package_version.as_ref().is_none_or(|version| &p.version == version))
+                .map(|p| keys[&p.id].clone())
+                .collect();
+            affected.sort();
+            affected.dedup();
+            affected
+        };
+        let affected: Vec<Vec<String>> = findings.iter().map(affected).collect();
+        
+        let mut packages = BTreeMap::new();
+        for package in &graph.root_packages {
+            let mut facts = self.package_facts(package);
+            facts.dependencies = graph.get_dependencies(&package.id).iter()
+                .filter_map(|edge| keys.get(&edge.to).cloned())
+                .collect();
+            facts.dependencies.sort();
+            facts.dependencies.dedup();
+            let key = &keys[&package.id];
+            facts.findings = findings.iter().zip(&affected)
+                .filter(|(_, affected)| affected.contains(key))
+                .map(|(f, _)| f.id.clone())
+                .collect();
+            packages.insert(key.clone(), facts);
+        }
+        
+        let edges = graph.edges.iter()
+            .filter_map(|edge| {
+                Some(EdgeFacts {
+                    from: keys.get(&edge.from)?.clone(),
+                    to: keys.get(&edge.to)?.clone(),
+                    kind: Self::dependency_kind(&edge.kind).to_string(),
+                    target: edge.target.clone(),
+                    optional: edge.optional,
+                })
+            })
+            .collect();
+        
+        let findings: Vec<_> = findings.iter().zip(affected)
+            .map(|(f, packages)| FindingFacts {
+                id: f.id.clone(),
+                package: f.package_name.clone(),
+                packages,
+                severity: f.severity.as_str().to_string(),
+                cvss_score: f.cvss_score,
+                patched_versions: f.patched_versions.clone(),
+                applicability: f.applicability.clone(),
+                minimal_safe_version: f.minimal_safe_version.clone(),
+                source: f.source.clone(),
+                affects_tcs: f.affects_tcs,
+            })
+            .collect();
+        
+        let summary = self.summarize(&packages, &findings);
+        
+        PolicyFacts {
+            schema_version: POLICY_FACTS_SCHEMA_VERSION.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            project: ProjectFacts {
+                id: graph.project_id.clone(),
+                ecosystem: graph.ecosystem.clone(),
+                lockfile_fingerprint: graph.metadata.lockfile_fingerprint.clone(),
+                offline_mode: graph.metadata.offline_mode,
+            },
+            packages,
+            edges,
+            findings,
+            audit: audit.map(Self::audit_facts),
+            vendor,
+            audit_criteria_gaps: Vec::new(),
+            tcs_pinning: Vec::new(),
+            summary,
+        }
+    }
+    
+    /// Record the TCS packages missing cargo-vet criteria their category requires
+    ///
+    /// `requirements` maps TCS categories (in any form `TcsCategory`
+    /// parses) to required criteria. Unaudited packages meet no criteria;
+    /// packages audited by other means than cargo-vet meet none either.
+    /// First-party packages are skipped unless `audit_first_party` is set.
+    pub fn add_criteria_gaps(
+        &self,
+        facts: &mut PolicyFacts,
+        graph: &DependencyGraph,
+        requirements: &BTreeMap<String, Vec<String>>,
+    ) {
+        for package in &graph.root_packages {
+            if package.is_first_party() && !self.config.audit_first_party {
+                continue;
+            }
+            let category = match &package.classification {
+                Classification::TCS { category, .. } => category.to_string(),
+                _ => continue,
+            };
+            let required = requirements.iter()
+                .find(|(key, _)| key.parse::<TcsCategory>().map(|c| c.to_string()).as_ref() == Ok(&category))
+                .map(|(_, required)| required);
+            let required = match required {
+                Some(required) if !required.is_empty() => required,
+                _ => continue,
+            };
+            
+            let met = match &package.audit_status {
+                AuditStatus::Audited { method: AuditMethod::CargoVet { criteria }, .. } => met_criteria(criteria),
+                _ => Vec::new(),
+            };
+            let missing: Vec<String> = required.iter().filter(|c| !met.contains(c)).cloned().collect();
+            if missing.is_empty() {
+                continue;
+            }
+            
+            facts.audit_criteria_gaps.push(AuditCriteriaGapFacts {
+                package: PolicyFacts::package_key(&package.name, &package.version),
+                category,
+                required: required.clone(),
+                met,
+                missing,
+            });
+        }
+        
+        facts.audit_criteria_gaps.sort_by(|a, b| a.package.cmp(&b.package));
+        facts.audit_criteria_gaps.dedup_by(|a, b| a.package == b.package);
+        facts.summary.criteria_gap_packages = facts.audit_criteria_gaps.len();
+    }
+    
+    /// Record the version pinning of TCS dependencies
+    pub fn add_pinning(&self, facts: &mut PolicyFacts, pinning: Vec<PinningFacts>) {
+        facts.summary.wildcard_tcs_dependencies = pinning.iter()
+            .filter(|p| p.pinning == VersionPinning::Wildcard)
+            .count();
+        facts.summary.updatable_tcs_dependencies = pinning.iter()
+            .filter(|p| p.update_available == Some(true))
+            .count();
+        facts.tcs_pinning = pinning;
+    }
+    
+    /// Describe the outcome of verifying the vendor snapshot of an epoch
+    pub fn vendor_facts(
+        &self,
+        epoch_id: &str,
+        verification: Result<VendorSnapshot>,
+        lockfile_fingerprint: &str,
+    ) -> VendorFacts {
+        match verification {
+            Ok(snapshot) => VendorFacts {
+                epoch_id: epoch_id.to_string(),
+                verified: true,
+                error: None,
+                lockfile_matches: snapshot.lockfile_fingerprint == lockfile_fingerprint,
+                total_packages: snapshot.total_packages,
+                content_digest: snapshot.content_digest,
+                created_at: Some(snapshot.created_at),
+            },
+            Err(e) => VendorFacts {
+                epoch_id: epoch_id.to_string(),
+                verified: false,
+                error: Some(e.to_string()),
+                lockfile_matches: false,
+                total_packages: 0,
+                content_digest: String::new(),
+                created_at: None,
+            },
+        }
+    }
+    
+    /// Facts for a single package, without dependencies and findings
+    fn package_facts(&self, package: &PackageNode) -> PackageFacts {
+        let (source, source_location, source_rev) = match &package.source {
+            PackageSource::Registry { url, .. } => ("registry", url.clone(), None),
+            PackageSource::Git { url, rev, .. } => ("git", url.clone(), Some(rev.clone())),
+            PackageSource::Local { path } => ("local", path.clone(), None),
+        };
+        
+        let (classification, category) = match &package.classification {
+            Classification::TCS { category, .. } => ("tcs", Some(category.to_string())),
+            Classification::Mechanical { category } => ("mechanical", Some(match category {
+                MechanicalCategory::Other(name) => format!("other:{}", name),
+                builtin => format!("{:?}", builtin),
+            })),
+            Classification::Unknown => ("unknown", None),
+        };
+        
+        let (audit_status, audit_criteria, exemption_expires) = match &package.audit_status {
+            AuditStatus::Audited { method: AuditMethod::CargoVet { criteria }, .. } => {
+                ("audited", Some(criteria.clone()), None)
+            },
+            AuditStatus::Audited { .. } => ("audited", None, None),
+            AuditStatus::Exempted { expires, .. } => ("exempted", None, expires.clone()),
+            AuditStatus::Unaudited => ("unaudited", None, None),
+        };
+        
+        let annotations = if self.config.include_annotations {
+            package.annotations.iter()
+                .map(|a| (a.key.clone(), a.value.clone()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        
+        PackageFacts {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: source.to_string(),
+            source_location,
+            source_rev,
+            checksum: package.checksum.clone(),
+            classification: classification.to_string(),
+            category,
+            origin: package.origin().as_str().to_string(),
+            audit_status: audit_status.to_string(),
+            audit_criteria,
+            exemption_expires,
+            proc_macro: package.is_proc_macro(),
+            dependencies: Vec::new(),
+            findings: Vec::new(),
+            annotations,
+        }
+    }
+    
+    /// Facts about the audit run itself
+    fn audit_facts(report: &AuditReport) -> AuditRunFacts {
+        let metadata = &report.execution_metadata;
+        
+        AuditRunFacts {
+            timestamp: metadata.timestamp.clone(),
+            tool_versions: metadata.tool_versions.clone().into_iter().collect(),
+            exit_codes: metadata.exit_codes.clone().into_iter().collect(),
+            warnings: metadata.warnings.iter().map(|w| w.warning_type.clone()).collect(),
+        }
+    }
+    
+    /// Pre-computed counts over the exported packages and findings
+    ///
+    /// First-party packages are left out of the TCS counts unless they
+    /// are held to audit requirements.
+    fn summarize(&self, packages: &BTreeMap<String, PackageFacts>, findings: &[FindingFacts]) -> FactsSummary {
+        let mut summary = FactsSummary {
+            total_packages: packages.len(),
+            ..FactsSummary::default()
+        };
+        
+        for package in packages.values() {
+            let first_party = package.origin == PackageOrigin::FirstParty.as_str();
+            if first_party {
+                summary.first_party_packages += 1;
+            }
+            if package.classification == "tcs" && (!first_party || self.config.audit_first_party) {
+                summary.tcs_packages += 1;
+                if package.audit_status == "unaudited" {
+                    summary.unaudited_tcs_packages += 1;
+                }
+            }
+            if package.source == "git" {
+                summary.git_packages += 1;
+            }
+        }
+        
+        for finding in findings {
+            *summary.findings_by_severity.entry(finding.severity.clone()).or_insert(0) += 1;
+        }
+        
+        summary
+    }
+    
+    /// Lowercase dependency kind name
+    fn dependency_kind(kind: &DependencyKind) -> &'static str {
+        match kind {
+            DependencyKind::Normal => "normal",
+            DependencyKind::Build => "build",
+            DependencyKind::Dev => "dev",
+        }
+    }
+}
+
+impl Default for FactsExporterConfig {
+    fn default() -> Self {
+        Self {
+            include_annotations: true,
+            audit_first_party: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use crate::config::RustAdapterConfig;
+    
+    #[test]
+    fn test_export_flattens_graph_and_findings() {
+        let exporter = FactsExporter::new(&RustAdapterConfig::default());
+        
+        let app = package("app", "1.0.0", Classification::Mechanical { category: MechanicalCategory::Utility });
+        let mut ring = package("ring", "1.0.0", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        ring.set_annotation("maintainers", serde_json::json!(2));
+        let old_ring = package("ring", "0.16.20", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_edge(DependencyEdge {
+            from: app.id,
+            to: ring.id,
+            kind: DependencyKind::Normal,
+            target: None,
+            optional: false,
+            features: vec![],
+        });
+        graph.add_package(app);
+        graph.add_package(ring);
+        graph.add_package(old_ring);
+        
+        let mut report = AuditReport::new();
+        report.findings.push(AuditFinding::new(
+            "RUSTSEC-2023-0001".to_string(),
+            "ring".to_string(),
+            "<1.0.1".to_string(),
+            Severity::High,
+            "Example".to_string(),
+        ).with_package_version("1.0.0".to_string()));
+        
+        let facts = exporter.export(&graph, Some(&report), None);
+        
+        assert_eq!(facts.schema_version, POLICY_FACTS_SCHEMA_VERSION);
+        assert_eq!(facts.packages["app@1.0.0"].dependencies, vec!["ring@1.0.0".to_string()]);
+        assert_eq!(facts.packages["ring@1.0.0"].classification, "tcs");
+        assert_eq!(facts.packages["ring@1.0.0"].category.as_deref(), Some("Cryptography"));
+        assert_eq!(facts.packages["ring@1.0.0"].findings, vec!["RUSTSEC-2023-0001".to_string()]);
+        // The finding names the locked version, so the other ring is unaffected
+        assert!(facts.packages["ring@0.16.20"].findings.is_empty());
+        assert_eq!(facts.packages["ring@1.0.0"].annotations["maintainers"], 2);
+        assert_eq!(facts.edges[0].kind, "normal");
+        assert_eq!(facts.findings[0].severity, "high");
+        assert_eq!(facts.findings[0].packages, vec!["ring@1.0.0".to_string()]);
+        assert_eq!(facts.summary.unaudited_tcs_packages, 2);
+        assert_eq!(facts.summary.findings_by_severity["high"], 1);
+        assert!(facts.vendor.is_none());
+        
+        // Without a version the finding applies to every ring
+        report.findings[0].package_version = None;
+        let facts = exporter.export(&graph, Some(&report), None);
+        assert_eq!(facts.findings[0].packages, vec!["ring@0.16.20".to_string(), "ring@1.0.0".to_string()]);
+        assert_eq!(facts.packages["ring@0.16.20"].findings, vec!["RUSTSEC-2023-0001".to_string()]);
+        
+        let mut config = RustAdapterConfig::default();
+        config.facts_config.include_annotations = false;
+        let facts = FactsExporter::new(&config).export(&graph, Some(&report), None);
+        assert!(facts.packages["ring@1.0.0"].annotations.is_empty());
+    }
+    
+    #[test]
+    fn test_criteria_gaps_against_policy_matrix() {
+        let exporter = FactsExporter::new(&RustAdapterConfig::default());
+        let audited = |criteria: &str| AuditStatus::Audited {
+            method: AuditMethod::CargoVet { criteria: criteria.to_string() },
+            auditor: "security@example.com".to_string(),
+            date: "2026-01-01".to_string(),
+        };
+        
+        let mut ring = package("ring", "1.0.0", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        ring.audit_status = audited("safe-to-deploy");
+        let mut rustls = package("rustls", "1.0.0", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        rustls.audit_status = audited("crypto-safe, safe-to-deploy");
+        let serde = package("serde", "1.0.0", Classification::TCS { category: TcsCategory::Serialization, rationale: String::new() });
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(ring);
+        graph.add_package(rustls);
+        graph.add_package(serde);
+        
+        let requirements = BTreeMap::from([
+            ("Cryptography".to_string(), vec!["crypto-safe".to_string(), "safe-to-run".to_string()]),
+            ("Serialization".to_string(), vec!["safe-to-deploy".to_string()]),
+        ]);
+        let mut facts = exporter.export(&graph, None, None);
+        exporter.add_criteria_gaps(&mut facts, &graph, &requirements);
+        
+        assert_eq!(facts.summary.criteria_gap_packages, 2);
+        let ring_gap = &facts.audit_criteria_gaps[0];
+        assert_eq!(ring_gap.package, "ring@1.0.0");
+        assert_eq!(ring_gap.met, vec!["safe-to-deploy".to_string(), "safe-to-run".to_string()]);
+        assert_eq!(ring_gap.missing, vec!["crypto-safe".to_string()]);
+        let serde_gap = &facts.audit_criteria_gaps[1];
+        assert_eq!(serde_gap.package, "serde@1.0.0");
+        assert_eq!(serde_gap.missing, vec!["safe-to-deploy".to_string()]);
+    }
+    
+    #[test]
+    fn test_first_party_packages_exempt_from_audit_requirements() {
+        let tcs = || Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() };
+        let mut billing = package("payments-billing", "1.0.0", tcs());
+        billing.set_origin(PackageOrigin::FirstParty);
+        let ring = package("ring", "1.0.0", tcs());
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(billing);
+        graph.add_package(ring);
+        let requirements = BTreeMap::from([("Cryptography".to_string(), vec!["crypto-safe".to_string()])]);
+        
+        let exporter = FactsExporter::new(&RustAdapterConfig::default());
+        let mut facts = exporter.export(&graph, None, None);
+        exporter.add_criteria_gaps(&mut facts, &graph, &requirements);
+        assert_eq!(facts.packages["payments-billing@1.0.0"].origin, "first_party");
+        assert_eq!(facts.packages["ring@1.0.0"].origin, "third_party");
+        assert_eq!(facts.summary.first_party_packages, 1);
+        assert_eq!((facts.summary.tcs_packages, facts.summary.unaudited_tcs_packages), (1, 1));
+        assert_eq!(facts.summary.criteria_gap_packages, 1);
+        
+        // Opting in holds workspace members to the same requirements
+        let mut config = RustAdapterConfig::default();
+        config.audit_config.audit_first_party = true;
+        let exporter = FactsExporter::new(&config);
+        let mut facts = exporter.export(&graph, None, None);
+        exporter.add_criteria_gaps(&mut facts, &graph, &requirements);
+        assert_eq!((facts.summary.tcs_packages, facts.summary.unaudited_tcs_packages), (2, 2));
+        assert_eq!(facts.summary.criteria_gap_packages, 2);
+    }
+}
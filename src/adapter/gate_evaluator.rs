@@ -0,0 +1,230 @@
+//! Pass/fail gating on previously generated reports
+//!
+//! This module checks the thresholds of the gating configuration against
+//! a policy facts export and a drift report. It never runs an analysis
+//! itself, so a pipeline can gate on exactly the reports it archived.
+//! A threshold whose report was not supplied fails rather than passing
+//! unchecked.
+
+use crate::models::*;
+
+/// Gate evaluator implementation
+#[derive(Debug, Clone)]
+pub struct GateEvaluator {
+    /// Gating thresholds
+    config: GatingConfig,
+    /// Whether evaluator is ready
+    ready: bool,
+}
+
+impl GateEvaluator {
+    /// Create new gate evaluator with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            config: config.gating_config.clone(),
+            ready: true,
+        }
+    }
+    
+    /// Check if evaluator is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Evaluate the configured thresholds against the supplied reports
+    pub fn evaluate(&self, facts: Option<&PolicyFacts>, drift: Option<&DriftReport>) -> GateReport {
+        let mut checks = Vec::new();
+        
+        let facts_fingerprint = facts.map(|facts| facts.project.lockfile_fingerprint.as_str());
+        let drift_fingerprint = drift.map(|drift| drift.lockfile_fingerprint.as_str());
+        if let (Some(facts_fingerprint), Some(drift_fingerprint)) = (facts_fingerprint, drift_fingerprint) {
+            if facts_fingerprint != drift_fingerprint {
+                checks.push(GateCheck {
+                    rule: "lockfile_fingerprint".to_string(),
+                    passed: false,
+                    reason: format!("Facts ({}) and drift report ({}) describe different Cargo.lock files",
+                        facts_fingerprint, drift_fingerprint),
+                });
+            }
+        }
+        
+        let limits = &self.config.max_findings;
+        let severity_limits = [
+            (Severity::Critical, limits.critical),
+            (Severity::High, limits.high),
+            (Severity::Medium, limits.medium),
+            (Severity::Low, limits.low),
+            (Severity::Info, limits.info),
+        ];
+        for (severity, limit) in severity_limits {
+            let Some(limit) = limit else { continue };
+            let rule = format!("max_findings.{}", severity.as_str());
+            let check = match facts {
+                Some(facts) => {
+                    let findings: Vec<&str> = facts.findings.iter()
+                        .filter(|finding| finding.severity == severity.as_str())
+                        .map(|finding| finding.id.as_str())
+                        .collect();
+                    if findings.len() <= limit {
+                        GateCheck { rule, passed: true, reason: format!("{} {} findings (limit {})", findings.len(), severity.as_str(), limit) }
+                    } else {
+                        GateCheck {
+                            rule,
+                            passed: false,
+                            reason: format!("{} {} findings exceed the limit of {}: {}",
+                                findings.len(), severity.as_str(), limit, findings.join(", ")),
+                        }
+                    }
+                },
+                None => Self::missing(rule, "facts"),
+            };
+            checks.push(check);
+        }
+        
+        if !self.config.allow_tcs_drift {
+            let rule = "allow_tcs_drift".to_string();
+            let check = match drift {
+                Some(drift) => {
+                    let tcs_drifts: Vec<&str> = drift.drifts.iter()
+                        .filter(|item| matches!(item.classification, Classification::TCS { .. }))
+                        .map(|item| item.package_name.as_str())
+                        .collect();
+                    if tcs_drifts.is_empty() {
+                        GateCheck { rule, passed: true, reason: "No TCS drift".to_string() }
+                    } else {
+                        GateCheck { rule, passed: false, reason: format!("TCS packages drifted: {}", tcs_drifts.join(", ")) }
+                    }
+                },
+                None => Self::missing(rule, "drift"),
+            };
+            checks.push(check);
+        }
+        
+        if let Some(minimum) = self.config.min_tcs_audit_coverage {
+            let rule = "min_tcs_audit_coverage".to_string();
+            let check = match facts {
+                Some(facts) => {
+                    let summary = &facts.summary;
+                    let coverage = if summary.tcs_packages == 0 {
+                        100.0
+                    } else {
+                        (summary.tcs_packages - summary.unaudited_tcs_packages) as f64 / summary.tcs_packages as f64 * 100.0
+                    };
+                    GateCheck {
+                        rule,
+                        passed: coverage >= minimum,
+                        reason: format!("{:.1}% of {} TCS packages audited (minimum {}%)", coverage, summary.tcs_packages, minimum),
+                    }
+                },
+                None => Self::missing(rule, "facts"),
+            };
+            checks.push(check);
+        }
+        
+        GateReport {
+            passed: checks.iter().all(|check| check.passed),
+            evaluated_at: chrono::Utc::now().to_rfc3339(),
+            lockfile_fingerprint: facts_fingerprint.or(drift_fingerprint).map(str::to_string),
+            checks,
+        }
+    }
+    
+    /// Failed check of a threshold whose report was not supplied
+    fn missing(rule: String, report: &str) -> GateCheck {
+        let reason = format!("No {} report supplied to check this threshold against", report);
+        GateCheck { rule, passed: false, reason }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    
+    fn facts(findings: &[(&str, &str)], tcs_packages: usize, unaudited_tcs_packages: usize) -> PolicyFacts {
+        PolicyFacts {
+            schema_version: POLICY_FACTS_SCHEMA_VERSION.to_string(),
+            generated_at: String::new(),
+            project: ProjectFacts {
+                id: "cli-project".to_string(),
+                ecosystem: "rust".to_string(),
+                lockfile_fingerprint: "abc".to_string(),
+                offline_mode: true,
+            },
+            packages: BTreeMap::new(),
+            edges: Vec::new(),
+            findings: findings.iter().map(|(id, severity)| FindingFacts {
+                id: id.to_string(),
+                package: "ring".to_string(),
+                packages: vec!["ring@1.0.0".to_string()],
+                severity: severity.to_string(),
+                cvss_score: None,
+                patched_versions: Vec::new(),
+                applicability: AdvisoryApplicability::default(),
+                minimal_safe_version: None,
+                source: "cargo-audit".to_string(),
+                affects_tcs: true,
+            }).collect(),
+            audit: None,
+            vendor: None,
+            audit_criteria_gaps: Vec::new(),
+            tcs_pinning: Vec::new(),
+            summary: FactsSummary { tcs_packages, unaudited_tcs_packages, ..Default::default() },
+        }
+    }
+    
+    fn drift(package_name: &str, classification: Classification) -> DriftReport {
+        DriftReport {
+            schema_version: DRIFT_REPORT_SCHEMA_VERSION.to_string(),
+            expected_epoch_id: "epoch-1".to_string(),
+            lockfile_fingerprint: "abc".to_string(),
+            analysis_timestamp: String::new(),
+            drifts: vec![DriftItem {
+                id: String::new(),
+                package_name: package_name.to_string(),
+                previous_version: Some("0.16.0".to_string()),
+                current_version: Some("0.17.0".to_string()),
+                previous_source: None,
+                current_source: None,
+                change_type: ChangeType::VersionChange,
+                priority: Priority::High,
+                classification,
+                is_high_risk_source_change: false,
+                details: None,
+                workspace_members: Vec::new(),
+                owners: Vec::new(),
+            }],
+            summary: DriftSummary::default(),
+            impact: DriftImpact::default(),
+            acknowledged_drifts: 0,
+            depth_suppressed_drifts: 0,
+            impact_model: ImpactModel::default(),
+        }
+    }
+    
+    #[test]
+    fn test_gate_checks_configured_thresholds_only() {
+        let mut config = RustAdapterConfig::default();
+        let facts = facts(&[("RUSTSEC-2025-0001", "high"), ("RUSTSEC-2025-0002", "low")], 4, 1);
+        let tcs_drift = drift("ring", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() });
+        
+        // Nothing configured: the adapter imposes no policy
+        let report = GateEvaluator::new(&config).evaluate(Some(&facts), Some(&tcs_drift));
+        assert!(report.passed);
+        assert!(report.checks.is_empty());
+        
+        config.gating_config.max_findings.critical = Some(0);
+        config.gating_config.max_findings.high = Some(0);
+        config.gating_config.allow_tcs_drift = false;
+        config.gating_config.min_tcs_audit_coverage = Some(75.0);
+        let report = GateEvaluator::new(&config).evaluate(Some(&facts), Some(&tcs_drift));
+        assert!(!report.passed);
+        let failed: Vec<&str> = report.failures().iter().map(|check| check.rule.as_str()).collect();
+        assert_eq!(failed, vec!["max_findings.high", "allow_tcs_drift"]);
+        assert!(report.checks.iter().any(|check| check.rule == "min_tcs_audit_coverage" && check.passed));
+        
+        // Thresholds whose report is missing fail instead of passing unchecked
+        let report = GateEvaluator::new(&config).evaluate(Some(&facts), None);
+        assert!(report.failures().iter().any(|check| check.rule == "allow_tcs_drift"));
+    }
+}
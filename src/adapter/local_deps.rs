@@ -0,0 +1,288 @@
+//! Verification of path dependencies
+//!
+//! `cargo vendor` copies registry and git packages only: packages locked
+//! without a source are workspace members or path dependencies, built
+//! straight from the repository. They are located by following the path
+//! dependencies the workspace manifests declare, then checked to lie
+//! inside the repository and to declare the locked name and version.
+//! Their content hashes are pinned in epoch snapshots, so a change to a
+//! local crate invalidates the epoch like a change to a vendored one.
+
+use crate::error::{AdapterError, Result};
+use crate::manifest::cargo_manifest::MANIFEST_FILE;
+use crate::manifest::{CargoManifest, ManifestDependencySource, WorkspaceManifest};
+use crate::models::{LocalDependency, LocalDependencyIssue, Project};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Snapshot metadata key the local dependencies of an epoch are pinned under
+pub const LOCAL_DEPENDENCIES_KEY: &str = "local_dependencies";
+
+/// Cargo.lock entries relevant to path dependencies
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+/// Outcome of verifying a project's path dependencies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LocalDependencyCheck {
+    /// Path dependencies that passed, sorted by name and version
+    pub verified: Vec<LocalDependency>,
+    /// Path dependencies that failed
+    pub issues: Vec<LocalDependencyIssue>,
+}
+
+/// Verify every package Cargo.lock locks without a source
+pub fn verify(project: &Project) -> Result<LocalDependencyCheck> {
+    let lockfile_path = project.lockfile_path();
+    let content = std::fs::read_to_string(&lockfile_path)
+        .map_err(|_| AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
+    let lockfile: Lockfile = toml::from_str(&content)
+        .map_err(|e| AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
+    
+    let mut check = LocalDependencyCheck::default();
+    let local: Vec<&LockedPackage> = lockfile.package.iter().filter(|package| package.source.is_none()).collect();
+    if local.is_empty() {
+        return Ok(check);
+    }
+    
+    let root = &project.paths.root;
+    let canonical_root = root.canonicalize()
+        .map_err(|_| AdapterError::file_not_found(root, "resolving repository root"))?;
+    let declared = declared_crates(root)?;
+    // Adapter output written inside the repository is not crate content
+    let outputs: Vec<PathBuf> = [
+        project.epochs_path(),
+        project.sboms_path(),
+        project.adrs_path(),
+        project.vendor_path(),
+        project.config_path(),
+    ].into_iter().map(|path| path.canonicalize().unwrap_or(path)).collect();
+    
+    for package in local {
+        let issue = |path: Option<&PathBuf>, reason: String| LocalDependencyIssue {
+            package_name: package.name.clone(),
+            version: package.version.clone(),
+            path: path.map(|path| path.strip_prefix(root).unwrap_or(path).to_path_buf()),
+            reason,
+        };
+        
+        let candidates = declared.get(&package.name).map(Vec::as_slice).unwrap_or_default();
+        if candidates.is_empty() {
+            check.issues.push(issue(None, "Not declared as a workspace member or path dependency".to_string()));
+            continue;
+        }
+        
+        // Several directories can declare the same name at different versions
+        let manifests: Vec<(&PathBuf, Option<CargoManifest>)> = candidates.iter()
+            .map(|dir| (dir, CargoManifest::load(&dir.join(MANIFEST_FILE)).ok()))
+            .collect();
+        let matching = manifests.iter().find(|(_, manifest)| {
+            manifest.as_ref().and_then(|m| m.package.as_ref())
+                .is_some_and(|p| p.name == package.name && p.version.as_deref().is_none_or(|v| v == package.version))
+        });
+        
+        let Some((dir, _)) = matching else {
+            let (dir, manifest) = &manifests[0];
+            let reason = match manifest.as_ref().and_then(|m| m.package.as_ref()) {
+                None => format!("No package manifest at {}", dir.display()),
+                Some(found) => format!(
+                    "Manifest declares {}@{}, Cargo.lock locks {}@{}",
+                    found.name,
+                    found.version.as_deref().unwrap_or("?"),
+                    package.name,
+                    package.version,
+                ),
+            };
+            check.issues.push(issue(Some(dir), reason));
+            continue;
+        };
+        
+        let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let Ok(relative) = canonical.strip_prefix(&canonical_root) else {
+            check.issues.push(issue(Some(dir), format!("{} is outside the repository", canonical.display())));
+            continue;
+        };
+        check.verified.push(LocalDependency {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            path: relative.to_path_buf(),
+            content_hash: content_hash(&canonical, &outputs)?,
+        });
+    }
+    
+    check.verified.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    check.issues.sort_by(|a, b| (&a.package_name, &a.version).cmp(&(&b.package_name, &b.version)));
+    Ok(check)
+}
+
+/// Crate directories by package name, following path dependencies from the workspace
+///
+/// A declared directory is listed even if it has no manifest, so a
+/// dangling path dependency is reported rather than unknown.
+fn declared_crates(root: &Path) -> Result<BTreeMap<String, Vec<PathBuf>>> {
+    let workspace = WorkspaceManifest::load(root)?;
+    let mut declared: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut visited = BTreeSet::new();
+    let mut queue: VecDeque<CargoManifest> = std::iter::once(workspace.root_manifest)
+        .chain(workspace.members)
+        .collect();
+    
+    while let Some(manifest) = queue.pop_front() {
+        let dir = manifest.path.parent().unwrap_or(root).to_path_buf();
+        if !visited.insert(dir.clone()) {
+            continue;
+        }
+        if let Some(package) = &manifest.package {
+            declared.entry(package.name.clone()).or_default().push(dir.clone());
+        }
+        
+        let dependencies = manifest.dependencies.iter()
+            .chain(manifest.patches.iter().map(|patch| &patch.dependency))
+            .chain(manifest.replacements.iter().map(|replacement| &replacement.dependency))
+            .chain(manifest.workspace.iter().flat_map(|workspace| workspace.dependencies.iter()));
+        for dependency in dependencies {
+            let ManifestDependencySource::Path { path } = &dependency.source else { continue };
+            let dependency_dir = dir.join(path);
+            match CargoManifest::load(&dependency_dir.join(MANIFEST_FILE)) {
+                Ok(dependency_manifest) => queue.push_back(dependency_manifest),
+                Err(_) => {
+                    let dirs = declared.entry(dependency.package.clone()).or_default();
+                    if !dirs.contains(&dependency_dir) {
+                        dirs.push(dependency_dir);
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(declared)
+}
+
+/// SHA256 over a crate's relative file paths and contents
+///
+/// Build output, hidden directories, nested crates (directories with
+/// their own manifest, such as workspace members below the root package)
+/// and the `excluded` paths are not part of the crate.
+fn content_hash(dir: &Path, excluded: &[PathBuf]) -> Result<String> {
+    let walk_error = |e: walkdir::Error| AdapterError::VendorVerificationFailed {
+        reason: format!("Failed to read local crate {}: {}", dir.display(), e),
+        affected_packages: vec![],
+        source: anyhow::anyhow!("Local crate is unreadable"),
+    };
+    let entries = walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            if excluded.iter().any(|path| path == entry.path()) {
+                return false;
+            }
+            let name = entry.file_name().to_string_lossy();
+            !entry.file_type().is_dir()
+                || (name != "target" && !name.starts_with('.') && !entry.path().join(MANIFEST_FILE).is_file())
+        });
+    
+    let mut hasher = Sha256::new();
+    for entry in entries {
+        let entry = entry.map_err(walk_error)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let content = std::fs::read(entry.path())
+            .map_err(|_| AdapterError::permission_denied(entry.path(), "hashing local crate"))?;
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(Sha256::digest(&content));
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    
+    #[test]
+    fn test_local_dependencies_verified_against_lockfile() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("repo");
+        write(&root.join("Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            util = { path = "crates/util" }
+            shared = { path = "../shared" }
+            gone = { path = "crates/gone" }
+        "#);
+        write(&root.join("src/main.rs"), "fn main() {}");
+        write(&root.join("crates/util/Cargo.toml"), "[package]\nname = \"util\"\nversion = \"0.2.0\"\n");
+        write(&root.join("crates/util/src/lib.rs"), "pub fn util() {}");
+        write(&temp.path().join("shared/Cargo.toml"), "[package]\nname = \"shared\"\nversion = \"1.0.0\"\n");
+        write(&root.join("Cargo.lock"), r#"
+            version = 3
+
+            [[package]]
+            name = "app"
+            version = "0.1.0"
+
+            [[package]]
+            name = "util"
+            version = "0.3.0"
+
+            [[package]]
+            name = "shared"
+            version = "1.0.0"
+
+            [[package]]
+            name = "gone"
+            version = "0.1.0"
+
+            [[package]]
+            name = "serde"
+            version = "1.0.0"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+        "#);
+        let project = Project::new("test".to_string(), "Test".to_string(), "rust".to_string(), root.clone());
+        
+        let check = verify(&project).unwrap();
+        assert_eq!(check.verified.len(), 1);
+        assert_eq!(check.verified[0].name, "app");
+        assert_eq!(check.verified[0].path, PathBuf::new());
+        let reasons: BTreeMap<&str, &str> = check.issues.iter().map(|i| (i.package_name.as_str(), i.reason.as_str())).collect();
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons["util"].contains("util@0.2.0"));
+        assert!(reasons["shared"].contains("outside the repository"));
+        assert!(reasons["gone"].contains("No package manifest"));
+        
+        // Nested crates are hashed on their own, not as part of the root package
+        let hash = check.verified[0].content_hash.clone();
+        write(&root.join("crates/util/src/lib.rs"), "pub fn util() { changed() }");
+        assert_eq!(verify(&project).unwrap().verified[0].content_hash, hash);
+        write(&root.join("src/main.rs"), "fn main() { changed() }");
+        assert_ne!(verify(&project).unwrap().verified[0].content_hash, hash);
+    }
+}
@@ -6,10 +6,34 @@
 pub mod rust_adapter;
 pub mod dependency_parser;
 pub mod tcs_classifier;
+pub mod classification_cache;
+pub mod crate_metadata_cache;
 pub mod audit_runner;
+pub mod audit_scheduler;
+pub mod binary_provenance;
 pub mod vendor_manager;
+pub mod local_deps;
+pub mod verify_progress;
+pub mod secret_scanner;
+pub mod transparency_log;
 pub mod sbom_generator;
 pub mod drift_detector;
+pub mod risk_scorer;
+pub mod registry_metadata;
+pub mod fact_collectors;
+pub mod facts_exporter;
+pub mod epoch_exporter;
+pub mod build_execution;
+pub mod version_skew;
+pub mod badge_generator;
+pub mod update_simulator;
+pub mod upgrade_advisor;
+pub mod export_redactor;
+pub mod org_rollup;
+pub mod gate_evaluator;
+pub mod attribution_collector;
+pub mod doctor;
+pub mod bundle_importer;
 
 // Re-export main adapter
 pub use rust_adapter::RustAdapter;
\ No newline at end of file
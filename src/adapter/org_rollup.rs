@@ -0,0 +1,250 @@
+//! Organization-level rollup of project analyses
+//!
+//! This module aggregates the parsed dependency graphs and audit reports
+//! of the projects listed in the organization configuration: which
+//! third-party dependencies are used where, which TCS components are
+//! shared between projects, and which findings affect several projects.
+//! Workspace members and path dependencies are project-local and left
+//! out of the dependency lists.
+
+use crate::error::AdapterError;
+use crate::models::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Organization rollup implementation
+#[derive(Debug, Clone)]
+pub struct OrgRollup {
+    /// Whether rollup is ready
+    ready: bool,
+}
+
+/// Outcome of analyzing one project of the organization
+#[derive(Debug)]
+pub struct OrgProjectAnalysis {
+    /// Project identifier
+    pub id: String,
+    /// Project name
+    pub name: String,
+    /// Parsed graph and audit report, or why the project could not be analyzed
+    pub result: Result<(DependencyGraph, AuditReport), AdapterError>,
+}
+
+impl OrgRollup {
+    /// Create new organization rollup with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self {
+            ready: true,
+        }
+    }
+    
+    /// Check if rollup is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Aggregate project analyses into a rollup report
+    ///
+    /// Failed projects are listed with their error and contribute nothing
+    /// else, so one broken checkout does not hide the rest of the report.
+    pub fn rollup(&self, organization: &str, analyses: &[OrgProjectAnalysis]) -> OrgRollupReport {
+        let mut projects = Vec::new();
+        let mut dependencies: BTreeMap<(String, String), OrgDependency> = BTreeMap::new();
+        let mut findings: BTreeMap<(String, String), OrgFinding> = BTreeMap::new();
+        let mut statistics = OrgRollupStatistics::default();
+        
+        for analysis in analyses {
+            let (graph, audit_report) = match &analysis.result {
+                Ok(result) => result,
+                Err(e) => {
+                    statistics.projects_failed += 1;
+                    projects.push(OrgProjectSummary {
+                        id: analysis.id.clone(),
+                        name: analysis.name.clone(),
+                        lockfile_fingerprint: None,
+                        dependencies: 0,
+                        tcs_dependencies: 0,
+                        findings_by_severity: BTreeMap::new(),
+                        error: Some(e.to_string()),
+                    });
+                    continue;
+                },
+            };
+            statistics.projects_analyzed += 1;
+            
+            let mut seen = BTreeSet::new();
+            let mut tcs_dependencies = 0;
+            for package in &graph.root_packages {
+                if matches!(package.source, PackageSource::Local { .. })
+                    || !seen.insert((package.name.clone(), package.version.clone()))
+                {
+                    continue;
+                }
+                let tcs_category = match &package.classification {
+                    Classification::TCS { category, .. } => Some(category.clone()),
+                    _ => None,
+                };
+                if tcs_category.is_some() {
+                    tcs_dependencies += 1;
+                }
+                
+                let dependency = dependencies.entry((package.name.clone(), package.version.clone()))
+                    .or_insert_with(|| OrgDependency {
+                        name: package.name.clone(),
+                        version: package.version.clone(),
+                        tcs_category: None,
+                        projects: Vec::new(),
+                    });
+                if dependency.tcs_category.is_none() {
+                    dependency.tcs_category = tcs_category;
+                }
+                dependency.projects.push(analysis.id.clone());
+            }
+            
+            let mut findings_by_severity: BTreeMap<String, usize> = BTreeMap::new();
+            for finding in &audit_report.findings {
+                *findings_by_severity.entry(finding.severity.as_str().to_string()).or_default() += 1;
+                
+                let org_finding = findings.entry((finding.id.clone(), finding.package_name.clone()))
+                    .or_insert_with(|| OrgFinding {
+                        id: finding.id.clone(),
+                        package_name: finding.package_name.clone(),
+                        severity: finding.severity.clone(),
+                        affects_tcs: false,
+                        projects: Vec::new(),
+                    });
+                org_finding.affects_tcs |= finding.affects_tcs;
+                if !org_finding.projects.contains(&analysis.id) {
+                    org_finding.projects.push(analysis.id.clone());
+                }
+            }
+            
+            projects.push(OrgProjectSummary {
+                id: analysis.id.clone(),
+                name: analysis.name.clone(),
+                lockfile_fingerprint: Some(graph.metadata.lockfile_fingerprint.clone()),
+                dependencies: seen.len(),
+                tcs_dependencies,
+                findings_by_severity,
+                error: None,
+            });
+        }
+        
+        let dependencies: Vec<OrgDependency> = dependencies.into_values().collect();
+        let shared_tcs_components: Vec<OrgDependency> = dependencies.iter()
+            .filter(|d| d.tcs_category.is_some() && d.projects.len() > 1)
+            .cloned()
+            .collect();
+        let mut findings: Vec<OrgFinding> = findings.into_values().collect();
+        findings.sort_by(|a, b| a.severity.cmp(&b.severity).then_with(|| b.projects.len().cmp(&a.projects.len())));
+        
+        statistics.unique_dependencies = dependencies.len();
+        statistics.unique_tcs_dependencies = dependencies.iter().filter(|d| d.tcs_category.is_some()).count();
+        statistics.unique_findings = findings.len();
+        statistics.shared_findings = findings.iter().filter(|f| f.projects.len() > 1).count();
+        
+        OrgRollupReport {
+            organization: organization.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            projects,
+            dependencies,
+            shared_tcs_components,
+            findings,
+            statistics,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use std::path::Path;
+    
+    fn finding(id: &str, package_name: &str, severity: Severity) -> AuditFinding {
+        AuditFinding {
+            id: id.to_string(),
+            package_name: package_name.to_string(),
+            package_version: Some("1.0.0".to_string()),
+            affected_versions: "<1.0.1".to_string(),
+            patched_versions: vec![">=1.0.1".to_string()],
+            unaffected_versions: Vec::new(),
+            applicability: AdvisoryApplicability::default(),
+            minimal_safe_version: None,
+            severity,
+            cvss_score: None,
+            description: String::new(),
+            references: Vec::new(),
+            source: "cargo-audit".to_string(),
+            affects_tcs: package_name == "ring",
+            dependency_paths: Vec::new(),
+            aliases: Vec::new(),
+            reported_by: Vec::new(),
+        }
+    }
+    
+    fn analysis(id: &str, packages: Vec<PackageNode>, findings: Vec<AuditFinding>) -> OrgProjectAnalysis {
+        let mut graph = DependencyGraph::new(id.to_string(), "rust".to_string());
+        graph.add_package(PackageNode {
+            source: PackageSource::Local { path: format!("/src/{}", id) },
+            ..package(id, "1.0.0", Classification::Unknown)
+        });
+        for package in packages {
+            graph.add_package(package);
+        }
+        let audit_report = AuditReport {
+            raw_cargo_audit: None,
+            raw_cargo_vet: None,
+            raw_cargo_vet_audits: None,
+            raw_cargo_vet_imports: None,
+            raw_cargo_vet_config: None,
+            execution_metadata: AuditExecutionMetadata {
+                timestamp: String::new(),
+                tool_versions: Default::default(),
+                execution_duration: 0,
+                exit_codes: Default::default(),
+                offline_mode: true,
+                warnings: Vec::new(),
+                lockfile_fingerprint: String::new(),
+            },
+            offline_mode: true,
+            findings,
+            upgrade_suggestions: Vec::new(),
+        };
+        OrgProjectAnalysis { id: id.to_string(), name: id.to_string(), result: Ok((graph, audit_report)) }
+    }
+    
+    #[test]
+    fn test_rollup_aggregates_shared_dependencies_and_findings() {
+        let crypto = || Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() };
+        let analyses = vec![
+            analysis("billing", vec![package("ring", "1.0.0", crypto()), package("serde", "1.0.0", Classification::Unknown)],
+                vec![finding("RUSTSEC-2025-0001", "ring", Severity::High)]),
+            analysis("portal", vec![package("ring", "1.0.0", crypto()), package("regex", "1.0.0", Classification::Unknown)],
+                vec![finding("RUSTSEC-2025-0001", "ring", Severity::High), finding("RUSTSEC-2025-0002", "regex", Severity::Critical)]),
+            OrgProjectAnalysis {
+                id: "legacy".to_string(),
+                name: "legacy".to_string(),
+                result: Err(AdapterError::file_not_found(Path::new("/src/legacy/Cargo.lock"), "Cargo.lock")),
+            },
+        ];
+        
+        let report = OrgRollup::new(&RustAdapterConfig::default()).rollup("acme", &analyses);
+        
+        assert_eq!(report.statistics.projects_analyzed, 2);
+        assert_eq!(report.statistics.projects_failed, 1);
+        assert!(report.projects[2].error.is_some());
+        assert_eq!(report.projects[0].dependencies, 2);
+        assert_eq!(report.projects[0].tcs_dependencies, 1);
+        
+        assert_eq!(report.statistics.unique_dependencies, 3);
+        assert!(report.dependencies.iter().all(|d| d.name != "billing" && d.name != "portal"));
+        assert_eq!(report.shared_tcs_components.len(), 1);
+        assert_eq!(report.shared_tcs_components[0].name, "ring");
+        assert_eq!(report.shared_tcs_components[0].projects, vec!["billing", "portal"]);
+        
+        assert_eq!(report.statistics.shared_findings, 1);
+        assert_eq!(report.findings[0].id, "RUSTSEC-2025-0002");
+        assert_eq!(report.findings[1].projects, vec!["billing", "portal"]);
+        assert!(report.findings[1].affects_tcs);
+    }
+}
@@ -0,0 +1,610 @@
+//! Registry popularity metadata for Rust dependencies
+//!
+//! When enabled with `registry_config.fetch_popularity`, in online mode,
+//! this component fetches download counts and release cadence from the
+//! crates.io API and attaches them to packages as annotations. Only
+//! crates locked from crates.io are looked up, so names of crates from
+//! private registries never leave the machine. Responses are cached in the
+//! shared cache per crate and per UTC day, so repeated runs on the same
+//! day make no API calls.
+//!
+//! The release list fetched alongside is used to annotate each package
+//! with its update lag. Offline, a local mirror of the crates.io index can
+//! stand in; it has no publish dates, so only the semver distance is known.
+//! Without a configured mirror, one imported from a security data bundle
+//! into the shared cache is used.
+//!
+//! License, categories and publisher of each version in use are kept in
+//! a metadata cache shared by every project on the machine (see
+//! [`CrateMetadataCache`]), so a crate version is fetched once per time to
+//! live rather than once per project. Offline, cached metadata of any age
+//! is used.
+
+use crate::adapter::bundle_importer::INDEX_MIRROR_ENTRY;
+use crate::adapter::crate_metadata_cache::{self, CrateMetadataCache};
+use crate::models::*;
+use crate::error::{AdapterError, Result};
+use crate::utils::{normalize_source, telemetry, CancellationToken, SharedCache, CRATES_IO_SOURCE};
+#[cfg(feature = "online")]
+use crate::utils::HttpClient;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Shared cache entry holding popularity responses
+pub const POPULARITY_CACHE_ENTRY: &str = "crates-io-popularity";
+
+/// Popularity data by crate name and version metadata by `name@version`
+type RegistryData = (HashMap<String, CratePopularity>, HashMap<String, CrateVersionMetadata>);
+
+/// Registry metadata fetcher implementation
+#[derive(Debug, Clone)]
+pub struct RegistryMetadataFetcher {
+    /// Fetcher configuration
+    config: RegistryMetadataConfig,
+    /// Whether fetcher is ready
+    ready: bool,
+    /// Token that aborts in-flight API requests when cancelled
+    cancel: CancellationToken,
+    /// Crate version metadata shared by all projects
+    metadata_cache: CrateMetadataCache,
+}
+
+/// Configuration for registry metadata fetcher
+#[derive(Debug, Clone)]
+pub struct RegistryMetadataConfig {
+    /// Whether popularity data is fetched at all
+    pub enabled: bool,
+    /// Adapter-wide offline mode
+    pub offline_mode: bool,
+    /// Registry API base URL
+    pub api_url: String,
+    /// Cache directory (relative paths resolve against the project root),
+    /// `None` when responses are not cached
+    pub cache_dir: Option<PathBuf>,
+    /// Number of crates requested per batched API call
+    pub batch_size: usize,
+    /// Minimum delay between API requests (milliseconds)
+    pub request_interval_ms: u64,
+    /// Local mirror of the crates.io index
+    pub index_dir: Option<PathBuf>,
+    /// HTTP client settings
+    pub http: HttpConfig,
+}
+
+impl RegistryMetadataFetcher {
+    /// Create new registry metadata fetcher with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        let registry = &config.registry_config;
+        let shared_cache = SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout);
+        let metadata_cache_dir = registry.metadata_cache_dir.clone()
+            .or_else(|| shared_cache.as_ref().map(|cache| cache.entry_path(crate_metadata_cache::SHARED_CACHE_ENTRY)));
+        let index_dir = registry.index_dir.clone()
+            .or_else(|| shared_cache.as_ref()
+                .map(|cache| cache.entry_path(INDEX_MIRROR_ENTRY))
+                .filter(|dir| dir.is_dir()));
+        let metadata_cache = match metadata_cache_dir {
+            Some(dir) if registry.metadata_cache_ttl > 0 => CrateMetadataCache::new(
+                dir,
+                Duration::from_secs(registry.metadata_cache_ttl),
+                registry.metadata_cache_read_only || config.offline_mode,
+            ),
+            _ => CrateMetadataCache::disabled(),
+        };
+        
+        Self {
+            config: RegistryMetadataConfig {
+                enabled: registry.fetch_popularity,
+                offline_mode: config.offline_mode,
+                api_url: registry.api_url.trim_end_matches('/').to_string(),
+                cache_dir: registry.cache_dir.clone()
+                    .or_else(|| shared_cache.as_ref().map(|cache| cache.entry_path(POPULARITY_CACHE_ENTRY))),
+                batch_size: registry.batch_size.max(1),
+                request_interval_ms: registry.request_interval_ms,
+                index_dir,
+                http: config.http_config.clone(),
+            },
+            ready: true,
+            cancel: CancellationToken::new(),
+            metadata_cache,
+        }
+    }
+    
+    /// Abort in-flight API requests when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Check if fetcher is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Whether popularity data should be fetched for a project
+    pub fn is_enabled_for(&self, project: &Project) -> bool {
+        self.config.enabled && !self.config.offline_mode && !project.requires_strict_security()
+    }
+    
+    /// Annotate registry packages with popularity data, version metadata and update lag
+    ///
+    /// Returns the number of packages annotated with popularity data.
+    /// Update lag is computed from the fetched release lists, falling back
+    /// to the index mirror for crates without one.
+    pub async fn annotate_graph(&self, project: &Project, graph: &mut DependencyGraph) -> Result<usize> {
+        let fetch = self.is_enabled_for(project) && !graph.metadata.offline_mode;
+        let (popularity, metadata) = self.registry_data(project, graph, fetch).await?;
+        
+        let now = Utc::now();
+        let mut index_releases: HashMap<String, Vec<CrateRelease>> = HashMap::new();
+        let mut annotated = 0;
+        for package in &mut graph.root_packages {
+            if let Some(version_metadata) = metadata.get(&format!("{}@{}", package.name, package.version)) {
+                let has_categories = package.annotation(annotation_keys::CATEGORIES).is_some();
+                for annotation in version_metadata.to_annotations(has_categories) {
+                    package.set_annotation(&annotation.key, annotation.value);
+                }
+            }
+            
+            let entry = popularity.get(&package.name);
+            if let Some(entry) = entry {
+                for annotation in entry.to_annotations() {
+                    package.set_annotation(&annotation.key, annotation.value);
+                }
+                annotated += 1;
+            }
+            
+            if !Self::is_crates_io(&package.source) {
+                continue;
+            }
+            let releases = match entry.filter(|e| !e.releases.is_empty()) {
+                Some(entry) => &entry.releases,
+                None => &*index_releases.entry(package.name.clone())
+                    .or_insert_with(|| self.index_releases(&package.name)),
+            };
+            if let Some(lag) = UpdateLag::compute(&package.version, releases, now) {
+                package.set_annotation(annotation_keys::UPDATE_LAG, serde_json::json!(lag));
+            }
+        }
+        
+        Ok(annotated)
+    }
+    
+    /// Popularity data and version metadata of the graph's crates.io crates
+    ///
+    /// Popularity entries cached for the current day and unexpired version
+    /// metadata are used as-is; when `fetch` is set, crates missing either
+    /// are requested. Without fetching, only cached version metadata (of any
+    /// age) is returned.
+    async fn registry_data(&self, project: &Project, graph: &DependencyGraph, fetch: bool) -> Result<RegistryData> {
+        let metadata_cache = if fetch { self.metadata_cache.clone() } else { self.metadata_cache.as_read_only() };
+        let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for package in graph.root_packages.iter().filter(|p| Self::is_crates_io(&p.source)) {
+            versions.entry(package.name.clone()).or_default().insert(package.version.clone());
+        }
+        
+        let mut metadata = HashMap::new();
+        let mut incomplete = BTreeSet::new();
+        if metadata_cache.is_enabled() {
+            for (name, crate_versions) in &versions {
+                for version in crate_versions {
+                    let cached = metadata_cache.get(name, version);
+                    telemetry::record_cache_access("crate-metadata", cached.is_some());
+                    match cached {
+                        Some(entry) => { metadata.insert(format!("{}@{}", name, version), entry); },
+                        None => { incomplete.insert(name.clone()); },
+                    }
+                }
+            }
+        }
+        if !fetch {
+            return Ok((HashMap::new(), metadata));
+        }
+        
+        let cache_dir = self.cache_dir_for(project);
+        let date = Utc::now().format("%Y-%m-%d").to_string();
+        let mut popularity = HashMap::new();
+        let mut missing = Vec::new();
+        for name in versions.keys() {
+            let cached = cache_dir.as_deref().and_then(|dir| Self::load_cached(dir, &date, name));
+            telemetry::record_cache_access("registry", cached.is_some());
+            match cached {
+                Some(entry) => { popularity.insert(name.clone(), entry); },
+                None => missing.push(name.clone()),
+            }
+        }
+        // A read-only metadata cache cannot be completed, so it does not trigger requests
+        if !metadata_cache.is_read_only() {
+            missing.extend(incomplete.into_iter().filter(|name| popularity.contains_key(name)));
+            missing.sort();
+        }
+        
+        if !missing.is_empty() {
+            let fetched = tokio::select! {
+                fetched = self.fetch_popularity(&missing) => fetched?,
+                _ = self.cancel.cancelled() => return Err(AdapterError::cancelled("crates.io metadata fetch")),
+            };
+            for (entry, crate_metadata) in fetched {
+                let in_use = &versions[&entry.name];
+                for version_metadata in crate_metadata.into_iter().filter(|m| in_use.contains(&m.version)) {
+                    metadata_cache.put(&version_metadata);
+                    metadata.insert(format!("{}@{}", version_metadata.name, version_metadata.version), version_metadata);
+                }
+                if let Some(cache_dir) = &cache_dir {
+                    Self::store_cached(cache_dir, &date, &entry)?;
+                }
+                popularity.insert(entry.name.clone(), entry);
+            }
+        }
+        
+        Ok((popularity, metadata))
+    }
+    
+    /// Non-yanked releases of a crate listed in the index mirror
+    ///
+    /// Returns no releases when no mirror is configured or the crate is
+    /// not in it.
+    fn index_releases(&self, crate_name: &str) -> Vec<CrateRelease> {
+        let Some(index_dir) = &self.config.index_dir else {
+            return Vec::new();
+        };
+        let Ok(content) = std::fs::read_to_string(index_dir.join(Self::index_path(crate_name))) else {
+            return Vec::new();
+        };
+        
+        content.lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|entry| !entry.get("yanked").and_then(|y| y.as_bool()).unwrap_or(false))
+            .filter_map(|entry| Some(CrateRelease {
+                version: entry.get("vers")?.as_str()?.to_string(),
+                published_at: None,
+            }))
+            .collect()
+    }
+    
+    /// Path of a crate's file relative to the index root
+    fn index_path(crate_name: &str) -> PathBuf {
+        let name = crate_name.to_ascii_lowercase();
+        match name.len() {
+            1 => PathBuf::from("1").join(&name),
+            2 => PathBuf::from("2").join(&name),
+            3 => PathBuf::from("3").join(&name[..1]).join(&name),
+            _ => PathBuf::from(&name[..2]).join(&name[2..4]).join(&name),
+        }
+    }
+    
+    /// Whether a package is locked from crates.io
+    fn is_crates_io(source: &PackageSource) -> bool {
+        let PackageSource::Registry { url, .. } = source else {
+            return false;
+        };
+        let source = if url.contains('+') { url.clone() } else { format!("registry+{}", url) };
+        normalize_source(&source) == CRATES_IO_SOURCE
+    }
+    
+    /// Resolve the cache directory for a project
+    fn cache_dir_for(&self, project: &Project) -> Option<PathBuf> {
+        let cache_dir = self.config.cache_dir.as_ref()?;
+        Some(if cache_dir.is_absolute() {
+            cache_dir.clone()
+        } else {
+            project.paths.root.join(cache_dir)
+        })
+    }
+    
+    /// Cache file path for a crate on a given day
+    fn cache_path(cache_dir: &Path, date: &str, crate_name: &str) -> PathBuf {
+        cache_dir.join(date).join(format!("{}.json", crate_name))
+    }
+    
+    /// Load a cached entry, ignoring unreadable or corrupt files
+    fn load_cached(cache_dir: &Path, date: &str, crate_name: &str) -> Option<CratePopularity> {
+        let path = Self::cache_path(cache_dir, date, crate_name);
+        crate::utils::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+    
+    /// Store an entry in the cache
+    fn store_cached(cache_dir: &Path, date: &str, entry: &CratePopularity) -> Result<()> {
+        let path = Self::cache_path(cache_dir, date, &entry.name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating registry cache"))?;
+        }
+        
+        let content = serde_json::to_string_pretty(entry)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize registry cache entry".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        crate::utils::write_atomic(&path, content)
+    }
+    
+    /// Fetch popularity data and version metadata for crates from the registry API
+    ///
+    /// Download counts are requested in batches of `batch_size` crates;
+    /// release cadence and version metadata need one crate request per
+    /// crate. Requests are issued sequentially with `request_interval_ms`
+    /// between them, and transient failures are retried.
+    #[cfg(feature = "online")]
+    async fn fetch_popularity(&self, names: &[String]) -> Result<Vec<(CratePopularity, Vec<CrateVersionMetadata>)>> {
+        let client = HttpClient::new(&self.config.http, self.config.offline_mode)?
+            .with_min_interval(Duration::from_millis(self.config.request_interval_ms));
+        let now = Utc::now();
+        let mut results = Vec::with_capacity(names.len());
+        
+        for batch in names.chunks(self.config.batch_size) {
+            let url = format!("{}/crates", self.config.api_url);
+            let mut query: Vec<(&str, String)> = batch.iter().map(|n| ("ids[]", n.clone())).collect();
+            query.push(("per_page", batch.len().to_string()));
+            
+            let body: serde_json::Value = client.get_json(&url, &query).await?;
+            let downloads: HashMap<String, (u64, Option<u64>)> = body.get("crates")
+                .and_then(|c| c.as_array())
+                .map(|crates| crates.iter()
+                    .filter_map(|c| {
+                        let name = c.get("name").or_else(|| c.get("id"))?.as_str()?.to_string();
+                        let downloads = c.get("downloads")?.as_u64()?;
+                        let recent = c.get("recent_downloads").and_then(|v| v.as_u64());
+                        Some((name, (downloads, recent)))
+                    })
+                    .collect())
+                .unwrap_or_default();
+            
+            for name in batch {
+                // Crates unknown to the registry (e.g. alternate registries) are skipped
+                let Some(&(downloads, recent_downloads)) = downloads.get(name) else {
+                    continue;
+                };
+                
+                let url = format!("{}/crates/{}", self.config.api_url, name);
+                let body: serde_json::Value = client.get_json(&url, &[]).await?;
+                let releases = Self::releases(&body);
+                let dates = Self::release_dates(&releases);
+                let (releases_last_year, days_since_last_release) = Self::release_cadence(&dates, now);
+                
+                results.push((CratePopularity {
+                    name: name.clone(),
+                    downloads,
+                    recent_downloads,
+                    releases_last_year,
+                    days_since_last_release,
+                    releases,
+                }, Self::version_metadata(name, &body, now)));
+            }
+        }
+        
+        Ok(results)
+    }
+    
+    /// Without the `online` feature nothing is fetched; cached data is still used
+    #[cfg(not(feature = "online"))]
+    async fn fetch_popularity(&self, _names: &[String]) -> Result<Vec<(CratePopularity, Vec<CrateVersionMetadata>)>> {
+        Ok(Vec::new())
+    }
+    
+    /// Extract non-yanked releases from a versions response
+    #[cfg(any(feature = "online", test))]
+    fn releases(body: &serde_json::Value) -> Vec<CrateRelease> {
+        body.get("versions")
+            .and_then(|v| v.as_array())
+            .map(|versions| versions.iter()
+                .filter(|v| !v.get("yanked").and_then(|y| y.as_bool()).unwrap_or(false))
+                .filter_map(|v| Some(CrateRelease {
+                    version: v.get("num")?.as_str()?.to_string(),
+                    published_at: v.get("created_at").and_then(|c| c.as_str()).map(str::to_string),
+                }))
+                .collect())
+            .unwrap_or_default()
+    }
+    
+    /// Extract the metadata of every version, yanked ones included, from a crate response
+    #[cfg_attr(not(feature = "online"), allow(dead_code))]
+    fn version_metadata(name: &str, body: &serde_json::Value, now: DateTime<Utc>) -> Vec<CrateVersionMetadata> {
+        let strings = |value: Option<&serde_json::Value>| -> Vec<String> {
+            value.and_then(|v| v.as_array())
+                .map(|values| values.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                .unwrap_or_default()
+        };
+        let categories = strings(body.get("crate").and_then(|c| c.get("categories")));
+        
+        body.get("versions")
+            .and_then(|v| v.as_array())
+            .map(|versions| versions.iter()
+                .filter_map(|v| Some(CrateVersionMetadata {
+                    name: name.to_string(),
+                    version: v.get("num")?.as_str()?.to_string(),
+                    license: v.get("license").and_then(|l| l.as_str()).map(str::to_string),
+                    publisher: v.get("published_by").and_then(|p| p.get("login")).and_then(|l| l.as_str()).map(str::to_string),
+                    categories: categories.clone(),
+                    fetched_at: now.to_rfc3339(),
+                }))
+                .collect())
+            .unwrap_or_default()
+    }
+    
+    /// Publish dates of releases, skipping those without a valid date
+    #[cfg(any(feature = "online", test))]
+    fn release_dates(releases: &[CrateRelease]) -> Vec<DateTime<Utc>> {
+        releases.iter()
+            .filter_map(|r| DateTime::parse_from_rfc3339(r.published_at.as_deref()?).ok())
+            .map(|d| d.with_timezone(&Utc))
+            .collect()
+    }
+    
+    /// Compute releases in the last year and days since the latest release
+    #[cfg(any(feature = "online", test))]
+    fn release_cadence(releases: &[DateTime<Utc>], now: DateTime<Utc>) -> (u32, Option<i64>) {
+        let year_ago = now - chrono::Duration::days(365);
+        let releases_last_year = releases.iter().filter(|d| **d >= year_ago).count() as u32;
+        let days_since_last_release = releases.iter().max().map(|latest| (now - *latest).num_days());
+        
+        (releases_last_year, days_since_last_release)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RustAdapterConfig;
+    
+    #[test]
+    fn test_release_cadence() {
+        let now = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let body = serde_json::json!({
+            "crate": { "categories": ["encoding"] },
+            "versions": [
+                { "num": "1.2.0", "created_at": "2024-05-22T10:00:00Z", "yanked": false,
+                  "license": "MIT", "published_by": { "login": "maintainer" } },
+                { "num": "1.1.1", "created_at": "2024-05-30T10:00:00Z", "yanked": true },
+                { "num": "1.1.0", "created_at": "2023-09-01T10:00:00Z", "yanked": false },
+                { "num": "1.0.0", "created_at": "2021-01-01T10:00:00Z", "yanked": false }
+            ]
+        });
+        
+        let releases = RegistryMetadataFetcher::releases(&body);
+        assert_eq!(releases.len(), 3);
+        let dates = RegistryMetadataFetcher::release_dates(&releases);
+        assert_eq!(RegistryMetadataFetcher::release_cadence(&dates, now), (2, Some(9)));
+        
+        let lag = UpdateLag::compute("1.0.0", &releases, now).unwrap();
+        assert_eq!(lag.latest_version, "1.2.0");
+        assert_eq!((lag.major_behind, lag.minor_behind, lag.patch_behind), (0, 2, 0));
+        assert_eq!(lag.releases_behind, 2);
+        assert_eq!(lag.days_behind_latest, Some(1237));
+        assert!(!UpdateLag::compute("1.2.0", &releases, now).unwrap().is_outdated());
+        assert_eq!(RegistryMetadataFetcher::release_cadence(&[], now), (0, None));
+        
+        let metadata = RegistryMetadataFetcher::version_metadata("serde", &body, now);
+        assert_eq!(metadata.len(), 4);
+        assert_eq!(metadata[0].license.as_deref(), Some("MIT"));
+        assert_eq!(metadata[0].publisher.as_deref(), Some("maintainer"));
+        assert_eq!(metadata[3].categories, vec!["encoding".to_string()]);
+    }
+    
+    #[tokio::test]
+    async fn test_annotate_graph_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = RustAdapterConfig::default();
+        config.registry_config.fetch_popularity = true;
+        config.registry_config.cache_dir = Some(dir.path().to_path_buf());
+        config.registry_config.metadata_cache_dir = Some(dir.path().join("metadata"));
+        let fetcher = RegistryMetadataFetcher::new(&config);
+        fetcher.metadata_cache.put(&CrateVersionMetadata {
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            license: Some("MIT OR Apache-2.0".to_string()),
+            publisher: Some("dtolnay".to_string()),
+            categories: vec!["encoding".to_string()],
+            fetched_at: Utc::now().to_rfc3339(),
+        });
+        
+        let date = Utc::now().format("%Y-%m-%d").to_string();
+        let entry = CratePopularity {
+            name: "serde".to_string(),
+            downloads: 250_000_000,
+            recent_downloads: Some(30_000_000),
+            releases_last_year: 12,
+            days_since_last_release: Some(5),
+            releases: vec![
+                CrateRelease { version: "1.0.0".to_string(), published_at: None },
+                CrateRelease { version: "2.0.0".to_string(), published_at: None },
+            ],
+        };
+        RegistryMetadataFetcher::store_cached(dir.path(), &date, &entry).unwrap();
+        assert_eq!(RegistryMetadataFetcher::load_cached(dir.path(), &date, "serde"), Some(entry.clone()));
+        RegistryMetadataFetcher::store_cached(dir.path(), &date, &CratePopularity { name: "internal".to_string(), ..entry }).unwrap();
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: String::new(),
+            },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        // Crates of other registries are never looked up on crates.io
+        graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "internal".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry {
+                url: "sparse+https://registry.example.com/index/".to_string(),
+                checksum: String::new(),
+            },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), dir.path().to_path_buf());
+        let annotated = fetcher.annotate_graph(&project, &mut graph).await.unwrap();
+        
+        assert_eq!(annotated, 1);
+        assert!(graph.root_packages[1].annotations.is_empty());
+        assert!(!RustAdapterConfig::default().registry_config.fetch_popularity);
+        let package = &graph.root_packages[0];
+        assert_eq!(package.annotation(annotation_keys::DOWNLOADS), Some(&serde_json::json!(250_000_000u64)));
+        assert_eq!(package.annotation(annotation_keys::RELEASES_LAST_YEAR), Some(&serde_json::json!(12)));
+        assert_eq!(UpdateLag::from_package(package).unwrap().major_behind, 1);
+        assert_eq!(package.annotation_str(annotation_keys::LICENSE), Some("MIT OR Apache-2.0"));
+        assert_eq!(package.annotation_str(annotation_keys::PUBLISHER), Some("dtolnay"));
+        assert_eq!(package.annotation(annotation_keys::CATEGORIES), Some(&serde_json::json!(["encoding"])));
+    }
+    
+    #[tokio::test]
+    async fn test_update_lag_from_index_mirror() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_file = dir.path().join("index").join(RegistryMetadataFetcher::index_path("Serde"));
+        std::fs::create_dir_all(index_file.parent().unwrap()).unwrap();
+        std::fs::write(&index_file, concat!(
+            r#"{"name":"serde","vers":"1.0.0","yanked":false}"#, "\n",
+            r#"{"name":"serde","vers":"1.0.1","yanked":false}"#, "\n",
+            r#"{"name":"serde","vers":"1.0.2","yanked":true}"#, "\n",
+        )).unwrap();
+        assert!(index_file.ends_with("se/rd/serde"));
+        
+        let mut config = RustAdapterConfig {
+            offline_mode: true,
+            ..RustAdapterConfig::default()
+        };
+        config.registry_config.index_dir = Some(dir.path().join("index"));
+        let fetcher = RegistryMetadataFetcher::new(&config);
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "serde".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: String::new(),
+            },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), dir.path().to_path_buf());
+        assert_eq!(fetcher.annotate_graph(&project, &mut graph).await.unwrap(), 0);
+        
+        let lag = UpdateLag::from_package(&graph.root_packages[0]).unwrap();
+        assert_eq!(lag.latest_version, "1.0.1");
+        assert_eq!((lag.patch_behind, lag.releases_behind, lag.days_behind_latest), (1, 1, None));
+        
+        let mut analysis = ProjectAnalysis::new(project);
+        analysis.record_update_lag(&graph);
+        assert_eq!(analysis.update_lag["unknown"].outdated, 1);
+        assert_eq!(analysis.update_lag["unknown"].mean_days_behind, None);
+    }
+}
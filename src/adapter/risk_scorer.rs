@@ -0,0 +1,197 @@
+//! Informational risk scoring for Rust dependencies
+//!
+//! This module combines facts gathered by the other components
+//! (classification, audit status, findings, source and release
+//! activity) into a per-package risk indicator. Scores are stored as
+//! annotations and never gate any operation.
+
+use crate::config::RiskConfig;
+use crate::models::*;
+
+/// Risk scorer implementation
+#[derive(Debug, Clone)]
+pub struct RiskScorer {
+    /// Scorer configuration
+    config: RiskConfig,
+    /// Whether scorer is ready
+    ready: bool,
+}
+
+impl RiskScorer {
+    /// Create new risk scorer with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            config: config.risk_config.clone(),
+            ready: true,
+        }
+    }
+    
+    /// Check if scorer is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Score every package, store the result as an annotation and summarize
+    pub fn score_graph(&self, graph: &mut DependencyGraph, findings: &[AuditFinding]) -> RiskSummary {
+        let mut scores = Vec::with_capacity(graph.root_packages.len());
+        
+        for package in &mut graph.root_packages {
+            let package_findings: Vec<_> = findings.iter()
+                .filter(|f| Self::finding_matches(f, package))
+                .collect();
+            let risk = self.score_package(package, &package_findings);
+            
+            scores.push(PackageRisk {
+                package_name: package.name.clone(),
+                package_version: package.version.clone(),
+                score: risk.score,
+            });
+            package.set_annotation(
+                annotation_keys::RISK_SCORE,
+                serde_json::to_value(&risk).unwrap_or_default(),
+            );
+        }
+        
+        RiskSummary::from_scores(scores, self.config.summary_limit)
+    }
+    
+    /// Whether a finding was reported for this exact package
+    ///
+    /// Findings carrying the locked version they were reported for match
+    /// that name@version only; others match every version of the crate
+    /// the advisory ranges cover.
+    fn finding_matches(finding: &AuditFinding, package: &PackageNode) -> bool {
+        if finding.package_name != package.name {
+            return false;
+        }
+        match &finding.package_version {
+            Some(version) => *version == package.version,
+            None => finding.applies_to(&package.version) != AdvisoryApplicability::NotApplicable,
+        }
+    }
+    
+    /// Score a single package from its facts and matching findings
+    pub fn score_package(&self, package: &PackageNode, findings: &[&AuditFinding]) -> RiskScore {
+        let mut factors = Vec::new();
+        
+        // Classification
+        let (classification, points) = match &package.classification {
+            Classification::TCS { .. } => ("tcs", 25.0),
+            Classification::Unknown => ("unknown", 15.0),
+            Classification::Mechanical { .. } => ("mechanical", 5.0),
+        };
+        factors.push(RiskFactor::new("classification", classification.to_string(), points));
+        
+        // Audit status
+        let (status, points) = match &package.audit_status {
+            AuditStatus::Unaudited => ("unaudited", 20.0),
+            AuditStatus::Exempted { .. } => ("exempted", 10.0),
+            AuditStatus::Audited { .. } => ("audited", 0.0),
+        };
+        factors.push(RiskFactor::new("audit_status", status.to_string(), points));
+        
+        // Findings, by most severe
+        if let Some(worst) = findings.iter().map(|f| Self::severity_points(&f.severity)).reduce(f64::max) {
+            factors.push(RiskFactor::new("findings", format!("{} finding(s)", findings.len()), worst));
+        }
+        
+        // Source type
+        let (source, points) = match &package.source {
+            PackageSource::Git { .. } => ("git", 15.0),
+            PackageSource::Local { .. } => ("local", 5.0),
+            PackageSource::Registry { .. } => ("registry", 0.0),
+        };
+        factors.push(RiskFactor::new("source", source.to_string(), points));
+        
+        // Release activity, when registry metadata was fetched
+        if let Some(days) = package.annotation(annotation_keys::DAYS_SINCE_LAST_RELEASE).and_then(|v| v.as_u64()) {
+            let points = if days >= self.config.stale_after_days { 10.0 } else { 0.0 };
+            factors.push(RiskFactor::new("days_since_last_release", days.to_string(), points));
+        }
+        
+        RiskScore::from_factors(factors)
+    }
+    
+    /// Points contributed by a finding severity
+    fn severity_points(severity: &Severity) -> f64 {
+        match severity {
+            Severity::Critical => 30.0,
+            Severity::High => 20.0,
+            Severity::Medium => 10.0,
+            Severity::Low => 5.0,
+            Severity::Info => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RustAdapterConfig;
+    use crate::test_support::package;
+    
+    fn crypto() -> Classification {
+        Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() }
+    }
+    
+    #[test]
+    fn test_package_score_combines_factors() {
+        let scorer = RiskScorer::new(&RustAdapterConfig::default());
+        
+        let mut node = PackageNode {
+            source: PackageSource::Git {
+                url: "https://github.com/sfackler/rust-openssl".to_string(),
+                rev: "abc123".to_string(),
+                checksum: String::new(),
+            },
+            ..package("openssl", "1.0.0", crypto())
+        };
+        node.set_annotation(annotation_keys::DAYS_SINCE_LAST_RELEASE, serde_json::json!(1000));
+        
+        let finding = AuditFinding::new(
+            "RUSTSEC-2023-0001".to_string(),
+            "openssl".to_string(),
+            "unknown".to_string(),
+            Severity::High,
+            "Example".to_string(),
+        );
+        
+        let risk = scorer.score_package(&node, &[&finding]);
+        // tcs 25 + unaudited 20 + high finding 20 + git 15 + stale 10
+        assert_eq!(risk.score, 90.0);
+        assert!(risk.informational);
+        assert_eq!(risk.factors.len(), 5);
+    }
+    
+    #[test]
+    fn test_graph_scoring_annotates_and_summarizes() {
+        let mut config = RustAdapterConfig::default();
+        config.risk_config.summary_limit = 2;
+        let scorer = RiskScorer::new(&config);
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(package("itoa", "1.0.0", Classification::Mechanical { category: MechanicalCategory::Utility }));
+        graph.add_package(package("ring", "0.16.20", crypto()));
+        graph.add_package(package("ring", "0.17.8", crypto()));
+        
+        // Reported for the older ring only
+        let mut finding = AuditFinding::new(
+            "RUSTSEC-2025-0009".to_string(),
+            "ring".to_string(),
+            "<0.17.12".to_string(),
+            Severity::High,
+            "Example".to_string(),
+        );
+        finding.package_version = Some("0.16.20".to_string());
+        
+        let summary = scorer.score_graph(&mut graph, &[finding]);
+        
+        assert_eq!(summary.package_count, 3);
+        assert_eq!(summary.max_score, 65.0);
+        assert_eq!(summary.highest.len(), 2);
+        assert_eq!((summary.highest[0].package_version.as_str(), summary.highest[0].score), ("0.16.20", 65.0));
+        assert_eq!((summary.highest[1].package_version.as_str(), summary.highest[1].score), ("0.17.8", 45.0));
+        assert_eq!(summary.notice, RISK_INFORMATIONAL_NOTICE);
+        assert!(graph.root_packages.iter().all(|p| p.annotation(annotation_keys::RISK_SCORE).is_some()));
+    }
+}
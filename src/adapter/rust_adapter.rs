@@ -4,11 +4,21 @@
 //! providing comprehensive dependency analysis, TCS classification,
 //! security auditing, vendoring, SBOM generation, and drift detection.
 
+use super::{
+    attribution_collector, audit_runner, badge_generator, binary_provenance, build_execution, bundle_importer,
+    dependency_parser, doctor, drift_detector, epoch_exporter, export_redactor, fact_collectors, facts_exporter,
+    gate_evaluator, org_rollup, registry_metadata, risk_scorer, sbom_generator, tcs_classifier, update_simulator,
+    upgrade_advisor, vendor_manager, version_skew,
+};
+use super::sbom_generator::Sbom;
 use crate::models::*;
 use crate::config::RustAdapterConfig;
 use crate::error::{AdapterError, Result};
+use crate::utils::{deadline, telemetry, CancellationToken, EventSink, ToolInvoker};
+use std::future::Future;
 use async_trait::async_trait;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Main Rust adapter implementing the EcosystemAdapter trait
 #[derive(Debug, Clone)]
@@ -19,25 +29,109 @@ pub struct RustAdapter {
     dependency_parser: dependency_parser::DependencyParser,
     tcs_classifier: tcs_classifier::TcsClassifier,
     audit_runner: audit_runner::AuditRunner,
+    binary_provenance: binary_provenance::BinaryProvenanceVerifier,
     vendor_manager: vendor_manager::VendorManager,
     sbom_generator: sbom_generator::SbomGenerator,
     drift_detector: drift_detector::DriftDetector,
+    risk_scorer: risk_scorer::RiskScorer,
+    registry_metadata: registry_metadata::RegistryMetadataFetcher,
+    fact_collectors: fact_collectors::FactCollectorRunner,
+    facts_exporter: facts_exporter::FactsExporter,
+    epoch_exporter: epoch_exporter::EpochExporter,
+    build_execution: build_execution::BuildExecutionAnalyzer,
+    version_skew: version_skew::VersionSkewAnalyzer,
+    badge_generator: badge_generator::BadgeGenerator,
+    update_simulator: update_simulator::UpdateSimulator,
+    upgrade_advisor: upgrade_advisor::UpgradeAdvisor,
+    export_redactor: export_redactor::ExportRedactor,
+    org_rollup: org_rollup::OrgRollup,
+    gate_evaluator: gate_evaluator::GateEvaluator,
+    attribution_collector: attribution_collector::AttributionCollector,
+    doctor: doctor::Doctor,
+    bundle_importer: bundle_importer::BundleImporter,
+    /// Token shared by all components to cancel running operations
+    cancel: CancellationToken,
+    /// Stream progress and result events are reported to
+    events: EventSink,
 }
 
 impl RustAdapter {
     /// Create a new Rust adapter with the given configuration
     pub fn new(config: RustAdapterConfig) -> Self {
+        let cancel = CancellationToken::new();
+        // One limit on external tools across all components
+        let tool_limiter = Arc::new(tokio::sync::Semaphore::new(config.performance_config.max_parallel_tool_invocations.max(1)));
+        
         Self {
-            dependency_parser: dependency_parser::DependencyParser::new(&config),
+            dependency_parser: dependency_parser::DependencyParser::new(&config)
+                .with_cancellation_token(cancel.clone()),
             tcs_classifier: tcs_classifier::TcsClassifier::new(&config),
-            audit_runner: audit_runner::AuditRunner::new(&config),
-            vendor_manager: vendor_manager::VendorManager::new(&config),
+            audit_runner: audit_runner::AuditRunner::new(&config)
+                .with_cancellation_token(cancel.clone())
+                .with_tool_limiter(tool_limiter.clone()),
+            binary_provenance: binary_provenance::BinaryProvenanceVerifier::new(&config),
+            vendor_manager: vendor_manager::VendorManager::new(&config)
+                .with_cancellation_token(cancel.clone()),
             sbom_generator: sbom_generator::SbomGenerator::new(&config),
             drift_detector: drift_detector::DriftDetector::new(&config),
+            risk_scorer: risk_scorer::RiskScorer::new(&config),
+            registry_metadata: registry_metadata::RegistryMetadataFetcher::new(&config)
+                .with_cancellation_token(cancel.clone()),
+            fact_collectors: fact_collectors::FactCollectorRunner::new(&config)
+                .with_cancellation_token(cancel.clone())
+                .with_tool_limiter(tool_limiter),
+            facts_exporter: facts_exporter::FactsExporter::new(&config),
+            epoch_exporter: epoch_exporter::EpochExporter::new(&config),
+            build_execution: build_execution::BuildExecutionAnalyzer::new(&config),
+            version_skew: version_skew::VersionSkewAnalyzer::new(&config),
+            badge_generator: badge_generator::BadgeGenerator::new(&config),
+            update_simulator: update_simulator::UpdateSimulator::new(&config)
+                .with_cancellation_token(cancel.clone()),
+            upgrade_advisor: upgrade_advisor::UpgradeAdvisor::new(&config),
+            export_redactor: export_redactor::ExportRedactor::new(&config),
+            org_rollup: org_rollup::OrgRollup::new(&config),
+            gate_evaluator: gate_evaluator::GateEvaluator::new(&config),
+            attribution_collector: attribution_collector::AttributionCollector::new(&config),
+            doctor: doctor::Doctor::new(&config)
+                .with_cancellation_token(cancel.clone()),
+            bundle_importer: bundle_importer::BundleImporter::new(&config),
+            cancel,
+            events: EventSink::disabled(),
             config,
         }
     }
     
+    /// Report progress and result events to `events`
+    pub fn with_event_sink(mut self, events: EventSink) -> Self {
+        self.drift_detector = self.drift_detector.with_event_sink(events.clone());
+        self.events = events;
+        self
+    }
+    
+    /// Run cargo and the audit tools of every component through `tools`
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.dependency_parser = self.dependency_parser.with_tool_invoker(tools.clone());
+        self.audit_runner = self.audit_runner.with_tool_invoker(tools.clone());
+        self.vendor_manager = self.vendor_manager.with_tool_invoker(tools.clone());
+        self.fact_collectors = self.fact_collectors.with_tool_invoker(tools.clone());
+        self.update_simulator = self.update_simulator.with_tool_invoker(tools.clone());
+        self.doctor = self.doctor.with_tool_invoker(tools);
+        self
+    }
+    
+    /// Get the stream events are reported to
+    pub fn event_sink(&self) -> &EventSink {
+        &self.events
+    }
+    
+    /// Get the token that cancels running operations
+    ///
+    /// Cancelling it kills any child process started by the adapter and
+    /// makes in-flight operations return `AdapterError::Cancelled`.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+    
     /// Get a reference to the adapter configuration
     pub fn config(&self) -> &RustAdapterConfig {
         &self.config
@@ -58,6 +152,11 @@ impl RustAdapter {
         &self.audit_runner
     }
     
+    /// Get a reference to the binary provenance verifier
+    pub fn binary_provenance(&self) -> &binary_provenance::BinaryProvenanceVerifier {
+        &self.binary_provenance
+    }
+    
     /// Get a reference to the vendor manager
     pub fn vendor_manager(&self) -> &vendor_manager::VendorManager {
         &self.vendor_manager
@@ -72,6 +171,544 @@ impl RustAdapter {
     pub fn drift_detector(&self) -> &drift_detector::DriftDetector {
         &self.drift_detector
     }
+    
+    /// Get a reference to the risk scorer
+    pub fn risk_scorer(&self) -> &risk_scorer::RiskScorer {
+        &self.risk_scorer
+    }
+    
+    /// Get a reference to the registry metadata fetcher
+    pub fn registry_metadata(&self) -> &registry_metadata::RegistryMetadataFetcher {
+        &self.registry_metadata
+    }
+    
+    /// Get a reference to the custom fact collector runner
+    pub fn fact_collectors(&self) -> &fact_collectors::FactCollectorRunner {
+        &self.fact_collectors
+    }
+    
+    /// Get a reference to the policy facts exporter
+    pub fn facts_exporter(&self) -> &facts_exporter::FactsExporter {
+        &self.facts_exporter
+    }
+    
+    /// Get a reference to the universal epoch exporter
+    pub fn epoch_exporter(&self) -> &epoch_exporter::EpochExporter {
+        &self.epoch_exporter
+    }
+    
+    /// Get a reference to the build-time execution analyzer
+    pub fn build_execution(&self) -> &build_execution::BuildExecutionAnalyzer {
+        &self.build_execution
+    }
+    
+    /// Get a reference to the version skew analyzer
+    pub fn version_skew(&self) -> &version_skew::VersionSkewAnalyzer {
+        &self.version_skew
+    }
+    
+    /// Get a reference to the badge generator
+    pub fn badge_generator(&self) -> &badge_generator::BadgeGenerator {
+        &self.badge_generator
+    }
+    
+    /// Get a reference to the update simulator
+    pub fn update_simulator(&self) -> &update_simulator::UpdateSimulator {
+        &self.update_simulator
+    }
+    
+    /// Get a reference to the upgrade advisor
+    pub fn upgrade_advisor(&self) -> &upgrade_advisor::UpgradeAdvisor {
+        &self.upgrade_advisor
+    }
+    
+    /// Get a reference to the export redactor
+    pub fn export_redactor(&self) -> &export_redactor::ExportRedactor {
+        &self.export_redactor
+    }
+    
+    /// Get a reference to the organization rollup
+    pub fn org_rollup(&self) -> &org_rollup::OrgRollup {
+        &self.org_rollup
+    }
+    
+    /// Get a reference to the gate evaluator
+    pub fn gate_evaluator(&self) -> &gate_evaluator::GateEvaluator {
+        &self.gate_evaluator
+    }
+    
+    /// Get a reference to the attribution collector
+    pub fn attribution_collector(&self) -> &attribution_collector::AttributionCollector {
+        &self.attribution_collector
+    }
+    
+    /// Get a reference to the environment doctor
+    pub fn doctor(&self) -> &doctor::Doctor {
+        &self.doctor
+    }
+    
+    /// Get a reference to the security data bundle importer
+    pub fn bundle_importer(&self) -> &bundle_importer::BundleImporter {
+        &self.bundle_importer
+    }
+    
+    /// Parse and classify Cargo.lock content without a project directory
+    ///
+    /// Nothing is read from disk and no cargo command or registry request
+    /// runs, so the graph has no workspace members, direct dependencies or
+    /// popularity data.
+    #[tracing::instrument(skip_all)]
+    pub async fn parse_lockfile_str(&self, lockfile_content: &str) -> Result<DependencyGraph> {
+        let mut dependency_graph = self.run_operation("parse", async {
+            self.dependency_parser.parse_lockfile_str(lockfile_content)
+        }).await?;
+        telemetry::record_packages("parse", dependency_graph.root_packages.len());
+
+        let project = dependency_parser::DependencyParser::in_memory_project();
+        self.run_operation("classify", async {
+            self.tcs_classifier.classify_graph(&project, &mut dependency_graph)
+        }).await?;
+
+        Ok(dependency_graph)
+    }
+
+    /// Run an operation within its `deadline_config` budget, recording its duration
+    ///
+    /// The operation is reported as a stage on the events stream.
+    async fn run_operation<T>(&self, operation: &'static str, future: impl Future<Output = Result<T>>) -> Result<T> {
+        let budget = self.config.deadline_config.budget(operation);
+        self.events.stage(operation, telemetry::measure(operation, deadline::enforce(operation, budget, future))).await
+    }
+    
+    /// Run the security audit and resolve its findings against a parsed graph
+    async fn audit_against(&self, project: &Project, dependency_graph: &DependencyGraph) -> Result<AuditReport> {
+        let audit_report = self.run_operation("audit", async {
+            let mut audit_report = self.audit_runner.run_comprehensive_audit(project).await?;
+            self.audit_runner.map_findings_to_graph(&mut audit_report, dependency_graph);
+            let manifest = crate::manifest::WorkspaceManifest::load(&project.paths.root).ok();
+            audit_report.upgrade_suggestions = self.upgrade_advisor.suggest(&audit_report, dependency_graph, manifest.as_ref());
+            audit_report.sort_canonical();
+            Ok::<_, AdapterError>(audit_report)
+        }).await?;
+        
+        let mut by_severity: std::collections::BTreeMap<&'static str, usize> = std::collections::BTreeMap::new();
+        for finding in &audit_report.findings {
+            *by_severity.entry(finding.severity.as_str()).or_default() += 1;
+            self.events.emit(AdapterEvent::FindingEmitted {
+                id: finding.id.clone(),
+                package_name: finding.package_name.clone(),
+                package_version: finding.package_version.clone(),
+                severity: finding.severity.clone(),
+            });
+        }
+        for (severity, count) in by_severity {
+            telemetry::record_findings(severity, count);
+        }
+        
+        Ok(audit_report)
+    }
+    
+    /// Gather policy facts for a project
+    ///
+    /// Runs the security audit unless `run_audit` is false and, when an
+    /// epoch is given, verifies its vendor snapshot. A failed snapshot
+    /// verification is recorded as a fact rather than returned as an error.
+    /// Audit criteria required by the project policy (and by
+    /// `[project.policy.audit_criteria]` in `project.toml`) are checked
+    /// against the criteria TCS packages were audited for. When the
+    /// workspace manifest can be read, the version pinning of TCS
+    /// dependencies is recorded too.
+    pub async fn policy_facts(&self, project: &Project, epoch_id: Option<&str>, run_audit: bool) -> Result<PolicyFacts> {
+        let mut dependency_graph = self.parse_dependencies(project).await?;
+        
+        let audit_report = if run_audit {
+            Some(self.audit_against(project, &dependency_graph).await?)
+        } else {
+            None
+        };
+        
+        let findings = audit_report.as_ref().map(|r| r.findings.as_slice()).unwrap_or_default();
+        self.risk_scorer.score_graph(&mut dependency_graph, findings);
+        
+        let vendor = epoch_id.map(|epoch_id| {
+            self.facts_exporter.vendor_facts(
+                epoch_id,
+                self.vendor_manager.verify_epoch_snapshot(project, epoch_id),
+                &dependency_graph.metadata.lockfile_fingerprint,
+            )
+        });
+        
+        let mut requirements = project.policy.audit_criteria.clone();
+        requirements.extend(crate::config::load_audit_criteria(project)?);
+        
+        let mut facts = self.facts_exporter.export(&dependency_graph, audit_report.as_ref(), vendor);
+        self.facts_exporter.add_criteria_gaps(&mut facts, &dependency_graph, &requirements);
+        if let Ok(manifest) = crate::manifest::WorkspaceManifest::load(&project.paths.root) {
+            let pinning = self.version_skew.pinning(&manifest, &dependency_graph);
+            self.facts_exporter.add_pinning(&mut facts, pinning);
+        }
+        Ok(facts)
+    }
+    
+    /// Describe an epoch in the Control Plane's universal format
+    ///
+    /// The epoch's vendor snapshot is verified first, so the exported
+    /// graph is the one of the Cargo.lock the epoch pins.
+    pub async fn export_epoch(&self, project: &Project, epoch_id: &str) -> Result<UniversalEpoch> {
+        let snapshot = self.vendor_manager.verify_epoch_snapshot(project, epoch_id)?;
+        let dependency_graph = self.parse_dependencies(project).await?;
+
+        self.epoch_exporter.export(&snapshot, &dependency_graph)
+    }
+
+    /// Compare declared version requirements with resolved versions
+    pub async fn version_skew_report(&self, project: &Project) -> Result<VersionSkewReport> {
+        let manifest = crate::manifest::WorkspaceManifest::load(&project.paths.root)?;
+        let dependency_graph = self.parse_dependencies(project).await?;
+        
+        Ok(self.version_skew.analyze(&manifest, &dependency_graph))
+    }
+    
+    /// Trace dependency drift from an epoch through all later epochs
+    ///
+    /// Epochs are ordered by snapshot creation time. The current Cargo.lock
+    /// is the last point of the timeline; its changes are reported as not
+    /// approved by any epoch.
+    pub async fn drift_timeline(&self, project: &Project, since: &str) -> Result<DriftTimeline> {
+        let snapshots = self.vendor_manager.list_epochs(project)?;
+        let start = snapshots.iter()
+            .position(|snapshot| snapshot.epoch_id == since)
+            .ok_or_else(|| AdapterError::EpochInvalidated {
+                epoch_id: since.to_string(),
+                reason: "No vendor snapshot for epoch".to_string(),
+                source: anyhow::anyhow!("Unknown epoch"),
+            })?;
+        
+        let mut states = Vec::new();
+        for snapshot in &snapshots[start..] {
+            states.push(EpochPackageState {
+                epoch_id: Some(snapshot.epoch_id.clone()),
+                created_at: Some(snapshot.created_at.clone()),
+                packages: self.vendor_manager.epoch_packages(project, &snapshot.epoch_id)?.packages,
+            });
+        }
+        states.push(EpochPackageState {
+            epoch_id: None,
+            created_at: None,
+            packages: self.vendor_manager.locked_packages(project)?.packages,
+        });
+        
+        let dependency_graph = self.parse_dependencies(project).await?;
+        self.drift_detector.drift_timeline(&states, &dependency_graph)
+    }
+    
+    /// Packages that drifted since the latest vendor snapshot
+    ///
+    /// Without a snapshot there is no approved baseline, so nothing is
+    /// reported. Snapshots refuse to run until each of these packages has
+    /// an approved drift decision.
+    pub async fn pending_drift(&self, project: &Project) -> Result<Vec<String>> {
+        let Some(latest) = self.vendor_manager.list_epochs(project)?.pop() else {
+            return Ok(Vec::new());
+        };
+        
+        Ok(self.drift_timeline(project, &latest.epoch_id).await?.packages.into_keys().collect())
+    }
+    
+    /// Simulate proposed updates without touching the project
+    ///
+    /// The updates are resolved in a temporary copy of the project; the
+    /// returned drift report compares the resulting Cargo.lock with the
+    /// current one.
+    pub async fn simulate_update(&self, project: &Project, updates: &[ProposedUpdate]) -> Result<UpdateSimulation> {
+        let base_graph = self.parse_dependencies(project).await?;
+        let base_state = EpochPackageState {
+            epoch_id: None,
+            created_at: None,
+            packages: self.vendor_manager.locked_packages(project)?.packages,
+        };
+        
+        let workspace = self.update_simulator.prepare(project, updates).await?;
+        let graph = self.parse_dependencies(&workspace.project).await?;
+        let simulated_state = EpochPackageState {
+            epoch_id: None,
+            created_at: None,
+            packages: self.vendor_manager.locked_packages(&workspace.project)?.packages,
+        };
+        
+        let max_depth = crate::config::load_max_transitive_depth(project)?;
+        let drift_report = self.drift_detector.with_max_transitive_depth(max_depth)
+            .projected_drift(&base_state, &simulated_state, &base_graph, &graph);
+        Ok(UpdateSimulation {
+            updates: updates.to_vec(),
+            base_lockfile_fingerprint: base_graph.metadata.lockfile_fingerprint,
+            graph,
+            drift_report,
+        })
+    }
+    
+    /// Audit binaries built with cargo-auditable against an approved epoch
+    ///
+    /// Besides advisories, each report carries the provenance of the
+    /// binary (see [`Self::verify_binary_provenance`]).
+    pub async fn audit_binaries(&self, project: &Project, binaries: &[PathBuf], epoch_id: Option<&str>) -> Result<Vec<BinaryAuditReport>> {
+        let provenance = self.verify_binary_provenance(project, binaries, epoch_id)?;
+
+        let mut reports = Vec::new();
+        for (binary, provenance) in binaries.iter().zip(provenance) {
+            let audit = self.audit_runner.audit_binary(project, binary).await?;
+            reports.push(BinaryAuditReport {
+                findings: audit.findings,
+                provenance,
+                raw_cargo_audit: audit.raw_cargo_audit,
+            });
+        }
+        Ok(reports)
+    }
+
+    /// Check supply chain status including the provenance of release binaries
+    ///
+    /// The report of [`EcosystemAdapter::check_supply_chain`] gets a
+    /// binary provenance section; a binary built from dependencies the
+    /// epoch does not pin makes the status critical.
+    pub async fn check_release_supply_chain(&self, project: &Project, binaries: &[PathBuf], epoch_id: Option<&str>) -> Result<SupplyChainReport> {
+        let mut report = self.check_supply_chain(project).await?;
+        report.binary_provenance = self.verify_binary_provenance(project, binaries, epoch_id)?;
+        report.determine_status();
+        Ok(report)
+    }
+
+    /// Compare the dependencies embedded in binaries with an approved epoch
+    ///
+    /// Each binary must carry cargo-auditable data; its packages are
+    /// compared with those pinned by `epoch_id`, or by Cargo.lock without
+    /// an epoch.
+    pub fn verify_binary_provenance(&self, project: &Project, binaries: &[PathBuf], epoch_id: Option<&str>) -> Result<Vec<BinaryProvenance>> {
+        let expected = match epoch_id {
+            Some(epoch_id) => self.vendor_manager.epoch_packages(project, epoch_id)?.packages,
+            None => self.vendor_manager.locked_packages(project)?.packages,
+        };
+        binaries.iter()
+            .map(|binary| self.binary_provenance.verify(binary, epoch_id, &expected))
+            .collect()
+    }
+
+    /// Drift between two Cargo.lock files, outside any project
+    ///
+    /// Both lockfiles are parsed and classified like
+    /// [`Self::parse_lockfile_str`]; the report lists what changes going
+    /// from `before` to `after`, with priorities following `after`.
+    pub async fn lockfile_drift(&self, before: &Path, after: &Path) -> Result<DriftReport> {
+        let read = |path: &Path| std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::file_not_found(path, "reading lockfile"));
+        let (before_content, after_content) = (read(before)?, read(after)?);
+
+        let before_graph = self.parse_lockfile_str(&before_content).await?;
+        let after_graph = self.parse_lockfile_str(&after_content).await?;
+        let state = |path: &Path, content: &str| -> Result<EpochPackageState> {
+            Ok(EpochPackageState {
+                epoch_id: None,
+                created_at: None,
+                packages: self.vendor_manager.lockfile_packages(path, content)?.packages,
+            })
+        };
+
+        Ok(self.drift_detector.projected_drift(
+            &state(before, &before_content)?,
+            &state(after, &after_content)?,
+            &before_graph,
+            &after_graph,
+        ))
+    }
+
+    /// Build the pull request payload remediating one audit finding
+    ///
+    /// Each locked version the finding applies to is upgraded to its
+    /// suggested version in a temporary copy of the project, rewriting
+    /// manifest requirements that exclude it. The payload carries the
+    /// patch against the project; the project itself is not modified.
+    pub async fn remediate(&self, project: &Project, finding_id: &str) -> Result<RemediationPayload> {
+        let audit_report = self.run_audit(project).await?;
+        let upgrades = self.upgrade_advisor.upgrades_for(&audit_report, finding_id)?;
+        
+        let workspace = self.update_simulator.copy(project)?;
+        let manifest = crate::manifest::WorkspaceManifest::load(&workspace.project.paths.root)?;
+        let mut requirement_updates = Vec::new();
+        for upgrade in &upgrades {
+            requirement_updates.extend(self.upgrade_advisor.bump_requirements(&manifest, upgrade)?);
+        }
+        
+        let updates: Vec<ProposedUpdate> = upgrades.iter()
+            .filter_map(|upgrade| Some(ProposedUpdate {
+                name: format!("{}@{}", upgrade.package_name, upgrade.current_version),
+                version: upgrade.suggested_version.clone()?,
+            }))
+            .collect();
+        self.update_simulator.apply(&workspace, &updates).await?;
+        
+        let mut changed_files: Vec<String> = requirement_updates.iter().map(|update| update.manifest.clone()).collect();
+        changed_files.push("Cargo.lock".to_string());
+        changed_files.sort();
+        changed_files.dedup();
+        let patch = self.upgrade_advisor.patch(&project.paths.root, &workspace.project.paths.root, &changed_files)?;
+        
+        Ok(RemediationPayload {
+            finding_id: finding_id.to_string(),
+            upgrades,
+            requirement_updates,
+            changed_files,
+            patch,
+        })
+    }
+    
+    /// Parse several lockfiles of a project into one graph
+    ///
+    /// `lockfiles` are relative to the project root and name either a
+    /// Cargo.lock or the directory holding it (see
+    /// `manifest::discover_lockfiles`). Each is parsed as a project of its
+    /// own, and the graphs are merged with the lockfiles recorded on every
+    /// package; the first lockfile's fingerprint identifies the graph.
+    pub async fn parse_lockfiles(&self, project: &Project, lockfiles: &[PathBuf]) -> Result<DependencyGraph> {
+        let mut merged = DependencyGraph::new(project.id.clone(), "rust".to_string());
+        for lockfile in lockfiles {
+            let dir = match lockfile.file_name() {
+                Some(name) if name == crate::manifest::discovery::LOCKFILE => lockfile.parent().unwrap_or(Path::new("")),
+                _ => lockfile.as_path(),
+            };
+            let lockfile_path = project.paths.root.join(dir).join(crate::manifest::discovery::LOCKFILE);
+            if !lockfile_path.is_file() {
+                return Err(AdapterError::file_not_found(&lockfile_path, "reading selected lockfile"));
+            }
+            
+            let mut member = project.clone();
+            member.paths.root = project.paths.root.join(dir);
+            let graph = self.parse_dependencies(&member).await?;
+            let provenance = dir.join(crate::manifest::discovery::LOCKFILE).to_string_lossy().replace('\\', "/");
+            merged.merge_lockfile(graph, &provenance);
+        }
+        
+        Ok(merged)
+    }
+    
+    /// Parse and audit every project of the organization and roll them up
+    ///
+    /// Relative project paths are resolved against `base_dir`. Projects
+    /// are analyzed one after another by this adapter, so they share its
+    /// caches. A project failing to parse or audit is recorded in the
+    /// report; cancellation aborts the whole rollup.
+    pub async fn org_report(&self, base_dir: &Path) -> Result<OrgRollupReport> {
+        let org = &self.config.org_config;
+        let mut analyses = Vec::new();
+        
+        for org_project in &org.projects {
+            let name = org_project.name.clone().unwrap_or_else(|| org_project.id.clone());
+            let project = Project::new(org_project.id.clone(), name.clone(), "rust".to_string(), base_dir.join(&org_project.path));
+            
+            let result = match self.parse_dependencies(&project).await {
+                Ok(graph) => self.audit_against(&project, &graph).await.map(|audit_report| (graph, audit_report)),
+                Err(e) => Err(e),
+            };
+            if let Err(e @ AdapterError::Cancelled { .. }) = result {
+                return Err(e);
+            }
+            if let Err(e) = &result {
+                tracing::warn!(project = %org_project.id, error = %e, "Failed to analyze organization project");
+            }
+            analyses.push(org_rollup::OrgProjectAnalysis { id: org_project.id.clone(), name, result });
+        }
+        
+        Ok(self.org_rollup.rollup(&org.name, &analyses))
+    }
+    
+    /// Report crates executing code at build time
+    ///
+    /// With an epoch, the vendor snapshot pinned to it is verified first
+    /// and scanned, so the report describes exactly the sources being
+    /// approved. Otherwise `vendored` (or the project vendor directory)
+    /// is scanned as is.
+    pub fn build_execution_report(
+        &self,
+        project: &Project,
+        epoch_id: Option<&str>,
+        vendored: Option<&Path>,
+    ) -> Result<BuildExecutionReport> {
+        let (vendor_dir, lockfile_fingerprint) = match epoch_id {
+            Some(epoch_id) => {
+                let snapshot = self.vendor_manager.verify_epoch_snapshot(project, epoch_id)?;
+                (project.paths.root.join(&snapshot.storage_path), snapshot.lockfile_fingerprint)
+            },
+            None => {
+                let vendor_dir = vendored.map(Path::to_path_buf).unwrap_or_else(|| project.vendor_path());
+                let lockfile_fingerprint = crate::utils::ChecksumCalculator::new()
+                    .calculate_lockfile_fingerprint(project.lockfile_path())
+                    .unwrap_or_default();
+                (vendor_dir, lockfile_fingerprint)
+            },
+        };
+        
+        let mut report = self.build_execution.analyze_vendor(&vendor_dir)?;
+        report.epoch_id = epoch_id.map(str::to_string);
+        report.lockfile_fingerprint = lockfile_fingerprint;
+        
+        Ok(report)
+    }
+    
+    /// Collect the license and notice files of the SBOM's third-party packages
+    ///
+    /// With an epoch, the verified vendor snapshot of the epoch is read;
+    /// otherwise `vendored` or the project vendor directory.
+    pub async fn attribution_bundle(
+        &self,
+        project: &Project,
+        epoch_id: Option<&str>,
+        vendored: Option<&Path>,
+    ) -> Result<AttributionBundle> {
+        let vendor_dir = match epoch_id {
+            Some(epoch_id) => {
+                let snapshot = self.vendor_manager.verify_epoch_snapshot(project, epoch_id)?;
+                project.paths.root.join(&snapshot.storage_path)
+            },
+            None => vendored.map(Path::to_path_buf).unwrap_or_else(|| project.vendor_path()),
+        };
+        
+        let graph = self.sbom_generator.in_scope(&self.parse_dependencies(project).await?);
+        let packages: Vec<&PackageNode> = graph.root_packages.iter()
+            .filter(|package| !matches!(package.source, PackageSource::Local { .. }))
+            .filter(|package| self.sbom_generator.should_include_package(package))
+            .collect();
+        
+        let mut bundle = self.attribution_collector.collect(&packages, &vendor_dir)?;
+        bundle.epoch_id = epoch_id.map(str::to_string);
+        bundle.lockfile_fingerprint = graph.metadata.lockfile_fingerprint.clone();
+        
+        Ok(bundle)
+    }
+    
+    /// Check the environment the adapter runs in for `project`
+    ///
+    /// `config_error` is the error the configuration file failed to load
+    /// with, if defaults are in use.
+    pub async fn doctor_report(&self, project: &Project, config_error: Option<&AdapterError>) -> DoctorReport {
+        self.doctor.diagnose(project, &self.vendor_manager, config_error).await
+    }
+    
+    /// Generate an SBOM in `scope` rather than the configured scope profile
+    pub async fn generate_scoped_sbom(&self, project: &Project, scope: Option<SbomScope>) -> Result<Sbom> {
+        // 1. Parse dependencies to get current graph, scoped to the policy's depth limit
+        let mut dependency_graph = self.parse_dependencies(project).await?;
+        if let Some(max_depth) = crate::config::load_max_transitive_depth(project)? {
+            dependency_graph = dependency_graph.within_depth(max_depth);
+        }
+        
+        // 2. Generate SBOM using configured format, pruned to the scope profile
+        let generator = match scope {
+            Some(scope) => self.sbom_generator.with_scope(scope),
+            None => self.sbom_generator.clone(),
+        };
+        let sbom = self.run_operation("sbom", generator.generate_sbom(project, &dependency_graph)).await?;
+        telemetry::record_packages("sbom", generator.in_scope(&dependency_graph).root_packages.len());
+        Ok(sbom)
+    }
 }
 
 #[async_trait]
@@ -87,32 +724,32 @@ impl EcosystemAdapter for RustAdapter {
     }
     
     /// Parse dependencies from a Rust project
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn parse_dependencies(&self, project: &Project) -> Result<DependencyGraph> {
         // 1. Parse Cargo.lock as authoritative source
-        let mut dependency_graph = self.dependency_parser.parse_dependencies(project).await?;
+        let mut dependency_graph = self.run_operation("parse", self.dependency_parser.parse_dependencies(project)).await?;
+        telemetry::record_packages("parse", dependency_graph.root_packages.len());
         
         // 2. Apply TCS classification to all packages
-        for package in &mut dependency_graph.root_packages {
-            let classification_result = self.tcs_classifier.classify_package(package).await?;
-            package.classification = match classification_result.role {
-                ToolchainRole::TCS(category) => Classification::TCS {
-                    category,
-                    rationale: classification_result.signals.iter()
-                        .map(|s| s.description())
-                        .collect::<Vec<_>>()
-                        .join("; "),
-                },
-                ToolchainRole::Mechanical(category) => Classification::Mechanical { category },
-            };
+        self.run_operation("classify", async {
+            self.tcs_classifier.classify_graph(project, &mut dependency_graph)
+        }).await?;
+        
+        // 3. Attach registry popularity data (online mode only, best effort)
+        match self.registry_metadata.annotate_graph(project, &mut dependency_graph).await {
+            Ok(_) => {},
+            Err(e @ AdapterError::Cancelled { .. }) => return Err(e),
+            Err(e) => tracing::warn!(error = %e, "Failed to fetch registry popularity data"),
         }
         
-        // 3. Validate the graph
-        dependency_graph.validate().map_err(|msg| {
-            AdapterError::Internal {
-                message: format!("Dependency graph validation failed: {}", msg),
-                source: anyhow::anyhow!("Graph validation error"),
-            }
-        })?;
+        // 4. Attach facts of the configured custom collectors
+        self.run_operation("collect", self.fact_collectors.annotate_graph(project, &mut dependency_graph)).await?;
+        
+        // 5. Validate the graph
+        let validation = dependency_graph.validate().into_result()?;
+        for finding in validation.findings.iter().filter(|f| f.severity == GraphFindingSeverity::Warning) {
+            tracing::warn!(kind = ?finding.kind, "{}", finding.message);
+        }
         
         Ok(dependency_graph)
     }
@@ -133,7 +770,7 @@ impl EcosystemAdapter for RustAdapter {
                         signals: Vec::new(),
                     }
                 },
-                Classification::Mechanical { category } => {
+                Classification::Mechanical { category: _ } => {
                     TcsPackageClassification {
                         package_name: package.name.clone(),
                         package_version: package.version.clone(),
@@ -161,43 +798,59 @@ impl EcosystemAdapter for RustAdapter {
     
     /// Detect drift between expected epoch and actual dependency graph
     async fn detect_drift(&self, expected: &Epoch, actual: &DependencyGraph) -> Result<DriftReport> {
-        self.drift_detector.detect_drift(expected, actual).await
+        telemetry::measure("drift", self.drift_detector.detect_drift(expected, actual)).await
     }
     
     /// Run comprehensive security audit
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn run_audit(&self, project: &Project) -> Result<AuditReport> {
-        self.audit_runner.run_comprehensive_audit(project).await
+        let dependency_graph = self.parse_dependencies(project).await?;
+        self.audit_against(project, &dependency_graph).await
     }
     
     /// Check supply chain security status
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn check_supply_chain(&self, project: &Project) -> Result<SupplyChainReport> {
         // 1. Parse dependencies
-        let dependency_graph = self.parse_dependencies(project).await?;
+        let mut dependency_graph = self.parse_dependencies(project).await?;
         
         // 2. Run audit
-        let audit_report = self.run_audit(project).await?;
+        let audit_report = self.audit_against(project, &dependency_graph).await?;
         
         // 3. Generate supply chain report
         let mut supply_chain_report = SupplyChainReport::new();
+        supply_chain_report.metadata.insert(
+            "lockfile_fingerprint".to_string(),
+            serde_json::json!(dependency_graph.metadata.lockfile_fingerprint),
+        );
+        
+        // Attach informational risk indicators (not a policy verdict)
+        let risk_summary = self.risk_scorer.score_graph(&mut dependency_graph, &audit_report.findings);
+        supply_chain_report.metadata.insert(
+            "risk_summary".to_string(),
+            serde_json::to_value(&risk_summary).unwrap_or_default(),
+        );
         
         // Add audit findings
         for finding in audit_report.findings {
             supply_chain_report.add_audit_finding(finding);
         }
         
-        // Add audit proofs
+        // Add audit proofs, from the audit status or else from audit records
+        // imported with security data bundles; workspace members need none
+        // unless configured
+        let audit_records = self.bundle_importer.installed_audit_records(&project.ecosystem)?;
+        let audit_first_party = self.config.audit_config.audit_first_party;
         for package in &dependency_graph.root_packages {
+            if package.is_first_party() && !audit_first_party {
+                continue;
+            }
             if let Classification::TCS { .. } = &package.classification {
-                if let AuditStatus::Audited { method, auditor, date } = &package.audit_status {
-                    let proof = AuditProof {
-                        method: method.clone(),
-                        auditor: auditor.clone(),
-                        date: date.clone(),
-                        signature: None,
-                        criteria: None,
-                        notes: None,
-                    };
-                    supply_chain_report.add_audit_proof(package.id.to_string(), proof);
+                let proof = AuditProof::from_status(&package.audit_status).or_else(|| audit_records.iter()
+                    .find(|record| record.package_name == package.name && record.package_version == package.version)
+                    .map(AuditProof::from_record));
+                if let Some(proof) = proof {
+                    supply_chain_report.add_audit_proof(&package.name, &package.version, proof);
                 } else {
                     supply_chain_report.add_unaudited_tcs(package.name.clone());
                 }
@@ -211,13 +864,52 @@ impl EcosystemAdapter for RustAdapter {
     }
     
     /// Vendor dependencies to target directory
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn vendor_dependencies(&self, project: &Project, target: &Path) -> Result<()> {
-        self.vendor_manager.vendor_dependencies(project, target).await
+        self.run_operation("vendor", self.vendor_manager.vendor_dependencies(project, target)).await
     }
     
     /// Verify vendored dependencies
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn verify_vendored(&self, project: &Project, vendored: &Path) -> Result<()> {
-        let verification_report = self.vendor_manager.verify_vendored(project, vendored).await?;
+        let verification_report = self.run_operation("verify", self.vendor_manager.verify_vendored(project, vendored)).await?;
+        
+        for mismatch in &verification_report.checksum_mismatches {
+            self.events.emit(AdapterEvent::VerificationFailed {
+                subject: mismatch.package_name.clone(),
+                reason: mismatch.details.clone().unwrap_or_else(|| format!(
+                    "Checksum is {}, expected {}", mismatch.actual_checksum, mismatch.expected_checksum)),
+            });
+        }
+        for package in &verification_report.missing_dependencies {
+            self.events.emit(AdapterEvent::VerificationFailed {
+                subject: package.clone(),
+                reason: "Missing from the vendor directory".to_string(),
+            });
+        }
+        for issue in &verification_report.local_dependency_issues {
+            tracing::warn!(package = %issue.package_name, version = %issue.version, "Path dependency failed verification: {}", issue.reason);
+            self.events.emit(AdapterEvent::VerificationFailed {
+                subject: format!("{}@{}", issue.package_name, issue.version),
+                reason: issue.reason.clone(),
+            });
+        }
+        
+        for finding in &verification_report.secret_findings {
+            tracing::warn!(path = %finding.path, line = finding.line, rule = %finding.rule,
+                "Possible secret in vendored source: {}", finding.redacted);
+        }
+        if self.config.vendor_config.secret_scan.fail_on_findings && !verification_report.secret_findings.is_empty() {
+            let mut packages: Vec<String> = verification_report.secret_findings.iter()
+                .map(|finding| finding.package.clone())
+                .collect();
+            packages.dedup();
+            return Err(AdapterError::VendorVerificationFailed {
+                reason: format!("{} possible secret(s) in vendored sources", verification_report.secret_findings.len()),
+                affected_packages: packages,
+                source: anyhow::anyhow!("Secret scan found embedded credentials"),
+            });
+        }
         
         if !verification_report.epoch_valid {
             return Err(AdapterError::EpochInvalidated {
@@ -231,12 +923,9 @@ impl EcosystemAdapter for RustAdapter {
     }
     
     /// Generate SBOM in specified format
+    #[tracing::instrument(skip_all, fields(project = %project.id))]
     async fn generate_sbom(&self, project: &Project) -> Result<Sbom> {
-        // 1. Parse dependencies to get current graph
-        let dependency_graph = self.parse_dependencies(project).await?;
-        
-        // 2. Generate SBOM using configured format
-        self.sbom_generator.generate_sbom(project, &dependency_graph).await
+        self.generate_scoped_sbom(project, None).await
     }
 }
 
@@ -277,8 +966,8 @@ pub trait EcosystemAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::project_types::*;
-    use std::path::PathBuf;
+    
+    
     
     #[test]
     fn test_ecosystem_name() {
@@ -303,12 +992,50 @@ mod tests {
         let adapter = RustAdapter::new(config);
         
         // Verify all components are created
-        assert!(adapter.config().offline_mode == false);
+        assert!(!adapter.config().offline_mode);
         assert!(adapter.dependency_parser().is_ready());
         assert!(adapter.tcs_classifier().is_ready());
         assert!(adapter.audit_runner().is_ready());
+        assert!(adapter.binary_provenance().is_ready());
         assert!(adapter.vendor_manager().is_ready());
         assert!(adapter.sbom_generator().is_ready());
         assert!(adapter.drift_detector().is_ready());
+        assert!(adapter.badge_generator().is_ready());
+        assert!(adapter.update_simulator().is_ready());
+        assert!(adapter.upgrade_advisor().is_ready());
+        assert!(adapter.export_redactor().is_ready());
+        assert!(adapter.org_rollup().is_ready());
+        assert!(adapter.gate_evaluator().is_ready());
+        assert!(adapter.attribution_collector().is_ready());
+        assert!(adapter.doctor().is_ready());
+        assert!(adapter.bundle_importer().is_ready());
+    }
+    
+    #[tokio::test]
+    async fn test_lockfile_drift() {
+        let lockfile = |packages: &[(&str, &str)]| {
+            let mut content = "version = 3\n".to_string();
+            for (name, version) in packages {
+                content.push_str(&format!(
+                    "\n[[package]]\nname = \"{}\"\nversion = \"{}\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                    name, version,
+                ));
+            }
+            content
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let (before, after) = (dir.path().join("before.lock"), dir.path().join("after.lock"));
+        std::fs::write(&before, lockfile(&[("itoa", "1.0.9"), ("log", "0.4.20")])).unwrap();
+        std::fs::write(&after, lockfile(&[("itoa", "1.0.11"), ("memchr", "2.7.1")])).unwrap();
+        let adapter = RustAdapter::new(RustAdapterConfig::default());
+        
+        let report = adapter.lockfile_drift(&before, &after).await.unwrap();
+        let change = |name: &str| report.drifts.iter().find(|drift| drift.package_name == name).map(|drift| drift.change_type.clone());
+        assert_eq!(report.drifts.len(), 3);
+        assert_eq!(change("itoa"), Some(ChangeType::VersionChange));
+        assert_eq!(change("log"), Some(ChangeType::Removal));
+        assert_eq!(change("memchr"), Some(ChangeType::Addition));
+        
+        assert!(adapter.lockfile_drift(&before, &dir.path().join("missing.lock")).await.is_err());
     }
 }
\ No newline at end of file
@@ -4,10 +4,25 @@
 //! ensuring policy neutrality by not including vulnerability scoring.
 
 use crate::models::*;
-use crate::error::Result;
-use async_trait::async_trait;
+use crate::error::{AdapterError, Result};
+use crate::manifest::{LockfileVcsState, ManifestPackage, WorkspaceManifest, VCS_PROPERTY};
+use crate::utils::lockfile::CRATES_IO_SOURCE;
+use crate::utils::{deadline, normalize_source, LOCKFILE_CHECKSUM_ALGORITHM};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 
+/// Bundled schemas by format and specification version
+///
+/// These are subsets of the official schemas restricted to the fields the
+/// generator emits, with their own `$id`s.
+const SCHEMAS: &[(&str, &str, &str)] = &[
+    ("CycloneDX", "1.4", include_str!("../../schemas/cyclonedx-1.4.subset.schema.json")),
+    ("CycloneDX", "1.5", include_str!("../../schemas/cyclonedx-1.5.subset.schema.json")),
+    ("CycloneDX", "1.6", include_str!("../../schemas/cyclonedx-1.6.subset.schema.json")),
+    ("SPDX", "SPDX-2.3", include_str!("../../schemas/spdx-2.3.subset.schema.json")),
+    ("SPDX", "SPDX-3.0", include_str!("../../schemas/spdx-3.0.subset.schema.json")),
+];
+
 /// SBOM generator implementation
 #[derive(Debug, Clone)]
 pub struct SbomGenerator {
@@ -30,6 +45,16 @@ pub struct SbomGeneratorConfig {
     pub include_licenses: bool,
     /// Document author
     pub author: String,
+    /// CycloneDX specification version
+    pub cyclonedx_version: CycloneDxVersion,
+    /// SPDX specification version
+    pub spdx_version: SpdxVersion,
+    /// Scope profile the dependency graph is pruned to
+    pub scope: Option<SbomScope>,
+    /// Whether classification and audit status are embedded as properties
+    pub include_security_posture: bool,
+    /// CPE vendor of each crate, keyed by crate name
+    pub cpe_vendors: HashMap<String, String>,
 }
 
 impl SbomGenerator {
@@ -42,6 +67,11 @@ impl SbomGenerator {
                 include_build_dependencies: config.sbom_config.include_build_dependencies,
                 include_licenses: config.sbom_config.include_licenses,
                 author: config.sbom_config.author.clone(),
+                cyclonedx_version: config.sbom_config.cyclonedx_version,
+                spdx_version: config.sbom_config.spdx_version,
+                scope: config.sbom_config.scope,
+                include_security_posture: config.sbom_config.include_security_posture,
+                cpe_vendors: config.sbom_config.cpe_vendors.clone(),
             },
             ready: true,
         }
@@ -52,8 +82,28 @@ impl SbomGenerator {
         self.ready
     }
     
-    /// Generate SBOM in configured format
+    /// Generator using `scope` instead of the configured scope profile
+    pub fn with_scope(&self, scope: SbomScope) -> Self {
+        Self {
+            config: SbomGeneratorConfig { scope: Some(scope), ..self.config.clone() },
+            ready: self.ready,
+        }
+    }
+    
+    /// The part of the graph the configured scope profile covers
+    ///
+    /// Without a profile the graph is returned whole, and packages are
+    /// filtered by the include flags as they are described.
+    pub fn in_scope(&self, dependency_graph: &DependencyGraph) -> DependencyGraph {
+        match self.config.scope {
+            Some(scope) => dependency_graph.through_kinds(scope.dependency_kinds()),
+            None => dependency_graph.clone(),
+        }
+    }
+    
+    /// Generate SBOM in configured format and scope
     pub async fn generate_sbom(&self, project: &Project, dependency_graph: &DependencyGraph) -> Result<Sbom> {
+        let dependency_graph = &self.in_scope(dependency_graph);
         match self.config.format {
             SbomFormat::SpdxJson => {
                 let spdx_doc = self.generate_spdx(project, dependency_graph).await?;
@@ -66,82 +116,557 @@ impl SbomGenerator {
         }
     }
     
-    /// Generate SPDX 2.3 document
+    /// Generate SPDX document in the configured version
+    ///
+    /// SPDX 3.0 documents share the 2.3 model and are rendered as an
+    /// element graph when serialized (see [`SpdxDocument::to_spdx3`]).
+    /// First-party workspace crates are the packages the document describes.
     pub async fn generate_spdx(&self, project: &Project, dependency_graph: &DependencyGraph) -> Result<SpdxDocument> {
         let namespace = format!("https://example.com/{}", project.id);
         let mut spdx_doc = SpdxDocument::new(project.name.clone(), namespace);
+        spdx_doc.spdx_version = self.config.spdx_version.as_str().to_string();
+        spdx_doc.creation_info.creators.push(format!("Organization: {}", self.config.author));
+        
+        if !dependency_graph.metadata.lockfile_fingerprint.is_empty() {
+            spdx_doc.creation_info.comment = Some(format!(
+                "rust:lockfile_fingerprint={}",
+                dependency_graph.metadata.lockfile_fingerprint
+            ));
+        }
+        
+        let vcs = Self::vcs_summary(dependency_graph).into_iter()
+            .map(|(name, value)| format!("{}={}", name, value));
+        let lines: Vec<String> = spdx_doc.creation_info.comment.take().into_iter().chain(vcs).collect();
+        spdx_doc.creation_info.comment = (!lines.is_empty()).then(|| lines.join("\n"));
         
         // Add packages to SPDX document
+        deadline::set_stage("describing packages");
+        deadline::set_total(dependency_graph.root_packages.len());
+        let mut taken_ids = HashSet::from([spdx_doc.spdx_id.clone()]);
+        let mut package_ids = HashMap::new();
+        let mut described = Vec::new();
         for package in &dependency_graph.root_packages {
+            deadline::check()?;
+            deadline::advance(1);
+            
             // Skip dev dependencies if not included
             if !self.should_include_package(package) {
                 continue;
             }
             
-            let spdx_package = self.create_spdx_package(project, package).await?;
+            let mut spdx_package = self.create_spdx_package(project, package).await?;
+            spdx_package.spdx_id = unique_identifier(&spdx_package.spdx_id, &mut taken_ids);
+            package_ids.insert(package.id, spdx_package.spdx_id.clone());
             spdx_doc.add_package(spdx_package);
+            described.push(package);
+        }
+        
+        if self.config.include_security_posture {
+            let summary = self.posture_summary(&described).into_iter()
+                .map(|(name, value)| format!("{}={}", name, value));
+            let lines: Vec<String> = spdx_doc.creation_info.comment.take().into_iter().chain(summary).collect();
+            spdx_doc.creation_info.comment = Some(lines.join("\n"));
         }
         
         // Add relationships between packages
-        self.add_spdx_relationships(&mut spdx_doc, dependency_graph);
+        self.add_spdx_relationships(&mut spdx_doc, dependency_graph, &package_ids);
+        
+        // The document describes the workspace crates
+        for package in described.iter().filter(|package| package.is_first_party()) {
+            spdx_doc.add_relationship(SpdxRelationship {
+                spdx_element_id: spdx_doc.spdx_id.clone(),
+                related_spdx_element: package_ids[&package.id].clone(),
+                relationship_type: "DESCRIBES".to_string(),
+                comment: None,
+            });
+        }
+        spdx_doc.sort_canonical();
         
         Ok(spdx_doc)
     }
     
-    /// Generate CycloneDX 1.4 document
+    /// Generate CycloneDX document in the configured version
+    ///
+    /// From 1.5 on, the BOM also records its lifecycle phase and a
+    /// formulation describing the lockfile resolution it was built from.
+    /// First-party workspace crates are subcomponents of the root component.
     pub async fn generate_cyclonedx(&self, project: &Project, dependency_graph: &DependencyGraph) -> Result<CycloneDxDocument> {
         let mut cyclonedx_doc = CycloneDxDocument::new();
+        cyclonedx_doc.spec_version = self.config.cyclonedx_version.as_str().to_string();
+        
+        // Describe the project itself so merged fragments can be wired to it
+        let manifest = WorkspaceManifest::load(&project.paths.root).ok();
+        let root_component = self.create_root_component(project, manifest.as_ref().and_then(|m| m.root_package()));
+        cyclonedx_doc.metadata.component = Some(root_component);
+        
+        if !dependency_graph.metadata.lockfile_fingerprint.is_empty() {
+            cyclonedx_doc.metadata.properties = Some(vec![CycloneDxProperty {
+                name: "rust:lockfile_fingerprint".to_string(),
+                value: dependency_graph.metadata.lockfile_fingerprint.clone(),
+            }]);
+        }
+        for (name, value) in Self::vcs_summary(dependency_graph) {
+            let properties = cyclonedx_doc.metadata.properties.get_or_insert_with(Vec::new);
+            properties.push(CycloneDxProperty { name: name.to_string(), value });
+        }
         
         // Add components to CycloneDX document
+        deadline::set_stage("describing packages");
+        deadline::set_total(dependency_graph.root_packages.len());
+        let mut taken_refs = HashSet::from([project.id.clone()]);
+        let mut component_refs = HashMap::new();
+        let mut described = Vec::new();
+        let mut subcomponents = Vec::new();
         for package in &dependency_graph.root_packages {
+            deadline::check()?;
+            deadline::advance(1);
+            
             // Skip dev dependencies if not included
             if !self.should_include_package(package) {
                 continue;
             }
             
-            let cyclonedx_component = self.create_cyclonedx_component(project, package).await?;
-            cyclonedx_doc.add_component(cyclonedx_component);
+            let mut cyclonedx_component = self.create_cyclonedx_component(project, package).await?;
+            let bom_ref = unique_identifier(cyclonedx_component.bom_ref.as_deref().unwrap_or_default(), &mut taken_refs);
+            component_refs.insert(package.id, bom_ref.clone());
+            cyclonedx_component.bom_ref = Some(bom_ref);
+            
+            // Workspace crates are parts of the project rather than dependencies of it
+            match package.is_first_party() {
+                true => subcomponents.push(cyclonedx_component),
+                false => cyclonedx_doc.add_component(cyclonedx_component),
+            }
+            described.push(package);
+        }
+        if let Some(root_component) = cyclonedx_doc.metadata.component.as_mut() {
+            root_component.components = (!subcomponents.is_empty()).then_some(subcomponents);
+        }
+        
+        if self.config.include_security_posture {
+            let properties = cyclonedx_doc.metadata.properties.get_or_insert_with(Vec::new);
+            for (name, value) in self.posture_summary(&described) {
+                properties.push(CycloneDxProperty { name: name.to_string(), value });
+            }
         }
         
         // Add dependencies
-        self.add_cyclonedx_dependencies(&mut cyclonedx_doc, dependency_graph);
+        self.add_cyclonedx_dependencies(&mut cyclonedx_doc, dependency_graph, &component_refs);
+        
+        if self.config.cyclonedx_version >= CycloneDxVersion::V1_5 {
+            cyclonedx_doc.metadata.lifecycles = Some(vec![CycloneDxLifecycle { phase: "build".to_string() }]);
+            cyclonedx_doc.formulation = Some(vec![self.create_formula(project, dependency_graph)]);
+        }
+        cyclonedx_doc.sort_canonical();
         
         Ok(cyclonedx_doc)
     }
     
-    /// Determine if package should be included in SBOM
-    fn should_include_package(&self, package: &PackageNode) -> bool {
-        // Check annotations for dependency kind
-        for annotation in &package.annotations {
-            if annotation.key == RustAnnotation::keys::DEPENDENCY_KIND {
-                if let Some(kind_str) = annotation.value.as_str() {
-                    if kind_str == "dev" && !self.config.include_dev_dependencies {
-                        return false;
-                    }
-                    if kind_str == "build" && !self.config.include_build_dependencies {
-                        return false;
-                    }
+    /// Validate a generated SBOM against the bundled schema for its version
+    ///
+    /// Run after fragments are merged so the delivered document is checked.
+    pub fn validate(&self, sbom: &Sbom) -> Result<()> {
+        let (format, version) = match sbom {
+            Sbom::Spdx(doc) => ("SPDX", doc.spdx_version.as_str()),
+            Sbom::CycloneDx(doc) => ("CycloneDX", doc.spec_version.as_str()),
+        };
+        let schema = SCHEMAS.iter()
+            .find(|(schema_format, schema_version, _)| *schema_format == format && *schema_version == version)
+            .map(|(_, _, schema)| *schema)
+            .ok_or_else(|| AdapterError::SchemaValidationFailed {
+                errors: vec![format!("No bundled schema for {} {}", format, version)],
+                source: anyhow::anyhow!("Unsupported SBOM specification version"),
+            })?;
+        
+        let validator = serde_json::from_str::<serde_json::Value>(schema)
+            .map_err(|e| e.to_string())
+            .and_then(|schema| jsonschema::validator_for(&schema).map_err(|e| e.to_string()));
+        let errors = match (validator, serde_json::to_value(sbom)) {
+            (Ok(validator), Ok(document)) => validator.iter_errors(&document)
+                .map(|error| {
+                    let pointer = error.instance_path.to_string();
+                    format!("{}: {}", if pointer.is_empty() { "/" } else { &pointer }, error)
+                })
+                .collect(),
+            (Err(e), _) => vec![format!("Bundled {} {} schema is invalid: {}", format, version, e)],
+            (_, Err(e)) => vec![format!("SBOM could not be serialized: {}", e)],
+        };
+        if !errors.is_empty() {
+            return Err(AdapterError::SchemaValidationFailed {
+                errors,
+                source: anyhow::anyhow!("Generated SBOM does not match the {} {} schema", format, version),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Merge an external SBOM fragment into a generated SBOM
+    ///
+    /// Fragments describe vendored non-crate assets (protobufs, JS bundles) and
+    /// must use the same format as the generated SBOM. The fragment is fully
+    /// validated before anything is merged; its top-level elements are then
+    /// wired to the project root so they are reachable in the graph.
+    pub fn merge_fragment(&self, sbom: &mut Sbom, fragment: &serde_json::Value) -> Result<()> {
+        match sbom {
+            Sbom::Spdx(spdx_doc) => {
+                self.merge_spdx_fragment(spdx_doc, fragment)?;
+                spdx_doc.sort_canonical();
+            },
+            Sbom::CycloneDx(cyclonedx_doc) => {
+                self.merge_cyclonedx_fragment(cyclonedx_doc, fragment)?;
+                cyclonedx_doc.sort_canonical();
+            },
+        }
+        Ok(())
+    }
+    
+    /// Validate and merge an SPDX 2.x fragment
+    fn merge_spdx_fragment(&self, spdx_doc: &mut SpdxDocument, fragment: &serde_json::Value) -> Result<()> {
+        let mut errors = Vec::new();
+        
+        let spdx_version = fragment.get("spdxVersion").and_then(|v| v.as_str());
+        if !spdx_version.is_some_and(|v| v.starts_with("SPDX-2.")) {
+            errors.push("Fragment is not an SPDX 2.x document (missing or unsupported spdxVersion)".to_string());
+        }
+        let fragment_name = fragment.get("name").and_then(|v| v.as_str()).unwrap_or("unnamed fragment");
+        
+        let mut known_ids: HashSet<String> = spdx_doc.packages.iter().map(|p| p.spdx_id.clone()).collect();
+        known_ids.insert(spdx_doc.spdx_id.clone());
+        
+        // Convert fragment packages
+        let mut packages = Vec::new();
+        let fragment_packages = fragment.get("packages").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        for (index, value) in fragment_packages.iter().enumerate() {
+            let field = |name: &str| value.get(name).and_then(|v| v.as_str()).map(|v| v.to_string());
+            
+            let (spdx_id, name) = match (field("SPDXID"), field("name")) {
+                (Some(spdx_id), Some(name)) => (spdx_id, name),
+                _ => {
+                    errors.push(format!("packages[{}]: missing SPDXID or name", index));
+                    continue;
+                },
+            };
+            
+            if !known_ids.insert(spdx_id.clone()) {
+                errors.push(format!("packages[{}]: duplicate SPDXID '{}'", index, spdx_id));
+                continue;
+            }
+            
+            let mut package = SpdxPackage::new(name, field("versionInfo").unwrap_or_default());
+            package.spdx_id = spdx_id;
+            package.download_location = field("downloadLocation");
+            package.license_concluded = field("licenseConcluded");
+            package.license_declared = field("licenseDeclared");
+            package.description = field("description");
+            package.homepage = field("homepage");
+            package.supplier = field("supplier");
+            package.copyright_text = field("copyrightText");
+            
+            match value.get("externalRefs").cloned().map(serde_json::from_value::<Vec<SpdxExternalReference>>) {
+                Some(Ok(external_refs)) => package.external_refs = external_refs,
+                Some(Err(e)) => errors.push(format!("packages[{}].externalRefs: {}", index, e)),
+                None => {},
+            }
+            
+            for checksum in value.get("checksums").and_then(|v| v.as_array()).into_iter().flatten() {
+                let algorithm = checksum.get("algorithm").and_then(|v| v.as_str());
+                let checksum_value = checksum.get("checksumValue").and_then(|v| v.as_str());
+                if let (Some(algorithm), Some(checksum_value)) = (algorithm, checksum_value) {
+                    package = package.add_checksum(algorithm.to_string(), checksum_value.to_string());
+                }
+            }
+            
+            packages.push(package);
+        }
+        
+        // Convert fragment relationships, mapping the fragment document onto ours
+        let fragment_document_id = fragment.get("SPDXID").and_then(|v| v.as_str()).unwrap_or("SPDXRef-DOCUMENT");
+        let mut relationships = Vec::new();
+        let fragment_relationships = fragment.get("relationships").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        for (index, value) in fragment_relationships.iter().enumerate() {
+            let field = |name: &str| value.get(name).and_then(|v| v.as_str());
+            
+            let (element, related, relationship_type) = match (field("spdxElementId"), field("relatedSpdxElement"), field("relationshipType")) {
+                (Some(element), Some(related), Some(relationship_type)) => (element, related, relationship_type),
+                _ => {
+                    errors.push(format!("relationships[{}]: missing spdxElementId, relatedSpdxElement or relationshipType", index));
+                    continue;
+                },
+            };
+            
+            // Document-level relationships are replaced by our own wiring
+            if element == fragment_document_id {
+                continue;
+            }
+            
+            for id in [element, related] {
+                if !known_ids.contains(id) {
+                    errors.push(format!("relationships[{}]: unknown SPDX element '{}'", index, id));
+                }
+            }
+            
+            relationships.push(SpdxRelationship {
+                spdx_element_id: element.to_string(),
+                related_spdx_element: related.to_string(),
+                relationship_type: relationship_type.to_string(),
+                comment: field("comment").map(|c| c.to_string()),
+            });
+        }
+        
+        if !errors.is_empty() {
+            return Err(AdapterError::SchemaValidationFailed {
+                errors,
+                source: anyhow::anyhow!("Invalid SPDX fragment: {}", fragment_name),
+            });
+        }
+        
+        // Wire top-level fragment packages to the document
+        let nested: HashSet<&str> = relationships.iter()
+            .map(|r| r.related_spdx_element.as_str())
+            .collect();
+        let top_level: Vec<String> = packages.iter()
+            .map(|p| p.spdx_id.clone())
+            .filter(|id| !nested.contains(id.as_str()))
+            .collect();
+        
+        for spdx_id in top_level {
+            spdx_doc.add_relationship(SpdxRelationship {
+                spdx_element_id: spdx_doc.spdx_id.clone(),
+                related_spdx_element: spdx_id,
+                relationship_type: "CONTAINS".to_string(),
+                comment: Some(format!("Merged from SBOM fragment: {}", fragment_name)),
+            });
+        }
+        
+        for package in packages {
+            spdx_doc.add_package(package);
+        }
+        
+        for relationship in relationships {
+            spdx_doc.add_relationship(relationship);
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate and merge a CycloneDX fragment
+    fn merge_cyclonedx_fragment(&self, cyclonedx_doc: &mut CycloneDxDocument, fragment: &serde_json::Value) -> Result<()> {
+        let mut errors = Vec::new();
+        
+        if fragment.get("bomFormat").and_then(|v| v.as_str()) != Some("CycloneDX") {
+            errors.push("Fragment is not a CycloneDX document (bomFormat must be \"CycloneDX\")".to_string());
+        }
+        let fragment_name = fragment.pointer("/metadata/component/name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unnamed fragment");
+        
+        let mut known_refs = HashSet::new();
+        for component in cyclonedx_doc.all_components() {
+            Self::collect_bom_refs(component, &mut known_refs);
+        }
+        
+        // Convert fragment components
+        let mut components = Vec::new();
+        let fragment_components = fragment.get("components").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        for (index, value) in fragment_components.iter().enumerate() {
+            let path = format!("components[{}]", index);
+            if let Some(component) = Self::convert_fragment_component(value, &path, fragment_name, &mut known_refs, &mut errors) {
+                components.push(component);
+            }
+        }
+        
+        // Convert fragment dependencies
+        let fragment_root_ref = fragment.pointer("/metadata/component/bom-ref").and_then(|v| v.as_str());
+        let mut dependencies = Vec::new();
+        let fragment_dependencies = fragment.get("dependencies").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        for (index, value) in fragment_dependencies.iter().enumerate() {
+            let from_ref = match value.get("ref").and_then(|v| v.as_str()) {
+                Some(from_ref) => from_ref.to_string(),
+                None => {
+                    errors.push(format!("dependencies[{}]: missing ref", index));
+                    continue;
+                },
+            };
+            let depends_on: Vec<String> = value.get("dependsOn")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect();
+            
+            // Dependencies of the fragment root are replaced by our own wiring
+            if fragment_root_ref == Some(from_ref.as_str()) {
+                continue;
+            }
+            
+            for reference in std::iter::once(&from_ref).chain(depends_on.iter()) {
+                if !known_refs.contains(reference) {
+                    errors.push(format!("dependencies[{}]: unknown bom-ref '{}'", index, reference));
                 }
             }
+            
+            dependencies.push(CycloneDxDependency {
+                r#ref: from_ref,
+                depends_on,
+            });
+        }
+        
+        if !errors.is_empty() {
+            return Err(AdapterError::SchemaValidationFailed {
+                errors,
+                source: anyhow::anyhow!("Invalid CycloneDX fragment: {}", fragment_name),
+            });
+        }
+        
+        // Wire top-level fragment components to the project root component
+        let root_ref = cyclonedx_doc.metadata.component.as_ref().and_then(|c| c.bom_ref.clone());
+        if let Some(root_ref) = root_ref {
+            let nested: HashSet<&str> = dependencies.iter()
+                .flat_map(|d| d.depends_on.iter().map(|r| r.as_str()))
+                .collect();
+            let top_level: Vec<String> = components.iter()
+                .filter_map(|c| c.bom_ref.clone())
+                .filter(|r| !nested.contains(r.as_str()))
+                .collect();
+            
+            if !top_level.is_empty() {
+                cyclonedx_doc.add_dependency(CycloneDxDependency {
+                    r#ref: root_ref,
+                    depends_on: top_level,
+                });
+            }
+        }
+        
+        for component in components {
+            cyclonedx_doc.add_component(component);
+        }
+        
+        for dependency in dependencies {
+            cyclonedx_doc.add_dependency(dependency);
+        }
+        
+        Ok(())
+    }
+    
+    /// Convert a CycloneDX fragment component and its subcomponents
+    ///
+    /// Problems are added to `errors` under `path`; components that cannot
+    /// be converted are left out.
+    fn convert_fragment_component(
+        value: &serde_json::Value,
+        path: &str,
+        fragment_name: &str,
+        known_refs: &mut HashSet<String>,
+        errors: &mut Vec<String>,
+    ) -> Option<CycloneDxComponent> {
+        let field = |name: &str| value.get(name).and_then(|v| v.as_str()).map(|v| v.to_string());
+        
+        let name = match field("name") {
+            Some(name) => name,
+            None => {
+                errors.push(format!("{}: missing name", path));
+                return None;
+            },
+        };
+        let version = field("version").unwrap_or_default();
+        let bom_ref = field("bom-ref").unwrap_or_else(|| format!("{}@{}", name, version));
+        
+        if !known_refs.insert(bom_ref.clone()) {
+            errors.push(format!("{}: duplicate bom-ref '{}'", path, bom_ref));
+            return None;
+        }
+        
+        let mut component = CycloneDxComponent::new(name, version)
+            .with_type(field("type").unwrap_or_else(|| "library".to_string()))
+            .with_bom_ref(bom_ref)
+            .add_property("rust:sbom_fragment".to_string(), fragment_name.to_string());
+        
+        if let Some(description) = field("description") {
+            component = component.with_description(description);
+        }
+        if let Some(purl) = field("purl") {
+            component = component.with_purl(purl);
+        }
+        
+        for hash in value.get("hashes").and_then(|v| v.as_array()).into_iter().flatten() {
+            let alg = hash.get("alg").and_then(|v| v.as_str());
+            let content = hash.get("content").and_then(|v| v.as_str());
+            if let (Some(alg), Some(content)) = (alg, content) {
+                component = component.add_hash(alg.to_string(), content.to_string());
+            }
+        }
+        
+        match value.get("licenses").cloned().map(serde_json::from_value::<Vec<CycloneDxLicenseChoice>>) {
+            Some(Ok(licenses)) if !licenses.is_empty() => component.licenses = Some(licenses),
+            Some(Err(e)) => errors.push(format!("{}.licenses: {}", path, e)),
+            _ => {},
+        }
+        match value.get("externalReferences").cloned().map(serde_json::from_value::<Vec<CycloneDxExternalReference>>) {
+            Some(Ok(references)) => {
+                for reference in references {
+                    component = component.add_external_reference(reference);
+                }
+            },
+            Some(Err(e)) => errors.push(format!("{}.externalReferences: {}", path, e)),
+            None => {},
+        }
+        
+        let subcomponents: Vec<CycloneDxComponent> = value.get("components")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(index, child)| {
+                let child_path = format!("{}.components[{}]", path, index);
+                Self::convert_fragment_component(child, &child_path, fragment_name, known_refs, errors)
+            })
+            .collect();
+        if !subcomponents.is_empty() {
+            component.components = Some(subcomponents);
         }
         
-        true
+        Some(component)
+    }
+    
+    /// Add the references of a component and its subcomponents
+    fn collect_bom_refs(component: &CycloneDxComponent, refs: &mut HashSet<String>) {
+        refs.extend(component.bom_ref.clone());
+        for subcomponent in component.components.iter().flatten() {
+            Self::collect_bom_refs(subcomponent, refs);
+        }
+    }
+    
+    /// Determine if package should be included in SBOM
+    ///
+    /// With a scope profile, every package of the scoped graph is included.
+    pub fn should_include_package(&self, package: &PackageNode) -> bool {
+        if self.config.scope.is_some() {
+            return true;
+        }
+        match package.dependency_kind() {
+            Some(DependencyKind::Dev) => self.config.include_dev_dependencies,
+            Some(DependencyKind::Build) => self.config.include_build_dependencies,
+            _ => true,
+        }
     }
     
     /// Create SPDX package from dependency graph node
-    async fn create_spdx_package(&self, project: &Project, package: &PackageNode) -> Result<SpdxPackage> {
+    async fn create_spdx_package(&self, _project: &Project, package: &PackageNode) -> Result<SpdxPackage> {
         let mut spdx_package = SpdxPackage::new(package.name.clone(), package.version.clone());
         
         // Set download location
         let download_location = match &package.source {
             PackageSource::Registry { url, .. } => url.clone(),
             PackageSource::Git { url, .. } => url.clone(),
+            PackageSource::Local { path } if path.is_empty() => "NOASSERTION".to_string(),
             PackageSource::Local { path } => format!("file://{}", path),
         };
         spdx_package = spdx_package.with_download_location(download_location);
         
-        // Add checksums
-        spdx_package = spdx_package.add_checksum("SHA256", package.checksum.clone());
+        // Add checksums (git and path packages have none)
+        if !package.checksum.is_empty() {
+            spdx_package = spdx_package.add_checksum(LOCKFILE_CHECKSUM_ALGORITHM.spdx_label().to_string(), package.checksum.clone());
+        }
         
         // Add license information if enabled
         if self.config.include_licenses {
@@ -150,10 +675,30 @@ impl SbomGenerator {
             spdx_package = spdx_package.with_license("MIT OR Apache-2.0".to_string());
         }
         
+        // Add descriptive metadata from cargo metadata annotations
+        if let Some(description) = package.annotation_str(annotation_keys::DESCRIPTION) {
+            spdx_package = spdx_package.with_description(description.to_string());
+        }
+        
+        if let Some(homepage) = package.annotation_str(annotation_keys::HOMEPAGE) {
+            spdx_package = spdx_package.with_homepage(homepage.to_string());
+        }
+        
+        if let Some(supplier) = self.supplier_name(package) {
+            spdx_package = spdx_package.with_supplier(format!("Person: {}", supplier));
+        }
+        
         // Add external references
+        spdx_package = spdx_package.add_external_reference(SpdxExternalReference {
+            reference_category: "PACKAGE-MANAGER".to_string(),
+            reference_type: "purl".to_string(),
+            reference_locator: self.package_url(package),
+            comment: None,
+        });
+        
         if let PackageSource::Git { url, rev, .. } = &package.source {
             let git_ref = SpdxExternalReference {
-                reference_category: "VCS".to_string(),
+                reference_category: "OTHER".to_string(),
                 reference_type: "git".to_string(),
                 reference_locator: url.clone(),
                 comment: Some(format!("Commit: {}", rev)),
@@ -161,41 +706,118 @@ impl SbomGenerator {
             spdx_package = spdx_package.add_external_reference(git_ref);
         }
         
+        let git_url = match &package.source {
+            PackageSource::Git { url, .. } => Some(Self::normalize_repository_url(url)),
+            _ => None,
+        };
+        let repository = package.annotation_str(annotation_keys::REPOSITORY)
+            .filter(|repository| git_url != Some(Self::normalize_repository_url(repository)));
+        if let Some(repository) = repository {
+            let repository_ref = SpdxExternalReference {
+                reference_category: "OTHER".to_string(),
+                reference_type: "repository".to_string(),
+                reference_locator: repository.to_string(),
+                comment: None,
+            };
+            spdx_package = spdx_package.add_external_reference(repository_ref);
+        }
+        
+        if let Some(cpe) = self.derive_cpe(package) {
+            let cpe_ref = SpdxExternalReference {
+                reference_category: "SECURITY".to_string(),
+                reference_type: "cpe23Type".to_string(),
+                reference_locator: cpe,
+                comment: None,
+            };
+            spdx_package = spdx_package.add_external_reference(cpe_ref);
+        }
+        
+        if self.config.include_security_posture {
+            let lines: Vec<String> = self.posture_properties(package).into_iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            spdx_package.comment = Some(lines.join("\n"));
+        }
+        
         Ok(spdx_package)
     }
     
     /// Create CycloneDX component from dependency graph node
-    async fn create_cyclonedx_component(&self, project: &Project, package: &PackageNode) -> Result<CycloneDxComponent> {
-        let mut component = CycloneDxComponent::new(package.name.clone(), package.version.clone());
+    async fn create_cyclonedx_component(&self, _project: &Project, package: &PackageNode) -> Result<CycloneDxComponent> {
+        let purl = self.package_url(package);
+        let mut component = CycloneDxComponent::new(package.name.clone(), package.version.clone())
+            .with_bom_ref(purl.clone())
+            .with_purl(purl);
         
-        // Add hashes
-        component = component.add_hash("SHA-256", package.checksum.clone());
+        // Add hashes (git and path packages have none)
+        if !package.checksum.is_empty() {
+            component = component.add_hash(LOCKFILE_CHECKSUM_ALGORITHM.cyclonedx_label().to_string(), package.checksum.clone());
+        }
         
         // Add scope based on dependency kind
-        let scope = self.get_component_scope(package);
-        component = component.with_scope(scope);
+        if let Some(scope) = self.get_component_scope(package) {
+            component = component.with_scope(scope);
+        }
         
         // Add license information if enabled
         if self.config.include_licenses {
-            let license_choice = CycloneDxLicenseChoice::Expression("MIT OR Apache-2.0".to_string());
+            let license_choice = CycloneDxLicenseChoice::Expression { expression: "MIT OR Apache-2.0".to_string() };
             component = component.with_license(license_choice);
         }
         
+        // Add descriptive metadata from cargo metadata annotations
+        if let Some(description) = package.annotation_str(annotation_keys::DESCRIPTION) {
+            component = component.with_description(description.to_string());
+        }
+        
+        if let Some(supplier) = self.supplier_name(package) {
+            component = component.with_supplier(CycloneDxOrganizationalEntity {
+                name: supplier,
+                url: Vec::new(),
+            });
+        }
+        
         // Add external references
         if let PackageSource::Git { url, .. } = &package.source {
-            let external_ref = CycloneDxExternalReference {
+            component = component.add_external_reference(CycloneDxExternalReference {
                 r#type: "vcs".to_string(),
                 url: url.clone(),
                 comment: Some("Git repository".to_string()),
-            };
-            if component.external_references.is_none() {
-                component.external_references = Some(vec![external_ref]);
-            } else {
-                component.external_references.as_mut().unwrap().push(external_ref);
-            }
+            });
+        }
+        
+        if let Some(homepage) = package.annotation_str(annotation_keys::HOMEPAGE) {
+            component = component.add_external_reference(CycloneDxExternalReference {
+                r#type: "website".to_string(),
+                url: homepage.to_string(),
+                comment: None,
+            });
+        }
+        
+        if let Some(cpe) = self.derive_cpe(package) {
+            component = component.with_cpe(cpe);
+        }
+        
+        // The declared repository of a git dependency is usually its source
+        let git_url = match &package.source {
+            PackageSource::Git { url, .. } => Some(Self::normalize_repository_url(url)),
+            _ => None,
+        };
+        let repository = package.annotation_str(annotation_keys::REPOSITORY)
+            .filter(|repository| git_url != Some(Self::normalize_repository_url(repository)));
+        if let Some(repository) = repository {
+            component = component.add_external_reference(CycloneDxExternalReference {
+                r#type: "vcs".to_string(),
+                url: repository.to_string(),
+                comment: Some("Declared repository".to_string()),
+            });
         }
         
         // Add Rust-specific properties
+        if let Some(kind) = package.dependency_kind() {
+            component = component.add_property("rust:dependency_kind".to_string(), format!("{:?}", kind).to_lowercase());
+        }
+        
         component = component.add_property(
             "rust:package_source".to_string(),
             format!("{:?}", package.source)
@@ -203,40 +825,251 @@ impl SbomGenerator {
         
         component = component.add_property(
             "rust:classification".to_string(),
-            format!("{:?}", package.classification)
+            package.classification.to_string()
         );
         
+        if self.config.include_security_posture {
+            for (name, value) in self.posture_properties(package) {
+                component = component.add_property(name.to_string(), value);
+            }
+        }
+        
         Ok(component)
     }
     
+    /// Create the component describing the project, enriched from its root manifest
+    fn create_root_component(&self, project: &Project, package: Option<&ManifestPackage>) -> CycloneDxComponent {
+        let version = package.and_then(|p| p.version.clone())
+            .or_else(|| project.metadata.version.clone())
+            .unwrap_or_default();
+        let mut component = CycloneDxComponent::new(project.name.clone(), version)
+            .with_type("application".to_string())
+            .with_bom_ref(project.id.clone());
+        
+        let package = match package {
+            Some(package) => package,
+            None => return component,
+        };
+        
+        component = component.add_property("rust:package_name".to_string(), package.name.clone());
+        
+        if let Some(description) = &package.description {
+            component = component.with_description(description.clone());
+        }
+        
+        if self.config.include_licenses {
+            if let Some(license) = &package.license {
+                component = component.with_license(CycloneDxLicenseChoice::Expression { expression: license.clone() });
+            }
+        }
+        
+        if let Some(author) = package.authors.first() {
+            let name = author.split('<').next().unwrap_or(author).trim();
+            if !name.is_empty() {
+                component = component.with_supplier(CycloneDxOrganizationalEntity {
+                    name: name.to_string(),
+                    url: Vec::new(),
+                });
+            }
+        }
+        
+        if let Some(repository) = &package.repository {
+            component = component.add_external_reference(CycloneDxExternalReference {
+                r#type: "vcs".to_string(),
+                url: repository.clone(),
+                comment: Some("Declared repository".to_string()),
+            });
+        }
+        
+        if let Some(homepage) = &package.homepage {
+            component = component.add_external_reference(CycloneDxExternalReference {
+                r#type: "website".to_string(),
+                url: homepage.clone(),
+                comment: None,
+            });
+        }
+        
+        if let Some(edition) = &package.edition {
+            component = component.add_property("rust:edition".to_string(), edition.clone());
+        }
+        
+        if let Some(rust_version) = &package.rust_version {
+            component = component.add_property("rust:rust_version".to_string(), rust_version.clone());
+        }
+        
+        component
+    }
+    
+    /// Create the formula recording the lockfile resolution the BOM describes
+    fn create_formula(&self, project: &Project, dependency_graph: &DependencyGraph) -> CycloneDxFormula {
+        let fingerprint = &dependency_graph.metadata.lockfile_fingerprint;
+        let properties = (!fingerprint.is_empty()).then(|| vec![CycloneDxProperty {
+            name: "rust:lockfile_fingerprint".to_string(),
+            value: fingerprint.clone(),
+        }]);
+        
+        CycloneDxFormula {
+            bom_ref: format!("formula:{}", project.id),
+            workflows: vec![CycloneDxWorkflow {
+                bom_ref: format!("workflow:{}", project.id),
+                uid: if fingerprint.is_empty() { project.id.clone() } else { fingerprint.clone() },
+                name: "cargo dependency resolution".to_string(),
+                task_types: vec!["build".to_string()],
+                properties,
+            }],
+        }
+    }
+    
+    /// Get supplier name from the first declared author, without email
+    fn supplier_name(&self, package: &PackageNode) -> Option<String> {
+        let authors = package.annotation(annotation_keys::AUTHORS)?;
+        let first_author = authors.as_array()?.first()?.as_str()?;
+        let name = first_author.split('<').next().unwrap_or(first_author).trim();
+        
+        if name.is_empty() {
+            None
+        } else {
+            Some(name.to_string())
+        }
+    }
+    
+    /// Derive a CPE 2.3 identifier for crates with a configured CPE vendor
+    ///
+    /// Forge owners are not CPE vendors, so crates without a configured
+    /// vendor get no CPE; values that would need CPE escaping are skipped
+    /// rather than guessed.
+    fn derive_cpe(&self, package: &PackageNode) -> Option<String> {
+        let vendor = self.config.cpe_vendors.get(&package.name)?.to_lowercase();
+        let product = package.name.to_lowercase();
+        
+        let is_cpe_safe = |value: &str| {
+            !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        };
+        
+        if !is_cpe_safe(&vendor) || !is_cpe_safe(&product) || !is_cpe_safe(&package.version) {
+            return None;
+        }
+        
+        Some(format!("cpe:2.3:a:{}:{}:{}:*:*:*:*:*:*:*", vendor, product, package.version))
+    }
+    
+    /// Repository URL with the `git+` prefix, `.git` suffix and trailing slash removed
+    fn normalize_repository_url(url: &str) -> String {
+        let url = url.trim().strip_prefix("git+").unwrap_or(url.trim());
+        let url = url.trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url).to_lowercase()
+    }
+    
     /// Get component scope based on dependency kind
+    ///
+    /// CycloneDX only knows required, optional and excluded; dev and build
+    /// dependencies do not ship, so they are excluded (the exact kind is
+    /// kept in the `rust:dependency_kind` property).
     fn get_component_scope(&self, package: &PackageNode) -> Option<String> {
-        for annotation in &package.annotations {
-            if annotation.key == RustAnnotation::keys::DEPENDENCY_KIND {
-                if let Some(kind_str) = annotation.value.as_str() {
-                    match kind_str {
-                        "dev" => return Some("development".to_string()),
-                        "build" => return Some("build".to_string()),
-                        "normal" => return Some("runtime".to_string()),
-                        _ => {}
-                    }
-                }
-            }
+        let scope = match package.dependency_kind() {
+            Some(DependencyKind::Dev) | Some(DependencyKind::Build) => "excluded",
+            // Default to required scope
+            Some(DependencyKind::Normal) | None => "required",
+        };
+        
+        Some(scope.to_string())
+    }
+    
+    /// Security posture properties of a package
+    ///
+    /// The TCS category (TCS packages only), the audit status and, for
+    /// audited packages, the audit method.
+    fn posture_properties(&self, package: &PackageNode) -> Vec<(&'static str, String)> {
+        let mut properties = Vec::new();
+        if let Classification::TCS { category, .. } = &package.classification {
+            properties.push(("rust:tcs_category", category.to_string()));
+        }
+        
+        let (status, method) = match &package.audit_status {
+            AuditStatus::Audited { method, .. } => ("audited", Some(match method {
+                AuditMethod::CargoVet { .. } => "cargo-vet",
+                AuditMethod::Manual { .. } => "manual",
+                AuditMethod::Imported { .. } => "imported",
+                AuditMethod::Exemption { .. } => "exemption",
+            })),
+            AuditStatus::Exempted { .. } => ("exempted", None),
+            AuditStatus::Unaudited => ("unaudited", None),
+        };
+        properties.push(("rust:audit_status", status.to_string()));
+        if let Some(method) = method {
+            properties.push(("rust:audit_method", method.to_string()));
         }
         
-        // Default to required scope
-        Some("required".to_string())
+        properties
+    }
+    
+    /// Commit the lockfile was read at and whether it matched that commit
+    fn vcs_summary(dependency_graph: &DependencyGraph) -> Vec<(&'static str, String)> {
+        let vcs = dependency_graph.metadata.properties.get(VCS_PROPERTY)
+            .and_then(|value| serde_json::from_value::<LockfileVcsState>(value.clone()).ok());
+        match vcs {
+            Some(vcs) => vec![
+                ("rust:vcs:commit", vcs.commit.clone()),
+                ("rust:vcs:lockfile_matches_commit", vcs.matches_head().to_string()),
+                ("rust:vcs:dirty", vcs.dirty.to_string()),
+            ],
+            None => Vec::new(),
+        }
+    }
+    
+    /// Counts of classifications and audit statuses over the described packages
+    fn posture_summary(&self, packages: &[&PackageNode]) -> Vec<(&'static str, String)> {
+        let count = |predicate: fn(&PackageNode) -> bool| packages.iter().filter(|p| predicate(p)).count().to_string();
+        vec![
+            ("rust:posture:tcs_packages", count(|p| matches!(p.classification, Classification::TCS { .. }))),
+            ("rust:posture:mechanical_packages", count(|p| matches!(p.classification, Classification::Mechanical { .. }))),
+            ("rust:posture:unknown_packages", count(|p| matches!(p.classification, Classification::Unknown))),
+            ("rust:posture:audited_packages", count(|p| matches!(p.audit_status, AuditStatus::Audited { .. }))),
+            ("rust:posture:exempted_packages", count(|p| matches!(p.audit_status, AuditStatus::Exempted { .. }))),
+            ("rust:posture:unaudited_packages", count(|p| matches!(p.audit_status, AuditStatus::Unaudited))),
+        ]
     }
     
-    /// Add SPDX relationships between packages
-    fn add_spdx_relationships(&self, spdx_doc: &mut SpdxDocument, dependency_graph: &DependencyGraph) {
+    /// Package URL of a package
+    ///
+    /// crates.io packages are identified by name and version alone; git and
+    /// other registry packages are qualified with their source, so the same
+    /// name and version from two sources get different package URLs.
+    fn package_url(&self, package: &PackageNode) -> String {
+        let purl = format!("pkg:cargo/{}@{}", percent_encode(&package.name), percent_encode(&package.version));
+        match &package.source {
+            PackageSource::Registry { url, .. } => {
+                let url = url.strip_prefix("registry+").or_else(|| url.strip_prefix("sparse+")).unwrap_or(url);
+                if normalize_source(&format!("registry+{}", url)) == CRATES_IO_SOURCE {
+                    purl
+                } else {
+                    format!("{}?repository_url={}", purl, percent_encode(url))
+                }
+            },
+            PackageSource::Git { url, rev, .. } => {
+                format!("{}?vcs_url={}", purl, percent_encode(&format!("git+{}@{}", url, rev)))
+            },
+            PackageSource::Local { .. } => purl,
+        }
+    }
+    
+    /// Add SPDX relationships between described packages
+    ///
+    /// Edges are resolved through the SPDX ids the packages were given;
+    /// edges to or from packages left out of the document are dropped.
+    fn add_spdx_relationships(&self, spdx_doc: &mut SpdxDocument, dependency_graph: &DependencyGraph, package_ids: &HashMap<PackageId, String>) {
+        let mut seen = HashSet::new();
         for edge in &dependency_graph.edges {
-            let from_package_id = format!("SPDXRef-{}", edge.from);
-            let to_package_id = format!("SPDXRef-{}", edge.to);
+            let (Some(from_package_id), Some(to_package_id)) = (package_ids.get(&edge.from), package_ids.get(&edge.to)) else {
+                continue;
+            };
+            if !seen.insert((from_package_id, to_package_id, format!("{:?}", edge.kind))) {
+                continue;
+            }
             
             let relationship = SpdxRelationship {
-                spdx_element_id: from_package_id,
-                related_spdx_element: to_package_id,
+                spdx_element_id: from_package_id.clone(),
+                related_spdx_element: to_package_id.clone(),
                 relationship_type: "DEPENDS_ON".to_string(),
                 comment: Some(format!("Dependency kind: {:?}", edge.kind)),
             };
@@ -245,24 +1078,54 @@ impl SbomGenerator {
         }
     }
     
-    /// Add CycloneDX dependencies
-    fn add_cyclonedx_dependencies(&self, cyclonedx_doc: &mut CycloneDxDocument, dependency_graph: &DependencyGraph) {
+    /// Add CycloneDX dependencies, one entry per described component
+    ///
+    /// Edges are resolved through the bom-refs the components were given;
+    /// components without dependencies are listed with an empty `dependsOn`.
+    fn add_cyclonedx_dependencies(&self, cyclonedx_doc: &mut CycloneDxDocument, dependency_graph: &DependencyGraph, component_refs: &HashMap<PackageId, String>) {
+        let mut depends_on: BTreeMap<&String, BTreeSet<&String>> = component_refs.values()
+            .map(|bom_ref| (bom_ref, BTreeSet::new()))
+            .collect();
         for edge in &dependency_graph.edges {
-            let from_ref = format!("pkg:{}", edge.from);
-            let to_ref = format!("pkg:{}", edge.to);
-            
-            let dependency = CycloneDxDependency {
-                ref: from_ref,
-                depends_on: vec![to_ref],
-            };
-            
-            cyclonedx_doc.add_dependency(dependency);
+            if let (Some(from_ref), Some(to_ref)) = (component_refs.get(&edge.from), component_refs.get(&edge.to)) {
+                depends_on.entry(from_ref).or_default().insert(to_ref);
+            }
+        }
+        
+        for (bom_ref, dependencies) in depends_on {
+            cyclonedx_doc.add_dependency(CycloneDxDependency {
+                r#ref: bom_ref.clone(),
+                depends_on: dependencies.into_iter().cloned().collect(),
+            });
         }
     }
 }
 
+/// `identifier`, suffixed with a counter if it is already taken
+fn unique_identifier(identifier: &str, taken: &mut HashSet<String>) -> String {
+    let mut candidate = identifier.to_string();
+    let mut counter = 1;
+    while !taken.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}-{}", identifier, counter);
+    }
+    candidate
+}
+
+/// Percent-encode a package URL component
+fn percent_encode(value: &str) -> String {
+    value.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' | b'/' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
 /// SBOM wrapper enum
+///
+/// Serializes as the wrapped document; SPDX 3.0 documents are rendered as
+/// an element graph.
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum Sbom {
     /// SPDX document
     Spdx(SpdxDocument),
@@ -270,6 +1133,96 @@ pub enum Sbom {
     CycloneDx(CycloneDxDocument),
 }
 
+impl Sbom {
+    /// Number of packages or components described
+    pub fn component_count(&self) -> usize {
+        match self {
+            Sbom::Spdx(doc) => doc.packages.len(),
+            Sbom::CycloneDx(doc) => doc.all_components().count() - usize::from(doc.metadata.component.is_some()),
+        }
+    }
+
+    /// Whether the document can be written in an encoding
+    ///
+    /// JSON is available for every document, XML for CycloneDX and
+    /// tag-value for SPDX 2.3.
+    pub fn supports(&self, encoding: SbomEncoding) -> bool {
+        match (self, encoding) {
+            (_, SbomEncoding::Json) => true,
+            (Sbom::CycloneDx(_), SbomEncoding::Xml) => true,
+            (Sbom::Spdx(doc), SbomEncoding::TagValue) => !doc.spdx_version.starts_with("SPDX-3"),
+            _ => false,
+        }
+    }
+
+    /// Conventional file extension of the document in an encoding
+    pub fn file_extension(&self, encoding: SbomEncoding) -> &'static str {
+        match (self, encoding) {
+            (Sbom::Spdx(_), SbomEncoding::TagValue) => "spdx",
+            (Sbom::Spdx(_), SbomEncoding::Xml) => "spdx.xml",
+            (Sbom::Spdx(_), SbomEncoding::Json) => "spdx.json",
+            (Sbom::CycloneDx(_), SbomEncoding::Xml) => "cdx.xml",
+            (Sbom::CycloneDx(_), _) => "cdx.json",
+        }
+    }
+
+    /// Serialize the document in an encoding
+    ///
+    /// The output is newline-terminated and depends only on the document,
+    /// so the same document always serializes to the same bytes.
+    pub fn to_bytes(&self, encoding: SbomEncoding, pretty: bool) -> Result<Vec<u8>> {
+        if !self.supports(encoding) {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "encoding".to_string(),
+                value: encoding.as_str().to_string(),
+                reason: format!("{} SBOMs cannot be written as {}", self.format_name(), encoding.as_str()),
+                source: anyhow::anyhow!("Unsupported SBOM encoding"),
+            });
+        }
+
+        let mut content = match (self, encoding) {
+            (Sbom::Spdx(doc), SbomEncoding::TagValue) => doc.to_tag_value(),
+            (Sbom::CycloneDx(doc), SbomEncoding::Xml) => doc.to_xml(pretty),
+            _ => {
+                let json = if pretty { serde_json::to_string_pretty(self) } else { serde_json::to_string(self) };
+                json.map_err(|e| AdapterError::Internal {
+                    message: "Failed to serialize SBOM".to_string(),
+                    source: anyhow::anyhow!("{}", e),
+                })?
+            },
+        };
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        Ok(content.into_bytes())
+    }
+
+    /// Write the document atomically, in the encoding implied by the file name
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let content = self.to_bytes(SbomEncoding::from_path(path), true)?;
+        crate::utils::write_atomic(path, content)
+    }
+
+    /// Format and version, as written in the document
+    fn format_name(&self) -> String {
+        match self {
+            Sbom::Spdx(doc) => doc.spdx_version.clone(),
+            Sbom::CycloneDx(doc) => format!("CycloneDX {}", doc.spec_version),
+        }
+    }
+}
+
+impl serde::Serialize for Sbom {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Sbom::Spdx(doc) if doc.spdx_version.starts_with("SPDX-3") => doc.to_spdx3().serialize(serializer),
+            Sbom::Spdx(doc) => doc.serialize(serializer),
+            Sbom::CycloneDx(doc) => doc.serialize(serializer),
+        }
+    }
+}
+
 impl Default for SbomGeneratorConfig {
     fn default() -> Self {
         Self {
@@ -278,6 +1231,11 @@ impl Default for SbomGeneratorConfig {
             include_build_dependencies: true,
             include_licenses: true,
             author: "Rust Ecosystem Adapter".to_string(),
+            cyclonedx_version: CycloneDxVersion::default(),
+            spdx_version: SpdxVersion::default(),
+            scope: None,
+            include_security_posture: false,
+            cpe_vendors: HashMap::new(),
         }
     }
 }
@@ -320,7 +1278,7 @@ mod tests {
                 checksum: "test-checksum".to_string(),
             },
             checksum: "test-checksum".to_string(),
-            classification: Classification::Mechanical(MechanicalCategory::Other("test".to_string())),
+            classification: Classification::Mechanical { category: MechanicalCategory::Other("test".to_string()) },
             audit_status: AuditStatus::Unaudited,
             annotations: vec![],
         };
@@ -360,7 +1318,7 @@ mod tests {
                 checksum: "test-checksum".to_string(),
             },
             checksum: "test-checksum".to_string(),
-            classification: Classification::Mechanical(MechanicalCategory::Other("test".to_string())),
+            classification: Classification::Mechanical { category: MechanicalCategory::Other("test".to_string()) },
             audit_status: AuditStatus::Unaudited,
             annotations: vec![],
         };
@@ -375,4 +1333,520 @@ mod tests {
         assert_eq!(cyclonedx_doc.components[0].name, "test-package");
         assert_eq!(cyclonedx_doc.components[0].version, "1.0.0");
     }
+    
+    #[tokio::test]
+    async fn test_merge_cyclonedx_fragment() {
+        let config = RustAdapterConfig::default();
+        let generator = SbomGenerator::new(&config);
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let mut sbom = Sbom::CycloneDx(generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap());
+        
+        let fragment = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "components": [
+                { "type": "file", "name": "api.proto", "version": "2", "bom-ref": "proto:api" },
+                { "type": "file", "name": "common.proto", "version": "2", "bom-ref": "proto:common" }
+            ],
+            "dependencies": [
+                { "ref": "proto:api", "dependsOn": ["proto:common"] }
+            ]
+        });
+        generator.merge_fragment(&mut sbom, &fragment).unwrap();
+        
+        let cyclonedx_doc = match sbom {
+            Sbom::CycloneDx(doc) => doc,
+            _ => unreachable!(),
+        };
+        assert_eq!(cyclonedx_doc.components.len(), 2);
+        assert!(cyclonedx_doc.dependencies.iter().any(|d| d.r#ref == "test" && d.depends_on == vec!["proto:api".to_string()]));
+        
+        // Dangling references are rejected without merging anything
+        let mut sbom = Sbom::CycloneDx(cyclonedx_doc);
+        let invalid = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "components": [{ "name": "bundle.js", "bom-ref": "js:bundle" }],
+            "dependencies": [{ "ref": "js:bundle", "dependsOn": ["js:missing"] }]
+        });
+        assert!(generator.merge_fragment(&mut sbom, &invalid).is_err());
+        if let Sbom::CycloneDx(doc) = &sbom {
+            assert_eq!(doc.components.len(), 2);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_merge_fragment_keeps_component_details() {
+        let config = RustAdapterConfig::default();
+        let generator = SbomGenerator::new(&config);
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let mut sbom = Sbom::CycloneDx(generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap());
+        
+        let fragment = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "metadata": { "component": { "name": "web-ui", "bom-ref": "npm:web-ui" } },
+            "components": [{
+                "name": "react",
+                "version": "18.2.0",
+                "bom-ref": "npm:react",
+                "purl": "pkg:npm/react@18.2.0",
+                "licenses": [{ "license": { "id": "MIT" } }],
+                "externalReferences": [{ "type": "vcs", "url": "https://github.com/facebook/react" }],
+                "components": [{ "name": "scheduler", "version": "0.23.0", "bom-ref": "npm:scheduler" }]
+            }],
+            "dependencies": [
+                { "ref": "npm:web-ui", "dependsOn": ["npm:react"] },
+                { "ref": "npm:react", "dependsOn": ["npm:scheduler"] }
+            ]
+        });
+        generator.merge_fragment(&mut sbom, &fragment).unwrap();
+        
+        let Sbom::CycloneDx(doc) = &sbom else { unreachable!() };
+        let react = &doc.components[0];
+        assert_eq!(react.purl.as_deref(), Some("pkg:npm/react@18.2.0"));
+        assert_eq!(react.licenses, Some(vec![CycloneDxLicenseChoice::License {
+            license: CycloneDxLicense { id: Some("MIT".to_string()), name: None, text: None, url: None },
+        }]));
+        assert_eq!(react.external_references.as_ref().unwrap()[0].url, "https://github.com/facebook/react");
+        assert_eq!(react.components.as_ref().unwrap()[0].bom_ref.as_deref(), Some("npm:scheduler"));
+        assert!(doc.dependencies.iter().any(|d| d.r#ref == "npm:react" && d.depends_on == vec!["npm:scheduler".to_string()]));
+        assert!(doc.dependencies.iter().any(|d| d.r#ref == "test" && d.depends_on == vec!["npm:react".to_string()]));
+        
+        // Nested references are known to later fragments
+        let duplicate = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "components": [{ "name": "scheduler", "version": "0.23.0", "bom-ref": "npm:scheduler" }]
+        });
+        assert!(generator.merge_fragment(&mut sbom, &duplicate).is_err());
+        
+        let mut sbom = Sbom::Spdx(generator.generate_spdx(&project, &dependency_graph).await.unwrap());
+        let fragment = serde_json::json!({
+            "spdxVersion": "SPDX-2.3",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "packages": [{
+                "SPDXID": "SPDXRef-react",
+                "name": "react",
+                "versionInfo": "18.2.0",
+                "licenseDeclared": "MIT",
+                "externalRefs": [{ "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": "pkg:npm/react@18.2.0" }]
+            }]
+        });
+        generator.merge_fragment(&mut sbom, &fragment).unwrap();
+        let Sbom::Spdx(doc) = &sbom else { unreachable!() };
+        let react = doc.packages.iter().find(|p| p.name == "react").unwrap();
+        assert_eq!(react.license_declared.as_deref(), Some("MIT"));
+        assert_eq!(react.external_refs[0].reference_locator, "pkg:npm/react@18.2.0");
+    }
+    
+    #[tokio::test]
+    async fn test_component_enrichment_from_annotations() {
+        let mut config = RustAdapterConfig::default();
+        config.sbom_config.cpe_vendors.insert("serde".to_string(), "serde-rs".to_string());
+        let generator = SbomGenerator::new(&config);
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        
+        let package = PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "serde".to_string(),
+            version: "1.0.130".to_string(),
+            source: PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: "test-checksum".to_string(),
+            },
+            checksum: "test-checksum".to_string(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![
+                RustAnnotation::new(
+                    annotation_keys::DESCRIPTION.to_string(),
+                    serde_json::json!("A serialization framework"),
+                ),
+                RustAnnotation::new(
+                    annotation_keys::HOMEPAGE.to_string(),
+                    serde_json::json!("https://serde.rs"),
+                ),
+                RustAnnotation::new(
+                    annotation_keys::REPOSITORY.to_string(),
+                    serde_json::json!("https://github.com/serde-rs/serde"),
+                ),
+                RustAnnotation::new(
+                    annotation_keys::AUTHORS.to_string(),
+                    serde_json::json!(["Erick Tryzelaar <erick.tryzelaar@gmail.com>"]),
+                ),
+            ],
+        };
+        
+        let component = generator.create_cyclonedx_component(&project, &package).await.unwrap();
+        assert_eq!(component.description.as_deref(), Some("A serialization framework"));
+        assert_eq!(component.supplier.as_ref().map(|s| s.name.as_str()), Some("Erick Tryzelaar"));
+        assert_eq!(component.cpe.as_deref(), Some("cpe:2.3:a:serde-rs:serde:1.0.130:*:*:*:*:*:*:*"));
+        
+        let spdx_package = generator.create_spdx_package(&project, &package).await.unwrap();
+        assert_eq!(spdx_package.homepage.as_deref(), Some("https://serde.rs"));
+        assert!(spdx_package.external_refs.iter().any(|r| r.reference_type == "cpe23Type"));
+        
+        // No CPE without a configured vendor, and one vcs reference for a git
+        // dependency whose declared repository is its source
+        let package = PackageNode {
+            name: "serde_json".to_string(),
+            source: PackageSource::Git {
+                url: "https://github.com/serde-rs/serde.git".to_string(),
+                rev: "abc123".to_string(),
+                checksum: String::new(),
+            },
+            ..package
+        };
+        let component = generator.create_cyclonedx_component(&project, &package).await.unwrap();
+        assert!(component.cpe.is_none());
+        assert_eq!(component.external_references.unwrap().iter().filter(|r| r.r#type == "vcs").count(), 1);
+        
+        let spdx_package = generator.create_spdx_package(&project, &package).await.unwrap();
+        assert!(!spdx_package.external_refs.iter().any(|r| r.reference_type == "cpe23Type"));
+        assert!(!spdx_package.external_refs.iter().any(|r| r.reference_type == "repository"));
+    }
+    
+    #[tokio::test]
+    async fn test_versioned_output_matches_bundled_schemas() {
+        let mut config = RustAdapterConfig::default();
+        config.sbom_config.cyclonedx_version = CycloneDxVersion::V1_6;
+        config.sbom_config.spdx_version = SpdxVersion::V3_0;
+        let generator = SbomGenerator::new(&config);
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let mut dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        dependency_graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "test-package".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: "a".repeat(64),
+            },
+            checksum: "a".repeat(64),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        
+        let cyclonedx_doc = generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap();
+        let mut invalid = cyclonedx_doc.clone();
+        invalid.bom_format = "SPDX".to_string();
+        match generator.validate(&Sbom::CycloneDx(invalid)) {
+            Err(AdapterError::SchemaValidationFailed { errors, .. }) => {
+                assert_eq!(errors.len(), 1);
+                assert!(errors[0].starts_with("/bomFormat: "), "{}", errors[0]);
+            },
+            other => panic!("expected a schema violation, got {:?}", other),
+        }
+        
+        let cyclonedx = Sbom::CycloneDx(cyclonedx_doc);
+        generator.validate(&cyclonedx).unwrap();
+        let json = serde_json::to_value(&cyclonedx).unwrap();
+        assert_eq!(json["specVersion"], "1.6");
+        assert_eq!(json["metadata"]["lifecycles"][0]["phase"], "build");
+        assert_eq!(json["formulation"][0]["workflows"][0]["taskTypes"][0], "build");
+        
+        let mut spdx_doc = generator.generate_spdx(&project, &dependency_graph).await.unwrap();
+        let package_id = spdx_doc.packages[0].spdx_id.clone();
+        spdx_doc.add_relationship(SpdxRelationship {
+            spdx_element_id: package_id.clone(),
+            related_spdx_element: spdx_doc.spdx_id.clone(),
+            relationship_type: "DESCRIBED_BY".to_string(),
+            comment: None,
+        });
+        let spdx = Sbom::Spdx(spdx_doc);
+        generator.validate(&spdx).unwrap();
+        
+        let json = serde_json::to_value(&spdx).unwrap();
+        let graph = json["@graph"].as_array().unwrap();
+        let describes = graph.iter().find(|e| e["type"] == "Relationship" && e["relationshipType"] == "describes").unwrap();
+        assert_eq!(describes["from"], "https://example.com/test#SPDXRef-DOCUMENT");
+        assert_eq!(describes["to"][0], format!("https://example.com/test#{}", package_id));
+        assert!(graph.iter().any(|e| e["type"] == "Relationship" && e["relationshipType"] == "hasDeclaredLicense"));
+        let document = graph.iter().find(|e| e["type"] == "SpdxDocument").unwrap();
+        assert_eq!(document["rootElement"][0], describes["to"][0]);
+    }
+    
+    #[tokio::test]
+    async fn test_relationships_reference_emitted_identifiers() {
+        let mut config = RustAdapterConfig::default();
+        config.sbom_config.include_dev_dependencies = false;
+        let generator = SbomGenerator::new(&config);
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let node = |name: &str, source: PackageSource, kind: &str| PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            source,
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![RustAnnotation::new(
+                annotation_keys::DEPENDENCY_KIND.to_string(),
+                serde_json::json!(kind),
+            )],
+        };
+        let registry = || PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() };
+        let app = node("app", PackageSource::Local { path: "/test".to_string() }, "normal");
+        let serde = node("serde", registry(), "normal");
+        let forked = node("serde", PackageSource::Git {
+            url: "https://github.com/fork/serde".to_string(),
+            rev: "abc123".to_string(),
+            checksum: String::new(),
+        }, "normal");
+        let mock = node("mockall", registry(), "dev");
+        
+        let mut dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        for (from, to) in [(&app, &serde), (&app, &forked), (&app, &mock), (&mock, &serde)] {
+            dependency_graph.add_edge(DependencyEdge {
+                from: from.id,
+                to: to.id,
+                kind: DependencyKind::Normal,
+                target: None,
+                optional: false,
+                features: Vec::new(),
+            });
+        }
+        for package in [&app, &serde, &forked, &mock] {
+            dependency_graph.add_package(package.clone());
+        }
+        
+        let cyclonedx_doc = generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap();
+        let refs: HashSet<&str> = cyclonedx_doc.components.iter().filter_map(|c| c.bom_ref.as_deref()).collect();
+        assert_eq!(refs.len(), 3);
+        assert!(refs.contains("pkg:cargo/serde@1.0.0"));
+        assert!(refs.contains("pkg:cargo/serde@1.0.0?vcs_url=git%2Bhttps://github.com/fork/serde%40abc123"));
+        assert_eq!(cyclonedx_doc.dependencies.len(), 3);
+        for dependency in &cyclonedx_doc.dependencies {
+            assert!(refs.contains(dependency.r#ref.as_str()));
+            assert!(dependency.depends_on.iter().all(|r| refs.contains(r.as_str())));
+        }
+        let app_dependency = cyclonedx_doc.dependencies.iter().find(|d| d.r#ref == "pkg:cargo/app@1.0.0").unwrap();
+        assert_eq!(app_dependency.depends_on.len(), 2);
+        
+        let spdx_doc = generator.generate_spdx(&project, &dependency_graph).await.unwrap();
+        let ids: HashSet<&str> = spdx_doc.packages.iter().map(|p| p.spdx_id.as_str()).collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(spdx_doc.relationships.len(), 2);
+        for relationship in &spdx_doc.relationships {
+            assert!(ids.contains(relationship.spdx_element_id.as_str()));
+            assert!(ids.contains(relationship.related_spdx_element.as_str()));
+        }
+        generator.validate(&Sbom::Spdx(spdx_doc)).unwrap();
+        generator.validate(&Sbom::CycloneDx(cyclonedx_doc)).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_first_party_crates_are_subcomponents_of_root() {
+        let generator = SbomGenerator::new(&RustAdapterConfig::default());
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let node = |name: &str, origin: PackageOrigin| {
+            let mut package = PackageNode {
+                id: uuid::Uuid::new_v4(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+                checksum: String::new(),
+                classification: Classification::Unknown,
+                audit_status: AuditStatus::Unaudited,
+                annotations: vec![],
+            };
+            package.set_origin(origin);
+            package
+        };
+        let app = node("app", PackageOrigin::FirstParty);
+        let core = node("app-core", PackageOrigin::FirstParty);
+        let serde = node("serde", PackageOrigin::ThirdParty);
+        
+        let mut dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        for (from, to) in [(&app, &core), (&core, &serde)] {
+            dependency_graph.add_edge(DependencyEdge {
+                from: from.id,
+                to: to.id,
+                kind: DependencyKind::Normal,
+                target: None,
+                optional: false,
+                features: Vec::new(),
+            });
+        }
+        for package in [&app, &core, &serde] {
+            dependency_graph.add_package(package.clone());
+        }
+        
+        let cyclonedx_doc = generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap();
+        let root = cyclonedx_doc.metadata.component.as_ref().unwrap();
+        let nested: Vec<&str> = root.components.iter().flatten().map(|c| c.name.as_str()).collect();
+        assert_eq!(nested, vec!["app", "app-core"]);
+        assert_eq!(cyclonedx_doc.components.len(), 1);
+        assert_eq!(cyclonedx_doc.components[0].name, "serde");
+        let refs: HashSet<&str> = cyclonedx_doc.all_components().filter_map(|c| c.bom_ref.as_deref()).collect();
+        assert!(cyclonedx_doc.dependencies.iter().all(|d| refs.contains(d.r#ref.as_str())));
+        assert!(cyclonedx_doc.to_xml(false).contains("<components><component type=\"library\" bom-ref=\"pkg:cargo/app@1.0.0\">"));
+        
+        let spdx_doc = generator.generate_spdx(&project, &dependency_graph).await.unwrap();
+        let described: Vec<&str> = spdx_doc.relationships.iter()
+            .filter(|r| r.relationship_type == "DESCRIBES" && r.spdx_element_id == spdx_doc.spdx_id)
+            .map(|r| r.related_spdx_element.as_str())
+            .collect();
+        assert_eq!(described.len(), 2);
+        
+        let cyclonedx = Sbom::CycloneDx(cyclonedx_doc);
+        assert_eq!(cyclonedx.component_count(), 3);
+        generator.validate(&cyclonedx).unwrap();
+        generator.validate(&Sbom::Spdx(spdx_doc)).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_security_posture_properties_are_opt_in() {
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let mut dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        dependency_graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "ring".to_string(),
+            version: "0.17.8".to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification: Classification::TCS { category: TcsCategory::Cryptography, rationale: "crypto".to_string() },
+            audit_status: AuditStatus::Audited {
+                method: AuditMethod::CargoVet { criteria: "safe-to-deploy".to_string() },
+                auditor: "security-team".to_string(),
+                date: "2024-01-01".to_string(),
+            },
+            annotations: vec![],
+        });
+        dependency_graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "itoa".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        let property = |component: &CycloneDxComponent, name: &str| component.properties.iter().flatten()
+            .find(|p| p.name == name)
+            .map(|p| p.value.clone());
+        
+        // Policy-neutral by default
+        let generator = SbomGenerator::new(&RustAdapterConfig::default());
+        let cyclonedx_doc = generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap();
+        assert!(cyclonedx_doc.components.iter().all(|c| property(c, "rust:audit_status").is_none()));
+        assert!(cyclonedx_doc.metadata.properties.is_none());
+        
+        let mut config = RustAdapterConfig::default();
+        config.sbom_config.include_security_posture = true;
+        let generator = SbomGenerator::new(&config);
+        let cyclonedx_doc = generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap();
+        let ring = cyclonedx_doc.components.iter().find(|c| c.name == "ring").unwrap();
+        assert_eq!(property(ring, "rust:tcs_category").as_deref(), Some("Cryptography"));
+        assert_eq!(property(ring, "rust:audit_status").as_deref(), Some("audited"));
+        assert_eq!(property(ring, "rust:audit_method").as_deref(), Some("cargo-vet"));
+        let itoa = cyclonedx_doc.components.iter().find(|c| c.name == "itoa").unwrap();
+        assert_eq!(property(itoa, "rust:tcs_category"), None);
+        assert_eq!(property(itoa, "rust:audit_status").as_deref(), Some("unaudited"));
+        let summary = cyclonedx_doc.metadata.properties.as_ref().unwrap();
+        assert!(summary.iter().any(|p| p.name == "rust:posture:tcs_packages" && p.value == "1"));
+        assert!(summary.iter().any(|p| p.name == "rust:posture:unaudited_packages" && p.value == "1"));
+        generator.validate(&Sbom::CycloneDx(cyclonedx_doc)).unwrap();
+        
+        let spdx_doc = generator.generate_spdx(&project, &dependency_graph).await.unwrap();
+        let ring = spdx_doc.packages.iter().find(|p| p.name == "ring").unwrap();
+        assert_eq!(ring.comment.as_deref(), Some("rust:tcs_category=Cryptography\nrust:audit_status=audited\nrust:audit_method=cargo-vet"));
+        assert!(spdx_doc.creation_info.comment.as_deref().unwrap().contains("rust:posture:audited_packages=1"));
+        generator.validate(&Sbom::Spdx(spdx_doc)).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_sbom_encodings() {
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        let mut dependency_graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        dependency_graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "ring".to_string(),
+            version: "0.17.8".to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification: Classification::TCS { category: TcsCategory::Cryptography, rationale: "crypto".to_string() },
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        let mut config = RustAdapterConfig::default();
+        config.sbom_config.include_security_posture = true;
+        let generator = SbomGenerator::new(&config);
+        let cyclonedx = Sbom::CycloneDx(generator.generate_cyclonedx(&project, &dependency_graph).await.unwrap());
+        let spdx = Sbom::Spdx(generator.generate_spdx(&project, &dependency_graph).await.unwrap());
+        
+        // JSON is newline-terminated and deterministic
+        let json = spdx.to_bytes(SbomEncoding::Json, false).unwrap();
+        assert!(json.ends_with(b"}\n"));
+        assert_eq!(json, spdx.to_bytes(SbomEncoding::Json, false).unwrap());
+        assert_eq!(spdx.file_extension(SbomEncoding::Json), "spdx.json");
+        
+        let xml = String::from_utf8(cyclonedx.to_bytes(SbomEncoding::Xml, true).unwrap()).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<bom xmlns=\"http://cyclonedx.org/schema/bom/1.4\""));
+        assert!(xml.contains("<purl>pkg:cargo/ring@0.17.8</purl>"));
+        assert!(xml.contains("<property name=\"rust:audit_status\">unaudited</property>"));
+        assert!(xml.ends_with("</bom>\n"));
+        
+        // Multi-line values are wrapped in text tags
+        let tag_value = String::from_utf8(spdx.to_bytes(SbomEncoding::TagValue, true).unwrap()).unwrap();
+        assert!(tag_value.starts_with("SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\n"));
+        assert!(tag_value.contains("PackageName: ring\n"));
+        assert!(tag_value.contains("PackageComment: <text>rust:tcs_category=Cryptography\nrust:audit_status=unaudited</text>\n"));
+        
+        assert!(spdx.to_bytes(SbomEncoding::Xml, true).is_err());
+        assert!(cyclonedx.to_bytes(SbomEncoding::TagValue, true).is_err());
+        
+        // The file name selects the encoding
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(format!("sbom.{}", cyclonedx.file_extension(SbomEncoding::Xml)));
+        cyclonedx.write_to(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), xml);
+    }
 }
@@ -5,13 +5,27 @@
 
 use crate::models::*;
 use crate::error::Result;
-use async_trait::async_trait;
+use crate::utils::{deadline, PatternOverrides, SharedCache};
+use super::classification_cache::{self, ClassificationCache};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Version of the classification rules
+///
+/// Bump whenever the default patterns or signal precedence change so that
+/// cached results computed under the old rules are discarded.
+pub const CLASSIFICATION_RULESET_VERSION: &str = "2";
+
+/// Labelled sample of 152 popular crates.io crates for `classifier bench`
+const BUNDLED_CORPUS: &str = include_str!("../../tests/fixtures/classifier-corpus/top-crates.json");
 
 /// TCS classifier implementation
 #[derive(Debug, Clone)]
 pub struct TcsClassifier {
     /// Classifier configuration
     config: TcsClassifierConfig,
+    /// Cache of graph node classifications
+    cache: ClassificationCache,
     /// Whether classifier is ready
     ready: bool,
 }
@@ -27,18 +41,50 @@ pub struct TcsClassifierConfig {
     pub default_category: MechanicalCategory,
     /// Classification confidence threshold
     pub confidence_threshold: f64,
+    /// Explicit TCS category overrides keyed by package name, glob or `/regex/`
+    pub explicit_tcs_overrides: PatternOverrides<TcsCategory>,
+    /// TCS categories keyed by crates.io category slug
+    pub category_mappings: BTreeMap<String, TcsCategory>,
+    /// TCS categories keyed by lowercase crates.io keyword
+    pub keyword_mappings: BTreeMap<String, TcsCategory>,
+    /// Maximum number of unclassified packages adjacent to TCS
+    pub max_tcs_adjacent_unknowns: Option<usize>,
 }
 
 impl TcsClassifier {
     /// Create new TCS classifier with configuration
     pub fn new(config: &RustAdapterConfig) -> Self {
+        let cache_root = config.classification_config.result_cache_dir.clone()
+            .or_else(|| SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout)
+                .map(|cache| cache.entry_path(classification_cache::SHARED_CACHE_ENTRY)));
+        let cache = match cache_root {
+            Some(root) if !config.classification_config.disable_result_cache => ClassificationCache::new(
+                &root,
+                CLASSIFICATION_RULESET_VERSION,
+                Self::config_fingerprint(config),
+            ),
+            _ => ClassificationCache::disabled(),
+        };
+        
         Self {
             config: TcsClassifierConfig {
                 classify_proc_macros: config.classification_config.classify_proc_macros,
                 classify_build_deps: config.classification_config.classify_build_deps,
                 default_category: config.classification_config.default_category.clone(),
                 confidence_threshold: config.classification_config.confidence_threshold,
+                explicit_tcs_overrides: PatternOverrides::new(&config.explicit_tcs_overrides),
+                category_mappings: Self::default_category_mappings().into_iter()
+                    .map(|(slug, category)| (slug.to_string(), category))
+                    .chain(config.classification_config.category_mappings.clone())
+                    .collect(),
+                keyword_mappings: Self::default_keyword_mappings().into_iter()
+                    .map(|(keyword, category)| (keyword.to_string(), category))
+                    .chain(config.classification_config.keyword_mappings.iter()
+                        .map(|(keyword, category)| (keyword.to_lowercase(), category.clone())))
+                    .collect(),
+                max_tcs_adjacent_unknowns: config.classification_config.max_tcs_adjacent_unknowns,
             },
+            cache,
             ready: true,
         }
     }
@@ -48,40 +94,409 @@ impl TcsClassifier {
         self.ready
     }
     
+    /// Get the classification result cache
+    pub fn cache(&self) -> &ClassificationCache {
+        &self.cache
+    }
+    
+    /// Fingerprint of every configuration setting a classification depends on
+    fn config_fingerprint(config: &RustAdapterConfig) -> String {
+        let classification = &config.classification_config;
+        // Through `Value` so that override tables hash in key order
+        let settings = serde_json::json!({
+            "explicit_tcs_overrides": config.explicit_tcs_overrides,
+            "classify_proc_macros": classification.classify_proc_macros,
+            "classify_build_deps": classification.classify_build_deps,
+            "default_category": classification.default_category,
+            "confidence_threshold": classification.confidence_threshold,
+            "category_mappings": classification.category_mappings,
+            "keyword_mappings": classification.keyword_mappings,
+        });
+        
+        format!("{:x}", Sha256::digest(settings.to_string().as_bytes()))
+    }
+    
     /// Classify a single package
     pub async fn classify_package(&self, package: &CargoPackage) -> Result<ClassificationResult> {
-        let mut signals = Vec::new();
+        let evaluations = self.evaluate_signals(None, &package.name, package.proc_macro, &PackageTerms::default());
+        Ok(Self::decide(&evaluations))
+    }
+    
+    /// Classify a graph node, taking the project's TCS lists into account
+    ///
+    /// Results are served from and stored in the classification cache.
+    pub fn classify_node(&self, project: &Project, package: &PackageNode) -> ClassificationResult {
+        if let Some(cached) = self.cache.get(&project.tcs, package) {
+            return cached;
+        }
         
-        // 1. Check explicit overrides (highest priority)
-        if let Some(override_category) = self.check_explicit_overrides(&package.name) {
-            signals.push(ClassificationSignal::ExplicitOverride(package.name.clone()));
-            return Ok(ClassificationResult::tcs(override_category, signals));
+        let evaluations = self.evaluate_signals(Some(&project.tcs), &package.name, package.is_proc_macro(), &PackageTerms::of(package));
+        let result = Self::decide(&evaluations);
+        self.cache.put(&project.tcs, package, &result);
+        result
+    }
+    
+    /// Classify every package of a graph in place
+    ///
+    /// Fails only if the classify deadline runs out part way through.
+    pub fn classify_graph(&self, project: &Project, graph: &mut DependencyGraph) -> Result<()> {
+        deadline::set_stage("classifying packages");
+        deadline::set_total(graph.root_packages.len());
+        for package in &mut graph.root_packages {
+            deadline::check()?;
+            let classification_result = self.classify_node(project, package);
+            package.classification = match classification_result.role {
+                ToolchainRole::TCS(category) => Classification::TCS {
+                    category,
+                    rationale: classification_result.signals.iter()
+                        .map(|s| s.description())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                },
+                ToolchainRole::Mechanical(category) => Classification::Mechanical { category },
+            };
+            deadline::advance(1);
+        }
+        Ok(())
+    }
+    
+    /// Compare the classifications of two graphs of a project
+    ///
+    /// Signals of changed packages are the matching signals under the
+    /// current configuration, so `after` should be classified with it.
+    pub fn classification_diff(&self, project: &Project, before: &DependencyGraph, after: &DependencyGraph) -> ClassificationDiff {
+        let mut diff = ClassificationDiff::compute(before, after);
+        for change in &mut diff.changes {
+            let Some(package) = after.find_package(&change.package_name, &change.package_version) else {
+                continue;
+            };
+            change.signals = self.explain_package(project, package).evaluations.iter()
+                .filter(|evaluation| evaluation.matched)
+                .map(|evaluation| evaluation.signal.description())
+                .collect();
         }
+        diff
+    }
+    
+    /// Explain the classification of a graph node, listing every signal considered
+    pub fn explain_package(&self, project: &Project, package: &PackageNode) -> ClassificationExplanation {
+        let evaluations = self.evaluate_signals(Some(&project.tcs), &package.name, package.is_proc_macro(), &PackageTerms::of(package));
         
-        // 2. Check dependency role
-        if self.config.classify_proc_macros && package.is_proc_macro {
-            signals.push(ClassificationSignal::ProcMacroUsage);
-            return Ok(ClassificationResult::tcs(TcsCategory::BuildTimeExecution, signals));
+        ClassificationExplanation {
+            package_name: package.name.clone(),
+            package_version: package.version.clone(),
+            result: Self::decide(&evaluations),
+            evaluations,
+        }
+    }
+    
+    /// Report the packages of a classified graph that no signal matched
+    ///
+    /// A package is unclassified when only the mechanical fallback matched
+    /// it. Packages sharing a dependency edge with a package the graph
+    /// classifies as TCS are marked as TCS-adjacent.
+    pub fn unclassified_report(&self, project: &Project, graph: &DependencyGraph) -> UnclassifiedReport {
+        let tcs: HashMap<_, _> = graph.root_packages.iter()
+            .filter(|package| matches!(package.classification, Classification::TCS { .. }))
+            .map(|package| (package.id, format!("{}@{}", package.name, package.version)))
+            .collect();
+        
+        let packages = graph.root_packages.iter()
+            .filter_map(|package| {
+                let explanation = self.explain_package(project, package);
+                let unclassified = explanation.evaluations.iter()
+                    .filter(|evaluation| evaluation.matched)
+                    .all(|evaluation| matches!(evaluation.signal, ClassificationSignal::DependencyKind(_)));
+                if !unclassified {
+                    return None;
+                }
+                
+                let mut tcs_neighbors: Vec<String> = graph.edges.iter()
+                    .filter_map(|edge| match (edge.from == package.id, edge.to == package.id) {
+                        (true, _) => tcs.get(&edge.to),
+                        (_, true) => tcs.get(&edge.from),
+                        _ => None,
+                    })
+                    .cloned()
+                    .collect();
+                tcs_neighbors.sort();
+                tcs_neighbors.dedup();
+                
+                Some(UnclassifiedPackage {
+                    package_name: explanation.package_name,
+                    package_version: explanation.package_version,
+                    tcs_neighbors,
+                    evaluations: explanation.evaluations,
+                })
+            })
+            .collect();
+        
+        UnclassifiedReport {
+            total_packages: graph.root_packages.len(),
+            packages,
+        }
+    }
+    
+    /// Fail when more TCS-adjacent packages are unclassified than configured
+    pub fn check_unclassified(&self, report: &UnclassifiedReport) -> Result<()> {
+        let Some(limit) = self.config.max_tcs_adjacent_unknowns else {
+            return Ok(());
+        };
+        let packages: Vec<String> = report.tcs_adjacent()
+            .map(|package| format!("{}@{}", package.package_name, package.package_version))
+            .collect();
+        if packages.len() <= limit {
+            return Ok(());
         }
         
-        // 3. Apply deterministic pattern matching
+        Err(crate::AdapterError::UnclassifiedTcsAdjacent {
+            count: packages.len(),
+            limit,
+            packages,
+            source: anyhow::anyhow!("classification_config.max_tcs_adjacent_unknowns exceeded"),
+        })
+    }
+    
+    /// Parse the labelled corpus bundled with the adapter
+    pub fn bundled_corpus() -> Result<Vec<CorpusCrate>> {
+        serde_json::from_str(BUNDLED_CORPUS)
+            .map_err(|e| crate::AdapterError::MetadataParseError {
+                field: "classifier_corpus".to_string(),
+                value: "bundled".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })
+    }
+    
+    /// Classify a labelled corpus and report how each rule fared
+    ///
+    /// Project TCS lists play no part, but explicit overrides and configured
+    /// mappings do, so the report reflects the effective configuration.
+    pub fn bench(&self, corpus: &[CorpusCrate]) -> ClassifierBenchReport {
+        let patterns = self.get_default_patterns();
+        let mut rules: Vec<BenchRuleStats> = Vec::new();
+        let mut results = Vec::new();
+        let mut partial_name_matches = Vec::new();
+        
+        for entry in corpus {
+            let terms = PackageTerms {
+                categories: entry.categories.clone(),
+                keywords: entry.keywords.clone(),
+            };
+            let evaluations = self.evaluate_signals(None, &entry.name, entry.proc_macro, &terms);
+            for evaluation in &evaluations {
+                let index = match rules.iter().position(|stats| stats.rule == evaluation.rule) {
+                    Some(index) => index,
+                    None => {
+                        rules.push(BenchRuleStats { rule: evaluation.rule.clone(), matched: 0, decisive: 0 });
+                        rules.len() - 1
+                    },
+                };
+                rules[index].matched += usize::from(evaluation.matched);
+                rules[index].decisive += usize::from(evaluation.decisive);
+            }
+            
+            for pattern in patterns.iter().filter(|pattern| pattern.matches(&entry.name)) {
+                if let Some(segment) = Self::partial_segment(&pattern.regex, &entry.name) {
+                    partial_name_matches.push(PartialNameMatch {
+                        name: entry.name.clone(),
+                        pattern: pattern.name.clone(),
+                        segment,
+                        expected: entry.expected.clone(),
+                    });
+                }
+            }
+            
+            results.push(BenchResult {
+                name: entry.name.clone(),
+                expected: entry.expected.clone(),
+                actual: Self::decide(&evaluations).tcs_category(),
+                rule: evaluations.iter()
+                    .find(|evaluation| evaluation.decisive)
+                    .map(|evaluation| evaluation.rule.clone())
+                    .unwrap_or_default(),
+            });
+        }
+        
+        ClassifierBenchReport {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            ruleset_version: CLASSIFICATION_RULESET_VERSION.to_string(),
+            results,
+            rules,
+            partial_name_matches,
+            baseline: None,
+        }
+    }
+    
+    /// Name segment a pattern matches only part of
+    ///
+    /// Names are split into segments at `-` and `_`. A leading or trailing
+    /// `.*` is ignored, so `.*aes.*` is judged by where `aes` matched.
+    fn partial_segment(regex: &str, name: &str) -> Option<String> {
+        let core = regex.trim_start_matches(".*").trim_end_matches(".*");
+        let found = regex::Regex::new(core).ok()?.find(name)?;
+        let is_boundary = |c: Option<char>| c.is_none_or(|c| c == '-' || c == '_');
+        if is_boundary(name[..found.start()].chars().next_back()) && is_boundary(name[found.end()..].chars().next()) {
+            return None;
+        }
+        
+        let start = name[..found.start()].rfind(['-', '_']).map_or(0, |i| i + 1);
+        let end = name[found.end()..].find(['-', '_']).map_or(name.len(), |i| found.end() + i);
+        Some(name[start..end].to_string())
+    }
+    
+    /// Evaluate all classification signals in precedence order
+    ///
+    /// The first matching signal is marked decisive; later signals are still
+    /// evaluated so that explanations show everything that was considered.
+    fn evaluate_signals(&self, project_tcs: Option<&ProjectTcs>, package_name: &str, is_proc_macro: bool, terms: &PackageTerms) -> Vec<SignalEvaluation> {
+        let mut evaluations = Vec::new();
+        let mut push = |signal: ClassificationSignal, matched: bool, rule: String, outcome: ToolchainRole| {
+            evaluations.push(SignalEvaluation {
+                weight: signal.weight(),
+                signal,
+                matched,
+                rule,
+                outcome,
+                decisive: false,
+            });
+        };
+        
+        // 1. Check explicit overrides (highest priority)
+        let override_match = self.check_explicit_overrides(package_name);
+        let rule = match &override_match {
+            Some((key, _)) if key != package_name => format!("classification_config.explicit_tcs_overrides pattern '{}'", key),
+            _ => "classification_config.explicit_tcs_overrides".to_string(),
+        };
+        push(
+            ClassificationSignal::ExplicitOverride(package_name.to_string()),
+            override_match.is_some(),
+            rule,
+            ToolchainRole::TCS(override_match
+                .map(|(_, category)| category)
+                .unwrap_or_else(|| TcsCategory::Custom("override".to_string()))),
+        );
+        
+        // 2. Check project TCS lists, including custom categories
+        if let Some(project_tcs) = project_tcs {
+            let listed = project_tcs.category_for(package_name);
+            let list = listed.as_ref().map(|(_, list)| list.clone()).unwrap_or_else(|| "tcs".to_string());
+            push(
+                ClassificationSignal::ProjectTcsList(list.clone()),
+                listed.is_some(),
+                format!("project {}", list),
+                ToolchainRole::TCS(listed.map(|(category, _)| category).unwrap_or_else(|| TcsCategory::Custom(list))),
+            );
+        }
+        
+        // 3. Check dependency role
+        push(
+            ClassificationSignal::ProcMacroUsage,
+            self.config.classify_proc_macros && is_proc_macro,
+            format!("classification_config.classify_proc_macros = {}", self.config.classify_proc_macros),
+            ToolchainRole::TCS(TcsCategory::BuildTimeExecution),
+        );
+        
+        // 4. Apply deterministic pattern matching
         for pattern in &self.get_default_patterns() {
-            if pattern.matches(&package.name) {
-                signals.push(ClassificationSignal::NamePattern(pattern.regex.clone()));
-                return Ok(ClassificationResult::tcs(pattern.category.clone(), signals));
+            push(
+                ClassificationSignal::NamePattern(pattern.regex.clone()),
+                pattern.matches(package_name),
+                format!("default pattern '{}' ({})", pattern.name, pattern.description),
+                ToolchainRole::TCS(pattern.category.clone()),
+            );
+        }
+        
+        // 5. Match crates.io categories and keywords from registry metadata
+        for category in &terms.categories {
+            if let Some((slug, tcs_category)) = self.category_mapping(category) {
+                push(
+                    ClassificationSignal::CargoCategory(category.clone()),
+                    true,
+                    format!("crates.io category '{}'", slug),
+                    ToolchainRole::TCS(tcs_category.clone()),
+                );
+            }
+        }
+        for keyword in &terms.keywords {
+            if let Some(tcs_category) = self.config.keyword_mappings.get(&keyword.to_lowercase()) {
+                push(
+                    ClassificationSignal::CargoKeyword(keyword.clone()),
+                    true,
+                    format!("crates.io keyword '{}'", keyword.to_lowercase()),
+                    ToolchainRole::TCS(tcs_category.clone()),
+                );
             }
         }
         
-        // 4. Default to Mechanical
-        signals.push(ClassificationSignal::DependencyKind(CargoDependencyKind::Normal));
-        Ok(ClassificationResult::mechanical(signals))
+        // 6. Default to Mechanical
+        push(
+            ClassificationSignal::DependencyKind(CargoDependencyKind::Normal),
+            true,
+            "fallback: no TCS signal matched".to_string(),
+            ToolchainRole::Mechanical(MechanicalCategory::Other("default".to_string())),
+        );
+        
+        if let Some(first_match) = evaluations.iter_mut().find(|e| e.matched) {
+            first_match.decisive = true;
+        }
+        
+        evaluations
+    }
+    
+    /// Build the classification result from the decisive signal
+    fn decide(evaluations: &[SignalEvaluation]) -> ClassificationResult {
+        let decisive = evaluations.iter()
+            .find(|e| e.decisive)
+            .expect("fallback signal always matches");
+        
+        ClassificationResult {
+            role: decisive.outcome.clone(),
+            signals: vec![decisive.signal.clone()],
+        }
     }
     
-    /// Check for explicit overrides
-    fn check_explicit_overrides(&self, package_name: &str) -> Option<TcsCategory> {
-        // This would check configuration for explicit overrides
-        // For now, return None (no overrides)
-        None
+    /// Check for explicit overrides, returning the matching key and category
+    fn check_explicit_overrides(&self, package_name: &str) -> Option<(String, TcsCategory)> {
+        self.config.explicit_tcs_overrides.get_with_key(package_name)
+            .map(|(key, category)| (key.to_string(), category.clone()))
+    }
+    
+    /// Mapping of a crates.io category slug, matching parent categories too
+    fn category_mapping(&self, category: &str) -> Option<(&str, &TcsCategory)> {
+        let mut slug = category;
+        loop {
+            if let Some((key, tcs_category)) = self.config.category_mappings.get_key_value(slug) {
+                return Some((key, tcs_category));
+            }
+            slug = slug.rsplit_once("::")?.0;
+        }
+    }
+    
+    /// Built-in mapping of crates.io categories to TCS categories
+    fn default_category_mappings() -> Vec<(&'static str, TcsCategory)> {
+        vec![
+            ("cryptography", TcsCategory::Cryptography),
+            ("authentication", TcsCategory::Authentication),
+            ("database", TcsCategory::Database),
+            ("database-implementations", TcsCategory::Database),
+            ("network-programming", TcsCategory::Transport),
+        ]
+    }
+    
+    /// Built-in mapping of crates.io keywords to TCS categories
+    fn default_keyword_mappings() -> Vec<(&'static str, TcsCategory)> {
+        vec![
+            ("crypto", TcsCategory::Cryptography),
+            ("cryptography", TcsCategory::Cryptography),
+            ("encryption", TcsCategory::Cryptography),
+            ("signature", TcsCategory::Cryptography),
+            ("authentication", TcsCategory::Authentication),
+            ("auth", TcsCategory::Authentication),
+            ("oauth", TcsCategory::Authentication),
+            ("tls", TcsCategory::Transport),
+            ("database", TcsCategory::Database),
+            ("sql", TcsCategory::Database),
+        ]
     }
     
     /// Get default TCS classification patterns
@@ -174,6 +589,29 @@ impl TcsClassifier {
     }
 }
 
+/// crates.io categories and keywords attached to a package by metadata enhancement
+#[derive(Debug, Default)]
+struct PackageTerms {
+    categories: Vec<String>,
+    keywords: Vec<String>,
+}
+
+impl PackageTerms {
+    /// Terms from a graph node's annotations
+    fn of(package: &PackageNode) -> Self {
+        let strings = |key: &str| -> Vec<String> {
+            package.annotation(key)
+                .and_then(|value| value.as_array())
+                .map(|values| values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        Self {
+            categories: strings(annotation_keys::CATEGORIES),
+            keywords: strings(annotation_keys::KEYWORDS),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +634,7 @@ mod tests {
         let crypto_package = CargoPackage {
             name: "sha2".to_string(),
             version: "0.10.0".to_string(),
+            checksum: "test-checksum".to_string(),
             source: CargoSource::Registry {
                 registry: "crates.io".to_string(),
                 checksum: "test-checksum".to_string(),
@@ -219,6 +658,7 @@ mod tests {
         let proc_macro_package = CargoPackage {
             name: "my-proc-macro".to_string(),
             version: "1.0.0".to_string(),
+            checksum: "test-checksum".to_string(),
             source: CargoSource::Registry {
                 registry: "crates.io".to_string(),
                 checksum: "test-checksum".to_string(),
@@ -242,6 +682,7 @@ mod tests {
         let mechanical_package = CargoPackage {
             name: "ordinary-utils".to_string(),
             version: "1.0.0".to_string(),
+            checksum: "test-checksum".to_string(),
             source: CargoSource::Registry {
                 registry: "crates.io".to_string(),
                 checksum: "test-checksum".to_string(),
@@ -255,4 +696,251 @@ mod tests {
         let result = classifier.classify_package(&mechanical_package).await.unwrap();
         assert!(!result.is_tcs());
     }
+    
+    #[test]
+    fn test_explain_package() {
+        let config = RustAdapterConfig::default();
+        let classifier = TcsClassifier::new(&config);
+        
+        let package = PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "sha2".to_string(),
+            version: "0.10.0".to_string(),
+            source: PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: "test-checksum".to_string(),
+            },
+            checksum: "test-checksum".to_string(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        
+        let explanation = classifier.explain_package(&project, &package);
+        assert!(explanation.result.is_tcs());
+        assert_eq!(explanation.result.tcs_category(), Some(TcsCategory::Cryptography));
+        
+        // Every default pattern plus override, project list, proc-macro and fallback signals are listed
+        assert_eq!(explanation.evaluations.len(), classifier.get_default_patterns().len() + 4);
+        
+        let decisive: Vec<_> = explanation.evaluations.iter().filter(|e| e.decisive).collect();
+        assert_eq!(decisive.len(), 1);
+        assert_eq!(decisive[0].rule, "default pattern 'crypto-sha2' (SHA-2 cryptographic functions)");
+        assert_eq!(decisive[0].weight, 0.7);
+        
+        // The fallback still matches but is not decisive
+        let fallback = explanation.evaluations.last().unwrap();
+        assert!(fallback.matched);
+        assert!(!fallback.decisive);
+    }
+    
+    #[test]
+    fn test_registry_category_and_keyword_signals() {
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.disable_result_cache = true;
+        config.classification_config.keyword_mappings.insert("Payments".to_string(), TcsCategory::Custom("payments".to_string()));
+        let classifier = TcsClassifier::new(&config);
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        
+        let mut package = PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "opaque-lib".to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Local { path: "../opaque-lib".to_string() },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        assert!(!classifier.classify_node(&project, &package).is_tcs());
+        
+        // Subcategories match their parent's mapping, and categories outweigh keywords
+        package.set_annotation(annotation_keys::CATEGORIES, serde_json::json!(["cryptography::cryptocurrencies"]));
+        package.set_annotation(annotation_keys::KEYWORDS, serde_json::json!(["payments"]));
+        let result = classifier.classify_node(&project, &package);
+        assert_eq!(result.tcs_category(), Some(TcsCategory::Cryptography));
+        assert_eq!(result.signals, vec![ClassificationSignal::CargoCategory("cryptography::cryptocurrencies".to_string())]);
+        
+        // Configured keyword mappings are case-insensitive
+        package.set_annotation(annotation_keys::CATEGORIES, serde_json::json!(["command-line-utilities"]));
+        let result = classifier.classify_node(&project, &package);
+        assert_eq!(result.tcs_category(), Some(TcsCategory::Custom("payments".to_string())));
+        assert_eq!(result.signals[0].weight(), 0.4);
+    }
+    
+    #[test]
+    fn test_unclassified_report() {
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.disable_result_cache = true;
+        config.classification_config.max_tcs_adjacent_unknowns = Some(0);
+        let classifier = TcsClassifier::new(&config);
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let mut ids = Vec::new();
+        for name in ["sha2", "opaque-glue", "ordinary-utils"] {
+            let id = uuid::Uuid::new_v4();
+            ids.push(id);
+            graph.add_package(PackageNode {
+                id,
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                source: PackageSource::Local { path: format!("../{}", name) },
+                checksum: String::new(),
+                classification: Classification::Unknown,
+                audit_status: AuditStatus::Unaudited,
+                annotations: vec![],
+            });
+        }
+        graph.add_edge(DependencyEdge { from: ids[1], to: ids[0], kind: DependencyKind::Normal, target: None, optional: false, features: vec![] });
+        classifier.classify_graph(&project, &mut graph).unwrap();
+        
+        let report = classifier.unclassified_report(&project, &graph);
+        assert_eq!(report.total_packages, 3);
+        let names: Vec<_> = report.packages.iter().map(|p| p.package_name.as_str()).collect();
+        assert_eq!(names, vec!["opaque-glue", "ordinary-utils"]);
+        assert_eq!(report.packages[0].tcs_neighbors, vec!["sha2@1.0.0".to_string()]);
+        assert!(!report.packages[1].is_tcs_adjacent());
+        assert!(!report.packages[0].evaluations.is_empty());
+        
+        let error = classifier.check_unclassified(&report).unwrap_err();
+        assert!(matches!(error, crate::AdapterError::UnclassifiedTcsAdjacent { count: 1, limit: 0, .. }));
+    }
+    
+    #[test]
+    fn test_custom_category_from_project_tcs() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.result_cache_dir = Some(cache_dir.path().to_path_buf());
+        let classifier = TcsClassifier::new(&config);
+        
+        let mut project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        project.tcs.custom.insert("payments".to_string(), vec!["stripe-rs".to_string()]);
+        
+        let package = PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "stripe-rs".to_string(),
+            version: "0.1.0".to_string(),
+            source: PackageSource::Local { path: "../stripe-rs".to_string() },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        
+        let result = classifier.classify_node(&project, &package);
+        assert_eq!(result.tcs_category(), Some(TcsCategory::Custom("payments".to_string())));
+        assert_eq!(result.signals, vec![ClassificationSignal::ProjectTcsList("tcs.custom.payments".to_string())]);
+        assert_eq!(classifier.cache().get(&project.tcs, &package), Some(result));
+        
+        // Changing an override invalidates the cached result
+        config.explicit_tcs_overrides.insert("stripe-*".to_string(), TcsCategory::Authentication);
+        let reconfigured = TcsClassifier::new(&config);
+        assert_eq!(reconfigured.cache().get(&project.tcs, &package), None);
+        assert_eq!(reconfigured.classify_node(&project, &package).tcs_category(), Some(TcsCategory::Authentication));
+    }
+    
+    #[test]
+    fn test_classification_diff() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.result_cache_dir = Some(cache_dir.path().to_path_buf());
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            std::path::PathBuf::from("/test"),
+        );
+        
+        let mut before = DependencyGraph::new("test".to_string(), "rust".to_string());
+        for name in ["sha2", "ordinary-utils"] {
+            before.add_package(PackageNode {
+                id: uuid::Uuid::new_v4(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                source: PackageSource::Local { path: format!("../{}", name) },
+                checksum: String::new(),
+                classification: Classification::Unknown,
+                audit_status: AuditStatus::Unaudited,
+                annotations: vec![],
+            });
+        }
+        TcsClassifier::new(&config).classify_graph(&project, &mut before).unwrap();
+        
+        // A new rule promotes a mechanical crate
+        config.explicit_tcs_overrides.insert("ordinary-*".to_string(), TcsCategory::Authentication);
+        let classifier = TcsClassifier::new(&config);
+        let mut after = before.clone();
+        classifier.classify_graph(&project, &mut after).unwrap();
+        
+        let diff = classifier.classification_diff(&project, &before, &after);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].package_name, "ordinary-utils");
+        assert!(diff.changes[0].signals.iter().any(|s| s.contains("ordinary-utils")));
+        assert_eq!(diff.promoted().count(), 1);
+        assert_eq!(diff.unmatched_packages, 0);
+        assert_eq!((diff.tcs_coverage_before, diff.tcs_coverage_after), (50.0, 100.0));
+        assert_eq!(diff.tcs_coverage_delta(), 50.0);
+    }
+    
+    #[test]
+    fn test_bench_reports_partial_matches_and_ruleset_changes() {
+        let mut config = RustAdapterConfig::default();
+        config.classification_config.disable_result_cache = true;
+        let corpus: Vec<CorpusCrate> = [
+            ("randomize-case", None),
+            ("rand_core", Some(TcsCategory::Random)),
+            ("sha2", Some(TcsCategory::Cryptography)),
+            ("jsonwebtoken", Some(TcsCategory::Authentication)),
+        ].into_iter().map(|(name, expected)| CorpusCrate {
+            name: name.to_string(),
+            categories: vec![],
+            keywords: vec!["jwt".to_string()],
+            proc_macro: false,
+            expected,
+        }).collect();
+        
+        let baseline = TcsClassifier::new(&config).bench(&corpus);
+        assert_eq!((baseline.match_rate(), baseline.accuracy()), (75.0, 50.0));
+        assert_eq!(baseline.false_positives().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["randomize-case"]);
+        assert_eq!(baseline.false_negatives().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["jsonwebtoken"]);
+        assert_eq!(baseline.partial_name_matches.len(), 1);
+        assert_eq!(baseline.partial_name_matches[0].segment, "randomize");
+        let rand_rule = baseline.rules.iter().find(|stats| stats.rule.contains("'random-rand'")).unwrap();
+        assert_eq!((rand_rule.matched, rand_rule.decisive), (2, 2));
+        
+        // A new keyword mapping fixes the false negative
+        config.classification_config.keyword_mappings.insert("jwt".to_string(), TcsCategory::Authentication);
+        let report = TcsClassifier::new(&config).bench(&corpus);
+        let diff = report.diff(&baseline);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].name, "jsonwebtoken");
+        assert!(diff.changes[0].is_fix());
+        assert_eq!((diff.accuracy_before, report.accuracy()), (50.0, 75.0));
+        assert_eq!(diff.unmatched_crates, 0);
+        
+        assert_eq!(TcsClassifier::bundled_corpus().unwrap().len(), 152);
+    }
 }
@@ -0,0 +1,477 @@
+//! Append-only, signed transparency log of supply-chain events
+//!
+//! Every vendoring, epoch creation and verification is appended to a
+//! JSON Lines file. Each entry carries the hash of the entry before it,
+//! so removing, reordering or editing an entry breaks the chain, and an
+//! Ed25519 signature over its own hash, so entries cannot be rewritten
+//! without the signing key. [`TransparencyLog::verify`] checks the whole
+//! chain against a configured trusted public key. Truncating the newest
+//! entries is only detectable against a head hash recorded elsewhere.
+//!
+//! The signing key must live outside the project tree, by default in the
+//! user configuration directory; anyone who can read it can rewrite the
+//! log undetected.
+
+use crate::error::{AdapterError, Result};
+use crate::models::TransparencyLogConfig;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Signing key below the user configuration directory
+const DEFAULT_KEY_FILE: &str = "rust-adapter/transparency-log.key";
+
+/// `previous_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Supply-chain event recorded in the log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LogEvent {
+    /// Dependencies were vendored
+    VendorDigest {
+        /// Vendor directory, relative to the project root
+        vendor_dir: String,
+        /// SHA256 of the vendor checksums manifest
+        manifest_digest: String,
+        /// Number of vendored packages
+        total_packages: usize,
+    },
+    /// A vendor snapshot was pinned to an epoch
+    EpochCreated {
+        /// Epoch ID
+        epoch_id: String,
+        /// Canonical fingerprint of the pinned Cargo.lock
+        lockfile_fingerprint: String,
+        /// Content digest of the pinned vendor directory
+        content_digest: String,
+        /// Number of vendored packages
+        total_packages: usize,
+    },
+    /// Vendored sources or an epoch snapshot were verified
+    Verification {
+        /// Vendor directory or `epoch:<id>`
+        subject: String,
+        /// Whether verification passed
+        passed: bool,
+        /// Failure description
+        detail: Option<String>,
+    },
+}
+
+/// Entry of the transparency log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogEntry {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+    /// Recording timestamp (RFC 3339)
+    pub timestamp: String,
+    /// Recorded event
+    pub event: LogEvent,
+    /// `entry_hash` of the previous entry, [`GENESIS_HASH`] for the first
+    pub previous_hash: String,
+    /// SHA256 over sequence, timestamp, event and previous hash
+    pub entry_hash: String,
+    /// Ed25519 signature of `entry_hash` (hex)
+    pub signature: String,
+    /// Public key the entry was signed with (hex)
+    pub public_key: String,
+}
+
+/// Signed content of an entry
+#[derive(Serialize)]
+struct SignedContent<'a> {
+    sequence: u64,
+    timestamp: &'a str,
+    event: &'a LogEvent,
+    previous_hash: &'a str,
+}
+
+impl LogEntry {
+    /// Hash of the entry's signed content
+    pub fn compute_hash(&self) -> String {
+        let content = SignedContent {
+            sequence: self.sequence,
+            timestamp: &self.timestamp,
+            event: &self.event,
+            previous_hash: &self.previous_hash,
+        };
+        let bytes = serde_json::to_vec(&content).unwrap_or_default();
+        format!("{:x}", Sha256::digest(bytes))
+    }
+    
+    /// Whether `signature` is a valid signature of `entry_hash` by `public_key`
+    fn signature_valid(&self) -> bool {
+        let key = decode::<32>(&self.public_key).and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+        let signature = decode::<64>(&self.signature).map(|bytes| Signature::from_bytes(&bytes));
+        match (key, signature) {
+            (Some(key), Some(signature)) => key.verify(self.entry_hash.as_bytes(), &signature).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Problem found while verifying the log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogProblem {
+    /// Line of the log file (1-based)
+    pub line: usize,
+    /// Sequence number of the entry, if it could be read
+    pub sequence: Option<u64>,
+    /// What is wrong
+    pub reason: String,
+}
+
+/// Result of verifying the log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogVerification {
+    /// Log file
+    pub log_file: PathBuf,
+    /// Number of entries read
+    pub entries: usize,
+    /// Hash of the last entry
+    pub head_hash: Option<String>,
+    /// Key the entries are expected to be signed with
+    pub public_key: Option<String>,
+    /// Problems found, in log order
+    pub problems: Vec<LogProblem>,
+}
+
+impl LogVerification {
+    /// Whether the log verified without problems
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Transparency log of one project
+#[derive(Debug, Clone)]
+pub struct TransparencyLog {
+    /// Log file
+    path: PathBuf,
+    /// Project root the signing key must stay out of
+    root: PathBuf,
+    /// Signing key file, `None` if no location is known
+    key_path: Option<PathBuf>,
+    /// Public key every entry must be signed with
+    trusted_public_key: Option<String>,
+}
+
+impl TransparencyLog {
+    /// Log of the project at `root`
+    pub fn new(root: &Path, config: &TransparencyLogConfig) -> Self {
+        Self {
+            path: root.join(&config.log_file),
+            root: root.to_path_buf(),
+            key_path: config.signing_key.as_ref().map(|key| root.join(key)).or_else(Self::default_key_path),
+            trusted_public_key: config.trusted_public_key.clone(),
+        }
+    }
+    
+    /// Default signing key shared by all projects of the current user
+    ///
+    /// Uses `$XDG_CONFIG_HOME`, falling back to `$HOME/.config`.
+    pub fn default_key_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join(DEFAULT_KEY_FILE))
+    }
+    
+    /// Log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Sign and append an event
+    ///
+    /// The log file is locked while the entry is chained and written, so
+    /// concurrent runs append in turn. Appending to a log whose last
+    /// entry cannot be read fails rather than starting a new chain.
+    pub fn append(&self, event: LogEvent) -> Result<LogEntry> {
+        let key = self.signing_key()?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating transparency log directory"))?;
+        }
+        
+        let mut file = std::fs::OpenOptions::new().create(true).read(true).append(true).open(&self.path)
+            .map_err(|_| AdapterError::permission_denied(&self.path, "opening transparency log"))?;
+        file.lock()
+            .map_err(|_| AdapterError::permission_denied(&self.path, "locking transparency log"))?;
+        
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|_| AdapterError::permission_denied(&self.path, "reading transparency log"))?;
+        let (sequence, previous_hash) = match content.lines().rev().find(|line| !line.trim().is_empty()) {
+            None => (0, GENESIS_HASH.to_string()),
+            Some(line) => {
+                let last: LogEntry = serde_json::from_str(line).map_err(|e| AdapterError::Internal {
+                    message: format!("Last entry of {} is unreadable; run 'rust-adapter log verify'", self.path.display()),
+                    source: anyhow::anyhow!("{}", e),
+                })?;
+                (last.sequence + 1, last.entry_hash)
+            },
+        };
+        
+        let mut entry = LogEntry {
+            sequence,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event,
+            previous_hash,
+            entry_hash: String::new(),
+            signature: String::new(),
+            public_key: encode(key.verifying_key().as_bytes()),
+        };
+        entry.entry_hash = entry.compute_hash();
+        entry.signature = encode(&key.sign(entry.entry_hash.as_bytes()).to_bytes());
+        
+        let line = serde_json::to_string(&entry).map_err(|e| AdapterError::Internal {
+            message: "Failed to serialize transparency log entry".to_string(),
+            source: anyhow::anyhow!("{}", e),
+        })?;
+        file.write_all(format!("{}\n", line).as_bytes())
+            .and_then(|_| file.sync_data())
+            .map_err(|_| AdapterError::permission_denied(&self.path, "appending to transparency log"))?;
+        
+        tracing::debug!(sequence = entry.sequence, hash = %entry.entry_hash, "Recorded transparency log entry");
+        Ok(entry)
+    }
+    
+    /// Public key (hex) of the signing key, creating the key on first use
+    ///
+    /// This is the value to configure as `trusted_public_key`.
+    pub fn public_key(&self) -> Result<String> {
+        Ok(encode(self.signing_key()?.verifying_key().as_bytes()))
+    }
+    
+    /// Check the hash chain and signatures of every entry
+    ///
+    /// Entries must be signed with the configured trusted key; without
+    /// one the log cannot be verified and an error is returned. When
+    /// `expected_head` is given, the log must contain an entry with that
+    /// hash, which detects truncation back past a head recorded earlier.
+    pub fn verify(&self, expected_head: Option<&str>) -> Result<LogVerification> {
+        let trusted_key = self.trusted_public_key.clone().ok_or_else(|| AdapterError::ConfigurationInvalid {
+            field: "transparency_log_config.trusted_public_key".to_string(),
+            value: String::new(),
+            reason: "Verifying the transparency log requires the public key of its signing key; see 'rust-adapter log public-key'".to_string(),
+            source: anyhow::anyhow!("No trusted public key configured"),
+        })?;
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(_) => return Err(AdapterError::permission_denied(&self.path, "reading transparency log")),
+        };
+        
+        let mut verification = LogVerification {
+            log_file: self.path.clone(),
+            entries: 0,
+            head_hash: None,
+            public_key: Some(trusted_key.clone()),
+            problems: Vec::new(),
+        };
+        let mut expected_sequence = 0;
+        let mut expected_previous = GENESIS_HASH.to_string();
+        let mut head_found = false;
+        
+        for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let mut problem = |sequence: Option<u64>, reason: String| {
+                verification.problems.push(LogProblem { line: index + 1, sequence, reason });
+            };
+            let entry: LogEntry = match serde_json::from_str(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    problem(None, format!("unreadable entry: {}", e));
+                    continue;
+                },
+            };
+            
+            let sequence = Some(entry.sequence);
+            if entry.sequence != expected_sequence {
+                problem(sequence, format!("expected sequence {}, entries were removed or reordered", expected_sequence));
+            }
+            if entry.previous_hash != expected_previous {
+                problem(sequence, "does not chain to the previous entry".to_string());
+            }
+            if entry.compute_hash() != entry.entry_hash {
+                problem(sequence, "contents do not match the entry hash, the entry was modified".to_string());
+            }
+            if entry.public_key != trusted_key {
+                problem(sequence, "signed with an untrusted key".to_string());
+            } else if !entry.signature_valid() {
+                problem(sequence, "invalid signature".to_string());
+            }
+            
+            head_found |= expected_head == Some(entry.entry_hash.as_str());
+            expected_sequence = entry.sequence + 1;
+            expected_previous = entry.entry_hash.clone();
+            verification.head_hash = Some(entry.entry_hash);
+            verification.entries += 1;
+        }
+        
+        if let Some(head) = expected_head.filter(|_| !head_found) {
+            verification.problems.push(LogProblem {
+                line: content.lines().count(),
+                sequence: None,
+                reason: format!("expected head {} is not in the log, the log was truncated or rewritten", head),
+            });
+        }
+        
+        Ok(verification)
+    }
+    
+    /// Load the signing key, creating it on first use
+    ///
+    /// Fails if the key would be inside the project tree.
+    fn signing_key(&self) -> Result<SigningKey> {
+        let key_path = self.key_path.as_ref().ok_or_else(|| AdapterError::ConfigurationInvalid {
+            field: "transparency_log_config.signing_key".to_string(),
+            value: String::new(),
+            reason: "No signing key location; set a path outside the project".to_string(),
+            source: anyhow::anyhow!("Neither XDG_CONFIG_HOME nor HOME is set"),
+        })?;
+        if resolve(key_path).starts_with(resolve(&self.root)) {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "transparency_log_config.signing_key".to_string(),
+                value: key_path.display().to_string(),
+                reason: "The signing key must be kept outside the project tree".to_string(),
+                source: anyhow::anyhow!("Signing key inside the project"),
+            });
+        }
+        
+        if let Ok(content) = std::fs::read_to_string(key_path) {
+            return decode::<32>(content.trim())
+                .map(|bytes| SigningKey::from_bytes(&bytes))
+                .ok_or_else(|| AdapterError::ConfigurationInvalid {
+                    field: "transparency_log_config.signing_key".to_string(),
+                    value: key_path.display().to_string(),
+                    reason: "Signing key file must contain a hex-encoded 32-byte Ed25519 secret key".to_string(),
+                    source: anyhow::anyhow!("Invalid signing key"),
+                });
+        }
+        
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        if let Some(parent) = key_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating signing key directory"))?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(key_path)
+            .and_then(|mut file| file.write_all(encode(&key.to_bytes()).as_bytes()))
+            .map_err(|_| AdapterError::permission_denied(key_path, "creating transparency log signing key"))?;
+        
+        tracing::info!(key = %key_path.display(), public_key = %encode(key.verifying_key().as_bytes()), "Created transparency log signing key");
+        Ok(key)
+    }
+}
+
+/// Absolute form of a path, with symlinks resolved if it exists
+fn resolve(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn decode<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Enabled log config with a signing key outside the project
+    fn config(keys: &Path) -> TransparencyLogConfig {
+        TransparencyLogConfig {
+            enabled: true,
+            signing_key: Some(keys.join("transparency-log.key")),
+            ..TransparencyLogConfig::default()
+        }
+    }
+    
+    #[test]
+    fn test_log_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = tempfile::tempdir().unwrap();
+        let mut config = config(keys.path());
+        config.trusted_public_key = Some(TransparencyLog::new(dir.path(), &config).public_key().unwrap());
+        let log = TransparencyLog::new(dir.path(), &config);
+        
+        for epoch_id in ["2024-q1", "2024-q2", "2024-q3"] {
+            log.append(LogEvent::EpochCreated {
+                epoch_id: epoch_id.to_string(),
+                lockfile_fingerprint: "lock".to_string(),
+                content_digest: "digest".to_string(),
+                total_packages: 3,
+            }).unwrap();
+        }
+        let intact = log.verify(None).unwrap();
+        assert!(intact.is_intact());
+        assert_eq!(intact.entries, 3);
+        let head = intact.head_hash.clone().unwrap();
+        
+        // Editing an entry breaks its hash
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        std::fs::write(log.path(), content.replacen("2024-q2", "2024-qX", 1)).unwrap();
+        let tampered = log.verify(None).unwrap();
+        assert_eq!(tampered.problems.len(), 1);
+        assert_eq!(tampered.problems[0].sequence, Some(1));
+        
+        // Dropping an entry breaks the chain; dropping the newest one only shows against a known head
+        let lines: Vec<&str> = content.lines().collect();
+        std::fs::write(log.path(), format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(!log.verify(None).unwrap().is_intact());
+        std::fs::write(log.path(), format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        assert!(log.verify(None).unwrap().is_intact());
+        assert!(!log.verify(Some(&head)).unwrap().is_intact());
+        
+        // Entries signed with another key are rejected
+        std::fs::write(log.path(), &content).unwrap();
+        let other_key = TransparencyLogConfig {
+            trusted_public_key: Some(encode(SigningKey::from_bytes(&[7; 32]).verifying_key().as_bytes())),
+            ..config.clone()
+        };
+        let untrusted = TransparencyLog::new(dir.path(), &other_key).verify(None).unwrap();
+        assert_eq!(untrusted.problems.len(), 3);
+        
+        // Without a trusted key the log cannot be verified
+        let unverifiable = TransparencyLogConfig { trusted_public_key: None, ..config };
+        assert!(matches!(
+            TransparencyLog::new(dir.path(), &unverifiable).verify(None),
+            Err(AdapterError::ConfigurationInvalid { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_signing_key_must_be_outside_project() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!TransparencyLogConfig::default().enabled);
+        
+        let inside = TransparencyLogConfig {
+            signing_key: Some(PathBuf::from(".rust-adapter/transparency-log.key")),
+            ..config(dir.path())
+        };
+        let log = TransparencyLog::new(dir.path(), &inside);
+        assert!(matches!(log.public_key(), Err(AdapterError::ConfigurationInvalid { .. })));
+        assert!(log.append(LogEvent::Verification { subject: "vendor".to_string(), passed: true, detail: None }).is_err());
+        assert!(!dir.path().join(".rust-adapter/transparency-log.key").exists());
+    }
+}
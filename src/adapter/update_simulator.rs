@@ -0,0 +1,213 @@
+//! What-if simulation of dependency updates
+//!
+//! Proposed updates are resolved in a temporary copy of the project with
+//! `cargo update --precise`, so the real Cargo.lock is never touched. The
+//! copy leaves out build output, version control and vendored sources;
+//! path dependencies outside the project root are not copied and must
+//! still be reachable from the copy for resolution to succeed.
+
+use crate::models::*;
+use crate::error::{AdapterError, Result};
+use crate::utils::{CancellationToken, ProcessInvoker, ToolInvocation, ToolInvoker};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Directories never copied into a simulation workspace
+const EXCLUDED_DIRS: &[&str] = &["target", ".git", "vendor", ".rust-adapter"];
+
+/// Update simulator implementation
+#[derive(Debug, Clone)]
+pub struct UpdateSimulator {
+    /// Whether to resolve without network access
+    offline_mode: bool,
+    /// Timeout for each `cargo update` invocation
+    timeout: Duration,
+    /// Whether simulator is ready
+    ready: bool,
+    /// Token that kills a running `cargo update` when cancelled
+    cancel: CancellationToken,
+    /// Runs cargo update
+    tools: Arc<dyn ToolInvoker>,
+}
+
+/// Temporary copy of a project, removed on drop
+#[derive(Debug)]
+pub struct SimulationWorkspace {
+    /// Project rooted at the copy
+    pub project: Project,
+    /// Temporary directory holding the copy
+    dir: PathBuf,
+}
+
+impl UpdateSimulator {
+    /// Create new update simulator with configuration
+    pub fn new(config: &RustAdapterConfig) -> Self {
+        Self {
+            offline_mode: config.offline_mode,
+            timeout: Duration::from_secs(config.tool_paths.default_timeout),
+            ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
+        }
+    }
+    
+    /// Kill running `cargo update` invocations when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run cargo through `tools` instead of spawning it directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
+    /// Check if simulator is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Copy a project and apply the proposed updates to the copy's Cargo.lock
+    pub async fn prepare(&self, project: &Project, updates: &[ProposedUpdate]) -> Result<SimulationWorkspace> {
+        let workspace = self.copy(project)?;
+        self.apply(&workspace, updates).await?;
+        Ok(workspace)
+    }
+    
+    /// Copy a project into a new simulation workspace
+    pub fn copy(&self, project: &Project) -> Result<SimulationWorkspace> {
+        let lockfile_path = project.lockfile_path();
+        if !lockfile_path.exists() {
+            return Err(AdapterError::file_not_found(&lockfile_path, "simulating dependency updates"));
+        }
+        
+        let dir = std::env::temp_dir().join(format!("rust-adapter-simulate-{}", uuid::Uuid::new_v4()));
+        let mut simulated = project.clone();
+        simulated.paths.root = dir.clone();
+        let workspace = SimulationWorkspace { project: simulated, dir };
+        
+        Self::copy_project(&project.paths.root, &workspace.dir)?;
+        Ok(workspace)
+    }
+    
+    /// Apply proposed updates to a workspace's Cargo.lock, in order
+    pub async fn apply(&self, workspace: &SimulationWorkspace, updates: &[ProposedUpdate]) -> Result<()> {
+        for update in updates {
+            self.apply_update(&workspace.dir, update).await?;
+        }
+        Ok(())
+    }
+    
+    /// Run `cargo update --precise` for one update in the copy
+    async fn apply_update(&self, root: &Path, update: &ProposedUpdate) -> Result<()> {
+        let mut invocation = ToolInvocation::new("cargo update", "cargo")
+            .args(["update", "--package", &update.name, "--precise", &update.version])
+            .current_dir(root)
+            .timeout(Some(self.timeout));
+        if self.offline_mode {
+            invocation = invocation.arg("--offline");
+        }
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if !output.is_success() {
+            return Err(AdapterError::ToolExecutionFailed {
+                tool: "cargo update".to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+                source: anyhow::anyhow!("Failed to resolve update {}", update),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Copy a project tree, skipping excluded directories and symlinks
+    fn copy_project(source: &Path, target: &Path) -> Result<()> {
+        let entries = walkdir::WalkDir::new(source)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !(entry.file_type().is_dir() && EXCLUDED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+            });
+        
+        for entry in entries {
+            let entry = entry.map_err(|e| AdapterError::permission_denied(
+                &e.path().map(Path::to_path_buf).unwrap_or_else(|| source.to_path_buf()),
+                "reading project for simulation",
+            ))?;
+            let Ok(relative) = entry.path().strip_prefix(source) else {
+                continue;
+            };
+            let destination = target.join(relative);
+            
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&destination)
+                    .map_err(|_| AdapterError::permission_denied(&destination, "creating simulation workspace"))?;
+            } else if entry.file_type().is_file() {
+                std::fs::copy(entry.path(), &destination)
+                    .map_err(|_| AdapterError::permission_denied(&destination, "copying project for simulation"))?;
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+impl Drop for SimulationWorkspace {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            tracing::debug!(path = %self.dir.display(), error = %e, "Failed to remove simulation workspace");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_copy_project_skips_excluded_dirs() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        std::fs::write(source.path().join("Cargo.toml"), "[workspace]\n").unwrap();
+        std::fs::write(source.path().join("Cargo.lock"), "version = 3\n").unwrap();
+        std::fs::create_dir_all(source.path().join("src")).unwrap();
+        std::fs::write(source.path().join("src/lib.rs"), "").unwrap();
+        std::fs::create_dir_all(source.path().join("target/debug")).unwrap();
+        std::fs::write(source.path().join("target/debug/out"), "").unwrap();
+        
+        UpdateSimulator::copy_project(source.path(), target.path()).unwrap();
+        
+        assert!(target.path().join("Cargo.lock").exists());
+        assert!(target.path().join("src/lib.rs").exists());
+        assert!(!target.path().join("target").exists());
+        assert_eq!("serde@1.0.200".parse::<ProposedUpdate>().unwrap().version, "1.0.200");
+        assert!("serde".parse::<ProposedUpdate>().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_updates_run_through_the_tool_invoker() {
+        use crate::utils::{ReplayInvoker, ToolOutput};
+        
+        let source = TempDir::new().unwrap();
+        std::fs::write(source.path().join("Cargo.lock"), "version = 3\n").unwrap();
+        let project = Project::new("simulate".to_string(), "Simulate".to_string(), "rust".to_string(), source.path().to_path_buf());
+        let config = RustAdapterConfig { offline_mode: true, ..RustAdapterConfig::default() };
+        
+        let tools = ReplayInvoker::new()
+            .with_output("cargo update --package serde --precise 1.0.200 --offline", ToolOutput::success(""))
+            .with_output("cargo update --package serde --precise 9.9.9 --offline", ToolOutput::failure(101, "no matching package"));
+        let simulator = UpdateSimulator::new(&config).with_tool_invoker(Arc::new(tools));
+        
+        let update = "serde@1.0.200".parse::<ProposedUpdate>().unwrap();
+        assert!(simulator.prepare(&project, &[update]).await.is_ok());
+        let update = "serde@9.9.9".parse::<ProposedUpdate>().unwrap();
+        assert!(matches!(
+            simulator.prepare(&project, &[update]).await,
+            Err(AdapterError::ToolExecutionFailed { exit_code: 101, .. })
+        ));
+    }
+}
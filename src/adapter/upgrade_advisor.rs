@@ -0,0 +1,360 @@
+//! Minimal-upgrade suggestions for audit findings
+//!
+//! For each locked package with applicable advisories, this module finds
+//! the smallest patched version that none of them affects and checks it
+//! against the requirements the workspace declares on the package.
+//! Suggestions are reported as facts; remediation payloads rewrite
+//! requirements only in a scratch copy of the project and hand back a
+//! patch, so no manifest or lockfile of the project itself is changed.
+
+use crate::error::{AdapterError, Result};
+use crate::manifest::{ManifestDependencySource, WorkspaceManifest};
+use crate::models::*;
+use semver::{Version, VersionReq};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, TableLike};
+
+/// Dependency tables of a manifest, also nested under `[target.<cfg>]`
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Upgrade advisor implementation
+#[derive(Debug, Clone)]
+pub struct UpgradeAdvisor {
+    /// Whether advisor is ready
+    ready: bool,
+}
+
+impl UpgradeAdvisor {
+    /// Create new upgrade advisor with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self { ready: true }
+    }
+    
+    /// Check if advisor is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Suggest an upgrade for every locked package with applicable findings
+    ///
+    /// Candidates are the lower bounds of the findings' patched
+    /// requirements; the smallest one that clears every applicable
+    /// finding is suggested. Without `manifest`, suggestions carry no
+    /// requirement checks. Suggestions are sorted by package and version.
+    pub fn suggest(&self, report: &AuditReport, graph: &DependencyGraph, manifest: Option<&WorkspaceManifest>) -> Vec<UpgradeSuggestion> {
+        let mut affected: BTreeMap<(String, Version), Vec<&AuditFinding>> = BTreeMap::new();
+        for finding in &report.findings {
+            let versions = graph.root_packages.iter()
+                .filter(|package| package.name == finding.package_name)
+                .filter(|package| finding.package_version.as_ref().is_none_or(|version| &package.version == version))
+                .filter(|package| finding.applies_to(&package.version) == AdvisoryApplicability::Applicable)
+                .filter_map(|package| Version::parse(&package.version).ok());
+            for version in versions {
+                affected.entry((finding.package_name.clone(), version)).or_default().push(finding);
+            }
+        }
+        
+        affected.into_iter()
+            .map(|((name, version), findings)| {
+                let suggested = Self::clearing_version(&version, &findings);
+                let requirements = manifest
+                    .map(|manifest| Self::requirement_checks(manifest, &name, &version, suggested.as_ref()))
+                    .unwrap_or_default();
+                
+                UpgradeSuggestion {
+                    package_name: name,
+                    current_version: version.to_string(),
+                    suggested_version: suggested.map(|v| v.to_string()),
+                    advisories: findings.iter().map(|finding| finding.id.clone()).collect(),
+                    requirements,
+                }
+            })
+            .collect()
+    }
+    
+    /// Suggestions clearing a finding, looked up by advisory ID or alias
+    ///
+    /// Fails when the finding does not apply to any locked version or when
+    /// no patched release clears it.
+    pub fn upgrades_for(&self, report: &AuditReport, finding_id: &str) -> Result<Vec<UpgradeSuggestion>> {
+        let unavailable = |reason: &str| AdapterError::RemediationUnavailable {
+            finding_id: finding_id.to_string(),
+            reason: reason.to_string(),
+            source: anyhow::anyhow!("No remediation for {}", finding_id),
+        };
+        
+        let ids: BTreeSet<&str> = report.findings.iter()
+            .filter(|finding| finding.id == finding_id || finding.aliases.iter().any(|alias| alias == finding_id))
+            .map(|finding| finding.id.as_str())
+            .collect();
+        if ids.is_empty() {
+            return Err(unavailable("no such finding in the audit report"));
+        }
+        
+        let upgrades: Vec<UpgradeSuggestion> = report.upgrade_suggestions.iter()
+            .filter(|suggestion| suggestion.advisories.iter().any(|id| ids.contains(id.as_str())))
+            .cloned()
+            .collect();
+        if upgrades.is_empty() {
+            return Err(unavailable("the finding does not apply to any locked version"));
+        }
+        if let Some(stuck) = upgrades.iter().find(|suggestion| suggestion.suggested_version.is_none()) {
+            return Err(unavailable(&format!("no patched release of {} clears every advisory of {}",
+                stuck.package_name, stuck.current_version)));
+        }
+        
+        Ok(upgrades)
+    }
+    
+    /// Rewrite manifest requirements that exclude the suggested version
+    ///
+    /// Only requirements the suggestion reports as unsatisfied are
+    /// rewritten, keeping their `=`, `^` or `~` operator. Manifests are
+    /// edited in place, so `manifest` should describe a scratch copy.
+    pub fn bump_requirements(&self, manifest: &WorkspaceManifest, suggestion: &UpgradeSuggestion) -> Result<Vec<RequirementUpdate>> {
+        let Some(version) = suggestion.suggested_version.as_deref().and_then(|v| Version::parse(v).ok()) else {
+            return Ok(Vec::new());
+        };
+        let failing: Vec<&str> = suggestion.requirements.iter()
+            .filter(|check| !check.satisfied)
+            .map(|check| check.requirement.as_str())
+            .collect();
+        if failing.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let mut updates = Vec::new();
+        for path in std::iter::once(&manifest.root_manifest).chain(&manifest.members).map(|m| &m.path) {
+            let content = std::fs::read_to_string(path)
+                .map_err(|_| AdapterError::file_not_found(path, "rewriting dependency requirements"))?;
+            let mut document: DocumentMut = content.parse().map_err(|e| AdapterError::CargoTomlParseError {
+                file: path.clone(),
+                error: format!("{}", e),
+                source: anyhow::anyhow!("Failed to parse manifest for remediation"),
+            })?;
+            
+            let bumped = Self::bump_document(&mut document, &suggestion.package_name, &failing, &version);
+            if bumped.is_empty() {
+                continue;
+            }
+            crate::utils::write_atomic(path, document.to_string())?;
+            
+            let relative = path.strip_prefix(&manifest.root).unwrap_or(path);
+            updates.extend(bumped.into_iter().map(|(previous, updated)| RequirementUpdate {
+                manifest: relative.to_string_lossy().replace('\\', "/"),
+                package_name: suggestion.package_name.clone(),
+                previous,
+                updated,
+            }));
+        }
+        Ok(updates)
+    }
+    
+    /// Unified diff of files between the project and its updated copy
+    pub fn patch(&self, original_root: &Path, updated_root: &Path, files: &[String]) -> Result<String> {
+        let mut patch = String::new();
+        for file in files {
+            let read = |root: &Path| {
+                let path = root.join(file);
+                std::fs::read_to_string(&path)
+                    .map_err(|_| AdapterError::file_not_found(&path, "generating remediation patch"))
+            };
+            let (old, new) = (read(original_root)?, read(updated_root)?);
+            
+            patch.push_str(&similar::TextDiff::from_lines(&old, &new)
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{}", file), &format!("b/{}", file))
+                .to_string());
+        }
+        Ok(patch)
+    }
+    
+    /// Rewrite matching requirements in every dependency table of a manifest
+    fn bump_document(document: &mut DocumentMut, name: &str, failing: &[&str], version: &Version) -> Vec<(String, String)> {
+        let mut bumped = Vec::new();
+        let root = document.as_table_mut();
+        
+        for key in DEPENDENCY_TABLES {
+            if let Some(table) = root.get_mut(key).and_then(Item::as_table_like_mut) {
+                Self::bump_table(table, name, failing, version, &mut bumped);
+            }
+        }
+        if let Some(table) = root.get_mut("workspace")
+            .and_then(|workspace| workspace.get_mut("dependencies"))
+            .and_then(Item::as_table_like_mut)
+        {
+            Self::bump_table(table, name, failing, version, &mut bumped);
+        }
+        if let Some(targets) = root.get_mut("target").and_then(Item::as_table_like_mut) {
+            for (_, target) in targets.iter_mut() {
+                for key in DEPENDENCY_TABLES {
+                    if let Some(table) = target.get_mut(key).and_then(Item::as_table_like_mut) {
+                        Self::bump_table(table, name, failing, version, &mut bumped);
+                    }
+                }
+            }
+        }
+        bumped
+    }
+    
+    /// Rewrite matching requirements in one dependency table
+    fn bump_table(table: &mut dyn TableLike, name: &str, failing: &[&str], version: &Version, bumped: &mut Vec<(String, String)>) {
+        for (key, item) in table.iter_mut() {
+            let package = item.get("package").and_then(Item::as_str).unwrap_or(key.get());
+            if package != name {
+                continue;
+            }
+            
+            let requirement = if item.is_str() { Some(item) } else { item.get_mut("version") };
+            let Some(value) = requirement.and_then(Item::as_value_mut) else {
+                continue;
+            };
+            let Some(previous) = value.as_str().filter(|previous| failing.contains(previous)).map(str::to_string) else {
+                continue;
+            };
+            
+            let operator: String = previous.trim_start().chars().take_while(|c| matches!(c, '=' | '^' | '~')).collect();
+            let updated = format!("{}{}", operator, version);
+            let decor = value.decor().clone();
+            *value = updated.as_str().into();
+            *value.decor_mut() = decor;
+            bumped.push((previous, updated));
+        }
+    }
+    
+    /// Smallest version above `current` that no finding affects
+    fn clearing_version(current: &Version, findings: &[&AuditFinding]) -> Option<Version> {
+        let mut candidates: Vec<Version> = findings.iter()
+            .flat_map(|finding| finding.patched_lower_bounds())
+            .filter(|candidate| candidate > current)
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        
+        candidates.into_iter().find(|candidate| {
+            let candidate = candidate.to_string();
+            findings.iter().all(|finding| finding.applies_to(&candidate) == AdvisoryApplicability::NotApplicable)
+        })
+    }
+    
+    /// Workspace requirements on `name` that `current` satisfies, checked against `suggested`
+    fn requirement_checks(manifest: &WorkspaceManifest, name: &str, current: &Version, suggested: Option<&Version>) -> Vec<RequirementCheck> {
+        let mut checks: Vec<RequirementCheck> = Vec::new();
+        for package_manifest in manifest.packages() {
+            let declared_by = package_manifest.package.as_ref()
+                .map(|package| package.name.as_str())
+                .unwrap_or_default();
+            
+            for dep in &package_manifest.dependencies {
+                if dep.package != name || matches!(dep.source, ManifestDependencySource::Path { .. }) {
+                    continue;
+                }
+                let Some(requirement) = &dep.version_req else {
+                    continue;
+                };
+                let Ok(req) = VersionReq::parse(requirement) else {
+                    continue;
+                };
+                if !req.matches(current) {
+                    continue;
+                }
+                
+                let check = RequirementCheck {
+                    declared_by: declared_by.to_string(),
+                    requirement: requirement.clone(),
+                    satisfied: suggested.is_some_and(|version| req.matches(version)),
+                };
+                if !checks.contains(&check) {
+                    checks.push(check);
+                }
+            }
+        }
+        checks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RustAdapterConfig;
+    use crate::manifest::CargoManifest;
+    use std::path::{Path, PathBuf};
+    
+    #[test]
+    fn test_suggestion_clears_all_advisories() {
+        let root_manifest = CargoManifest::parse(Path::new("Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+            
+            [dependencies]
+            ring = "0.16"
+        "#).unwrap();
+        let manifest = WorkspaceManifest {
+            root: PathBuf::from("."),
+            root_manifest,
+            members: vec![],
+        };
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "ring".to_string(),
+            version: "0.16.5".to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        
+        let mut report = AuditReport::new();
+        report.add_finding(AuditFinding::new("RUSTSEC-2025-0001".to_string(), "ring".to_string(), "<0.17.12".to_string(), Severity::High, String::new())
+            .add_patched_version("^0.16.20".to_string())
+            .add_patched_version(">=0.17.12".to_string()));
+        report.add_finding(AuditFinding::new("RUSTSEC-2025-0002".to_string(), "ring".to_string(), "<0.17.0".to_string(), Severity::Low, String::new())
+            .add_patched_version(">=0.17.0".to_string()));
+        
+        let suggestions = UpgradeAdvisor::new(&RustAdapterConfig::default()).suggest(&report, &graph, Some(&manifest));
+        
+        // 0.16.20 only clears the first advisory; 0.17.12 clears both but leaves `ring = "0.16"`
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].suggested_version.as_deref(), Some("0.17.12"));
+        assert_eq!(suggestions[0].advisories.len(), 2);
+        assert_eq!(suggestions[0].requirements, vec![RequirementCheck {
+            declared_by: "app".to_string(),
+            requirement: "0.16".to_string(),
+            satisfied: false,
+        }]);
+        assert!(!suggestions[0].within_requirements());
+    }
+    
+    #[test]
+    fn test_bump_document_keeps_operator_and_layout() {
+        let mut document: DocumentMut = r#"
+[dependencies]
+ring = "0.16" # crypto
+serde = "1"
+
+[target.'cfg(unix)'.dependencies]
+crypto = { package = "ring", version = "=0.16.5", default-features = false }
+
+[dev-dependencies]
+ring = "0.17"
+"#.parse().unwrap();
+        let version = Version::parse("0.17.12").unwrap();
+        
+        let bumped = UpgradeAdvisor::bump_document(&mut document, "ring", &["0.16", "=0.16.5"], &version);
+        
+        assert_eq!(bumped, vec![
+            ("0.16".to_string(), "0.17.12".to_string()),
+            ("=0.16.5".to_string(), "=0.17.12".to_string()),
+        ]);
+        let content = document.to_string();
+        assert!(content.contains("ring = \"0.17.12\" # crypto"));
+        assert!(content.contains("version = \"=0.17.12\", default-features = false"));
+        assert!(content.contains("ring = \"0.17\"\n"));
+    }
+}
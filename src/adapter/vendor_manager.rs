@@ -5,9 +5,88 @@
 
 use crate::models::*;
 use crate::error::Result;
-use async_trait::async_trait;
-use std::path::Path;
-use std::process::Command;
+use crate::utils::{atomic_write, config_path, deadline, v1_metadata_checksum, AtomicFile, CancellationToken, ChecksumAlgorithm, ChecksumCalculator, DigestConfig, ProcessInvoker, SharedCache, ToolInvocation, ToolInvoker, LOCKFILE_CHECKSUM_ALGORITHM};
+use super::local_deps::{self, LOCAL_DEPENDENCIES_KEY};
+use super::secret_scanner::SecretScanner;
+use super::transparency_log::{LogEvent, TransparencyLog};
+use super::verify_progress::{self, ProgressKey, VerifyProgress};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Checksums manifest file written to the vendor directory root
+pub const VENDOR_MANIFEST_FILE: &str = ".vendor-checksums.json";
+
+/// Per-package checksum file expected by Cargo directory sources
+const CARGO_CHECKSUM_FILE: &str = ".cargo-checksum.json";
+
+/// Name of the manifest entry stored first in vendor archives
+const ARCHIVE_MANIFEST_ENTRY: &str = "vendor-archive-manifest.json";
+
+/// Directory prefix of vendored files inside vendor archives
+const ARCHIVE_VENDOR_PREFIX: &str = "vendor";
+
+/// Modification time recorded for every archive entry
+const ARCHIVE_MTIME: u64 = 0;
+
+/// Zstandard compression level for vendor archives
+const ARCHIVE_COMPRESSION_LEVEL: i32 = 9;
+
+/// Vendor snapshot file within an epoch directory
+const EPOCH_SNAPSHOT_FILE: &str = "vendor-snapshot.json";
+
+/// File list stored next to each epoch's vendor snapshot
+const EPOCH_FILES_FILE: &str = "vendor-files.json";
+
+/// Content-addressed store of vendored file contents, shared by all epochs
+const EPOCH_OBJECTS_DIR: &str = ".objects";
+
+/// Source name used for vendored sources in Cargo configuration
+const VENDORED_SOURCE_NAME: &str = "vendored-sources";
+
+/// Download URL template of crates.io
+const CRATES_IO_DOWNLOAD: &str = "https://static.crates.io/crates/{crate}/{crate}-{version}.crate";
+
+/// Markers of a registry download URL template
+const DOWNLOAD_MARKERS: &[&str] = &["{crate}", "{version}", "{prefix}", "{lowerprefix}", "{sha256-checksum}"];
+
+/// Packages hashed between saves of verification progress
+const VERIFY_PROGRESS_INTERVAL: usize = 64;
+
+/// Subset of Cargo.lock needed to plan vendoring
+#[derive(Debug, Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// Package entry in Cargo.lock
+#[derive(Debug, Clone, Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    checksum: Option<String>,
+}
+
+/// Packages to add, replace and remove during a delta run
+#[derive(Debug, Default)]
+struct DeltaPlan {
+    /// Packages not vendored before
+    added: Vec<VendorManifestEntry>,
+    /// Packages whose checksum changed
+    changed: Vec<VendorManifestEntry>,
+    /// Packages no longer in Cargo.lock
+    removed: Vec<VendorManifestEntry>,
+    /// Number of untouched packages
+    unchanged: usize,
+}
 
 /// Vendor manager implementation
 #[derive(Debug, Clone)]
@@ -16,6 +95,10 @@ pub struct VendorManager {
     config: VendorManagerConfig,
     /// Whether manager is ready
     ready: bool,
+    /// Token that stops running cargo commands when cancelled
+    cancel: CancellationToken,
+    /// Runs cargo vendor, fetch and build
+    tools: Arc<dyn ToolInvoker>,
 }
 
 /// Configuration for vendor manager
@@ -31,6 +114,26 @@ pub struct VendorManagerConfig {
     pub malware_scan: bool,
     /// Whether to compare with fresh downloads
     pub compare_fresh: bool,
+    /// Maximum number of threads used for checksum hashing
+    pub max_hashing_threads: usize,
+    /// Buffer size used when reading vendored files
+    pub io_buffer_size: usize,
+    /// Whether to re-vendor only changed packages
+    pub delta_vendoring: bool,
+    /// Whether verification resumes from progress saved by an interrupted run
+    pub resume_verification: bool,
+    /// Directory in the shared cache holding verification progress, `None` if no cache location is known
+    pub progress_dir: Option<std::path::PathBuf>,
+    /// Transparency log receiving vendoring, epoch and verification events
+    pub transparency_log: TransparencyLogConfig,
+    /// Scan of vendored sources for embedded secrets
+    pub secret_scan: SecretScanConfig,
+    /// Download URL templates of alternative registries, keyed by index URL
+    pub registry_downloads: HashMap<String, String>,
+    /// Whether to verify path dependencies and pin their contents in epochs
+    pub verify_local_deps: bool,
+    /// Digest of archive manifests, epoch file lists and archive checksum files
+    pub digest: DigestConfig,
 }
 
 impl VendorManager {
@@ -43,53 +146,1752 @@ impl VendorManager {
                 verify_checksums: config.vendor_config.verify_checksums,
                 malware_scan: config.vendor_config.malware_scan,
                 compare_fresh: config.vendor_config.compare_fresh,
+                max_hashing_threads: config.performance_config.max_hashing_threads,
+                io_buffer_size: config.performance_config.io_buffer_size,
+                delta_vendoring: config.vendor_config.delta_vendoring,
+                resume_verification: !config.vendor_config.full_verification,
+                progress_dir: SharedCache::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout)
+                    .map(|cache| cache.entry_path(verify_progress::PROGRESS_ENTRY)),
+                transparency_log: config.transparency_log_config.clone(),
+                secret_scan: config.vendor_config.secret_scan.clone(),
+                registry_downloads: config.vendor_config.registry_downloads.clone(),
+                verify_local_deps: config.vendor_config.verify_local_deps,
+                digest: config.digest_config,
             },
             ready: true,
+            cancel: CancellationToken::new(),
+            tools: ProcessInvoker::shared(),
+        }
+    }
+    
+    /// Stop running cargo commands when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Run cargo through `tools` instead of spawning it directly
+    pub fn with_tool_invoker(mut self, tools: Arc<dyn ToolInvoker>) -> Self {
+        self.tools = tools;
+        self
+    }
+    
+    /// Check if manager is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Vendor dependencies to target directory
+    pub async fn vendor_dependencies(&self, project: &Project, target: &Path) -> Result<()> {
+        if self.config.delta_vendoring {
+            return self.vendor_dependencies_delta(project, target).await.map(|_| ());
+        }
+        
+        self.vendor_dependencies_full(project, target).await
+    }
+    
+    /// Re-vendor only packages that changed since the last vendoring
+    ///
+    /// The new Cargo.lock is diffed against the checksums manifest in the
+    /// vendor directory. Added and changed registry packages are extracted
+    /// from Cargo's local registry cache after verifying their checksum,
+    /// and dropped packages are removed. A full `cargo vendor` is run
+    /// instead whenever the manifest is missing or does not match the
+    /// vendor directory, a git dependency changed, or the directory
+    /// layout would shift.
+    pub async fn vendor_dependencies_delta(&self, project: &Project, target: &Path) -> Result<DeltaVendorReport> {
+        let locked = Self::load_locked_packages(project)?;
+        
+        let plan = match Self::load_manifest(target) {
+            None => Err("no vendor checksums manifest".to_string()),
+            Some(manifest) => Self::check_manifest_consistency(target, &manifest)
+                .and_then(|_| Self::plan_delta(&manifest, &locked)),
+        };
+        
+        let plan = match plan {
+            Ok(plan) => plan,
+            Err(reason) => return self.fall_back_to_full(project, target, reason).await,
+        };
+        
+        match self.apply_delta(project, target, &plan).await {
+            Ok(()) => {},
+            Err(e @ (crate::AdapterError::Cancelled { .. } | crate::AdapterError::ToolTimeout { .. } | crate::AdapterError::DeadlineExceeded { .. })) => return Err(e),
+            Err(e) => return self.fall_back_to_full(project, target, e.to_string()).await,
+        }
+        Self::write_manifest(target, &Self::build_manifest(&locked))?;
+        
+        // Same checks as a full vendoring, so a delta cannot bring in unverified content
+        deadline::set_stage("checking Cargo.lock completeness");
+        self.verify_lockfile_completeness(project, target).await?;
+        if self.config.verify_checksums {
+            self.validate_checksums(project, target).await?;
+        }
+        self.generate_cargo_config(target).await?;
+        self.log_vendored_size(project, target);
+        self.record_vendor_digest(project, target, locked.len());
+        
+        let key = |entry: &VendorManifestEntry| format!("{}@{}", entry.name, entry.version);
+        Ok(DeltaVendorReport {
+            full_vendor: false,
+            fallback_reason: None,
+            added: plan.added.iter().map(key).collect(),
+            changed: plan.changed.iter().map(key).collect(),
+            removed: plan.removed.iter().map(key).collect(),
+            unchanged: plan.unchanged,
+        })
+    }
+    
+    /// Describe what vendoring would change without touching the vendor directory
+    ///
+    /// Uses the same manifest checks as a delta run, so the plan reports a
+    /// full vendoring exactly when the delta would fall back to one.
+    pub fn plan_vendor(&self, project: &Project, target: &Path, delta: bool) -> Result<ChangePlan> {
+        let locked = Self::load_locked_packages(project)?;
+        let key = |entry: &VendorManifestEntry| format!("{}@{}", entry.name, entry.version);
+        let mut plan = ChangePlan::new("vendor");
+        let manifest = Self::load_manifest(target);
+
+        let delta_plan = match (&manifest, delta || self.config.delta_vendoring) {
+            (_, false) => Err("delta vendoring not requested".to_string()),
+            (None, true) => Err("no vendor checksums manifest".to_string()),
+            (Some(manifest), true) => Self::check_manifest_consistency(target, manifest)
+                .and_then(|_| Self::plan_delta(manifest, &locked)),
+        };
+
+        match delta_plan {
+            Ok(delta_plan) => {
+                plan.packages_vendored = delta_plan.added.iter().chain(&delta_plan.changed).map(key).collect();
+                plan.packages_removed = delta_plan.removed.iter().map(key).collect();
+                plan.files_written = delta_plan.added.iter().chain(&delta_plan.changed).map(|entry| target.join(&entry.path)).collect();
+                plan.files_removed = delta_plan.changed.iter().chain(&delta_plan.removed).map(|entry| target.join(&entry.path)).collect();
+                plan.notes.push(format!("{} package(s) unchanged", delta_plan.unchanged));
+            },
+            Err(reason) => {
+                let desired = Self::build_manifest(&locked);
+                plan.packages_vendored = desired.packages.keys().cloned().collect();
+                plan.packages_removed = manifest.iter()
+                    .flat_map(|manifest| manifest.packages.keys())
+                    .filter(|key| !desired.packages.contains_key(*key))
+                    .cloned()
+                    .collect();
+                plan.files_written.push(target.to_path_buf());
+                plan.notes.push(format!("Full vendoring with cargo vendor: {}", reason));
+            },
+        }
+
+        plan.files_written.push(target.join(VENDOR_MANIFEST_FILE));
+        plan.files_written.push(target.join(".cargo").join("config.toml"));
+
+        Ok(plan)
+    }
+
+    /// Describe a vendor directory, including the on-disk size of each package
+    ///
+    /// Packages are taken from the checksums manifest written by vendoring.
+    pub fn vendor_info(&self, project: &Project, vendor_dir: &Path) -> Result<VendorInfo> {
+        let manifest = Self::load_manifest(vendor_dir)
+            .ok_or_else(|| crate::AdapterError::file_not_found(&vendor_dir.join(VENDOR_MANIFEST_FILE), "reading vendor checksums manifest"))?;
+
+        let mut info = VendorInfo::new(vendor_dir.to_path_buf());
+        info.metadata.checksums_file = PathBuf::from(VENDOR_MANIFEST_FILE);
+        info.lockfile_fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path())?;
+
+        for entry in manifest.packages.values() {
+            let checksum = entry.checksum.clone().unwrap_or_default();
+            let source = Self::package_source(entry.source.as_deref(), &checksum);
+            let size_bytes = Self::directory_size(&vendor_dir.join(&entry.path));
+
+            info.add_package(VendorPackageInfo::new(entry.name.clone(), entry.version.clone(), source, checksum, entry.path.clone())
+                .with_size(size_bytes));
+        }
+        info.offline_ready = vendor_dir.join(".cargo").join("config.toml").is_file();
+
+        Ok(info)
+    }
+
+    /// Universal source of a vendored package from its Cargo.lock source string
+    fn package_source(source: Option<&str>, checksum: &str) -> PackageSource {
+        match source.and_then(|source| source.split_once('+')) {
+            Some(("git", location)) => {
+                let (url, rev) = location.split_once('#').unwrap_or((location, ""));
+                PackageSource::Git {
+                    url: url.split('?').next().unwrap_or(url).to_string(),
+                    rev: rev.to_string(),
+                    checksum: checksum.to_string(),
+                }
+            },
+            Some((_, url)) => PackageSource::Registry {
+                url: url.to_string(),
+                checksum: checksum.to_string(),
+            },
+            None => PackageSource::Registry {
+                url: "https://crates.io".to_string(),
+                checksum: checksum.to_string(),
+            },
+        }
+    }
+
+    /// Total size of the regular files below a directory
+    fn directory_size(dir: &Path) -> u64 {
+        walkdir::WalkDir::new(dir)
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+
+    /// Run a full vendoring after a delta could not be applied
+    async fn fall_back_to_full(&self, project: &Project, target: &Path, reason: String) -> Result<DeltaVendorReport> {
+        tracing::warn!(reason = %reason, "Delta vendoring not possible, vendoring all packages");
+        self.vendor_dependencies_full(project, target).await?;
+        Ok(DeltaVendorReport::full(Some(reason)))
+    }
+    
+    /// Vendor all dependencies with `cargo vendor`
+    async fn vendor_dependencies_full(&self, project: &Project, target: &Path) -> Result<()> {
+        // 1. Execute cargo vendor <target_dir>
+        deadline::set_stage("running cargo vendor");
+        let invocation = ToolInvocation::new("cargo vendor", "cargo")
+            .arg("vendor")
+            .arg(target)
+            .current_dir(&project.paths.root)
+            .timeout(Some(self.vendor_timeout()));
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if !output.is_success() {
+            return Err(crate::AdapterError::ToolExecutionFailed {
+                tool: "cargo vendor".to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+                source: anyhow::anyhow!("cargo vendor execution failed"),
+            });
+        }
+        
+        // 2. Record checksums manifest for later delta runs
+        let locked = Self::load_locked_packages(project)?;
+        Self::write_manifest(target, &Self::build_manifest(&locked))?;
+        
+        // 3. Verify Cargo.lock completeness
+        deadline::set_stage("checking Cargo.lock completeness");
+        self.verify_lockfile_completeness(project, target).await?;
+        
+        // 4. Verify checksums if enabled
+        if self.config.verify_checksums {
+            self.validate_checksums(project, target).await?;
+        }
+        
+        // 5. Generate .cargo/config.toml for offline builds
+        self.generate_cargo_config(target).await?;
+        
+        self.log_vendored_size(project, target);
+        self.record_vendor_digest(project, target, locked.len());
+        
+        Ok(())
+    }
+    
+    /// Append an event to the project's transparency log
+    ///
+    /// Recording never fails the operation; a failure is logged instead
+    /// and shows up in `rust-adapter log verify` as a gap in the history.
+    fn record_event(&self, project: &Project, event: LogEvent) {
+        if !self.config.transparency_log.enabled {
+            return;
+        }
+        let log = TransparencyLog::new(&project.paths.root, &self.config.transparency_log);
+        if let Err(e) = log.append(event) {
+            tracing::warn!(log = %log.path().display(), error = %e, "Failed to record transparency log entry");
+        }
+    }
+    
+    /// Record the digest of a freshly vendored directory's checksums manifest
+    fn record_vendor_digest(&self, project: &Project, target: &Path, total_packages: usize) {
+        let manifest_digest = match Self::hash_file(&target.join(VENDOR_MANIFEST_FILE), self.config.io_buffer_size.max(1), &DigestConfig::SHA256_HEX) {
+            Ok(digest) => digest,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to hash vendor checksums manifest for the transparency log");
+                return;
+            },
+        };
+        let vendor_dir = target.strip_prefix(&project.paths.root).unwrap_or(target);
+        self.record_event(project, LogEvent::VendorDigest {
+            vendor_dir: vendor_dir.display().to_string(),
+            manifest_digest,
+            total_packages,
+        });
+    }
+    
+    /// Log the size of a freshly vendored directory
+    fn log_vendored_size(&self, project: &Project, target: &Path) {
+        match self.vendor_info(project, target) {
+            Ok(info) => tracing::info!(
+                packages = info.total_packages,
+                total_size_bytes = info.metadata.total_size_bytes,
+                largest = ?info.packages_by_size().first().map(|package| format!("{}@{}", package.name, package.version)),
+                "Vendored packages",
+            ),
+            Err(e) => tracing::debug!(error = %e, "Failed to measure vendor directory"),
+        }
+    }
+    
+    /// Load vendored packages from Cargo.lock
+    ///
+    /// Packages without a source are workspace members or path
+    /// dependencies, which `cargo vendor` does not copy.
+    fn load_locked_packages(project: &Project) -> Result<Vec<LockedPackage>> {
+        let lockfile_path = project.lockfile_path();
+        let lockfile_content = std::fs::read_to_string(&lockfile_path)
+            .map_err(|_| crate::AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
+        Self::parse_locked_packages(&lockfile_path, &lockfile_content)
+    }
+    
+    /// Packages with a source in Cargo.lock content read from `lockfile_path`
+    fn parse_locked_packages(lockfile_path: &Path, lockfile_content: &str) -> Result<Vec<LockedPackage>> {
+        let lockfile: Lockfile = toml::from_str(lockfile_content)
+            .map_err(|e| crate::AdapterError::cargo_lock_parse_error(lockfile_path, 0, &e.to_string()))?;
+        
+        Ok(lockfile.package.into_iter()
+            .filter(|package| package.source.is_some())
+            .map(|mut package| {
+                if package.checksum.is_none() {
+                    package.checksum = v1_metadata_checksum(&lockfile.metadata, &package.name, &package.version, package.source.as_deref());
+                }
+                package
+            })
+            .collect())
+    }
+    
+    /// Build the checksums manifest for a set of locked packages
+    ///
+    /// Directory names follow `cargo vendor`: the newest version of a
+    /// crate uses the bare name, older versions get a version suffix.
+    fn build_manifest(locked: &[LockedPackage]) -> VendorChecksumManifest {
+        let mut newest: HashMap<&str, &str> = HashMap::new();
+        for package in locked {
+            let entry = newest.entry(package.name.as_str()).or_insert(package.version.as_str());
+            if Self::compare_versions(&package.version, entry) == std::cmp::Ordering::Greater {
+                *entry = package.version.as_str();
+            }
+        }
+        
+        let packages = locked.iter()
+            .map(|package| {
+                let path = if newest.get(package.name.as_str()) == Some(&package.version.as_str()) {
+                    PathBuf::from(&package.name)
+                } else {
+                    PathBuf::from(format!("{}-{}", package.name, package.version))
+                };
+                
+                (format!("{}@{}", package.name, package.version), VendorManifestEntry {
+                    name: package.name.clone(),
+                    version: package.version.clone(),
+                    source: package.source.clone(),
+                    checksum: package.checksum.clone(),
+                    path,
+                })
+            })
+            .collect();
+        
+        VendorChecksumManifest {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            packages,
+        }
+    }
+    
+    /// Load the checksums manifest, if present and readable
+    fn load_manifest(vendor_dir: &Path) -> Option<VendorChecksumManifest> {
+        let manifest_path = vendor_dir.join(VENDOR_MANIFEST_FILE);
+        atomic_write::recover_partial_writes(&manifest_path);
+        let content = std::fs::read_to_string(manifest_path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+    
+    /// Write the checksums manifest to the vendor directory
+    fn write_manifest(vendor_dir: &Path, manifest: &VendorChecksumManifest) -> Result<()> {
+        let manifest_path = vendor_dir.join(VENDOR_MANIFEST_FILE);
+        let content = serde_json::to_string_pretty(manifest)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to serialize vendor checksums manifest".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        
+        atomic_write::write_atomic(&manifest_path, content)
+    }
+    
+    /// Check that the vendor directory still matches its manifest
+    fn check_manifest_consistency(vendor_dir: &Path, manifest: &VendorChecksumManifest) -> std::result::Result<(), String> {
+        for (key, entry) in &manifest.packages {
+            let checksum_path = vendor_dir.join(&entry.path).join(CARGO_CHECKSUM_FILE);
+            let recorded: serde_json::Value = std::fs::read_to_string(&checksum_path).ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .ok_or_else(|| format!("{} is missing or unreadable for {}", CARGO_CHECKSUM_FILE, key))?;
+            
+            if let Some(expected) = &entry.checksum {
+                if recorded.get("package").and_then(|v| v.as_str()) != Some(expected.as_str()) {
+                    return Err(format!("vendored package {} does not match the manifest checksum", key));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Diff locked packages against the manifest
+    ///
+    /// Returns the reason a full vendoring is required if the delta
+    /// cannot be applied safely.
+    fn plan_delta(manifest: &VendorChecksumManifest, locked: &[LockedPackage]) -> std::result::Result<DeltaPlan, String> {
+        let desired = Self::build_manifest(locked);
+        let mut plan = DeltaPlan::default();
+        
+        for (key, entry) in &desired.packages {
+            match manifest.packages.get(key) {
+                None => plan.added.push(entry.clone()),
+                Some(existing) if existing.checksum != entry.checksum || existing.source != entry.source => {
+                    plan.changed.push(entry.clone());
+                },
+                Some(existing) if existing.path != entry.path => {
+                    return Err(format!("vendor directory for {} moves from {:?} to {:?}", key, existing.path, entry.path));
+                },
+                Some(_) => plan.unchanged += 1,
+            }
+        }
+        
+        plan.removed = manifest.packages.iter()
+            .filter(|(key, _)| !desired.packages.contains_key(*key))
+            .map(|(_, entry)| entry.clone())
+            .collect();
+        
+        if let Some(entry) = plan.added.iter().chain(&plan.changed).find(|entry| entry.checksum.is_none()) {
+            return Err(format!("{}@{} has no registry checksum and cannot be vendored incrementally", entry.name, entry.version));
+        }
+        
+        let removed_paths: HashSet<_> = plan.removed.iter().chain(&plan.changed).map(|entry| &entry.path).collect();
+        let kept_paths: HashSet<_> = manifest.packages.values()
+            .map(|entry| &entry.path)
+            .filter(|path| !removed_paths.contains(path))
+            .collect();
+        if let Some(entry) = plan.added.iter().find(|entry| kept_paths.contains(&entry.path)) {
+            return Err(format!("vendor directory {:?} is still in use", entry.path));
+        }
+        
+        Ok(plan)
+    }
+    
+    /// Remove dropped packages and extract added or changed ones
+    async fn apply_delta(&self, project: &Project, vendor_dir: &Path, plan: &DeltaPlan) -> Result<()> {
+        if plan.added.is_empty() && plan.changed.is_empty() && plan.removed.is_empty() {
+            return Ok(());
+        }
+        
+        for entry in plan.removed.iter().chain(&plan.changed) {
+            let package_dir = vendor_dir.join(&entry.path);
+            if package_dir.exists() {
+                std::fs::remove_dir_all(&package_dir)
+                    .map_err(|_| crate::AdapterError::permission_denied(&package_dir, "removing vendored package"))?;
+            }
+        }
+        
+        let to_vendor: Vec<_> = plan.added.iter().chain(&plan.changed).collect();
+        if to_vendor.is_empty() {
+            return Ok(());
+        }
+        
+        // Populate the local registry cache; already cached crates are not downloaded again
+        deadline::set_stage("running cargo fetch");
+        let invocation = ToolInvocation::new("cargo fetch", "cargo")
+            .args(["fetch", "--locked"])
+            .current_dir(&project.paths.root)
+            .timeout(Some(self.vendor_timeout()));
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if !output.is_success() {
+            return Err(crate::AdapterError::ToolExecutionFailed {
+                tool: "cargo fetch".to_string(),
+                exit_code: output.exit_code,
+                stderr: output.stderr,
+                source: anyhow::anyhow!("cargo fetch execution failed"),
+            });
+        }
+        
+        let registry_caches = Self::registry_cache_dirs();
+        deadline::set_stage("extracting packages");
+        deadline::set_total(to_vendor.len());
+        for entry in to_vendor {
+            deadline::check()?;
+            let checksum = entry.checksum.as_deref().unwrap_or_default();
+            let file_name = format!("{}-{}.crate", entry.name, entry.version);
+            let crate_file = registry_caches.iter()
+                .map(|dir| dir.join(&file_name))
+                .find(|path| path.is_file())
+                .ok_or_else(|| crate::AdapterError::VendorVerificationFailed {
+                    reason: format!("{} not found in the local registry cache", file_name),
+                    affected_packages: vec![entry.name.clone()],
+                    source: anyhow::anyhow!("Missing cached crate"),
+                })?;
+            
+            Self::extract_crate(&crate_file, &vendor_dir.join(&entry.path), &entry.name, checksum)?;
+            deadline::advance(1);
+        }
+        
+        Ok(())
+    }
+    
+    /// Directories of Cargo's downloaded `.crate` files
+    fn registry_cache_dirs() -> Vec<PathBuf> {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")));
+        
+        let mut dirs: Vec<_> = cargo_home
+            .and_then(|home| std::fs::read_dir(home.join("registry").join("cache")).ok())
+            .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect())
+            .unwrap_or_default();
+        dirs.sort();
+        dirs
+    }
+    
+    /// Verify and extract a `.crate` archive into a vendored package directory
+    ///
+    /// Writes `.cargo-checksum.json` the same way `cargo vendor` does so the
+    /// directory remains usable as a Cargo directory source.
+    fn extract_crate(crate_file: &Path, package_dir: &Path, package_name: &str, checksum: &str) -> Result<()> {
+        use sha2::{Digest, Sha256};
+        use std::io::Read;
+        
+        let data = std::fs::read(crate_file)
+            .map_err(|_| crate::AdapterError::permission_denied(crate_file, "reading crate archive"))?;
+        
+        let actual_checksum = format!("{:x}", Sha256::digest(&data));
+        if actual_checksum != checksum {
+            return Err(crate::AdapterError::checksum_mismatch(package_name, checksum, &actual_checksum));
+        }
+        
+        let invalid_archive = |reason: String| crate::AdapterError::VendorVerificationFailed {
+            reason,
+            affected_packages: vec![package_name.to_string()],
+            source: anyhow::anyhow!("Invalid crate archive"),
+        };
+        
+        let mut files = BTreeMap::new();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(&data[..]));
+        let entries = archive.entries()
+            .map_err(|e| invalid_archive(format!("Failed to read {:?}: {}", crate_file, e)))?;
+        
+        for entry in entries {
+            let mut entry = entry.map_err(|e| invalid_archive(format!("Failed to read {:?}: {}", crate_file, e)))?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            
+            // Entries are prefixed with "<name>-<version>/"
+            let entry_path = entry.path()
+                .map_err(|e| invalid_archive(format!("Invalid entry path in {:?}: {}", crate_file, e)))?
+                .into_owned();
+            let relative: PathBuf = entry_path.components().skip(1).collect();
+            if relative.as_os_str().is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+                return Err(invalid_archive(format!("Unsafe entry path {:?} in {:?}", entry_path, crate_file)));
+            }
+            if Self::is_excluded_from_vendor(&relative) {
+                continue;
+            }
+            
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)
+                .map_err(|e| invalid_archive(format!("Failed to read {:?} from {:?}: {}", relative, crate_file, e)))?;
+            
+            let output_path = package_dir.join(&relative);
+            if let Some(parent) = output_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|_| crate::AdapterError::permission_denied(parent, "creating vendored package directory"))?;
+            }
+            std::fs::write(&output_path, &contents)
+                .map_err(|_| crate::AdapterError::permission_denied(&output_path, "writing vendored file"))?;
+            
+            let key = relative.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(key, format!("{:x}", Sha256::digest(&contents)));
+        }
+        
+        let checksum_path = package_dir.join(CARGO_CHECKSUM_FILE);
+        let checksum_content = serde_json::json!({ "files": files, "package": checksum });
+        atomic_write::write_atomic(&checksum_path, checksum_content.to_string())
+    }
+    
+    /// Files `cargo vendor` leaves out of vendored packages
+    fn is_excluded_from_vendor(relative: &Path) -> bool {
+        relative.components().any(|c| c.as_os_str() == ".git")
+            || matches!(
+                relative.file_name().and_then(|name| name.to_str()),
+                Some(".gitattributes" | ".gitignore" | ".cargo-ok")
+            )
+    }
+    
+    /// Compare two semantic versions, ranking pre-releases below releases
+    fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+        let parse = |version: &str| {
+            let version = version.split('+').next().unwrap_or(version);
+            let (core, pre) = match version.split_once('-') {
+                Some((core, pre)) => (core, Some(pre.to_string())),
+                None => (version, None),
+            };
+            let numbers: Vec<u64> = core.split('.').map(|part| part.parse().unwrap_or(0)).collect();
+            (numbers, pre)
+        };
+        
+        let (a_core, a_pre) = parse(a);
+        let (b_core, b_pre) = parse(b);
+        a_core.cmp(&b_core).then_with(|| match (a_pre, b_pre) {
+            (None, None) => std::cmp::Ordering::Equal,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(&b),
+        })
+    }
+    
+    /// Create a reproducible compressed archive of a vendor directory
+    ///
+    /// Entries are sorted by path and written with fixed mtimes, owners and
+    /// modes, preceded by a manifest of every file's size and checksum in
+    /// the configured digest. The archive digest is written in hex to
+    /// `<archive>.<algorithm>`, e.g. `<archive>.sha256` in `sha256sum` format.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn archive_vendor(&self, vendor_dir: &Path, output: &Path, epoch_id: Option<&str>) -> Result<VendorArchiveInfo> {
+        if !vendor_dir.is_dir() {
+            return Err(crate::AdapterError::file_not_found(vendor_dir, "reading vendor directory"));
+        }
+        
+        let files = self.collect_archive_files(vendor_dir, output, &self.config.digest)?;
+        let manifest = VendorArchiveManifest::new(epoch_id.map(str::to_string), files, self.config.digest);
+        let manifest_json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to serialize vendor archive manifest".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        
+        let write_error = |e: std::io::Error| Self::archive_error(output, format!("Failed to write archive: {}", e));
+        
+        let file = AtomicFile::create(output)?;
+        let encoder = zstd::Encoder::new(file, ARCHIVE_COMPRESSION_LEVEL).map_err(write_error)?;
+        let mut builder = tar::Builder::new(encoder);
+        
+        Self::append_archive_entry(&mut builder, ARCHIVE_MANIFEST_ENTRY, false, &manifest_json[..], manifest_json.len() as u64)
+            .map_err(write_error)?;
+        
+        for entry in &manifest.files {
+            let source_path = vendor_dir.join(&entry.path);
+            let source = std::fs::File::open(&source_path)
+                .map_err(|_| crate::AdapterError::permission_denied(&source_path, "reading vendored file"))?;
+            
+            let archive_path = format!("{}/{}", ARCHIVE_VENDOR_PREFIX, entry.path);
+            Self::append_archive_entry(&mut builder, &archive_path, entry.executable, std::io::Read::take(source, entry.size_bytes), entry.size_bytes)
+                .map_err(write_error)?;
+        }
+        
+        builder.into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(write_error)?
+            .commit()?;
+        
+        let algorithm = self.config.digest.algorithm;
+        let archive_digest = Self::hash_file(output, self.config.io_buffer_size.max(1), &DigestConfig::hex(algorithm))?;
+        let digest_path = Self::archive_digest_path(output, algorithm);
+        let file_name = output.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        atomic_write::write_atomic(&digest_path, format!("{}  {}\n", archive_digest, file_name))?;
+        
+        Ok(VendorArchiveInfo {
+            archive_path: output.to_path_buf(),
+            archive_digest,
+            digest_path,
+            manifest,
+        })
+    }
+    
+    /// Archives are zstd-compressed, and zstd is not built for wasm32
+    #[cfg(target_arch = "wasm32")]
+    pub fn archive_vendor(&self, _vendor_dir: &Path, output: &Path, _epoch_id: Option<&str>) -> Result<VendorArchiveInfo> {
+        Err(Self::archive_error(output, "Vendor archives are not supported on wasm32".to_string()))
+    }
+    
+    /// Unpack a vendor archive and verify it against its digest and manifest
+    ///
+    /// The archive digest is checked against `<archive>.<algorithm>` before
+    /// anything is unpacked. Files are extracted into a staging directory
+    /// next to `target` and moved into place only once every file matches
+    /// the manifest; `target` must not already exist.
+    pub fn restore_vendor(&self, archive: &Path, target: &Path) -> Result<VendorArchiveManifest> {
+        self.check_restore(archive, target)?;
+        
+        let target_name = target.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| ARCHIVE_VENDOR_PREFIX.to_string());
+        let staging = target.with_file_name(format!(".{}.restore", target_name));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)
+                .map_err(|_| crate::AdapterError::permission_denied(&staging, "removing stale restore directory"))?;
+        }
+        
+        let manifest = match self.unpack_archive(archive, &staging) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&staging);
+                return Err(e);
+            },
+        };
+        
+        std::fs::rename(&staging, target)
+            .map_err(|_| crate::AdapterError::permission_denied(target, "moving restored vendor directory"))?;
+        
+        Ok(manifest)
+    }
+    
+    /// Pin the vendor directory and Cargo.lock to an epoch
+    ///
+    /// The vendor directory must match Cargo.lock according to its
+    /// checksums manifest, and every package in `drifted` (packages changed
+    /// since the previous epoch, see [`RustAdapter::pending_drift`]) must
+    /// have an approved review decision. The snapshot is written to
+    /// `<epochs>/<epoch_id>/vendor-snapshot.json`.
+    ///
+    /// [`RustAdapter::pending_drift`]: crate::adapter::RustAdapter::pending_drift
+    pub fn snapshot_vendor(&self, project: &Project, vendor_dir: &Path, epoch_id: &str, drifted: &[String]) -> Result<VendorSnapshot> {
+        let snapshot_path = Self::epoch_snapshot_path(project, epoch_id)?;
+        let locked = Self::load_locked_packages(project)?;
+        
+        Self::check_vendor_matches_lockfile(vendor_dir, &locked)
+            .map_err(|reason| crate::AdapterError::VendorVerificationFailed {
+                reason,
+                affected_packages: vec![],
+                source: anyhow::anyhow!("Vendor directory does not match Cargo.lock"),
+            })?;
+        let local_dependencies = self.verified_local_dependencies(project)?;
+        
+        let files = self.collect_archive_files(vendor_dir, &snapshot_path, &self.config.digest)?;
+        let manifest = VendorArchiveManifest::new(Some(epoch_id.to_string()), files, self.config.digest);
+        
+        let storage_path = vendor_dir.strip_prefix(&project.paths.root).unwrap_or(vendor_dir);
+        let mut snapshot = VendorSnapshot::new(epoch_id.to_string(), storage_path.to_path_buf());
+        snapshot.total_packages = locked.len();
+        snapshot.total_size_bytes = manifest.total_size_bytes;
+        snapshot.checksums_file = PathBuf::from(VENDOR_MANIFEST_FILE);
+        snapshot.lockfile_fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path())?;
+        snapshot.content_digest = manifest.content_digest.clone();
+        if let Some(local_dependencies) = local_dependencies {
+            snapshot.metadata.insert(LOCAL_DEPENDENCIES_KEY.to_string(), serde_json::to_value(local_dependencies).unwrap_or_default());
+        }
+        if let Some(review) = self.check_review(project, epoch_id, &snapshot.lockfile_fingerprint, drifted)? {
+            snapshot.metadata.insert("review".to_string(), serde_json::json!({
+                "approved": review.approved(),
+                "updated_at": review.updated_at,
+                "reviewers": review.decisions.values().map(|d| d.reviewer.as_str()).collect::<BTreeSet<_>>(),
+            }));
+        }
+        snapshot.mark_verified();
+        
+        if let Some(parent) = snapshot_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| crate::AdapterError::permission_denied(parent, "creating epoch directory"))?;
+        }
+        
+        // Keep changed file contents so later epochs can be diffed against this one
+        let previous = self.list_epochs(project)?.into_iter()
+            .rev()
+            .find(|previous| previous.epoch_id != epoch_id)
+            .and_then(|previous| Self::load_epoch_files(project, &previous.epoch_id).ok());
+        let stored = Self::store_epoch_objects(project, vendor_dir, &manifest, previous.as_ref())?;
+        tracing::debug!(epoch = %epoch_id, stored, files = manifest.files.len(), "Stored changed vendored files");
+        let files_content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to serialize vendored file list".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        atomic_write::write_atomic(snapshot_path.with_file_name(EPOCH_FILES_FILE), files_content)?;
+        
+        let content = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to serialize vendor snapshot".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        atomic_write::write_atomic(&snapshot_path, content)?;
+        
+        self.record_event(project, LogEvent::EpochCreated {
+            epoch_id: snapshot.epoch_id.clone(),
+            lockfile_fingerprint: snapshot.lockfile_fingerprint.clone(),
+            content_digest: snapshot.content_digest.clone(),
+            total_packages: snapshot.total_packages,
+        });
+        
+        Ok(snapshot)
+    }
+    
+    /// Describe what pinning the vendor directory to an epoch would write
+    ///
+    /// Runs the same checks as `snapshot_vendor`, so a plan is only
+    /// returned when the snapshot would succeed.
+    pub fn plan_snapshot(&self, project: &Project, vendor_dir: &Path, epoch_id: &str, drifted: &[String]) -> Result<ChangePlan> {
+        let snapshot_path = Self::epoch_snapshot_path(project, epoch_id)?;
+        let locked = Self::load_locked_packages(project)?;
+
+        Self::check_vendor_matches_lockfile(vendor_dir, &locked)
+            .map_err(|reason| crate::AdapterError::VendorVerificationFailed {
+                reason,
+                affected_packages: vec![],
+                source: anyhow::anyhow!("Vendor directory does not match Cargo.lock"),
+            })?;
+        self.verified_local_dependencies(project)?;
+
+        let lockfile_fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path())?;
+        let review = self.check_review(project, epoch_id, &lockfile_fingerprint, drifted)?;
+
+        let files = self.collect_archive_files(vendor_dir, &snapshot_path, &self.config.digest)?;
+        let new_objects = files.iter()
+            .filter(|file| !Self::epoch_object_path(project, &self.config.digest, &file.checksum).is_file())
+            .count();
+
+        let mut plan = ChangePlan::new("vendor snapshot");
+        plan.files_written.push(snapshot_path.clone());
+        plan.files_written.push(snapshot_path.with_file_name(EPOCH_FILES_FILE));
+        plan.notes.push(format!("{} package(s), {} file(s), {} new object(s) in the epoch store", locked.len(), files.len(), new_objects));
+        if let Some(review) = review {
+            plan.notes.push(format!("{} reviewed item(s) approved", review.approved()));
+        }
+
+        let epochs = self.list_epochs(project)?;
+        if epochs.iter().any(|snapshot| snapshot.epoch_id == epoch_id) {
+            plan.notes.push(format!("Existing snapshot for epoch {} would be replaced", epoch_id));
+        }
+        plan.epochs_superseded = epochs.into_iter()
+            .rev()
+            .find(|snapshot| snapshot.epoch_id != epoch_id)
+            .map(|snapshot| snapshot.epoch_id)
+            .into_iter()
+            .collect();
+
+        Ok(plan)
+    }
+
+    /// Load the review decisions recorded for the project, if any
+    pub fn load_review_decisions(&self, project: &Project) -> Result<Option<ReviewDecisions>> {
+        let path = project.epochs_path().join(REVIEW_DECISIONS_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        
+        atomic_write::recover_partial_writes(&path);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| crate::AdapterError::permission_denied(&path, "reading review decisions"))?;
+        let decisions = serde_json::from_str(&content)
+            .map_err(|e| crate::AdapterError::ConfigurationInvalid {
+                field: "review".to_string(),
+                value: path.display().to_string(),
+                reason: format!("Invalid review decisions: {}", e),
+                source: anyhow::anyhow!("Invalid review decisions file"),
+            })?;
+        Ok(Some(decisions))
+    }
+    
+    /// Write the review decisions of the project
+    pub fn save_review_decisions(&self, project: &Project, decisions: &ReviewDecisions) -> Result<()> {
+        let epochs_dir = project.epochs_path();
+        std::fs::create_dir_all(&epochs_dir)
+            .map_err(|_| crate::AdapterError::permission_denied(&epochs_dir, "creating epoch directory"))?;
+        let content = serde_json::to_string_pretty(decisions)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to serialize review decisions".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        atomic_write::write_atomic(epochs_dir.join(REVIEW_DECISIONS_FILE), content)
+    }
+    
+    /// Review decisions made against the Cargo.lock being pinned
+    ///
+    /// Decisions made against another Cargo.lock are stale and ignored.
+    /// Any rejected item blocks the epoch, and so does a drifted package
+    /// without an approved decision.
+    fn check_review(&self, project: &Project, epoch_id: &str, lockfile_fingerprint: &str, drifted: &[String]) -> Result<Option<ReviewDecisions>> {
+        let review = match self.load_review_decisions(project)? {
+            Some(review) if review.lockfile_fingerprint == lockfile_fingerprint => Some(review),
+            Some(_) => {
+                tracing::warn!("Ignoring review decisions made against another Cargo.lock");
+                None
+            },
+            None => None,
+        };
+        
+        if let Some(review) = &review {
+            let rejected = review.rejected();
+            if !rejected.is_empty() {
+                return Err(crate::AdapterError::EpochInvalidated {
+                    epoch_id: epoch_id.to_string(),
+                    reason: format!("Reviewers rejected {}", rejected.join(", ")),
+                    source: anyhow::anyhow!("Rejected review items"),
+                });
+            }
+        }
+        
+        let unreviewed: Vec<&str> = drifted.iter()
+            .filter(|package| {
+                let key = ReviewDecisions::drift_key(package);
+                review.as_ref().and_then(|review| review.status(&key)) != Some(ReviewStatus::Approved)
+            })
+            .map(String::as_str)
+            .collect();
+        if !unreviewed.is_empty() {
+            return Err(crate::AdapterError::EpochInvalidated {
+                epoch_id: epoch_id.to_string(),
+                reason: format!("Drift not approved by a reviewer: {}", unreviewed.join(", ")),
+                source: anyhow::anyhow!("Unreviewed drift"),
+            });
+        }
+        
+        Ok(review)
+    }
+
+    /// Describe the files archiving a vendor directory would write
+    pub fn plan_archive(&self, vendor_dir: &Path, output: &Path, epoch_id: Option<&str>) -> Result<ChangePlan> {
+        if !vendor_dir.is_dir() {
+            return Err(crate::AdapterError::file_not_found(vendor_dir, "reading vendor directory"));
+        }
+
+        let files = self.collect_archive_files(vendor_dir, output, &self.config.digest)?;
+        let manifest = VendorArchiveManifest::new(epoch_id.map(str::to_string), files, self.config.digest);
+        let mut plan = ChangePlan::new("vendor archive");
+        plan.files_written.push(output.to_path_buf());
+        plan.files_written.push(Self::archive_digest_path(output, self.config.digest.algorithm));
+        plan.notes.push(format!("{} file(s), {} bytes, content digest {}", manifest.total_files, manifest.total_size_bytes, manifest.content_digest));
+
+        Ok(plan)
+    }
+
+    /// Check the archive digest and that the restore target is free
+    ///
+    /// The digest file of the configured algorithm is preferred; archives
+    /// written with another algorithm are checked with theirs. Digest files
+    /// of weak algorithms are never trusted.
+    fn check_restore(&self, archive: &Path, target: &Path) -> Result<()> {
+        let preferred = self.config.digest.algorithm;
+        let algorithm = std::iter::once(preferred)
+            .chain(ChecksumAlgorithm::ALL)
+            .filter(|algorithm| !algorithm.is_weak())
+            .find(|algorithm| Self::archive_digest_path(archive, *algorithm).is_file())
+            .unwrap_or(preferred);
+        if let Err(reason) = DigestConfig::hex(algorithm).validate() {
+            return Err(Self::archive_error(archive, format!("Archive digest rejected: {}", reason)));
+        }
+        let digest_path = Self::archive_digest_path(archive, algorithm);
+        let digest_content = std::fs::read_to_string(&digest_path)
+            .map_err(|_| crate::AdapterError::file_not_found(&digest_path, "reading archive digest"))?;
+        let expected_digest = digest_content.split_whitespace().next().unwrap_or_default();
+
+        let actual_digest = Self::hash_file(archive, self.config.io_buffer_size.max(1), &DigestConfig::hex(algorithm))?;
+        if actual_digest != expected_digest {
+            return Err(crate::AdapterError::checksum_mismatch(
+                &archive.display().to_string(),
+                expected_digest,
+                &actual_digest,
+            ));
+        }
+
+        if target.exists() {
+            return Err(Self::archive_error(archive, format!("Restore target {:?} already exists", target)));
+        }
+
+        Ok(())
+    }
+
+    /// Describe what restoring a vendor archive would write
+    ///
+    /// Only the archive digest is checked; the manifest is verified while
+    /// unpacking.
+    pub fn plan_restore(&self, archive: &Path, target: &Path) -> Result<ChangePlan> {
+        self.check_restore(archive, target)?;
+
+        let mut plan = ChangePlan::new("vendor restore");
+        plan.files_written.push(target.to_path_buf());
+
+        Ok(plan)
+    }
+
+    /// Verify that Cargo.lock and the vendor directory still match an epoch
+    ///
+    /// Fails closed: a missing or unreadable snapshot is treated the same
+    /// as a mismatch. The outcome is recorded in the transparency log.
+    pub fn verify_epoch_snapshot(&self, project: &Project, epoch_id: &str) -> Result<VendorSnapshot> {
+        let result = self.check_epoch_snapshot(project, epoch_id);
+        self.record_event(project, LogEvent::Verification {
+            subject: format!("epoch:{}", epoch_id),
+            passed: result.is_ok(),
+            detail: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result
+    }
+    
+    /// Compare Cargo.lock and the vendor directory with an epoch's snapshot
+    fn check_epoch_snapshot(&self, project: &Project, epoch_id: &str) -> Result<VendorSnapshot> {
+        let invalidated = |reason: String| crate::AdapterError::EpochInvalidated {
+            epoch_id: epoch_id.to_string(),
+            reason,
+            source: anyhow::anyhow!("Epoch verification failed"),
+        };
+        
+        let snapshot_path = Self::epoch_snapshot_path(project, epoch_id)?;
+        atomic_write::recover_partial_writes(&snapshot_path);
+        let content = std::fs::read_to_string(&snapshot_path)
+            .map_err(|_| invalidated(format!("No readable vendor snapshot at {:?}", snapshot_path)))?;
+        let snapshot: VendorSnapshot = serde_json::from_str(&content)
+            .map_err(|e| invalidated(format!("Vendor snapshot {:?} is truncated or corrupt: {}", snapshot_path, e)))?;
+        
+        if snapshot.epoch_id != epoch_id {
+            return Err(invalidated(format!("Snapshot belongs to epoch {}", snapshot.epoch_id)));
+        }
+        if snapshot.lockfile_fingerprint.is_empty() || snapshot.content_digest.is_empty() {
+            return Err(invalidated("Snapshot does not pin Cargo.lock and vendor contents".to_string()));
+        }
+        
+        let lockfile_fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path())?;
+        if lockfile_fingerprint != snapshot.lockfile_fingerprint {
+            return Err(invalidated("Cargo.lock does not match the epoch".to_string()));
+        }
+        
+        let vendor_dir = project.paths.root.join(&snapshot.storage_path);
+        let locked = Self::load_locked_packages(project)?;
+        Self::check_vendor_matches_lockfile(&vendor_dir, &locked).map_err(invalidated)?;
+        
+        // Hash with the digest the epoch was pinned with
+        let digest = Self::load_epoch_files(project, epoch_id).map(|files| files.digest).unwrap_or_default();
+        let files = self.collect_archive_files(&vendor_dir, &snapshot_path, &digest)?;
+        let content_digest = VendorArchiveManifest::new(None, files, digest).content_digest;
+        if content_digest != snapshot.content_digest {
+            return Err(invalidated("Vendor directory contents do not match the epoch".to_string()));
+        }
+        
+        if let Some(pinned) = snapshot.metadata.get(LOCAL_DEPENDENCIES_KEY) {
+            let pinned: Vec<LocalDependency> = serde_json::from_value(pinned.clone())
+                .map_err(|e| invalidated(format!("Pinned local dependencies are corrupt: {}", e)))?;
+            let current = local_deps::verify(project)?.verified;
+            if let Some(changed) = pinned.iter().find(|dependency| !current.contains(dependency)) {
+                return Err(invalidated(format!("Local crate {}@{} at {} changed since the epoch", changed.name, changed.version, changed.path.display())));
+            }
+        }
+        
+        Ok(snapshot)
+    }
+    
+    /// Path dependencies to pin in an epoch, failing if any does not verify
+    ///
+    /// `None` when local dependency verification is disabled.
+    fn verified_local_dependencies(&self, project: &Project) -> Result<Option<Vec<LocalDependency>>> {
+        if !self.config.verify_local_deps {
+            return Ok(None);
+        }
+        let check = local_deps::verify(project)?;
+        if !check.issues.is_empty() {
+            return Err(crate::AdapterError::VendorVerificationFailed {
+                reason: check.issues.iter()
+                    .map(|issue| format!("{}@{}: {}", issue.package_name, issue.version, issue.reason))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                affected_packages: check.issues.iter().map(|issue| issue.package_name.clone()).collect(),
+                source: anyhow::anyhow!("Path dependencies failed verification"),
+            });
+        }
+        Ok(Some(check.verified))
+    }
+    
+    /// Build the project offline from the vendor snapshot pinned to an epoch
+    ///
+    /// Runs `cargo build --frozen` with source replacement pointing at the
+    /// vendor directory, and only after `verify_epoch_snapshot` succeeds.
+    pub async fn build_epoch(&self, project: &Project, epoch_id: &str, cargo_args: &[String]) -> Result<()> {
+        let snapshot = self.verify_epoch_snapshot(project, epoch_id)?;
+        let vendor_dir = project.paths.root.join(&snapshot.storage_path);
+        let vendor_dir = vendor_dir.canonicalize().unwrap_or(vendor_dir);
+        let locked = Self::load_locked_packages(project)?;
+        
+        let mut args = vec!["build".to_string(), "--frozen".to_string()];
+        for (key, value) in Self::vendored_source_config(&vendor_dir, &locked) {
+            args.push("--config".to_string());
+            args.push(format!("{}={}", key, value));
+        }
+        args.extend(cargo_args.iter().cloned());
+        
+        // Build output goes straight to the terminal; builds are not time limited
+        let invocation = ToolInvocation::new("cargo build", "cargo")
+            .args(&args)
+            .current_dir(&project.paths.root)
+            .env("CARGO_NET_OFFLINE", "true")
+            .inherit_output();
+        let output = self.tools.invoke(&invocation, &self.cancel).await?;
+        
+        if !output.is_success() {
+            return Err(crate::AdapterError::ToolExecutionFailed {
+                tool: "cargo build".to_string(),
+                exit_code: output.exit_code,
+                stderr: String::new(),
+                source: anyhow::anyhow!("cargo build execution failed"),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Configured limit for cargo commands that download or vendor crates
+    fn vendor_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.vendor_timeout)
+    }
+    
+    /// Compare the vendored sources pinned to two epochs file by file
+    ///
+    /// With `package`, only the vendored directories of that crate
+    /// (`<name>` or `<name>-<version>`) are compared. With `unified`, each
+    /// change carries a unified diff built from the epoch object store.
+    pub fn diff_epochs(
+        &self,
+        project: &Project,
+        epoch_a: &str,
+        epoch_b: &str,
+        package: Option<&str>,
+        unified: bool,
+    ) -> Result<VendorDiffReport> {
+        let files_a = Self::load_epoch_files(project, epoch_a)?;
+        let files_b = Self::load_epoch_files(project, epoch_b)?;
+        
+        let in_scope = |file: &&VendorArchiveFile| match package {
+            Some(name) => Self::is_package_file(&file.path, name),
+            None => true,
+        };
+        let by_path = |files: &'_ [VendorArchiveFile]| -> BTreeMap<String, VendorArchiveFile> {
+            files.iter().filter(in_scope).map(|file| (file.path.clone(), file.clone())).collect()
+        };
+        let (old_files, new_files) = (by_path(&files_a.files), by_path(&files_b.files));
+        let paths: BTreeSet<&String> = old_files.keys().chain(new_files.keys()).collect();
+        
+        let mut report = VendorDiffReport {
+            epoch_a: epoch_a.to_string(),
+            epoch_b: epoch_b.to_string(),
+            package: package.map(str::to_string),
+            changes: Vec::new(),
+            unchanged: 0,
+        };
+        
+        // Checksums of epochs pinned with different digests are not comparable
+        let same_contents = |old: &VendorArchiveFile, new: &VendorArchiveFile| match files_a.digest == files_b.digest {
+            true => old.checksum == new.checksum,
+            false => {
+                let contents_a = Self::read_epoch_object(project, &files_a.digest, &old.checksum);
+                contents_a.is_some() && contents_a == Self::read_epoch_object(project, &files_b.digest, &new.checksum)
+            },
+        };
+        
+        for path in paths {
+            let (old, new) = (old_files.get(path), new_files.get(path));
+            let kind = match (old, new) {
+                (Some(old), Some(new)) if same_contents(old, new) && old.executable == new.executable => {
+                    report.unchanged += 1;
+                    continue;
+                },
+                (Some(_), Some(_)) => VendorFileChangeKind::Modified,
+                (None, Some(_)) => VendorFileChangeKind::Added,
+                (Some(_), None) => VendorFileChangeKind::Removed,
+                (None, None) => continue,
+            };
+            
+            let sha256_a = old.map(|file| file.checksum.clone());
+            let sha256_b = new.map(|file| file.checksum.clone());
+            let unified_diff = if unified {
+                Self::unified_file_diff(project, path, (&files_a.digest, sha256_a.as_deref()), (&files_b.digest, sha256_b.as_deref()))
+            } else {
+                None
+            };
+            
+            report.changes.push(VendorFileChange {
+                path: path.clone(),
+                kind,
+                sha256_a,
+                sha256_b,
+                unified_diff,
+            });
+        }
+        
+        Ok(report)
+    }
+    
+    /// Vendor snapshots of all epochs, oldest first
+    ///
+    /// Epoch directories without a readable snapshot are skipped.
+    pub fn list_epochs(&self, project: &Project) -> Result<Vec<VendorSnapshot>> {
+        let entries = match std::fs::read_dir(project.epochs_path()) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(Vec::new()),
+        };
+        
+        let mut snapshots = Vec::new();
+        for entry in entries.flatten() {
+            let snapshot_path = entry.path().join(EPOCH_SNAPSHOT_FILE);
+            if !snapshot_path.is_file() {
+                continue;
+            }
+            
+            atomic_write::recover_partial_writes(&snapshot_path);
+            let snapshot = std::fs::read_to_string(&snapshot_path).ok()
+                .and_then(|content| serde_json::from_str::<VendorSnapshot>(&content).ok());
+            match snapshot {
+                Some(snapshot) if entry.file_name().to_str() == Some(snapshot.epoch_id.as_str()) => snapshots.push(snapshot),
+                _ => tracing::warn!(path = %snapshot_path.display(), "Skipping unreadable epoch snapshot"),
+            }
+        }
+        snapshots.sort_by(|a, b| (&a.created_at, &a.epoch_id).cmp(&(&b.created_at, &b.epoch_id)));
+        
+        Ok(snapshots)
+    }
+    
+    /// Packages pinned by an epoch
+    ///
+    /// Read from the checksums manifest archived with the epoch's vendored
+    /// files, so no vendor directory is needed.
+    pub fn epoch_packages(&self, project: &Project, epoch_id: &str) -> Result<VendorChecksumManifest> {
+        let invalidated = |reason: String| crate::AdapterError::EpochInvalidated {
+            epoch_id: epoch_id.to_string(),
+            reason,
+            source: anyhow::anyhow!("Epoch packages unavailable"),
+        };
+        
+        let files = Self::load_epoch_files(project, epoch_id)?;
+        let file = files.files.iter()
+            .find(|file| file.path == VENDOR_MANIFEST_FILE)
+            .ok_or_else(|| invalidated(format!("Epoch does not archive {}", VENDOR_MANIFEST_FILE)))?;
+        let contents = Self::read_epoch_object(project, &files.digest, &file.checksum)
+            .ok_or_else(|| invalidated(format!("{} is missing from the epoch object store", VENDOR_MANIFEST_FILE)))?;
+        
+        serde_json::from_slice(&contents)
+            .map_err(|e| invalidated(format!("Archived {} is invalid: {}", VENDOR_MANIFEST_FILE, e)))
+    }
+    
+    /// Manifest of the sources an artifact mirror needs to rebuild an epoch
+    ///
+    /// Built from the epoch's archived package list, so it covers exactly
+    /// what the epoch pinned rather than the current Cargo.lock.
+    pub fn mirror_manifest(&self, project: &Project, epoch_id: &str) -> Result<MirrorManifest> {
+        let packages = self.epoch_packages(project, epoch_id)?;
+        let lockfile_fingerprint = self.list_epochs(project)?.into_iter()
+            .find(|snapshot| snapshot.epoch_id == epoch_id)
+            .map(|snapshot| snapshot.lockfile_fingerprint)
+            .unwrap_or_default();
+        
+        let mut manifest = MirrorManifest {
+            epoch_id: epoch_id.to_string(),
+            lockfile_fingerprint,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            packages: Vec::new(),
+            unsupported: Vec::new(),
+        };
+        for entry in packages.packages.values() {
+            match Self::mirror_source(entry, &self.config.registry_downloads) {
+                Some(source) => manifest.packages.push(MirrorEntry {
+                    name: entry.name.clone(),
+                    version: entry.version.clone(),
+                    source,
+                }),
+                None => manifest.unsupported.push(format!("{}@{}: {}",
+                    entry.name, entry.version, entry.source.as_deref().unwrap_or("-"))),
+            }
+        }
+        
+        Ok(manifest)
+    }
+    
+    /// Fetchable source of a locked package, if its source kind is supported
+    fn mirror_source(entry: &VendorManifestEntry, downloads: &HashMap<String, String>) -> Option<MirrorSource> {
+        let source = entry.source.as_deref()?;
+        
+        if let Some(git) = source.strip_prefix("git+") {
+            let (location, rev) = git.split_once('#')?;
+            let (url, query) = location.split_once('?').unwrap_or((location, ""));
+            let reference = query.split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(name, _)| matches!(*name, "branch" | "tag" | "rev"))
+                .map(|(_, value)| value.to_string());
+            return Some(MirrorSource::Git { url: url.to_string(), rev: rev.to_string(), reference });
+        }
+        
+        let index = match source.strip_prefix("registry+") {
+            Some(url) => url,
+            None if source.starts_with("sparse+") => source,
+            None => return None,
+        };
+        let template = if source.contains("crates.io-index") || source == "sparse+https://index.crates.io/" {
+            Some(CRATES_IO_DOWNLOAD)
+        } else {
+            downloads.get(index).map(String::as_str)
+        };
+        
+        Some(MirrorSource::Registry {
+            index: index.to_string(),
+            download_url: template.map(|template| Self::download_url(template, entry)),
+            checksum: entry.checksum.clone(),
+        })
+    }
+    
+    /// Expand a registry download URL template for a package
+    ///
+    /// Follows Cargo: a template without markers gets
+    /// `/{crate}/{version}/download` appended.
+    fn download_url(template: &str, entry: &VendorManifestEntry) -> String {
+        let name = entry.name.as_str();
+        if !DOWNLOAD_MARKERS.iter().any(|marker| template.contains(marker)) {
+            return format!("{}/{}/{}/download", template.trim_end_matches('/'), name, entry.version);
+        }
+        
+        let prefix = match name.len() {
+            1 => "1".to_string(),
+            2 => "2".to_string(),
+            3 => format!("3/{}", &name[..1]),
+            _ => format!("{}/{}", &name[..2], &name[2..4]),
+        };
+        template
+            .replace("{crate}", name)
+            .replace("{version}", &entry.version)
+            .replace("{lowerprefix}", &prefix.to_lowercase())
+            .replace("{prefix}", &prefix)
+            .replace("{sha256-checksum}", entry.checksum.as_deref().unwrap_or_default())
+    }
+    
+    /// Packages pinned by the current Cargo.lock
+    pub fn locked_packages(&self, project: &Project) -> Result<VendorChecksumManifest> {
+        Ok(Self::build_manifest(&Self::load_locked_packages(project)?))
+    }
+    
+    /// Packages pinned by Cargo.lock content, outside any project
+    ///
+    /// `lockfile_path` only names the lockfile in errors.
+    pub fn lockfile_packages(&self, lockfile_path: &Path, lockfile_content: &str) -> Result<VendorChecksumManifest> {
+        Ok(Self::build_manifest(&Self::parse_locked_packages(lockfile_path, lockfile_content)?))
+    }
+    
+    /// Load the vendored file list recorded for an epoch
+    fn load_epoch_files(project: &Project, epoch_id: &str) -> Result<VendorArchiveManifest> {
+        let files_path = Self::epoch_snapshot_path(project, epoch_id)?.with_file_name(EPOCH_FILES_FILE);
+        atomic_write::recover_partial_writes(&files_path);
+        
+        let content = std::fs::read_to_string(&files_path)
+            .map_err(|_| crate::AdapterError::file_not_found(&files_path, &format!("reading vendored file list of epoch {}", epoch_id)))?;
+        let manifest: VendorArchiveManifest = serde_json::from_str(&content)
+            .map_err(|e| crate::AdapterError::MetadataParseError {
+                field: "vendor_files".to_string(),
+                value: files_path.display().to_string(),
+                source: anyhow::anyhow!("Invalid vendored file list: {}", e),
+            })?;
+        
+        if let Err(reason) = manifest.digest.validate() {
+            return Err(crate::AdapterError::EpochInvalidated {
+                epoch_id: epoch_id.to_string(),
+                reason: format!("Vendored file list digest rejected: {}", reason),
+                source: anyhow::anyhow!("Weak epoch file list digest"),
+            });
+        }
+        if manifest.compute_content_digest() != manifest.content_digest {
+            return Err(crate::AdapterError::EpochInvalidated {
+                epoch_id: epoch_id.to_string(),
+                reason: "Vendored file list does not match its content digest".to_string(),
+                source: anyhow::anyhow!("Epoch file list tampered"),
+            });
         }
+        
+        Ok(manifest)
     }
     
-    /// Check if manager is ready
-    pub fn is_ready(&self) -> bool {
-        self.ready
+    /// Copy changed vendored files into the content-addressed object store
+    ///
+    /// Files pinned unchanged by the previous epoch were stored with it, and
+    /// contents already in the store are never written twice. Returns the
+    /// number of objects written.
+    fn store_epoch_objects(
+        project: &Project,
+        vendor_dir: &Path,
+        manifest: &VendorArchiveManifest,
+        previous: Option<&VendorArchiveManifest>,
+    ) -> Result<usize> {
+        let unchanged: HashSet<(&str, &str)> = previous
+            .filter(|previous| previous.digest == manifest.digest)
+            .map(|previous| previous.files.iter().map(|file| (file.path.as_str(), file.checksum.as_str())).collect())
+            .unwrap_or_default();
+        
+        let mut stored = 0;
+        for file in &manifest.files {
+            if unchanged.contains(&(file.path.as_str(), file.checksum.as_str())) {
+                continue;
+            }
+            let object_path = Self::epoch_object_path(project, &manifest.digest, &file.checksum);
+            if object_path.is_file() {
+                continue;
+            }
+            
+            let source_path = vendor_dir.join(&file.path);
+            let contents = std::fs::read(&source_path)
+                .map_err(|_| crate::AdapterError::permission_denied(&source_path, "reading vendored file"))?;
+            if manifest.digest.digest(&contents) != file.checksum {
+                return Err(crate::AdapterError::VendorVerificationFailed {
+                    reason: format!("{} changed while the snapshot was taken", file.path),
+                    affected_packages: vec![],
+                    source: anyhow::anyhow!("Vendored file modified during snapshot"),
+                });
+            }
+            
+            if let Some(parent) = object_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|_| crate::AdapterError::permission_denied(parent, "creating epoch object store"))?;
+            }
+            atomic_write::write_atomic(&object_path, contents)?;
+            stored += 1;
+        }
+        
+        Ok(stored)
     }
     
-    /// Vendor dependencies to target directory
-    pub async fn vendor_dependencies(&self, project: &Project, target: &Path) -> Result<()> {
-        // 1. Execute cargo vendor <target_dir>
-        let output = Command::new("cargo")
-            .args(&["vendor", target.to_str().unwrap()])
-            .current_dir(&project.paths.root)
-            .output()
-            .map_err(|_| crate::AdapterError::tool_not_found("cargo"))?;
+    /// Read a stored file by checksum, verifying its contents
+    fn read_epoch_object(project: &Project, digest: &DigestConfig, checksum: &str) -> Option<Vec<u8>> {
+        if !digest.is_well_formed(checksum) {
+            return None;
+        }
+        let contents = std::fs::read(Self::epoch_object_path(project, digest, checksum)).ok()?;
+        (digest.digest(&contents) == checksum).then_some(contents)
+    }
+    
+    /// Path of a stored file in the epoch object store
+    ///
+    /// SHA-256 objects sit at the top of the store; objects of other
+    /// digests are kept apart under the digest's label.
+    fn epoch_object_path(project: &Project, digest: &DigestConfig, checksum: &str) -> PathBuf {
+        let mut store = project.epochs_path().join(EPOCH_OBJECTS_DIR);
+        if *digest != DigestConfig::SHA256_HEX {
+            store.push(digest.label());
+        }
+        let prefix = checksum.get(..2).unwrap_or(checksum);
+        store.join(prefix).join(checksum)
+    }
+    
+    /// Unified diff between two stored versions of a file
+    ///
+    /// Returns `None` when a side is missing from the object store.
+    fn unified_file_diff(
+        project: &Project,
+        path: &str,
+        (digest_a, sha256_a): (&DigestConfig, Option<&str>),
+        (digest_b, sha256_b): (&DigestConfig, Option<&str>),
+    ) -> Option<String> {
+        let read = |digest: &DigestConfig, checksum: Option<&str>| match checksum {
+            Some(checksum) => Self::read_epoch_object(project, digest, checksum),
+            None => Some(Vec::new()),
+        };
+        let (old, new) = (read(digest_a, sha256_a)?, read(digest_b, sha256_b)?);
         
-        if !output.status.success() {
-            return Err(crate::AdapterError::ToolExecutionFailed {
-                tool: "cargo vendor".to_string(),
-                exit_code: output.status.code().unwrap_or(-1),
-                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-                source: anyhow::anyhow!("cargo vendor execution failed"),
+        let old_name = if sha256_a.is_some() { format!("a/{}", path) } else { "/dev/null".to_string() };
+        let new_name = if sha256_b.is_some() { format!("b/{}", path) } else { "/dev/null".to_string() };
+        
+        match (std::str::from_utf8(&old), std::str::from_utf8(&new)) {
+            (Ok(old), Ok(new)) => Some(similar::TextDiff::from_lines(old, new)
+                .unified_diff()
+                .context_radius(3)
+                .header(&old_name, &new_name)
+                .to_string()),
+            _ => Some(format!("Binary files {} and {} differ\n", old_name, new_name)),
+        }
+    }
+    
+    /// Whether a vendored path belongs to a crate's directory
+    ///
+    /// Cargo vendors a crate as `<name>`, or as `<name>-<version>` when
+    /// several versions are vendored.
+    fn is_package_file(path: &str, package: &str) -> bool {
+        let dir = path.split('/').next().unwrap_or_default();
+        dir == package
+            || dir.strip_prefix(package)
+                .and_then(|rest| rest.strip_prefix('-'))
+                .is_some_and(|version| semver::Version::parse(version).is_ok())
+    }
+    
+    /// Path of the vendor snapshot for an epoch
+    fn epoch_snapshot_path(project: &Project, epoch_id: &str) -> Result<PathBuf> {
+        let mut components = Path::new(epoch_id).components();
+        if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+            return Err(crate::AdapterError::ConfigurationInvalid {
+                field: "epoch".to_string(),
+                value: epoch_id.to_string(),
+                reason: "Epoch ID must be a single path component".to_string(),
+                source: anyhow::anyhow!("Invalid epoch ID"),
             });
         }
         
-        // 2. Verify Cargo.lock completeness
-        self.verify_lockfile_completeness(project, target).await?;
+        Ok(project.epochs_path().join(epoch_id).join(EPOCH_SNAPSHOT_FILE))
+    }
+    
+    /// Check that the vendor directory matches its manifest and Cargo.lock
+    fn check_vendor_matches_lockfile(vendor_dir: &Path, locked: &[LockedPackage]) -> std::result::Result<(), String> {
+        let manifest = Self::load_manifest(vendor_dir)
+            .ok_or_else(|| format!("No vendor checksums manifest in {:?}", vendor_dir))?;
+        Self::check_manifest_consistency(vendor_dir, &manifest)?;
         
-        // 3. Verify checksums if enabled
-        if self.config.verify_checksums {
-            self.validate_checksums(project, target).await?;
+        let plan = Self::plan_delta(&manifest, locked)?;
+        if !plan.added.is_empty() || !plan.changed.is_empty() || !plan.removed.is_empty() {
+            return Err(format!(
+                "Vendor directory differs from Cargo.lock: {} missing, {} changed, {} extra package(s)",
+                plan.added.len(), plan.changed.len(), plan.removed.len(),
+            ));
         }
         
-        // 4. Generate .cargo/config.toml for offline builds
-        self.generate_cargo_config(target).await?;
-        
         Ok(())
     }
     
+    /// Cargo `--config` entries replacing locked sources with the vendor directory
+    ///
+    /// Mirrors the configuration printed by `cargo vendor`.
+    fn vendored_source_config(vendor_dir: &Path, locked: &[LockedPackage]) -> Vec<(String, String)> {
+        let quote = |value: &str| toml::Value::String(value.to_string()).to_string();
+        let vendored = quote(VENDORED_SOURCE_NAME);
+        
+        let mut config = vec![
+            ("source.crates-io.replace-with".to_string(), vendored.clone()),
+            (format!("source.{}.directory", VENDORED_SOURCE_NAME), quote(&config_path(vendor_dir))),
+        ];
+        
+        let sources: std::collections::BTreeSet<&str> = locked.iter()
+            .filter_map(|package| package.source.as_deref())
+            .collect();
+        
+        for source in sources {
+            let source = source.split('#').next().unwrap_or(source);
+            let key = format!("source.{}", quote(source));
+            
+            if let Some(git) = source.strip_prefix("git+") {
+                let (url, query) = git.split_once('?').unwrap_or((git, ""));
+                config.push((format!("{}.git", key), quote(url)));
+                for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+                    if matches!(name, "branch" | "tag" | "rev") {
+                        config.push((format!("{}.{}", key, name), quote(value)));
+                    }
+                }
+            } else if source.contains("crates.io-index") || source == "sparse+https://index.crates.io/" {
+                continue;
+            } else {
+                let registry = source.strip_prefix("registry+").unwrap_or(source);
+                config.push((format!("{}.registry", key), quote(registry)));
+            }
+            config.push((format!("{}.replace-with", key), vendored.clone()));
+        }
+        
+        config
+    }
+    
+    /// Collect vendored files in path order with sizes and checksums of `digest`
+    fn collect_archive_files(&self, vendor_dir: &Path, output: &Path, digest: &DigestConfig) -> Result<Vec<VendorArchiveFile>> {
+        let buffer_size = self.config.io_buffer_size.max(1);
+        let output = output.canonicalize().ok();
+        let mut files = Vec::new();
+        
+        for entry in walkdir::WalkDir::new(vendor_dir).follow_links(false).sort_by_file_name() {
+            let entry = entry.map_err(|e| Self::archive_error(vendor_dir, format!("Failed to walk vendor directory: {}", e)))?;
+            let file_type = entry.file_type();
+            
+            if file_type.is_dir() {
+                continue;
+            }
+            if !file_type.is_file() {
+                return Err(Self::archive_error(vendor_dir, format!("Unsupported non-regular file {:?}", entry.path())));
+            }
+            if output.is_some() && entry.path().canonicalize().ok() == output {
+                continue;
+            }
+            if atomic_write::is_partial_write(entry.path()) {
+                continue;
+            }
+            
+            let relative = entry.path().strip_prefix(vendor_dir).unwrap_or(entry.path());
+            let metadata = entry.metadata()
+                .map_err(|e| Self::archive_error(vendor_dir, format!("Failed to read metadata of {:?}: {}", entry.path(), e)))?;
+            
+            #[cfg(unix)]
+            let executable = std::os::unix::fs::PermissionsExt::mode(&metadata.permissions()) & 0o111 != 0;
+            #[cfg(not(unix))]
+            let executable = false;
+            
+            files.push(VendorArchiveFile {
+                path: relative.components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                size_bytes: metadata.len(),
+                checksum: Self::hash_file(entry.path(), buffer_size, digest)?,
+                executable,
+            });
+        }
+        
+        Ok(files)
+    }
+    
+    /// Append a regular file entry with normalized metadata
+    fn append_archive_entry<W: std::io::Write, R: std::io::Read>(
+        builder: &mut tar::Builder<W>,
+        path: &str,
+        executable: bool,
+        data: R,
+        size: u64,
+    ) -> std::io::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(size);
+        header.set_mode(if executable { 0o755 } else { 0o644 });
+        header.set_mtime(ARCHIVE_MTIME);
+        header.set_uid(0);
+        header.set_gid(0);
+        builder.append_data(&mut header, path, data)
+    }
+    
+    /// Extract and verify every archive entry into a staging directory
+    #[cfg(target_arch = "wasm32")]
+    fn unpack_archive(&self, archive: &Path, _staging: &Path) -> Result<VendorArchiveManifest> {
+        Err(Self::archive_error(archive, "Vendor archives are not supported on wasm32".to_string()))
+    }
+    
+    /// Extract and verify every archive entry into a staging directory
+    #[cfg(not(target_arch = "wasm32"))]
+    fn unpack_archive(&self, archive: &Path, staging: &Path) -> Result<VendorArchiveManifest> {
+        use std::io::{Read, Write};
+        
+        let read_error = |e: std::io::Error| Self::archive_error(archive, format!("Failed to read archive: {}", e));
+        
+        let file = std::fs::File::open(archive)
+            .map_err(|_| crate::AdapterError::file_not_found(archive, "reading vendor archive"))?;
+        let mut tar_archive = tar::Archive::new(zstd::Decoder::new(file).map_err(read_error)?);
+        let mut entries = tar_archive.entries().map_err(read_error)?;
+        
+        // The manifest is always the first entry
+        let mut first = entries.next()
+            .ok_or_else(|| Self::archive_error(archive, "Archive is empty".to_string()))?
+            .map_err(read_error)?;
+        if first.path().map_err(read_error)?.as_ref() != Path::new(ARCHIVE_MANIFEST_ENTRY) {
+            return Err(Self::archive_error(archive, format!("First entry is not {}", ARCHIVE_MANIFEST_ENTRY)));
+        }
+        let manifest: VendorArchiveManifest = serde_json::from_reader(&mut first)
+            .map_err(|e| Self::archive_error(archive, format!("Invalid archive manifest: {}", e)))?;
+        
+        if manifest.format_version != VendorArchiveManifest::FORMAT_VERSION {
+            return Err(Self::archive_error(archive, format!("Unsupported archive format version {}", manifest.format_version)));
+        }
+        if let Err(reason) = manifest.digest.validate() {
+            return Err(Self::archive_error(archive, format!("Archive manifest digest rejected: {}", reason)));
+        }
+        if manifest.compute_content_digest() != manifest.content_digest {
+            return Err(Self::archive_error(archive, "Archive manifest content digest does not match its file list".to_string()));
+        }
+        
+        let expected: HashMap<&str, &VendorArchiveFile> = manifest.files.iter()
+            .map(|file| (file.path.as_str(), file))
+            .collect();
+        let mut restored = HashSet::new();
+        let mut buffer = vec![0u8; self.config.io_buffer_size.max(1)];
+        
+        for entry in entries {
+            let mut entry = entry.map_err(read_error)?;
+            let entry_path = entry.path().map_err(read_error)?.into_owned();
+            
+            if !entry.header().entry_type().is_file() {
+                return Err(Self::archive_error(archive, format!("Unexpected non-file entry {:?}", entry_path)));
+            }
+            let relative = entry_path.strip_prefix(ARCHIVE_VENDOR_PREFIX)
+                .ok()
+                .filter(|relative| !relative.as_os_str().is_empty() && relative.components().all(|c| matches!(c, Component::Normal(_))))
+                .ok_or_else(|| Self::archive_error(archive, format!("Unsafe entry path {:?}", entry_path)))?;
+            let key = relative.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            
+            let recorded = *expected.get(key.as_str())
+                .ok_or_else(|| Self::archive_error(archive, format!("Entry {} is not listed in the manifest", key)))?;
+            if !restored.insert(key.clone()) {
+                return Err(Self::archive_error(archive, format!("Duplicate entry {}", key)));
+            }
+            
+            let output_path = staging.join(relative);
+            if let Some(parent) = output_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|_| crate::AdapterError::permission_denied(parent, "creating restore directory"))?;
+            }
+            let mut output = std::fs::File::create(&output_path)
+                .map_err(|_| crate::AdapterError::permission_denied(&output_path, "writing restored file"))?;
+            
+            let mut hasher = manifest.digest.hasher();
+            let mut size = 0u64;
+            loop {
+                let read = entry.read(&mut buffer).map_err(read_error)?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+                output.write_all(&buffer[..read])
+                    .map_err(|_| crate::AdapterError::permission_denied(&output_path, "writing restored file"))?;
+                size += read as u64;
+            }
+            
+            let actual = manifest.digest.finish(hasher);
+            if size != recorded.size_bytes || actual != recorded.checksum {
+                return Err(crate::AdapterError::checksum_mismatch(&key, &recorded.checksum, &actual));
+            }
+            
+            #[cfg(unix)]
+            if recorded.executable {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&output_path, std::fs::Permissions::from_mode(0o755))
+                    .map_err(|_| crate::AdapterError::permission_denied(&output_path, "setting file permissions"))?;
+            }
+        }
+        
+        let missing: Vec<_> = manifest.files.iter()
+            .filter(|file| !restored.contains(&file.path))
+            .map(|file| file.path.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(Self::archive_error(archive, format!("Archive is missing {} file(s) listed in the manifest: {}", missing.len(), missing.join(", "))));
+        }
+        
+        Ok(manifest)
+    }
+    
+    /// Path of the digest file for an archive, named after the algorithm
+    fn archive_digest_path(archive: &Path, algorithm: ChecksumAlgorithm) -> PathBuf {
+        let mut name = archive.as_os_str().to_os_string();
+        name.push(".");
+        name.push(algorithm.as_str());
+        PathBuf::from(name)
+    }
+    
+    /// Calculate the digest of a file using streamed reads
+    fn hash_file(path: &Path, buffer_size: usize, digest: &DigestConfig) -> Result<String> {
+        let file = std::fs::File::open(path)
+            .map_err(|_| crate::AdapterError::file_not_found(path, "reading file for checksum"))?;
+        digest.digest_reader(file, buffer_size)
+            .map_err(|_| crate::AdapterError::permission_denied(path, "reading file for checksum"))
+    }
+    
+    /// Build a vendor archive error
+    fn archive_error(path: &Path, reason: String) -> crate::AdapterError {
+        crate::AdapterError::VendorVerificationFailed {
+            reason,
+            affected_packages: vec![],
+            source: anyhow::anyhow!("Vendor archive {:?} failed verification", path),
+        }
+    }
+    
     /// Verify vendored dependencies
     pub async fn verify_vendored(&self, project: &Project, vendored: &Path) -> Result<VerificationReport> {
         let mut report = VerificationReport::new();
         
         // 1. Check vendor directory structure
+        deadline::set_stage("checking vendor structure");
         report.structure_valid = self.verify_vendor_structure(vendored).await?;
         
         // 2. Verify checksums
@@ -101,16 +1903,40 @@ impl VendorManager {
         }
         
         // 3. Verify Cargo.lock completeness
+        deadline::set_stage("checking Cargo.lock completeness");
         let missing_deps = self.check_missing_dependencies(project, vendored).await?;
         for dep in missing_deps {
             report.add_missing_dependency(dep);
         }
         
-        // 4. Validate Cargo configuration
+        // 4. Verify path dependencies, which are not vendored
+        if self.config.verify_local_deps {
+            deadline::set_stage("verifying local dependencies");
+            report.local_dependency_issues = local_deps::verify(project)?.issues;
+        }
+        
+        // 5. Validate Cargo configuration
+        deadline::set_stage("validating cargo config");
         report.config_valid = self.validate_cargo_config(vendored).await?;
         
-        // 5. Determine verification result
+        // 6. Scan vendored sources for embedded secrets
+        if self.config.secret_scan.enabled {
+            deadline::set_stage("scanning for secrets");
+            report.secret_findings = SecretScanner::new(&self.config.secret_scan).scan(vendored)?;
+        }
+        
+        // 7. Determine verification result
         report.determine_result();
+        if self.config.secret_scan.fail_on_findings && !report.secret_findings.is_empty() {
+            report.result = VerificationResult::Failed;
+        }
+        
+        let subject = vendored.strip_prefix(&project.paths.root).unwrap_or(vendored);
+        self.record_event(project, LogEvent::Verification {
+            subject: subject.display().to_string(),
+            passed: report.is_success(),
+            detail: (!report.is_success()).then(|| format!("{:?}", report.result)),
+        });
         
         Ok(report)
     }
@@ -120,15 +1946,10 @@ impl VendorManager {
         // This would check that all packages listed in Cargo.lock
         // have corresponding directories in vendor/
         
-        let lockfile_path = project.lockfile_path();
-        let lockfile_content = std::fs::read_to_string(&lockfile_path)
-            .map_err(|e| crate::AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
-        
-        let cargo_lock: CargoLock = toml::from_str(&lockfile_content)
-            .map_err(|e| crate::AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
+        let locked = Self::load_locked_packages(project)?;
         
-        for package in &cargo_lock.package {
-            let vendor_package_path = vendor_dir.join(&package.name);
+        for package in Self::build_manifest(&locked).packages.into_values() {
+            let vendor_package_path = vendor_dir.join(&package.path);
             if !vendor_package_path.exists() {
                 return Err(crate::AdapterError::VendorVerificationFailed {
                     reason: format!("Missing vendored package: {}", package.name),
@@ -146,53 +1967,109 @@ impl VendorManager {
         // This would calculate SHA256 hashes of vendored packages
         // and compare them against Cargo.lock checksums
         
-        let lockfile_path = project.lockfile_path();
-        let lockfile_content = std::fs::read_to_string(&lockfile_path)
-            .map_err(|e| crate::AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
+        let locked = Self::load_locked_packages(project)?;
+        let checksummed: Vec<_> = locked.iter()
+            .filter(|package| package.checksum.is_some())
+            .collect();
+        let names: Vec<String> = checksummed.iter().map(|package| package.name.clone()).collect();
+        deadline::set_stage("hashing packages");
+        deadline::set_total(names.len());
+        let actual_checksums = self.calculate_package_checksums(vendor_dir, &names).await?;
         
-        let cargo_lock: CargoLock = toml::from_str(&lockfile_content)
-            .map_err(|e| crate::AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
-        
-        for package in &cargo_lock.package {
-            if let Some(expected_checksum) = &package.checksum {
-                let actual_checksum = self.calculate_package_checksum(vendor_dir, &package.name).await?;
-                
-                if actual_checksum != *expected_checksum {
-                    return Err(crate::AdapterError::checksum_mismatch(
-                        &package.name,
-                        expected_checksum,
-                        &actual_checksum,
-                    ));
-                }
+        for (package, actual_checksum) in checksummed.into_iter().zip(actual_checksums) {
+            let actual_checksum = actual_checksum?;
+            let expected_checksum = package.checksum.as_deref().unwrap_or_default();
+            
+            if actual_checksum != expected_checksum {
+                return Err(crate::AdapterError::checksum_mismatch(
+                    &package.name,
+                    expected_checksum,
+                    &actual_checksum,
+                ));
             }
         }
         
         Ok(())
     }
     
-    /// Calculate checksum of vendored package
-    async fn calculate_package_checksum(&self, vendor_dir: &Path, package_name: &str) -> Result<String> {
-        use sha2::{Digest, Sha256};
-        use std::fs;
+    /// Calculate checksums of several vendored packages
+    ///
+    /// Packages are hashed in batches of at most `max_hashing_threads` on the
+    /// blocking thread pool, and results are returned in the same order as
+    /// `package_names`. Fails only if the operation deadline runs out
+    /// between batches.
+    async fn calculate_package_checksums(&self, vendor_dir: &Path, package_names: &[String]) -> Result<Vec<Result<String>>> {
+        let buffer_size = self.config.io_buffer_size.max(1);
+        let mut checksums = Vec::with_capacity(package_names.len());
         
-        let package_path = vendor_dir.join(package_name);
+        for batch in package_names.chunks(self.config.max_hashing_threads.max(1)) {
+            deadline::check()?;
+            let vendor_dir = vendor_dir.to_path_buf();
+            let batch = batch.to_vec();
+            let hashed = tokio::task::spawn_blocking(move || Self::hash_package_batch(&vendor_dir, &batch, buffer_size))
+                .await
+                .map_err(|e| crate::AdapterError::VendorVerificationFailed {
+                    reason: format!("Checksum calculation task failed: {}", e),
+                    affected_packages: Vec::new(),
+                    source: anyhow::anyhow!("Hashing task did not complete"),
+                })?;
+            deadline::advance(hashed.len());
+            checksums.extend(hashed);
+        }
+        
+        Ok(checksums)
+    }
+    
+    /// Hash one batch of vendored packages on scoped threads
+    fn hash_package_batch(vendor_dir: &Path, batch: &[String], buffer_size: usize) -> Vec<Result<String>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = batch.iter()
+                .map(|name| scope.spawn(move || Self::hash_package_dir(&vendor_dir.join(name), buffer_size)))
+                .collect();
+            
+            handles.into_iter().zip(batch).map(|(handle, name)| {
+                handle.join().unwrap_or_else(|_| Err(crate::AdapterError::VendorVerificationFailed {
+                    reason: format!("Checksum calculation panicked for package: {}", name),
+                    affected_packages: vec![name.clone()],
+                    source: anyhow::anyhow!("Hashing thread panicked"),
+                }))
+            }).collect()
+        })
+    }
+    
+    /// Calculate checksum of a vendored package directory
+    ///
+    /// Compared with Cargo.lock checksums, so always uses their algorithm.
+    fn hash_package_dir(package_path: &std::path::PathBuf, buffer_size: usize) -> Result<String> {
+        use std::fs;
+        use std::io::Read;
         
         // Simple checksum calculation of package directory
-        let mut hasher = Sha256::new();
+        let digest = DigestConfig::hex(LOCKFILE_CHECKSUM_ALGORITHM);
+        let mut hasher = digest.hasher();
+        let mut buffer = vec![0u8; buffer_size];
         
-        let walk_dir = fs::read_dir(&package_path)
-            .map_err(|e| crate::AdapterError::permission_denied(&package_path, "reading package directory"))?;
+        let walk_dir = fs::read_dir(package_path)
+            .map_err(|_e| crate::AdapterError::permission_denied(package_path, "reading package directory"))?;
         
         for entry in walk_dir.flatten() {
             let path = entry.path();
             if path.is_file() {
-                let contents = fs::read(&path)
-                    .map_err(|e| crate::AdapterError::permission_denied(&path, "reading file"))?;
-                hasher.update(&contents);
+                let mut file = fs::File::open(&path)
+                    .map_err(|_e| crate::AdapterError::permission_denied(&path, "reading file"))?;
+                
+                loop {
+                    let read = file.read(&mut buffer)
+                        .map_err(|_e| crate::AdapterError::permission_denied(&path, "reading file"))?;
+                    if read == 0 {
+                        break;
+                    }
+                    hasher.update(&buffer[..read]);
+                }
             }
         }
         
-        Ok(format!("{:x}", hasher.finalize()))
+        Ok(digest.finish(hasher))
     }
     
     /// Generate .cargo/config.toml for offline builds
@@ -202,19 +2079,19 @@ impl VendorManager {
         
         // Create .cargo directory if it doesn't exist
         std::fs::create_dir_all(&cargo_config_dir)
-            .map_err(|e| crate::AdapterError::permission_denied(&cargo_config_dir, "creating .cargo directory"))?;
+            .map_err(|_e| crate::AdapterError::permission_denied(&cargo_config_dir, "creating .cargo directory"))?;
         
-        // Generate config.toml content
+        // Generate config.toml content; the path is quoted as a TOML string
+        let directory = toml::Value::String(config_path(vendor_dir));
         let config_content = format!(r#"
 [source.crates-io]
 replace-with = "vendored-sources"
 
 [source.vendored-sources]
-directory = "{}"
-"#, vendor_dir.parent().unwrap_or(vendor_dir).display());
+directory = {}
+"#, directory);
         
-        std::fs::write(&cargo_config_path, config_content)
-            .map_err(|e| crate::AdapterError::permission_denied(&cargo_config_path, "writing cargo config"))?;
+        atomic_write::write_atomic(&cargo_config_path, config_content)?;
         
         Ok(())
     }
@@ -234,57 +2111,105 @@ directory = "{}"
     }
     
     /// Check for missing dependencies
+    ///
+    /// Lock entries without a source are workspace members or path
+    /// dependencies, which `cargo vendor` never copies, so they are not
+    /// expected in the vendor directory. Registry and git packages are,
+    /// even when they share a name with a workspace member.
     async fn check_missing_dependencies(&self, project: &Project, vendor_dir: &Path) -> Result<Vec<String>> {
-        let mut missing = Vec::new();
-        
-        let lockfile_path = project.lockfile_path();
-        let lockfile_content = std::fs::read_to_string(&lockfile_path)
-            .map_err(|e| crate::AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
-        
-        let cargo_lock: CargoLock = toml::from_str(&lockfile_content)
-            .map_err(|e| crate::AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
-        
-        for package in &cargo_lock.package {
-            let vendor_package_path = vendor_dir.join(&package.name);
-            if !vendor_package_path.exists() {
-                missing.push(package.name.clone());
-            }
-        }
+        let locked = Self::load_locked_packages(project)?;
         
-        Ok(missing)
+        Ok(Self::build_manifest(&locked).packages.into_values()
+            .filter(|entry| !vendor_dir.join(&entry.path).exists())
+            .map(|entry| entry.name)
+            .collect())
     }
     
     /// Verify checksums against lockfile
+    ///
+    /// Verified packages are saved to the project's verification progress
+    /// in the shared cache every few batches. Unless resuming is disabled,
+    /// packages an interrupted run already verified are skipped if their
+    /// files are unchanged. The progress is cleared once all packages are
+    /// checked. Without a shared cache no progress is kept.
     async fn verify_checksums_against_lockfile(&self, project: &Project, vendor_dir: &Path) -> Result<Vec<ChecksumMismatch>> {
         let mut mismatches = Vec::new();
         
-        let lockfile_path = project.lockfile_path();
-        let lockfile_content = std::fs::read_to_string(&lockfile_path)
-            .map_err(|e| crate::AdapterError::file_not_found(&lockfile_path, "reading lockfile"))?;
+        let locked = Self::load_locked_packages(project)?;
+        let checksummed: Vec<_> = locked.iter()
+            .filter(|package| package.checksum.is_some())
+            .collect();
+        
+        let lockfile_fingerprint = ChecksumCalculator::new()
+            .calculate_lockfile_fingerprint(project.lockfile_path())
+            .unwrap_or_default();
+        let store = self.config.progress_dir.as_ref().and_then(|dir| match ProgressKey::load_or_create(dir) {
+            Ok(key) => Some((VerifyProgress::path(dir, &project.paths.root), key)),
+            Err(e) => {
+                tracing::warn!(error = %e, "Verification progress unavailable");
+                None
+            },
+        });
+        let mut progress = store.as_ref().map(|(path, key)| match self.config.resume_verification {
+            true => VerifyProgress::load(path, vendor_dir, &lockfile_fingerprint, key.clone()),
+            false => VerifyProgress::new(vendor_dir, &lockfile_fingerprint, key.clone()),
+        });
         
-        let cargo_lock: CargoLock = toml::from_str(&lockfile_content)
-            .map_err(|e| crate::AdapterError::cargo_lock_parse_error(&lockfile_path, 0, &e.to_string()))?;
+        let total = checksummed.len();
+        let pending: Vec<_> = checksummed.into_iter()
+            .filter(|package| {
+                let expected_checksum = package.checksum.as_deref().unwrap_or_default();
+                !progress.as_ref().is_some_and(|progress| progress.is_verified(&package.name, &vendor_dir.join(&package.name), expected_checksum))
+            })
+            .collect();
+        deadline::set_stage("verifying package checksums");
+        deadline::set_total(total);
+        deadline::advance(total - pending.len());
+        if pending.len() < total {
+            tracing::info!(skipped = total - pending.len(), remaining = pending.len(), "Skipping packages verified by an interrupted run");
+        }
         
-        for package in &cargo_lock.package {
-            if let Some(expected_checksum) = &package.checksum {
-                let actual_checksum = self.calculate_package_checksum(vendor_dir, &package.name).await?;
+        for chunk in pending.chunks(VERIFY_PROGRESS_INTERVAL) {
+            if self.cancel.is_cancelled() {
+                return Err(crate::AdapterError::cancelled("vendor verification"));
+            }
+            
+            let names: Vec<String> = chunk.iter().map(|package| package.name.clone()).collect();
+            let actual_checksums = self.calculate_package_checksums(vendor_dir, &names).await?;
+            
+            for (package, actual_checksum) in chunk.iter().zip(actual_checksums) {
+                let actual_checksum = actual_checksum?;
+                let expected_checksum = package.checksum.clone().unwrap_or_default();
                 
-                if actual_checksum != *expected_checksum {
+                if actual_checksum == expected_checksum {
+                    if let Some(progress) = &mut progress {
+                        progress.mark_verified(&package.name, &vendor_dir.join(&package.name), &actual_checksum);
+                    }
+                } else {
                     mismatches.push(ChecksumMismatch::new(
                         package.name.clone(),
-                        expected_checksum.clone(),
+                        expected_checksum,
                         actual_checksum,
                     ).with_severity(crate::models::vendor_types::ErrorSeverity::Critical));
                 }
             }
+            
+            if let (Some((path, _)), Some(progress)) = (&store, &progress) {
+                if let Err(e) = progress.save(path) {
+                    tracing::warn!(error = %e, "Failed to save verification progress");
+                }
+            }
         }
         
+        if let Some((path, _)) = &store {
+            VerifyProgress::clear(path);
+        }
         Ok(mismatches)
     }
     
     /// Validate Cargo configuration
     async fn validate_cargo_config(&self, vendor_dir: &Path) -> Result<bool> {
-        let cargo_config_path = vendor_dir.join(".cargo/config.toml");
+        let cargo_config_path = vendor_dir.join(".cargo").join("config.toml");
         
         if !cargo_config_path.exists() {
             return Ok(false);
@@ -292,11 +2217,9 @@ directory = "{}"
         
         // Basic validation - check if file can be parsed
         let config_content = std::fs::read_to_string(&cargo_config_path)
-            .map_err(|e| crate::AdapterError::file_not_found(&cargo_config_path, "reading cargo config"))?;
+            .map_err(|_| crate::AdapterError::file_not_found(&cargo_config_path, "reading cargo config"))?;
         
-        toml::from_str::<serde_json::Value>(&config_content)
-            .map(|_| true)
-            .map_err(|_| false)
+        Ok(toml::from_str::<toml::Value>(&config_content).is_ok())
     }
 }
 
@@ -308,6 +2231,16 @@ impl Default for VendorManagerConfig {
             verify_checksums: true,
             malware_scan: false,
             compare_fresh: false,
+            max_hashing_threads: 4,
+            io_buffer_size: 64 * 1024,
+            delta_vendoring: false,
+            resume_verification: true,
+            progress_dir: None,
+            transparency_log: TransparencyLogConfig::default(),
+            secret_scan: SecretScanConfig::default(),
+            registry_downloads: HashMap::new(),
+            verify_local_deps: true,
+            digest: DigestConfig::default(),
         }
     }
 }
@@ -336,4 +2269,527 @@ mod tests {
         // For now, we'll test the basic functionality
         assert!(manager.is_ready());
     }
+    
+    #[tokio::test]
+    async fn test_cargo_vendor_failure_is_reported() {
+        let project_dir = tempfile::tempdir().unwrap();
+        let project = Project::new("test".to_string(), "Test".to_string(), "rust".to_string(), project_dir.path().to_path_buf());
+        let target = project_dir.path().join("vendor");
+        
+        let tools = crate::utils::ReplayInvoker::new().with_output(
+            &format!("cargo vendor {}", target.display()),
+            crate::utils::ToolOutput::failure(101, "error: failed to sync"),
+        );
+        let manager = VendorManager::new(&RustAdapterConfig::default()).with_tool_invoker(Arc::new(tools));
+        
+        match manager.vendor_dependencies_full(&project, &target).await {
+            Err(crate::AdapterError::ToolExecutionFailed { exit_code, stderr, .. }) => {
+                assert_eq!(exit_code, 101);
+                assert_eq!(stderr, "error: failed to sync");
+            },
+            other => panic!("Expected ToolExecutionFailed, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_bounded_parallel_hashing_preserves_order() {
+        let vendor_dir = tempfile::tempdir().unwrap();
+        let names: Vec<String> = (0..5).map(|i| format!("pkg{}", i)).collect();
+        for name in &names {
+            let package_dir = vendor_dir.path().join(name);
+            std::fs::create_dir_all(&package_dir).unwrap();
+            std::fs::write(package_dir.join("lib.rs"), name.repeat(100)).unwrap();
+        }
+        
+        let mut config = RustAdapterConfig::default();
+        config.performance_config.max_hashing_threads = 2;
+        config.performance_config.io_buffer_size = 7;
+        let manager = VendorManager::new(&config);
+        
+        let checksums = manager.calculate_package_checksums(vendor_dir.path(), &names).await.unwrap();
+        assert_eq!(checksums.len(), names.len());
+        for (name, checksum) in names.iter().zip(&checksums) {
+            let expected = VendorManager::hash_package_dir(&vendor_dir.path().join(name), 1024).unwrap();
+            assert_eq!(checksum.as_ref().unwrap(), &expected);
+        }
+        assert_ne!(checksums[0].as_ref().unwrap(), checksums[1].as_ref().unwrap());
+    }
+    
+    fn locked(name: &str, version: &str, checksum: Option<&str>) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: Some(match checksum {
+                Some(_) => "registry+https://github.com/rust-lang/crates.io-index".to_string(),
+                None => format!("git+https://github.com/example/{}#abc123", name),
+            }),
+            checksum: checksum.map(str::to_string),
+        }
+    }
+    
+    #[test]
+    fn test_delta_plan() {
+        let previous = VendorManager::build_manifest(&[
+            locked("itoa", "1.0.9", Some("aa")),
+            locked("ryu", "1.0.15", Some("bb")),
+            locked("syn", "1.0.109", Some("cc")),
+            locked("syn", "2.0.38", Some("dd")),
+        ]);
+        assert_eq!(previous.packages["syn@1.0.109"].path, PathBuf::from("syn-1.0.109"));
+        assert_eq!(previous.packages["syn@2.0.38"].path, PathBuf::from("syn"));
+        
+        let plan = VendorManager::plan_delta(&previous, &[
+            locked("itoa", "1.0.9", Some("aa")),
+            locked("ryu", "1.0.16", Some("ee")),
+            locked("syn", "1.0.109", Some("cc")),
+            locked("syn", "2.0.38", Some("ff")),
+        ]).unwrap();
+        
+        assert_eq!(plan.unchanged, 2);
+        assert_eq!(plan.added.len(), 1);
+        assert_eq!(plan.added[0].version, "1.0.16");
+        assert_eq!(plan.changed.len(), 1);
+        assert_eq!(plan.changed[0].name, "syn");
+        assert_eq!(plan.removed.len(), 1);
+        assert_eq!(plan.removed[0].version, "1.0.15");
+        
+        // A newer syn would rename the existing 2.x directory
+        assert!(VendorManager::plan_delta(&previous, &[
+            locked("syn", "2.0.38", Some("dd")),
+            locked("syn", "2.1.0", Some("gg")),
+        ]).is_err());
+        
+        // Git dependencies are only vendored by a full run
+        assert!(VendorManager::plan_delta(&previous, &[
+            locked("itoa", "1.0.9", Some("aa")),
+            locked("private", "0.1.0", None),
+        ]).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_delta_vendoring_validates_checksums() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n").unwrap();
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() { evil() }\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa").join(CARGO_CHECKSUM_FILE), r#"{"files":{},"package":"aa"}"#).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[locked("itoa", "1.0.9", Some("aa"))])).unwrap();
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        
+        // Nothing to re-vendor, but the vendored contents still have to match Cargo.lock
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        assert!(matches!(
+            manager.vendor_dependencies_delta(&project, &vendor_dir).await,
+            Err(crate::AdapterError::ChecksumMismatch { .. }),
+        ));
+        
+        let mut config = RustAdapterConfig::default();
+        config.vendor_config.verify_checksums = false;
+        let report = VendorManager::new(&config).vendor_dependencies_delta(&project, &vendor_dir).await.unwrap();
+        assert!(!report.full_vendor);
+        assert_eq!(report.unchanged, 1);
+    }
+    
+    #[test]
+    fn test_extract_crate_writes_cargo_checksums() {
+        use sha2::{Digest, Sha256};
+        
+        let dir = tempfile::tempdir().unwrap();
+        let crate_file = dir.path().join("demo-0.1.0.crate");
+        
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        for (path, contents) in [("demo-0.1.0/Cargo.toml", "[package]\nname = \"demo\"\n"), ("demo-0.1.0/src/lib.rs", "pub fn demo() {}\n"), ("demo-0.1.0/.cargo-ok", "ok")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, contents.as_bytes()).unwrap();
+        }
+        let data = builder.into_inner().unwrap().finish().unwrap();
+        std::fs::write(&crate_file, &data).unwrap();
+        let checksum = format!("{:x}", Sha256::digest(&data));
+        
+        let package_dir = dir.path().join("vendor").join("demo");
+        assert!(VendorManager::extract_crate(&crate_file, &package_dir, "demo", "0000").is_err());
+        VendorManager::extract_crate(&crate_file, &package_dir, "demo", &checksum).unwrap();
+        
+        assert!(package_dir.join("src/lib.rs").is_file());
+        assert!(!package_dir.join(".cargo-ok").exists());
+        
+        let recorded: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(package_dir.join(CARGO_CHECKSUM_FILE)).unwrap()
+        ).unwrap();
+        assert_eq!(recorded["package"], serde_json::json!(checksum));
+        assert!(recorded["files"].get("src/lib.rs").is_some());
+        assert_eq!(recorded["files"].as_object().unwrap().len(), 2);
+    }
+    
+    #[test]
+    fn test_archive_is_reproducible_and_restores() {
+        let dir = tempfile::tempdir().unwrap();
+        let vendor_dir = dir.path().join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa/src")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/src/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa/Cargo.toml"), "[package]\nname = \"itoa\"\n").unwrap();
+        std::fs::write(vendor_dir.join(VENDOR_MANIFEST_FILE), "{}").unwrap();
+        
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        let first = manager.archive_vendor(&vendor_dir, &dir.path().join("vendor-a.tar.zst"), Some("epoch-1")).unwrap();
+        let second = manager.archive_vendor(&vendor_dir, &dir.path().join("vendor-b.tar.zst"), Some("epoch-1")).unwrap();
+        
+        assert_eq!(first.archive_digest, second.archive_digest);
+        assert_eq!(first.manifest.total_files, 3);
+        assert_eq!(first.manifest.files[0].path, VENDOR_MANIFEST_FILE);
+        assert!(std::fs::read_to_string(&first.digest_path).unwrap().ends_with("  vendor-a.tar.zst\n"));
+        
+        let restored = dir.path().join("restored");
+        let manifest = manager.restore_vendor(&first.archive_path, &restored).unwrap();
+        assert_eq!(manifest, first.manifest);
+        assert_eq!(std::fs::read_to_string(restored.join("itoa/src/lib.rs")).unwrap(), "pub fn itoa() {}\n");
+        
+        // Existing targets are never overwritten
+        assert!(manager.restore_vendor(&first.archive_path, &restored).is_err());
+        
+        // A tampered archive fails the digest check
+        let mut bytes = std::fs::read(&first.archive_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        std::fs::write(&first.archive_path, bytes).unwrap();
+        assert!(manager.restore_vendor(&first.archive_path, &dir.path().join("tampered")).is_err());
+        assert!(!dir.path().join("tampered").exists());
+        
+        // Archives digested with a weak algorithm are not trusted
+        let config = RustAdapterConfig { digest_config: DigestConfig::hex(ChecksumAlgorithm::Md5), ..RustAdapterConfig::default() };
+        let weak = VendorManager::new(&config);
+        let archived = weak.archive_vendor(&vendor_dir, &dir.path().join("vendor-md5.tar.zst"), None).unwrap();
+        assert!(weak.restore_vendor(&archived.archive_path, &dir.path().join("weak")).is_err());
+        assert!(!dir.path().join("weak").exists());
+    }
+    
+    #[test]
+    fn test_epoch_snapshot_fails_closed_on_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lockfile = "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n";
+        std::fs::write(root.join("Cargo.lock"), lockfile).unwrap();
+        
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa").join(CARGO_CHECKSUM_FILE), r#"{"files":{},"package":"aa"}"#).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[locked("itoa", "1.0.9", Some("aa"))])).unwrap();
+        
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        
+        assert!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").is_err());
+        assert!(manager.snapshot_vendor(&project, &vendor_dir, "../escape", &[]).is_err());
+        
+        let snapshot = manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &[]).unwrap();
+        assert_eq!(snapshot.storage_path, PathBuf::from("vendor"));
+        assert_eq!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").unwrap().content_digest, snapshot.content_digest);
+        
+        // Tampered vendored source
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() { evil() }\n").unwrap();
+        assert!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").is_err());
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        assert!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").is_ok());
+        
+        // Changed lockfile
+        std::fs::write(root.join("Cargo.lock"), lockfile.replace("1.0.9", "1.0.10")).unwrap();
+        assert!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").is_err());
+    }
+
+    #[test]
+    fn test_epoch_pins_local_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(&vendor_dir).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[])).unwrap();
+        
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        let snapshot = manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &[]).unwrap();
+        let pinned: Vec<LocalDependency> = serde_json::from_value(snapshot.metadata[LOCAL_DEPENDENCIES_KEY].clone()).unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].name, "app");
+        assert!(manager.verify_epoch_snapshot(&project, "2025-Q4-001").is_ok());
+        
+        std::fs::write(root.join("src/main.rs"), "fn main() { evil() }\n").unwrap();
+        let error = manager.verify_epoch_snapshot(&project, "2025-Q4-001").unwrap_err();
+        assert!(error.to_string().contains("app@0.1.0"));
+        
+        // A lockfile entry no manifest declares fails the snapshot
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n\n[[package]]\nname = \"app\"\nversion = \"0.2.0\"\n").unwrap();
+        assert!(manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-002", &[]).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_plans_write_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lockfile = "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n";
+        std::fs::write(root.join("Cargo.lock"), lockfile).unwrap();
+
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa").join(CARGO_CHECKSUM_FILE), r#"{"files":{},"package":"aa"}"#).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[locked("itoa", "1.0.9", Some("aa"))])).unwrap();
+
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &[]).unwrap();
+
+        let plan = manager.plan_snapshot(&project, &vendor_dir, "2025-Q4-002", &[]).unwrap();
+        assert_eq!(plan.epochs_superseded, vec!["2025-Q4-001".to_string()]);
+        assert!(!plan.files_written.iter().any(|path| path.exists()));
+        assert!(manager.list_epochs(&project).unwrap().iter().all(|snapshot| snapshot.epoch_id == "2025-Q4-001"));
+
+        // A newer itoa would be the only package re-vendored
+        std::fs::write(root.join("Cargo.lock"), lockfile.replace("1.0.9", "1.0.10").replace("\"aa\"", "\"bb\"")).unwrap();
+        let plan = manager.plan_vendor(&project, &vendor_dir, true).unwrap();
+        assert_eq!(plan.packages_vendored, vec!["itoa@1.0.10".to_string()]);
+        assert_eq!(plan.packages_removed, vec!["itoa@1.0.9".to_string()]);
+        assert!(vendor_dir.join("itoa/lib.rs").exists());
+
+        let archive = root.join("vendor.tar.zst");
+        let plan = manager.plan_archive(&vendor_dir, &archive, None).unwrap();
+        assert_eq!(plan.files_written, vec![archive.clone(), VendorManager::archive_digest_path(&archive, ChecksumAlgorithm::Sha256)]);
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn test_snapshot_consumes_review_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lockfile = "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n";
+        std::fs::write(root.join("Cargo.lock"), lockfile).unwrap();
+
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa").join(CARGO_CHECKSUM_FILE), r#"{"files":{},"package":"aa"}"#).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[locked("itoa", "1.0.9", Some("aa"))])).unwrap();
+
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        let fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path()).unwrap();
+
+        let drifted = vec!["itoa".to_string()];
+        
+        // Drift nobody reviewed blocks the snapshot
+        assert!(manager.plan_snapshot(&project, &vendor_dir, "2025-Q4-001", &drifted).is_err());
+        assert!(manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &drifted).is_err());
+
+        let mut review = ReviewDecisions::new(fingerprint);
+        review.decide(ReviewDecisions::drift_key("itoa"), ReviewItemKind::Drift, ReviewStatus::Rejected, "alice");
+        manager.save_review_decisions(&project, &review).unwrap();
+        assert!(manager.plan_snapshot(&project, &vendor_dir, "2025-Q4-001", &[]).is_err());
+        assert!(manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &[]).is_err());
+
+        review.decide(ReviewDecisions::drift_key("itoa"), ReviewItemKind::Drift, ReviewStatus::Approved, "alice");
+        manager.save_review_decisions(&project, &review).unwrap();
+        let snapshot = manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-001", &drifted).unwrap();
+        assert_eq!(snapshot.metadata["review"]["approved"], 1);
+        assert_eq!(manager.list_epochs(&project).unwrap().len(), 1);
+
+        // Decisions made against another Cargo.lock are ignored
+        review.lockfile_fingerprint = "stale".to_string();
+        review.decide(ReviewDecisions::drift_key("itoa"), ReviewItemKind::Drift, ReviewStatus::Rejected, "alice");
+        manager.save_review_decisions(&project, &review).unwrap();
+        assert!(manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-002", &drifted).is_err());
+        assert!(!manager.snapshot_vendor(&project, &vendor_dir, "2025-Q4-002", &[]).unwrap().metadata.contains_key("review"));
+    }
+
+    #[test]
+    fn test_vendor_info_sizes_by_classification() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n").unwrap();
+
+        let vendor_dir = root.join("vendor");
+        for (path, size) in [("ring", 300), ("itoa", 100)] {
+            std::fs::create_dir_all(vendor_dir.join(path).join("src")).unwrap();
+            std::fs::write(vendor_dir.join(path).join("src/lib.rs"), vec![b'x'; size]).unwrap();
+        }
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[
+            locked("ring", "0.17.8", Some("aa")),
+            locked("itoa", "1.0.9", Some("bb")),
+        ])).unwrap();
+
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let info = VendorManager::new(&RustAdapterConfig::default()).vendor_info(&project, &vendor_dir).unwrap();
+        assert_eq!(info.total_packages, 2);
+        assert_eq!(info.metadata.total_size_bytes, 400);
+        assert_eq!(info.packages_by_size()[0].name, "ring");
+        assert_eq!(info.get_package("itoa").unwrap().size_bytes, 100);
+
+        let node = |name: &str, version: &str, classification: Classification| PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            version: version.to_string(),
+            source: PackageSource::Registry { url: "https://crates.io".to_string(), checksum: String::new() },
+            checksum: String::new(),
+            classification,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        };
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(node("ring", "0.17.8", Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() }));
+        graph.add_package(node("itoa", "1.0.9", Classification::Mechanical { category: MechanicalCategory::Utility }));
+
+        let stats = info.size_by_classification(&graph);
+        assert_eq!(stats["tcs"].total_size_bytes, 300);
+        assert_eq!(stats["tcs"].largest.as_deref(), Some("ring@0.17.8"));
+        assert!((stats["mechanical"].share - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_diff_epochs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lockfile = "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n";
+        std::fs::write(root.join("Cargo.lock"), lockfile).unwrap();
+        
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa/README.md"), "itoa\n").unwrap();
+        std::fs::write(vendor_dir.join("itoa").join(CARGO_CHECKSUM_FILE), r#"{"files":{},"package":"aa"}"#).unwrap();
+        VendorManager::write_manifest(&vendor_dir, &VendorManager::build_manifest(&[locked("itoa", "1.0.9", Some("aa"))])).unwrap();
+        
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let manager = VendorManager::new(&RustAdapterConfig::default());
+        manager.snapshot_vendor(&project, &vendor_dir, "epoch-a", &[]).unwrap();
+        let count_objects = || walkdir::WalkDir::new(project.epochs_path().join(EPOCH_OBJECTS_DIR))
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .count();
+        let objects_a = count_objects();
+        
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() { evil() }\n").unwrap();
+        std::fs::remove_file(vendor_dir.join("itoa/README.md")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/build.rs"), "fn main() {}\n").unwrap();
+        manager.snapshot_vendor(&project, &vendor_dir, "epoch-b", &[]).unwrap();
+        
+        // Only the changed and added files are stored again
+        assert_eq!(count_objects(), objects_a + 2);
+        
+        let report = manager.diff_epochs(&project, "epoch-a", "epoch-b", Some("itoa"), true).unwrap();
+        let summary: Vec<_> = report.changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(summary, vec![
+            ("itoa/README.md", VendorFileChangeKind::Removed),
+            ("itoa/build.rs", VendorFileChangeKind::Added),
+            ("itoa/lib.rs", VendorFileChangeKind::Modified),
+        ]);
+        assert_eq!(report.unchanged, 1);
+        let diff = report.changes[2].unified_diff.as_deref().unwrap();
+        assert!(diff.contains("-pub fn itoa() {}"));
+        assert!(diff.contains("+pub fn itoa() { evil() }"));
+        
+        assert!(manager.diff_epochs(&project, "epoch-a", "epoch-b", Some("ryu"), false).unwrap().is_empty());
+        assert!(manager.diff_epochs(&project, "epoch-a", "missing", None, false).is_err());
+        assert!(VendorManager::is_package_file("itoa-1.0.9/src/lib.rs", "itoa"));
+        assert!(VendorManager::is_package_file("itoa-1.0.0-rc.1/src/lib.rs", "itoa"));
+        assert!(!VendorManager::is_package_file("itoa-macros/src/lib.rs", "itoa"));
+        assert!(!VendorManager::is_package_file("itoa-2d/src/lib.rs", "itoa"));
+    }
+    
+    #[tokio::test]
+    async fn test_checksum_verification_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let lockfile = "version = 3\n\n[[package]]\nname = \"itoa\"\nversion = \"1.0.9\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"aa\"\n";
+        std::fs::write(root.join("Cargo.lock"), lockfile).unwrap();
+        
+        let vendor_dir = root.join("vendor");
+        std::fs::create_dir_all(vendor_dir.join("itoa")).unwrap();
+        std::fs::write(vendor_dir.join("itoa/lib.rs"), "pub fn itoa() {}\n").unwrap();
+        
+        // An interrupted run recorded itoa as verified
+        let project = Project::new("test".to_string(), "test".to_string(), "rust".to_string(), root.to_path_buf());
+        let mut config = RustAdapterConfig::default();
+        config.cache_config.shared_dir = Some(dir.path().join("cache"));
+        let progress_dir = VendorManager::new(&config).config.progress_dir.unwrap();
+        let progress_path = VerifyProgress::path(&progress_dir, root);
+        let key = ProgressKey::load_or_create(&progress_dir).unwrap();
+        let fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project.lockfile_path()).unwrap();
+        let mut progress = VerifyProgress::new(&vendor_dir, &fingerprint, key);
+        progress.mark_verified("itoa", &vendor_dir.join("itoa"), "aa");
+        progress.save(&progress_path).unwrap();
+        
+        let mut full_config = config.clone();
+        full_config.vendor_config.full_verification = true;
+        let full = VendorManager::new(&full_config).verify_checksums_against_lockfile(&project, &vendor_dir).await.unwrap();
+        assert_eq!(full.len(), 1);
+        assert!(!progress_path.exists());
+        
+        progress.save(&progress_path).unwrap();
+        let resumed = VendorManager::new(&config)
+            .verify_checksums_against_lockfile(&project, &vendor_dir).await.unwrap();
+        assert!(resumed.is_empty());
+        assert!(!progress_path.exists());
+    }
+    
+    #[test]
+    fn test_vendored_source_config() {
+        let config = VendorManager::vendored_source_config(Path::new("/work/vendor"), &[
+            locked("itoa", "1.0.9", Some("aa")),
+            locked("private", "0.1.0", None),
+        ]);
+        
+        assert_eq!(config[0], ("source.crates-io.replace-with".to_string(), "\"vendored-sources\"".to_string()));
+        assert_eq!(config[1], ("source.vendored-sources.directory".to_string(), "\"/work/vendor\"".to_string()));
+        assert!(config.contains(&(
+            "source.\"git+https://github.com/example/private\".git".to_string(),
+            "\"https://github.com/example/private\"".to_string(),
+        )));
+        assert_eq!(config.len(), 4);
+    }
+    
+    #[test]
+    fn test_mirror_source() {
+        let entry = |name: &str, source: &str, checksum: Option<&str>| VendorManifestEntry {
+            name: name.to_string(),
+            version: "1.0.9".to_string(),
+            source: Some(source.to_string()),
+            checksum: checksum.map(str::to_string),
+            path: PathBuf::from(name),
+        };
+        let downloads = HashMap::from([(
+            "sparse+https://crates.example.com/index/".to_string(),
+            "https://crates.example.com/dl/{prefix}/{crate}/{version}/{sha256-checksum}".to_string(),
+        )]);
+        
+        let crates_io = entry("itoa", "registry+https://github.com/rust-lang/crates.io-index", Some("aa"));
+        assert_eq!(VendorManager::mirror_source(&crates_io, &downloads), Some(MirrorSource::Registry {
+            index: "https://github.com/rust-lang/crates.io-index".to_string(),
+            download_url: Some("https://static.crates.io/crates/itoa/itoa-1.0.9.crate".to_string()),
+            checksum: Some("aa".to_string()),
+        }));
+        
+        let private = entry("acme", "sparse+https://crates.example.com/index/", Some("bb"));
+        let Some(MirrorSource::Registry { download_url, .. }) = VendorManager::mirror_source(&private, &downloads) else {
+            panic!("expected registry source");
+        };
+        assert_eq!(download_url.as_deref(), Some("https://crates.example.com/dl/ac/me/acme/1.0.9/bb"));
+        
+        let git = entry("ring", "git+https://github.com/briansmith/ring?branch=main#abc123", None);
+        assert_eq!(VendorManager::mirror_source(&git, &downloads), Some(MirrorSource::Git {
+            url: "https://github.com/briansmith/ring".to_string(),
+            rev: "abc123".to_string(),
+            reference: Some("main".to_string()),
+        }));
+        
+        assert_eq!(VendorManager::mirror_source(&entry("local", "directory+/opt/crates", None), &downloads), None);
+    }
 }
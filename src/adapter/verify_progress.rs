@@ -0,0 +1,297 @@
+//! Saved progress of vendor checksum verification
+//!
+//! Verifying a large vendor directory hashes every package against its
+//! locked checksum. The packages whose checksum matched are recorded as
+//! verification goes, together with a fingerprint of their files, so a
+//! run that is interrupted can be resumed: packages recorded as verified
+//! whose files are unchanged are not checked against the lockfile again.
+//! The progress file is removed once a verification completes, so every
+//! complete run after that starts from scratch.
+//!
+//! Fingerprints are keyed BLAKE3 hashes of the files' metadata: size,
+//! modification time and, on Unix, inode and status change time, which
+//! writing a file always updates and which can't be set back. Files are
+//! not read, so a resumed run only walks the directory. The key and the
+//! progress files live in the shared cache directory rather than the
+//! project tree, so a progress file planted in the repository can't make
+//! a package count as verified.
+
+use crate::error::{AdapterError, Result};
+use crate::utils::write_atomic;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Shared cache entry holding progress files and their key
+pub const PROGRESS_ENTRY: &str = "verify-progress";
+
+/// Fingerprint key, relative to the progress directory
+const KEY_FILE: &str = "progress.key";
+
+/// Secret key of the file fingerprints
+#[derive(Clone, PartialEq)]
+pub struct ProgressKey([u8; 32]);
+
+impl std::fmt::Debug for ProgressKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressKey(..)")
+    }
+}
+
+impl ProgressKey {
+    /// Load the key from a progress directory, creating it on first use
+    pub fn load_or_create(progress_dir: &Path) -> Result<Self> {
+        let path = progress_dir.join(KEY_FILE);
+        let mut key = [0u8; 32];
+        if let Ok(mut file) = std::fs::File::open(&path) {
+            return match file.read_exact(&mut key) {
+                Ok(()) => Ok(Self(key)),
+                Err(_) => Err(AdapterError::ConfigurationInvalid {
+                    field: "cache_config.shared_dir".to_string(),
+                    value: path.display().to_string(),
+                    reason: "Verification progress key must hold 32 bytes".to_string(),
+                    source: anyhow::anyhow!("Invalid verification progress key"),
+                }),
+            };
+        }
+        
+        rand_core::RngCore::fill_bytes(&mut rand_core::OsRng, &mut key);
+        std::fs::create_dir_all(progress_dir)
+            .map_err(|_| AdapterError::permission_denied(progress_dir, "creating verification progress directory"))?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(mut file) => file.write_all(&key)
+                .map_err(|_| AdapterError::permission_denied(&path, "writing verification progress key"))
+                .map(|_| Self(key)),
+            // Another run created the key first
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Self::load_or_create(progress_dir),
+            Err(_) => Err(AdapterError::permission_denied(&path, "creating verification progress key")),
+        }
+    }
+    
+    /// Placeholder replaced by the real key after deserializing
+    fn unset() -> Self {
+        Self([0u8; 32])
+    }
+}
+
+/// Verification progress for one vendor directory and lockfile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerifyProgress {
+    /// Vendor directory being verified
+    pub vendor_dir: PathBuf,
+    /// Fingerprint of the Cargo.lock the checksums come from
+    pub lockfile_fingerprint: String,
+    /// Verified packages by vendor directory name
+    pub packages: BTreeMap<String, VerifiedPackage>,
+    /// Key of the file fingerprints, never written to the progress file
+    #[serde(skip, default = "ProgressKey::unset")]
+    key: ProgressKey,
+}
+
+/// Package whose content hash matched its locked checksum
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerifiedPackage {
+    /// Content hash that was verified
+    pub checksum: String,
+    /// Keyed hash of the package's checksum, file names and file metadata
+    pub files_fingerprint: String,
+}
+
+impl VerifyProgress {
+    /// Empty progress for a vendor directory and lockfile
+    pub fn new(vendor_dir: &Path, lockfile_fingerprint: &str, key: ProgressKey) -> Self {
+        Self {
+            vendor_dir: vendor_dir.to_path_buf(),
+            lockfile_fingerprint: lockfile_fingerprint.to_string(),
+            packages: BTreeMap::new(),
+            key,
+        }
+    }
+    
+    /// Progress file of a project below a progress directory
+    ///
+    /// Named after a hash of the project root, so projects sharing the
+    /// cache do not overwrite each other's progress.
+    pub fn path(progress_dir: &Path, project_root: &Path) -> PathBuf {
+        let root = Sha256::digest(project_root.to_string_lossy().as_bytes());
+        progress_dir.join(format!("{:x}.json", root))
+    }
+    
+    /// Load saved progress for the same vendor directory and lockfile
+    ///
+    /// Progress saved for another directory or lockfile, or unreadable
+    /// progress, is discarded and verification starts over. Entries
+    /// fingerprinted with another key never match.
+    pub fn load(path: &Path, vendor_dir: &Path, lockfile_fingerprint: &str, key: ProgressKey) -> Self {
+        crate::utils::recover_partial_writes(path);
+        let saved = std::fs::read_to_string(path).ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|saved| saved.vendor_dir == vendor_dir && saved.lockfile_fingerprint == lockfile_fingerprint);
+        
+        match saved {
+            Some(saved) => {
+                tracing::info!(verified = saved.packages.len(), "Resuming vendor verification");
+                Self { key, ..saved }
+            },
+            None => Self::new(vendor_dir, lockfile_fingerprint, key),
+        }
+    }
+    
+    /// Write the progress to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating verification progress directory"))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize verification progress".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        write_atomic(path, content.as_bytes())
+    }
+    
+    /// Remove saved progress, after a completed verification
+    pub fn clear(path: &Path) {
+        let _ = std::fs::remove_file(path);
+    }
+    
+    /// Whether a package was verified against `checksum` and its files have not changed since
+    pub fn is_verified(&self, name: &str, package_dir: &Path, checksum: &str) -> bool {
+        self.packages.get(name).is_some_and(|verified| {
+            verified.checksum == checksum
+                && files_fingerprint(&self.key, package_dir, checksum).is_some_and(|fingerprint| fingerprint == verified.files_fingerprint)
+        })
+    }
+    
+    /// Record a package whose content hash matched
+    pub fn mark_verified(&mut self, name: &str, package_dir: &Path, checksum: &str) {
+        if let Some(files_fingerprint) = files_fingerprint(&self.key, package_dir, checksum) {
+            self.packages.insert(name.to_string(), VerifiedPackage {
+                checksum: checksum.to_string(),
+                files_fingerprint,
+            });
+        }
+    }
+}
+
+/// Keyed hash of a checksum and the relative path and metadata of every file in a directory
+///
+/// `None` if a file's metadata cannot be read.
+fn files_fingerprint(key: &ProgressKey, dir: &Path, checksum: &str) -> Option<String> {
+    let mut hasher = blake3::Hasher::new_keyed(&key.0);
+    hasher.update(checksum.as_bytes());
+    hasher.update(b"\0");
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.ok()?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir).ok()?;
+        let metadata = entry.metadata().ok()?;
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(&metadata.len().to_le_bytes());
+        let modified = metadata.modified().ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        hasher.update(&modified.as_nanos().to_le_bytes());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            hasher.update(&metadata.dev().to_le_bytes());
+            hasher.update(&metadata.ino().to_le_bytes());
+            hasher.update(&metadata.ctime().to_le_bytes());
+            hasher.update(&metadata.ctime_nsec().to_le_bytes());
+        }
+    }
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn vendor(dir: &Path) -> PathBuf {
+        let vendor_dir = dir.join("vendor");
+        for name in ["itoa", "ryu"] {
+            std::fs::create_dir_all(vendor_dir.join(name)).unwrap();
+            std::fs::write(vendor_dir.join(name).join("lib.rs"), name).unwrap();
+        }
+        vendor_dir
+    }
+    
+    #[test]
+    fn test_progress_resumes_only_unchanged_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let vendor_dir = vendor(dir.path());
+        let progress_dir = dir.path().join("cache").join(PROGRESS_ENTRY);
+        let progress_path = VerifyProgress::path(&progress_dir, dir.path());
+        let key = ProgressKey::load_or_create(&progress_dir).unwrap();
+        assert_eq!(ProgressKey::load_or_create(&progress_dir).unwrap(), key);
+        
+        let mut progress = VerifyProgress::new(&vendor_dir, "lock-1", key.clone());
+        progress.mark_verified("itoa", &vendor_dir.join("itoa"), "aaa");
+        progress.mark_verified("ryu", &vendor_dir.join("ryu"), "bbb");
+        progress.save(&progress_path).unwrap();
+        
+        // Changing a file invalidates that package only
+        std::fs::write(vendor_dir.join("ryu").join("lib.rs"), "tampered").unwrap();
+        let resumed = VerifyProgress::load(&progress_path, &vendor_dir, "lock-1", key.clone());
+        assert!(resumed.is_verified("itoa", &vendor_dir.join("itoa"), "aaa"));
+        assert!(!resumed.is_verified("itoa", &vendor_dir.join("itoa"), "other-checksum"));
+        assert!(!resumed.is_verified("ryu", &vendor_dir.join("ryu"), "bbb"));
+        
+        // Progress for another lockfile is discarded
+        assert!(VerifyProgress::load(&progress_path, &vendor_dir, "lock-2", key).packages.is_empty());
+        
+        VerifyProgress::clear(&progress_path);
+        assert!(!progress_path.exists());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_same_size_same_mtime_tamper_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let vendor_dir = vendor(dir.path());
+        let file = vendor_dir.join("itoa").join("lib.rs");
+        let key = ProgressKey::load_or_create(&dir.path().join(PROGRESS_ENTRY)).unwrap();
+        
+        let mut progress = VerifyProgress::new(&vendor_dir, "lock-1", key);
+        progress.mark_verified("itoa", &vendor_dir.join("itoa"), "aaa");
+        
+        // Rewrite the file with content of the same length and restore its
+        // mtime, which still updates its status change time (once the
+        // filesystem's coarse clock has ticked)
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let modified = std::fs::metadata(&file).unwrap().modified().unwrap();
+        std::fs::write(&file, "ITOA").unwrap();
+        std::fs::File::options().write(true).open(&file).unwrap().set_modified(modified).unwrap();
+        assert_eq!(std::fs::metadata(&file).unwrap().modified().unwrap(), modified);
+        
+        assert!(!progress.is_verified("itoa", &vendor_dir.join("itoa"), "aaa"));
+    }
+    
+    #[test]
+    fn test_progress_from_another_key_is_not_trusted() {
+        let dir = tempfile::tempdir().unwrap();
+        let vendor_dir = vendor(dir.path());
+        let progress_path = dir.path().join("progress.json");
+        
+        let planted = ProgressKey::load_or_create(&dir.path().join("planted")).unwrap();
+        let mut progress = VerifyProgress::new(&vendor_dir, "lock-1", planted);
+        progress.mark_verified("itoa", &vendor_dir.join("itoa"), "aaa");
+        progress.save(&progress_path).unwrap();
+        
+        let key = ProgressKey::load_or_create(&dir.path().join(PROGRESS_ENTRY)).unwrap();
+        let loaded = VerifyProgress::load(&progress_path, &vendor_dir, "lock-1", key);
+        assert_eq!(loaded.packages.len(), 1);
+        assert!(!loaded.is_verified("itoa", &vendor_dir.join("itoa"), "aaa"));
+    }
+}
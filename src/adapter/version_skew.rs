@@ -0,0 +1,373 @@
+//! Version requirement skew analysis
+//!
+//! This module compares the version requirements declared in Cargo.toml
+//! with the versions resolved in Cargo.lock. It reports requirements no
+//! resolved version satisfies, resolved versions sitting exactly at the
+//! lowest version a requirement admits, and wildcard requirements on TCS
+//! crates. It also describes how tightly each TCS dependency is pinned
+//! for the policy facts.
+
+use crate::manifest::{ManifestDependencySource, WorkspaceManifest};
+use crate::models::*;
+use semver::{Comparator, Op, Version, VersionReq};
+use std::collections::BTreeSet;
+
+/// Version skew analyzer implementation
+#[derive(Debug, Clone)]
+pub struct VersionSkewAnalyzer {
+    /// Analyzer configuration
+    config: VersionSkewConfig,
+    /// Whether analyzer is ready
+    ready: bool,
+}
+
+/// Configuration for version skew analyzer
+#[derive(Debug, Clone)]
+pub struct VersionSkewConfig {
+    /// Report resolved versions equal to the requirement's lower bound
+    pub report_barely_satisfied: bool,
+}
+
+impl VersionSkewAnalyzer {
+    /// Create new version skew analyzer with configuration
+    pub fn new(_config: &RustAdapterConfig) -> Self {
+        Self {
+            config: VersionSkewConfig::default(),
+            ready: true,
+        }
+    }
+    
+    /// Check if analyzer is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready
+    }
+    
+    /// Check every registry or git requirement declared by workspace packages
+    ///
+    /// Path dependencies and declarations without a version requirement
+    /// are skipped, as are crates absent from Cargo.lock (for example
+    /// optional dependencies that are not enabled).
+    pub fn analyze(&self, manifest: &WorkspaceManifest, graph: &DependencyGraph) -> VersionSkewReport {
+        let members = manifest.member_names();
+        let mut seen = BTreeSet::new();
+        let mut checked = 0;
+        let mut findings = Vec::new();
+        
+        for package_manifest in manifest.packages() {
+            let declared_by = package_manifest.package.as_ref()
+                .map(|package| package.name.as_str())
+                .unwrap_or_default();
+            
+            for dep in &package_manifest.dependencies {
+                if members.contains(dep.package.as_str()) || matches!(dep.source, ManifestDependencySource::Path { .. }) {
+                    continue;
+                }
+                let requirement = match &dep.version_req {
+                    Some(requirement) => requirement,
+                    None => continue,
+                };
+                if !seen.insert((declared_by, dep.package.as_str(), requirement.as_str())) {
+                    continue;
+                }
+                
+                let nodes: Vec<_> = graph.root_packages.iter().filter(|p| p.name == dep.package).collect();
+                if nodes.is_empty() {
+                    continue;
+                }
+                
+                let req = match VersionReq::parse(requirement) {
+                    Ok(req) => req,
+                    Err(e) => {
+                        tracing::debug!(crate_name = %dep.package, requirement = %requirement, error = %e, "Skipping unparsable version requirement");
+                        continue;
+                    },
+                };
+                
+                checked += 1;
+                let tcs = nodes.iter().any(|p| matches!(p.classification, Classification::TCS { .. }));
+                let mut resolved: Vec<Version> = nodes.iter().filter_map(|p| Version::parse(&p.version).ok()).collect();
+                resolved.sort();
+                
+                let finding = |kind: VersionSkewKind, severity: Severity, message: String| VersionSkewFinding {
+                    kind,
+                    severity,
+                    crate_name: dep.package.clone(),
+                    declared_by: declared_by.to_string(),
+                    requirement: requirement.clone(),
+                    resolved_versions: resolved.iter().map(Version::to_string).collect(),
+                    tcs,
+                    message,
+                };
+                
+                if tcs && Self::is_wildcard(&req) {
+                    findings.push(finding(
+                        VersionSkewKind::WildcardTcs,
+                        Severity::Medium,
+                        format!("TCS crate {} is declared with wildcard requirement '{}'", dep.package, requirement),
+                    ));
+                }
+                
+                match resolved.iter().rev().find(|version| req.matches(version)) {
+                    None => findings.push(finding(
+                        VersionSkewKind::Unsatisfied,
+                        if tcs { Severity::High } else { Severity::Medium },
+                        format!("No version of {} in Cargo.lock satisfies '{}'", dep.package, requirement),
+                    )),
+                    Some(version) if self.config.report_barely_satisfied && Self::is_lower_bound(&req, version) => {
+                        findings.push(finding(
+                            VersionSkewKind::BarelySatisfied,
+                            if tcs { Severity::Low } else { Severity::Info },
+                            format!("{} {} is the lowest version '{}' admits", dep.package, version, requirement),
+                        ));
+                    },
+                    Some(_) => {},
+                }
+            }
+        }
+        
+        findings.sort_by(|a, b| {
+            (&a.severity, a.kind, &a.crate_name, &a.declared_by)
+                .cmp(&(&b.severity, b.kind, &b.crate_name, &b.declared_by))
+        });
+        
+        VersionSkewReport {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            lockfile_fingerprint: graph.metadata.lockfile_fingerprint.clone(),
+            declarations_checked: checked,
+            findings,
+        }
+    }
+    
+    /// Describe how tightly each TCS registry dependency is pinned
+    ///
+    /// Covers the registry dependencies workspace packages declare on
+    /// crates classified TCS; a declaration without a requirement is read
+    /// as `*`. Whether `cargo update` would move the lock is judged from
+    /// the newer releases recorded in the package's update lag and this
+    /// declaration's requirement alone, so requirements other packages put
+    /// on the crate may still hold it back. Entries are sorted by crate
+    /// and declaring package.
+    pub fn pinning(&self, manifest: &WorkspaceManifest, graph: &DependencyGraph) -> Vec<PinningFacts> {
+        let members = manifest.member_names();
+        let mut seen = BTreeSet::new();
+        let mut pinning = Vec::new();
+        
+        for package_manifest in manifest.packages() {
+            let declared_by = package_manifest.package.as_ref()
+                .map(|package| package.name.as_str())
+                .unwrap_or_default();
+            
+            for dep in &package_manifest.dependencies {
+                if members.contains(dep.package.as_str()) || !matches!(dep.source, ManifestDependencySource::Registry { .. }) {
+                    continue;
+                }
+                let requirement = dep.version_req.as_deref().unwrap_or("*");
+                if !seen.insert((declared_by, dep.package.as_str(), requirement)) {
+                    continue;
+                }
+                
+                let nodes: Vec<_> = graph.root_packages.iter().filter(|p| p.name == dep.package).collect();
+                if !nodes.iter().any(|p| matches!(p.classification, Classification::TCS { .. })) {
+                    continue;
+                }
+                let req = match VersionReq::parse(requirement) {
+                    Ok(req) => req,
+                    Err(e) => {
+                        tracing::debug!(crate_name = %dep.package, requirement = %requirement, error = %e, "Skipping unparsable version requirement");
+                        continue;
+                    },
+                };
+                
+                let kind = Self::pinning_kind(&req);
+                let locked = nodes.iter()
+                    .filter_map(|p| Version::parse(&p.version).ok().map(|version| (version, *p)))
+                    .filter(|(version, _)| req.matches(version))
+                    .max_by(|a, b| a.0.cmp(&b.0))
+                    .map(|(_, package)| package);
+                let update_version = locked
+                    .and_then(UpdateLag::from_package)
+                    .map(|lag| lag.newer_versions.iter()
+                        .filter_map(|v| Version::parse(v).ok())
+                        .filter(|v| req.matches(v))
+                        .max());
+                let update_available = match (kind, &update_version) {
+                    (_, Some(version)) => Some(version.is_some()),
+                    (VersionPinning::Exact, None) => Some(false),
+                    _ => None,
+                };
+                
+                pinning.push(PinningFacts {
+                    crate_name: dep.package.clone(),
+                    declared_by: declared_by.to_string(),
+                    requirement: requirement.to_string(),
+                    pinning: kind,
+                    locked: locked.map(|p| PolicyFacts::package_key(&p.name, &p.version)),
+                    update_available,
+                    update_version: update_version.flatten().map(|v| v.to_string()),
+                });
+            }
+        }
+        
+        pinning.sort_by(|a, b| (&a.crate_name, &a.declared_by).cmp(&(&b.crate_name, &b.declared_by)));
+        pinning
+    }
+    
+    /// Classify a requirement by how tightly it pins
+    fn pinning_kind(req: &VersionReq) -> VersionPinning {
+        if Self::is_wildcard(req) {
+            return VersionPinning::Wildcard;
+        }
+        match req.comparators.as_slice() {
+            [c] if c.op == Op::Exact && c.minor.is_some() && c.patch.is_some() => VersionPinning::Exact,
+            [c] if c.op == Op::Tilde => VersionPinning::Tilde,
+            [c] if c.op == Op::Caret => VersionPinning::Caret,
+            _ => VersionPinning::Range,
+        }
+    }
+    
+    /// Whether a requirement accepts any version of some major (or any at all)
+    fn is_wildcard(req: &VersionReq) -> bool {
+        req.comparators.is_empty() || req.comparators.iter().any(|c| c.op == Op::Wildcard)
+    }
+    
+    /// Whether `version` is the lowest version a single-range requirement admits
+    ///
+    /// Exact (`=`) requirements are deliberate pins and never reported.
+    fn is_lower_bound(req: &VersionReq, version: &Version) -> bool {
+        match req.comparators.as_slice() {
+            [comparator] if matches!(comparator.op, Op::Caret | Op::Tilde | Op::GreaterEq) => {
+                Self::comparator_minimum(comparator) == *version
+            },
+            _ => false,
+        }
+    }
+    
+    /// Lowest version matched by a lower-bounded comparator
+    fn comparator_minimum(comparator: &Comparator) -> Version {
+        let mut version = Version::new(
+            comparator.major,
+            comparator.minor.unwrap_or(0),
+            comparator.patch.unwrap_or(0),
+        );
+        version.pre = comparator.pre.clone();
+        version
+    }
+}
+
+impl Default for VersionSkewConfig {
+    fn default() -> Self {
+        Self {
+            report_barely_satisfied: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    use crate::config::RustAdapterConfig;
+    use crate::manifest::CargoManifest;
+    use std::path::{Path, PathBuf};
+    
+    #[test]
+    fn test_analyze_reports_skew() {
+        let root_manifest = CargoManifest::parse(Path::new("Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+            
+            [dependencies]
+            serde = "1.0.190"
+            ring = "*"
+            itoa = "1.0"
+            log = "0.4.14"
+            missing = "1"
+        "#).unwrap();
+        let manifest = WorkspaceManifest {
+            root: PathBuf::from("."),
+            root_manifest,
+            members: vec![],
+        };
+        
+        let crypto = || Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() };
+        let utility = || Classification::Mechanical { category: MechanicalCategory::Utility };
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(package("serde", "1.0.150", utility()));
+        graph.add_package(package("ring", "0.17.8", crypto()));
+        graph.add_package(package("itoa", "1.0.11", utility()));
+        graph.add_package(package("log", "0.4.14", utility()));
+        
+        let analyzer = VersionSkewAnalyzer::new(&RustAdapterConfig::default());
+        let report = analyzer.analyze(&manifest, &graph);
+        
+        assert_eq!(report.declarations_checked, 4);
+        assert_eq!(report.findings.len(), 3);
+        assert_eq!(report.findings[0].kind, VersionSkewKind::Unsatisfied);
+        assert_eq!(report.findings[0].crate_name, "serde");
+        assert_eq!(report.findings[0].resolved_versions, vec!["1.0.150".to_string()]);
+        assert_eq!(report.findings[1].kind, VersionSkewKind::WildcardTcs);
+        assert_eq!(report.findings[1].crate_name, "ring");
+        assert_eq!(report.findings[2].kind, VersionSkewKind::BarelySatisfied);
+        assert_eq!(report.findings[2].crate_name, "log");
+        assert_eq!(report.findings[2].severity, Severity::Info);
+    }
+    
+    #[test]
+    fn test_pinning_of_tcs_dependencies() {
+        let root_manifest = CargoManifest::parse(Path::new("Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+            
+            [dependencies]
+            ring = "=0.17.8"
+            rustls = "0.23.5"
+            sha2 = "~0.10.2"
+            openssl = "*"
+            itoa = "1"
+        "#).unwrap();
+        let manifest = WorkspaceManifest {
+            root: PathBuf::from("."),
+            root_manifest,
+            members: vec![],
+        };
+        
+        let crypto = || Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() };
+        let lag = |current: &str, releases: &[&str]| {
+            let releases: Vec<CrateRelease> = releases.iter()
+                .map(|version| CrateRelease { version: version.to_string(), published_at: None })
+                .collect();
+            serde_json::json!(UpdateLag::compute(current, &releases, chrono::Utc::now()).unwrap())
+        };
+        let mut rustls = package("rustls", "0.23.5", crypto());
+        rustls.set_annotation(annotation_keys::UPDATE_LAG, lag("0.23.5", &["0.23.5", "0.23.12", "0.24.0"]));
+        let mut sha2 = package("sha2", "0.10.8", crypto());
+        sha2.set_annotation(annotation_keys::UPDATE_LAG, lag("0.10.8", &["0.10.8", "0.11.0"]));
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(package("ring", "0.17.8", crypto()));
+        graph.add_package(rustls);
+        graph.add_package(sha2);
+        graph.add_package(package("openssl", "0.10.64", crypto()));
+        graph.add_package(package("itoa", "1.0.11", Classification::Mechanical { category: MechanicalCategory::Utility }));
+        
+        let analyzer = VersionSkewAnalyzer::new(&RustAdapterConfig::default());
+        let pinning = analyzer.pinning(&manifest, &graph);
+        
+        let names: Vec<_> = pinning.iter().map(|p| p.crate_name.as_str()).collect();
+        assert_eq!(names, vec!["openssl", "ring", "rustls", "sha2"]);
+        let kinds: Vec<_> = pinning.iter().map(|p| p.pinning).collect();
+        assert_eq!(kinds, vec![VersionPinning::Wildcard, VersionPinning::Exact, VersionPinning::Caret, VersionPinning::Tilde]);
+        
+        // Unknown releases leave the update open, except for exact pins
+        assert_eq!(pinning[0].update_available, None);
+        assert_eq!(pinning[1].update_available, Some(false));
+        assert_eq!(pinning[1].locked.as_deref(), Some("ring@0.17.8"));
+        
+        // Only releases the requirement admits count
+        assert_eq!(pinning[2].update_available, Some(true));
+        assert_eq!(pinning[2].update_version.as_deref(), Some("0.23.12"));
+        assert_eq!(pinning[3].update_available, Some(false));
+        assert_eq!(pinning[3].update_version, None);
+    }
+}
@@ -4,6 +4,12 @@
 //! for all adapter components.
 
 pub mod rust_config;
+pub mod overrides;
+pub mod project_file;
+pub mod reload;
 
 // Re-export main configuration
-pub use rust_config::RustAdapterConfig;
+pub use rust_config::{BundleConfig, DeadlineConfig, ExportRedactionConfig, FactCollectorConfig, FactsConfig, GatingConfig, HttpConfig, OrgConfig, OrgProject, RiskConfig, RustAdapterConfig, SecretScanConfig, SeverityLimits, TelemetryConfig, TransparencyLogConfig};
+pub use overrides::{OverrideEditor, OverrideEntry};
+pub use project_file::{load_audit_criteria, load_max_transitive_depth, load_project_owners};
+pub use reload::{ConfigChangeEntry, ConfigReloader, ReloadOutcome};
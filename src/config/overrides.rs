@@ -0,0 +1,247 @@
+//! Explicit TCS override management
+//!
+//! This module edits `explicit_tcs_overrides` in an adapter configuration
+//! file in place, preserving comments and formatting elsewhere in the file.
+
+use crate::config::RustAdapterConfig;
+use crate::error::{AdapterError, Result};
+use crate::models::TcsCategory;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, InlineTable, Item, Table};
+
+/// Configuration table holding explicit overrides
+const OVERRIDES_TABLE: &str = "explicit_tcs_overrides";
+
+/// Comment prefix used to record the reason for an override
+const REASON_PREFIX: &str = "# reason: ";
+
+/// Explicit TCS override entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverrideEntry {
+    /// Crate name
+    pub crate_name: String,
+    /// Assigned TCS category
+    pub category: TcsCategory,
+    /// Recorded reason, if any
+    pub reason: Option<String>,
+}
+
+/// Editor for explicit TCS overrides in a configuration file
+#[derive(Debug, Clone)]
+pub struct OverrideEditor {
+    /// Configuration file path
+    path: PathBuf,
+    /// Parsed configuration document
+    document: DocumentMut,
+}
+
+/// Subset of the configuration needed to read overrides
+#[derive(Deserialize)]
+struct OverridesSection {
+    #[serde(default)]
+    explicit_tcs_overrides: BTreeMap<String, TcsCategory>,
+}
+
+impl OverrideEditor {
+    /// Open a configuration file for editing
+    ///
+    /// A missing file starts from the default configuration so that the
+    /// saved result remains loadable.
+    pub fn open(path: &Path) -> Result<Self> {
+        let path = path.to_path_buf();
+        crate::utils::recover_partial_writes(&path);
+        
+        let content = if path.exists() {
+            std::fs::read_to_string(&path)
+                .map_err(|_| AdapterError::permission_denied(&path, "reading config file"))?
+        } else {
+            toml::to_string(&RustAdapterConfig::default())
+                .map_err(|e| AdapterError::Internal {
+                    message: "Failed to serialize default configuration".to_string(),
+                    source: anyhow::anyhow!("{}", e),
+                })?
+        };
+        
+        let document = content.parse::<DocumentMut>()
+            .map_err(|e| Self::invalid(&path, format!("TOML parsing error: {}", e)))?;
+        
+        Ok(Self { path, document })
+    }
+    
+    /// Add or replace the override for a crate
+    pub fn add(&mut self, crate_name: &str, category: &TcsCategory, reason: &str) -> Result<()> {
+        if crate_name.trim().is_empty() {
+            return Err(Self::invalid(&self.path, "Crate name cannot be empty".to_string()));
+        }
+        
+        let table = self.overrides_table_mut()?;
+        table.insert(crate_name, Self::category_item(category));
+        
+        if let Some(mut key) = table.key_mut(crate_name) {
+            let reason = reason.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+            let prefix = if reason.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}\n", REASON_PREFIX, reason)
+            };
+            key.leaf_decor_mut().set_prefix(prefix);
+        }
+        
+        Ok(())
+    }
+    
+    /// Remove the override for a crate, returning whether one existed
+    pub fn remove(&mut self, crate_name: &str) -> Result<bool> {
+        match self.document.get_mut(OVERRIDES_TABLE).and_then(|item| item.as_table_like_mut()) {
+            Some(table) => Ok(table.remove(crate_name).is_some()),
+            None => Ok(false),
+        }
+    }
+    
+    /// List configured overrides, sorted by crate name
+    pub fn list(&self) -> Result<Vec<OverrideEntry>> {
+        let section: OverridesSection = toml::from_str(&self.document.to_string())
+            .map_err(|e| Self::invalid(&self.path, format!("Invalid {}: {}", OVERRIDES_TABLE, e)))?;
+        
+        let table = self.document.get(OVERRIDES_TABLE).and_then(|item| item.as_table());
+        
+        Ok(section.explicit_tcs_overrides.into_iter()
+            .map(|(crate_name, category)| {
+                let reason = table
+                    .and_then(|t| t.key(&crate_name))
+                    .and_then(|key| key.leaf_decor().prefix())
+                    .and_then(|prefix| prefix.as_str())
+                    .and_then(|prefix| prefix.lines().find_map(|line| line.trim().strip_prefix(REASON_PREFIX)))
+                    .map(str::to_string);
+                
+                OverrideEntry { crate_name, category, reason }
+            })
+            .collect())
+    }
+    
+    /// Validate and write the configuration back to disk
+    ///
+    /// The file is replaced atomically, and only if the edited document
+    /// still parses as a complete adapter configuration.
+    pub fn save(&self) -> Result<()> {
+        crate::utils::write_atomic(&self.path, self.render()?)
+    }
+    
+    /// Validated content `save` would write
+    pub fn render(&self) -> Result<String> {
+        let content = self.document.to_string();
+        
+        toml::from_str::<RustAdapterConfig>(&content)
+            .map_err(|e| Self::invalid(&self.path, format!("Edited configuration is invalid: {}", e)))?;
+        
+        Ok(content)
+    }
+    
+    /// Configuration file being edited
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Get the overrides table, creating it if needed
+    fn overrides_table_mut(&mut self) -> Result<&mut Table> {
+        let path = self.path.clone();
+        let item = self.document.entry(OVERRIDES_TABLE)
+            .or_insert_with(|| Item::Table(Table::new()));
+        
+        // Promote `explicit_tcs_overrides = { ... }` so entries can carry comments
+        if let Some(inline) = item.as_inline_table() {
+            *item = Item::Table(inline.clone().into_table());
+        }
+        
+        item.as_table_mut()
+            .ok_or_else(|| Self::invalid(&path, format!("{} must be a table", OVERRIDES_TABLE)))
+    }
+    
+    /// Convert a category to its TOML representation
+    fn category_item(category: &TcsCategory) -> Item {
+        let name = match category {
+            TcsCategory::Cryptography => "Cryptography",
+            TcsCategory::Authentication => "Authentication",
+            TcsCategory::Serialization => "Serialization",
+            TcsCategory::Transport => "Transport",
+            TcsCategory::Database => "Database",
+            TcsCategory::Random => "Random",
+            TcsCategory::BuildTimeExecution => "BuildTimeExecution",
+            TcsCategory::Custom(custom) => {
+                let mut table = InlineTable::new();
+                table.insert("Custom", custom.as_str().into());
+                return toml_edit::value(table);
+            },
+        };
+        
+        toml_edit::value(name)
+    }
+    
+    /// Build a configuration error for this file
+    fn invalid(path: &Path, reason: String) -> AdapterError {
+        AdapterError::ConfigurationInvalid {
+            field: OVERRIDES_TABLE.to_string(),
+            value: format!("{:?}", path),
+            reason,
+            source: anyhow::anyhow!("Override edit failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn write_default_config(dir: &Path) -> PathBuf {
+        let path = dir.join("rust-adapter.toml");
+        let content = format!(
+            "# Adapter configuration\n{}",
+            toml::to_string(&RustAdapterConfig::default()).unwrap()
+        );
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+    
+    #[test]
+    fn test_add_list_remove_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_default_config(dir.path());
+        
+        let mut editor = OverrideEditor::open(&path).unwrap();
+        editor.add("openssl", &TcsCategory::Cryptography, "Wraps system TLS").unwrap();
+        editor.add("my-policy", &TcsCategory::Custom("policy".to_string()), "").unwrap();
+        editor.save().unwrap();
+        
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Adapter configuration\n"));
+        assert!(content.contains("# reason: Wraps system TLS\nopenssl = \"Cryptography\""));
+        
+        let config: RustAdapterConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.explicit_tcs_overrides.get("openssl"), Some(&TcsCategory::Cryptography));
+        
+        let editor = OverrideEditor::open(&path).unwrap();
+        let entries = editor.list().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].crate_name, "openssl");
+        assert_eq!(entries[1].reason.as_deref(), Some("Wraps system TLS"));
+        assert_eq!(entries[0].category, TcsCategory::Custom("policy".to_string()));
+        assert_eq!(entries[0].reason, None);
+        
+        let mut editor = editor;
+        assert!(editor.remove("openssl").unwrap());
+        assert!(!editor.remove("openssl").unwrap());
+        editor.save().unwrap();
+        assert_eq!(OverrideEditor::open(&path).unwrap().list().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_category_parsing() {
+        assert_eq!("crypto".parse::<TcsCategory>(), Ok(TcsCategory::Cryptography));
+        assert_eq!("Authentication".parse::<TcsCategory>(), Ok(TcsCategory::Authentication));
+        assert_eq!("custom:Payments".parse::<TcsCategory>(), Ok(TcsCategory::Custom("Payments".to_string())));
+        assert!("custom:".parse::<TcsCategory>().is_err());
+        assert!("cryptic".parse::<TcsCategory>().is_err());
+    }
+}
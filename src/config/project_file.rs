@@ -0,0 +1,154 @@
+//! Project configuration file reading
+//!
+//! This module reads the sections of a project's `project.toml` that
+//! adapter components consume directly. Other sections are ignored, and
+//! a missing file yields empty defaults.
+
+use crate::error::{AdapterError, Result};
+use crate::models::{Project, ProjectOwners, TcsCategory};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Subset of `project.toml` read by the adapter
+#[derive(Deserialize)]
+struct ProjectFile {
+    #[serde(default)]
+    project: ProjectSection,
+}
+
+/// `[project]` table of `project.toml`
+#[derive(Default, Deserialize)]
+struct ProjectSection {
+    #[serde(default)]
+    owners: ProjectOwners,
+    #[serde(default)]
+    policy: PolicySection,
+}
+
+/// `[project.policy]` table of `project.toml`
+#[derive(Default, Deserialize)]
+struct PolicySection {
+    #[serde(default)]
+    audit_criteria: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    max_transitive_depth: Option<usize>,
+}
+
+/// Load team ownership from the project's configuration file
+pub fn load_project_owners(project: &Project) -> Result<ProjectOwners> {
+    Ok(load_project_section(&project.config_path())?.owners)
+}
+
+/// Load the cargo-vet criteria required of TCS packages, by category
+///
+/// Categories are written as accepted on the command line (`crypto`,
+/// `serialization`, `custom:<name>`, ...) and returned in their canonical
+/// form, so they compare equal to `TcsCategory::to_string()`.
+pub fn load_audit_criteria(project: &Project) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut requirements = BTreeMap::new();
+    for (category, criteria) in load_project_section(&project.config_path())?.policy.audit_criteria {
+        let parsed: TcsCategory = category.parse()
+            .map_err(|reason| AdapterError::ConfigurationInvalid {
+                field: format!("project.policy.audit_criteria.{}", category),
+                value: category.clone(),
+                reason,
+                source: anyhow::anyhow!("Unknown TCS category"),
+            })?;
+        requirements.insert(parsed.to_string(), criteria);
+    }
+    
+    Ok(requirements)
+}
+
+/// Load the maximum transitive dependency depth the project analyzes
+///
+/// `max_transitive_depth` in `[project.policy]` takes precedence over the
+/// project's policy; `None` means no limit.
+pub fn load_max_transitive_depth(project: &Project) -> Result<Option<usize>> {
+    let policy = load_project_section(&project.config_path())?.policy;
+    Ok(policy.max_transitive_depth.or(project.policy.max_transitive_depth))
+}
+
+/// Read the `[project]` table, defaulting when the file does not exist
+fn load_project_section(path: &Path) -> Result<ProjectSection> {
+    if !path.exists() {
+        return Ok(ProjectSection::default());
+    }
+    
+    let content = std::fs::read_to_string(path)
+        .map_err(|_| AdapterError::permission_denied(path, "reading project config file"))?;
+    let file: ProjectFile = toml::from_str(&content)
+        .map_err(|e| AdapterError::ConfigurationInvalid {
+            field: "project".to_string(),
+            value: path.display().to_string(),
+            reason: format!("TOML parsing error: {}", e),
+            source: anyhow::anyhow!("Invalid project config file"),
+        })?;
+    
+    Ok(file.project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_load_project_owners() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            temp_dir.path().to_path_buf(),
+        );
+        
+        assert!(load_project_owners(&project).unwrap().teams.is_empty());
+        
+        std::fs::write(project.config_path(), r#"
+            [project]
+            id = "test"
+            
+            [project.owners.payments]
+            members = ["payments-*", "billing"]
+            crates = ["stripe*"]
+            reviewers = ["@acme/payments"]
+            
+            [project.owners.platform]
+            members = ["/core|runtime/"]
+        "#).unwrap();
+        
+        let owners = load_project_owners(&project).unwrap();
+        assert_eq!(owners.teams.len(), 2);
+        assert_eq!(owners.teams["payments"].members, vec!["payments-*".to_string(), "billing".to_string()]);
+        assert_eq!(owners.teams["payments"].reviewers, vec!["@acme/payments".to_string()]);
+        assert!(owners.teams["platform"].crates.is_empty());
+    }
+    
+    #[test]
+    fn test_load_audit_criteria() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = Project::new(
+            "test".to_string(),
+            "Test Project".to_string(),
+            "rust".to_string(),
+            temp_dir.path().to_path_buf(),
+        );
+        
+        assert!(load_audit_criteria(&project).unwrap().is_empty());
+        
+        std::fs::write(project.config_path(), r#"
+            [project.policy.audit_criteria]
+            crypto = ["crypto-safe", "safe-to-deploy"]
+            serialization = ["safe-to-deploy"]
+        "#).unwrap();
+        
+        let requirements = load_audit_criteria(&project).unwrap();
+        assert_eq!(requirements["Cryptography"], vec!["crypto-safe".to_string(), "safe-to-deploy".to_string()]);
+        assert_eq!(requirements["Serialization"], vec!["safe-to-deploy".to_string()]);
+        
+        std::fs::write(project.config_path(), "[project.policy.audit_criteria]\nnot-a-category = []\n").unwrap();
+        assert!(load_audit_criteria(&project).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Configuration hot-reload for long-running modes
+//!
+//! A [`ConfigReloader`] owns the adapter used by a long-running process and
+//! polls `rust-adapter.toml` for changes. A changed file is parsed and
+//! validated before anything is replaced; a valid configuration yields a
+//! freshly built adapter that is swapped in as a whole, so an operation
+//! never sees components configured from two different files. Operations
+//! already running keep the adapter they started with.
+//!
+//! Every applied change is appended to an audit log as one JSON line.
+//! Rejected files leave the running configuration untouched.
+
+use crate::config::RustAdapterConfig;
+use crate::error::{AdapterError, Result};
+use crate::utils::CancellationToken;
+use crate::RustAdapter;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// Audit log entry for an applied configuration change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigChangeEntry {
+    /// Time the change was applied (RFC 3339)
+    pub applied_at: String,
+    /// Configuration file
+    pub path: PathBuf,
+    /// SHA-256 of the previous file content
+    pub previous_fingerprint: Option<String>,
+    /// SHA-256 of the applied file content
+    pub fingerprint: String,
+    /// Top-level configuration sections that changed
+    pub changed_sections: Vec<String>,
+}
+
+/// Outcome of checking the configuration file once
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReloadOutcome {
+    /// File content is unchanged
+    Unchanged,
+    /// New configuration was validated and swapped in
+    Applied(ConfigChangeEntry),
+    /// File changed but could not be loaded; the running configuration is kept
+    Rejected(String),
+}
+
+/// Adapter whose configuration follows a file
+#[derive(Debug)]
+pub struct ConfigReloader {
+    /// Watched configuration file
+    path: PathBuf,
+    /// Append-only log of applied changes
+    audit_log: PathBuf,
+    /// Adapter built from the last valid configuration
+    adapter: RwLock<Arc<RustAdapter>>,
+    /// Fingerprint of the last file content seen, applied or rejected
+    fingerprint: Mutex<Option<String>>,
+}
+
+impl ConfigReloader {
+    /// Load the configuration file and build the initial adapter
+    pub fn new(path: PathBuf, audit_log: PathBuf) -> Result<Self> {
+        let content = Self::read(&path)?;
+        let config = Self::parse(&path, &content)?;
+        
+        Ok(Self {
+            path,
+            audit_log,
+            adapter: RwLock::new(Arc::new(RustAdapter::new(config))),
+            fingerprint: Mutex::new(Some(Self::fingerprint(&content))),
+        })
+    }
+    
+    /// Adapter for the next operation
+    pub fn adapter(&self) -> Arc<RustAdapter> {
+        self.adapter.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    /// Reload the configuration if the file changed
+    pub fn check(&self) -> Result<ReloadOutcome> {
+        let content = Self::read(&self.path)?;
+        let fingerprint = Self::fingerprint(&content);
+        
+        let mut seen = self.fingerprint.lock().unwrap_or_else(|e| e.into_inner());
+        if seen.as_deref() == Some(fingerprint.as_str()) {
+            return Ok(ReloadOutcome::Unchanged);
+        }
+        let previous_fingerprint = seen.replace(fingerprint.clone());
+        
+        let config = match Self::parse(&self.path, &content) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!(path = %self.path.display(), error = %e, "Rejected configuration change");
+                return Ok(ReloadOutcome::Rejected(e.to_string()));
+            },
+        };
+        
+        let changed_sections = Self::changed_sections(self.adapter().config(), &config);
+        let entry = ConfigChangeEntry {
+            applied_at: chrono::Utc::now().to_rfc3339(),
+            path: self.path.clone(),
+            previous_fingerprint,
+            fingerprint,
+            changed_sections,
+        };
+        
+        *self.adapter.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(RustAdapter::new(config));
+        self.append_audit_entry(&entry)?;
+        tracing::info!(path = %self.path.display(), sections = ?entry.changed_sections, "Applied configuration change");
+        
+        Ok(ReloadOutcome::Applied(entry))
+    }
+    
+    /// Check the file every `interval` until `cancel` is cancelled
+    ///
+    /// Unreadable files are logged and retried on the next tick.
+    pub async fn watch(&self, interval: Duration, cancel: &CancellationToken) {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {},
+                _ = cancel.cancelled() => return,
+            }
+            if let Err(e) = self.check() {
+                tracing::warn!(path = %self.path.display(), error = %e, "Failed to check configuration file");
+            }
+        }
+    }
+    
+    /// Read the configuration file
+    fn read(path: &Path) -> Result<String> {
+        std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::file_not_found(path, "reading config file"))
+    }
+    
+    /// Parse and validate configuration content
+    ///
+    /// Validation always runs, regardless of `schema_validation`, since a
+    /// bad file would otherwise replace a working configuration.
+    fn parse(path: &Path, content: &str) -> Result<RustAdapterConfig> {
+        let config: RustAdapterConfig = toml::from_str(content)
+            .map_err(|e| AdapterError::ConfigurationInvalid {
+                field: "config_file".to_string(),
+                value: format!("{:?}", path),
+                reason: format!("TOML parsing error: {}", e),
+                source: anyhow::anyhow!("TOML parsing failed"),
+            })?;
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// SHA-256 of file content
+    fn fingerprint(content: &str) -> String {
+        format!("{:x}", Sha256::digest(content.as_bytes()))
+    }
+    
+    /// Top-level sections that differ between two configurations
+    fn changed_sections(previous: &RustAdapterConfig, next: &RustAdapterConfig) -> Vec<String> {
+        let (Ok(serde_json::Value::Object(previous)), Ok(serde_json::Value::Object(next))) =
+            (serde_json::to_value(previous), serde_json::to_value(next))
+        else {
+            return Vec::new();
+        };
+        
+        let mut sections: Vec<String> = previous.keys().chain(next.keys())
+            .filter(|key| previous.get(*key) != next.get(*key))
+            .cloned()
+            .collect();
+        sections.sort();
+        sections.dedup();
+        sections
+    }
+    
+    /// Append an entry to the audit log
+    fn append_audit_entry(&self, entry: &ConfigChangeEntry) -> Result<()> {
+        if let Some(parent) = self.audit_log.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|_| AdapterError::permission_denied(parent, "creating config audit log directory"))?;
+        }
+        
+        let line = serde_json::to_string(entry)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize config audit entry".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.audit_log)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|_| AdapterError::permission_denied(&self.audit_log, "appending config audit log"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_validated_reload_and_audit_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("rust-adapter.toml");
+        let audit_log = temp_dir.path().join("logs/config-audit.jsonl");
+        
+        let mut config = RustAdapterConfig::default();
+        std::fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+        let reloader = ConfigReloader::new(config_path.clone(), audit_log.clone()).unwrap();
+        let initial = reloader.adapter();
+        assert_eq!(reloader.check().unwrap(), ReloadOutcome::Unchanged);
+        
+        config.offline_mode = true;
+        std::fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+        let ReloadOutcome::Applied(entry) = reloader.check().unwrap() else {
+            panic!("valid change not applied");
+        };
+        assert_eq!(entry.changed_sections, vec!["offline_mode".to_string()]);
+        assert!(reloader.adapter().config().offline_mode);
+        assert!(!initial.config().offline_mode);
+        
+        // An invalid file is rejected and the running configuration kept
+        config.tool_paths.default_timeout = 0;
+        std::fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+        assert!(matches!(reloader.check().unwrap(), ReloadOutcome::Rejected(_)));
+        assert!(reloader.adapter().config().offline_mode);
+        assert_eq!(reloader.adapter().config().tool_paths.default_timeout, RustAdapterConfig::default().tool_paths.default_timeout);
+        
+        let log = std::fs::read_to_string(&audit_log).unwrap();
+        assert_eq!(log.lines().count(), 1);
+        let logged: ConfigChangeEntry = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert_eq!(logged, entry);
+    }
+}
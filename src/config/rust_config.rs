@@ -5,6 +5,7 @@
 
 use crate::models::*;
 use crate::error::{AdapterError, Result};
+use crate::utils::DigestConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -12,7 +13,7 @@ use std::path::PathBuf;
 /// Main configuration structure for Rust Adapter
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RustAdapterConfig {
-    /// Explicit TCS category overrides
+    /// Explicit TCS category overrides keyed by package name, glob or `/regex/`
     pub explicit_tcs_overrides: HashMap<String, TcsCategory>,
     /// Custom TCS classification patterns
     pub custom_tcs_patterns: Vec<TcsPattern>,
@@ -28,6 +29,57 @@ pub struct RustAdapterConfig {
     pub classification_config: ClassificationConfig,
     /// Logging configuration
     pub logging_config: LoggingConfig,
+    /// Concurrency and resource limits
+    #[serde(default)]
+    pub performance_config: PerformanceConfig,
+    /// Registry API configuration (online mode only)
+    #[serde(default)]
+    pub registry_config: RegistryConfig,
+    /// Shared cache location and locking
+    #[serde(default)]
+    pub cache_config: CacheConfig,
+    /// HTTP client of online operations
+    #[serde(default)]
+    pub http_config: HttpConfig,
+    /// Algorithm and encoding of artifact, run manifest and vendor archive digests
+    #[serde(default)]
+    pub digest_config: DigestConfig,
+    /// Metrics and trace export
+    #[serde(default)]
+    pub telemetry_config: TelemetryConfig,
+    /// Time budgets of adapter operations
+    #[serde(default)]
+    pub deadline_config: DeadlineConfig,
+    /// Signed log of vendoring, epoch and verification events
+    #[serde(default)]
+    pub transparency_log_config: TransparencyLogConfig,
+    /// Signing keys trusted for air-gapped bundles
+    #[serde(default)]
+    pub bundle_config: BundleConfig,
+    /// Redaction of exports shared with third parties
+    #[serde(default)]
+    pub export_redaction_config: ExportRedactionConfig,
+    /// Projects of the organization, for rollup reports
+    #[serde(default)]
+    pub org_config: OrgConfig,
+    /// Thresholds of the `gate` command
+    #[serde(default)]
+    pub gating_config: GatingConfig,
+    /// Informational risk scoring
+    #[serde(default)]
+    pub risk_config: RiskConfig,
+    /// Contents of the policy facts export
+    #[serde(default)]
+    pub facts_config: FactsConfig,
+    /// External commands attaching facts to packages during parsing
+    #[serde(default)]
+    pub fact_collectors: Vec<FactCollectorConfig>,
+    /// Weights and thresholds of drift impact assessment
+    #[serde(default)]
+    pub drift_impact_model: ImpactModel,
+    /// Drift detection settings, including per-package priority overrides
+    #[serde(default)]
+    pub drift_detection_config: DriftDetectionConfig,
     /// Offline mode flag
     pub offline_mode: bool,
     /// Schema validation flag
@@ -60,6 +112,53 @@ pub struct VendorConfig {
     pub malware_scan: bool,
     /// Whether to compare with fresh downloads
     pub compare_fresh: bool,
+    /// Whether to re-vendor only packages changed since the last vendoring
+    #[serde(default)]
+    pub delta_vendoring: bool,
+    /// Whether to hash every package on verification, ignoring progress
+    /// saved by an interrupted run
+    #[serde(default)]
+    pub full_verification: bool,
+    /// Scan of vendored sources for embedded secrets
+    #[serde(default)]
+    pub secret_scan: SecretScanConfig,
+    /// Download URL templates of alternative registries, keyed by index URL
+    ///
+    /// Templates use the markers of a registry's `config.json` `dl` field
+    /// (`{crate}`, `{version}`, `{prefix}`, `{lowerprefix}`,
+    /// `{sha256-checksum}`). crates.io needs no entry.
+    #[serde(default)]
+    pub registry_downloads: HashMap<String, String>,
+    /// Whether to verify path dependencies and pin their contents in epochs
+    #[serde(default = "default_verify_local_deps")]
+    pub verify_local_deps: bool,
+}
+
+fn default_verify_local_deps() -> bool {
+    true
+}
+
+/// Scan of vendored sources for embedded secrets
+///
+/// Known credential formats (cloud access keys, private key PEM blocks,
+/// service tokens) are matched by regular expression; quoted values
+/// assigned to secret-like names are reported only when their Shannon
+/// entropy reaches `entropy_threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SecretScanConfig {
+    /// Whether vendor verification scans for secrets
+    pub enabled: bool,
+    /// Minimum entropy (bits per character) of generic secret values
+    pub entropy_threshold: f64,
+    /// Minimum length of generic secret values
+    pub min_secret_length: usize,
+    /// Files larger than this many bytes are skipped
+    pub max_file_size: u64,
+    /// Vendor-relative paths (globs) not scanned, e.g. `ring-*/tests/*`
+    pub allowed_paths: Vec<String>,
+    /// Whether findings fail verification instead of warning
+    pub fail_on_findings: bool,
 }
 
 /// Audit configuration
@@ -75,6 +174,21 @@ pub struct AuditConfig {
     pub cache_results: bool,
     /// Advisory database path (optional)
     pub advisory_db_path: Option<PathBuf>,
+    /// Minimum supported tool versions, keyed by tool name
+    #[serde(default = "default_min_tool_versions")]
+    pub min_tool_versions: HashMap<String, String>,
+    /// Whether workspace members are held to the audit requirements of third-party crates
+    #[serde(default)]
+    pub audit_first_party: bool,
+}
+
+fn default_min_tool_versions() -> HashMap<String, String> {
+    HashMap::from([
+        // JSON report layout stabilised in 0.17
+        ("cargo-audit".to_string(), "0.17.0".to_string()),
+        // `cargo vet dump` output format changed before 0.8
+        ("cargo-vet".to_string(), "0.8.0".to_string()),
+    ])
 }
 
 /// Classification configuration
@@ -88,6 +202,28 @@ pub struct ClassificationConfig {
     pub default_category: MechanicalCategory,
     /// Classification confidence threshold
     pub confidence_threshold: f64,
+    /// Drift priority for each custom TCS category (defaults to Critical)
+    #[serde(default)]
+    pub custom_category_priorities: HashMap<String, Priority>,
+    /// Classification result cache directory (defaults to the shared cache)
+    #[serde(default)]
+    pub result_cache_dir: Option<PathBuf>,
+    /// Whether to bypass the classification result cache
+    #[serde(default)]
+    pub disable_result_cache: bool,
+    /// TCS categories for crates.io category slugs, added to the built-in mapping
+    ///
+    /// A slug also matches its subcategories (`cryptography` matches
+    /// `cryptography::cryptocurrencies`).
+    #[serde(default)]
+    pub category_mappings: HashMap<String, TcsCategory>,
+    /// TCS categories for crates.io keywords, added to the built-in mapping
+    #[serde(default)]
+    pub keyword_mappings: HashMap<String, TcsCategory>,
+    /// Fail classification when more unclassified packages than this
+    /// depend on or are depended on by TCS packages (unset disables the check)
+    #[serde(default)]
+    pub max_tcs_adjacent_unknowns: Option<usize>,
 }
 
 /// Logging configuration
@@ -103,6 +239,308 @@ pub struct LoggingConfig {
     pub include_tool_details: bool,
 }
 
+/// Registry API configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegistryConfig {
+    /// Whether to fetch download counts and release cadence of crates.io
+    /// crates in online mode (opt-in)
+    pub fetch_popularity: bool,
+    /// Registry API base URL
+    pub api_url: String,
+    /// Directory for cached registry responses (defaults to the shared cache)
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    /// Number of crates requested per batched API call
+    pub batch_size: usize,
+    /// Minimum delay between API requests (milliseconds)
+    pub request_interval_ms: u64,
+    /// Local mirror of the crates.io index, used for update lag when offline
+    pub index_dir: Option<PathBuf>,
+    /// Age after which cached crate version metadata is fetched again (seconds, 0 disables the cache)
+    #[serde(default = "default_metadata_cache_ttl")]
+    pub metadata_cache_ttl: u64,
+    /// Crate version metadata cache shared by all projects (defaults to the shared cache)
+    #[serde(default)]
+    pub metadata_cache_dir: Option<PathBuf>,
+    /// Whether the metadata cache is only read, e.g. when provisioned for CI hosts
+    #[serde(default)]
+    pub metadata_cache_read_only: bool,
+}
+
+fn default_metadata_cache_ttl() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+/// Shared cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheConfig {
+    /// Cache directory shared by concurrent runs (defaults to the user cache directory)
+    pub shared_dir: Option<PathBuf>,
+    /// Maximum time to wait for a cache entry locked by another run (seconds)
+    pub lock_timeout: u64,
+    /// Age after which the shared advisory database is fetched again (seconds)
+    pub advisory_db_max_age: u64,
+}
+
+/// HTTP client shared by all online operations
+///
+/// Offline mode refuses every request regardless of these settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// Timeout of a single request attempt (seconds)
+    pub timeout: u64,
+    /// Retries of a request failing with a connection error, a timeout,
+    /// or a 429 or 5xx response
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further one (milliseconds)
+    pub initial_backoff_ms: u64,
+    /// Longest backoff between retries (milliseconds); `Retry-After` delays are not capped
+    pub max_backoff_ms: u64,
+    /// Idle connections kept open per host for reuse
+    pub max_idle_per_host: usize,
+    /// Proxy URL for all requests (defaults to `HTTPS_PROXY`/`HTTP_PROXY`)
+    pub proxy: Option<String>,
+    /// PEM file of additional CA certificates to trust, e.g. of an intercepting proxy
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl HttpConfig {
+    /// Check the settings are usable
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.timeout == 0 {
+            return Err("Timeout cannot be zero".to_string());
+        }
+        if self.initial_backoff_ms > self.max_backoff_ms {
+            return Err("Initial backoff cannot exceed the maximum backoff".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// OpenTelemetry export configuration
+///
+/// Takes effect only in builds with the `otel` feature.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TelemetryConfig {
+    /// Whether metrics and traces are exported
+    pub enabled: bool,
+    /// OTLP/HTTP collector endpoint, e.g. `http://localhost:4318`
+    /// (defaults to `OTEL_EXPORTER_OTLP_ENDPOINT`)
+    pub otlp_endpoint: Option<String>,
+    /// Service name reported with all metrics and spans
+    pub service_name: String,
+    /// Interval between metric exports (seconds)
+    pub export_interval: u64,
+}
+
+/// Time budgets of adapter operations (seconds, 0 for no limit)
+///
+/// A budget covers the whole operation, including every tool it runs;
+/// individual tool invocations are still bounded by their own timeouts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DeadlineConfig {
+    /// Parsing Cargo.lock and enhancing the graph with cargo metadata
+    pub parse: u64,
+    /// Classifying the packages of a graph
+    pub classify: u64,
+    /// Running cargo-audit and cargo-vet
+    pub audit: u64,
+    /// Vendoring dependencies
+    pub vendor: u64,
+    /// Verifying a vendor directory
+    pub verify: u64,
+    /// Generating an SBOM
+    pub sbom: u64,
+}
+
+impl DeadlineConfig {
+    /// Budget of an operation, `None` if it is unlimited
+    pub fn budget(&self, operation: &str) -> Option<std::time::Duration> {
+        let seconds = match operation {
+            "parse" => self.parse,
+            "classify" => self.classify,
+            "audit" => self.audit,
+            "vendor" => self.vendor,
+            "verify" => self.verify,
+            "sbom" => self.sbom,
+            _ => 0,
+        };
+        (seconds > 0).then(|| std::time::Duration::from_secs(seconds))
+    }
+}
+
+/// Append-only transparency log of supply-chain events
+///
+/// Disabled by default. Relative paths are resolved against the project
+/// root. The signing key is created on first use and must be outside the
+/// project; together with a trusted public key kept in configuration,
+/// that is what makes rewriting the log detectable by someone who can
+/// write to the project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TransparencyLogConfig {
+    /// Whether events are recorded
+    pub enabled: bool,
+    /// Log file
+    pub log_file: PathBuf,
+    /// Ed25519 signing key file (hex-encoded secret key), outside the
+    /// project; defaults to the user configuration directory
+    pub signing_key: Option<PathBuf>,
+    /// Public key (hex) every entry must be signed with; verification
+    /// fails while it is unset
+    pub trusted_public_key: Option<String>,
+}
+
+/// Air-gapped security data bundles
+///
+/// Bundles are only installed when their manifest is signed by one of
+/// the trusted keys.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BundleConfig {
+    /// Ed25519 public keys (hex) bundles may be signed with
+    pub trusted_public_keys: Vec<String>,
+}
+
+/// Redaction of graphs, SBOMs and drift timelines shared with third parties
+///
+/// Internal packages are renamed to a salted hash of their name that is
+/// the same in every export, so redacted documents can still be
+/// correlated. Local paths are always dropped, and so are sources and
+/// references whose URL matches `redacted_locations`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExportRedactionConfig {
+    /// Whether exports are redacted (also enabled per run with `--redact`)
+    pub enabled: bool,
+    /// Package name patterns of internal crates
+    pub internal_packages: Vec<String>,
+    /// Whether path dependencies (including workspace members) are internal
+    pub redact_path_packages: bool,
+    /// URL patterns of sources and references to drop (e.g., `https://git.corp.example/*`)
+    pub redacted_locations: Vec<String>,
+    /// Salt mixed into name hashes so they cannot be reversed by hashing guessed names
+    pub salt: String,
+}
+
+/// Projects rolled up into one organization-level report
+///
+/// Project paths are resolved against the directory of the configuration
+/// file, so one config can describe a checkout of several repositories.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct OrgConfig {
+    /// Organization name shown in the rollup report
+    pub name: String,
+    /// Projects belonging to the organization
+    pub projects: Vec<OrgProject>,
+}
+
+/// Project definition within an organization
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgProject {
+    /// Unique project identifier
+    pub id: String,
+    /// Project name (defaults to the identifier)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Project root directory
+    pub path: PathBuf,
+}
+
+/// Pass/fail thresholds evaluated by `rust-adapter gate`
+///
+/// The adapter ships no policy of its own: every limit is unset by
+/// default and the gate passes until the user configures one. Unknown
+/// keys are rejected so a misspelled limit cannot silently pass.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GatingConfig {
+    /// Maximum number of findings allowed per severity
+    pub max_findings: SeverityLimits,
+    /// Whether drift of TCS packages is allowed
+    pub allow_tcs_drift: bool,
+    /// Minimum share of TCS packages with an audit, in percent
+    pub min_tcs_audit_coverage: Option<f64>,
+}
+
+/// Informational per-package risk scoring
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskConfig {
+    /// Number of highest-scoring packages kept in summaries
+    pub summary_limit: usize,
+    /// Days without a release after which a crate counts as stale
+    pub stale_after_days: u64,
+}
+
+/// Contents of the policy facts export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FactsConfig {
+    /// Whether package annotations are included in the exported facts
+    pub include_annotations: bool,
+}
+
+/// Per-severity limits; an unset severity is not limited
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeverityLimits {
+    /// Critical findings allowed
+    pub critical: Option<usize>,
+    /// High severity findings allowed
+    pub high: Option<usize>,
+    /// Medium severity findings allowed
+    pub medium: Option<usize>,
+    /// Low severity findings allowed
+    pub low: Option<usize>,
+    /// Informational findings allowed
+    pub info: Option<usize>,
+}
+
+/// External command attaching facts to packages during parsing
+///
+/// The command runs once per matching package, with `{name}`, `{version}`,
+/// `{source}` and `{checksum}` in its arguments replaced by the package's
+/// values. It prints a JSON object on stdout; each entry is attached to
+/// the package as a `<name>.<key>` annotation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FactCollectorConfig {
+    /// Collector name, prefixed to the annotation keys it sets
+    pub name: String,
+    /// Program followed by its arguments
+    pub command: Vec<String>,
+    /// Packages the collector runs for, by name, glob or `/regex/` (all if empty)
+    #[serde(default)]
+    pub packages: Vec<String>,
+    /// Time limit per package (seconds)
+    #[serde(default = "default_fact_collector_timeout")]
+    pub timeout: u64,
+    /// Whether a failing collector fails parsing rather than being skipped
+    #[serde(default)]
+    pub required: bool,
+}
+
+fn default_fact_collector_timeout() -> u64 {
+    30
+}
+
+/// Concurrency and resource limits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    /// Maximum number of external tools running at once
+    pub max_parallel_tool_invocations: usize,
+    /// Maximum number of threads used for checksum hashing
+    pub max_hashing_threads: usize,
+    /// Maximum size of `cargo metadata` output accepted for parsing (bytes)
+    pub metadata_memory_limit: u64,
+    /// Buffer size used for file reads (bytes)
+    pub io_buffer_size: usize,
+}
+
 impl Default for RustAdapterConfig {
     fn default() -> Self {
         Self {
@@ -114,6 +552,23 @@ impl Default for RustAdapterConfig {
             audit_config: AuditConfig::default(),
             classification_config: ClassificationConfig::default(),
             logging_config: LoggingConfig::default(),
+            performance_config: PerformanceConfig::default(),
+            registry_config: RegistryConfig::default(),
+            cache_config: CacheConfig::default(),
+            http_config: HttpConfig::default(),
+            digest_config: DigestConfig::default(),
+            telemetry_config: TelemetryConfig::default(),
+            deadline_config: DeadlineConfig::default(),
+            transparency_log_config: TransparencyLogConfig::default(),
+            bundle_config: BundleConfig::default(),
+            export_redaction_config: ExportRedactionConfig::default(),
+            org_config: OrgConfig::default(),
+            gating_config: GatingConfig::default(),
+            risk_config: RiskConfig::default(),
+            facts_config: FactsConfig::default(),
+            fact_collectors: Vec::new(),
+            drift_impact_model: ImpactModel::default(),
+            drift_detection_config: DriftDetectionConfig::default(),
             offline_mode: false,
             schema_validation: true,
         }
@@ -139,6 +594,24 @@ impl Default for VendorConfig {
             verify_checksums: true,
             malware_scan: false,
             compare_fresh: false,
+            delta_vendoring: false,
+            full_verification: false,
+            secret_scan: SecretScanConfig::default(),
+            registry_downloads: HashMap::new(),
+            verify_local_deps: true,
+        }
+    }
+}
+
+impl Default for SecretScanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            entropy_threshold: 4.0,
+            min_secret_length: 20,
+            max_file_size: 1024 * 1024,
+            allowed_paths: Vec::new(),
+            fail_on_findings: false,
         }
     }
 }
@@ -151,6 +624,8 @@ impl Default for AuditConfig {
             run_cargo_vet: true,
             cache_results: true,
             advisory_db_path: None,
+            min_tool_versions: default_min_tool_versions(),
+            audit_first_party: false,
         }
     }
 }
@@ -162,6 +637,12 @@ impl Default for ClassificationConfig {
             classify_build_deps: false,
             default_category: MechanicalCategory::Other("default".to_string()),
             confidence_threshold: 0.7,
+            custom_category_priorities: HashMap::new(),
+            result_cache_dir: None,
+            disable_result_cache: false,
+            category_mappings: HashMap::new(),
+            keyword_mappings: HashMap::new(),
+            max_tcs_adjacent_unknowns: None,
         }
     }
 }
@@ -177,11 +658,155 @@ impl Default for LoggingConfig {
     }
 }
 
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            fetch_popularity: false,
+            api_url: "https://crates.io/api/v1".to_string(),
+            cache_dir: None,
+            batch_size: 50,
+            request_interval_ms: 1000, // crates.io crawler policy: 1 request/second
+            index_dir: None,
+            metadata_cache_ttl: default_metadata_cache_ttl(),
+            metadata_cache_dir: None,
+            metadata_cache_read_only: false,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            shared_dir: None,
+            lock_timeout: 120, // 2 minutes
+            advisory_db_max_age: 3600, // 1 hour
+        }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: 30,
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            max_idle_per_host: 8,
+            proxy: None,
+            ca_bundle: None,
+        }
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: None,
+            service_name: "rust-ecosystem-adapter".to_string(),
+            export_interval: 60,
+        }
+    }
+}
+
+impl Default for DeadlineConfig {
+    fn default() -> Self {
+        Self {
+            parse: 300, // 5 minutes
+            classify: 120, // 2 minutes
+            audit: 1200, // 20 minutes
+            vendor: 1800, // 30 minutes
+            verify: 900, // 15 minutes
+            sbom: 300, // 5 minutes
+        }
+    }
+}
+
+impl Default for TransparencyLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            log_file: PathBuf::from(".rust-adapter/transparency-log.jsonl"),
+            signing_key: None,
+            trusted_public_key: None,
+        }
+    }
+}
+
+impl Default for ExportRedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            internal_packages: Vec::new(),
+            redact_path_packages: true,
+            redacted_locations: Vec::new(),
+            salt: String::new(),
+        }
+    }
+}
+
+impl Default for GatingConfig {
+    fn default() -> Self {
+        Self {
+            max_findings: SeverityLimits::default(),
+            allow_tcs_drift: true,
+            min_tcs_audit_coverage: None,
+        }
+    }
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            summary_limit: 10,
+            stale_after_days: 730,
+        }
+    }
+}
+
+impl Default for FactsConfig {
+    fn default() -> Self {
+        Self {
+            include_annotations: true,
+        }
+    }
+}
+
+impl FactCollectorConfig {
+    /// Check the name, command, timeout and package patterns
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.name.is_empty() || !self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err("Collector names must be non-empty and contain only letters, digits, '-' and '_'".to_string());
+        }
+        if self.command.first().is_none_or(String::is_empty) {
+            return Err("Command must name a program".to_string());
+        }
+        if self.timeout == 0 {
+            return Err("Timeout cannot be zero".to_string());
+        }
+        for pattern in &self.packages {
+            crate::utils::NamePattern::parse(pattern)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            max_parallel_tool_invocations: 2,
+            max_hashing_threads: 4,
+            metadata_memory_limit: 512 * 1024 * 1024, // 512 MiB
+            io_buffer_size: 64 * 1024, // 64 KiB
+        }
+    }
+}
+
 impl RustAdapterConfig {
     /// Load configuration from file
     pub fn load_from_file(path: &PathBuf) -> Result<Self> {
         let config_content = std::fs::read_to_string(path)
-            .map_err(|e| AdapterError::file_not_found(path, "reading config file"))?;
+            .map_err(|_e| AdapterError::file_not_found(path, "reading config file"))?;
         
         let config: RustAdapterConfig = toml::from_str(&config_content)
             .map_err(|e| AdapterError::ConfigurationInvalid {
@@ -200,18 +825,25 @@ impl RustAdapterConfig {
     }
     
     /// Load configuration with fallback to defaults
+    ///
+    /// A configuration that cannot be loaded is reported as a `tracing`
+    /// warning; use [`Self::load_or_default`] to handle it yourself.
     pub fn load_with_defaults(path: Option<&PathBuf>) -> Result<Self> {
-        match path {
-            Some(p) => {
-                match Self::load_from_file(p) {
-                    Ok(config) => Ok(config),
-                    Err(e) => {
-                        eprintln!("Warning: Invalid config at {:?}: {}, using defaults", p, e);
-                        Ok(Self::default())
-                    }
-                }
-            },
-            None => Ok(Self::default()),
+        let (config, fallback) = Self::load_or_default(path);
+        if let (Some(p), Some(e)) = (path, fallback) {
+            tracing::warn!(path = %p.display(), error = %e, "Invalid configuration, using defaults");
+        }
+        Ok(config)
+    }
+
+    /// Load configuration, falling back to defaults
+    ///
+    /// Returns the error that caused the fallback alongside the defaults.
+    pub fn load_or_default(path: Option<&PathBuf>) -> (Self, Option<AdapterError>) {
+        match path.map(Self::load_from_file) {
+            Some(Ok(config)) => (config, None),
+            Some(Err(e)) => (Self::default(), Some(e)),
+            None => (Self::default(), None),
         }
     }
     
@@ -236,6 +868,102 @@ impl RustAdapterConfig {
             });
         }
         
+        let override_keys = self.explicit_tcs_overrides.keys()
+            .map(|key| ("explicit_tcs_overrides", key))
+            .chain(self.vendor_config.secret_scan.allowed_paths.iter()
+                .map(|key| ("vendor_config.secret_scan.allowed_paths", key)))
+            .chain(self.export_redaction_config.internal_packages.iter()
+                .map(|key| ("export_redaction_config.internal_packages", key)))
+            .chain(self.export_redaction_config.redacted_locations.iter()
+                .map(|key| ("export_redaction_config.redacted_locations", key)));
+        for (table, key) in override_keys {
+            if let Err(reason) = crate::utils::NamePattern::parse(key) {
+                return Err(AdapterError::ConfigurationInvalid {
+                    field: format!("{}.{}", table, key),
+                    value: key.clone(),
+                    reason,
+                    source: anyhow::anyhow!("Invalid override pattern"),
+                });
+            }
+        }
+        
+        let mut org_project_ids = std::collections::HashSet::new();
+        if let Some(duplicate) = self.org_config.projects.iter().find(|p| !org_project_ids.insert(&p.id)) {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "org_config.projects".to_string(),
+                value: duplicate.id.clone(),
+                reason: "Project identifiers must be unique".to_string(),
+                source: anyhow::anyhow!("Duplicate project identifier"),
+            });
+        }
+
+        if let Some(coverage) = self.gating_config.min_tcs_audit_coverage {
+            if !(0.0..=100.0).contains(&coverage) {
+                return Err(AdapterError::ConfigurationInvalid {
+                    field: "gating_config.min_tcs_audit_coverage".to_string(),
+                    value: coverage.to_string(),
+                    reason: "Coverage must be a percentage between 0 and 100".to_string(),
+                    source: anyhow::anyhow!("Invalid coverage threshold"),
+                });
+            }
+        }
+
+        let performance = &self.performance_config;
+        if performance.max_parallel_tool_invocations == 0
+            || performance.max_hashing_threads == 0
+            || performance.metadata_memory_limit == 0
+            || performance.io_buffer_size == 0
+        {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "performance_config".to_string(),
+                value: format!("{:?}", performance),
+                reason: "Performance limits must be greater than zero".to_string(),
+                source: anyhow::anyhow!("Invalid performance limits"),
+            });
+        }
+        
+        if let Err(reason) = self.digest_config.validate() {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "digest_config.algorithm".to_string(),
+                value: self.digest_config.algorithm.as_str().to_string(),
+                reason,
+                source: anyhow::anyhow!("Weak digest algorithm"),
+            });
+        }
+        
+        if let Err(reason) = self.http_config.validate() {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "http_config".to_string(),
+                value: format!("{:?}", self.http_config),
+                reason,
+                source: anyhow::anyhow!("Invalid HTTP client settings"),
+            });
+        }
+        
+        let mut collector_names = std::collections::HashSet::new();
+        for collector in &self.fact_collectors {
+            let reason = match collector.validate() {
+                Err(reason) => reason,
+                Ok(()) if !collector_names.insert(&collector.name) => "Collector names must be unique".to_string(),
+                Ok(()) => continue,
+            };
+            return Err(AdapterError::ConfigurationInvalid {
+                field: format!("fact_collectors.{}", collector.name),
+                value: collector.command.join(" "),
+                reason,
+                source: anyhow::anyhow!("Invalid fact collector"),
+            });
+        }
+        
+        if let Err(reason) = self.drift_impact_model.validate() {
+            return Err(AdapterError::ConfigurationInvalid {
+                field: "drift_impact_model".to_string(),
+                value: String::new(),
+                reason,
+                source: anyhow::anyhow!("Invalid drift impact model"),
+            });
+        }
+        
         // Validate log level
         let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
         if !valid_log_levels.contains(&self.logging_config.level.as_str()) {
@@ -264,14 +992,14 @@ impl RustAdapterConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
+    
     
     #[test]
     fn test_default_config() {
         let config = RustAdapterConfig::default();
         
-        assert!(config.offline_mode == false);
-        assert!(config.schema_validation == true);
+        assert!(!config.offline_mode);
+        assert!(config.schema_validation);
         assert!(config.classification_config.classify_proc_macros);
         assert!(config.audit_config.run_cargo_audit);
         assert!(config.vendor_config.verify_checksums);
@@ -292,6 +1020,42 @@ mod tests {
         config.classification_config.confidence_threshold = 0.7;
         config.tool_paths.default_timeout = 0;
         assert!(config.validate().is_err());
+        
+        // Zero parallelism should fail
+        config.tool_paths.default_timeout = 300;
+        config.performance_config.max_hashing_threads = 0;
+        assert!(config.validate().is_err());
+        
+        // Invalid override pattern should fail
+        config.performance_config.max_hashing_threads = 4;
+        assert!(config.validate().is_ok());
+        config.explicit_tcs_overrides.insert("/(ring/".to_string(), TcsCategory::Cryptography);
+        assert!(config.validate().is_err());
+        
+        // Fact collectors need a command and unique names
+        config.explicit_tcs_overrides.clear();
+        let collector: FactCollectorConfig = toml::from_str("name = \"internal\"\ncommand = [\"internal-meta\", \"{name}\"]").unwrap();
+        assert_eq!(collector.timeout, 30);
+        config.fact_collectors = vec![collector.clone()];
+        assert!(config.validate().is_ok());
+        config.fact_collectors.push(collector);
+        assert!(config.validate().is_err());
+        config.fact_collectors = vec![FactCollectorConfig { command: vec![], ..config.fact_collectors[0].clone() }];
+        assert!(config.validate().is_err());
+        
+        // Impact thresholds must ascend and weights be non-negative
+        config.fact_collectors.clear();
+        config.drift_impact_model = toml::from_str("tcs_weight = 5.0\nminor_threshold = 50.0\nmajor_threshold = 200.0").unwrap();
+        assert!(config.validate().is_ok());
+        config.drift_impact_model.moderate_threshold = Some(300.0);
+        assert!(config.validate().is_err());
+        config.drift_impact_model = ImpactModel { removal_weight: -1.0, ..ImpactModel::default() };
+        assert!(config.validate().is_err());
+        
+        // Weak digest algorithms are rejected
+        config.drift_impact_model = ImpactModel::default();
+        config.digest_config = crate::utils::DigestConfig::hex(crate::utils::ChecksumAlgorithm::Md5);
+        assert!(config.validate().is_err());
     }
     
     #[test]
@@ -315,4 +1079,41 @@ mod tests {
         let result = RustAdapterConfig::load_with_defaults(None);
         assert!(result.is_ok()); // Should use defaults
     }
+
+    #[test]
+    fn test_gating_config_rejects_unknown_keys() {
+        let gating: GatingConfig = toml::from_str("allow_tcs_drift = false\n[max_findings]\ncritical = 0\n").unwrap();
+        assert_eq!(gating.max_findings.critical, Some(0));
+        assert_eq!(gating.max_findings.high, None);
+
+        assert!(toml::from_str::<GatingConfig>("[max_findings]\ncritcal = 0\n").is_err());
+    }
+
+    #[test]
+    fn test_audit_config_without_newer_fields_loads() {
+        let audit: AuditConfig = toml::from_str(
+            "audit_timeout = 60\nrun_cargo_audit = true\nrun_cargo_vet = false\ncache_results = true\n",
+        ).unwrap();
+        assert_eq!(audit.min_tool_versions, AuditConfig::default().min_tool_versions);
+        assert!(!audit.audit_first_party);
+    }
+
+    #[test]
+    fn test_partial_performance_config_loads() {
+        let performance: PerformanceConfig = toml::from_str("max_hashing_threads = 2\n").unwrap();
+        assert_eq!(performance.max_hashing_threads, 2);
+        assert_eq!(performance.metadata_memory_limit, PerformanceConfig::default().metadata_memory_limit);
+    }
+
+    #[test]
+    fn test_load_or_default_returns_fallback_error() {
+        let (mut config, fallback) = RustAdapterConfig::load_or_default(Some(&PathBuf::from("nonexistent.toml")));
+        let default = RustAdapterConfig::default();
+        config.sbom_config.created_at = default.sbom_config.created_at.clone();
+        assert_eq!(config, default);
+        assert!(matches!(fallback, Some(AdapterError::FileNotFound { .. })));
+
+        let (_, fallback) = RustAdapterConfig::load_or_default(None);
+        assert!(fallback.is_none());
+    }
 }
@@ -3,7 +3,7 @@
 //! This module defines structured error types with stable error codes,
 //! actionable guidance, and context-specific information.
 
-use std::{collections::HashMap, path::PathBuf, time::Duration};
+use std::{collections::HashMap, path::{Path, PathBuf}, time::Duration};
 use thiserror::Error;
 
 /// Result type alias for the adapter
@@ -46,6 +46,29 @@ pub enum AdapterError {
     ToolTimeout { 
         tool: String, 
         timeout: Duration,
+        /// Output the tool produced before it was stopped
+        partial_output: String,
+        #[source] 
+        source: anyhow::Error 
+    },
+    
+    #[error("Deadline exceeded: {operation} did not finish within {budget:?} ({stage}: {completed}/{total})")]
+    DeadlineExceeded {
+        operation: String,
+        budget: Duration,
+        /// Step the operation was in when the budget ran out
+        stage: String,
+        /// Items of that step completed
+        completed: usize,
+        /// Items of that step in total, 0 if unknown
+        total: usize,
+        #[source]
+        source: anyhow::Error
+    },
+    
+    #[error("Operation cancelled: {operation}")]
+    Cancelled { 
+        operation: String,
         #[source] 
         source: anyhow::Error 
     },
@@ -67,6 +90,14 @@ pub enum AdapterError {
         source: anyhow::Error 
     },
     
+    #[error("Cache entry locked: {path} after {timeout:?}")]
+    CacheLocked { 
+        path: PathBuf, 
+        timeout: Duration,
+        #[source] 
+        source: anyhow::Error 
+    },
+    
     #[error("Invalid path: {path} - {reason}")]
     InvalidPath { 
         path: String, 
@@ -93,7 +124,14 @@ pub enum AdapterError {
         source: anyhow::Error 
     },
     
-    #[error("Metadata parse error in field '{field}': {error}")]
+    #[error("Dependency graph validation failed with {} error(s)", .findings.len())]
+    GraphValidationFailed { 
+        findings: Vec<crate::models::GraphValidationFinding>,
+        #[source] 
+        source: anyhow::Error 
+    },
+    
+    #[error("Metadata parse error in field '{field}': {source}")]
     MetadataParseError { 
         field: String, 
         value: String,
@@ -133,6 +171,25 @@ pub enum AdapterError {
         source: anyhow::Error 
     },
     
+    /// Classification errors
+    #[error("{count} unclassified package(s) adjacent to TCS exceed the limit of {limit}")]
+    UnclassifiedTcsAdjacent { 
+        count: usize, 
+        limit: usize, 
+        packages: Vec<String>,
+        #[source] 
+        source: anyhow::Error 
+    },
+    
+    /// Remediation errors
+    #[error("Cannot remediate finding '{finding_id}': {reason}")]
+    RemediationUnavailable { 
+        finding_id: String, 
+        reason: String,
+        #[source] 
+        source: anyhow::Error 
+    },
+    
     /// Integrity errors
     #[error("Checksum mismatch for package '{package}': expected {expected}, got {actual}")]
     ChecksumMismatch { 
@@ -152,6 +209,14 @@ pub enum AdapterError {
         source: anyhow::Error 
     },
     
+    #[error("Bundle {bundle:?} failed verification: {reason}")]
+    BundleVerificationFailed {
+        bundle: PathBuf,
+        reason: String,
+        #[source]
+        source: anyhow::Error
+    },
+    
     #[error("Epoch invalidated: {epoch_id} - {reason}")]
     EpochInvalidated { 
         epoch_id: String, 
@@ -177,11 +242,16 @@ impl AdapterError {
             Self::EpochInvalidated { .. } => ErrorSeverity::Critical,
             Self::ToolNotFound { .. } => ErrorSeverity::High,
             Self::VendorVerificationFailed { .. } => ErrorSeverity::High,
+            Self::BundleVerificationFailed { .. } => ErrorSeverity::High,
             Self::ConfigurationInvalid { .. } => ErrorSeverity::Medium,
             Self::SchemaValidationFailed { .. } => ErrorSeverity::Medium,
+            Self::RemediationUnavailable { .. } => ErrorSeverity::Medium,
             Self::CargoLockParseError { .. } => ErrorSeverity::High,
+            Self::GraphValidationFailed { .. } => ErrorSeverity::High,
+            Self::UnclassifiedTcsAdjacent { .. } => ErrorSeverity::High,
             Self::ToolExecutionFailed { .. } => ErrorSeverity::High,
             Self::ToolTimeout { .. } => ErrorSeverity::High,
+            Self::DeadlineExceeded { .. } => ErrorSeverity::High,
             Self::NetworkTimeout { .. } => ErrorSeverity::Medium,
             Self::RegistryUnavailable { .. } => ErrorSeverity::Medium,
             _ => ErrorSeverity::Low,
@@ -194,18 +264,25 @@ impl AdapterError {
             Self::ToolNotFound { .. } => "TOOL_NOT_FOUND",
             Self::ToolExecutionFailed { .. } => "TOOL_EXECUTION_FAILED",
             Self::ToolTimeout { .. } => "TOOL_TIMEOUT",
+            Self::DeadlineExceeded { .. } => "DEADLINE_EXCEEDED",
+            Self::Cancelled { .. } => "OPERATION_CANCELLED",
             Self::FileNotFound { .. } => "FILE_NOT_FOUND",
             Self::PermissionDenied { .. } => "PERMISSION_DENIED",
+            Self::CacheLocked { .. } => "CACHE_LOCKED",
             Self::InvalidPath { .. } => "INVALID_PATH",
             Self::CargoLockParseError { .. } => "CARGO_LOCK_PARSE_ERROR",
             Self::CargoTomlParseError { .. } => "CARGO_TOML_PARSE_ERROR",
+            Self::GraphValidationFailed { .. } => "GRAPH_VALIDATION_FAILED",
             Self::MetadataParseError { .. } => "METADATA_PARSE_ERROR",
             Self::NetworkTimeout { .. } => "NETWORK_TIMEOUT",
             Self::RegistryUnavailable { .. } => "REGISTRY_UNAVAILABLE",
             Self::ConfigurationInvalid { .. } => "CONFIGURATION_INVALID",
             Self::SchemaValidationFailed { .. } => "SCHEMA_VALIDATION_FAILED",
+            Self::UnclassifiedTcsAdjacent { .. } => "UNCLASSIFIED_TCS_ADJACENT",
+            Self::RemediationUnavailable { .. } => "REMEDIATION_UNAVAILABLE",
             Self::ChecksumMismatch { .. } => "CHECKSUM_MISMATCH",
             Self::VendorVerificationFailed { .. } => "VENDOR_VERIFICATION_FAILED",
+            Self::BundleVerificationFailed { .. } => "BUNDLE_VERIFICATION_FAILED",
             Self::EpochInvalidated { .. } => "EPOCH_INVALIDATED",
             Self::Internal { .. } => "INTERNAL_ERROR",
         }
@@ -229,11 +306,29 @@ impl AdapterError {
                 format!("Try running with appropriate privileges for: {}", operation),
                 "Consider using a different directory".to_string(),
             ],
+            Self::InvalidPath { path, .. } => vec![
+                format!("Check the path: {}", path),
+                "Run the command inside a Rust project or pass --project <path>".to_string(),
+            ],
             Self::CargoLockParseError { file, line, .. } => vec![
                 format!("Check Cargo.lock syntax at line {} in {}", line, file.display()),
                 "Try running 'cargo generate-lockfile' to regenerate".to_string(),
                 "Ensure Cargo.lock is not corrupted".to_string(),
             ],
+            Self::GraphValidationFailed { findings, .. } => findings.iter()
+                .map(|finding| finding.message.clone())
+                .chain(["Regenerate Cargo.lock with 'cargo generate-lockfile' and parse again".to_string()])
+                .collect(),
+            Self::UnclassifiedTcsAdjacent { packages, limit, .. } => vec![
+                format!("Classify these packages: {}", packages.join(", ")),
+                "Add them to explicit_tcs_overrides or a project TCS list, or map their crates.io categories in classification_config".to_string(),
+                format!("Raise classification_config.max_tcs_adjacent_unknowns (currently {}) to accept them", limit),
+                "Run 'rust-adapter classify --unknown-only' to see the signals evaluated for each".to_string(),
+            ],
+            Self::RemediationUnavailable { finding_id, .. } => vec![
+                format!("Run 'rust-adapter audit' to check that {} still applies to a locked version", finding_id),
+                "Findings without a patched release need a replacement crate or a reviewed exemption".to_string(),
+            ],
             Self::ChecksumMismatch { package, expected, actual, .. } => vec![
                 format!("Potential supply chain attack detected for package: {}", package),
                 format!("Expected checksum: {}", expected),
@@ -241,6 +336,25 @@ impl AdapterError {
                 "Do NOT update vendored copy. Investigate immediately.".to_string(),
                 "Consider re-vendoring from a trusted network".to_string(),
             ],
+            Self::BundleVerificationFailed { bundle, .. } => vec![
+                format!("Do NOT install {}; obtain a new copy through the approved transfer channel", bundle.display()),
+                "Check that the signing key of the preparing host is in bundle_config.trusted_public_keys".to_string(),
+            ],
+            Self::ToolTimeout { tool, timeout, .. } => vec![
+                format!("{} did not finish within {:?}", tool, timeout),
+                "Increase vendor_config.vendor_timeout or audit_config.audit_timeout".to_string(),
+                "Check whether the tool is blocked waiting on the network or a lock".to_string(),
+            ],
+            Self::DeadlineExceeded { operation, budget, stage, .. } => vec![
+                format!("{} was stopped during '{}' after its {:?} budget", operation, stage, budget),
+                format!("Increase deadline_config.{} or set it to 0 to disable the budget", operation),
+                "Check whether the operation is blocked waiting on the network or a lock".to_string(),
+            ],
+            Self::CacheLocked { path, timeout, .. } => vec![
+                format!("Another adapter run held {} for longer than {:?}", path.display(), timeout),
+                "Increase cache_config.lock_timeout if concurrent runs are expected".to_string(),
+                "Run 'rust-adapter cache status' to see which entries are locked".to_string(),
+            ],
             Self::NetworkTimeout { operation, .. } => vec![
                 format!("Check network connectivity for operation: {}", operation),
                 "Try increasing timeout in configuration".to_string(),
@@ -268,6 +382,24 @@ impl AdapterError {
             Self::ToolNotFound { tool, .. } => {
                 context.insert("tool".to_string(), tool.clone());
             },
+            Self::ToolTimeout { tool, timeout, .. } => {
+                context.insert("tool".to_string(), tool.clone());
+                context.insert("timeout".to_string(), format!("{:?}", timeout));
+            },
+            Self::DeadlineExceeded { operation, budget, stage, completed, total, .. } => {
+                context.insert("operation".to_string(), operation.clone());
+                context.insert("budget".to_string(), format!("{:?}", budget));
+                context.insert("stage".to_string(), stage.clone());
+                context.insert("completed".to_string(), completed.to_string());
+                context.insert("total".to_string(), total.to_string());
+            },
+            Self::Cancelled { operation, .. } => {
+                context.insert("operation".to_string(), operation.clone());
+            },
+            Self::CacheLocked { path, timeout, .. } => {
+                context.insert("path".to_string(), path.display().to_string());
+                context.insert("timeout".to_string(), format!("{:?}", timeout));
+            },
             Self::FileNotFound { path, context: ctx, .. } => {
                 context.insert("path".to_string(), path.display().to_string());
                 context.insert("context".to_string(), ctx.clone());
@@ -277,6 +409,18 @@ impl AdapterError {
                 context.insert("line".to_string(), line.to_string());
                 context.insert("parse_error".to_string(), error.clone());
             },
+            Self::UnclassifiedTcsAdjacent { count, limit, .. } => {
+                context.insert("count".to_string(), count.to_string());
+                context.insert("limit".to_string(), limit.to_string());
+            },
+            Self::RemediationUnavailable { finding_id, reason, .. } => {
+                context.insert("finding_id".to_string(), finding_id.clone());
+                context.insert("reason".to_string(), reason.clone());
+            },
+            Self::BundleVerificationFailed { bundle, reason, .. } => {
+                context.insert("bundle".to_string(), bundle.display().to_string());
+                context.insert("reason".to_string(), reason.clone());
+            },
             Self::ChecksumMismatch { package, expected, actual, .. } => {
                 context.insert("package".to_string(), package.clone());
                 context.insert("expected_checksum".to_string(), expected.clone());
@@ -298,31 +442,49 @@ impl AdapterError {
         }
     }
     
-    pub fn file_not_found(path: &PathBuf, context: &str) -> Self {
+    pub fn file_not_found(path: &Path, context: &str) -> Self {
         Self::FileNotFound {
-            path: path.clone(),
+            path: path.to_path_buf(),
             context: context.to_string(),
             source: anyhow::anyhow!("File not found: {}", path.display()),
         }
     }
     
-    pub fn permission_denied(path: &PathBuf, operation: &str) -> Self {
+    pub fn permission_denied(path: &Path, operation: &str) -> Self {
         Self::PermissionDenied {
-            path: path.clone(),
+            path: path.to_path_buf(),
             operation: operation.to_string(),
             source: anyhow::anyhow!("Permission denied for {}", operation),
         }
     }
     
-    pub fn cargo_lock_parse_error(file: &PathBuf, line: usize, error: &str) -> Self {
+    pub fn cargo_lock_parse_error(file: &Path, line: usize, error: &str) -> Self {
         Self::CargoLockParseError {
-            file: file.clone(),
+            file: file.to_path_buf(),
             line,
             error: error.to_string(),
             source: anyhow::anyhow!("Parse error at line {}: {}", line, error),
         }
     }
     
+    pub fn cancelled(operation: &str) -> Self {
+        Self::Cancelled {
+            operation: operation.to_string(),
+            source: anyhow::anyhow!("Cancellation requested"),
+        }
+    }
+    
+    pub fn deadline_exceeded(operation: &str, budget: Duration, stage: &str, completed: usize, total: usize) -> Self {
+        Self::DeadlineExceeded {
+            operation: operation.to_string(),
+            budget,
+            stage: stage.to_string(),
+            completed,
+            total,
+            source: anyhow::anyhow!("Operation budget of {:?} spent", budget),
+        }
+    }
+    
     pub fn checksum_mismatch(package: &str, expected: &str, actual: &str) -> Self {
         Self::ChecksumMismatch {
             package: package.to_string(),
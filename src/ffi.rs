@@ -0,0 +1,206 @@
+//! C ABI for non-Rust tooling
+//!
+//! Every entry point takes a JSON request as a NUL-terminated UTF-8 string
+//! and returns a JSON response allocated by the adapter, which the caller
+//! must release with `rust_adapter_free_string`. Responses have the shape
+//! `{"ok": true, "result": ...}` or `{"ok": false, "error": "..."}`;
+//! only a NULL request yields a NULL response.
+//!
+//! Requests name the project root and, optionally, a configuration file:
+//!
+//! ```json
+//! {"project_path": "/path/to/project", "config_path": "rust-adapter.toml"}
+//! ```
+//!
+//! `rust_adapter_detect_drift` additionally takes the expected `epoch`.
+//! The declarations for C callers are in `include/rust_adapter.h`; Python
+//! can load the library with `ctypes` against the same declarations.
+//! Build the shared library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+//!
+//! The ABI is versioned by `RUST_ADAPTER_ABI_VERSION`, which is bumped on
+//! any incompatible change to function signatures or request shapes.
+
+use crate::adapter::rust_adapter::EcosystemAdapter;
+use crate::adapter::sbom_generator::Sbom;
+use crate::config::{load_project_owners, RustAdapterConfig};
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use crate::RustAdapter;
+use serde::Deserialize;
+use std::ffi::{c_char, CStr, CString};
+use std::path::PathBuf;
+
+/// Version of the C ABI
+pub const RUST_ADAPTER_ABI_VERSION: u32 = 1;
+
+/// Request common to all entry points
+#[derive(Debug, Deserialize)]
+struct ProjectRequest {
+    /// Project root directory
+    project_path: PathBuf,
+    /// Adapter configuration file (defaults are used when absent)
+    #[serde(default)]
+    config_path: Option<PathBuf>,
+}
+
+/// Drift detection request
+#[derive(Debug, Deserialize)]
+struct DriftRequest {
+    /// Project to compare
+    #[serde(flatten)]
+    project: ProjectRequest,
+    /// Epoch the current dependencies are compared against
+    epoch: Epoch,
+}
+
+/// Version of the C ABI implemented by this library
+#[no_mangle]
+pub extern "C" fn rust_adapter_abi_version() -> u32 {
+    RUST_ADAPTER_ABI_VERSION
+}
+
+/// Parse and classify the dependency graph of a project
+///
+/// # Safety
+///
+/// `request` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rust_adapter_parse_dependencies(request: *const c_char) -> *mut c_char {
+    call(request, |request: ProjectRequest| async move {
+        let (adapter, project) = request.open()?;
+        adapter.parse_dependencies(&project).await
+    })
+}
+
+/// Generate an SBOM in the configured format
+///
+/// # Safety
+///
+/// `request` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rust_adapter_generate_sbom(request: *const c_char) -> *mut c_char {
+    call(request, |request: ProjectRequest| async move {
+        let (adapter, project) = request.open()?;
+        let sbom: Sbom = adapter.generate_sbom(&project).await?;
+        Ok(sbom)
+    })
+}
+
+/// Detect drift of the current dependencies from an epoch
+///
+/// # Safety
+///
+/// `request` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rust_adapter_detect_drift(request: *const c_char) -> *mut c_char {
+    call(request, |request: DriftRequest| async move {
+        let (adapter, project) = request.project.open()?;
+        let dependency_graph = adapter.parse_dependencies(&project).await?;
+        let mut drift_report = adapter.detect_drift(&request.epoch, &dependency_graph).await?;
+        
+        let owners = load_project_owners(&project)?;
+        adapter.drift_detector().attribute_drifts(&mut drift_report, &dependency_graph, &owners);
+        Ok(drift_report)
+    })
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+///
+/// `value` must be NULL or a pointer returned by this library that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rust_adapter_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+impl ProjectRequest {
+    /// Create the adapter and project described by the request
+    fn open(&self) -> Result<(RustAdapter, Project)> {
+        let config = RustAdapterConfig::load_with_defaults(self.config_path.as_ref())?;
+        let project = Project::new(
+            "ffi-project".to_string(),
+            "FFI Project".to_string(),
+            "rust".to_string(),
+            self.project_path.clone(),
+        );
+        
+        Ok((RustAdapter::new(config), project))
+    }
+}
+
+/// Decode a request, run an operation to completion and encode the response
+///
+/// Panics are caught so that they never unwind into the caller.
+unsafe fn call<R, F, Fut, T>(request: *const c_char, operation: F) -> *mut c_char
+where
+    R: serde::de::DeserializeOwned,
+    F: FnOnce(R) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+    T: serde::Serialize,
+{
+    if request.is_null() {
+        return std::ptr::null_mut();
+    }
+    let request = CStr::from_ptr(request).to_string_lossy().into_owned();
+    
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let request: R = serde_json::from_str(&request)
+            .map_err(|e| format!("Invalid request: {}", e))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("Failed to start runtime: {}", e))?;
+        
+        let result = runtime.block_on(operation(request)).map_err(|e: AdapterError| e.to_string())?;
+        serde_json::to_value(result).map_err(|e| format!("Failed to encode result: {}", e))
+    }));
+    
+    let response = match outcome {
+        Ok(Ok(result)) => serde_json::json!({ "ok": true, "result": result }),
+        Ok(Err(error)) => serde_json::json!({ "ok": false, "error": error }),
+        Err(_) => serde_json::json!({ "ok": false, "error": "Internal error: adapter panicked" }),
+    };
+    
+    // JSON text never contains NUL bytes
+    CString::new(response.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Call an entry point with a request and decode the response
+    fn invoke(entry: unsafe extern "C" fn(*const c_char) -> *mut c_char, request: &str) -> serde_json::Value {
+        let request = CString::new(request).unwrap();
+        unsafe {
+            let response = entry(request.as_ptr());
+            let value = serde_json::from_str(CStr::from_ptr(response).to_str().unwrap()).unwrap();
+            rust_adapter_free_string(response);
+            value
+        }
+    }
+    
+    #[test]
+    fn test_error_responses() {
+        let response = invoke(rust_adapter_parse_dependencies, "not json");
+        assert_eq!(response["ok"], false);
+        assert!(response["error"].as_str().unwrap().starts_with("Invalid request"));
+        
+        let missing = r#"{"project_path": "/nonexistent/rust-adapter-ffi"}"#;
+        let response = invoke(rust_adapter_parse_dependencies, missing);
+        assert_eq!(response["ok"], false);
+        
+        unsafe {
+            assert!(rust_adapter_parse_dependencies(std::ptr::null()).is_null());
+            rust_adapter_free_string(std::ptr::null_mut());
+        }
+        assert_eq!(rust_adapter_abi_version(), RUST_ADAPTER_ABI_VERSION);
+    }
+}
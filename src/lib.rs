@@ -14,21 +14,39 @@
 //! 
 //! # Example Usage
 //! 
-//! ```rust
-//! use rust_ecosystem_adapter::{RustAdapter, RustAdapterConfig};
+//! ```no_run
+//! use rust_ecosystem_adapter::adapter::rust_adapter::EcosystemAdapter;
+//! use rust_ecosystem_adapter::{Project, RustAdapter, RustAdapterConfig};
 //! 
+//! # async fn example() -> rust_ecosystem_adapter::Result<()> {
 //! let config = RustAdapterConfig::default();
 //! let adapter = RustAdapter::new(config);
 //! 
-//! let project = Project::new("/path/to/rust/project")?;
+//! let project = Project::new(
+//!     "my-project".to_string(),
+//!     "My Project".to_string(),
+//!     "rust".to_string(),
+//!     "/path/to/rust/project".into(),
+//! );
 //! let dependency_graph = adapter.parse_dependencies(&project).await?;
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod adapter;
 pub mod config;
 pub mod error;
+pub mod manifest;
 pub mod models;
 pub mod utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(test)]
+mod test_support;
 
 pub use adapter::RustAdapter;
 pub use config::RustAdapterConfig;
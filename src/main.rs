@@ -3,9 +3,19 @@
 //! This module provides the command-line interface for the adapter,
 //! allowing users to run various operations from the command line.
 
-use clap::{Parser, Subcommand};
-use rust_ecosystem_adapter::{RustAdapter, RustAdapterConfig, Project};
-use std::path::PathBuf;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use rust_ecosystem_adapter::{AdapterError, RustAdapter, RustAdapterConfig, Project};
+use rust_ecosystem_adapter::adapter::audit_scheduler::{AuditScheduler, ReportDirectorySink};
+use rust_ecosystem_adapter::adapter::rust_adapter::EcosystemAdapter;
+use rust_ecosystem_adapter::adapter::tcs_classifier::TcsClassifier;
+use rust_ecosystem_adapter::adapter::transparency_log::TransparencyLog;
+use rust_ecosystem_adapter::config::OverrideEditor;
+use rust_ecosystem_adapter::manifest::discover_project_root;
+use rust_ecosystem_adapter::models::{AdapterEvent, ArtifactEntry, BinaryProvenance, ChangePlan, ClassifierBenchReport, CorpusCrate, DoctorStatus, DependencyGraph, DriftQuery, DriftReport, MirrorSource, PolicyFacts, ProjectOwners, ProposedUpdate, ReportKind, ReportSummary, SbomEncoding, SbomScope, SignalEvaluation, TcsCategory, VendorFileChangeKind, VersionSkewKind};
+use rust_ecosystem_adapter::utils::{telemetry, EventSink, ChecksumCalculator, DigestConfig, GraphFilter, GraphView, NamePattern, ReportTemplate, SharedCache};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 /// Rust Ecosystem Adapter CLI
 #[derive(Parser, Debug)]
@@ -24,6 +34,26 @@ pub struct Cli {
     #[arg(short, long, default_value = "info")]
     log_level: String,
     
+    /// Print what vendor, snapshot, archive, restore, override and cache
+    /// commands would change without writing anything
+    #[arg(long, global = true)]
+    dry_run: bool,
+    
+    /// Redact internal package names, local paths and configured URLs in
+    /// exported graphs, SBOMs and drift timelines
+    #[arg(long, global = true)]
+    redact: bool,
+    
+    /// List the artifacts written by this run, with sizes and SHA-256
+    /// digests, in this manifest (entries of an existing manifest are kept)
+    #[arg(long, global = true)]
+    manifest: Option<PathBuf>,
+    
+    /// Stream NDJSON progress and result events to this file (`-` for
+    /// stdout, sending human-readable output and logs to stderr instead)
+    #[arg(long, global = true)]
+    events: Option<PathBuf>,
+    
     /// Command to run
     #[command(subcommand)]
     command: Commands,
@@ -33,239 +63,2656 @@ pub struct Cli {
 pub enum Commands {
     /// Parse dependencies from Cargo.lock
     Parse {
-        /// Project path
+        /// Project path (discovered from the current directory if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        project: Option<PathBuf>,
+        /// Read Cargo.lock content from stdin instead of a project directory
+        #[arg(long, conflicts_with = "project")]
+        stdin: bool,
+        /// Output layout (default: json with --stdin, tree otherwise)
+        #[arg(long, value_parser = ["tree", "table", "json"])]
+        format: Option<String>,
+        /// Only show TCS packages (and the paths leading to them)
+        #[arg(long)]
+        only_tcs: bool,
+        /// Only show packages fetched from git
+        #[arg(long)]
+        only_git: bool,
+        /// Maximum depth below the workspace members
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Only show this classification (crypto, auth, ..., custom:<name>, mechanical, unknown)
+        #[arg(long)]
+        classification: Option<String>,
+        /// Lockfile (or its directory) relative to the project root to include; repeatable
+        #[arg(long = "lockfile", conflicts_with = "stdin")]
+        lockfiles: Vec<PathBuf>,
+        /// Include every Cargo.lock below the project root (excluded members, nested crates)
+        #[arg(long, conflicts_with_all = ["stdin", "lockfiles"])]
+        all_lockfiles: bool,
+    },
+    /// Explain TCS classification decisions
+    Classify {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Only explain this package
+        #[arg(long, conflicts_with = "diff")]
+        package: Option<String>,
+        /// Only list packages no classification signal matched
+        #[arg(long, conflicts_with_all = ["package", "diff"])]
+        unknown_only: bool,
+        /// Write the classified dependency graph (JSON) to this file
+        #[arg(long)]
+        snapshot: Option<PathBuf>,
+        /// Compare classifications with a graph snapshot instead of explaining them
+        #[arg(long)]
+        diff: Option<PathBuf>,
+        /// Snapshot to compare against instead of the current classification
+        #[arg(long, requires = "diff")]
+        against: Option<PathBuf>,
+    },
+    /// Developer commands for the TCS classification ruleset
+    Classifier {
+        /// Classifier action
+        #[command(subcommand)]
+        action: ClassifierAction,
+    },
+    /// Manage explicit TCS overrides in the config file
+    Override {
+        /// Override action
+        #[command(subcommand)]
+        action: OverrideAction,
     },
     /// Run security audit
     Audit {
-        /// Project path
+        /// Project path (discovered from the current directory if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        project: Option<PathBuf>,
+        /// Write the audit report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Render the report through this MiniJinja template instead of JSON
+        #[arg(long)]
+        template: Option<PathBuf>,
+        /// Audit binaries built with cargo-auditable instead of the project (repeatable)
+        #[arg(long = "binary", conflicts_with = "template")]
+        binaries: Vec<PathBuf>,
+        /// Epoch the binaries' embedded dependencies must match (defaults to Cargo.lock)
+        #[arg(short, long, requires = "binaries")]
+        epoch: Option<String>,
+    },
+    /// Check supply chain status: audit findings, proofs and TCS audit coverage
+    SupplyChain {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Write the supply chain report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Render the report through this MiniJinja template instead of JSON
+        #[arg(long)]
+        template: Option<PathBuf>,
+        /// Verify the provenance of release binaries built with cargo-auditable (repeatable)
+        #[arg(long = "binary")]
+        binaries: Vec<PathBuf>,
+        /// Epoch the binaries' embedded dependencies must match (defaults to Cargo.lock)
+        #[arg(short, long, requires = "binaries")]
+        epoch: Option<String>,
     },
     /// Generate SBOM
     Sbom {
-        /// Project path
+        /// Project path (discovered from the current directory if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        project: Option<PathBuf>,
         /// Output file
         #[arg(short, long)]
         output: Option<PathBuf>,
         /// SBOM format
         #[arg(short, long, default_value = "spdx")]
         format: String,
+        /// External SBOM fragment to merge (repeatable)
+        #[arg(long)]
+        merge: Vec<PathBuf>,
+        /// Scope profile: distribution, development or full (overrides config)
+        #[arg(long)]
+        scope: Option<String>,
+        /// Output encoding: json, xml or tag-value (defaults to the output file extension)
+        #[arg(long)]
+        encoding: Option<String>,
     },
     /// Vendor dependencies
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Vendor {
-        /// Project path
+        /// Vendor archive action
+        #[command(subcommand)]
+        action: Option<VendorAction>,
+        /// Project path (discovered from the current directory if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        project: Option<PathBuf>,
         /// Output directory
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Re-vendor only packages changed since the last vendoring
+        #[arg(long)]
+        delta: bool,
+    },
+    /// Build offline from the vendor snapshot pinned to an epoch
+    Build {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Epoch ID
+        #[arg(short, long)]
+        epoch: String,
+        /// Extra arguments passed to `cargo build`
+        #[arg(last = true)]
+        cargo_args: Vec<String>,
     },
     /// Verify vendored dependencies
     VerifyVendor {
-        /// Project path
+        /// Project path (discovered from the current directory if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        project: Option<PathBuf>,
         /// Vendored directory path
         #[arg(short, long)]
         vendored: PathBuf,
+        /// Hash every package instead of resuming an interrupted verification
+        #[arg(long)]
+        no_resume: bool,
+        /// Scan vendored sources for embedded secrets
+        #[arg(long)]
+        scan_secrets: bool,
     },
     /// Detect dependency drift
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Drift {
-        /// Project path
+        /// Export or acknowledge drift reports for review
+        #[command(subcommand)]
+        action: Option<DriftAction>,
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Expected epoch ID
+        #[arg(short, long, required_unless_present = "since")]
+        epoch: Option<String>,
+        /// Build a drift timeline from this epoch through all later epochs
+        #[arg(long, conflicts_with = "epoch")]
+        since: Option<String>,
+        /// Output file for the drift report or timeline (JSON)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Group drifts by workspace member or owning team
+        #[arg(long, value_parser = ["member", "team"], conflicts_with = "since")]
+        group_by: Option<String>,
+        /// Render the drift report through this MiniJinja template instead of JSON
+        #[arg(long, conflicts_with = "since")]
+        template: Option<PathBuf>,
+        /// Only report drifts of these priorities (comma-separated: critical, high, medium, low)
+        #[arg(long, value_delimiter = ',', conflicts_with = "since")]
+        priority: Vec<String>,
+        /// Only report these change types (comma-separated: addition, removal, version-change, source-change, multiple-changes)
+        #[arg(long, value_delimiter = ',', conflicts_with = "since")]
+        change_type: Vec<String>,
+        /// Only report drifts of these classifications (comma-separated: tcs, mechanical, unknown)
+        #[arg(long, value_delimiter = ',', conflicts_with = "since")]
+        classification: Vec<String>,
+        /// Only report packages matching this name, glob or /regex/ (repeatable)
+        #[arg(long, conflicts_with = "since")]
+        package: Vec<String>,
+        /// Skip this many matching drifts, most urgent first
+        #[arg(long, default_value_t = 0, conflicts_with_all = ["since", "group_by"])]
+        offset: usize,
+        /// Report at most this many matching drifts
+        #[arg(long, conflicts_with_all = ["since", "group_by"])]
+        limit: Option<usize>,
+        /// Report counts and impact only, without drift items
+        #[arg(long, conflicts_with_all = ["since", "group_by"])]
+        summary_only: bool,
+    },
+    /// Compare two Cargo.lock files directly, without a project
+    LockDiff {
+        /// Lockfile before the change
+        before: PathBuf,
+        /// Lockfile after the change
+        after: PathBuf,
+        /// Output file for the drift report (JSON)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Render the drift report through this MiniJinja template instead of JSON
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+    /// Export policy facts as JSON input for OPA/Rego or CUE
+    Facts {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Epoch whose vendor snapshot is verified and included
+        #[arg(short, long)]
+        epoch: Option<String>,
+        /// Skip the security audit
+        #[arg(long)]
+        no_audit: bool,
+    },
+    /// Exchange epochs with the Control Plane in its universal format
+    Epoch {
+        /// Epoch action
+        #[command(subcommand)]
+        action: EpochAction,
+    },
+    /// Inspect the transparency log of supply-chain events
+    Log {
+        /// Log action
+        #[command(subcommand)]
+        action: LogAction,
+    },
+    /// Report crates executing code at build time (proc-macros, build scripts)
+    BuildReport {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Vendor directory to scan (defaults to the project vendor directory)
+        #[arg(short, long, conflicts_with = "epoch")]
+        vendored: Option<PathBuf>,
+        /// Epoch whose verified vendor snapshot is scanned
+        #[arg(short, long)]
+        epoch: Option<String>,
+        /// Write the JSON report to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Collect vendored license and notice files into a THIRD-PARTY-NOTICES bundle
+    Attribution {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Vendor directory to collect from (defaults to the project vendor directory)
+        #[arg(short, long, conflicts_with = "epoch")]
+        vendored: Option<PathBuf>,
+        /// Epoch whose verified vendor snapshot is collected from
+        #[arg(short, long)]
+        epoch: Option<String>,
+        /// Notices text file
+        #[arg(short, long, default_value = "THIRD-PARTY-NOTICES.txt")]
+        output: PathBuf,
+        /// JSON index of packages and license texts (defaults to the notices file with a .json extension)
+        #[arg(long)]
+        index: Option<PathBuf>,
+    },
+    /// Render a status badge from the latest recorded report
+    Badge {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Report the badge describes
+        #[arg(long, value_parser = ["audit", "drift", "sbom"])]
+        kind: String,
+        /// Badge format (SVG image or shields.io endpoint JSON)
+        #[arg(short, long, value_parser = ["svg", "json"], default_value = "svg")]
+        format: String,
+        /// Left-hand badge text (defaults to the report kind)
+        #[arg(long)]
+        label: Option<String>,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect or clean the cache shared by concurrent runs
+    Cache {
+        /// Cache action
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Install security data bundles prepared for air-gapped sites
+    Bundle {
+        /// Bundle action
+        #[command(subcommand)]
+        action: BundleAction,
+    },
+    /// Report declared version requirements at odds with Cargo.lock
+    Skew {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Write the JSON report to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the drift proposed updates would cause, without applying them
+    Simulate {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Update to simulate as <crate>@<version> (repeatable)
+        #[arg(short, long = "update", required = true)]
+        updates: Vec<ProposedUpdate>,
+        /// Write the simulated graph and drift report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write the sources an artifact mirror must pre-fetch to rebuild an epoch
+    MirrorManifest {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Epoch ID
+        #[arg(short, long)]
+        epoch: String,
+        /// Write the manifest (JSON) to this file (printed if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Produce a patch upgrading past an audit finding, for a bot to open as a pull request
+    Remediate {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Advisory ID or alias to remediate
+        #[arg(short, long)]
+        finding: String,
+        /// Write the unified diff to this file (printed if omitted)
         #[arg(short, long)]
-        project: PathBuf,
+        output: Option<PathBuf>,
+        /// Write the remediation summary (JSON) to this file
+        #[arg(short, long)]
+        summary: Option<PathBuf>,
+    },
+    /// Verify artifacts against a run manifest written with `--manifest`
+    VerifyArtifacts {
+        /// Run manifest
+        #[arg(value_name = "MANIFEST")]
+        manifest_file: PathBuf,
+    },
+    /// Check the gating thresholds of the config against generated reports
+    Gate {
+        /// Policy facts (JSON) written by `rust-adapter facts`
+        #[arg(long, required_unless_present = "drift")]
+        facts: Option<PathBuf>,
+        /// Drift report (JSON) written by `rust-adapter drift --epoch <id> --output`
+        #[arg(long)]
+        drift: Option<PathBuf>,
+        /// Write the gate verdict (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Check tools, configuration and project files before running an analysis
+    Doctor {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Output layout
+        #[arg(short, long, value_parser = ["text", "json"], default_value = "text")]
+        format: String,
+        /// Write the doctor report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Re-audit projects periodically and whenever the advisory database changes
+    Serve {
+        /// Project paths (the project of the current directory if omitted)
+        #[arg(short, long)]
+        project: Vec<PathBuf>,
+        /// Seconds between audits of each project
+        #[arg(long, default_value_t = 3600)]
+        interval: u64,
+        /// Seconds between checks for due audits and advisory database updates
+        #[arg(long, default_value_t = 60)]
+        poll: u64,
+        /// Directory keeping the latest audit report of each project and a log of runs
+        #[arg(short, long, default_value = ".rust-adapter/scheduled-audits")]
+        output: PathBuf,
+    },
+    /// Organization-wide commands over the projects listed in the config
+    Org {
+        #[command(subcommand)]
+        action: OrgAction,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to complete for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Generate man pages
+    Man {
+        /// Write rust-adapter.1 and a page per subcommand into this directory
+        /// (prints rust-adapter.1 to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Review drift and findings interactively; decisions are checked by `vendor snapshot`
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Epoch drift is computed from (defaults to the most recent epoch)
+        #[arg(long)]
+        since: Option<String>,
+        /// Epoch vendored sources are diffed against (defaults to the most recent epoch)
+        #[arg(long)]
+        to: Option<String>,
+        /// Reviewer recorded with decisions
+        #[arg(long)]
+        reviewer: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VendorAction {
+    /// Create a reproducible compressed archive of a vendor directory
+    Archive {
+        /// Vendor directory to archive
+        #[arg(short, long, default_value = "vendor")]
+        vendored: PathBuf,
+        /// Archive path (defaults to vendor-<epoch>.tar.zst)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Epoch ID recorded in the archive manifest
+        #[arg(short, long)]
+        epoch: Option<String>,
+    },
+    /// Pin the vendor directory and Cargo.lock to an epoch
+    Snapshot {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Vendor directory to pin
+        #[arg(short, long, default_value = "vendor")]
+        vendored: PathBuf,
+        /// Epoch ID
+        #[arg(short, long)]
+        epoch: String,
+    },
+    /// Show file-level differences of vendored sources between two epochs
+    Diff {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Epoch to compare from
+        #[arg(long)]
+        epoch_a: String,
+        /// Epoch to compare to
+        #[arg(long)]
+        epoch_b: String,
+        /// Only compare this crate's vendored files
+        #[arg(long)]
+        package: Option<String>,
+        /// Print a unified diff for each changed file
+        #[arg(long)]
+        unified: bool,
+    },
+    /// Unpack a vendor archive and verify it against its manifest and digest
+    Restore {
+        /// Archive path
+        #[arg(short, long)]
+        archive: PathBuf,
+        /// Directory to restore into (must not exist)
+        #[arg(short, long, default_value = "vendor")]
+        output: PathBuf,
+    },
+    /// Report the size of each vendored package and of each classification
+    Report {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Vendor directory to measure
+        #[arg(short, long, default_value = "vendor")]
+        vendored: PathBuf,
+        /// Package ordering
+        #[arg(long, value_parser = ["size", "name"], default_value = "size")]
+        sort_by: String,
+        /// Only list the first N packages
+        #[arg(long)]
+        top: Option<usize>,
+        /// Write the full report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DriftAction {
+    /// Write the drift report, with item IDs to acknowledge, as versioned JSON
+    Export {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
         /// Expected epoch ID
         #[arg(short, long)]
         epoch: String,
+        /// Output file for the drift report (JSON)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Acknowledge drift items; later runs suppress them until the change differs
+    Ack {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Drift report written by `drift export`
+        #[arg(long)]
+        report: PathBuf,
+        /// Comma-separated IDs of the items to acknowledge
+        #[arg(long, value_delimiter = ',', required = true)]
+        items: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum EpochAction {
+    /// Write an epoch as a universal epoch document (JSON)
+    Export {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Epoch ID
+        #[arg(short, long)]
+        epoch: String,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Validate a universal epoch document and convert it to a graph snapshot
+    Import {
+        /// Universal epoch document
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Write the imported dependency graph (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OrgAction {
+    /// Parse and audit every project and write a single rollup report
+    Report {
+        /// Write the rollup report (JSON) to this file (printed if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LogAction {
+    /// Check the log's hash chain and signatures for tampering
+    Verify {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+        /// Hash of a previously recorded head entry that must still be in the log
+        #[arg(long)]
+        head: Option<String>,
+    },
+    /// Print the public key to configure as transparency_log_config.trusted_public_key
+    PublicKey {
+        /// Project path (discovered from the current directory if omitted)
+        #[arg(short, long)]
+        project: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ClassifierAction {
+    /// Classify a labelled sample of 152 popular crates and report match
+    /// rates and likely false positives
+    Bench {
+        /// Labelled corpus (JSON) to use instead of the bundled sample, e.g. the full top-1000
+        #[arg(long)]
+        corpus: Option<PathBuf>,
+        /// Bench report (JSON) of an earlier ruleset to diff against
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Write the bench report (JSON) to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Show the size, age and lock state of each cache entry
+    Status,
+    /// Remove cache entries
+    Clean {
+        /// Only remove this entry (e.g. advisory-db, classification)
+        #[arg(long)]
+        entry: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BundleAction {
+    /// Verify a signed bundle and install its sections into the shared cache
+    Import {
+        /// Bundle archive (.tar or .tar.gz)
+        #[arg(value_name = "TAR")]
+        bundle: PathBuf,
+        /// Install the bundle even if it is older than the installed sections
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OverrideAction {
+    /// Add or replace an override
+    Add {
+        /// Crate name
+        #[arg(value_name = "CRATE")]
+        crate_name: String,
+        /// TCS category (crypto, auth, serialization, transport, database, random, build, custom:<name>)
+        #[arg(long)]
+        category: String,
+        /// Reason recorded alongside the override
+        #[arg(long)]
+        reason: String,
     },
+    /// Remove an override
+    Remove {
+        /// Crate name
+        #[arg(value_name = "CRATE")]
+        crate_name: String,
+    },
+    /// List configured overrides
+    List,
+}
+
+/// Sections of `--help` and the subcommands listed in each
+///
+/// Subcommands missing here are listed under "Other commands", so a new
+/// subcommand is never left out of `--help`.
+const HELP_SECTIONS: &[(&str, &[&str])] = &[
+    ("Analysis", &["parse", "classify", "audit", "supply-chain", "sbom", "build-report", "skew", "facts", "attribution"]),
+    ("Vendoring and builds", &["vendor", "verify-vendor", "build", "mirror-manifest", "verify-artifacts"]),
+    ("Drift and updates", &["drift", "lock-diff", "simulate", "remediate", "tui"]),
+    ("Policy and reporting", &["gate", "badge", "epoch", "log", "org", "serve"]),
+    ("Configuration and maintenance", &["override", "classifier", "cache", "bundle", "doctor"]),
+    ("Packaging", &["completions", "man"]),
+];
+
+/// CLI definition with the subcommands grouped into sections in `--help`
+fn cli_command() -> clap::Command {
+    let command = Cli::command();
+    let subcommands: Vec<&clap::Command> = command.get_subcommands().filter(|c| !c.is_hide_set()).collect();
+    let width = subcommands.iter().map(|c| c.get_name().len()).max().unwrap_or_default();
+    let line = |c: &clap::Command| {
+        let about = c.get_about().map(|about| about.to_string()).unwrap_or_default();
+        format!("  {:<width$}  {}\n", c.get_name(), about.lines().next().unwrap_or_default())
+    };
+    
+    let mut sections = String::new();
+    for (heading, names) in HELP_SECTIONS {
+        let lines: String = names.iter()
+            .filter_map(|name| subcommands.iter().find(|c| c.get_name() == *name))
+            .map(|c| line(c))
+            .collect();
+        if !lines.is_empty() {
+            sections.push_str(&format!("{}:\n{}\n", heading, lines));
+        }
+    }
+    let other: String = subcommands.iter()
+        .filter(|c| !HELP_SECTIONS.iter().any(|(_, names)| names.contains(&c.get_name())))
+        .map(|c| line(c))
+        .collect();
+    if !other.is_empty() {
+        sections.push_str(&format!("Other commands:\n{}\n", other));
+    }
+    
+    let template = format!("{{before-help}}{{about-with-newline}}\n{{usage-heading}} {{usage}}\n\n{}Options:\n{{options}}{{after-help}}", sections);
+    command.help_template(template)
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+    let cli = Cli::from_arg_matches(&cli_command().get_matches()).unwrap_or_else(|e| e.exit());
+    
+    // Packaging commands need neither configuration nor an adapter
+    match &cli.command {
+        Commands::Completions { shell } => {
+            clap_complete::generate(*shell, &mut cli_command(), "rust-adapter", &mut std::io::stdout());
+            return Ok(());
+        },
+        Commands::Man { output } => return cmd_man(output.as_deref()),
+        _ => {},
+    }
+    
+    // Load configuration; the doctor reports an invalid one instead of failing
+    let (mut config, config_error) = match &cli.command {
+        Commands::Doctor { .. } if cli.config.exists() => RustAdapterConfig::load_or_default(Some(&cli.config)),
+        _ => (load_config(&cli.config).await?, None),
+    };
+    config.export_redaction_config.enabled |= cli.redact;
+    if let Commands::VerifyVendor { no_resume, scan_secrets, .. } = &cli.command {
+        config.vendor_config.full_verification |= *no_resume;
+        config.vendor_config.secret_scan.enabled |= *scan_secrets;
+    }
+    
+    // Open the events stream before anything is printed, then initialize
+    // logging and, when configured, metric and trace export (flushed when
+    // the guard is dropped on exit)
+    let events = match &cli.events {
+        Some(target) => open_event_sink(target).map_err(|e| format!("Failed to open events stream: {}", e))?,
+        None => EventSink::disabled(),
+    };
+    let _telemetry = init_logging(&cli.log_level, &config)?;
+    
+    // Create adapter
+    let _ = ARTIFACT_DIGEST.set(config.digest_config);
+    let adapter = RustAdapter::new(config).with_event_sink(events.clone());
+    
+    // First Ctrl+C cancels running operations and their child processes, a second one exits
+    let cancel = adapter.cancellation_token().clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("Cancelling... (press Ctrl+C again to exit immediately)");
+            cancel.cancel();
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+    
+    // Run command, reporting its outcome as the last event
+    let command = command_name(&cli.command);
+    let result: Result<(), Box<dyn std::error::Error>> = async move {
+        match cli.command {
+            Commands::Parse { project, stdin, format, only_tcs, only_git, depth, classification, lockfiles, all_lockfiles } => {
+                let filter = GraphFilter {
+                    only_tcs,
+                    only_git,
+                    classification: classification.map(|c| c.parse()).transpose()?,
+                    max_depth: depth,
+                };
+                let format = format.unwrap_or_else(|| if stdin { "json" } else { "tree" }.to_string());
+                match stdin {
+                    true => cmd_parse_stdin(&adapter, &format, filter).await?,
+                    false => cmd_parse(&adapter, &resolve_project(project)?, &format, filter, lockfiles, all_lockfiles).await?,
+                }
+            },
+            Commands::Classify { project, package, unknown_only, snapshot, diff, against } => {
+                let project = resolve_project(project)?;
+                match diff {
+                    Some(before) => cmd_classify_diff(&adapter, &project, &before, &against, &snapshot).await?,
+                    None => cmd_classify(&adapter, &project, package.as_deref(), unknown_only, &snapshot).await?,
+                }
+            },
+            Commands::Classifier { action: ClassifierAction::Bench { corpus, baseline, output } } => {
+                cmd_classifier_bench(&adapter, corpus.as_ref(), baseline.as_ref(), &output)?;
+            },
+            Commands::Override { action } => {
+                cmd_override(&cli.config, action, cli.dry_run)?;
+            },
+            Commands::Audit { project, output, binaries, epoch, .. } if !binaries.is_empty() => {
+                let project = resolve_project(project)?;
+                cmd_audit_binaries(&adapter, &project, &binaries, epoch.as_deref(), &output).await?;
+            },
+            Commands::Audit { project, output, template, .. } => {
+                let project = resolve_project(project)?;
+                cmd_audit(&adapter, &project, &output, &template).await?;
+            },
+            Commands::SupplyChain { project, output, template, binaries, epoch } => {
+                let project = resolve_project(project)?;
+                cmd_supply_chain(&adapter, &project, &binaries, epoch.as_deref(), &output, &template).await?;
+            },
+            Commands::Sbom { project, output, format, merge, scope, encoding } => {
+                let project = resolve_project(project)?;
+                let scope = scope.map(|s| s.parse::<SbomScope>()).transpose()?;
+                let encoding = encoding.map(|e| e.parse::<SbomEncoding>()).transpose()?;
+                cmd_sbom(&adapter, &project, &output, &format, &merge, scope, encoding).await?;
+            },
+            Commands::Vendor { action: Some(VendorAction::Report { project, vendored, sort_by, top, output }), .. } => {
+                let project = resolve_project(project)?;
+                cmd_vendor_report(&adapter, &project, &vendored, &sort_by, top, &output).await?;
+            },
+            Commands::Vendor { action: Some(action), .. } => {
+                cmd_vendor_action(&adapter, action, cli.dry_run).await?;
+            },
+            Commands::Vendor { action: None, project, output, delta } => {
+                let project = resolve_project(project)?;
+                cmd_vendor(&adapter, &project, &output, delta, cli.dry_run).await?;
+            },
+            Commands::Build { project, epoch, cargo_args } => {
+                let project = resolve_project(project)?;
+                cmd_build(&adapter, &project, &epoch, &cargo_args).await?;
+            },
+            Commands::VerifyVendor { project, vendored, .. } => {
+                let project = resolve_project(project)?;
+                cmd_verify_vendor(&adapter, &project, &vendored).await?;
+            },
+            Commands::Drift { action: Some(DriftAction::Export { project, epoch, output }), .. } => {
+                let project = resolve_project(project)?;
+                cmd_drift_export(&adapter, &project, &epoch, &output).await?;
+            },
+            Commands::Drift { action: Some(DriftAction::Ack { project, report, items }), .. } => {
+                let project = resolve_project(project)?;
+                cmd_drift_ack(&adapter, &project, &report, &items)?;
+            },
+            Commands::Drift {
+                action: None, project, epoch, since, output, group_by, template,
+                priority, change_type, classification, package, offset, limit, summary_only,
+            } => {
+                let project = resolve_project(project)?;
+                let view = DriftView {
+                    query: DriftQuery {
+                        priorities: priority.iter().map(|p| p.parse()).collect::<Result<_, _>>()?,
+                        change_types: change_type.iter().map(|c| c.parse()).collect::<Result<_, _>>()?,
+                        classifications: classification.iter().map(|c| c.parse()).collect::<Result<_, _>>()?,
+                        packages: package.iter().map(|p| NamePattern::parse(p)).collect::<Result<_, _>>()?,
+                    },
+                    offset,
+                    limit,
+                    summary_only,
+                };
+                match (epoch, since) {
+                    (_, Some(since)) => cmd_drift_timeline(&adapter, &project, &since, &output).await?,
+                    (Some(epoch), None) => cmd_drift(&adapter, &project, &epoch, group_by.as_deref(), &view, &output, &template).await?,
+                    (None, None) => unreachable!("clap requires --epoch or --since"),
+                }
+            },
+            Commands::LockDiff { before, after, output, template } => {
+                cmd_lock_diff(&adapter, &before, &after, &output, &template).await?;
+            },
+            Commands::VerifyArtifacts { manifest_file } => {
+                cmd_verify_artifacts(&manifest_file, adapter.event_sink())?;
+            },
+            Commands::Gate { facts, drift, output } => {
+                cmd_gate(&adapter, facts.as_ref(), drift.as_ref(), &output)?;
+            },
+            Commands::Facts { project, output, epoch, no_audit } => {
+                let project = resolve_project(project)?;
+                cmd_facts(&adapter, &project, &output, epoch.as_deref(), !no_audit).await?;
+            },
+            Commands::Epoch { action: EpochAction::Export { project, epoch, output } } => {
+                let project = resolve_project(project)?;
+                cmd_epoch_export(&adapter, &project, &epoch, &output).await?;
+            },
+            Commands::Epoch { action: EpochAction::Import { input, output } } => {
+                cmd_epoch_import(&adapter, &input, &output)?;
+            },
+            Commands::Log { action: LogAction::Verify { project, head } } => {
+                let project = resolve_project(project)?;
+                cmd_log_verify(&adapter, &project, head.as_deref())?;
+            },
+            Commands::Log { action: LogAction::PublicKey { project } } => {
+                let project = resolve_project(project)?;
+                let log = TransparencyLog::new(&project, &adapter.config().transparency_log_config);
+                println!("{}", log.public_key()?);
+            },
+            Commands::BuildReport { project, vendored, epoch, output } => {
+                let project = resolve_project(project)?;
+                cmd_build_report(&adapter, &project, vendored.as_deref(), epoch.as_deref(), &output)?;
+            },
+            Commands::Attribution { project, vendored, epoch, output, index } => {
+                let project = resolve_project(project)?;
+                cmd_attribution(&adapter, &project, vendored.as_deref(), epoch.as_deref(), &output, index.as_ref()).await?;
+            },
+            Commands::Badge { project, kind, format, label, output } => {
+                let project = resolve_project(project)?;
+                cmd_badge(&adapter, &project, kind.parse()?, &format, label.as_deref(), &output)?;
+            },
+            Commands::Cache { action } => {
+                cmd_cache(&adapter, action, cli.dry_run)?;
+            },
+            Commands::Bundle { action } => {
+                cmd_bundle(&adapter, action, cli.dry_run)?;
+            },
+            Commands::Skew { project, output } => {
+                let project = resolve_project(project)?;
+                cmd_skew(&adapter, &project, &output).await?;
+            },
+            Commands::Simulate { project, updates, output } => {
+                let project = resolve_project(project)?;
+                cmd_simulate(&adapter, &project, &updates, &output).await?;
+            },
+            Commands::MirrorManifest { project, epoch, output } => {
+                let project = resolve_project(project)?;
+                cmd_mirror_manifest(&adapter, &project, &epoch, &output)?;
+            },
+            Commands::Remediate { project, finding, output, summary } => {
+                let project = resolve_project(project)?;
+                cmd_remediate(&adapter, &project, &finding, &output, &summary).await?;
+            },
+            Commands::Doctor { project, format, output } => {
+                let project = resolve_project(project)?;
+                cmd_doctor(&adapter, &project, config_error.as_ref(), &format, &output).await?;
+            },
+            Commands::Serve { project, interval, poll, output } => {
+                let projects = match project.is_empty() {
+                    true => vec![resolve_project(None)?],
+                    false => project,
+                };
+                cmd_serve(&adapter, &projects, interval, poll, &output).await?;
+            },
+            Commands::Org { action: OrgAction::Report { output } } => {
+                let base_dir = cli.config.parent().map(PathBuf::from).unwrap_or_default();
+                cmd_org_report(&adapter, &base_dir, &output).await?;
+            },
+            Commands::Completions { .. } | Commands::Man { .. } => unreachable!("handled before the configuration is loaded"),
+            #[cfg(feature = "tui")]
+            Commands::Tui { project, since, to, reviewer } => {
+                let project = resolve_project(project)?;
+                cmd_tui(&adapter, &project, since.as_deref(), to.as_deref(), reviewer).await?;
+            },
+        }
+        
+        if let Some(manifest_path) = &cli.manifest {
+            let artifacts = ARTIFACTS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            let digest = ARTIFACT_DIGEST.get().copied().unwrap_or_default();
+            let manifest = rust_ecosystem_adapter::utils::update_manifest(manifest_path, &artifacts, &digest)
+                .map_err(|e| format!("Failed to write run manifest: {}", e))?;
+            eprintln!("Run manifest written: {:?} ({} artifacts)", manifest_path, manifest.artifacts.len());
+        }
+        
+        Ok(())
+    }.await;
+    
+    events.emit(AdapterEvent::Result {
+        command,
+        success: result.is_ok(),
+        error: result.as_ref().err().map(|e| e.to_string()),
+        artifacts: ARTIFACTS.lock().unwrap_or_else(|e| e.into_inner()).iter().map(|a| a.path.clone()).collect(),
+    });
+    result
+}
+
+/// Artifacts written by this run, listed in the `--manifest` file on success
+static ARTIFACTS: Mutex<Vec<ArtifactEntry>> = Mutex::new(Vec::new());
+
+/// Digest of artifact checksum files and the run manifest, from `digest_config`
+static ARTIFACT_DIGEST: OnceLock<DigestConfig> = OnceLock::new();
+
+/// Write an output file with its checksum file and remember it for the run manifest
+fn write_artifact<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<(), AdapterError> {
+    let digest = ARTIFACT_DIGEST.get().copied().unwrap_or_default();
+    let entry = rust_ecosystem_adapter::utils::write_artifact_with(path, contents, &digest)?;
+    ARTIFACTS.lock().unwrap_or_else(|e| e.into_inner()).push(entry);
+    Ok(())
+}
+
+/// Command name as typed on the command line (`verify-vendor`)
+fn command_name(command: &Commands) -> String {
+    let debug = format!("{:?}", command);
+    let variant = debug.split(|c: char| !c.is_alphanumeric()).next().unwrap_or_default();
+    let mut name = String::new();
+    for (index, c) in variant.char_indices() {
+        if c.is_uppercase() && index > 0 {
+            name.push('-');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+/// Use the `--project` path, or discover the project containing the current directory
+fn resolve_project(project: Option<PathBuf>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(project) = project {
+        return Ok(project);
+    }
+    
+    let project = discover_project_root(&std::env::current_dir()?)?;
+    eprintln!("Using project: {}", project.display());
+    Ok(project)
+}
+
+/// Initialize logging
+fn init_logging(level: &str, config: &RustAdapterConfig) -> Result<Option<telemetry::TelemetryGuard>, Box<dyn std::error::Error>> {
+    use tracing_subscriber::EnvFilter;
+    
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(level));
+    
+    let telemetry = telemetry::init(&config.telemetry_config)?;
+    tracing::subscriber::set_global_default(telemetry::subscriber(filter, telemetry.as_ref(), std::io::stdout))?;
+    
+    Ok(telemetry)
+}
+
+/// Open the `--events` stream
+///
+/// `-` keeps stdout for events alone: the original stdout is duplicated
+/// for the sink and stdout is pointed at stderr, so human-readable output
+/// and logs can't interleave with the NDJSON. Where that redirection is
+/// unavailable `-` is rejected.
+fn open_event_sink(target: &Path) -> Result<EventSink, Box<dyn std::error::Error>> {
+    if target != Path::new("-") {
+        return Ok(EventSink::create(target)?);
+    }
+    
+    #[cfg(unix)]
+    {
+        use std::os::fd::FromRawFd;
+        
+        // SAFETY: plain descriptor calls on the process's own standard
+        // streams; the duplicated descriptor is owned by the file below
+        let events_fd = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if events_fd < 0 || unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let stream = unsafe { std::fs::File::from_raw_fd(events_fd) };
+        Ok(EventSink::from_writer(Box::new(stream)))
+    }
+    #[cfg(not(unix))]
+    {
+        Err("`--events -` needs human-readable output redirected to stderr, which is only supported on Unix; pass a file instead".into())
+    }
+}
+
+/// Load configuration from file
+async fn load_config(config_path: &PathBuf) -> Result<RustAdapterConfig, Box<dyn std::error::Error>> {
+    let config = if config_path.exists() {
+        RustAdapterConfig::load_from_file(config_path)
+            .map_err(|e| format!("Failed to load config: {}", e))?
+    } else {
+        eprintln!("Config file {:?} not found, using defaults", config_path);
+        RustAdapterConfig::default()
+    };
+    
+    Ok(config)
+}
+
+/// Parse dependencies command
+async fn cmd_parse(
+    adapter: &RustAdapter,
+    project: &Path,
+    format: &str,
+    filter: GraphFilter,
+    mut lockfiles: Vec<PathBuf>,
+    all_lockfiles: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    eprintln!("Parsing dependencies from project: {:?}", project);
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    if all_lockfiles {
+        lockfiles = rust_ecosystem_adapter::manifest::discover_lockfiles(project);
+    }
+    let mut dependency_graph = match lockfiles.is_empty() {
+        true => adapter.parse_dependencies(&project_obj).await,
+        false => {
+            for lockfile in &lockfiles {
+                eprintln!("  Including {}", lockfile.display());
+            }
+            adapter.parse_lockfiles(&project_obj, &lockfiles).await
+        },
+    }.map_err(|e| format!("Failed to parse dependencies: {}", e))?;
+    
+    eprintln!("Successfully parsed {} dependencies", dependency_graph.root_packages.len());
+    let depths = dependency_graph.package_depths();
+    if let Some(deepest) = depths.values().max() {
+        eprintln!("  Maximum depth: {}", deepest);
+    }
+    let max_depth = rust_ecosystem_adapter::config::load_max_transitive_depth(&project_obj)
+        .map_err(|e| format!("Failed to load project policy: {}", e))?;
+    if let Some(max_depth) = max_depth {
+        let beyond = depths.values().filter(|depth| **depth > max_depth).count();
+        if beyond > 0 {
+            eprintln!("  {} packages deeper than the policy limit of {}", beyond, max_depth);
+        }
+    }
+    
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_graph(&mut dependency_graph);
+    }
+    print_graph(&dependency_graph, format, filter)
+}
+
+/// Parse command reading Cargo.lock content from stdin
+async fn cmd_parse_stdin(
+    adapter: &RustAdapter,
+    format: &str,
+    filter: GraphFilter,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let lockfile_content = std::io::read_to_string(std::io::stdin())
+        .map_err(|e| format!("Failed to read Cargo.lock from stdin: {}", e))?;
+    
+    let mut dependency_graph = adapter.parse_lockfile_str(&lockfile_content).await
+        .map_err(|e| format!("Failed to parse dependencies: {}", e))?;
+    
+    eprintln!("Successfully parsed {} dependencies", dependency_graph.root_packages.len());
+    
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_graph(&mut dependency_graph);
+    }
+    print_graph(&dependency_graph, format, filter)
+}
+
+/// Print a parsed graph as JSON, or as a filtered tree or table
+fn print_graph(graph: &DependencyGraph, format: &str, filter: GraphFilter) -> Result<(), Box<dyn std::error::Error>> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(graph)?);
+        return Ok(());
+    }
+    
+    let view = GraphView::new(graph, filter);
+    let output = match format {
+        "table" => view.render_table(),
+        _ => view.render_tree(),
+    };
+    if output.is_empty() {
+        eprintln!("No packages match the given filters");
+    } else {
+        println!("{}", output);
+    }
+    
+    Ok(())
+}
+
+/// Explain classification command
+async fn cmd_classify(
+    adapter: &RustAdapter,
+    project: &Path,
+    package_filter: Option<&str>,
+    unknown_only: bool,
+    snapshot: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let dependency_graph = adapter.parse_dependencies(&project_obj).await
+        .map_err(|e| format!("Failed to parse dependencies: {}", e))?;
+    if let Some(snapshot_path) = snapshot {
+        write_graph_snapshot(adapter, &dependency_graph, snapshot_path)?;
+    }
+    
+    let classifier = adapter.tcs_classifier();
+    let unclassified = classifier.unclassified_report(&project_obj, &dependency_graph);
+    if unknown_only {
+        println!("Unclassified packages: {} of {} ({} adjacent to TCS)",
+            unclassified.packages.len(), unclassified.total_packages, unclassified.tcs_adjacent().count());
+        for package in &unclassified.packages {
+            match package.is_tcs_adjacent() {
+                true => println!("{} {} (adjacent to TCS: {})", package.package_name, package.package_version, package.tcs_neighbors.join(", ")),
+                false => println!("{} {}", package.package_name, package.package_version),
+            }
+            print_evaluations(&package.evaluations);
+        }
+        classifier.check_unclassified(&unclassified)?;
+        return Ok(());
+    }
+    
+    let packages: Vec<_> = dependency_graph.root_packages.iter()
+        .filter(|p| package_filter.is_none_or(|name| p.name == name))
+        .collect();
+    
+    if let Some(name) = package_filter {
+        if packages.is_empty() {
+            return Err(format!("Package '{}' not found in dependency graph", name).into());
+        }
+    }
+    
+    for package in packages {
+        let explanation = adapter.tcs_classifier().explain_package(&project_obj, package);
+        let classification = match &explanation.result.role {
+            rust_ecosystem_adapter::models::ToolchainRole::TCS(category) => format!("TCS: {}", category),
+            rust_ecosystem_adapter::models::ToolchainRole::Mechanical(category) => format!("Mechanical: {:?}", category),
+        };
+        
+        println!("{} {} => {}", explanation.package_name, explanation.package_version, classification);
+        print_evaluations(&explanation.evaluations);
+    }
+    
+    if package_filter.is_none() {
+        classifier.check_unclassified(&unclassified)?;
+    }
+    
+    Ok(())
+}
+
+/// Print evaluated classification signals, marking the decisive one
+fn print_evaluations(evaluations: &[SignalEvaluation]) {
+    for evaluation in evaluations {
+        let marker = match (evaluation.decisive, evaluation.matched) {
+            (true, _) => "=>",
+            (false, true) => " +",
+            (false, false) => " -",
+        };
+        println!("  {} [weight {:.1}] {} (rule: {})",
+            marker, evaluation.weight, evaluation.signal.description(), evaluation.rule);
+    }
+}
+
+/// Classification diff command
+async fn cmd_classify_diff(
+    adapter: &RustAdapter,
+    project: &Path,
+    before_path: &PathBuf,
+    against: &Option<PathBuf>,
+    snapshot: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let before = read_graph_snapshot(before_path)?;
+    let after = match against {
+        Some(after_path) => read_graph_snapshot(after_path)?,
+        None => adapter.parse_dependencies(&project_obj).await
+            .map_err(|e| format!("Failed to parse dependencies: {}", e))?,
+    };
+    if let Some(snapshot_path) = snapshot {
+        write_graph_snapshot(adapter, &after, snapshot_path)?;
+    }
+    
+    let diff = adapter.tcs_classifier().classification_diff(&project_obj, &before, &after);
+    if diff.before_fingerprint != diff.after_fingerprint {
+        eprintln!("Warning: snapshots describe different Cargo.lock states; only packages present in both are compared");
+    }
+    
+    println!("Classification changes: {} ({} now TCS, {} no longer TCS)",
+        diff.changes.len(), diff.promoted().count(), diff.demoted().count());
+    for change in &diff.changes {
+        println!("  {} {}: {} => {}", change.package_name, change.package_version, change.before, change.after);
+        for signal in &change.signals {
+            println!("    + {}", signal);
+        }
+    }
+    if diff.unmatched_packages > 0 {
+        println!("Packages present in only one graph: {}", diff.unmatched_packages);
+    }
+    println!("TCS coverage: {:.1}% -> {:.1}% ({:+.1} points)",
+        diff.tcs_coverage_before, diff.tcs_coverage_after, diff.tcs_coverage_delta());
+    
+    Ok(())
+}
+
+/// Classifier bench command
+fn cmd_classifier_bench(
+    adapter: &RustAdapter,
+    corpus: Option<&PathBuf>,
+    baseline: Option<&PathBuf>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let corpus: Vec<CorpusCrate> = match corpus {
+        Some(path) => read_report(path, "corpus")?,
+        None => TcsClassifier::bundled_corpus()?,
+    };
+    let label = |category: &Option<TcsCategory>| category.as_ref().map_or("mechanical".to_string(), |c| c.to_string());
+    
+    let mut report = adapter.tcs_classifier().bench(&corpus);
+    if let Some(baseline_path) = baseline {
+        let baseline: ClassifierBenchReport = read_report(baseline_path, "bench report")?;
+        report.baseline = Some(report.diff(&baseline));
+    }
+    
+    println!("Classifier bench (ruleset {}): {} crates", report.ruleset_version, report.results.len());
+    println!("TCS match rate: {:.1}%, agreement with labels: {:.1}%", report.match_rate(), report.accuracy());
+    println!("Rules:");
+    for stats in &report.rules {
+        println!("  {:>4} matched {:>4} decisive  {}", stats.matched, stats.decisive, stats.rule);
+    }
+    
+    let false_positives: Vec<_> = report.false_positives().collect();
+    println!("False positives (labelled mechanical): {}", false_positives.len());
+    for result in false_positives {
+        println!("  {} => {} (rule: {})", result.name, label(&result.actual), result.rule);
+    }
+    let false_negatives: Vec<_> = report.false_negatives().collect();
+    println!("False negatives (labelled TCS): {}", false_negatives.len());
+    for result in false_negatives {
+        println!("  {} (labelled {})", result.name, label(&result.expected));
+    }
+    println!("Partial name matches: {}", report.partial_name_matches.len());
+    for partial in &report.partial_name_matches {
+        println!("  {}: pattern '{}' matched inside '{}' (labelled {})",
+            partial.name, partial.pattern, partial.segment, label(&partial.expected));
+    }
+    
+    if let Some(diff) = &report.baseline {
+        println!("Changes against ruleset {}: {} ({} towards the label)",
+            diff.baseline_ruleset_version, diff.changes.len(), diff.changes.iter().filter(|change| change.is_fix()).count());
+        for change in &diff.changes {
+            println!("  {}: {} => {} (labelled {})", change.name, label(&change.before), label(&change.after), label(&change.expected));
+        }
+        if diff.unmatched_crates > 0 {
+            println!("Crates present in only one report: {}", diff.unmatched_crates);
+        }
+        println!("TCS match rate: {:.1}% -> {:.1}%, agreement with labels: {:.1}% -> {:.1}%",
+            diff.match_rate_before, report.match_rate(), diff.accuracy_before, report.accuracy());
+    }
+    
+    if let Some(output_path) = output {
+        let report_content = serde_json::to_string_pretty(&report)?;
+        write_artifact(output_path, report_content)
+            .map_err(|e| format!("Failed to write bench report: {}", e))?;
+        println!("Bench report written: {:?}", output_path);
+    }
+    
+    Ok(())
+}
+
+/// Read a JSON report or input file
+fn read_report<T: serde::de::DeserializeOwned>(path: &PathBuf, kind: &str) -> Result<T, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {} {:?}: {}", kind, path, e))?;
+    Ok(serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid {} {:?}: {}", kind, path, e))?)
+}
+
+/// Read a dependency graph snapshot written by `classify --snapshot`
+fn read_graph_snapshot(path: &PathBuf) -> Result<DependencyGraph, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read graph snapshot {:?}: {}", path, e))?;
+    let graph = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid graph snapshot {:?}: {}", path, e))?;
+    Ok(graph)
+}
+
+/// Write a dependency graph snapshot, redacted when export redaction is enabled
+fn write_graph_snapshot(adapter: &RustAdapter, graph: &DependencyGraph, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let content = match adapter.export_redactor().is_enabled() {
+        true => {
+            let mut graph = graph.clone();
+            adapter.export_redactor().redact_graph(&mut graph);
+            serde_json::to_string_pretty(&graph)?
+        },
+        false => serde_json::to_string_pretty(graph)?,
+    };
+    write_artifact(path, content)
+        .map_err(|e| format!("Failed to write graph snapshot: {}", e))?;
+    eprintln!("Graph snapshot written: {:?}", path);
+    Ok(())
+}
+
+/// Status badge command
+fn cmd_badge(
+    adapter: &RustAdapter,
+    project: &Path,
+    kind: ReportKind,
+    format: &str,
+    label: Option<&str>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let badge = adapter.badge_generator().badge(&project_obj, kind, label)
+        .map_err(|e| format!("No {} report recorded yet, run `rust-adapter {}` first: {}", kind, kind, e))?;
+    let content = match format {
+        "json" => serde_json::to_string_pretty(&badge.to_endpoint_json())?,
+        _ => badge.to_svg(),
+    };
+    
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, content)
+                .map_err(|e| format!("Failed to write badge: {}", e))?;
+            eprintln!("Badge written: {:?} ({}: {})", output_path, badge.label, badge.message);
+        },
+        None => println!("{}", content),
+    }
+    
+    Ok(())
+}
+
+/// Record a report summary for badges and print its summary line
+fn record_summary(adapter: &RustAdapter, project: &Project, summary: ReportSummary) {
+    println!("Summary: {}", summary.summary_line());
+    if let Err(e) = adapter.badge_generator().record(project, &summary) {
+        eprintln!("Warning: failed to record {} summary: {}", summary.kind, e);
+    }
+}
+
+/// Print the changes a dry run would have made
+fn print_plan(plan: &ChangePlan) {
+    println!("Dry run: {} (nothing written)", plan.operation);
+    if plan.is_empty() {
+        println!("  No changes");
+    }
+    for package in &plan.packages_vendored {
+        println!("  + {}", package);
+    }
+    for package in &plan.packages_removed {
+        println!("  - {}", package);
+    }
+    for epoch in &plan.epochs_superseded {
+        println!("  Would supersede epoch: {}", epoch);
+    }
+    for path in &plan.files_written {
+        println!("  Would write: {}", path.display());
+    }
+    for path in &plan.files_removed {
+        println!("  Would remove: {}", path.display());
+    }
+    for note in &plan.notes {
+        println!("  {}", note);
+    }
+}
+
+/// Print a config edit a dry run would have saved
+fn print_config_plan(editor: &OverrideEditor, change: String) -> Result<(), Box<dyn std::error::Error>> {
+    let content = editor.render().map_err(|e| format!("Failed to save config: {}", e))?;
+    
+    let mut plan = ChangePlan::new("override");
+    plan.files_written.push(editor.path().to_path_buf());
+    plan.notes.push(change);
+    print_plan(&plan);
+    print!("{}", content);
+    
+    Ok(())
+}
+
+/// Shared cache maintenance command
+fn cmd_cache(adapter: &RustAdapter, action: CacheAction, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let cache = SharedCache::from_config(adapter.config())
+        .ok_or("No shared cache directory: set cache_config.shared_dir or HOME")?;
+    
+    match action {
+        CacheAction::Status => {
+            println!("Shared cache: {}", cache.root().display());
+            let entries = cache.status();
+            if entries.is_empty() {
+                println!("  (empty)");
+            }
+            for entry in entries {
+                println!("  {:<16} {:>10} bytes {:>7} files  modified {}{}",
+                    entry.name,
+                    entry.size_bytes,
+                    entry.file_count,
+                    entry.modified.as_deref().unwrap_or("-"),
+                    if entry.locked { "  [locked]" } else { "" });
+            }
+        },
+        CacheAction::Clean { entry } if dry_run => {
+            let mut plan = ChangePlan::new("cache clean");
+            plan.files_removed = cache.plan_clean(entry.as_deref());
+            print_plan(&plan);
+        },
+        CacheAction::Clean { entry } => {
+            let removed = cache.clean(entry.as_deref())
+                .map_err(|e| format!("Failed to clean cache: {}", e))?;
+            if removed.is_empty() {
+                println!("Nothing to clean");
+            }
+            for name in removed {
+                println!("Removed cache entry: {}", name);
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+/// Man page generation command
+fn cmd_man(output: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut command = Cli::command();
+    let Some(dir) = output else {
+        clap_mangen::Man::new(command).render(&mut std::io::stdout())?;
+        return Ok(());
+    };
+    
+    command.build();
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut pages = Vec::new();
+    man_pages(&command, "rust-adapter", &mut pages)?;
+    for (name, page) in &pages {
+        write_artifact(dir.join(format!("{}.1", name)), page)?;
+    }
+    println!("Man pages written: {} ({} pages)", dir.display(), pages.len());
+    
+    Ok(())
+}
+
+/// Render the man page of a command and of each of its subcommands
+///
+/// Subcommand pages are named after their full command line, as in
+/// `rust-adapter-vendor-snapshot`.
+fn man_pages(command: &clap::Command, name: &str, pages: &mut Vec<(String, Vec<u8>)>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut page = Vec::new();
+    clap_mangen::Man::new(command.clone()).title(name).render(&mut page)?;
+    pages.push((name.to_string(), page));
+    
+    for subcommand in command.get_subcommands().filter(|c| !c.is_hide_set() && c.get_name() != "help") {
+        man_pages(subcommand, &format!("{}-{}", name, subcommand.get_name()), pages)?;
+    }
+    Ok(())
+}
+
+/// Security data bundle command
+fn cmd_bundle(adapter: &RustAdapter, action: BundleAction, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        BundleAction::Import { bundle, force } if dry_run => {
+            let report = adapter.bundle_importer().verify(&bundle, force)
+                .map_err(|e| format!("Failed to verify bundle: {}", e))?;
+            let mut plan = ChangePlan::new("bundle import");
+            plan.files_written = report.sections.iter().map(|section| section.path.clone()).collect();
+            plan.notes.push(format!("Signed by {} at {}", report.signing_key, report.created_at));
+            print_plan(&plan);
+        },
+        BundleAction::Import { bundle, force } => {
+            let report = adapter.bundle_importer().import(&bundle, force)
+                .map_err(|e| format!("Failed to import bundle: {}", e))?;
+            println!("Bundle prepared {} signed by {}", report.created_at, report.signing_key);
+            for section in &report.sections {
+                println!("  Installed {:<14} {:>7} files {:>10} bytes -> {}",
+                    section.section.as_str(),
+                    section.files,
+                    section.size_bytes,
+                    section.path.display());
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+/// Manage explicit TCS overrides command
+fn cmd_override(config_path: &Path, action: OverrideAction, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut editor = OverrideEditor::open(config_path)
+        .map_err(|e| format!("Failed to open config: {}", e))?;
+    
+    match action {
+        OverrideAction::Add { crate_name, category, reason } => {
+            let category: TcsCategory = category.parse()?;
+            editor.add(&crate_name, &category, &reason)?;
+            if dry_run {
+                return print_config_plan(&editor, format!("Add override: {} => {:?}", crate_name, category));
+            }
+            editor.save().map_err(|e| format!("Failed to save config: {}", e))?;
+            println!("Override added: {} => {:?}", crate_name, category);
+        },
+        OverrideAction::Remove { crate_name } => {
+            if !editor.remove(&crate_name)? {
+                return Err(format!("No override configured for '{}'", crate_name).into());
+            }
+            if dry_run {
+                return print_config_plan(&editor, format!("Remove override: {}", crate_name));
+            }
+            editor.save().map_err(|e| format!("Failed to save config: {}", e))?;
+            println!("Override removed: {}", crate_name);
+        },
+        OverrideAction::List => {
+            let entries = editor.list()?;
+            if entries.is_empty() {
+                println!("No explicit TCS overrides configured");
+            }
+            for entry in entries {
+                match entry.reason {
+                    Some(reason) => println!("  {} => {:?} ({})", entry.crate_name, entry.category, reason),
+                    None => println!("  {} => {:?}", entry.crate_name, entry.category),
+                }
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+/// Run audit command
+async fn cmd_audit(
+    adapter: &RustAdapter,
+    project: &Path,
+    output: &Option<PathBuf>,
+    template: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Running security audit for project: {:?}", project);
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let audit_report = adapter.run_audit(&project_obj).await
+        .map_err(|e| format!("Failed to run audit: {}", e))?;
+    
+    println!("Audit completed successfully");
+    
+    if let Some(cargo_audit_output) = &audit_report.raw_cargo_audit {
+        println!("Cargo-audit output available ({} bytes)", cargo_audit_output.len());
+    }
+    
+    if let Some(cargo_vet_output) = &audit_report.raw_cargo_vet {
+        println!("Cargo-vet output available ({} bytes)", cargo_vet_output.len());
+    }
+    
+    if let Some(cargo_vet_audits) = &audit_report.raw_cargo_vet_audits {
+        println!("Cargo-vet audits available ({} bytes)", cargo_vet_audits.len());
+    }
+    
+    if let Some(cargo_vet_imports) = &audit_report.raw_cargo_vet_imports {
+        println!("Cargo-vet imports available ({} bytes)", cargo_vet_imports.len());
+    }
+    
+    if let Some(cargo_vet_config) = &audit_report.raw_cargo_vet_config {
+        println!("Cargo-vet config and exemptions available ({} bytes)", cargo_vet_config.len());
+    }
+    
+    println!("Total findings: {}", audit_report.findings.len());
+    for finding in &audit_report.findings {
+        println!("  {} {} ({:?}){}", finding.id, finding.package_name, finding.severity,
+            if finding.affects_tcs { " [TCS]" } else { "" });
+        for path in &finding.dependency_paths {
+            println!("    via {}", path.join(" -> "));
+        }
+    }
+    if !audit_report.upgrade_suggestions.is_empty() {
+        println!("Upgrade suggestions:");
+        for suggestion in &audit_report.upgrade_suggestions {
+            match &suggestion.suggested_version {
+                Some(version) => println!("  {} {} -> {} ({}){}", suggestion.package_name, suggestion.current_version,
+                    version, suggestion.advisories.join(", "),
+                    if suggestion.within_requirements() { "" } else { " [manifest change needed]" }),
+                None => println!("  {} {}: no patched version clears {}", suggestion.package_name,
+                    suggestion.current_version, suggestion.advisories.join(", ")),
+            }
+            for check in suggestion.requirements.iter().filter(|check| !check.satisfied) {
+                println!("    {} requires {} {}", check.declared_by, suggestion.package_name, check.requirement);
+            }
+        }
+    }
+    for warning in &audit_report.execution_metadata.warnings {
+        println!("Warning: {}", warning.message);
+    }
+    record_summary(adapter, &project_obj, ReportSummary::from_audit(&audit_report));
+    
+    write_report("Audit", &audit_report, output, template)
+}
+
+/// Binary audit command
+///
+/// Fails if a binary was built from dependencies the epoch does not pin.
+async fn cmd_audit_binaries(
+    adapter: &RustAdapter,
+    project: &Path,
+    binaries: &[PathBuf],
+    epoch: Option<&str>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Auditing {} binaries against {}", binaries.len(), epoch.map_or("Cargo.lock".to_string(), |epoch| format!("epoch {}", epoch)));
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let reports = adapter.audit_binaries(&project_obj, binaries, epoch).await
+        .map_err(|e| format!("Failed to audit binaries: {}", e))?;
+    
+    for report in &reports {
+        print_provenance(std::slice::from_ref(&report.provenance));
+        for finding in &report.findings {
+            println!("    {} {} ({:?})", finding.id, finding.package_name, finding.severity);
+        }
+    }
+    
+    if let Some(output_path) = output {
+        write_artifact(output_path, serde_json::to_string_pretty(&reports)?)
+            .map_err(|e| format!("Failed to write binary audit report: {}", e))?;
+        println!("Binary audit report written: {:?}", output_path);
+    }
+    
+    let mismatched = reports.iter().filter(|report| !report.provenance.verified).count();
+    if mismatched > 0 {
+        return Err(format!("{} binaries were built from an unapproved dependency set", mismatched).into());
+    }
+    Ok(())
+}
+
+/// Print the provenance of binaries
+fn print_provenance(provenance: &[BinaryProvenance]) {
+    for binary in provenance {
+        let status = if binary.verified { "ok" } else { "MISMATCH" };
+        println!("  [{}] {}: {} embedded packages", status, binary.binary, binary.embedded_packages);
+        for package in &binary.unexpected_packages {
+            println!("    not pinned: {}", package);
+        }
+        for package in &binary.source_mismatches {
+            println!("    pinned from another source: {}", package);
+        }
+    }
+}
+
+/// Supply chain check command
+async fn cmd_supply_chain(
+    adapter: &RustAdapter,
+    project: &Path,
+    binaries: &[PathBuf],
+    epoch: Option<&str>,
+    output: &Option<PathBuf>,
+    template: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Checking supply chain for project: {:?}", project);
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let report = adapter.check_release_supply_chain(&project_obj, binaries, epoch).await
+        .map_err(|e| format!("Failed to check supply chain: {}", e))?;
+    
+    println!("Supply chain status: {:?}", report.status);
+    println!("Audit findings: {}", report.audit_findings.len());
+    println!("TCS audit coverage: {:.1}%", report.tcs_audit_coverage);
+    for package in &report.unaudited_tcs {
+        println!("  unaudited TCS: {}", package);
+    }
+    print_provenance(&report.binary_provenance);
+    
+    write_report("Supply chain", &report, output, template)
+}
+
+/// Write a report as JSON to `output`, or rendered through `template` to `output` or stdout
+fn write_report<T: serde::Serialize>(
+    kind: &str,
+    report: &T,
+    output: &Option<PathBuf>,
+    template: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = match template {
+        Some(template) => ReportTemplate::load(template)
+            .and_then(|template| template.render(report))
+            .map_err(|e| format!("Failed to render {} report: {}", kind.to_lowercase(), e))?,
+        None if output.is_some() => serde_json::to_string_pretty(report)?,
+        None => return Ok(()),
+    };
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, content)
+                .map_err(|e| format!("Failed to write {} report: {}", kind.to_lowercase(), e))?;
+            println!("{} report written: {:?}", kind, output_path);
+        },
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Generate SBOM command
+async fn cmd_sbom(
+    adapter: &RustAdapter,
+    project: &Path,
+    output: &Option<PathBuf>,
+    format: &str,
+    merge: &[PathBuf],
+    scope: Option<SbomScope>,
+    encoding: Option<SbomEncoding>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Generating {} SBOM for project: {:?}", format, project);
+    if let Some(scope) = scope {
+        println!("Scope: {}", scope.as_str());
+    }
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let mut sbom = adapter.generate_scoped_sbom(&project_obj, scope).await
+        .map_err(|e| format!("Failed to generate SBOM: {}", e))?;
+    
+    for fragment_path in merge {
+        let fragment_content = std::fs::read_to_string(fragment_path)
+            .map_err(|e| format!("Failed to read SBOM fragment {:?}: {}", fragment_path, e))?;
+        let fragment: serde_json::Value = serde_json::from_str(&fragment_content)
+            .map_err(|e| format!("Failed to parse SBOM fragment {:?}: {}", fragment_path, e))?;
+        
+        adapter.sbom_generator().merge_fragment(&mut sbom, &fragment)
+            .map_err(|e| match &e {
+                AdapterError::SchemaValidationFailed { errors, .. } => {
+                    format!("Invalid SBOM fragment {:?}: {}", fragment_path, errors.join("; "))
+                },
+                _ => format!("Failed to merge SBOM fragment {:?}: {}", fragment_path, e),
+            })?;
+        
+        println!("Merged SBOM fragment: {:?}", fragment_path);
+    }
+    
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_sbom(&mut sbom);
+    }
+    
+    adapter.sbom_generator().validate(&sbom)
+        .map_err(|e| match &e {
+            AdapterError::SchemaValidationFailed { errors, .. } => {
+                format!("Generated SBOM failed schema validation: {}", errors.join("; "))
+            },
+            _ => format!("Failed to validate SBOM: {}", e),
+        })?;
+    
+    let encoding = encoding
+        .or_else(|| output.as_deref().map(SbomEncoding::from_path))
+        .unwrap_or_default();
+    let output_path = output.clone()
+        .unwrap_or_else(|| PathBuf::from(format!("sbom.{}", sbom.file_extension(encoding))));
+    let fingerprint = ChecksumCalculator::new().calculate_lockfile_fingerprint(project_obj.lockfile_path())
+        .unwrap_or_default();
+    record_summary(adapter, &project_obj, ReportSummary::from_sbom(sbom.component_count(), fingerprint));
+    
+    let sbom_content = sbom.to_bytes(encoding, true)?;
+    
+    write_artifact(&output_path, sbom_content)
+        .map_err(|e| format!("Failed to write SBOM: {}", e))?;
+    
+    println!("SBOM generated successfully: {:?}", output_path);
+    
+    Ok(())
+}
+
+/// Vendor dependencies command
+async fn cmd_vendor(
+    adapter: &RustAdapter,
+    project: &Path,
+    output: &Option<PathBuf>,
+    delta: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let default_output = PathBuf::from("vendor");
+    let output_dir = output.as_ref().unwrap_or(&default_output);
+    
+    println!("Vendoring dependencies from project: {:?}", project);
+    println!("Output directory: {:?}", output_dir);
+    
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    if dry_run {
+        let plan = adapter.vendor_manager().plan_vendor(&project_obj, output_dir, delta)
+            .map_err(|e| format!("Failed to plan vendoring: {}", e))?;
+        print_plan(&plan);
+        return Ok(());
+    }
+    
+    if delta || adapter.config().vendor_config.delta_vendoring {
+        let report = adapter.vendor_manager().vendor_dependencies_delta(&project_obj, output_dir).await
+            .map_err(|e| format!("Failed to vendor dependencies: {}", e))?;
+        
+        if report.full_vendor {
+            println!("Full vendoring performed: {}", report.fallback_reason.as_deref().unwrap_or("requested"));
+        } else {
+            println!("Delta vendoring: {} added, {} changed, {} removed, {} unchanged",
+                report.added.len(), report.changed.len(), report.removed.len(), report.unchanged);
+            for package in &report.added {
+                println!("  + {}", package);
+            }
+            for package in &report.changed {
+                println!("  ~ {}", package);
+            }
+            for package in &report.removed {
+                println!("  - {}", package);
+            }
+        }
+    } else {
+        adapter.vendor_dependencies(&project_obj, output_dir).await
+            .map_err(|e| format!("Failed to vendor dependencies: {}", e))?;
+    }
+    
+    println!("Dependencies vendored successfully");
+    if let Ok(info) = adapter.vendor_manager().vendor_info(&project_obj, output_dir) {
+        println!("  Packages: {} ({} bytes)", info.total_packages, info.metadata.total_size_bytes);
+    }
+    
+    Ok(())
+}
+
+/// Vendored package size report command
+async fn cmd_vendor_report(
+    adapter: &RustAdapter,
+    project: &Path,
+    vendored: &PathBuf,
+    sort_by: &str,
+    top: Option<usize>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    let vendored = if vendored.is_absolute() { vendored.clone() } else { project.join(vendored) };
+    
+    let info = adapter.vendor_manager().vendor_info(&project_obj, &vendored)
+        .map_err(|e| format!("Failed to read vendor directory: {}", e))?;
+    let dependency_graph = adapter.parse_dependencies(&project_obj).await
+        .map_err(|e| format!("Failed to parse dependencies: {}", e))?;
+    let by_classification = info.size_by_classification(&dependency_graph);
+    
+    println!("Vendored packages: {} ({} bytes)", info.total_packages, info.metadata.total_size_bytes);
+    println!("By classification:");
+    for (classification, stats) in &by_classification {
+        println!("  {:<12} {:>5} packages {:>12} bytes {:>5.1}%  largest {}",
+            classification,
+            stats.packages,
+            stats.total_size_bytes,
+            stats.share * 100.0,
+            stats.largest.as_deref().unwrap_or("-"));
+    }
+    
+    let mut packages = info.packages_by_size();
+    if sort_by == "name" {
+        packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    }
+    println!("Packages:");
+    let total = info.metadata.total_size_bytes.max(1) as f64;
+    for package in packages.iter().take(top.unwrap_or(usize::MAX)) {
+        println!("  {:>12} bytes {:>5.1}%  {}@{}",
+            package.size_bytes,
+            package.size_bytes as f64 / total * 100.0,
+            package.name,
+            package.version);
+    }
+    
+    if let Some(output_path) = output {
+        let report = serde_json::json!({
+            "vendor": info,
+            "by_classification": by_classification,
+        });
+        write_artifact(output_path, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to: {:?}", output_path);
+    }
+    
+    Ok(())
+}
+
+/// Vendor archive, snapshot and restore command
+async fn cmd_vendor_action(
+    adapter: &RustAdapter,
+    action: VendorAction,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        VendorAction::Archive { vendored, output, epoch } => {
+            let output = match (output, &epoch) {
+                (Some(output), _) => output,
+                (None, Some(epoch)) => PathBuf::from(format!("vendor-{}.tar.zst", epoch)),
+                (None, None) => return Err("either --output or --epoch is required".into()),
+            };
+            
+            if dry_run {
+                let plan = adapter.vendor_manager().plan_archive(&vendored, &output, epoch.as_deref())
+                    .map_err(|e| format!("Failed to plan vendor archive: {}", e))?;
+                print_plan(&plan);
+                return Ok(());
+            }
+            
+            println!("Archiving vendor directory: {:?}", vendored);
+            
+            let info = adapter.vendor_manager().archive_vendor(&vendored, &output, epoch.as_deref())
+                .map_err(|e| format!("Failed to archive vendor directory: {}", e))?;
+            
+            println!("Archive written: {:?}", info.archive_path);
+            println!("  Files: {} ({} bytes)", info.manifest.total_files, info.manifest.total_size_bytes);
+            println!("  Content digest: {}", info.manifest.content_digest);
+            println!("  Archive SHA256: {} ({:?})", info.archive_digest, info.digest_path);
+        },
+        VendorAction::Snapshot { project, vendored, epoch } => {
+            let project = resolve_project(project)?;
+            let project_obj = Project::new(
+                "cli-project".to_string(),
+                "CLI Project".to_string(),
+                "rust".to_string(),
+                project.clone(),
+            );
+            let vendored = if vendored.is_absolute() { vendored } else { project.join(vendored) };
+            let drifted = adapter.pending_drift(&project_obj).await
+                .map_err(|e| format!("Failed to compute pending drift: {}", e))?;
+            
+            if dry_run {
+                let plan = adapter.vendor_manager().plan_snapshot(&project_obj, &vendored, &epoch, &drifted)
+                    .map_err(|e| format!("Failed to plan vendor snapshot: {}", e))?;
+                print_plan(&plan);
+                return Ok(());
+            }
+            
+            let snapshot = adapter.vendor_manager().snapshot_vendor(&project_obj, &vendored, &epoch, &drifted)
+                .map_err(|e| format!("Failed to snapshot vendor directory: {}", e))?;
+            
+            println!("Vendor snapshot pinned to epoch {}", snapshot.epoch_id);
+            println!("  Packages: {}", snapshot.total_packages);
+            println!("  Lockfile fingerprint: {}", snapshot.lockfile_fingerprint);
+            println!("  Content digest: {}", snapshot.content_digest);
+        },
+        VendorAction::Diff { project, epoch_a, epoch_b, package, unified } => {
+            let project = resolve_project(project)?;
+            let project_obj = Project::new(
+                "cli-project".to_string(),
+                "CLI Project".to_string(),
+                "rust".to_string(),
+                project.clone(),
+            );
+            
+            let report = adapter.vendor_manager()
+                .diff_epochs(&project_obj, &epoch_a, &epoch_b, package.as_deref(), unified)
+                .map_err(|e| format!("Failed to diff vendored sources: {}", e))?;
+            
+            println!("Vendored source changes from {} to {}", report.epoch_a, report.epoch_b);
+            if let Some(package) = &report.package {
+                println!("  Package: {}", package);
+            }
+            println!("  Added: {}", report.count(VendorFileChangeKind::Added));
+            println!("  Removed: {}", report.count(VendorFileChangeKind::Removed));
+            println!("  Modified: {}", report.count(VendorFileChangeKind::Modified));
+            println!("  Unchanged: {}", report.unchanged);
+            
+            for change in &report.changes {
+                let marker = match change.kind {
+                    VendorFileChangeKind::Added => "A",
+                    VendorFileChangeKind::Removed => "D",
+                    VendorFileChangeKind::Modified => "M",
+                };
+                let hash = |sha256: &Option<String>| sha256.as_deref().map(|h| &h[..h.len().min(12)]).unwrap_or("-").to_string();
+                println!("{} {} ({} -> {})", marker, change.path, hash(&change.sha256_a), hash(&change.sha256_b));
+                
+                if unified {
+                    match &change.unified_diff {
+                        Some(diff) => print!("{}", diff),
+                        None => println!("  (contents not available in the epoch object store)"),
+                    }
+                }
+            }
+        },
+        VendorAction::Report { .. } => unreachable!("vendor report is handled by cmd_vendor_report"),
+        VendorAction::Restore { archive, output } if dry_run => {
+            let plan = adapter.vendor_manager().plan_restore(&archive, &output)
+                .map_err(|e| format!("Failed to plan vendor restore: {}", e))?;
+            print_plan(&plan);
+        },
+        VendorAction::Restore { archive, output } => {
+            println!("Restoring vendor archive: {:?}", archive);
+            
+            let manifest = adapter.vendor_manager().restore_vendor(&archive, &output)
+                .map_err(|e| format!("Failed to restore vendor archive: {}", e))?;
+            
+            println!("Restored {} verified files to {:?}", manifest.total_files, output);
+            if let Some(epoch) = &manifest.epoch_id {
+                println!("  Epoch: {}", epoch);
+            }
+        },
+    }
+    
+    Ok(())
+}
+
+/// Export policy facts command
+async fn cmd_facts(
+    adapter: &RustAdapter,
+    project: &Path,
+    output: &Option<PathBuf>,
+    epoch: Option<&str>,
+    run_audit: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let facts = adapter.policy_facts(&project_obj, epoch, run_audit).await
+        .map_err(|e| format!("Failed to collect policy facts: {}", e))?;
+    let facts_content = serde_json::to_string_pretty(&facts)?;
+    
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, facts_content)
+                .map_err(|e| format!("Failed to write policy facts: {}", e))?;
+            eprintln!("Policy facts written: {:?} ({} packages)", output_path, facts.summary.total_packages);
+        },
+        None => println!("{}", facts_content),
+    }
+    
+    Ok(())
+}
+
+/// Universal epoch export command
+async fn cmd_epoch_export(
+    adapter: &RustAdapter,
+    project: &Path,
+    epoch: &str,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let document = adapter.export_epoch(&project_obj, epoch).await
+        .map_err(|e| match &e {
+            AdapterError::SchemaValidationFailed { errors, .. } => format!("Failed to export epoch {}: {}", epoch, errors.join("; ")),
+            _ => format!("Failed to export epoch {}: {}", epoch, e),
+        })?;
+    let content = serde_json::to_string_pretty(&document)?;
+    
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, content)
+                .map_err(|e| format!("Failed to write epoch document: {}", e))?;
+            eprintln!("Epoch {} written: {:?} ({} packages)", epoch, output_path, document.packages.len());
+        },
+        None => println!("{}", content),
+    }
+    
+    Ok(())
+}
+
+/// Universal epoch import command
+fn cmd_epoch_import(
+    adapter: &RustAdapter,
+    input: &PathBuf,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(input)
+        .map_err(|e| format!("Failed to read epoch document {:?}: {}", input, e))?;
+    
+    let imported = adapter.epoch_exporter().import(&content)
+        .map_err(|e| match &e {
+            AdapterError::SchemaValidationFailed { errors, .. } => format!("Invalid epoch document {:?}: {}", input, errors.join("; ")),
+            _ => format!("Failed to import epoch document {:?}: {}", input, e),
+        })?;
+    
+    println!("Epoch {} (created {})", imported.epoch_id, imported.created_at);
+    println!("  Packages: {}", imported.graph.root_packages.len());
+    println!("  Edges: {}", imported.graph.edges.len());
+    if let Some(vendor) = &imported.vendor {
+        println!("  Vendor digest: {}", vendor.content_digest);
+    }
+    
+    if let Some(output_path) = output {
+        write_graph_snapshot(adapter, &imported.graph, output_path)?;
+    }
+    
+    Ok(())
+}
+
+/// Transparency log verification command
+fn cmd_log_verify(
+    adapter: &RustAdapter,
+    project_path: &Path,
+    head: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let log = TransparencyLog::new(project_path, &adapter.config().transparency_log_config);
+    let verification = log.verify(head)?;
+    
+    println!("Transparency log: {}", verification.log_file.display());
+    println!("  Entries: {}", verification.entries);
+    println!("  Head: {}", verification.head_hash.as_deref().unwrap_or("-"));
+    println!("  Signing key: {}", verification.public_key.as_deref().unwrap_or("-"));
+    
+    if verification.is_intact() {
+        println!("Transparency log is intact");
+        return Ok(());
+    }
+    
+    for problem in &verification.problems {
+        match problem.sequence {
+            Some(sequence) => println!("  line {} (entry {}): {}", problem.line, sequence, problem.reason),
+            None => println!("  line {}: {}", problem.line, problem.reason),
+        }
+    }
+    Err(format!("Transparency log has {} problem(s)", verification.problems.len()).into())
+}
+
+/// Build-time execution report command
+fn cmd_build_report(
+    adapter: &RustAdapter,
+    project: &Path,
+    vendored: Option<&std::path::Path>,
+    epoch: Option<&str>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
     
-    // Initialize logging
-    init_logging(&cli.log_level);
+    let report = adapter.build_execution_report(&project_obj, epoch, vendored)
+        .map_err(|e| format!("Failed to analyze build-time execution: {}", e))?;
     
-    // Load configuration
-    let config = load_config(&cli.config).await?;
+    println!("Build-time execution in {:?}", report.vendor_dir);
+    if let Some(epoch) = &report.epoch_id {
+        println!("  Epoch: {}", epoch);
+    }
+    println!("  Crates scanned: {}", report.summary.crates_scanned);
+    println!("  Build scripts: {}, proc-macros: {}", report.summary.build_scripts, report.summary.proc_macros);
+    println!("  Appear to use fs: {}, net: {}, process: {}",
+        report.summary.filesystem, report.summary.network, report.summary.process_spawn);
+    if report.summary.not_fully_analyzed > 0 {
+        println!("  Not fully analyzed (review manually): {}", report.summary.not_fully_analyzed);
+    }
     
-    // Create adapter
-    let adapter = RustAdapter::new(config);
+    for entry in &report.crates {
+        let mut kinds = Vec::new();
+        if let Some(script) = &entry.build_script {
+            kinds.push(format!("build script {}", script.display()));
+        }
+        if entry.proc_macro {
+            kinds.push("proc-macro".to_string());
+        }
+        let capabilities: Vec<_> = entry.capabilities.iter().map(|c| c.to_string()).collect();
+        let capabilities = if capabilities.is_empty() { "none detected".to_string() } else { capabilities.join(", ") };
+        
+        println!("{} {} ({}): {}", entry.name, entry.version, kinds.join(", "), capabilities);
+        for evidence in &entry.evidence {
+            println!("  [{}] {}:{} ({})", evidence.capability, evidence.file.display(), evidence.line, evidence.pattern);
+        }
+        for skipped in &entry.not_analyzed {
+            println!("  [not analyzed] {}: {}", skipped.file.display(), skipped.reason);
+        }
+    }
     
-    // Run command
-    match cli.command {
-        Commands::Parse { project } => {
-            cmd_parse(&adapter, &project).await?;
-        },
-        Commands::Audit { project } => {
-            cmd_audit(&adapter, &project).await?;
-        },
-        Commands::Sbom { project, output, format } => {
-            cmd_sbom(&adapter, &project, &output, &format).await?;
-        },
-        Commands::Vendor { project, output } => {
-            cmd_vendor(&adapter, &project, &output).await?;
-        },
-        Commands::VerifyVendor { project, vendored } => {
-            cmd_verify_vendor(&adapter, &project, &vendored).await?;
-        },
-        Commands::Drift { project, epoch } => {
-            cmd_drift(&adapter, &project, &epoch).await?;
-        },
+    if let Some(output_path) = output {
+        let report_content = serde_json::to_string_pretty(&report)?;
+        write_artifact(output_path, report_content)
+            .map_err(|e| format!("Failed to write build-time execution report: {}", e))?;
+        println!("Report written: {:?}", output_path);
     }
     
     Ok(())
 }
 
-/// Initialize logging
-fn init_logging(level: &str) {
-    use tracing_subscriber::{EnvFilter, fmt};
+/// Third-party attribution command
+async fn cmd_attribution(
+    adapter: &RustAdapter,
+    project: &Path,
+    vendored: Option<&std::path::Path>,
+    epoch: Option<&str>,
+    output: &PathBuf,
+    index: Option<&PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
     
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new(level));
+    let bundle = adapter.attribution_bundle(&project_obj, epoch, vendored).await
+        .map_err(|e| format!("Failed to collect attribution: {}", e))?;
+    
+    let stats = &bundle.statistics;
+    println!("Packages attributed: {}", stats.packages);
+    println!("License and notice files: {} ({} distinct texts)", stats.files, stats.distinct_texts);
+    for package in &stats.packages_without_license_file {
+        println!("  No license file: {}", package);
+    }
+    for package in &bundle.not_vendored {
+        println!("  Not vendored: {}", package);
+    }
     
-    fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .with_timer(false)
-        .compact()
-        .init();
+    write_artifact(output, bundle.render_notices())
+        .map_err(|e| format!("Failed to write notices: {}", e))?;
+    println!("Notices written: {:?}", output);
+    
+    let index_path = index.cloned().unwrap_or_else(|| output.with_extension("json"));
+    let index_content = serde_json::to_string_pretty(&bundle)?;
+    write_artifact(&index_path, index_content)
+        .map_err(|e| format!("Failed to write attribution index: {}", e))?;
+    println!("Index written: {:?}", index_path);
+    
+    Ok(())
 }
 
-/// Load configuration from file
-async fn load_config(config_path: &PathBuf) -> Result<RustAdapterConfig, Box<dyn std::error::Error>> {
-    if config_path.exists() {
-        RustAdapterConfig::load_from_file(config_path)
-            .map_err(|e| format!("Failed to load config: {}", e))?
-    } else {
-        eprintln!("Config file {:?} not found, using defaults", config_path);
-        RustAdapterConfig::default()
+/// Version requirement skew command
+async fn cmd_skew(
+    adapter: &RustAdapter,
+    project: &Path,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let report = adapter.version_skew_report(&project_obj).await
+        .map_err(|e| format!("Failed to analyze version requirements: {}", e))?;
+    
+    println!("Checked {} declared requirements against Cargo.lock", report.declarations_checked);
+    println!("  Unsatisfied: {}", report.count(VersionSkewKind::Unsatisfied));
+    println!("  Barely satisfied: {}", report.count(VersionSkewKind::BarelySatisfied));
+    println!("  Wildcard on TCS: {}", report.count(VersionSkewKind::WildcardTcs));
+    
+    for finding in &report.findings {
+        println!("  [{:?}] {} (declared by {}, resolved: {})",
+            finding.severity, finding.message, finding.declared_by, finding.resolved_versions.join(", "));
     }
     
-    Ok(config)
+    if let Some(output_path) = output {
+        let report_content = serde_json::to_string_pretty(&report)?;
+        write_artifact(output_path, report_content)
+            .map_err(|e| format!("Failed to write version skew report: {}", e))?;
+        println!("Report written: {:?}", output_path);
+    }
+    
+    Ok(())
 }
 
-/// Parse dependencies command
-async fn cmd_parse(adapter: &RustAdapter, project: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Parsing dependencies from project: {:?}", project);
+/// Update what-if simulation command
+async fn cmd_simulate(
+    adapter: &RustAdapter,
+    project: &Path,
+    updates: &[ProposedUpdate],
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let names: Vec<String> = updates.iter().map(ToString::to_string).collect();
+    println!("Simulating updates: {}", names.join(", "));
     
     let project_obj = Project::new(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
     
-    let dependency_graph = adapter.parse_dependencies(&project_obj).await
-        .map_err(|e| format!("Failed to parse dependencies: {}", e))?;
+    let simulation = adapter.simulate_update(&project_obj, updates).await
+        .map_err(|e| format!("Failed to simulate updates: {}", e))?;
     
-    println!("Successfully parsed {} dependencies", dependency_graph.root_packages.len());
+    let report = &simulation.drift_report;
+    println!("Packages after update: {}", simulation.graph.root_packages.len());
+    println!("Drifts from current Cargo.lock: {}", report.drifts.len());
+    for drift in &report.drifts {
+        println!("  {} - {}: {} -> {} {:?} [{}]",
+            drift.package_name,
+            drift.change_type,
+            drift.previous_version.as_deref().unwrap_or("-"),
+            drift.current_version.as_deref().unwrap_or("-"),
+            drift.priority,
+            drift.classification);
+    }
     
-    for package in &dependency_graph.root_packages {
-        println!("  {} {} ({})", package.name, package.version, 
-            match &package.classification {
-                crate::models::dependency_graph::Classification::TCS { category, .. } => 
-                    format!("TCS: {:?}", category),
-                crate::models::dependency_graph::Classification::Mechanical { .. } => 
-                    "Mechanical".to_string(),
-                crate::models::dependency_graph::Classification::Unknown => 
-                    "Unknown".to_string(),
-            });
+    if let Some(output_path) = output {
+        let simulation_content = serde_json::to_string_pretty(&simulation)?;
+        write_artifact(output_path, simulation_content)
+            .map_err(|e| format!("Failed to write simulation report: {}", e))?;
+        println!("Report written: {:?}", output_path);
     }
     
     Ok(())
 }
 
-/// Run audit command
-async fn cmd_audit(adapter: &RustAdapter, project: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Running security audit for project: {:?}", project);
+/// Mirroring manifest command
+fn cmd_mirror_manifest(
+    adapter: &RustAdapter,
+    project: &Path,
+    epoch: &str,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let manifest = adapter.vendor_manager().mirror_manifest(&project_obj, epoch)
+        .map_err(|e| format!("Failed to build mirror manifest: {}", e))?;
+    let without_url = manifest.packages.iter()
+        .filter(|package| matches!(package.source, MirrorSource::Registry { download_url: None, .. }))
+        .count();
+    
+    eprintln!("Packages to mirror for epoch {}: {}", epoch, manifest.packages.len());
+    if without_url > 0 {
+        eprintln!("Warning: {} registry package(s) have no download URL; add their registry to vendor_config.registry_downloads", without_url);
+    }
+    for unsupported in &manifest.unsupported {
+        eprintln!("Warning: cannot mirror {}", unsupported);
+    }
+    
+    let manifest_content = serde_json::to_string_pretty(&manifest)?;
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, manifest_content)
+                .map_err(|e| format!("Failed to write mirror manifest: {}", e))?;
+            eprintln!("Manifest written: {:?}", output_path);
+        },
+        None => println!("{}", manifest_content),
+    }
+    
+    Ok(())
+}
+
+/// Remediation payload command
+async fn cmd_remediate(
+    adapter: &RustAdapter,
+    project: &Path,
+    finding: &str,
+    output: &Option<PathBuf>,
+    summary: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    eprintln!("Remediating finding: {}", finding);
     
     let project_obj = Project::new(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
     
-    let audit_report = adapter.run_audit(&project_obj).await
-        .map_err(|e| format!("Failed to run audit: {}", e))?;
+    let payload = adapter.remediate(&project_obj, finding).await
+        .map_err(|e| format!("Failed to remediate {}: {}", finding, e))?;
     
-    println!("Audit completed successfully");
+    for upgrade in &payload.upgrades {
+        eprintln!("  {} {} -> {}", upgrade.package_name, upgrade.current_version,
+            upgrade.suggested_version.as_deref().unwrap_or("-"));
+    }
+    for update in &payload.requirement_updates {
+        eprintln!("  {}: {} {} -> {}", update.manifest, update.package_name, update.previous, update.updated);
+    }
     
-    if let Some(cargo_audit_output) = &audit_report.raw_cargo_audit {
-        println!("Cargo-audit output available ({} bytes)", cargo_audit_output.len());
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, &payload.patch)
+                .map_err(|e| format!("Failed to write patch: {}", e))?;
+            eprintln!("Patch written: {:?}", output_path);
+        },
+        None => print!("{}", payload.patch),
+    }
+    if let Some(summary_path) = summary {
+        let summary_content = serde_json::to_string_pretty(&payload)?;
+        write_artifact(summary_path, summary_content)
+            .map_err(|e| format!("Failed to write remediation summary: {}", e))?;
+        eprintln!("Summary written: {:?}", summary_path);
     }
     
-    if let Some(cargo_vet_output) = &audit_report.raw_cargo_vet {
-        println!("Cargo-vet output available ({} bytes)", cargo_vet_output.len());
+    Ok(())
+}
+
+/// Organization rollup report command
+async fn cmd_serve(
+    adapter: &RustAdapter,
+    projects: &[PathBuf],
+    interval: u64,
+    poll: u64,
+    output: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sink = ReportDirectorySink::new(output.to_path_buf())
+        .map_err(|e| format!("Failed to open scheduled audit directory: {}", e))?;
+    let mut scheduler = AuditScheduler::for_adapter(Arc::new(adapter.clone()), Arc::new(sink));
+    for path in projects {
+        let id = path.canonicalize().ok()
+            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "cli-project".to_string());
+        scheduler = scheduler.add_project(
+            Project::new(id.clone(), id, "rust".to_string(), path.clone()),
+            Duration::from_secs(interval),
+        );
     }
     
-    println!("Total findings: {}", audit_report.findings.len());
+    eprintln!("Auditing {} project(s) every {}s into {:?} (Ctrl+C to stop)", projects.len(), interval, output);
+    scheduler.run(Duration::from_secs(poll), adapter.cancellation_token()).await;
+    Ok(())
+}
+
+async fn cmd_org_report(
+    adapter: &RustAdapter,
+    base_dir: &Path,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let report = adapter.org_report(base_dir).await
+        .map_err(|e| format!("Failed to build organization report: {}", e))?;
+    if report.projects.is_empty() {
+        return Err("No projects configured in org_config.projects".into());
+    }
+    
+    eprintln!("Organization: {}", report.organization);
+    for project in &report.projects {
+        match &project.error {
+            Some(error) => eprintln!("  {} - failed: {}", project.id, error),
+            None => eprintln!("  {} - {} dependencies ({} TCS), {} findings", project.id, project.dependencies,
+                project.tcs_dependencies, project.findings_by_severity.values().sum::<usize>()),
+        }
+    }
+    let statistics = &report.statistics;
+    eprintln!("Unique dependencies: {} ({} TCS, {} TCS shared)", statistics.unique_dependencies,
+        statistics.unique_tcs_dependencies, report.shared_tcs_components.len());
+    eprintln!("Unique findings: {} ({} in several projects)", statistics.unique_findings, statistics.shared_findings);
+    
+    let report_content = serde_json::to_string_pretty(&report)?;
+    match output {
+        Some(output_path) => {
+            write_artifact(output_path, report_content)
+                .map_err(|e| format!("Failed to write organization report: {}", e))?;
+            eprintln!("Report written: {:?}", output_path);
+        },
+        None => println!("{}", report_content),
+    }
     
     Ok(())
 }
 
-/// Generate SBOM command
-async fn cmd_sbom(
+/// Interactive review command
+#[cfg(feature = "tui")]
+async fn cmd_tui(
+    adapter: &RustAdapter,
+    project: &Path,
+    since: Option<&str>,
+    to: Option<&str>,
+    reviewer: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    rust_ecosystem_adapter::tui::run(adapter, &project_obj, since, to, &reviewer).await
+        .map_err(|e| format!("Review session failed: {}", e))?;
+    
+    let review = adapter.vendor_manager().load_review_decisions(&project_obj)
+        .map_err(|e| format!("Failed to read review decisions: {}", e))?;
+    if let Some(review) = review {
+        println!("Review decisions: {} approved, {} rejected", review.approved(), review.rejected().len());
+    }
+    
+    Ok(())
+}
+
+/// Multi-epoch drift timeline command
+async fn cmd_drift_timeline(
     adapter: &RustAdapter,
-    project: &PathBuf,
+    project: &Path,
+    since: &str,
     output: &Option<PathBuf>,
-    format: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Generating {} SBOM for project: {:?}", format, project);
+    println!("Building drift timeline since epoch: {}", since);
     
     let project_obj = Project::new(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
     
-    let sbom = adapter.generate_sbom(&project_obj).await
-        .map_err(|e| format!("Failed to generate SBOM: {}", e))?;
+    let mut timeline = adapter.drift_timeline(&project_obj, since).await
+        .map_err(|e| format!("Failed to build drift timeline: {}", e))?;
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_drift_timeline(&mut timeline);
+    }
     
-    let output_path = output.as_ref().unwrap_or(&PathBuf::from(format!("sbom.{}", format)));
+    for epoch in &timeline.epochs {
+        println!("  {} (from {}): {} changes, {} packages",
+            epoch.epoch_id.as_deref().unwrap_or("Cargo.lock (unapproved)"),
+            epoch.previous_epoch_id, epoch.changes, epoch.total_packages);
+    }
     
-    let sbom_content = match sbom {
-        crate::models::Sbom::Spdx(doc) => serde_json::to_string_pretty(&doc)?,
-        crate::models::Sbom::CycloneDx(doc) => serde_json::to_string_pretty(&doc)?,
-    };
+    for (package, changes) in &timeline.packages {
+        println!("  {}", package);
+        for change in changes {
+            println!("    {:?} {} -> {} [{:?}] approved in {}",
+                change.change_type,
+                change.previous_version.as_deref().unwrap_or("-"),
+                change.current_version.as_deref().unwrap_or("-"),
+                change.priority,
+                change.approved_in.as_deref().unwrap_or("(none)"));
+        }
+    }
     
-    std::fs::write(output_path, sbom_content)
-        .map_err(|e| format!("Failed to write SBOM: {}", e))?;
+    let stats = &timeline.statistics;
+    println!("Epochs covered: {}", stats.epochs_covered);
+    println!("Total changes: {} ({} additions, {} removals, {} version changes, {} source changes)",
+        stats.total_changes, stats.additions, stats.removals, stats.version_changes, stats.source_changes);
+    println!("Packages changed: {} ({} in more than one epoch)", stats.packages_changed, stats.packages_changed_repeatedly);
+    println!("Unapproved changes: {}", stats.unapproved_changes);
     
-    println!("SBOM generated successfully: {:?}", output_path);
+    if let Some(output_path) = output {
+        let timeline_content = serde_json::to_string_pretty(&timeline)?;
+        write_artifact(output_path, timeline_content)
+            .map_err(|e| format!("Failed to write drift timeline: {}", e))?;
+        println!("Timeline written: {:?}", output_path);
+    }
     
     Ok(())
 }
 
-/// Vendor dependencies command
-async fn cmd_vendor(
+/// Epoch-pinned build command
+async fn cmd_build(
     adapter: &RustAdapter,
-    project: &PathBuf,
-    output: &Option<PathBuf>,
+    project: &Path,
+    epoch: &str,
+    cargo_args: &[String],
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let output_dir = output.as_ref().unwrap_or(&PathBuf::from("vendor"));
-    
-    println!("Vendoring dependencies from project: {:?}", project);
-    println!("Output directory: {:?}", output_dir);
+    println!("Building against epoch: {}", epoch);
     
     let project_obj = Project::new(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
     
-    adapter.vendor_dependencies(&project_obj, output_dir).await
-        .map_err(|e| format!("Failed to vendor dependencies: {}", e))?;
+    adapter.vendor_manager().build_epoch(&project_obj, epoch, cargo_args).await
+        .map_err(|e| format!("Epoch build failed: {}", e))?;
     
-    println!("Dependencies vendored successfully");
+    println!("Build completed from epoch {}", epoch);
     
     Ok(())
 }
@@ -273,7 +2720,7 @@ async fn cmd_vendor(
 /// Verify vendored dependencies command
 async fn cmd_verify_vendor(
     adapter: &RustAdapter,
-    project: &PathBuf,
+    project: &Path,
     vendored: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("Verifying vendored dependencies: {:?}", vendored);
@@ -282,7 +2729,7 @@ async fn cmd_verify_vendor(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
     
     adapter.verify_vendored(&project_obj, vendored).await
@@ -293,11 +2740,34 @@ async fn cmd_verify_vendor(
     Ok(())
 }
 
+/// Filter and page of a drift report requested on the command line
+struct DriftView {
+    /// Drifts to report
+    query: DriftQuery,
+    /// Matching drifts to skip
+    offset: usize,
+    /// Maximum number of drifts to report
+    limit: Option<usize>,
+    /// Whether to leave drift items out
+    summary_only: bool,
+}
+
+impl DriftView {
+    /// Whether the view differs from the full report
+    fn is_narrowed(&self) -> bool {
+        !self.query.is_empty() || self.offset > 0 || self.limit.is_some() || self.summary_only
+    }
+}
+
 /// Detect drift command
 async fn cmd_drift(
     adapter: &RustAdapter,
-    project: &PathBuf,
+    project: &Path,
     epoch: &str,
+    group_by: Option<&str>,
+    view: &DriftView,
+    output: &Option<PathBuf>,
+    template: &Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("Detecting drift against epoch: {}", epoch);
     
@@ -305,30 +2775,284 @@ async fn cmd_drift(
         "cli-project".to_string(),
         "CLI Project".to_string(),
         "rust".to_string(),
-        project.clone(),
+        project.to_path_buf(),
     );
+    let (drift_report, owners) = project_drift(adapter, &project_obj, epoch).await?;
+    
+    println!("Drift detection completed");
+    println!("Total drifts detected: {}", drift_report.drifts.len());
+    if drift_report.acknowledged_drifts > 0 {
+        println!("Acknowledged drifts suppressed: {}", drift_report.acknowledged_drifts);
+    }
+    if drift_report.depth_suppressed_drifts > 0 {
+        println!("Drifts beyond the transitive depth limit suppressed: {}", drift_report.depth_suppressed_drifts);
+    }
+    record_summary(adapter, &project_obj, ReportSummary::from_drift(&drift_report));
+    
+    if view.is_narrowed() && group_by.is_none() {
+        let page = drift_report.page(&view.query, view.offset, view.limit, view.summary_only);
+        println!("Matching drifts: {}", page.matching_drifts);
+        for drift in page.drifts.iter().flatten() {
+            println!("  {} {} - {:?}: {:?} [{}]", drift.id, drift.package_name, drift.change_type, drift.priority, drift.classification);
+        }
+        if let Some(next_offset) = page.next_offset {
+            println!("More drifts follow; continue with --offset {}", next_offset);
+        }
+        return write_report("Drift", &page, output, template);
+    }
     
+    let mut drift_report = drift_report;
+    drift_report.drifts.retain(|drift| view.query.matches(drift));
+    let groups = match group_by {
+        Some("member") => Some(drift_report.by_workspace_member()),
+        Some("team") => Some(drift_report.by_team()),
+        _ => None,
+    };
+    match groups {
+        Some(groups) => {
+            for (group, drifts) in groups {
+                let reviewers = owners.teams.get(&group)
+                    .filter(|_| group_by == Some("team"))
+                    .map(|team| team.reviewers.join(", "))
+                    .unwrap_or_default();
+                if reviewers.is_empty() {
+                    println!("{} ({} drifts)", group, drifts.len());
+                } else {
+                    println!("{} ({} drifts, reviewers: {})", group, drifts.len(), reviewers);
+                }
+                for drift in drifts {
+                    println!("  {} {} - {}: {:?} [{}]", drift.id, drift.package_name, drift.change_type, drift.priority, drift.classification);
+                }
+            }
+        },
+        None => {
+            for drift in &drift_report.drifts {
+                println!("  {} {} - {}: {:?} [{}]", drift.id, drift.package_name, drift.change_type, drift.priority, drift.classification);
+            }
+        },
+    }
+    
+    write_report("Drift", &drift_report, output, template)
+}
+
+/// Lockfile comparison command
+async fn cmd_lock_diff(
+    adapter: &RustAdapter,
+    before: &Path,
+    after: &Path,
+    output: &Option<PathBuf>,
+    template: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Comparing {:?} with {:?}", before, after);
+    
+    let mut drift_report = adapter.lockfile_drift(before, after).await
+        .map_err(|e| format!("Failed to compare lockfiles: {}", e))?;
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_drift_report(&mut drift_report);
+    }
+    
+    println!("Total drifts detected: {}", drift_report.drifts.len());
+    for drift in &drift_report.drifts {
+        println!("  {} {} - {}: {:?} [{}]", drift.id, drift.package_name, drift.change_type, drift.priority, drift.classification);
+    }
+    
+    write_report("Drift", &drift_report, output, template)
+}
+
+/// Drift of a project against an epoch, attributed to owners, without acknowledged items
+async fn project_drift(
+    adapter: &RustAdapter,
+    project_obj: &Project,
+    epoch: &str,
+) -> Result<(DriftReport, ProjectOwners), Box<dyn std::error::Error>> {
     // Parse current dependencies first
-    let dependency_graph = adapter.parse_dependencies(&project_obj).await
+    let dependency_graph = adapter.parse_dependencies(project_obj).await
         .map_err(|e| format!("Failed to parse dependencies: {}", e))?;
     
     // Create a mock epoch for demonstration
-    let expected_epoch = crate::models::drift_types::Epoch {
+    let expected_epoch = rust_ecosystem_adapter::models::drift_types::Epoch {
         id: epoch.to_string(),
-        analysis_timestamp: chrono::Utc::now().to_rfc3339(),
-        drifts: vec![],
-        summary: crate::models::drift_types::DriftSummary::default(),
-        impact: crate::models::drift_types::DriftImpact::default(),
+        project_id: project_obj.id.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
     };
     
-    let drift_report = adapter.detect_drift(&expected_epoch, &dependency_graph).await
+    let max_depth = rust_ecosystem_adapter::config::load_max_transitive_depth(project_obj)
+        .map_err(|e| format!("Failed to load project policy: {}", e))?;
+    let mut drift_report = adapter.drift_detector().with_max_transitive_depth(max_depth)
+        .detect_drift(&expected_epoch, &dependency_graph).await
         .map_err(|e| format!("Failed to detect drift: {}", e))?;
     
-    println!("Drift detection completed");
-    println!("Total drifts detected: {}", drift_report.drifts.len());
+    let acknowledgements = adapter.drift_detector().load_acknowledgements(project_obj)
+        .map_err(|e| format!("Failed to load drift acknowledgements: {}", e))?;
+    drift_report.suppress_acknowledged(&acknowledgements);
     
-    for drift in &drift_report.drifts {
-        println!("  {} - {}: {:?}", drift.package_name, drift.change_type, drift.priority);
+    let owners = rust_ecosystem_adapter::config::load_project_owners(project_obj)
+        .map_err(|e| format!("Failed to load project owners: {}", e))?;
+    adapter.drift_detector().attribute_drifts(&mut drift_report, &dependency_graph, &owners);
+    if adapter.export_redactor().is_enabled() {
+        adapter.export_redactor().redact_drift_report(&mut drift_report);
+    }
+    
+    Ok((drift_report, owners))
+}
+
+/// Drift report export command
+async fn cmd_drift_export(
+    adapter: &RustAdapter,
+    project: &Path,
+    epoch: &str,
+    output: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    let (drift_report, _) = project_drift(adapter, &project_obj, epoch).await?;
+    
+    let report_content = serde_json::to_string_pretty(&drift_report)?;
+    write_artifact(output, report_content)
+        .map_err(|e| format!("Failed to write drift report: {}", e))?;
+    println!("Drift report written: {:?} ({} items, {} acknowledged, {} beyond the depth limit)",
+        output, drift_report.drifts.len(), drift_report.acknowledged_drifts, drift_report.depth_suppressed_drifts);
+    
+    Ok(())
+}
+
+/// Drift acknowledgement command
+fn cmd_drift_ack(
+    adapter: &RustAdapter,
+    project: &Path,
+    report: &PathBuf,
+    items: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let content = std::fs::read_to_string(report)
+        .map_err(|e| format!("Failed to read drift report {:?}: {}", report, e))?;
+    let drift_report = DriftReport::from_json(&content)
+        .map_err(|e| format!("Invalid drift report {:?}: {}", report, e))?;
+    
+    let mut acknowledgements = adapter.drift_detector().load_acknowledgements(&project_obj)
+        .map_err(|e| format!("Failed to load drift acknowledgements: {}", e))?;
+    acknowledgements.acknowledge(&drift_report, items)?;
+    adapter.drift_detector().save_acknowledgements(&project_obj, &acknowledgements)
+        .map_err(|e| format!("Failed to save drift acknowledgements: {}", e))?;
+    
+    for drift in drift_report.drifts.iter().filter(|drift| items.contains(&drift.id)) {
+        println!("  Acknowledged {} {} - {:?}", drift.id, drift.package_name, drift.change_type);
+    }
+    println!("{} drift item(s) acknowledged ({} in total)", items.len(), acknowledgements.items.len());
+    
+    Ok(())
+}
+
+/// Artifact verification command
+fn cmd_verify_artifacts(manifest_path: &Path, events: &EventSink) -> Result<(), Box<dyn std::error::Error>> {
+    let verifications = rust_ecosystem_adapter::utils::verify_manifest(manifest_path)
+        .map_err(|e| format!("Failed to read run manifest: {}", e))?;
+    
+    let mut failed = 0;
+    for verification in &verifications {
+        match &verification.reason {
+            None => println!("  [ok] {}", verification.path),
+            Some(reason) => {
+                failed += 1;
+                println!("  [FAIL] {}: {}", verification.path, reason);
+                events.emit(AdapterEvent::VerificationFailed { subject: verification.path.clone(), reason: reason.clone() });
+            },
+        }
+    }
+    
+    if failed > 0 {
+        return Err(format!("{} of {} artifacts failed verification", failed, verifications.len()).into());
+    }
+    println!("All {} artifacts verified", verifications.len());
+    
+    Ok(())
+}
+
+/// Gate command
+/// Environment doctor command
+async fn cmd_doctor(
+    adapter: &RustAdapter,
+    project: &Path,
+    config_error: Option<&AdapterError>,
+    format: &str,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let project_obj = Project::new(
+        "cli-project".to_string(),
+        "CLI Project".to_string(),
+        "rust".to_string(),
+        project.to_path_buf(),
+    );
+    
+    let report = adapter.doctor_report(&project_obj, config_error).await;
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => {
+            for check in &report.checks {
+                println!("  [{}] {}: {}", check.status.as_str(), check.name, check.message);
+                for guidance in &check.guidance {
+                    println!("      - {}", guidance);
+                }
+            }
+        },
+    }
+    
+    if let Some(output_path) = output {
+        let report_content = serde_json::to_string_pretty(&report)?;
+        write_artifact(output_path, report_content)
+            .map_err(|e| format!("Failed to write doctor report: {}", e))?;
+        eprintln!("Doctor report written: {:?}", output_path);
+    }
+    
+    if !report.healthy {
+        return Err(format!("{} of {} checks failed", report.count(DoctorStatus::Fail), report.checks.len()).into());
+    }
+    if format != "json" {
+        println!("Environment ready ({} warnings)", report.count(DoctorStatus::Warn));
+    }
+    
+    Ok(())
+}
+
+fn cmd_gate(
+    adapter: &RustAdapter,
+    facts: Option<&PathBuf>,
+    drift: Option<&PathBuf>,
+    output: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let facts: Option<PolicyFacts> = facts.map(|path| read_report(path, "policy facts")).transpose()?;
+    let drift: Option<DriftReport> = drift.map(|path| read_report(path, "drift report")).transpose()?;
+    
+    let report = adapter.gate_evaluator().evaluate(facts.as_ref(), drift.as_ref());
+    for check in &report.checks {
+        println!("  [{}] {}: {}", if check.passed { "pass" } else { "FAIL" }, check.rule, check.reason);
+    }
+    
+    if let Some(output_path) = output {
+        let report_content = serde_json::to_string_pretty(&report)?;
+        write_artifact(output_path, report_content)
+            .map_err(|e| format!("Failed to write gate report: {}", e))?;
+        println!("Gate report written: {:?}", output_path);
+    }
+    
+    if !report.passed {
+        return Err(format!("Gate failed: {} of {} checks failed", report.failures().len(), report.checks.len()).into());
+    }
+    if report.checks.is_empty() {
+        println!("Gate passed (no thresholds configured in [gating_config])");
+    } else {
+        println!("Gate passed: {} checks", report.checks.len());
     }
     
     Ok(())
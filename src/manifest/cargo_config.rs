@@ -0,0 +1,359 @@
+//! Cargo configuration source replacement
+//!
+//! Cargo merges the `.cargo/config.toml` files of the directory it runs in
+//! and each of its parents, then `$CARGO_HOME/config.toml`. A `[source]`
+//! table in any of them can redirect crates.io to another registry without
+//! a trace in Cargo.toml or Cargo.lock. This module reads the `[source]`
+//! and `[registries]` tables of that hierarchy as Cargo sees them from the
+//! project root, and compares the effective source replacements with the
+//! config written when the project was vendored.
+
+use crate::error::{AdapterError, Result};
+use crate::utils::config_path;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Directory holding Cargo configuration files
+pub const CONFIG_DIR: &str = ".cargo";
+
+/// Graph metadata property the effective source configuration is recorded in
+pub const CARGO_CONFIG_PROPERTY: &str = "cargo_config";
+
+/// Source crates.io packages are locked from
+const CRATES_IO: &str = "crates-io";
+
+/// Index of crates.io when no `[source.crates-io]` overrides it
+const CRATES_IO_INDEX: &str = "https://github.com/rust-lang/crates.io-index";
+
+/// Effective `[source]` and `[registries]` tables of the Cargo config hierarchy
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CargoSourceConfig {
+    /// Config files read, most specific first
+    pub files: Vec<PathBuf>,
+    /// Source definitions by name
+    pub sources: BTreeMap<String, SourceDefinition>,
+    /// Alternative registry definitions by name
+    pub registries: BTreeMap<String, RegistryDefinition>,
+    /// Replacements differing from the vendor-generated config
+    #[serde(default)]
+    pub replacement_mismatches: Vec<SourceReplacementMismatch>,
+}
+
+/// A `[source.<name>]` table
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SourceDefinition {
+    /// Source this one is replaced with
+    pub replace_with: Option<String>,
+    /// Registry index URL
+    pub registry: Option<String>,
+    /// Directory source, canonicalized after resolving against the config file's location
+    pub directory: Option<String>,
+    /// Local registry, canonicalized after resolving against the config file's location
+    pub local_registry: Option<String>,
+    /// Git repository URL
+    pub git: Option<String>,
+    /// Config file that first defined the source
+    pub defined_in: PathBuf,
+}
+
+/// A `[registries.<name>]` table
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RegistryDefinition {
+    /// Registry index URL
+    pub index: Option<String>,
+    /// Config file that first defined the registry
+    pub defined_in: PathBuf,
+}
+
+/// A source replaced differently than in the vendor-generated config
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceReplacementMismatch {
+    /// Replaced source
+    pub source: String,
+    /// Where the vendor-generated config sends it, `None` if not replaced
+    pub expected: Option<String>,
+    /// Where the effective config sends it, `None` if not replaced
+    pub actual: Option<String>,
+    /// Config file defining the effective replacement
+    pub defined_in: Option<PathBuf>,
+}
+
+/// Subset of a Cargo config file read by the adapter
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    source: BTreeMap<String, SourceTable>,
+    #[serde(default)]
+    registries: BTreeMap<String, RegistryTable>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SourceTable {
+    replace_with: Option<String>,
+    registry: Option<String>,
+    directory: Option<PathBuf>,
+    local_registry: Option<PathBuf>,
+    git: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RegistryTable {
+    index: Option<String>,
+}
+
+impl CargoSourceConfig {
+    /// Load the configuration Cargo sees when run in `root`
+    pub fn load(root: &Path) -> Result<Self> {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(CONFIG_DIR)));
+        Self::load_from(root, cargo_home.as_deref())
+    }
+    
+    /// Load the configuration of `root` and its parents, then of `cargo_home`
+    ///
+    /// A directory's `config` file is used instead of its `config.toml`
+    /// when both exist, as in Cargo. Values of more specific files win.
+    pub fn load_from(root: &Path, cargo_home: Option<&Path>) -> Result<Self> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut files: Vec<PathBuf> = root.ancestors()
+            .filter_map(|dir| Self::config_file(&dir.join(CONFIG_DIR)))
+            .collect();
+        if let Some(home_file) = cargo_home.and_then(Self::config_file) {
+            let home_file = home_file.canonicalize().unwrap_or(home_file);
+            if !files.contains(&home_file) {
+                files.push(home_file);
+            }
+        }
+        
+        let mut config = Self::default();
+        for file in files {
+            let base = Self::config_base(&file).to_path_buf();
+            config.merge_file(&file, &base)?;
+        }
+        Ok(config)
+    }
+    
+    /// Read a single config file
+    ///
+    /// Relative paths are resolved against the directory holding the
+    /// file's `.cargo` directory, as Cargo does.
+    pub fn read_file(path: &Path) -> Result<Self> {
+        Self::read_file_in(path, Self::config_base(path))
+    }
+    
+    /// Read a config file meant to be used from `root`
+    ///
+    /// For the config written by vendoring, which is copied into the
+    /// project's `.cargo` directory: relative paths are resolved against
+    /// the project root rather than the file's own location.
+    pub fn read_file_in(path: &Path, root: &Path) -> Result<Self> {
+        let mut config = Self::default();
+        config.merge_file(path, root)?;
+        Ok(config)
+    }
+    
+    /// Where a source is replaced to, following `replace-with` chains
+    ///
+    /// `None` when the source is not replaced. Directory and local
+    /// registry sources are described by their canonical path, so the
+    /// same directory written relative or absolute compares equal.
+    pub fn replacement(&self, source: &str) -> Option<String> {
+        let name = match self.final_source(source)? {
+            Ok(name) => name,
+            Err(name) => return Some(format!("replacement cycle through {}", name)),
+        };
+        
+        let location = match self.sources.get(name) {
+            Some(SourceDefinition { directory: Some(path), .. }) => format!("directory {}", path),
+            Some(SourceDefinition { local_registry: Some(path), .. }) => format!("local-registry {}", path),
+            Some(SourceDefinition { registry: Some(url), .. }) => format!("registry {}", url),
+            Some(SourceDefinition { git: Some(url), .. }) => format!("git {}", url),
+            _ if name == CRATES_IO => format!("registry {}", CRATES_IO_INDEX),
+            _ => format!("undefined source {}", name),
+        };
+        Some(format!("{} ({})", name, location))
+    }
+    
+    /// Whether `source` is replaced with the directory source at `dir`
+    ///
+    /// Both paths are compared canonicalized.
+    pub fn replaces_with_directory(&self, source: &str, dir: &Path) -> bool {
+        let Some(Ok(name)) = self.final_source(source) else {
+            return false;
+        };
+        self.sources.get(name)
+            .and_then(|definition| definition.directory.as_deref())
+            .is_some_and(|directory| directory == canonical_path(dir))
+    }
+    
+    /// Replacements that differ from `expected`
+    pub fn replacement_mismatches(&self, expected: &CargoSourceConfig) -> Vec<SourceReplacementMismatch> {
+        let replaced: BTreeSet<&String> = self.sources.iter()
+            .chain(&expected.sources)
+            .filter(|(_, definition)| definition.replace_with.is_some())
+            .map(|(name, _)| name)
+            .collect();
+        
+        replaced.into_iter()
+            .filter_map(|source| {
+                let (expected, actual) = (expected.replacement(source), self.replacement(source));
+                (expected != actual).then(|| SourceReplacementMismatch {
+                    source: source.clone(),
+                    expected,
+                    actual,
+                    defined_in: self.sources.get(source)
+                        .filter(|definition| definition.replace_with.is_some())
+                        .map(|definition| definition.defined_in.clone()),
+                })
+            })
+            .collect()
+    }
+    
+    /// Last source of a `replace-with` chain starting at `source`
+    ///
+    /// `None` when the source is not replaced; `Err` with the source
+    /// closing the cycle when the chain loops.
+    fn final_source<'a>(&'a self, source: &'a str) -> Option<std::result::Result<&'a str, &'a str>> {
+        let mut name = self.sources.get(source)?.replace_with.as_deref()?;
+        let mut visited = BTreeSet::from([source]);
+        while let Some(next) = self.sources.get(name).and_then(|definition| definition.replace_with.as_deref()) {
+            if !visited.insert(name) {
+                return Some(Err(name));
+            }
+            name = next;
+        }
+        Some(Ok(name))
+    }
+    
+    /// Config file of a `.cargo` directory, if any
+    fn config_file(dir: &Path) -> Option<PathBuf> {
+        [dir.join("config"), dir.join("config.toml")].into_iter().find(|path| path.is_file())
+    }
+    
+    /// Directory holding the `.cargo` directory of a config file
+    fn config_base(path: &Path) -> &Path {
+        path.parent().and_then(Path::parent).unwrap_or(Path::new(""))
+    }
+    
+    /// Add the tables of a config file, keeping values already set
+    ///
+    /// Relative paths are resolved against `base`.
+    fn merge_file(&mut self, path: &Path, base: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::file_not_found(path, "reading Cargo config"))?;
+        let file: ConfigFile = toml::from_str(&content)
+            .map_err(|e| AdapterError::ConfigurationInvalid {
+                field: "source".to_string(),
+                value: path.display().to_string(),
+                reason: format!("TOML parsing error: {}", e),
+                source: anyhow::anyhow!("Invalid Cargo config file"),
+            })?;
+        
+        let resolve = |dir: Option<PathBuf>| dir.map(|dir| canonical_path(&base.join(dir)));
+        for (name, table) in file.source {
+            let definition = self.sources.entry(name).or_insert_with(|| SourceDefinition {
+                defined_in: path.to_path_buf(),
+                ..SourceDefinition::default()
+            });
+            definition.replace_with = definition.replace_with.take().or(table.replace_with);
+            definition.registry = definition.registry.take().or(table.registry);
+            definition.directory = definition.directory.take().or(resolve(table.directory));
+            definition.local_registry = definition.local_registry.take().or(resolve(table.local_registry));
+            definition.git = definition.git.take().or(table.git);
+        }
+        for (name, table) in file.registries {
+            let definition = self.registries.entry(name).or_insert_with(|| RegistryDefinition {
+                index: None,
+                defined_in: path.to_path_buf(),
+            });
+            definition.index = definition.index.take().or(table.index);
+        }
+        
+        self.files.push(path.to_path_buf());
+        Ok(())
+    }
+}
+
+/// Canonical form of a path, as written into Cargo config
+///
+/// Paths that do not exist are only simplified.
+fn canonical_path(path: &Path) -> String {
+    config_path(&path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_replacements_compared_with_vendor_config() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("repo");
+        let home = temp.path().join("home");
+        std::fs::create_dir_all(root.join(CONFIG_DIR)).unwrap();
+        std::fs::create_dir_all(root.join("vendor").join(CONFIG_DIR)).unwrap();
+        std::fs::create_dir_all(&home).unwrap();
+        let vendor_dir = canonical_path(&root.join("vendor"));
+        
+        // Relative to the project root the vendored config is used from
+        let vendored_config = root.join("vendor").join(CONFIG_DIR).join("config.toml");
+        std::fs::write(&vendored_config, r#"
+            [source.crates-io]
+            replace-with = "vendored-sources"
+
+            [source.vendored-sources]
+            directory = "vendor"
+        "#).unwrap();
+        std::fs::write(home.join("config.toml"), format!(r#"
+            [source.crates-io]
+            replace-with = "vendored-sources"
+
+            [source.vendored-sources]
+            directory = "{}/../repo/vendor"
+
+            [registries.internal]
+            index = "sparse+https://registry.example.com/index/"
+        "#, config_path(&root))).unwrap();
+        let expected = CargoSourceConfig::read_file_in(&vendored_config, &root).unwrap();
+        assert!(expected.replaces_with_directory("crates-io", &root.join("vendor")));
+        
+        // The user-level config names the same directory another way
+        let config = CargoSourceConfig::load_from(&root, Some(&home)).unwrap();
+        assert_eq!(config.replacement("crates-io"), Some(format!("vendored-sources (directory {})", vendor_dir)));
+        assert!(config.replaces_with_directory("crates-io", &root.join("vendor")));
+        assert_eq!(config.registries["internal"].index.as_deref(), Some("sparse+https://registry.example.com/index/"));
+        assert!(config.replacement_mismatches(&expected).is_empty());
+        
+        // A directory source elsewhere does not hold the vendored tree
+        std::fs::write(root.join(CONFIG_DIR).join("config.toml"), r#"
+            [source.vendored-sources]
+            directory = "/elsewhere/vendor"
+        "#).unwrap();
+        let config = CargoSourceConfig::load_from(&root, Some(&home)).unwrap();
+        assert!(!config.replaces_with_directory("crates-io", &root.join("vendor")));
+        let mismatches = config.replacement_mismatches(&expected);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].actual.as_deref(), Some("vendored-sources (directory /elsewhere/vendor)"));
+        
+        // A repository config overrides it and redirects crates.io
+        std::fs::write(root.join(CONFIG_DIR).join("config.toml"), r#"
+            [source.crates-io]
+            replace-with = "mirror"
+
+            [source.mirror]
+            registry = "sparse+https://attacker.example/index/"
+        "#).unwrap();
+        let config = CargoSourceConfig::load_from(&root, Some(&home)).unwrap();
+        assert_eq!(config.files.len(), 2);
+        let mismatches = config.replacement_mismatches(&expected);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].source, "crates-io");
+        assert_eq!(mismatches[0].expected, Some(format!("vendored-sources (directory {})", vendor_dir)));
+        assert_eq!(mismatches[0].actual.as_deref(), Some("mirror (registry sparse+https://attacker.example/index/)"));
+        assert_eq!(mismatches[0].defined_in.as_deref(), Some(config.files[0].as_path()));
+    }
+}
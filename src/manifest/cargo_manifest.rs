@@ -0,0 +1,783 @@
+//! Cargo.toml parsing
+//!
+//! This module reads package and workspace manifests: declared
+//! dependencies with their version requirements and features, feature
+//! tables, `[patch]` and `[replace]` sections, and package metadata.
+//! Fields and dependencies inherited with `workspace = true` are resolved
+//! against the workspace root when a whole workspace is loaded.
+
+use crate::error::{AdapterError, Result};
+use crate::models::DependencyKind;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Manifest file name
+pub const MANIFEST_FILE: &str = "Cargo.toml";
+
+/// Parsed Cargo.toml manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CargoManifest {
+    /// Manifest path
+    pub path: PathBuf,
+    /// `[package]` section (absent for virtual manifests)
+    pub package: Option<ManifestPackage>,
+    /// Declared dependencies of all kinds and targets
+    pub dependencies: Vec<ManifestDependency>,
+    /// `[features]` table
+    pub features: BTreeMap<String, Vec<String>>,
+    /// `[patch]` entries
+    pub patches: Vec<ManifestPatch>,
+    /// `[replace]` entries
+    pub replacements: Vec<ManifestReplacement>,
+    /// `[workspace]` section
+    pub workspace: Option<ManifestWorkspace>,
+}
+
+/// `[package]` section of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestPackage {
+    /// Package name
+    pub name: String,
+    /// Package version (absent if unset or not resolvable)
+    pub version: Option<String>,
+    /// Rust edition
+    pub edition: Option<String>,
+    /// Package description
+    pub description: Option<String>,
+    /// SPDX license expression
+    pub license: Option<String>,
+    /// Repository URL
+    pub repository: Option<String>,
+    /// Homepage URL
+    pub homepage: Option<String>,
+    /// Minimum supported Rust version
+    pub rust_version: Option<String>,
+    /// Package authors
+    pub authors: Vec<String>,
+    /// `[package.metadata]` table
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// A declared dependency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestDependency {
+    /// Name the dependency is declared under
+    pub name: String,
+    /// Name of the depended-on crate (differs from `name` when renamed)
+    pub package: String,
+    /// Version requirement
+    pub version_req: Option<String>,
+    /// Dependency source
+    pub source: ManifestDependencySource,
+    /// Dependency kind
+    pub kind: DependencyKind,
+    /// Target `cfg` expression or triple (if target-specific)
+    pub target: Option<String>,
+    /// Features enabled on the dependency
+    pub features: Vec<String>,
+    /// Whether default features are enabled
+    pub default_features: bool,
+    /// Whether the dependency is optional
+    pub optional: bool,
+    /// Whether the declaration uses `workspace = true`
+    pub inherited: bool,
+}
+
+/// Source of a declared dependency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum ManifestDependencySource {
+    /// Registry dependency (`None` means crates.io)
+    Registry {
+        /// Alternative registry name
+        registry: Option<String>,
+    },
+    /// Git dependency
+    Git {
+        /// Repository URL
+        url: String,
+        /// Branch
+        branch: Option<String>,
+        /// Tag
+        tag: Option<String>,
+        /// Revision
+        rev: Option<String>,
+    },
+    /// Path dependency
+    Path {
+        /// Path relative to the declaring manifest
+        path: String,
+    },
+}
+
+/// Entry of a `[patch.<source>]` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestPatch {
+    /// Patched source (`crates-io` or a URL)
+    pub source: String,
+    /// Replacement dependency
+    pub dependency: ManifestDependency,
+}
+
+/// Entry of the `[replace]` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestReplacement {
+    /// Replaced package ID spec (e.g. `foo:1.0.0`)
+    pub spec: String,
+    /// Replacement dependency
+    pub dependency: ManifestDependency,
+}
+
+/// `[workspace]` section of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestWorkspace {
+    /// Member path patterns
+    pub members: Vec<String>,
+    /// Excluded paths
+    pub exclude: Vec<String>,
+    /// Default member paths
+    pub default_members: Vec<String>,
+    /// `[workspace.dependencies]` available for inheritance
+    pub dependencies: Vec<ManifestDependency>,
+    /// `[workspace.metadata]` table
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// A workspace root manifest and its member manifests
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceManifest {
+    /// Workspace root directory
+    pub root: PathBuf,
+    /// Root manifest
+    pub root_manifest: CargoManifest,
+    /// Member manifests, excluding the root package, sorted by path
+    pub members: Vec<CargoManifest>,
+}
+
+/// Raw manifest as written on disk
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawManifest {
+    #[serde(default)]
+    package: Option<RawPackage>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default)]
+    target: BTreeMap<String, RawTarget>,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    patch: BTreeMap<String, BTreeMap<String, RawDependency>>,
+    #[serde(default)]
+    replace: BTreeMap<String, RawDependency>,
+    #[serde(default)]
+    workspace: Option<RawWorkspace>,
+}
+
+/// Raw `[target.<cfg>]` table
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawTarget {
+    #[serde(default)]
+    dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: BTreeMap<String, RawDependency>,
+}
+
+/// Raw `[package]` table
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawPackage {
+    name: String,
+    #[serde(default)]
+    version: Option<Inheritable<String>>,
+    #[serde(default)]
+    edition: Option<Inheritable<String>>,
+    #[serde(default)]
+    description: Option<Inheritable<String>>,
+    #[serde(default)]
+    license: Option<Inheritable<String>>,
+    #[serde(default)]
+    repository: Option<Inheritable<String>>,
+    #[serde(default)]
+    homepage: Option<Inheritable<String>>,
+    #[serde(default)]
+    rust_version: Option<Inheritable<String>>,
+    #[serde(default)]
+    authors: Option<Inheritable<Vec<String>>>,
+    #[serde(default)]
+    metadata: Option<toml::Value>,
+}
+
+/// Raw `[workspace]` table
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    default_members: Vec<String>,
+    #[serde(default)]
+    package: RawWorkspacePackage,
+    #[serde(default)]
+    dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default)]
+    metadata: Option<toml::Value>,
+}
+
+/// Raw `[workspace.package]` table
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawWorkspacePackage {
+    version: Option<String>,
+    edition: Option<String>,
+    description: Option<String>,
+    license: Option<String>,
+    repository: Option<String>,
+    homepage: Option<String>,
+    rust_version: Option<String>,
+    authors: Option<Vec<String>>,
+}
+
+/// Package field that may be inherited from the workspace
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Inheritable<T> {
+    Value(T),
+    Workspace { workspace: bool },
+}
+
+/// Raw dependency declaration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum RawDependency {
+    Version(String),
+    Detailed(RawDependencySpec),
+}
+
+/// Raw dependency table
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawDependencySpec {
+    version: Option<String>,
+    registry: Option<String>,
+    git: Option<String>,
+    branch: Option<String>,
+    tag: Option<String>,
+    rev: Option<String>,
+    path: Option<String>,
+    package: Option<String>,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    optional: bool,
+    #[serde(alias = "default_features")]
+    default_features: Option<bool>,
+    #[serde(default)]
+    workspace: bool,
+}
+
+impl CargoManifest {
+    /// Load a single manifest without resolving workspace inheritance
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = Self::read_raw(path)?;
+        Ok(Self::from_raw(path, raw, None))
+    }
+    
+    /// Parse manifest content without resolving workspace inheritance
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        let raw = Self::parse_raw(path, content)?;
+        Ok(Self::from_raw(path, raw, None))
+    }
+    
+    /// Dependencies of the given kind
+    pub fn dependencies_of_kind(&self, kind: DependencyKind) -> impl Iterator<Item = &ManifestDependency> {
+        self.dependencies.iter().filter(move |d| d.kind == kind)
+    }
+    
+    /// Read and parse a manifest file
+    fn read_raw(path: &Path) -> Result<RawManifest> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::file_not_found(path, "reading Cargo.toml"))?;
+        Self::parse_raw(path, &content)
+    }
+    
+    /// Parse manifest content
+    fn parse_raw(path: &Path, content: &str) -> Result<RawManifest> {
+        toml::from_str(content).map_err(|e| AdapterError::CargoTomlParseError {
+            file: path.to_path_buf(),
+            error: e.to_string(),
+            source: anyhow::anyhow!("Invalid Cargo.toml"),
+        })
+    }
+    
+    /// Convert a raw manifest, resolving inheritance from `workspace` if given
+    fn from_raw(path: &Path, raw: RawManifest, workspace: Option<&RawWorkspace>) -> Self {
+        // A workspace root resolves inheritance against itself
+        let workspace = workspace.or(raw.workspace.as_ref());
+        let inherited_deps = workspace.map(|ws| &ws.dependencies);
+        let mut dependencies = Vec::new();
+        
+        let sections = [
+            (&raw.dependencies, DependencyKind::Normal),
+            (&raw.dev_dependencies, DependencyKind::Dev),
+            (&raw.build_dependencies, DependencyKind::Build),
+        ];
+        for (section, kind) in sections {
+            for (name, dep) in section {
+                dependencies.push(ManifestDependency::from_raw(name, dep, kind.clone(), None, inherited_deps));
+            }
+        }
+        
+        for (target, table) in &raw.target {
+            let sections = [
+                (&table.dependencies, DependencyKind::Normal),
+                (&table.dev_dependencies, DependencyKind::Dev),
+                (&table.build_dependencies, DependencyKind::Build),
+            ];
+            for (section, kind) in sections {
+                for (name, dep) in section {
+                    dependencies.push(ManifestDependency::from_raw(name, dep, kind.clone(), Some(target), inherited_deps));
+                }
+            }
+        }
+        
+        let patches = raw.patch.iter()
+            .flat_map(|(source, entries)| {
+                entries.iter().map(move |(name, dep)| ManifestPatch {
+                    source: source.clone(),
+                    dependency: ManifestDependency::from_raw(name, dep, DependencyKind::Normal, None, None),
+                })
+            })
+            .collect();
+        
+        let replacements = raw.replace.iter()
+            .map(|(spec, dep)| {
+                let name = spec.split([':', '@']).next().unwrap_or(spec);
+                ManifestReplacement {
+                    spec: spec.clone(),
+                    dependency: ManifestDependency::from_raw(name, dep, DependencyKind::Normal, None, None),
+                }
+            })
+            .collect();
+        
+        let defaults = workspace.map(|ws| &ws.package);
+        
+        let package = raw.package.map(|package| {
+            let string = |field: Option<Inheritable<String>>, inherited: Option<&Option<String>>| match field {
+                Some(Inheritable::Value(value)) => Some(value),
+                Some(Inheritable::Workspace { workspace: true }) => inherited.cloned().flatten(),
+                _ => None,
+            };
+            
+            ManifestPackage {
+                name: package.name,
+                version: string(package.version, defaults.map(|d| &d.version)),
+                edition: string(package.edition, defaults.map(|d| &d.edition)),
+                description: string(package.description, defaults.map(|d| &d.description)),
+                license: string(package.license, defaults.map(|d| &d.license)),
+                repository: string(package.repository, defaults.map(|d| &d.repository)),
+                homepage: string(package.homepage, defaults.map(|d| &d.homepage)),
+                rust_version: string(package.rust_version, defaults.map(|d| &d.rust_version)),
+                authors: match package.authors {
+                    Some(Inheritable::Value(authors)) => authors,
+                    Some(Inheritable::Workspace { workspace: true }) => {
+                        defaults.and_then(|d| d.authors.clone()).unwrap_or_default()
+                    },
+                    _ => Vec::new(),
+                },
+                metadata: package.metadata.and_then(|value| serde_json::to_value(value).ok()),
+            }
+        });
+        
+        let workspace = raw.workspace.map(|ws| ManifestWorkspace {
+            dependencies: ws.dependencies.iter()
+                .map(|(name, dep)| ManifestDependency::from_raw(name, dep, DependencyKind::Normal, None, None))
+                .collect(),
+            members: ws.members,
+            exclude: ws.exclude,
+            default_members: ws.default_members,
+            metadata: ws.metadata.and_then(|value| serde_json::to_value(value).ok()),
+        });
+        
+        Self {
+            path: path.to_path_buf(),
+            package,
+            dependencies,
+            features: raw.features,
+            patches,
+            replacements,
+            workspace,
+        }
+    }
+}
+
+impl ManifestDependency {
+    /// Convert a raw declaration, resolving `workspace = true` if possible
+    fn from_raw(
+        name: &str,
+        dep: &RawDependency,
+        kind: DependencyKind,
+        target: Option<&String>,
+        workspace_deps: Option<&BTreeMap<String, RawDependency>>,
+    ) -> Self {
+        let spec = match dep {
+            RawDependency::Version(version) => RawDependencySpec {
+                version: Some(version.clone()),
+                ..RawDependencySpec::default()
+            },
+            RawDependency::Detailed(spec) => spec.clone(),
+        };
+        
+        // Inherited declarations take source and version from the workspace;
+        // the member may add features and mark the dependency optional
+        let inherited = spec.workspace;
+        let (base, extra_features) = match (inherited, workspace_deps.and_then(|deps| deps.get(name))) {
+            (true, Some(RawDependency::Version(version))) => (RawDependencySpec {
+                version: Some(version.clone()),
+                ..RawDependencySpec::default()
+            }, spec.features.clone()),
+            (true, Some(RawDependency::Detailed(ws_spec))) => (ws_spec.clone(), spec.features.clone()),
+            _ => (spec.clone(), Vec::new()),
+        };
+        
+        let source = match (&base.git, &base.path) {
+            (Some(url), _) => ManifestDependencySource::Git {
+                url: url.clone(),
+                branch: base.branch.clone(),
+                tag: base.tag.clone(),
+                rev: base.rev.clone(),
+            },
+            (None, Some(path)) => ManifestDependencySource::Path { path: path.clone() },
+            (None, None) => ManifestDependencySource::Registry { registry: base.registry.clone() },
+        };
+        
+        let mut features = base.features.clone();
+        for feature in extra_features {
+            if !features.contains(&feature) {
+                features.push(feature);
+            }
+        }
+        
+        Self {
+            name: name.to_string(),
+            package: base.package.clone().unwrap_or_else(|| name.to_string()),
+            version_req: base.version.clone(),
+            source,
+            kind,
+            target: target.cloned(),
+            features,
+            default_features: base.default_features.unwrap_or(true),
+            optional: spec.optional,
+            inherited,
+        }
+    }
+}
+
+impl WorkspaceManifest {
+    /// Load the manifest in `project_root` and, if it defines a workspace, its members
+    ///
+    /// Members are taken from `workspace.members` (with `*` and `?`
+    /// wildcards in path components) minus `workspace.exclude`. Path
+    /// dependencies that are implicitly workspace members are not added.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let root_path = project_root.join(MANIFEST_FILE);
+        let raw = CargoManifest::read_raw(&root_path)?;
+        
+        let member_dirs = match &raw.workspace {
+            Some(ws) => Self::expand_members(project_root, &ws.members, &ws.exclude),
+            None => Vec::new(),
+        };
+        
+        let mut members = Vec::new();
+        for member_dir in member_dirs {
+            let member_path = member_dir.join(MANIFEST_FILE);
+            let member_raw = CargoManifest::read_raw(&member_path)?;
+            members.push(CargoManifest::from_raw(&member_path, member_raw, raw.workspace.as_ref()));
+        }
+        
+        Ok(Self {
+            root: project_root.to_path_buf(),
+            root_manifest: CargoManifest::from_raw(&root_path, raw, None),
+            members,
+        })
+    }
+    
+    /// All manifests declaring a package: the root package first, then members
+    pub fn packages(&self) -> impl Iterator<Item = &CargoManifest> {
+        std::iter::once(&self.root_manifest)
+            .chain(self.members.iter())
+            .filter(|manifest| manifest.package.is_some())
+    }
+    
+    /// Package describing the project as a whole
+    ///
+    /// This is the root package, or the only member of a virtual workspace.
+    pub fn root_package(&self) -> Option<&ManifestPackage> {
+        match (&self.root_manifest.package, self.members.as_slice()) {
+            (Some(package), _) => Some(package),
+            (None, [member]) => member.package.as_ref(),
+            _ => None,
+        }
+    }
+    
+    /// Names of the packages belonging to the workspace
+    pub fn member_names(&self) -> BTreeSet<&str> {
+        self.packages()
+            .filter_map(|manifest| manifest.package.as_ref())
+            .map(|package| package.name.as_str())
+            .collect()
+    }
+    
+    /// Directory of the workspace package `name` `version`, relative to the root
+    ///
+    /// Only manifests found at the workspace member paths (and the root
+    /// package) are considered, so a third-party crate sharing a member's
+    /// name is not mistaken for it. Packages without a version are 0.0.0,
+    /// as in Cargo.
+    pub fn member_dir(&self, name: &str, version: &str) -> Option<PathBuf> {
+        self.packages()
+            .find(|manifest| manifest.package.as_ref().is_some_and(|package| {
+                package.name == name && package.version.as_deref().unwrap_or("0.0.0") == version
+            }))
+            .and_then(|manifest| manifest.path.parent())
+            .map(|dir| dir.strip_prefix(&self.root).unwrap_or(dir).to_path_buf())
+    }
+    
+    /// Dependencies declared by workspace packages on crates outside the workspace
+    pub fn direct_dependencies(&self) -> Vec<&ManifestDependency> {
+        let members = self.member_names();
+        
+        self.packages()
+            .flat_map(|manifest| manifest.dependencies.iter())
+            .filter(|dep| !members.contains(dep.package.as_str()))
+            .collect()
+    }
+    
+    /// Whether a crate is declared directly by any workspace package
+    pub fn is_direct_dependency(&self, crate_name: &str) -> bool {
+        self.direct_dependencies().iter().any(|dep| dep.package == crate_name)
+    }
+    
+    /// Features requested on a crate across all workspace declarations
+    pub fn requested_features(&self, crate_name: &str) -> BTreeSet<String> {
+        self.direct_dependencies().into_iter()
+            .filter(|dep| dep.package == crate_name)
+            .flat_map(|dep| dep.features.iter().cloned())
+            .collect()
+    }
+    
+    /// `[patch]` entries, which Cargo honors only in the workspace root
+    pub fn patches(&self) -> &[ManifestPatch] {
+        &self.root_manifest.patches
+    }
+    
+    /// `[replace]` entries, which Cargo honors only in the workspace root
+    pub fn replacements(&self) -> &[ManifestReplacement] {
+        &self.root_manifest.replacements
+    }
+    
+    /// Member directories matching the patterns, minus exclusions
+    pub(crate) fn expand_members(root: &Path, patterns: &[String], exclude: &[String]) -> Vec<PathBuf> {
+        let excluded: Vec<PathBuf> = exclude.iter().map(|path| root.join(path)).collect();
+        let mut dirs = BTreeSet::new();
+        
+        for pattern in patterns {
+            let mut candidates = vec![root.to_path_buf()];
+            for component in Path::new(pattern).components() {
+                let component = component.as_os_str().to_string_lossy();
+                if !component.contains(['*', '?']) {
+                    candidates = candidates.into_iter().map(|dir| dir.join(component.as_ref())).collect();
+                    continue;
+                }
+                
+                candidates = candidates.iter()
+                    .filter_map(|dir| std::fs::read_dir(dir).ok())
+                    .flat_map(|entries| entries.flatten())
+                    .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                    .filter(|entry| Self::matches_wildcard(&component, &entry.file_name().to_string_lossy()))
+                    .map(|entry| entry.path())
+                    .collect();
+            }
+            
+            dirs.extend(candidates.into_iter().filter(|dir| {
+                dir.join(MANIFEST_FILE).is_file()
+                    && dir != root
+                    && !excluded.iter().any(|excluded| dir.starts_with(excluded))
+            }));
+        }
+        
+        dirs.into_iter().collect()
+    }
+    
+    /// Match a path component against a pattern with `*` and `?` wildcards
+    fn matches_wildcard(pattern: &str, name: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        let (mut p, mut n) = (0, 0);
+        let mut backtrack: Option<(usize, usize)> = None;
+        
+        while n < name.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p, n));
+                    p += 1;
+                },
+                Some(&c) if c == '?' || c == name[n] => {
+                    p += 1;
+                    n += 1;
+                },
+                _ => match backtrack {
+                    Some((star, matched)) => {
+                        p = star + 1;
+                        n = matched + 1;
+                        backtrack = Some((star, matched + 1));
+                    },
+                    None => return false,
+                },
+            }
+        }
+        
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    
+    #[test]
+    fn test_parse_package_manifest() {
+        let manifest = CargoManifest::parse(Path::new("Cargo.toml"), r#"
+            [package]
+            name = "demo"
+            version = "0.1.0"
+            license = "MIT"
+            
+            [package.metadata.docs]
+            all-features = true
+            
+            [dependencies]
+            serde = { version = "1.0", features = ["derive"] }
+            rand_core = { package = "rand", version = "0.8", default-features = false, optional = true }
+            local = { path = "../local" }
+            
+            [target.'cfg(unix)'.dependencies]
+            libc = "0.2"
+            
+            [build-dependencies]
+            cc = "1"
+            
+            [features]
+            default = ["rand_core"]
+            
+            [patch.crates-io]
+            serde = { git = "https://github.com/serde-rs/serde", branch = "main" }
+            
+            [replace]
+            "libc:0.2.150" = { path = "vendor/libc" }
+        "#).unwrap();
+        
+        let package = manifest.package.as_ref().unwrap();
+        assert_eq!(package.version.as_deref(), Some("0.1.0"));
+        assert_eq!(package.metadata.as_ref().unwrap()["docs"]["all-features"], serde_json::json!(true));
+        
+        let rand = manifest.dependencies.iter().find(|d| d.name == "rand_core").unwrap();
+        assert_eq!(rand.package, "rand");
+        assert!(rand.optional);
+        assert!(!rand.default_features);
+        
+        let libc = manifest.dependencies.iter().find(|d| d.name == "libc").unwrap();
+        assert_eq!(libc.target.as_deref(), Some("cfg(unix)"));
+        assert_eq!(libc.version_req.as_deref(), Some("0.2"));
+        
+        assert_eq!(manifest.dependencies_of_kind(DependencyKind::Build).count(), 1);
+        assert!(matches!(
+            manifest.dependencies.iter().find(|d| d.name == "local").unwrap().source,
+            ManifestDependencySource::Path { .. }
+        ));
+        assert_eq!(manifest.features["default"], vec!["rand_core".to_string()]);
+        assert_eq!(manifest.patches[0].source, "crates-io");
+        assert!(matches!(manifest.patches[0].dependency.source, ManifestDependencySource::Git { .. }));
+        assert_eq!(manifest.replacements[0].dependency.name, "libc");
+    }
+    
+    #[test]
+    fn test_load_workspace_resolves_inheritance() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        
+        write(&root.join("Cargo.toml"), r#"
+            [workspace]
+            members = ["crates/*"]
+            exclude = ["crates/experimental"]
+            
+            [workspace.package]
+            version = "2.0.0"
+            license = "Apache-2.0"
+            
+            [workspace.dependencies]
+            tokio = { version = "1.0", features = ["rt"] }
+        "#);
+        write(&root.join("crates/app/Cargo.toml"), r#"
+            [package]
+            name = "app"
+            version.workspace = true
+            license.workspace = true
+            
+            [dependencies]
+            tokio = { workspace = true, features = ["macros"] }
+            core = { path = "../core" }
+        "#);
+        write(&root.join("crates/core/Cargo.toml"), "[package]\nname = \"core\"\nversion = \"0.1.0\"\n");
+        write(&root.join("crates/experimental/Cargo.toml"), "[package]\nname = \"experimental\"\nversion = \"0.1.0\"\n");
+        write(&root.join("crates/docs/README.md"), "not a crate");
+        
+        let workspace = WorkspaceManifest::load(root).unwrap();
+        
+        assert_eq!(workspace.members.len(), 2);
+        assert_eq!(workspace.member_names(), BTreeSet::from(["app", "core"]));
+        assert!(workspace.root_package().is_none());
+        assert_eq!(workspace.member_dir("core", "0.1.0"), Some(PathBuf::from("crates/core")));
+        assert_eq!(workspace.member_dir("core", "0.2.0"), None);
+        assert_eq!(workspace.member_dir("experimental", "0.1.0"), None);
+        
+        let app = workspace.members.iter().find_map(|m| m.package.as_ref().filter(|p| p.name == "app")).unwrap();
+        assert_eq!(app.version.as_deref(), Some("2.0.0"));
+        assert_eq!(app.license.as_deref(), Some("Apache-2.0"));
+        
+        assert!(workspace.is_direct_dependency("tokio"));
+        assert!(!workspace.is_direct_dependency("core"));
+        assert_eq!(
+            workspace.requested_features("tokio"),
+            BTreeSet::from(["rt".to_string(), "macros".to_string()])
+        );
+        
+        let tokio = workspace.direct_dependencies()[0];
+        assert!(tokio.inherited);
+        assert_eq!(tokio.version_req.as_deref(), Some("1.0"));
+    }
+    
+    #[test]
+    fn test_matches_wildcard() {
+        assert!(WorkspaceManifest::matches_wildcard("*", "app"));
+        assert!(WorkspaceManifest::matches_wildcard("adapter-*", "adapter-rust"));
+        assert!(WorkspaceManifest::matches_wildcard("a?p", "app"));
+        assert!(!WorkspaceManifest::matches_wildcard("adapter-*", "core"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Project root discovery
+//!
+//! Commands run without `--project` find the project the way cargo finds
+//! its workspace: the nearest Cargo.toml at or above the starting
+//! directory is the current package, and the project root is the
+//! workspace that lists it as a member, or the package itself. The root
+//! must hold the Cargo.lock the adapter parses.
+
+use super::cargo_manifest::{CargoManifest, WorkspaceManifest, MANIFEST_FILE};
+use crate::error::{AdapterError, Result};
+use std::path::{Path, PathBuf};
+
+/// Lockfile a project root must contain
+pub const LOCKFILE: &str = "Cargo.lock";
+
+/// Find the root of the project containing `start`
+///
+/// Fails when no project is found, when more than one workspace claims
+/// the current package, or when the root has no Cargo.lock; the error
+/// lists the candidate roots so one can be passed with `--project`.
+pub fn discover_project_root(start: &Path) -> Result<PathBuf> {
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    let lock_dirs: Vec<&Path> = start.ancestors().filter(|dir| dir.join(LOCKFILE).is_file()).collect();
+    
+    // Without any manifest, a directory with a lockfile is still parseable
+    let Some(package_dir) = start.ancestors().find(|dir| dir.join(MANIFEST_FILE).is_file()) else {
+        return lock_dirs.first()
+            .map(|dir| dir.to_path_buf())
+            .ok_or_else(|| not_found(&start));
+    };
+    
+    let roots = workspace_roots(package_dir);
+    let root = match roots.as_slice() {
+        [] => package_dir,
+        [root] => *root,
+        _ => return Err(ambiguous(&start, "more than one workspace includes the current package", &roots)),
+    };
+    
+    if !root.join(LOCKFILE).is_file() {
+        return match lock_dirs.is_empty() {
+            true => Err(not_found(&start)),
+            false => Err(ambiguous(&start, &format!("{} has no {}", root.display(), LOCKFILE), &lock_dirs)),
+        };
+    }
+    
+    tracing::debug!(root = %root.display(), "Discovered project root");
+    Ok(root.to_path_buf())
+}
+
+/// Workspace roots at or above `package_dir` that include it, nearest first
+///
+/// A package declaring `[workspace]` itself is its own root, as in cargo.
+/// Manifests that cannot be parsed are skipped.
+fn workspace_roots(package_dir: &Path) -> Vec<&Path> {
+    let mut roots = Vec::new();
+    for dir in package_dir.ancestors() {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.is_file() {
+            continue;
+        }
+        let workspace = match CargoManifest::load(&path) {
+            Ok(manifest) => manifest.workspace,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Skipping unreadable manifest");
+                continue;
+            },
+        };
+        let Some(workspace) = workspace else {
+            continue;
+        };
+        
+        if dir == package_dir {
+            return vec![dir];
+        }
+        if WorkspaceManifest::expand_members(dir, &workspace.members, &workspace.exclude).iter().any(|member| member == package_dir) {
+            roots.push(dir);
+        }
+    }
+    roots
+}
+
+/// Lockfiles at or below `root`, relative to it, the root's own first
+///
+/// Finds the lockfiles of excluded workspace members and nested standalone
+/// crates. Build output (`target`), hidden directories and vendored crates
+/// (marked by `.cargo-checksum.json`) are not searched.
+pub fn discover_lockfiles(root: &Path) -> Vec<PathBuf> {
+    let walker = walkdir::WalkDir::new(root).follow_links(false).into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 || !entry.file_type().is_dir() {
+                return true;
+            }
+            let name = entry.file_name().to_string_lossy();
+            name != "target" && !name.starts_with('.') && !entry.path().join(".cargo-checksum.json").is_file()
+        });
+    
+    let mut lockfiles: Vec<PathBuf> = walker.flatten()
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == LOCKFILE)
+        .filter_map(|entry| entry.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect();
+    lockfiles.sort_by_key(|path| (path.components().count(), path.clone()));
+    lockfiles
+}
+
+fn not_found(start: &Path) -> AdapterError {
+    AdapterError::InvalidPath {
+        path: start.display().to_string(),
+        reason: format!("no {} found in this directory or any parent directory", LOCKFILE),
+        source: anyhow::anyhow!("Project not found"),
+    }
+}
+
+fn ambiguous(start: &Path, reason: &str, candidates: &[&Path]) -> AdapterError {
+    let candidates = candidates.iter()
+        .map(|dir| dir.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    AdapterError::InvalidPath {
+        path: start.display().to_string(),
+        reason: format!("ambiguous project: {}; candidates: {}", reason, candidates),
+        source: anyhow::anyhow!("Ambiguous project root"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    
+    #[test]
+    fn test_discover_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        write(&root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n");
+        write(&root.join("Cargo.lock"), "version = 3\n");
+        write(&root.join("crates/core/Cargo.toml"), "[package]\nname = \"core\"\nversion = \"0.1.0\"\n");
+        std::fs::create_dir_all(root.join("crates/core/src")).unwrap();
+        
+        // A member's source directory resolves to the workspace root
+        assert_eq!(discover_project_root(&root.join("crates/core/src")).unwrap(), root);
+        assert_eq!(discover_project_root(&root).unwrap(), root);
+        
+        // A standalone package with its own lockfile is its own root
+        write(&root.join("tools/gen/Cargo.toml"), "[package]\nname = \"gen\"\nversion = \"0.1.0\"\n");
+        write(&root.join("tools/gen/Cargo.lock"), "version = 3\n");
+        assert_eq!(discover_project_root(&root.join("tools/gen")).unwrap(), root.join("tools/gen"));
+        
+        // A package without a lockfile that no workspace includes is ambiguous
+        write(&root.join("scratch/Cargo.toml"), "[package]\nname = \"scratch\"\nversion = \"0.1.0\"\n");
+        let error = discover_project_root(&root.join("scratch")).unwrap_err();
+        assert!(matches!(&error, AdapterError::InvalidPath { reason, .. } if reason.contains(&root.display().to_string())));
+    }
+    
+    #[test]
+    fn test_discover_lockfiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("Cargo.lock"), "version = 3\n");
+        write(&root.join("tools/gen/Cargo.lock"), "version = 3\n");
+        write(&root.join("examples/Cargo.lock"), "version = 3\n");
+        write(&root.join("target/package/app-0.1.0/Cargo.lock"), "version = 3\n");
+        write(&root.join(".git/Cargo.lock"), "version = 3\n");
+        write(&root.join("vendor/foo/Cargo.lock"), "version = 3\n");
+        write(&root.join("vendor/foo/.cargo-checksum.json"), "{}");
+        
+        assert_eq!(discover_lockfiles(root), vec![
+            PathBuf::from("Cargo.lock"),
+            PathBuf::from("examples/Cargo.lock"),
+            PathBuf::from("tools/gen/Cargo.lock"),
+        ]);
+    }
+}
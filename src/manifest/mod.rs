@@ -0,0 +1,22 @@
+//! Cargo.toml manifest module for Rust Ecosystem Adapter
+//!
+//! This module parses Cargo.toml manifests of a project and its workspace
+//! members. Manifests describe declared intent only; Cargo.lock remains
+//! the authoritative source of resolved dependency state.
+
+pub mod cargo_config;
+pub mod cargo_manifest;
+pub mod discovery;
+pub mod vcs;
+
+// Re-export manifest types
+pub use cargo_config::{
+    CargoSourceConfig, RegistryDefinition, SourceDefinition, SourceReplacementMismatch,
+    CARGO_CONFIG_PROPERTY,
+};
+pub use cargo_manifest::{
+    CargoManifest, ManifestDependency, ManifestDependencySource, ManifestPackage,
+    ManifestPatch, ManifestReplacement, ManifestWorkspace, WorkspaceManifest,
+};
+pub use discovery::{discover_lockfiles, discover_project_root};
+pub use vcs::{LockfileVcsState, VCS_PROPERTY};
@@ -0,0 +1,158 @@
+//! Version control state of Cargo.lock
+//!
+//! An SBOM or audit report is only reproducible if the Cargo.lock it was
+//! produced from can be found again. This module asks git whether the
+//! lockfile is committed, whether the working tree copy differs from the
+//! HEAD commit, and which commit HEAD is, so artifacts can name the exact
+//! source state they describe.
+
+use crate::error::{AdapterError, Result};
+use crate::utils::resolve_program;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Graph metadata property the VCS state of Cargo.lock is recorded in
+pub const VCS_PROPERTY: &str = "vcs";
+
+/// Git state of a project's Cargo.lock
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LockfileVcsState {
+    /// SHA of the HEAD commit
+    pub commit: String,
+    /// Lockfile path relative to the repository root
+    pub lockfile: PathBuf,
+    /// Whether the lockfile is part of the HEAD commit
+    pub committed: bool,
+    /// Whether the index holds changes to the lockfile
+    pub staged: bool,
+    /// Whether the working tree copy has unstaged changes
+    pub modified: bool,
+    /// Whether the lockfile is not tracked at all
+    pub untracked: bool,
+    /// Whether any tracked file of the working tree differs from HEAD
+    pub dirty: bool,
+}
+
+impl LockfileVcsState {
+    /// Read the git state of the lockfile at `lockfile_path`
+    ///
+    /// Fails if git is not installed or the lockfile is not inside a git
+    /// working tree.
+    pub fn load(lockfile_path: &Path) -> Result<Self> {
+        let dir = lockfile_path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let name = lockfile_path.file_name().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("Cargo.lock"));
+        
+        let commit = git(dir, &["rev-parse", "--verify", "HEAD"])?.trim().to_string();
+        let prefix = git(dir, &["rev-parse", "--show-prefix"])?.trim().to_string();
+        let name_arg = name.to_string_lossy().to_string();
+        
+        let listed = git(dir, &["ls-tree", "--name-only", "HEAD", "--", &name_arg])?;
+        let status = git(dir, &["status", "--porcelain", "--untracked-files=all", "--", &name_arg])?;
+        let worktree = git(dir, &["status", "--porcelain", "--untracked-files=no"])?;
+        
+        let mut state = Self {
+            commit,
+            lockfile: Path::new(&prefix).join(&name),
+            committed: !listed.trim().is_empty(),
+            dirty: !worktree.trim().is_empty(),
+            ..Self::default()
+        };
+        for line in status.lines() {
+            let mut codes = line.chars();
+            let (index, worktree) = (codes.next().unwrap_or(' '), codes.next().unwrap_or(' '));
+            if index == '?' {
+                state.untracked = true;
+                continue;
+            }
+            state.staged |= index != ' ';
+            state.modified |= worktree != ' ';
+        }
+        Ok(state)
+    }
+    
+    /// Whether the lockfile analyzed is exactly the one in the HEAD commit
+    pub fn matches_head(&self) -> bool {
+        self.committed && !self.staged && !self.modified && !self.untracked
+    }
+    
+    /// Problems that keep the lockfile from being traced to the HEAD commit
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.untracked || !self.committed {
+            problems.push(format!("{} is not committed", self.lockfile.display()));
+        }
+        if self.staged {
+            problems.push(format!("{} has staged changes", self.lockfile.display()));
+        }
+        if self.modified {
+            problems.push(format!("{} has unstaged modifications", self.lockfile.display()));
+        }
+        problems
+    }
+}
+
+/// Run git in `dir` and return its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new(resolve_program("git"))
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| AdapterError::ToolNotFound {
+            tool: "git".to_string(),
+            source: anyhow::anyhow!("{}", e),
+        })?;
+    if !output.status.success() {
+        return Err(AdapterError::ToolExecutionFailed {
+            tool: format!("git {}", args.join(" ")),
+            exit_code: output.status.code().unwrap_or(-1),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            source: anyhow::anyhow!("Command exited with non-zero status"),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_lockfile_vcs_state() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let run = |args: &[&str]| git(root, args);
+        if run(&["init", "--quiet"]).is_err() {
+            return; // git is not available
+        }
+        run(&["config", "user.email", "test@example.com"]).unwrap();
+        run(&["config", "user.name", "Test"]).unwrap();
+        
+        let lockfile = root.join("Cargo.lock");
+        std::fs::write(&lockfile, "version = 3\n").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        
+        // Never committed
+        assert!(LockfileVcsState::load(&lockfile).is_err());
+        run(&["add", "Cargo.toml"]).unwrap();
+        run(&["commit", "--quiet", "-m", "manifest"]).unwrap();
+        let state = LockfileVcsState::load(&lockfile).unwrap();
+        assert!(state.untracked && !state.committed && !state.matches_head());
+        
+        // Committed and clean
+        run(&["add", "Cargo.lock"]).unwrap();
+        run(&["commit", "--quiet", "-m", "lockfile"]).unwrap();
+        let state = LockfileVcsState::load(&lockfile).unwrap();
+        assert!(state.matches_head() && !state.dirty);
+        assert_eq!(state.lockfile, PathBuf::from("Cargo.lock"));
+        assert_eq!(state.commit, run(&["rev-parse", "HEAD"]).unwrap().trim());
+        
+        // Modified in the working tree
+        std::fs::write(&lockfile, "version = 4\n").unwrap();
+        let state = LockfileVcsState::load(&lockfile).unwrap();
+        assert!(state.modified && !state.staged && state.dirty && !state.matches_head());
+        assert_eq!(state.problems().len(), 1);
+    }
+}
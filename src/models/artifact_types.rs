@@ -0,0 +1,70 @@
+//! Artifact manifest types
+//!
+//! This module defines the run manifest listing the artifacts (SBOMs,
+//! reports, exports) a run emitted with their sizes and digests, so they
+//! can be verified after transfer into an air-gapped environment.
+
+use crate::utils::DigestConfig;
+use serde::{Deserialize, Serialize};
+
+/// Artifacts emitted by one or more runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunManifest {
+    /// Last update timestamp
+    pub generated_at: String,
+    /// Version of the adapter that wrote the manifest
+    pub tool_version: String,
+    /// Artifacts, sorted by path
+    pub artifacts: Vec<ArtifactEntry>,
+}
+
+/// An emitted artifact
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArtifactEntry {
+    /// Path, relative to the manifest's directory when the artifact is below it
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Digest of the contents
+    #[serde(alias = "sha256")]
+    pub checksum: String,
+    /// Algorithm and encoding of `checksum`; SHA-256 in hex for older manifests
+    #[serde(default)]
+    pub digest: DigestConfig,
+}
+
+/// Outcome of verifying one artifact against a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArtifactVerification {
+    /// Artifact path as listed in the manifest
+    pub path: String,
+    /// Whether size and digest match
+    pub passed: bool,
+    /// Why verification failed
+    pub reason: Option<String>,
+}
+
+impl RunManifest {
+    /// Create an empty manifest
+    pub fn new() -> Self {
+        Self {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            artifacts: Vec::new(),
+        }
+    }
+    
+    /// Add an artifact, replacing an earlier entry for the same path
+    pub fn record(&mut self, entry: ArtifactEntry) {
+        self.artifacts.retain(|artifact| artifact.path != entry.path);
+        self.artifacts.push(entry);
+        self.artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        self.generated_at = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+impl Default for RunManifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
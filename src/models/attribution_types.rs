@@ -0,0 +1,126 @@
+//! Third-party attribution types
+//!
+//! This module defines the attribution bundle built from the license and
+//! notice files of vendored crates: the JSON index of which package ships
+//! which text, and the deduplicated texts rendered into the
+//! THIRD-PARTY-NOTICES file.
+
+use serde::{Deserialize, Serialize};
+
+/// Attribution bundle for the packages of an SBOM
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributionBundle {
+    /// Bundle generation timestamp
+    pub generated_at: String,
+    /// Epoch whose vendor snapshot was collected, if any
+    pub epoch_id: Option<String>,
+    /// Canonical Cargo.lock fingerprint the bundle describes
+    pub lockfile_fingerprint: String,
+    /// Attributed packages, sorted by name and version
+    pub packages: Vec<AttributedPackage>,
+    /// Distinct license and notice texts, in order of first use
+    pub texts: Vec<AttributionText>,
+    /// SBOM packages without a vendored copy (`name@version`)
+    pub not_vendored: Vec<String>,
+    /// Aggregate statistics
+    pub statistics: AttributionStatistics,
+}
+
+/// A package and the license files it ships
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributedPackage {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// License expression declared in the vendored `Cargo.toml`
+    pub license: Option<String>,
+    /// License and notice files found at the crate root
+    pub files: Vec<AttributionFile>,
+}
+
+/// A license or notice file of a package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributionFile {
+    /// Path relative to the vendor directory
+    pub path: String,
+    /// Kind of file
+    pub kind: AttributionKind,
+    /// Identifier of the text in `AttributionBundle::texts`
+    pub text_id: String,
+}
+
+/// Kind of attribution file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributionKind {
+    /// License terms (LICENSE, COPYING, UNLICENSE)
+    License,
+    /// Attribution notice (NOTICE, COPYRIGHT)
+    Notice,
+}
+
+/// A distinct license or notice text
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributionText {
+    /// SHA-256 of the normalized text
+    pub id: String,
+    /// Kind of the files carrying the text
+    pub kind: AttributionKind,
+    /// Packages carrying the text (`name@version`)
+    pub packages: Vec<String>,
+    /// Normalized text; rendered into the notices file, not the JSON index
+    #[serde(skip)]
+    pub content: String,
+}
+
+/// Aggregate statistics of an attribution bundle
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AttributionStatistics {
+    /// Number of attributed packages
+    pub packages: usize,
+    /// Number of license and notice files collected
+    pub files: usize,
+    /// Number of distinct texts after deduplication
+    pub distinct_texts: usize,
+    /// Packages vendored without any license file (`name@version`)
+    pub packages_without_license_file: Vec<String>,
+}
+
+impl std::fmt::Display for AttributionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributionKind::License => write!(f, "license"),
+            AttributionKind::Notice => write!(f, "notice"),
+        }
+    }
+}
+
+impl AttributionBundle {
+    /// Render the THIRD-PARTY-NOTICES text
+    ///
+    /// Lists every package with its declared license, then each distinct
+    /// text once, headed by the packages it applies to.
+    pub fn render_notices(&self) -> String {
+        let rule = "=".repeat(78);
+        let mut out = String::new();
+        out.push_str("THIRD-PARTY SOFTWARE NOTICES\n\n");
+        out.push_str("This software includes the following third-party packages.\n\n");
+        for package in &self.packages {
+            out.push_str(&format!("  {} {} ({})\n", package.name, package.version,
+                package.license.as_deref().unwrap_or("license not declared")));
+        }
+        
+        for (index, text) in self.texts.iter().enumerate() {
+            out.push_str(&format!("\n{}\n[{}] {} text used by:\n", rule, index + 1, text.kind));
+            for package in &text.packages {
+                out.push_str(&format!("  {}\n", package));
+            }
+            out.push_str(&format!("{}\n\n", rule));
+            out.push_str(text.content.trim_end());
+            out.push('\n');
+        }
+        
+        out
+    }
+}
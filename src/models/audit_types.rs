@@ -3,9 +3,12 @@
 //! This module defines types for security audit operations,
 //! including results from cargo-audit and cargo-vet tools.
 
+use semver::{Comparator, Op, Prerelease, Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use super::dependency_graph::*;
+use super::ordering::sorted_map;
+use super::project_types::AnalysisWarning;
 
 /// Comprehensive audit report from security tools
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -14,12 +17,28 @@ pub struct AuditReport {
     pub raw_cargo_audit: Option<String>,
     /// Raw output from cargo-vet (if available)
     pub raw_cargo_vet: Option<String>,
+    /// Contents of `audits.toml` from an ingested cargo-vet store
+    ///
+    /// Set instead of `raw_cargo_vet` by offline ingestion, which has no
+    /// `cargo vet dump` output to record.
+    #[serde(default)]
+    pub raw_cargo_vet_audits: Option<String>,
+    /// Contents of `imports.lock` from an ingested cargo-vet store
+    #[serde(default)]
+    pub raw_cargo_vet_imports: Option<String>,
+    /// Contents of `config.toml` from an ingested cargo-vet store, which
+    /// holds its exemptions
+    #[serde(default)]
+    pub raw_cargo_vet_config: Option<String>,
     /// Execution metadata
     pub execution_metadata: AuditExecutionMetadata,
     /// Whether audit was run in offline mode
     pub offline_mode: bool,
     /// Processed audit findings
     pub findings: Vec<AuditFinding>,
+    /// Smallest upgrades clearing the applicable findings of each package
+    #[serde(default)]
+    pub upgrade_suggestions: Vec<UpgradeSuggestion>,
 }
 
 /// Audit execution metadata
@@ -28,13 +47,20 @@ pub struct AuditExecutionMetadata {
     /// Audit timestamp
     pub timestamp: String,
     /// Tool versions used
+    #[serde(serialize_with = "sorted_map")]
     pub tool_versions: HashMap<String, String>,
     /// Execution duration
     pub execution_duration: u64, // in milliseconds
     /// Exit codes from tools
+    #[serde(serialize_with = "sorted_map")]
     pub exit_codes: HashMap<String, i32>,
     /// Whether offline mode was used
     pub offline_mode: bool,
+    /// Structured warnings raised during execution
+    pub warnings: Vec<AnalysisWarning>,
+    /// Canonical Cargo.lock fingerprint the audit ran against
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
 }
 
 /// Individual audit finding
@@ -44,10 +70,22 @@ pub struct AuditFinding {
     pub id: String,
     /// Package name affected
     pub package_name: String,
-    /// Affected versions
+    /// Locked version the finding was reported for, if known
+    #[serde(default)]
+    pub package_version: Option<String>,
+    /// Affected versions (semver requirements, alternatives separated by `||`)
     pub affected_versions: String,
-    /// Patched versions
+    /// Patched versions (one semver requirement each)
     pub patched_versions: Vec<String>,
+    /// Versions never affected (one semver requirement each)
+    #[serde(default)]
+    pub unaffected_versions: Vec<String>,
+    /// Whether the advisory covers the locked version(s)
+    #[serde(default)]
+    pub applicability: AdvisoryApplicability,
+    /// Smallest patched version that clears the advisory for every affected locked version
+    #[serde(default)]
+    pub minimal_safe_version: Option<String>,
     /// Severity level
     pub severity: Severity,
     /// CVSS score (if available)
@@ -60,6 +98,95 @@ pub struct AuditFinding {
     pub source: String,
     /// Whether this affects TCS components
     pub affects_tcs: bool,
+    /// Dependency paths from the workspace to the affected package ("name@version" each)
+    #[serde(default)]
+    pub dependency_paths: Vec<Vec<String>>,
+    /// Other identifiers of the same advisory (CVE, GHSA, ...)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Every source that reported the advisory, once merged
+    #[serde(default)]
+    pub reported_by: Vec<String>,
+}
+
+/// Smallest upgrade clearing every applicable advisory of a locked package
+///
+/// Suggestions are facts for a reviewer; nothing is changed automatically.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpgradeSuggestion {
+    /// Package name
+    pub package_name: String,
+    /// Locked version
+    pub current_version: String,
+    /// Smallest version no applicable advisory affects, if a patched release clears them all
+    pub suggested_version: Option<String>,
+    /// Advisories affecting the locked version
+    pub advisories: Vec<String>,
+    /// Manifest requirements the locked version satisfies, checked against the suggested version
+    pub requirements: Vec<RequirementCheck>,
+}
+
+/// Manifest requirement checked against a suggested version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementCheck {
+    /// Workspace package declaring the requirement
+    pub declared_by: String,
+    /// Declared version requirement
+    pub requirement: String,
+    /// Whether the suggested version satisfies the requirement
+    pub satisfied: bool,
+}
+
+impl UpgradeSuggestion {
+    /// Whether the upgrade needs no manifest change (`cargo update` suffices)
+    ///
+    /// Transitive dependencies without a workspace requirement qualify
+    /// when a suggested version exists.
+    pub fn within_requirements(&self) -> bool {
+        self.suggested_version.is_some() && self.requirements.iter().all(|check| check.satisfied)
+    }
+}
+
+/// Changes remediating one finding, ready to be opened as a pull request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RemediationPayload {
+    /// Finding ID (or alias) the remediation was requested for
+    pub finding_id: String,
+    /// Upgrades applied, one per affected locked version
+    pub upgrades: Vec<UpgradeSuggestion>,
+    /// Manifest requirements rewritten to admit the upgrades
+    pub requirement_updates: Vec<RequirementUpdate>,
+    /// Changed files, relative to the project root
+    pub changed_files: Vec<String>,
+    /// Unified diff of the changed files
+    pub patch: String,
+}
+
+/// Version requirement rewritten in a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementUpdate {
+    /// Manifest path, relative to the project root
+    pub manifest: String,
+    /// Dependency package name
+    pub package_name: String,
+    /// Requirement before the change
+    pub previous: String,
+    /// Requirement after the change
+    pub updated: String,
+}
+
+/// Whether an advisory applies to the versions actually locked
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvisoryApplicability {
+    /// A locked version is in the affected range and neither patched nor
+    /// unaffected, or could not be ruled out
+    Applicable,
+    /// Every locked version is outside the affected range, patched or unaffected
+    NotApplicable,
+    /// Applicability has not been determined
+    #[default]
+    Unknown,
 }
 
 /// Severity levels for security findings
@@ -90,10 +217,16 @@ pub struct AuditProof {
     pub signature: Option<String>,
     /// Audit criteria
     pub criteria: Option<String>,
+    /// Criteria the package meets through this audit, including implied ones
+    #[serde(default)]
+    pub criteria_met: Vec<String>,
     /// Additional notes
     pub notes: Option<String>,
 }
 
+/// Built-in cargo-vet criteria and the criteria they imply
+const IMPLIED_CRITERIA: &[(&str, &str)] = &[("safe-to-deploy", "safe-to-run")];
+
 /// Audit record that can be shared across projects
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AuditRecord {
@@ -126,10 +259,16 @@ pub struct SupplyChainReport {
     pub status: SupplyChainStatus,
     /// Audit findings
     pub audit_findings: Vec<AuditFinding>,
-    /// Audit proofs
+    /// Audit proofs of TCS components, keyed by `name@version`
     pub audit_proofs: HashMap<String, AuditProof>,
     /// Unaudited TCS components
     pub unaudited_tcs: Vec<String>,
+    /// Percentage of TCS components with an audit proof
+    #[serde(default)]
+    pub tcs_audit_coverage: f64,
+    /// Provenance of release binaries built with cargo-auditable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binary_provenance: Vec<BinaryProvenance>,
     /// Report generation timestamp
     pub generated_at: String,
     /// Report metadata
@@ -151,6 +290,34 @@ pub enum SupplyChainStatus {
     Unknown,
 }
 
+/// Audit of a binary built with cargo-auditable
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BinaryAuditReport {
+    /// Advisories affecting the embedded dependencies
+    pub findings: Vec<AuditFinding>,
+    /// Embedded dependencies compared with the expected ones
+    pub provenance: BinaryProvenance,
+    /// Raw output from `cargo audit bin`
+    pub raw_cargo_audit: Option<String>,
+}
+
+/// Dependency list embedded in a binary compared with an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BinaryProvenance {
+    /// Verified binary
+    pub binary: String,
+    /// Epoch the embedded dependencies were compared with, `None` for Cargo.lock
+    pub epoch_id: Option<String>,
+    /// Number of non-local packages embedded in the binary
+    pub embedded_packages: usize,
+    /// Embedded packages (`name@version`) the epoch does not pin
+    pub unexpected_packages: Vec<String>,
+    /// Embedded packages (`name@version`) pinned from a different kind of source
+    pub source_mismatches: Vec<String>,
+    /// Whether the binary was built from the expected dependency set
+    pub verified: bool,
+}
+
 /// Cargo-audit advisory structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CargoAuditAdvisory {
@@ -247,15 +414,25 @@ pub struct CargoVetCriteria {
     pub safe_to_deploy: bool,
 }
 
+impl Default for AuditReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AuditReport {
     /// Create new empty audit report
     pub fn new() -> Self {
         Self {
             raw_cargo_audit: None,
             raw_cargo_vet: None,
+            raw_cargo_vet_audits: None,
+            raw_cargo_vet_imports: None,
+            raw_cargo_vet_config: None,
             execution_metadata: AuditExecutionMetadata::default(),
             offline_mode: false,
             findings: Vec::new(),
+            upgrade_suggestions: Vec::new(),
         }
     }
     
@@ -264,6 +441,54 @@ impl AuditReport {
         self.findings.push(finding);
     }
     
+    /// Sort findings by package name, locked version and advisory ID
+    ///
+    /// Upgrade suggestions are sorted by package name and version.
+    pub fn sort_canonical(&mut self) {
+        self.findings.sort_by(|a, b| {
+            (&a.package_name, &a.package_version, &a.id).cmp(&(&b.package_name, &b.package_version, &b.id))
+        });
+        self.upgrade_suggestions.sort_by(|a, b| {
+            (&a.package_name, &a.current_version).cmp(&(&b.package_name, &b.current_version))
+        });
+    }
+    
+    /// Merge findings that report the same advisory for the same package
+    ///
+    /// Findings are grouped by package and overlapping advisory IDs or
+    /// aliases, so one finding can join two groups that then become one.
+    /// The first finding of a group keeps its position. Returns the number
+    /// of findings merged away.
+    pub fn deduplicate_findings(&mut self) -> usize {
+        let before = self.findings.len();
+        let mut merged: Vec<AuditFinding> = Vec::new();
+        
+        for finding in std::mem::take(&mut self.findings) {
+            let mut matching = merged.iter()
+                .enumerate()
+                .filter(|(_, existing)| existing.same_advisory(&finding))
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>()
+                .into_iter();
+            
+            match matching.next() {
+                Some(first) => {
+                    merged[first].merge(finding);
+                    // Groups bridged by this finding, removed back to front to keep indices valid
+                    let bridged: Vec<usize> = matching.collect();
+                    for index in bridged.into_iter().rev() {
+                        let other = merged.remove(index);
+                        merged[first].merge(other);
+                    }
+                },
+                None => merged.push(finding),
+            }
+        }
+        
+        self.findings = merged;
+        before - self.findings.len()
+    }
+    
     /// Get findings by severity
     pub fn findings_by_severity(&self, severity: Severity) -> Vec<&AuditFinding> {
         self.findings.iter()
@@ -318,6 +543,8 @@ impl Default for AuditExecutionMetadata {
             execution_duration: 0,
             exit_codes: HashMap::new(),
             offline_mode: false,
+            warnings: Vec::new(),
+            lockfile_fingerprint: String::new(),
         }
     }
 }
@@ -334,15 +561,233 @@ impl AuditFinding {
         Self {
             id,
             package_name,
+            package_version: None,
             affected_versions,
             patched_versions: Vec::new(),
+            unaffected_versions: Vec::new(),
+            applicability: AdvisoryApplicability::Unknown,
+            minimal_safe_version: None,
             severity,
             cvss_score: None,
             description,
             references: Vec::new(),
             source: "unknown".to_string(),
             affects_tcs: false,
+            dependency_paths: Vec::new(),
+            aliases: Vec::new(),
+            reported_by: Vec::new(),
+        }
+    }
+    
+    /// Add an alias identifier of the advisory
+    pub fn add_alias(mut self, alias: String) -> Self {
+        if alias != self.id && !self.aliases.contains(&alias) {
+            self.aliases.push(alias);
         }
+        self
+    }
+    
+    /// Whether two findings report the same advisory for the same package
+    ///
+    /// Findings match when their IDs or aliases overlap.
+    pub fn same_advisory(&self, other: &AuditFinding) -> bool {
+        let identifiers = |finding: &AuditFinding| -> Vec<String> {
+            std::iter::once(&finding.id).chain(&finding.aliases).map(|id| id.to_ascii_uppercase()).collect()
+        };
+        let theirs = identifiers(other);
+        self.package_name == other.package_name
+            && identifiers(self).iter().any(|id| theirs.contains(id))
+    }
+    
+    /// Fold another report of the same advisory into this one
+    ///
+    /// Keeps the highest severity and CVSS score, the first parseable
+    /// affected range and the union of aliases, references, patched
+    /// versions, paths and reporting sources.
+    pub fn merge(&mut self, other: AuditFinding) {
+        fn union(into: &mut Vec<String>, from: Vec<String>) {
+            for item in from {
+                if !into.contains(&item) {
+                    into.push(item);
+                }
+            }
+        }
+        
+        let own_sources = self.sources();
+        let other_sources = other.sources();
+        self.reported_by = own_sources;
+        union(&mut self.reported_by, other_sources);
+        self.reported_by.sort();
+        
+        if other.severity < self.severity {
+            self.severity = other.severity;
+        }
+        self.cvss_score = match (self.cvss_score, other.cvss_score) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        if self.description.is_empty() {
+            self.description = other.description;
+        }
+        if self.package_version.is_none() {
+            self.package_version = other.package_version;
+        }
+        if self.affected_requirements().is_none() {
+            self.affected_versions = other.affected_versions;
+        }
+        self.affects_tcs |= other.affects_tcs;
+        
+        union(&mut self.aliases, std::iter::once(other.id).chain(other.aliases).collect());
+        let id = self.id.clone();
+        self.aliases.retain(|alias| *alias != id);
+        union(&mut self.references, other.references);
+        union(&mut self.patched_versions, other.patched_versions);
+        union(&mut self.unaffected_versions, other.unaffected_versions);
+        for path in other.dependency_paths {
+            if !self.dependency_paths.contains(&path) {
+                self.dependency_paths.push(path);
+            }
+        }
+    }
+    
+    /// Whether the finding may apply to the locked version(s)
+    ///
+    /// Only findings known not to apply are excluded.
+    pub fn is_applicable(&self) -> bool {
+        self.applicability != AdvisoryApplicability::NotApplicable
+    }
+    
+    /// Affected version requirements, `None` when the range cannot be parsed
+    pub fn affected_requirements(&self) -> Option<Vec<VersionReq>> {
+        Self::parse_requirements(&self.affected_versions)
+    }
+    
+    /// Patched version requirements, skipping any that cannot be parsed
+    pub fn patched_requirements(&self) -> Vec<VersionReq> {
+        self.patched_versions.iter()
+            .filter_map(|patched| Self::parse_requirements(patched))
+            .flatten()
+            .collect()
+    }
+    
+    /// Unaffected version requirements, skipping any that cannot be parsed
+    pub fn unaffected_requirements(&self) -> Vec<VersionReq> {
+        self.unaffected_versions.iter()
+            .filter_map(|unaffected| Self::parse_requirements(unaffected))
+            .flatten()
+            .collect()
+    }
+    
+    /// Whether the advisory applies to `version`
+    ///
+    /// A version is affected when it matches the affected range and no
+    /// patched or unaffected requirement. Pre-releases are compared by
+    /// semver precedence, so `1.0.0-beta.3` is affected by `<1.0.0`.
+    /// Whatever cannot be ruled out, because the version or the affected
+    /// range cannot be parsed, is affected.
+    pub fn applies_to(&self, version: &str) -> AdvisoryApplicability {
+        let Ok(version) = Version::parse(version) else {
+            return AdvisoryApplicability::Applicable;
+        };
+        let cleared = self.patched_requirements().into_iter()
+            .chain(self.unaffected_requirements())
+            .any(|req| Self::requirement_matches(&req, &version));
+        if cleared {
+            return AdvisoryApplicability::NotApplicable;
+        }
+        
+        match self.affected_requirements() {
+            Some(affected) if !affected.iter().any(|req| Self::requirement_matches(req, &version)) => AdvisoryApplicability::NotApplicable,
+            _ => AdvisoryApplicability::Applicable,
+        }
+    }
+    
+    /// Whether `version` satisfies `req`, pre-releases included
+    ///
+    /// `VersionReq::matches` only lets a pre-release satisfy a requirement
+    /// naming a pre-release of the same release. A comparator admitting
+    /// every pre-release of the version's own release lifts that rule
+    /// without widening the range, so the other comparators decide.
+    fn requirement_matches(req: &VersionReq, version: &Version) -> bool {
+        if version.pre.is_empty() {
+            return req.matches(version);
+        }
+        let mut req = req.clone();
+        req.comparators.push(Comparator {
+            op: Op::GreaterEq,
+            major: version.major,
+            minor: Some(version.minor),
+            patch: Some(version.patch),
+            pre: Prerelease::new("0").unwrap_or_default(),
+        });
+        req.matches(version)
+    }
+    
+    /// Smallest patched version above `version`
+    ///
+    /// Candidates are the lower bounds of the patched requirements, so a
+    /// `^0.16.20, >=0.17.12` advisory suggests 0.16.20 for 0.16.5.
+    pub fn minimal_patched_version(&self, version: &str) -> Option<Version> {
+        let version = Version::parse(version).ok()?;
+        self.patched_lower_bounds().into_iter()
+            .filter(|candidate| *candidate > version)
+            .min()
+    }
+    
+    /// Lowest version admitted by each patched requirement
+    pub fn patched_lower_bounds(&self) -> Vec<Version> {
+        self.patched_requirements().iter()
+            .map(|req| (req, Self::lower_bound(req)))
+            .filter(|(req, candidate)| req.matches(candidate))
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+    
+    /// Parse `||`-separated semver requirements
+    ///
+    /// Returns `None` when the string is empty or any alternative fails
+    /// to parse, as for "unknown".
+    fn parse_requirements(requirements: &str) -> Option<Vec<VersionReq>> {
+        let parsed: Option<Vec<VersionReq>> = requirements.split("||")
+            .map(|req| VersionReq::parse(req.trim()).ok())
+            .collect();
+        parsed.filter(|reqs| !reqs.is_empty() && !requirements.trim().is_empty())
+    }
+    
+    /// Lowest version a requirement can match, ignoring upper bounds
+    fn lower_bound(req: &VersionReq) -> Version {
+        req.comparators.iter()
+            .map(|comparator| {
+                let (major, minor, patch) = (comparator.major, comparator.minor.unwrap_or(0), comparator.patch.unwrap_or(0));
+                let mut bound = match (&comparator.op, comparator.minor, comparator.patch) {
+                    (Op::Less | Op::LessEq, _, _) => Version::new(0, 0, 0),
+                    (Op::Greater, _, Some(patch)) => Version::new(major, minor, patch + 1),
+                    (Op::Greater, Some(minor), None) => Version::new(major, minor + 1, 0),
+                    (Op::Greater, None, None) => Version::new(major + 1, 0, 0),
+                    _ => Version::new(major, minor, patch),
+                };
+                if matches!(comparator.op, Op::GreaterEq | Op::Exact | Op::Caret | Op::Tilde) {
+                    bound.pre = comparator.pre.clone();
+                }
+                bound
+            })
+            .max()
+            .unwrap_or_else(|| Version::new(0, 0, 0))
+    }
+    
+    /// Sources that reported the finding
+    pub fn sources(&self) -> Vec<String> {
+        if self.reported_by.is_empty() {
+            vec![self.source.clone()]
+        } else {
+            self.reported_by.clone()
+        }
+    }
+    
+    /// Set the locked version the finding was reported for
+    pub fn with_package_version(mut self, version: String) -> Self {
+        self.package_version = Some(version);
+        self
     }
     
     /// Set TCS impact
@@ -363,6 +808,12 @@ impl AuditFinding {
         self
     }
     
+    /// Add unaffected version
+    pub fn add_unaffected_version(mut self, version: String) -> Self {
+        self.unaffected_versions.push(version);
+        self
+    }
+    
     /// Set CVSS score
     pub fn with_cvss_score(mut self, score: f64) -> Self {
         self.cvss_score = Some(score);
@@ -398,6 +849,23 @@ impl Severity {
             Severity::Info => "gray",
         }
     }
+    
+    /// Lowercase severity name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::High => "high",
+            Severity::Medium => "medium",
+            Severity::Low => "low",
+            Severity::Info => "info",
+        }
+    }
+}
+
+impl Default for SupplyChainReport {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SupplyChainReport {
@@ -408,14 +876,21 @@ impl SupplyChainReport {
             audit_findings: Vec::new(),
             audit_proofs: HashMap::new(),
             unaudited_tcs: Vec::new(),
+            tcs_audit_coverage: 100.0,
+            binary_provenance: Vec::new(),
             generated_at: chrono::Utc::now().to_rfc3339(),
             metadata: HashMap::new(),
         }
     }
     
     /// Determine overall status based on findings
+    ///
+    /// A release binary built from dependencies the epoch does not pin is
+    /// critical, like a critical advisory.
     pub fn determine_status(&mut self) {
-        self.status = if self.audit_findings.iter().any(|f| f.severity == Severity::Critical) {
+        self.status = if self.audit_findings.iter().any(|f| f.severity == Severity::Critical)
+            || self.binary_provenance.iter().any(|provenance| !provenance.verified)
+        {
             SupplyChainStatus::Critical
         } else if !self.unaudited_tcs.is_empty() {
             SupplyChainStatus::Insufficient
@@ -426,14 +901,98 @@ impl SupplyChainReport {
         };
     }
     
-    /// Add audit proof
-    pub fn add_audit_proof(&mut self, package_id: String, proof: AuditProof) {
-        self.audit_proofs.insert(package_id, proof);
+    /// Add audit finding, unless the same advisory is already reported for the package
+    pub fn add_audit_finding(&mut self, finding: AuditFinding) {
+        let duplicate = self.audit_findings.iter().any(|f| {
+            f.id == finding.id && f.package_name == finding.package_name && f.package_version == finding.package_version
+        });
+        if !duplicate {
+            self.audit_findings.push(finding);
+        }
+    }
+    
+    /// Add audit proof of a TCS component
+    pub fn add_audit_proof(&mut self, package_name: &str, package_version: &str, proof: AuditProof) {
+        self.audit_proofs.insert(format!("{}@{}", package_name, package_version), proof);
+        self.update_coverage();
     }
     
     /// Add unaudited TCS component
     pub fn add_unaudited_tcs(&mut self, package_name: String) {
         self.unaudited_tcs.push(package_name);
+        self.update_coverage();
+    }
+    
+    /// Recompute the audit coverage of TCS components (100% without any)
+    fn update_coverage(&mut self) {
+        let total = self.audit_proofs.len() + self.unaudited_tcs.len();
+        self.tcs_audit_coverage = if total == 0 {
+            100.0
+        } else {
+            self.audit_proofs.len() as f64 * 100.0 / total as f64
+        };
+    }
+}
+
+/// Criteria met by a cargo-vet audit, including the criteria they imply
+///
+/// Audits certifying several criteria list them separated by commas.
+pub fn met_criteria(criteria: &str) -> Vec<String> {
+    let mut met: Vec<String> = criteria.split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+        .collect();
+    for (criterion, implied) in IMPLIED_CRITERIA {
+        if met.iter().any(|c| c == criterion) && !met.iter().any(|c| c == implied) {
+            met.push(implied.to_string());
+        }
+    }
+    met
+}
+
+impl AuditProof {
+    /// Proof of an audited package, linked to the criteria it meets
+    ///
+    /// Returns `None` unless the status is `Audited`. Cargo-vet audits
+    /// meet their criteria and the criteria these imply; other audit
+    /// methods are not tied to criteria.
+    pub fn from_status(status: &AuditStatus) -> Option<Self> {
+        let (method, auditor, date) = match status {
+            AuditStatus::Audited { method, auditor, date } => (method, auditor, date),
+            _ => return None,
+        };
+        
+        let criteria = match method {
+            AuditMethod::CargoVet { criteria } => Some(criteria.clone()),
+            _ => None,
+        };
+        let criteria_met = criteria.as_deref().map(met_criteria).unwrap_or_default();
+        
+        Some(Self {
+            method: method.clone(),
+            auditor: auditor.clone(),
+            date: date.clone(),
+            signature: None,
+            criteria,
+            criteria_met,
+            notes: None,
+        })
+    }
+    
+    /// Proof from a shared audit record
+    ///
+    /// The record's criteria count as met, with the criteria they imply.
+    pub fn from_record(record: &AuditRecord) -> Self {
+        Self {
+            method: record.method.clone(),
+            auditor: record.auditor.clone(),
+            date: record.audit_date.clone(),
+            signature: record.signature.clone(),
+            criteria: Some(record.criteria.clone()),
+            criteria_met: met_criteria(&record.criteria),
+            notes: record.notes.clone(),
+        }
     }
 }
 
@@ -478,4 +1037,32 @@ impl AuditRecord {
         self.source_project = Some(source_project);
         self
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_chain_report_links_proofs_to_criteria() {
+        let mut report = SupplyChainReport::new();
+        assert_eq!(report.tcs_audit_coverage, 100.0);
+        
+        let status = AuditStatus::Audited {
+            method: AuditMethod::CargoVet { criteria: "safe-to-deploy".to_string() },
+            auditor: "security@example.com".to_string(),
+            date: "2026-01-01".to_string(),
+        };
+        let proof = AuditProof::from_status(&status).unwrap();
+        assert_eq!(proof.criteria.as_deref(), Some("safe-to-deploy"));
+        assert_eq!(proof.criteria_met, vec!["safe-to-deploy", "safe-to-run"]);
+        assert!(AuditProof::from_status(&AuditStatus::Unaudited).is_none());
+        
+        report.add_audit_proof("ring", "0.17.8", proof);
+        report.add_unaudited_tcs("rustls".to_string());
+        report.add_unaudited_tcs("webpki".to_string());
+        report.add_unaudited_tcs("sha2".to_string());
+        assert!(report.audit_proofs.contains_key("ring@0.17.8"));
+        assert_eq!(report.tcs_audit_coverage, 25.0);
+    }
+}
@@ -0,0 +1,275 @@
+//! Report summary and badge types
+//!
+//! This module defines the severity summaries kept for the latest audit,
+//! drift and SBOM reports of a project, and the status badges derived
+//! from them.
+
+use serde::{Deserialize, Serialize};
+use super::audit_types::{AuditReport, Severity};
+use super::drift_types::{DriftReport, Priority};
+
+/// Report a summary and badge describe
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    /// Security audit
+    Audit,
+    /// Dependency drift
+    Drift,
+    /// Software bill of materials
+    Sbom,
+}
+
+/// Severity summary of the latest report of one kind
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReportSummary {
+    /// Report kind
+    pub kind: ReportKind,
+    /// Report generation timestamp
+    pub generated_at: String,
+    /// Canonical Cargo.lock fingerprint the report describes
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
+    /// Critical findings or drifts
+    pub critical: usize,
+    /// High severity findings or drifts
+    pub high: usize,
+    /// Medium severity findings or drifts
+    pub medium: usize,
+    /// Low severity findings or drifts
+    pub low: usize,
+    /// Total items (findings, drifts or SBOM components)
+    pub total: usize,
+}
+
+/// Status badge in the shields.io model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Badge {
+    /// Left-hand text
+    pub label: String,
+    /// Right-hand text
+    pub message: String,
+    /// Right-hand colour (shields.io colour name)
+    pub color: String,
+}
+
+impl std::fmt::Display for ReportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportKind::Audit => write!(f, "audit"),
+            ReportKind::Drift => write!(f, "drift"),
+            ReportKind::Sbom => write!(f, "sbom"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportKind {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "audit" => Ok(ReportKind::Audit),
+            "drift" => Ok(ReportKind::Drift),
+            "sbom" => Ok(ReportKind::Sbom),
+            _ => Err(format!("Unknown report kind '{}'. Valid kinds: audit, drift, sbom", s)),
+        }
+    }
+}
+
+impl ReportSummary {
+    /// Summarize an audit report by finding severity
+    ///
+    /// Informational findings and findings not applicable to the locked
+    /// versions count towards the total only.
+    pub fn from_audit(report: &AuditReport) -> Self {
+        let count = |severity: Severity| report.findings.iter()
+            .filter(|f| f.severity == severity && f.is_applicable())
+            .count();
+        
+        Self {
+            kind: ReportKind::Audit,
+            generated_at: report.execution_metadata.timestamp.clone(),
+            lockfile_fingerprint: report.execution_metadata.lockfile_fingerprint.clone(),
+            critical: count(Severity::Critical),
+            high: count(Severity::High),
+            medium: count(Severity::Medium),
+            low: count(Severity::Low),
+            total: report.findings.len(),
+        }
+    }
+    
+    /// Summarize a drift report by drift priority
+    pub fn from_drift(report: &DriftReport) -> Self {
+        let count = |priority: Priority| report.drifts.iter().filter(|d| d.priority == priority).count();
+        
+        Self {
+            kind: ReportKind::Drift,
+            generated_at: report.analysis_timestamp.clone(),
+            lockfile_fingerprint: report.lockfile_fingerprint.clone(),
+            critical: count(Priority::Critical),
+            high: count(Priority::High),
+            medium: count(Priority::Medium),
+            low: count(Priority::Low),
+            total: report.drifts.len(),
+        }
+    }
+    
+    /// Summarize an SBOM by its number of components
+    pub fn from_sbom(component_count: usize, lockfile_fingerprint: String) -> Self {
+        Self {
+            kind: ReportKind::Sbom,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            lockfile_fingerprint,
+            critical: 0,
+            high: 0,
+            medium: 0,
+            low: 0,
+            total: component_count,
+        }
+    }
+    
+    /// One-line summary, e.g. `0 critical / 2 high`
+    ///
+    /// Medium and low counts are included when non-zero.
+    pub fn summary_line(&self) -> String {
+        if self.kind == ReportKind::Sbom {
+            return format!("{} components", self.total);
+        }
+        
+        let mut parts = vec![format!("{} critical", self.critical), format!("{} high", self.high)];
+        if self.medium > 0 {
+            parts.push(format!("{} medium", self.medium));
+        }
+        if self.low > 0 {
+            parts.push(format!("{} low", self.low));
+        }
+        parts.join(" / ")
+    }
+    
+    /// Badge for this summary, coloured by the most severe count
+    pub fn badge(&self, label: Option<&str>) -> Badge {
+        let default_label = match self.kind {
+            ReportKind::Audit => "supply-chain",
+            ReportKind::Drift => "drift",
+            ReportKind::Sbom => "sbom",
+        };
+        let color = if self.kind == ReportKind::Sbom {
+            "blue"
+        } else if self.critical > 0 {
+            "red"
+        } else if self.high > 0 {
+            "orange"
+        } else if self.medium > 0 {
+            "yellow"
+        } else {
+            "brightgreen"
+        };
+        
+        Badge {
+            label: label.unwrap_or(default_label).to_string(),
+            message: self.summary_line(),
+            color: color.to_string(),
+        }
+    }
+}
+
+impl Badge {
+    /// shields.io endpoint JSON
+    pub fn to_endpoint_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "schemaVersion": 1,
+            "label": self.label,
+            "message": self.message,
+            "color": self.color,
+        })
+    }
+    
+    /// Flat-style SVG badge
+    ///
+    /// Text widths are estimated, so the layout approximates shields.io
+    /// rather than matching it exactly.
+    pub fn to_svg(&self) -> String {
+        let text_width = |text: &str| text.chars().count() * 7 + 10;
+        let label_width = text_width(&self.label);
+        let message_width = text_width(&self.message);
+        let width = label_width + message_width;
+        let label = xml_escape(&self.label);
+        let message = xml_escape(&self.message);
+        
+        format!(
+            concat!(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="20" role="img" aria-label="{l}: {m}">"##,
+                r##"<title>{l}: {m}</title>"##,
+                r##"<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>"##,
+                r##"<clipPath id="r"><rect width="{w}" height="20" rx="3" fill="#fff"/></clipPath>"##,
+                r##"<g clip-path="url(#r)"><rect width="{lw}" height="20" fill="#555"/><rect x="{lw}" width="{mw}" height="20" fill="{c}"/><rect width="{w}" height="20" fill="url(#s)"/></g>"##,
+                r##"<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##,
+                r##"<text x="{lx}" y="14">{l}</text><text x="{mx}" y="14">{m}</text></g></svg>"##,
+            ),
+            w = width,
+            lw = label_width,
+            mw = message_width,
+            lx = label_width / 2,
+            mx = label_width + message_width / 2,
+            c = color_hex(&self.color),
+            l = label,
+            m = message,
+        )
+    }
+}
+
+/// Hex value of a shields.io colour name
+fn color_hex(color: &str) -> &str {
+    match color {
+        "brightgreen" => "#4c1",
+        "yellow" => "#dfb317",
+        "orange" => "#fe7d37",
+        "red" => "#e05d44",
+        "blue" => "#007ec6",
+        other => other,
+    }
+}
+
+/// Escape text for use in SVG content and attributes
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_summary_badges() {
+        let mut summary = ReportSummary {
+            kind: ReportKind::Audit,
+            generated_at: "2024-01-01T00:00:00Z".to_string(),
+            lockfile_fingerprint: String::new(),
+            critical: 0,
+            high: 2,
+            medium: 0,
+            low: 1,
+            total: 3,
+        };
+        
+        let badge = summary.badge(None);
+        assert_eq!(badge.label, "supply-chain");
+        assert_eq!(badge.message, "0 critical / 2 high / 1 low");
+        assert_eq!(badge.color, "orange");
+        assert_eq!(badge.to_endpoint_json()["schemaVersion"], 1);
+        
+        summary.high = 0;
+        summary.low = 0;
+        assert_eq!(summary.badge(Some("deps & <vendors>")).color, "brightgreen");
+        let svg = summary.badge(Some("deps & <vendors>")).to_svg();
+        assert!(svg.contains("deps &amp; &lt;vendors&gt;: 0 critical / 0 high"));
+        assert!(svg.contains("fill=\"#4c1\""));
+        
+        let sbom = ReportSummary::from_sbom(152, String::new());
+        assert_eq!(sbom.badge(None).message, "152 components");
+        assert_eq!("SBOM".parse::<ReportKind>(), Ok(ReportKind::Sbom));
+    }
+}
@@ -0,0 +1,138 @@
+//! Build-time execution report types
+//!
+//! This module defines the report listing every crate that runs code
+//! during a build (proc-macros and build scripts) together with the
+//! capabilities their sources appear to use. Capabilities are detected
+//! by simple source heuristics and are meant to guide review during
+//! epoch approval, not to prove or rule out behavior.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Build-time execution report for a vendor directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BuildExecutionReport {
+    /// Report generation timestamp
+    pub generated_at: String,
+    /// Epoch whose vendor snapshot was scanned (if any)
+    pub epoch_id: Option<String>,
+    /// Canonical Cargo.lock fingerprint of the scanned sources
+    pub lockfile_fingerprint: String,
+    /// Vendor directory that was scanned
+    pub vendor_dir: PathBuf,
+    /// Crates executing code at build time, sorted by name and version
+    pub crates: Vec<BuildExecutionCrate>,
+    /// Report summary
+    pub summary: BuildExecutionSummary,
+}
+
+/// A crate executing code at build time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BuildExecutionCrate {
+    /// Crate name
+    pub name: String,
+    /// Crate version
+    pub version: String,
+    /// Directory of the crate, relative to the vendor directory
+    pub path: PathBuf,
+    /// Whether the crate is a procedural macro
+    pub proc_macro: bool,
+    /// Build script path, relative to the crate directory
+    pub build_script: Option<PathBuf>,
+    /// Native library linked through the `links` manifest key
+    pub links: Option<String>,
+    /// Capabilities the build-time sources appear to use
+    pub capabilities: Vec<BuildCapability>,
+    /// Source locations supporting each detected capability
+    pub evidence: Vec<CapabilityEvidence>,
+    /// Number of source files scanned
+    pub files_scanned: usize,
+    /// Build-time source files that could not be scanned
+    #[serde(default)]
+    pub not_analyzed: Vec<UnanalyzedFile>,
+}
+
+/// Build-time source file left out of the scan
+///
+/// Capabilities used only in such a file go undetected, so the crate
+/// needs a manual review of it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnanalyzedFile {
+    /// Source file, relative to the crate directory
+    pub file: PathBuf,
+    /// Why the file was not scanned
+    pub reason: String,
+}
+
+/// Capability used by build-time code
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuildCapability {
+    /// Filesystem access
+    Filesystem,
+    /// Network access
+    Network,
+    /// Spawning external processes
+    ProcessSpawn,
+}
+
+/// Source location where a capability was detected
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapabilityEvidence {
+    /// Detected capability
+    pub capability: BuildCapability,
+    /// Source file, relative to the crate directory
+    pub file: PathBuf,
+    /// Line number (1-based)
+    pub line: usize,
+    /// Pattern that matched
+    pub pattern: String,
+}
+
+/// Summary of a build-time execution report
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BuildExecutionSummary {
+    /// Number of vendored crates scanned
+    pub crates_scanned: usize,
+    /// Number of crates with a build script
+    pub build_scripts: usize,
+    /// Number of proc-macro crates
+    pub proc_macros: usize,
+    /// Number of crates appearing to access the filesystem
+    pub filesystem: usize,
+    /// Number of crates appearing to access the network
+    pub network: usize,
+    /// Number of crates appearing to spawn processes
+    pub process_spawn: usize,
+    /// Number of crates with build-time source files that could not be scanned
+    #[serde(default)]
+    pub not_fully_analyzed: usize,
+}
+
+impl std::fmt::Display for BuildCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildCapability::Filesystem => write!(f, "fs"),
+            BuildCapability::Network => write!(f, "net"),
+            BuildCapability::ProcessSpawn => write!(f, "process"),
+        }
+    }
+}
+
+impl BuildExecutionSummary {
+    /// Summarize the crates listed in a report
+    pub fn from_crates(crates_scanned: usize, crates: &[BuildExecutionCrate]) -> Self {
+        let with = |capability: BuildCapability| {
+            crates.iter().filter(|c| c.capabilities.contains(&capability)).count()
+        };
+        
+        Self {
+            crates_scanned,
+            build_scripts: crates.iter().filter(|c| c.build_script.is_some()).count(),
+            proc_macros: crates.iter().filter(|c| c.proc_macro).count(),
+            filesystem: with(BuildCapability::Filesystem),
+            network: with(BuildCapability::Network),
+            process_spawn: with(BuildCapability::ProcessSpawn),
+            not_fully_analyzed: crates.iter().filter(|c| !c.not_analyzed.is_empty()).count(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Air-gapped security data bundle types
+//!
+//! A bundle carries security data prepared on a connected host to sites
+//! without network access: a RustSec advisory database snapshot, a sparse
+//! index mirror, shared audit records and an OSV export. The bundle
+//! manifest lists every file with its digest and is signed with Ed25519,
+//! so a bundle altered in transit is rejected before anything is installed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Name of the manifest entry, always the first entry of a bundle
+pub const BUNDLE_MANIFEST_ENTRY: &str = "bundle-manifest.json";
+
+/// Name of the signature entry, always the second entry of a bundle
+///
+/// Holds the hex-encoded Ed25519 signature of the manifest entry's bytes.
+pub const BUNDLE_SIGNATURE_ENTRY: &str = "bundle-manifest.sig";
+
+/// Kind of security data a bundle carries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum BundleSection {
+    /// RustSec advisory database snapshot
+    AdvisoryDb,
+    /// Sparse crates.io index mirror
+    IndexMirror,
+    /// Shared audit records (JSON arrays of `AuditRecord`)
+    AuditRecords,
+}
+
+impl BundleSection {
+    /// Every section, in installation order
+    pub const ALL: [Self; 3] = [Self::AdvisoryDb, Self::IndexMirror, Self::AuditRecords];
+    
+    /// Top-level directory of the section in a bundle
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AdvisoryDb => "advisory-db",
+            Self::IndexMirror => "index-mirror",
+            Self::AuditRecords => "audit-records",
+        }
+    }
+    
+    /// Section a `/`-separated bundle path belongs to
+    pub fn of_path(path: &str) -> Option<Self> {
+        let (directory, rest) = path.split_once('/')?;
+        if rest.is_empty() {
+            return None;
+        }
+        Self::ALL.into_iter().find(|section| section.as_str() == directory)
+    }
+}
+
+/// Signed manifest of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleManifest {
+    /// Bundle format version
+    pub format_version: u32,
+    /// When the bundle was prepared (RFC 3339)
+    pub created_at: String,
+    /// Bundled files, sorted by path
+    pub files: Vec<BundleFile>,
+}
+
+impl BundleManifest {
+    /// Bundle format version written by this adapter
+    pub const FORMAT_VERSION: u32 = 1;
+    
+    /// Sections with at least one file
+    pub fn sections(&self) -> BTreeSet<BundleSection> {
+        self.files.iter().filter_map(|file| BundleSection::of_path(&file.path)).collect()
+    }
+}
+
+/// File recorded in a bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleFile {
+    /// Path in the bundle, '/'-separated and starting with the section directory
+    pub path: String,
+    /// File size in bytes
+    pub size_bytes: u64,
+    /// SHA256 of file contents
+    pub sha256: String,
+}
+
+/// Result of verifying or importing a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleImportReport {
+    /// Bundle file
+    pub bundle: PathBuf,
+    /// When the bundle was prepared
+    pub created_at: String,
+    /// Trusted public key (hex) the manifest is signed with
+    pub signing_key: String,
+    /// Whether the sections were installed, or only verified
+    pub installed: bool,
+    /// Sections of the bundle, in installation order
+    pub sections: Vec<BundleSectionSummary>,
+}
+
+/// Section of a verified or imported bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleSectionSummary {
+    /// Section
+    pub section: BundleSection,
+    /// Shared cache entry the section replaces
+    pub path: PathBuf,
+    /// Number of files
+    pub files: usize,
+    /// Total size in bytes
+    pub size_bytes: u64,
+}
@@ -78,10 +78,13 @@ pub struct CargoDependency {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum CargoDependencyKind {
     /// Normal runtime dependency
+    #[serde(alias = "normal")]
     Normal,
     /// Build-time dependency
+    #[serde(alias = "build")]
     Build,
     /// Development dependency
+    #[serde(alias = "dev")]
     Dev,
 }
 
@@ -125,6 +128,9 @@ pub struct CargoMetadataPackage {
     pub repository: Option<String>,
     /// Package homepage
     pub homepage: Option<String>,
+    /// Package authors
+    #[serde(default)]
+    pub authors: Vec<String>,
     /// Package dependencies
     pub dependencies: Vec<CargoMetadataDependency>,
     /// Package targets
@@ -172,11 +178,274 @@ pub struct CargoLock {
     pub package: Vec<CargoPackage>,
 }
 
+/// Registry popularity and release cadence for a crate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CratePopularity {
+    /// Crate name
+    pub name: String,
+    /// All-time download count
+    pub downloads: u64,
+    /// Downloads in the last 90 days
+    pub recent_downloads: Option<u64>,
+    /// Number of non-yanked releases published in the last 365 days
+    pub releases_last_year: u32,
+    /// Days since the most recent non-yanked release
+    pub days_since_last_release: Option<i64>,
+    /// Non-yanked releases, used to compute update lag
+    #[serde(default)]
+    pub releases: Vec<CrateRelease>,
+}
+
+/// Non-yanked published release of a crate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrateRelease {
+    /// Release version
+    pub version: String,
+    /// Publish timestamp (RFC 3339), unknown when read from an index mirror
+    pub published_at: Option<String>,
+}
+
+/// Registry metadata of one published crate version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrateVersionMetadata {
+    /// Crate name
+    pub name: String,
+    /// Crate version
+    pub version: String,
+    /// License expression the version was published with
+    pub license: Option<String>,
+    /// Login of the account that published the version
+    pub publisher: Option<String>,
+    /// crates.io category slugs of the crate
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// When the metadata was fetched (RFC 3339)
+    pub fetched_at: String,
+}
+
+/// How far a dependency is behind the latest published release
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpdateLag {
+    /// Latest published release
+    pub latest_version: String,
+    /// Major versions behind the latest release
+    pub major_behind: u64,
+    /// Minor versions behind, within the same major version
+    pub minor_behind: u64,
+    /// Patch versions behind, within the same minor version
+    pub patch_behind: u64,
+    /// Number of newer releases
+    pub releases_behind: usize,
+    /// Days since the version in use was released
+    pub days_since_release: Option<i64>,
+    /// Days between the release of the version in use and the latest release
+    pub days_behind_latest: Option<i64>,
+    /// Releases newer than the version in use, oldest first
+    #[serde(default)]
+    pub newer_versions: Vec<String>,
+}
+
+impl CratePopularity {
+    /// Convert popularity data to Rust annotations
+    pub fn to_annotations(&self) -> Vec<RustAnnotation> {
+        let mut annotations = vec![
+            RustAnnotation::new(annotation_keys::DOWNLOADS.to_string(), serde_json::json!(self.downloads)),
+            RustAnnotation::new(annotation_keys::RELEASES_LAST_YEAR.to_string(), serde_json::json!(self.releases_last_year)),
+        ];
+        
+        if let Some(recent) = self.recent_downloads {
+            annotations.push(RustAnnotation::new(annotation_keys::RECENT_DOWNLOADS.to_string(), serde_json::json!(recent)));
+        }
+        
+        if let Some(days) = self.days_since_last_release {
+            annotations.push(RustAnnotation::new(annotation_keys::DAYS_SINCE_LAST_RELEASE.to_string(), serde_json::json!(days)));
+        }
+        
+        annotations
+    }
+}
+
+impl CrateVersionMetadata {
+    /// Convert version metadata to Rust annotations
+    ///
+    /// Categories are left to cargo metadata when it already provided them.
+    pub fn to_annotations(&self, has_categories: bool) -> Vec<RustAnnotation> {
+        let mut annotations = Vec::new();
+        
+        if let Some(license) = &self.license {
+            annotations.push(RustAnnotation::new(annotation_keys::LICENSE.to_string(), serde_json::json!(license)));
+        }
+        
+        if let Some(publisher) = &self.publisher {
+            annotations.push(RustAnnotation::new(annotation_keys::PUBLISHER.to_string(), serde_json::json!(publisher)));
+        }
+        
+        if !has_categories && !self.categories.is_empty() {
+            annotations.push(RustAnnotation::new(annotation_keys::CATEGORIES.to_string(), serde_json::json!(self.categories)));
+        }
+        
+        annotations
+    }
+}
+
+impl UpdateLag {
+    /// Compute the lag of `current` against the known releases of its crate
+    ///
+    /// Pre-releases are only considered when `current` is itself a
+    /// pre-release. Returns `None` when `current` or every release fails to
+    /// parse as semver.
+    pub fn compute(current: &str, releases: &[CrateRelease], now: chrono::DateTime<chrono::Utc>) -> Option<Self> {
+        let current_version = semver::Version::parse(current).ok()?;
+        let candidates: Vec<(semver::Version, &CrateRelease)> = releases.iter()
+            .filter_map(|r| semver::Version::parse(&r.version).ok().map(|v| (v, r)))
+            .filter(|(v, _)| v.pre.is_empty() || !current_version.pre.is_empty())
+            .collect();
+        let (latest, latest_release) = candidates.iter().max_by(|a, b| a.0.cmp(&b.0))?;
+        
+        let published = |release: &CrateRelease| release.published_at.as_deref()
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map(|d| d.with_timezone(&chrono::Utc));
+        let current_published = candidates.iter()
+            .find(|(v, _)| *v == current_version)
+            .and_then(|(_, r)| published(r));
+        
+        let (major_behind, minor_behind, patch_behind) = if latest <= &current_version {
+            (0, 0, 0)
+        } else if latest.major != current_version.major {
+            (latest.major - current_version.major, 0, 0)
+        } else if latest.minor != current_version.minor {
+            (0, latest.minor - current_version.minor, 0)
+        } else {
+            (0, 0, latest.patch.saturating_sub(current_version.patch))
+        };
+        
+        let mut newer: Vec<&semver::Version> = candidates.iter()
+            .map(|(v, _)| v)
+            .filter(|v| **v > current_version)
+            .collect();
+        newer.sort();
+        newer.dedup();
+        
+        Some(Self {
+            latest_version: latest.to_string(),
+            major_behind,
+            minor_behind,
+            patch_behind,
+            releases_behind: candidates.iter().filter(|(v, _)| *v > current_version).count(),
+            days_since_release: current_published.map(|d| (now - d).num_days()),
+            days_behind_latest: current_published
+                .zip(published(latest_release))
+                .map(|(current, latest)| (latest - current).num_days().max(0)),
+            newer_versions: newer.iter().map(|v| v.to_string()).collect(),
+        })
+    }
+    
+    /// Whether a newer release exists
+    pub fn is_outdated(&self) -> bool {
+        self.releases_behind > 0
+    }
+    
+    /// Read the lag recorded on a package
+    pub fn from_package(package: &PackageNode) -> Option<Self> {
+        package.annotation(annotation_keys::UPDATE_LAG)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+impl ClassificationDiff {
+    /// Compare the classifications of two graphs
+    ///
+    /// Packages are matched by name and version. A changed TCS rationale
+    /// alone is not a change; the rationale of the later classification is
+    /// reported as its signals.
+    pub fn compute(before: &DependencyGraph, after: &DependencyGraph) -> Self {
+        let before_packages: HashMap<(&str, &str), &PackageNode> = before.root_packages.iter()
+            .map(|p| ((p.name.as_str(), p.version.as_str()), p))
+            .collect();
+        
+        let mut matched = 0;
+        let mut changes = Vec::new();
+        for package in &after.root_packages {
+            let Some(previous) = before_packages.get(&(package.name.as_str(), package.version.as_str())) else {
+                continue;
+            };
+            matched += 1;
+            if Self::same_class(&previous.classification, &package.classification) {
+                continue;
+            }
+            
+            let signals = match &package.classification {
+                Classification::TCS { rationale, .. } => rationale.split("; ")
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            changes.push(ClassificationChange {
+                package_name: package.name.clone(),
+                package_version: package.version.clone(),
+                before: previous.classification.clone(),
+                after: package.classification.clone(),
+                signals,
+            });
+        }
+        changes.sort_by(|a, b| (&a.package_name, &a.package_version).cmp(&(&b.package_name, &b.package_version)));
+        
+        Self {
+            before_fingerprint: before.metadata.lockfile_fingerprint.clone(),
+            after_fingerprint: after.metadata.lockfile_fingerprint.clone(),
+            changes,
+            unmatched_packages: before.root_packages.len() + after.root_packages.len() - 2 * matched,
+            tcs_coverage_before: Self::tcs_coverage(before),
+            tcs_coverage_after: Self::tcs_coverage(after),
+        }
+    }
+    
+    /// Change of the TCS share in percentage points
+    pub fn tcs_coverage_delta(&self) -> f64 {
+        self.tcs_coverage_after - self.tcs_coverage_before
+    }
+    
+    /// Packages that became TCS
+    pub fn promoted(&self) -> impl Iterator<Item = &ClassificationChange> {
+        self.changes.iter().filter(|c| Self::is_tcs(&c.after) && !Self::is_tcs(&c.before))
+    }
+    
+    /// Packages that are no longer TCS
+    pub fn demoted(&self) -> impl Iterator<Item = &ClassificationChange> {
+        self.changes.iter().filter(|c| Self::is_tcs(&c.before) && !Self::is_tcs(&c.after))
+    }
+    
+    /// Whether two classifications assign the same class, ignoring rationale
+    fn same_class(a: &Classification, b: &Classification) -> bool {
+        match (a, b) {
+            (Classification::TCS { category: a, .. }, Classification::TCS { category: b, .. }) => a == b,
+            (a, b) => a == b,
+        }
+    }
+    
+    /// Whether a classification is TCS
+    fn is_tcs(classification: &Classification) -> bool {
+        matches!(classification, Classification::TCS { .. })
+    }
+    
+    /// Percentage of a graph's packages classified as TCS
+    fn tcs_coverage(graph: &DependencyGraph) -> f64 {
+        if graph.root_packages.is_empty() {
+            return 0.0;
+        }
+        let tcs = graph.root_packages.iter().filter(|p| Self::is_tcs(&p.classification)).count();
+        tcs as f64 / graph.root_packages.len() as f64 * 100.0
+    }
+}
+
 /// Classification signal for TCS classification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ClassificationSignal {
     /// Explicit override configuration
     ExplicitOverride(String),
+    /// Crate listed in a project TCS list
+    ProjectTcsList(String),
     /// Dependency kind indicates TCS
     DependencyKind(CargoDependencyKind),
     /// Build script usage detected
@@ -200,6 +469,124 @@ pub struct ClassificationResult {
     pub signals: Vec<ClassificationSignal>,
 }
 
+/// A classification signal evaluated for a package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalEvaluation {
+    /// Signal that was checked
+    pub signal: ClassificationSignal,
+    /// Whether the signal matched the package
+    pub matched: bool,
+    /// Relative weight of the signal
+    pub weight: f64,
+    /// Configuration rule that produced the signal
+    pub rule: String,
+    /// Role assigned when this signal decides the classification
+    pub outcome: ToolchainRole,
+    /// Whether this signal determined the final classification
+    pub decisive: bool,
+}
+
+/// Explanation of how a package was classified
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassificationExplanation {
+    /// Package name
+    pub package_name: String,
+    /// Package version
+    pub package_version: String,
+    /// Final classification
+    pub result: ClassificationResult,
+    /// Every signal considered, in evaluation order
+    pub evaluations: Vec<SignalEvaluation>,
+}
+
+/// TCS classification of every package of a graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TcsClassification {
+    /// Classification of each package
+    pub packages: Vec<TcsPackageClassification>,
+}
+
+/// TCS classification of one package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TcsPackageClassification {
+    /// Package name
+    pub package_name: String,
+    /// Package version
+    pub package_version: String,
+    /// TCS category, or `None` for mechanical and unknown packages
+    pub tcs_category: Option<TcsCategory>,
+    /// Classification rationale
+    pub rationale: Option<String>,
+    /// Signals the classification is based on
+    pub signals: Vec<ClassificationSignal>,
+}
+
+impl TcsClassification {
+    /// Create an empty classification
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Add the classification of a package
+    pub fn add_package_classification(&mut self, classification: TcsPackageClassification) {
+        self.packages.push(classification);
+    }
+}
+
+/// Package whose classification differs between two graphs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassificationChange {
+    /// Package name
+    pub package_name: String,
+    /// Package version
+    pub package_version: String,
+    /// Classification in the earlier graph
+    pub before: Classification,
+    /// Classification in the later graph
+    pub after: Classification,
+    /// Signals behind the later classification
+    pub signals: Vec<String>,
+}
+
+/// Classification differences between two graphs of the same project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassificationDiff {
+    /// Lockfile fingerprint of the earlier graph
+    pub before_fingerprint: String,
+    /// Lockfile fingerprint of the later graph
+    pub after_fingerprint: String,
+    /// Packages present in both graphs whose classification changed
+    pub changes: Vec<ClassificationChange>,
+    /// Packages present in only one of the graphs
+    pub unmatched_packages: usize,
+    /// TCS share of the earlier graph's packages (percent)
+    pub tcs_coverage_before: f64,
+    /// TCS share of the later graph's packages (percent)
+    pub tcs_coverage_after: f64,
+}
+
+/// Package that no classification signal matched
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnclassifiedPackage {
+    /// Package name
+    pub package_name: String,
+    /// Package version
+    pub package_version: String,
+    /// TCS packages it depends on or that depend on it (`name@version`)
+    pub tcs_neighbors: Vec<String>,
+    /// Every signal evaluated, in evaluation order
+    pub evaluations: Vec<SignalEvaluation>,
+}
+
+/// Packages of a graph that could not be classified
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnclassifiedReport {
+    /// Number of packages in the graph
+    pub total_packages: usize,
+    /// Unclassified packages, in graph order
+    pub packages: Vec<UnclassifiedPackage>,
+}
+
 /// Toolchain role (TCS vs Mechanical)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ToolchainRole {
@@ -302,6 +689,55 @@ impl CargoMetadataPackage {
             self.license_file.as_ref().map(|_| "NOASSERTION".to_string())
         })
     }
+    
+    /// Convert descriptive metadata to Rust annotations
+    pub fn to_annotations(&self) -> Vec<RustAnnotation> {
+        let mut annotations = Vec::new();
+        
+        let optional_strings = [
+            (annotation_keys::DESCRIPTION, &self.description),
+            (annotation_keys::HOMEPAGE, &self.homepage),
+            (annotation_keys::REPOSITORY, &self.repository),
+            (annotation_keys::EDITION, &self.edition),
+            (annotation_keys::RUST_VERSION, &self.rust_version),
+        ];
+        for (key, value) in optional_strings {
+            if let Some(value) = value {
+                annotations.push(RustAnnotation::new(key.to_string(), serde_json::Value::String(value.clone())));
+            }
+        }
+        
+        let string_lists = [
+            (annotation_keys::AUTHORS, &self.authors),
+            (annotation_keys::CATEGORIES, &self.categories),
+            (annotation_keys::KEYWORDS, &self.keywords),
+        ];
+        for (key, values) in string_lists {
+            if !values.is_empty() {
+                annotations.push(RustAnnotation::new(key.to_string(), serde_json::json!(values)));
+            }
+        }
+        
+        if self.has_proc_macro_target() {
+            annotations.push(RustAnnotation::new(annotation_keys::PROC_MACRO.to_string(), serde_json::Value::Bool(true)));
+        }
+        
+        annotations
+    }
+}
+
+impl UnclassifiedPackage {
+    /// Whether the package shares a dependency edge with a TCS package
+    pub fn is_tcs_adjacent(&self) -> bool {
+        !self.tcs_neighbors.is_empty()
+    }
+}
+
+impl UnclassifiedReport {
+    /// Unclassified packages sharing a dependency edge with a TCS package
+    pub fn tcs_adjacent(&self) -> impl Iterator<Item = &UnclassifiedPackage> {
+        self.packages.iter().filter(|package| package.is_tcs_adjacent())
+    }
 }
 
 impl ClassificationSignal {
@@ -311,6 +747,9 @@ impl ClassificationSignal {
             ClassificationSignal::ExplicitOverride(name) => {
                 format!("Explicit override configuration for package: {}", name)
             },
+            ClassificationSignal::ProjectTcsList(list) => {
+                format!("Listed in project TCS list: {}", list)
+            },
             ClassificationSignal::DependencyKind(kind) => {
                 format!("Dependency kind indicates TCS: {:?}", kind)
             },
@@ -331,6 +770,20 @@ impl ClassificationSignal {
             },
         }
     }
+    
+    /// Get signal weight (higher-weight signals take precedence)
+    pub fn weight(&self) -> f64 {
+        match self {
+            ClassificationSignal::ExplicitOverride(_) => 1.0,
+            ClassificationSignal::ProjectTcsList(_) => 0.95,
+            ClassificationSignal::ProcMacroUsage => 0.9,
+            ClassificationSignal::BuildScriptUsage => 0.8,
+            ClassificationSignal::NamePattern(_) => 0.7,
+            ClassificationSignal::CargoCategory(_) => 0.5,
+            ClassificationSignal::CargoKeyword(_) => 0.4,
+            ClassificationSignal::DependencyKind(_) => 0.1,
+        }
+    }
 }
 
 impl ClassificationResult {
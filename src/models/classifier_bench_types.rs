@@ -0,0 +1,175 @@
+//! Classifier benchmark types
+//!
+//! This module defines the labelled crate corpus the TCS classifier is
+//! benchmarked against and the report of how a ruleset classifies it, so
+//! pattern changes can be reviewed against known crates before release.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use super::dependency_graph::TcsCategory;
+
+/// A crate of the benchmark corpus
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CorpusCrate {
+    /// Crate name
+    pub name: String,
+    /// crates.io category slugs
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// crates.io keywords
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Whether the crate is a proc-macro
+    #[serde(default)]
+    pub proc_macro: bool,
+    /// TCS category a reviewer assigned, `None` for mechanical crates
+    pub expected: Option<TcsCategory>,
+}
+
+/// Classification of a corpus by one ruleset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassifierBenchReport {
+    /// Report generation timestamp
+    pub generated_at: String,
+    /// Classification ruleset version the corpus was classified with
+    pub ruleset_version: String,
+    /// Per-crate results, in corpus order
+    pub results: Vec<BenchResult>,
+    /// How often each rule matched, in evaluation order
+    pub rules: Vec<BenchRuleStats>,
+    /// Name patterns that matched only part of a name segment
+    pub partial_name_matches: Vec<PartialNameMatch>,
+    /// Changes against the baseline report, if one was given
+    #[serde(default)]
+    pub baseline: Option<ClassifierBenchDiff>,
+}
+
+/// Classification of one corpus crate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchResult {
+    /// Crate name
+    pub name: String,
+    /// Labelled TCS category, `None` for mechanical
+    pub expected: Option<TcsCategory>,
+    /// Classified TCS category, `None` for mechanical
+    pub actual: Option<TcsCategory>,
+    /// Rule of the decisive signal
+    pub rule: String,
+}
+
+/// Match counts of one classification rule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchRuleStats {
+    /// Rule, as shown in classification explanations
+    pub rule: String,
+    /// Corpus crates the rule matched
+    pub matched: usize,
+    /// Corpus crates the rule decided
+    pub decisive: usize,
+}
+
+/// A name pattern matching inside a name segment (`rand` in `operand`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartialNameMatch {
+    /// Crate name
+    pub name: String,
+    /// Pattern name
+    pub pattern: String,
+    /// Name segment the pattern matched part of
+    pub segment: String,
+    /// Labelled TCS category, `None` for mechanical
+    pub expected: Option<TcsCategory>,
+}
+
+/// Classification changes between two bench reports
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassifierBenchDiff {
+    /// Ruleset version of the baseline report
+    pub baseline_ruleset_version: String,
+    /// Crates classified differently, in corpus order
+    pub changes: Vec<BenchChange>,
+    /// Crates present in only one of the reports
+    pub unmatched_crates: usize,
+    /// TCS match rate of the baseline (percent)
+    pub match_rate_before: f64,
+    /// Accuracy of the baseline against the labels (percent)
+    pub accuracy_before: f64,
+}
+
+/// A corpus crate classified differently than in the baseline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchChange {
+    /// Crate name
+    pub name: String,
+    /// Labelled TCS category, `None` for mechanical
+    pub expected: Option<TcsCategory>,
+    /// Baseline TCS category, `None` for mechanical
+    pub before: Option<TcsCategory>,
+    /// Current TCS category, `None` for mechanical
+    pub after: Option<TcsCategory>,
+}
+
+impl ClassifierBenchReport {
+    /// Share of corpus crates classified as TCS (percent)
+    pub fn match_rate(&self) -> f64 {
+        Self::percent(self.results.iter().filter(|result| result.actual.is_some()).count(), self.results.len())
+    }
+    
+    /// Share of corpus crates classified as labelled (percent)
+    pub fn accuracy(&self) -> f64 {
+        Self::percent(self.results.iter().filter(|result| result.actual == result.expected).count(), self.results.len())
+    }
+    
+    /// Crates labelled mechanical but classified as TCS
+    pub fn false_positives(&self) -> impl Iterator<Item = &BenchResult> {
+        self.results.iter().filter(|result| result.expected.is_none() && result.actual.is_some())
+    }
+    
+    /// Crates labelled TCS but classified as mechanical
+    pub fn false_negatives(&self) -> impl Iterator<Item = &BenchResult> {
+        self.results.iter().filter(|result| result.expected.is_some() && result.actual.is_none())
+    }
+    
+    /// Compare with the report of an earlier ruleset
+    pub fn diff(&self, baseline: &ClassifierBenchReport) -> ClassifierBenchDiff {
+        let before: HashMap<&str, &BenchResult> = baseline.results.iter()
+            .map(|result| (result.name.as_str(), result))
+            .collect();
+        
+        let mut matched = 0;
+        let changes = self.results.iter()
+            .filter_map(|result| {
+                let previous = before.get(result.name.as_str())?;
+                matched += 1;
+                (previous.actual != result.actual).then(|| BenchChange {
+                    name: result.name.clone(),
+                    expected: result.expected.clone(),
+                    before: previous.actual.clone(),
+                    after: result.actual.clone(),
+                })
+            })
+            .collect();
+        
+        ClassifierBenchDiff {
+            baseline_ruleset_version: baseline.ruleset_version.clone(),
+            changes,
+            unmatched_crates: self.results.len() + baseline.results.len() - 2 * matched,
+            match_rate_before: baseline.match_rate(),
+            accuracy_before: baseline.accuracy(),
+        }
+    }
+    
+    fn percent(count: usize, total: usize) -> f64 {
+        match total {
+            0 => 0.0,
+            total => count as f64 / total as f64 * 100.0,
+        }
+    }
+}
+
+impl BenchChange {
+    /// Whether the change moves the crate towards its label
+    pub fn is_fix(&self) -> bool {
+        self.after == self.expected
+    }
+}
@@ -1,107 +1,13 @@
 //! Configuration types for Rust Ecosystem Adapter
 //! 
-//! This module defines configuration structures for adapter behavior,
-//! TCS classification rules, tool paths, and validation schemas.
+//! The adapter configuration is defined in [`crate::config`] and
+//! re-exported here for the components; this module adds detailed
+//! validation, merging and schema types on top of it.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use super::dependency_graph::*;
-use super::cargo_types::*;
 
-/// Main configuration structure for Rust Adapter
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct RustAdapterConfig {
-    /// Explicit TCS category overrides
-    pub explicit_tcs_overrides: HashMap<String, TcsCategory>,
-    /// Custom TCS classification patterns
-    pub custom_tcs_patterns: Vec<TcsPattern>,
-    /// Tool path configurations
-    pub tool_paths: ToolPaths,
-    /// Vendoring configuration
-    pub vendor_config: VendorConfig,
-    /// SBOM generation configuration
-    pub sbom_config: SbomConfig,
-    /// Audit configuration
-    pub audit_config: AuditConfig,
-    /// Classification configuration
-    pub classification_config: ClassificationConfig,
-    /// Logging configuration
-    pub logging_config: LoggingConfig,
-    /// Offline mode flag
-    pub offline_mode: bool,
-    /// Schema validation flag
-    pub schema_validation: bool,
-}
-
-/// Tool path configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct ToolPaths {
-    /// Path to cargo executable
-    pub cargo: PathBuf,
-    /// Path to cargo-audit (optional)
-    pub cargo_audit: Option<PathBuf>,
-    /// Path to cargo-vet (optional)
-    pub cargo_vet: Option<PathBuf>,
-    /// Default timeout for tool execution (seconds)
-    pub default_timeout: u64,
-}
-
-/// Vendoring configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct VendorConfig {
-    /// Default vendor directory path
-    pub default_vendor_dir: PathBuf,
-    /// Vendor timeout (seconds)
-    pub vendor_timeout: u64,
-    /// Whether to verify checksums by default
-    pub verify_checksums: bool,
-    /// Whether to scan for malware
-    pub malware_scan: bool,
-    /// Whether to compare with fresh downloads
-    pub compare_fresh: bool,
-}
-
-/// Audit configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct AuditConfig {
-    /// Audit timeout (seconds)
-    pub audit_timeout: u64,
-    /// Whether to run cargo-audit
-    pub run_cargo_audit: bool,
-    /// Whether to run cargo-vet
-    pub run_cargo_vet: bool,
-    /// Whether to cache audit results
-    pub cache_results: bool,
-    /// Advisory database path (optional)
-    pub advisory_db_path: Option<PathBuf>,
-}
-
-/// Classification configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct ClassificationConfig {
-    /// Whether to classify proc-macros as TCS
-    pub classify_proc_macros: bool,
-    /// Whether to classify build dependencies as TCS
-    pub classify_build_deps: bool,
-    /// Default category for unclassified packages
-    pub default_category: MechanicalCategory,
-    /// Classification confidence threshold
-    pub confidence_threshold: f64,
-}
-
-/// Logging configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct LoggingConfig {
-    /// Log level (trace, debug, info, warn, error)
-    pub level: String,
-    /// Whether to enable structured logging
-    pub structured: bool,
-    /// Log file path (optional)
-    pub log_file: Option<PathBuf>,
-    /// Whether to include tool execution details
-    pub include_tool_details: bool,
-}
+pub use crate::config::rust_config::*;
 
 /// Configuration validation result
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -242,147 +148,7 @@ pub enum ConflictResolution {
     Manual,
 }
 
-impl Default for RustAdapterConfig {
-    fn default() -> Self {
-        Self {
-            explicit_tcs_overrides: HashMap::new(),
-            custom_tcs_patterns: Vec::new(),
-            tool_paths: ToolPaths::default(),
-            vendor_config: VendorConfig::default(),
-            sbom_config: SbomConfig::default(),
-            audit_config: AuditConfig::default(),
-            classification_config: ClassificationConfig::default(),
-            logging_config: LoggingConfig::default(),
-            offline_mode: false,
-            schema_validation: true,
-        }
-    }
-}
-
-impl Default for ToolPaths {
-    fn default() -> Self {
-        Self {
-            cargo: PathBuf::from("cargo"),
-            cargo_audit: None,
-            cargo_vet: None,
-            default_timeout: 300, // 5 minutes
-        }
-    }
-}
-
-impl Default for VendorConfig {
-    fn default() -> Self {
-        Self {
-            default_vendor_dir: PathBuf::from("vendor"),
-            vendor_timeout: 600, // 10 minutes
-            verify_checksums: true,
-            malware_scan: false,
-            compare_fresh: false,
-        }
-    }
-}
-
-impl Default for AuditConfig {
-    fn default() -> Self {
-        Self {
-            audit_timeout: 300, // 5 minutes
-            run_cargo_audit: true,
-            run_cargo_vet: true,
-            cache_results: true,
-            advisory_db_path: None,
-        }
-    }
-}
-
-impl Default for ClassificationConfig {
-    fn default() -> Self {
-        Self {
-            classify_proc_macros: true,
-            classify_build_deps: false,
-            default_category: MechanicalCategory::Other("default".to_string()),
-            confidence_threshold: 0.7,
-        }
-    }
-}
-
-impl Default for LoggingConfig {
-    fn default() -> Self {
-        Self {
-            level: "info".to_string(),
-            structured: false,
-            log_file: None,
-            include_tool_details: false,
-        }
-    }
-}
-
 impl RustAdapterConfig {
-    /// Load configuration from file
-    pub fn load_from_file(path: &PathBuf) -> crate::Result<Self> {
-        let config_content = std::fs::read_to_string(path)
-            .map_err(|e| crate::AdapterError::file_not_found(path, "reading config file"))?;
-        
-        let config: RustAdapterConfig = toml::from_str(&config_content)
-            .map_err(|e| crate::AdapterError::ConfigurationInvalid {
-                field: "config_file".to_string(),
-                value: format!("{:?}", path),
-                reason: format!("TOML parsing error: {}", e),
-                source: anyhow::anyhow!("TOML parsing failed"),
-            })?;
-        
-        // Validate configuration
-        if config.schema_validation {
-            config.validate()?;
-        }
-        
-        Ok(config)
-    }
-    
-    /// Load configuration with fallback to defaults
-    pub fn load_with_defaults(path: Option<&PathBuf>) -> crate::Result<Self> {
-        match path {
-            Some(p) => {
-                match Self::load_from_file(p) {
-                    Ok(config) => Ok(config),
-                    Err(e) => {
-                        eprintln!("Warning: Invalid config at {:?}: {}, using defaults", p, e);
-                        Ok(Self::default())
-                    }
-                }
-            },
-            None => Ok(Self::default()),
-        }
-    }
-    
-    /// Validate configuration against schema
-    pub fn validate(&self) -> crate::Result<()> {
-        let validation_result = self.validate_detailed();
-        
-        if !validation_result.is_valid {
-            let error_messages: Vec<String> = validation_result.errors
-                .iter()
-                .map(|e| format!("{}: {}", e.field, e.message))
-                .collect();
-            
-            return Err(crate::AdapterError::ConfigurationInvalid {
-                field: "validation".to_string(),
-                value: "multiple errors".to_string(),
-                reason: error_messages.join("; "),
-                source: anyhow::anyhow!("Configuration validation failed"),
-            });
-        }
-        
-        // Log warnings
-        for warning in &validation_result.warnings {
-            eprintln!("Config warning: {} - {}", warning.field, warning.message);
-            if let Some(suggestion) = &warning.suggestion {
-                eprintln!("  Suggestion: {}", suggestion);
-            }
-        }
-        
-        Ok(())
-    }
-    
     /// Detailed validation with specific errors and warnings
     pub fn validate_detailed(&self) -> ConfigValidationResult {
         let mut errors = Vec::new();
@@ -415,6 +181,59 @@ impl RustAdapterConfig {
             });
         }
         
+        // Validate override patterns
+        let override_keys = self.explicit_tcs_overrides.keys()
+            .map(|key| ("explicit_tcs_overrides", key));
+        for (table, key) in override_keys {
+            if let Err(reason) = crate::utils::NamePattern::parse(key) {
+                errors.push(ConfigValidationError {
+                    field: format!("{}.{}", table, key),
+                    message: reason,
+                    severity: ConfigErrorSeverity::Error,
+                });
+            }
+        }
+        
+        // Validate gating config
+        if let Some(coverage) = self.gating_config.min_tcs_audit_coverage {
+            if !(0.0..=100.0).contains(&coverage) {
+                errors.push(ConfigValidationError {
+                    field: "gating_config.min_tcs_audit_coverage".to_string(),
+                    message: "Coverage must be a percentage between 0 and 100".to_string(),
+                    severity: ConfigErrorSeverity::Error,
+                });
+            }
+        }
+        
+        // Validate HTTP client config
+        if let Err(reason) = self.http_config.validate() {
+            errors.push(ConfigValidationError {
+                field: "http_config".to_string(),
+                message: reason,
+                severity: ConfigErrorSeverity::Error,
+            });
+        }
+        
+        // Validate fact collectors
+        for collector in &self.fact_collectors {
+            if let Err(reason) = collector.validate() {
+                errors.push(ConfigValidationError {
+                    field: format!("fact_collectors.{}", collector.name),
+                    message: reason,
+                    severity: ConfigErrorSeverity::Error,
+                });
+            }
+        }
+        
+        // Validate drift impact model
+        if let Err(reason) = self.drift_impact_model.validate() {
+            errors.push(ConfigValidationError {
+                field: "drift_impact_model".to_string(),
+                message: reason,
+                severity: ConfigErrorSeverity::Error,
+            });
+        }
+        
         // Validate logging config
         let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
         if !valid_log_levels.contains(&self.logging_config.level.as_str()) {
@@ -425,6 +244,23 @@ impl RustAdapterConfig {
             });
         }
         
+        // Validate performance limits
+        let performance_limits = [
+            ("performance_config.max_parallel_tool_invocations", self.performance_config.max_parallel_tool_invocations as u64),
+            ("performance_config.max_hashing_threads", self.performance_config.max_hashing_threads as u64),
+            ("performance_config.metadata_memory_limit", self.performance_config.metadata_memory_limit),
+            ("performance_config.io_buffer_size", self.performance_config.io_buffer_size as u64),
+        ];
+        for (field, value) in performance_limits {
+            if value == 0 {
+                errors.push(ConfigValidationError {
+                    field: field.to_string(),
+                    message: "Limit must be greater than zero".to_string(),
+                    severity: ConfigErrorSeverity::Error,
+                });
+            }
+        }
+        
         // Warnings for optional tools
         if self.audit_config.run_cargo_audit && self.tool_paths.cargo_audit.is_none() {
             warnings.push(ConfigValidationWarning {
@@ -452,7 +288,7 @@ impl RustAdapterConfig {
     /// Merge with another configuration
     pub fn merge_with(&self, other: &RustAdapterConfig) -> ConfigMergeResult {
         let mut conflicts = Vec::new();
-        let mut applied_defaults = Vec::new();
+        let applied_defaults = Vec::new();
         
         // This is a simplified merge implementation
         // In a real implementation, you'd need more sophisticated merging logic
@@ -483,6 +319,23 @@ impl RustAdapterConfig {
             audit_config: other.audit_config.clone(),
             classification_config: other.classification_config.clone(),
             logging_config: other.logging_config.clone(),
+            performance_config: other.performance_config.clone(),
+            registry_config: other.registry_config.clone(),
+            cache_config: other.cache_config.clone(),
+            http_config: other.http_config.clone(),
+            digest_config: other.digest_config,
+            telemetry_config: other.telemetry_config.clone(),
+            deadline_config: other.deadline_config.clone(),
+            transparency_log_config: other.transparency_log_config.clone(),
+            bundle_config: other.bundle_config.clone(),
+            export_redaction_config: other.export_redaction_config.clone(),
+            org_config: other.org_config.clone(),
+            gating_config: other.gating_config.clone(),
+            risk_config: other.risk_config.clone(),
+            facts_config: other.facts_config.clone(),
+            drift_detection_config: other.drift_detection_config.clone(),
+            fact_collectors: other.fact_collectors.clone(),
+            drift_impact_model: other.drift_impact_model.clone(),
             offline_mode: other.offline_mode,
             schema_validation: other.schema_validation,
         };
@@ -493,16 +346,6 @@ impl RustAdapterConfig {
             applied_defaults,
         }
     }
-    
-    /// Get effective tool path, checking environment and defaults
-    pub fn get_tool_path(&self, tool: &str) -> Option<PathBuf> {
-        match tool {
-            "cargo" => Some(self.tool_paths.cargo.clone()),
-            "cargo-audit" => self.tool_paths.cargo_audit.clone(),
-            "cargo-vet" => self.tool_paths.cargo_vet.clone(),
-            _ => None,
-        }
-    }
 }
 
 impl ConfigValidationResult {
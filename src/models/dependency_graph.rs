@@ -5,8 +5,9 @@
 //! for Rust-specific annotations.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
+use super::ordering::{sorted_map, Ranks};
 
 /// Unique identifier for a package
 pub type PackageId = Uuid;
@@ -135,6 +136,56 @@ pub enum TcsCategory {
     Custom(String),
 }
 
+impl std::fmt::Display for TcsCategory {
+    /// Format the category in the same form accepted by `FromStr`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TcsCategory::Custom(name) => write!(f, "custom:{}", name),
+            builtin => write!(f, "{:?}", builtin),
+        }
+    }
+}
+
+impl std::fmt::Display for Classification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Classification::TCS { category, .. } => write!(f, "TCS ({})", category),
+            Classification::Mechanical { category } => write!(f, "Mechanical ({:?})", category),
+            Classification::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl std::str::FromStr for TcsCategory {
+    type Err = String;
+    
+    /// Parse a category name as used on the command line
+    ///
+    /// Accepts variant names case-insensitively, common short forms such as
+    /// `crypto` and `auth`, and `custom:<name>` for custom categories.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(name) = s.strip_prefix("custom:") {
+            if !name.trim().is_empty() {
+                return Ok(TcsCategory::Custom(name.trim().to_string()));
+            }
+        }
+        
+        match s.to_ascii_lowercase().as_str() {
+            "crypto" | "cryptography" => Ok(TcsCategory::Cryptography),
+            "auth" | "authentication" => Ok(TcsCategory::Authentication),
+            "serde" | "serialization" => Ok(TcsCategory::Serialization),
+            "network" | "transport" => Ok(TcsCategory::Transport),
+            "db" | "database" => Ok(TcsCategory::Database),
+            "rng" | "random" => Ok(TcsCategory::Random),
+            "build" | "build-time-execution" | "buildtimeexecution" => Ok(TcsCategory::BuildTimeExecution),
+            _ => Err(format!(
+                "Unknown TCS category '{}'. Valid categories: crypto, auth, serialization, transport, database, random, build, custom:<name>",
+                s
+            )),
+        }
+    }
+}
+
 /// Mechanical component categories
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MechanicalCategory {
@@ -213,6 +264,26 @@ pub enum DependencyKind {
     Dev,
 }
 
+/// Where a package is developed, relative to the analyzed workspace
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageOrigin {
+    /// Member of the analyzed workspace
+    FirstParty,
+    /// Crate consumed from a registry, a git repository or a path outside the workspace
+    ThirdParty,
+}
+
+impl PackageOrigin {
+    /// Annotation value of the origin
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PackageOrigin::FirstParty => "first_party",
+            PackageOrigin::ThirdParty => "third_party",
+        }
+    }
+}
+
 /// Rust-specific annotation for UGDG compatibility
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RustAnnotation {
@@ -230,12 +301,17 @@ pub struct GraphMetadata {
     /// Graph generation timestamp
     pub generated_at: String,
     /// Tool version information
+    #[serde(serialize_with = "sorted_map")]
     pub tool_versions: HashMap<String, String>,
     /// Graph schema version
     pub schema_version: String,
     /// Whether this graph was generated in offline mode
     pub offline_mode: bool,
+    /// Canonical Cargo.lock fingerprint the graph was built from
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
     /// Additional metadata
+    #[serde(serialize_with = "sorted_map")]
     pub properties: HashMap<String, serde_json::Value>,
 }
 
@@ -261,6 +337,82 @@ impl DependencyGraph {
         self.edges.push(edge);
     }
     
+    /// Merge the graph parsed from another lockfile into this one
+    ///
+    /// Packages with the same name, version and source become one node,
+    /// annotated with every lockfile (relative to the project root) that
+    /// resolves them. Merging into an empty graph adopts the metadata of
+    /// the merged graph, so the first lockfile's fingerprint stays the
+    /// graph fingerprint; every lockfile's fingerprint is listed in the
+    /// `lockfiles` metadata property.
+    pub fn merge_lockfile(&mut self, other: DependencyGraph, lockfile: &str) {
+        if self.root_packages.is_empty() && self.edges.is_empty() {
+            self.metadata = other.metadata.clone();
+        }
+        
+        let mut ids = HashMap::new();
+        for package in other.root_packages {
+            let id = package.id;
+            let existing = self.root_packages.iter()
+                .position(|p| p.name == package.name && p.version == package.version && p.source == package.source);
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    self.root_packages.push(package);
+                    self.root_packages.len() - 1
+                },
+            };
+            let target = &mut self.root_packages[index];
+            ids.insert(id, target.id);
+            
+            let mut lockfiles = target.annotation(annotation_keys::LOCKFILES)
+                .and_then(|value| value.as_array())
+                .cloned()
+                .unwrap_or_default();
+            if !lockfiles.iter().any(|value| value == lockfile) {
+                lockfiles.push(serde_json::Value::from(lockfile));
+            }
+            target.set_annotation(annotation_keys::LOCKFILES, serde_json::Value::Array(lockfiles));
+        }
+        
+        for edge in other.edges {
+            let (Some(from), Some(to)) = (ids.get(&edge.from), ids.get(&edge.to)) else { continue };
+            let edge = DependencyEdge { from: *from, to: *to, ..edge };
+            if !self.edges.contains(&edge) {
+                self.edges.push(edge);
+            }
+        }
+        
+        let fingerprints = self.metadata.properties.entry("lockfiles".to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if let Some(fingerprints) = fingerprints.as_object_mut() {
+            fingerprints.insert(lockfile.to_string(), serde_json::Value::from(other.metadata.lockfile_fingerprint));
+        }
+        
+        self.annotate_depths();
+        self.sort_canonical();
+    }
+    
+    /// Sort packages by name, version and ID, and edges by the packages they connect
+    ///
+    /// Package IDs are random, so edges are ordered by the positions of
+    /// their endpoints rather than by ID. Edge features are sorted too.
+    pub fn sort_canonical(&mut self) {
+        self.root_packages.sort_by(|a, b| (&a.name, &a.version, a.id).cmp(&(&b.name, &b.version, b.id)));
+        let ranks = Ranks::new(self.root_packages.iter().map(|package| package.id));
+        for edge in &mut self.edges {
+            edge.features.sort();
+        }
+        self.edges.sort_by_cached_key(|edge| (
+            ranks.get(&edge.from),
+            ranks.get(&edge.to),
+            format!("{:?}", edge.kind),
+            edge.target.clone(),
+            edge.from,
+            edge.to,
+        ));
+    }
+    
     /// Find a package by name and version
     pub fn find_package(&self, name: &str, version: &str) -> Option<&PackageNode> {
         self.root_packages.iter().find(|p| p.name == name && p.version == version)
@@ -281,27 +433,609 @@ impl DependencyGraph {
         self.edges.iter().filter(|e| e.to == *package_id).collect()
     }
     
-    /// Validate the graph for basic consistency
-    pub fn validate(&self) -> Result<(), String> {
-        // Check that all edge references exist
+    /// Packages developed in the analyzed workspace
+    pub fn first_party_packages(&self) -> impl Iterator<Item = &PackageNode> {
+        self.root_packages.iter().filter(|p| p.is_first_party())
+    }
+    
+    /// Packages consumed from outside the analyzed workspace
+    pub fn third_party_packages(&self) -> impl Iterator<Item = &PackageNode> {
+        self.root_packages.iter().filter(|p| !p.is_first_party())
+    }
+    
+    /// Names of the workspace members depending on each package
+    ///
+    /// Covers direct and transitive dependencies of every member; packages
+    /// no member reaches are absent from the map.
+    pub fn workspace_member_dependents(&self) -> HashMap<PackageId, BTreeSet<String>> {
+        let mut dependencies: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
         for edge in &self.edges {
-            if self.find_package_by_id(&edge.from).is_none() {
-                return Err(format!("Edge references non-existent package: {:?}", edge.from));
+            dependencies.entry(edge.from).or_default().push(edge.to);
+        }
+        
+        let mut dependents: HashMap<PackageId, BTreeSet<String>> = HashMap::new();
+        for member in self.root_packages.iter().filter(|p| p.is_workspace_member()) {
+            let mut stack = vec![member.id];
+            let mut visited = BTreeSet::from([member.id]);
+            while let Some(id) = stack.pop() {
+                for dependency in dependencies.get(&id).into_iter().flatten() {
+                    if visited.insert(*dependency) {
+                        dependents.entry(*dependency).or_default().insert(member.name.clone());
+                        stack.push(*dependency);
+                    }
+                }
             }
-            if self.find_package_by_id(&edge.to).is_none() {
-                return Err(format!("Edge references non-existent package: {:?}", edge.to));
+        }
+        
+        dependents
+    }
+    
+    /// Shortest dependency paths from the roots to a package
+    ///
+    /// Each path starts at a root (see `validate`) and ends at `target`;
+    /// at most one path per root is returned, shortest first, up to
+    /// `max_paths`. A root package yields the path containing only itself.
+    pub fn dependency_paths(&self, target: &PackageId, max_paths: usize) -> Vec<Vec<PackageId>> {
+        let edges: Vec<&DependencyEdge> = self.edges.iter().filter(|edge| edge.from != edge.to).collect();
+        let mut dependents: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
+        for edge in &edges {
+            dependents.entry(edge.to).or_default().push(edge.from);
+        }
+        let roots: HashSet<PackageId> = self.roots(&edges).into_iter().collect();
+        
+        // Breadth-first towards the roots, remembering the next hop to the target
+        let mut next_hop: HashMap<PackageId, Option<PackageId>> = HashMap::from([(*target, None)]);
+        let mut queue = std::collections::VecDeque::from([*target]);
+        let mut paths = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            if paths.len() >= max_paths {
+                break;
+            }
+            if roots.contains(&id) {
+                let mut path = vec![id];
+                while let Some(Some(next)) = next_hop.get(path.last().unwrap_or(&id)) {
+                    path.push(*next);
+                }
+                paths.push(path);
+            }
+            for dependent in dependents.get(&id).into_iter().flatten() {
+                if !next_hop.contains_key(dependent) {
+                    next_hop.insert(*dependent, Some(id));
+                    queue.push_back(*dependent);
+                }
             }
         }
         
-        // Check for duplicate package IDs
-        let mut seen_ids = std::collections::HashSet::new();
+        paths
+    }
+    
+    /// Shortest distance of each package from the roots
+    ///
+    /// Roots (see `validate`) are at depth 0. Packages no root reaches are
+    /// absent from the map.
+    pub fn package_depths(&self) -> HashMap<PackageId, usize> {
+        let edges: Vec<&DependencyEdge> = self.edges.iter().filter(|edge| edge.from != edge.to).collect();
+        let mut dependencies: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
+        for edge in &edges {
+            dependencies.entry(edge.from).or_default().push(edge.to);
+        }
+        
+        let mut depths = HashMap::new();
+        let mut queue = std::collections::VecDeque::new();
+        for root in self.roots(&edges) {
+            depths.insert(root, 0);
+            queue.push_back(root);
+        }
+        while let Some(id) = queue.pop_front() {
+            let depth = depths[&id];
+            for dependency in dependencies.get(&id).into_iter().flatten() {
+                if !depths.contains_key(dependency) {
+                    depths.insert(*dependency, depth + 1);
+                    queue.push_back(*dependency);
+                }
+            }
+        }
+        
+        depths
+    }
+    
+    /// Record each package's depth as its `depth` annotation
+    ///
+    /// Packages no root reaches lose a previously recorded depth.
+    pub fn annotate_depths(&mut self) {
+        let depths = self.package_depths();
+        for package in &mut self.root_packages {
+            match depths.get(&package.id) {
+                Some(depth) => package.set_annotation(annotation_keys::DEPTH, serde_json::Value::from(*depth)),
+                None => package.annotations.retain(|annotation| annotation.key != annotation_keys::DEPTH),
+            }
+        }
+    }
+    
+    /// The part of the graph at most `max_depth` below the roots
+    ///
+    /// Packages no root reaches are left out, as are edges to or from
+    /// removed packages.
+    pub fn within_depth(&self, max_depth: usize) -> DependencyGraph {
+        let depths = self.package_depths();
+        let kept: HashSet<PackageId> = depths.iter()
+            .filter(|(_, depth)| **depth <= max_depth)
+            .map(|(id, _)| *id)
+            .collect();
+        
+        DependencyGraph {
+            root_packages: self.root_packages.iter().filter(|p| kept.contains(&p.id)).cloned().collect(),
+            edges: self.edges.iter().filter(|e| kept.contains(&e.from) && kept.contains(&e.to)).cloned().collect(),
+            ..self.clone()
+        }
+    }
+    
+    /// The part of the graph reachable from the roots through edges of `kinds`
+    ///
+    /// Roots are determined from all edges, so a package depended on only
+    /// through another kind of edge is removed rather than becoming a root.
+    /// Edges of other kinds are left out.
+    pub fn through_kinds(&self, kinds: &[DependencyKind]) -> DependencyGraph {
+        let edges: Vec<&DependencyEdge> = self.edges.iter().filter(|edge| edge.from != edge.to).collect();
+        let mut dependencies: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
+        for edge in edges.iter().filter(|edge| kinds.contains(&edge.kind)) {
+            dependencies.entry(edge.from).or_default().push(edge.to);
+        }
+        
+        let roots = self.roots(&edges);
+        let mut kept: HashSet<PackageId> = roots.iter().copied().collect();
+        let mut queue: std::collections::VecDeque<PackageId> = roots.into();
+        while let Some(id) = queue.pop_front() {
+            for dependency in dependencies.get(&id).into_iter().flatten() {
+                if kept.insert(*dependency) {
+                    queue.push_back(*dependency);
+                }
+            }
+        }
+        
+        DependencyGraph {
+            root_packages: self.root_packages.iter().filter(|p| kept.contains(&p.id)).cloned().collect(),
+            edges: self.edges.iter()
+                .filter(|e| kinds.contains(&e.kind) && kept.contains(&e.from) && kept.contains(&e.to))
+                .cloned()
+                .collect(),
+            ..self.clone()
+        }
+    }
+    
+    /// Packages the graph starts from
+    ///
+    /// The workspace members, or every package without dependents when no
+    /// member is annotated.
+    fn roots(&self, edges: &[&DependencyEdge]) -> Vec<PackageId> {
+        let members: Vec<PackageId> = self.root_packages.iter()
+            .filter(|p| p.is_workspace_member())
+            .map(|p| p.id)
+            .collect();
+        if !members.is_empty() {
+            return members;
+        }
+        
+        let depended_on: HashSet<PackageId> = edges.iter().map(|edge| edge.to).collect();
+        self.root_packages.iter()
+            .map(|p| p.id)
+            .filter(|id| !depended_on.contains(id))
+            .collect()
+    }
+    
+    /// Validate the graph's structure
+    ///
+    /// Dangling edges and duplicate package IDs are errors. Self-edges,
+    /// dependency cycles and packages unreachable from any root are
+    /// reported as well; a cycle is only informational when it passes
+    /// through a dev-dependency, which Cargo allows. Roots are the
+    /// workspace members, or every package without dependents when no
+    /// member is annotated.
+    pub fn validate(&self) -> GraphValidation {
+        let mut findings = Vec::new();
+        let mut seen_ids = HashSet::new();
         for package in &self.root_packages {
-            if !seen_ids.insert(&package.id) {
-                return Err(format!("Duplicate package ID: {:?}", package.id));
+            if !seen_ids.insert(package.id) {
+                findings.push(GraphValidationFinding::new(
+                    GraphFindingKind::DuplicatePackageId,
+                    GraphFindingSeverity::Error,
+                    vec![package.id],
+                    format!("Duplicate package ID {} ({} {})", package.id, package.name, package.version),
+                ));
+            }
+        }
+        
+        let mut edges: Vec<&DependencyEdge> = Vec::new();
+        for edge in &self.edges {
+            let missing: Vec<PackageId> = [edge.from, edge.to].into_iter()
+                .filter(|id| !seen_ids.contains(id))
+                .collect();
+            if !missing.is_empty() {
+                findings.push(GraphValidationFinding::new(
+                    GraphFindingKind::DanglingEdge,
+                    GraphFindingSeverity::Error,
+                    missing.clone(),
+                    format!("Edge {} -> {} references non-existent package {}", edge.from, edge.to, missing[0]),
+                ));
+            } else if edge.from == edge.to {
+                findings.push(GraphValidationFinding::new(
+                    GraphFindingKind::SelfEdge,
+                    GraphFindingSeverity::Warning,
+                    vec![edge.from],
+                    format!("{} depends on itself", self.package_label(&edge.from)),
+                ));
+            } else {
+                edges.push(edge);
             }
         }
         
-        Ok(())
+        findings.extend(self.cycle_findings(&edges));
+        findings.extend(self.orphan_findings(&edges));
+        GraphValidation { findings }
+    }
+    
+    /// Findings for each dependency cycle among valid edges
+    fn cycle_findings(&self, edges: &[&DependencyEdge]) -> Vec<GraphValidationFinding> {
+        let mut adjacency: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
+        for edge in edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+        
+        strongly_connected_components(&self.root_packages.iter().map(|p| p.id).collect::<Vec<_>>(), &adjacency)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let members: HashSet<&PackageId> = component.iter().collect();
+                let through_dev = edges.iter().any(|edge| {
+                    edge.kind == DependencyKind::Dev && members.contains(&edge.from) && members.contains(&edge.to)
+                });
+                let names: Vec<String> = component.iter().map(|id| self.package_label(id)).collect();
+                let (kind, severity, message) = if through_dev {
+                    (GraphFindingKind::DevCycle, GraphFindingSeverity::Info,
+                        format!("Dependency cycle through dev-dependencies: {}", names.join(" -> ")))
+                } else {
+                    (GraphFindingKind::Cycle, GraphFindingSeverity::Warning,
+                        format!("Dependency cycle: {}", names.join(" -> ")))
+                };
+                GraphValidationFinding::new(kind, severity, component, message)
+            })
+            .collect()
+    }
+    
+    /// Findings for packages unreachable from any root
+    fn orphan_findings(&self, edges: &[&DependencyEdge]) -> Vec<GraphValidationFinding> {
+        let mut adjacency: HashMap<PackageId, Vec<PackageId>> = HashMap::new();
+        for edge in edges {
+            adjacency.entry(edge.from).or_default().push(edge.to);
+        }
+        
+        let roots = self.roots(edges);
+        let mut reachable: HashSet<PackageId> = roots.iter().copied().collect();
+        let mut stack = roots;
+        while let Some(id) = stack.pop() {
+            for dependency in adjacency.get(&id).into_iter().flatten() {
+                if reachable.insert(*dependency) {
+                    stack.push(*dependency);
+                }
+            }
+        }
+        
+        self.root_packages.iter()
+            .filter(|package| !reachable.contains(&package.id))
+            .map(|package| GraphValidationFinding::new(
+                GraphFindingKind::OrphanPackage,
+                GraphFindingSeverity::Warning,
+                vec![package.id],
+                format!("{} {} is not reachable from any root package", package.name, package.version),
+            ))
+            .collect()
+    }
+    
+    /// "name version" of a package, or its ID when unknown
+    fn package_label(&self, id: &PackageId) -> String {
+        self.find_package_by_id(id)
+            .map(|p| format!("{} {}", p.name, p.version))
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+/// Strongly connected components of a directed graph (Tarjan), in discovery order
+fn strongly_connected_components(nodes: &[PackageId], adjacency: &HashMap<PackageId, Vec<PackageId>>) -> Vec<Vec<PackageId>> {
+    struct State<'a> {
+        adjacency: &'a HashMap<PackageId, Vec<PackageId>>,
+        next_index: usize,
+        index: HashMap<PackageId, usize>,
+        low_link: HashMap<PackageId, usize>,
+        stack: Vec<PackageId>,
+        on_stack: HashSet<PackageId>,
+        components: Vec<Vec<PackageId>>,
+    }
+    
+    fn visit(state: &mut State<'_>, node: PackageId) {
+        state.index.insert(node, state.next_index);
+        state.low_link.insert(node, state.next_index);
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+        
+        let adjacency = state.adjacency;
+        for &next in adjacency.get(&node).into_iter().flatten() {
+            if !state.index.contains_key(&next) {
+                visit(state, next);
+                let low = state.low_link[&node].min(state.low_link[&next]);
+                state.low_link.insert(node, low);
+            } else if state.on_stack.contains(&next) {
+                let low = state.low_link[&node].min(state.index[&next]);
+                state.low_link.insert(node, low);
+            }
+        }
+        
+        if state.low_link[&node] == state.index[&node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(&member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.reverse();
+            state.components.push(component);
+        }
+    }
+    
+    let mut state = State {
+        adjacency,
+        next_index: 0,
+        index: HashMap::new(),
+        low_link: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for &node in nodes {
+        if !state.index.contains_key(&node) {
+            visit(&mut state, node);
+        }
+    }
+    state.components
+}
+
+/// Kind of graph validation finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphFindingKind {
+    /// Edge referencing a package not in the graph
+    DanglingEdge,
+    /// Two packages sharing an ID
+    DuplicatePackageId,
+    /// Package depending on itself
+    SelfEdge,
+    /// Cycle of normal or build dependencies
+    Cycle,
+    /// Cycle passing through a dev-dependency
+    DevCycle,
+    /// Package unreachable from any root
+    OrphanPackage,
+}
+
+/// Severity of a graph validation finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFindingSeverity {
+    /// Expected structure worth knowing about
+    Info,
+    /// Suspicious structure
+    Warning,
+    /// Inconsistent graph
+    Error,
+}
+
+/// Structural problem found in a dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphValidationFinding {
+    /// Finding kind
+    pub kind: GraphFindingKind,
+    /// Finding severity
+    pub severity: GraphFindingSeverity,
+    /// Packages involved (cycle members in dependency order)
+    pub packages: Vec<PackageId>,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Outcome of [`DependencyGraph::validate`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GraphValidation {
+    /// Findings in detection order
+    pub findings: Vec<GraphValidationFinding>,
+}
+
+impl GraphValidationFinding {
+    /// Create new validation finding
+    pub fn new(kind: GraphFindingKind, severity: GraphFindingSeverity, packages: Vec<PackageId>, message: String) -> Self {
+        Self { kind, severity, packages, message }
+    }
+}
+
+impl GraphValidation {
+    /// Whether the graph has no error findings
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+    
+    /// Error findings
+    pub fn errors(&self) -> impl Iterator<Item = &GraphValidationFinding> {
+        self.findings.iter().filter(|f| f.severity == GraphFindingSeverity::Error)
+    }
+    
+    /// Findings of a kind
+    pub fn of_kind(&self, kind: GraphFindingKind) -> impl Iterator<Item = &GraphValidationFinding> {
+        self.findings.iter().filter(move |f| f.kind == kind)
+    }
+    
+    /// Convert error findings into an error, keeping the graph usable otherwise
+    pub fn into_result(self) -> crate::Result<Self> {
+        if self.is_valid() {
+            return Ok(self);
+        }
+        let errors: Vec<GraphValidationFinding> = self.errors().cloned().collect();
+        Err(crate::AdapterError::GraphValidationFailed {
+            source: anyhow::anyhow!("{}", errors[0].message),
+            findings: errors,
+        })
+    }
+}
+
+impl PackageNode {
+    /// Create an unclassified, unaudited package with a fresh ID
+    ///
+    /// The checksum is taken from a registry source and left empty otherwise.
+    pub fn new(name: String, version: String, source: PackageSource) -> Self {
+        let checksum = match &source {
+            PackageSource::Registry { checksum, .. } => checksum.clone(),
+            _ => String::new(),
+        };
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            version,
+            source,
+            checksum,
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: Vec::new(),
+        }
+    }
+    
+    /// Get the value of a Rust annotation by key
+    pub fn annotation(&self, key: &str) -> Option<&serde_json::Value> {
+        self.annotations.iter().find(|a| a.key == key).map(|a| &a.value)
+    }
+    
+    /// Get a non-empty string annotation by key
+    pub fn annotation_str(&self, key: &str) -> Option<&str> {
+        self.annotation(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+    
+    /// Set a Rust annotation, replacing any existing values for the same key
+    pub fn set_annotation(&mut self, key: &str, value: serde_json::Value) {
+        match self.annotations.iter().position(|a| a.key == key) {
+            Some(index) => {
+                self.annotations[index].value = value;
+                
+                // Keep only the first entry for this key
+                let mut seen = false;
+                self.annotations.retain(|a| a.key != key || !std::mem::replace(&mut seen, true));
+            },
+            None => self.annotations.push(RustAnnotation::new(key.to_string(), value)),
+        }
+    }
+    
+    /// Dependency kind recorded for this package
+    pub fn dependency_kind(&self) -> Option<DependencyKind> {
+        match self.annotation_str(annotation_keys::DEPENDENCY_KIND)? {
+            "normal" => Some(DependencyKind::Normal),
+            "build" => Some(DependencyKind::Build),
+            "dev" => Some(DependencyKind::Dev),
+            _ => None,
+        }
+    }
+    
+    /// Enabled features recorded for this package
+    pub fn features(&self) -> Vec<String> {
+        self.annotation(annotation_keys::FEATURES)
+            .and_then(|v| v.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+    
+    /// Whether this package is a procedural macro crate
+    pub fn is_proc_macro(&self) -> bool {
+        self.annotation(annotation_keys::PROC_MACRO)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+    
+    /// Rust edition declared by this package
+    pub fn edition(&self) -> Option<&str> {
+        self.annotation_str(annotation_keys::EDITION)
+    }
+    
+    /// Whether this package is a member of the analyzed workspace
+    pub fn is_workspace_member(&self) -> bool {
+        self.annotation(annotation_keys::WORKSPACE_MEMBER)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+    
+    /// Whether this package is developed in the workspace or consumed from elsewhere
+    ///
+    /// Graphs without an origin annotation fall back to the workspace
+    /// member annotation.
+    pub fn origin(&self) -> PackageOrigin {
+        match self.annotation_str(annotation_keys::ORIGIN) {
+            Some("first_party") => PackageOrigin::FirstParty,
+            Some(_) => PackageOrigin::ThirdParty,
+            None if self.is_workspace_member() => PackageOrigin::FirstParty,
+            None => PackageOrigin::ThirdParty,
+        }
+    }
+    
+    /// Whether this package is a first-party workspace crate
+    pub fn is_first_party(&self) -> bool {
+        self.origin() == PackageOrigin::FirstParty
+    }
+    
+    /// Record where this package is developed
+    pub fn set_origin(&mut self, origin: PackageOrigin) {
+        self.set_annotation(annotation_keys::ORIGIN, serde_json::Value::String(origin.as_str().to_string()));
+    }
+    
+    /// Distance from the roots recorded by `DependencyGraph::annotate_depths`
+    pub fn depth(&self) -> Option<usize> {
+        self.annotation(annotation_keys::DEPTH)
+            .and_then(|v| v.as_u64())
+            .map(|depth| depth as usize)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl DependencyGraph {
+    /// Serialize the graph to CBOR for compact exchange with the Control Plane
+    ///
+    /// The encoding carries the same fields as the JSON form, including
+    /// `metadata.schema_version`.
+    pub fn to_cbor(&self) -> crate::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(self, &mut bytes)
+            .map_err(|e| crate::AdapterError::Internal {
+                message: "Failed to encode dependency graph as CBOR".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        Ok(bytes)
+    }
+    
+    /// Deserialize a graph previously produced by [`DependencyGraph::to_cbor`]
+    pub fn from_cbor(bytes: &[u8]) -> crate::Result<Self> {
+        let graph: DependencyGraph = ciborium::de::from_reader(bytes)
+            .map_err(|e| crate::AdapterError::MetadataParseError {
+                field: "dependency_graph".to_string(),
+                value: format!("{} bytes", bytes.len()),
+                source: anyhow::anyhow!("Failed to decode CBOR dependency graph: {}", e),
+            })?;
+        
+        if graph.metadata.schema_version.is_empty() {
+            return Err(crate::AdapterError::MetadataParseError {
+                field: "metadata.schema_version".to_string(),
+                value: String::new(),
+                source: anyhow::anyhow!("CBOR dependency graph is missing a schema version"),
+            });
+        }
+        
+        Ok(graph)
     }
 }
 
@@ -312,6 +1046,7 @@ impl Default for GraphMetadata {
             tool_versions: HashMap::new(),
             schema_version: "1.0.0".to_string(),
             offline_mode: false,
+            lockfile_fingerprint: String::new(),
             properties: HashMap::new(),
         }
     }
@@ -326,16 +1061,196 @@ impl RustAnnotation {
             value,
         }
     }
+}
+
+/// Common keys of Rust annotations
+pub mod annotation_keys {
+    pub const FEATURES: &str = "features";
+    pub const DEPENDENCY_KIND: &str = "dependency_kind";
+    pub const TARGET_SPECIFIC: &str = "target_specific";
+    pub const PROC_MACRO: &str = "proc_macro";
+    pub const CATEGORIES: &str = "categories";
+    pub const KEYWORDS: &str = "keywords";
+    pub const EDITION: &str = "edition";
+    pub const RUST_VERSION: &str = "rust_version";
+    pub const DESCRIPTION: &str = "description";
+    pub const HOMEPAGE: &str = "homepage";
+    pub const REPOSITORY: &str = "repository";
+    pub const AUTHORS: &str = "authors";
+    pub const LICENSE: &str = "license";
+    pub const PUBLISHER: &str = "publisher";
+    pub const RISK_SCORE: &str = "risk_score";
+    pub const DOWNLOADS: &str = "downloads";
+    pub const RECENT_DOWNLOADS: &str = "recent_downloads";
+    pub const RELEASES_LAST_YEAR: &str = "releases_last_year";
+    pub const DAYS_SINCE_LAST_RELEASE: &str = "days_since_last_release";
+    pub const UPDATE_LAG: &str = "update_lag";
+    pub const DIRECT_DEPENDENCY: &str = "direct_dependency";
+    pub const WORKSPACE_MEMBER: &str = "workspace_member";
+    pub const ORIGIN: &str = "origin";
+    pub const REQUESTED_FEATURES: &str = "requested_features";
+    pub const LOCKFILES: &str = "lockfiles";
+    pub const DEPTH: &str = "depth";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     
-    /// Common annotation keys
-    pub mod keys {
-        pub const FEATURES: &str = "features";
-        pub const DEPENDENCY_KIND: &str = "dependency_kind";
-        pub const TARGET_SPECIFIC: &str = "target_specific";
-        pub const PROC_MACRO: &str = "proc_macro";
-        pub const CATEGORIES: &str = "categories";
-        pub const KEYWORDS: &str = "keywords";
-        pub const EDITION: &str = "edition";
-        pub const RUST_VERSION: &str = "rust_version";
+    fn package(graph: &mut DependencyGraph, name: &str) -> PackageId {
+        let id = Uuid::new_v4();
+        graph.add_package(PackageNode {
+            id,
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            source: PackageSource::Local { path: format!("../{}", name) },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: vec![],
+        });
+        id
     }
-}
\ No newline at end of file
+    
+    fn edge(graph: &mut DependencyGraph, from: PackageId, to: PackageId, kind: DependencyKind) {
+        graph.add_edge(DependencyEdge { from, to, kind, target: None, optional: false, features: vec![] });
+    }
+    
+    #[test]
+    fn test_validate_structure() {
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        let app = package(&mut graph, "app");
+        let a = package(&mut graph, "a");
+        let b = package(&mut graph, "b");
+        let tests = package(&mut graph, "test-utils");
+        let orphan = package(&mut graph, "orphan");
+        graph.root_packages[0].set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::json!(true));
+        
+        edge(&mut graph, app, a, DependencyKind::Normal);
+        edge(&mut graph, a, b, DependencyKind::Normal);
+        edge(&mut graph, b, a, DependencyKind::Build);
+        edge(&mut graph, app, tests, DependencyKind::Dev);
+        edge(&mut graph, tests, app, DependencyKind::Normal);
+        edge(&mut graph, orphan, orphan, DependencyKind::Normal);
+        
+        let validation = graph.validate();
+        assert!(validation.is_valid());
+        let cycle: Vec<_> = validation.of_kind(GraphFindingKind::Cycle).collect();
+        assert_eq!(cycle.len(), 1);
+        assert_eq!(cycle[0].packages.len(), 2);
+        assert_eq!(validation.of_kind(GraphFindingKind::DevCycle).next().unwrap().severity, GraphFindingSeverity::Info);
+        assert_eq!(validation.of_kind(GraphFindingKind::SelfEdge).count(), 1);
+        assert_eq!(validation.of_kind(GraphFindingKind::OrphanPackage).next().unwrap().packages, vec![orphan]);
+        assert_eq!(graph.dependency_paths(&b, 5), vec![vec![app, a, b]]);
+        assert_eq!(graph.dependency_paths(&app, 5), vec![vec![app]]);
+        assert!(graph.dependency_paths(&orphan, 5).is_empty());
+        
+        edge(&mut graph, app, Uuid::new_v4(), DependencyKind::Normal);
+        let validation = graph.validate();
+        assert_eq!(validation.errors().count(), 1);
+        assert!(matches!(validation.into_result(), Err(crate::AdapterError::GraphValidationFailed { .. })));
+    }
+    
+    #[test]
+    fn test_merge_lockfile_records_provenance() {
+        let mut app = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        let app_root = package(&mut app, "app");
+        let app_serde = package(&mut app, "serde");
+        edge(&mut app, app_root, app_serde, DependencyKind::Normal);
+        app.metadata.lockfile_fingerprint = "app-lock".to_string();
+        
+        let mut tool = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        let tool_root = package(&mut tool, "tool");
+        let tool_serde = package(&mut tool, "serde");
+        edge(&mut tool, tool_root, tool_serde, DependencyKind::Normal);
+        tool.metadata.lockfile_fingerprint = "tool-lock".to_string();
+        
+        let mut merged = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        merged.merge_lockfile(app, "Cargo.lock");
+        merged.merge_lockfile(tool, "tools/gen/Cargo.lock");
+        
+        assert_eq!(merged.root_packages.len(), 3);
+        assert_eq!(merged.metadata.lockfile_fingerprint, "app-lock");
+        assert_eq!(merged.metadata.properties["lockfiles"]["tools/gen/Cargo.lock"], "tool-lock");
+        let serde = merged.find_package("serde", "1.0.0").unwrap();
+        assert_eq!(serde.annotation(annotation_keys::LOCKFILES).unwrap(),
+            &serde_json::json!(["Cargo.lock", "tools/gen/Cargo.lock"]));
+        assert_eq!(merged.get_dependents(&serde.id).len(), 2);
+    }
+    
+    #[test]
+    fn test_sort_canonical_makes_serialization_stable() {
+        let mut graph = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        let app = package(&mut graph, "app");
+        let serde = package(&mut graph, "serde");
+        let log = package(&mut graph, "log");
+        edge(&mut graph, app, serde, DependencyKind::Normal);
+        edge(&mut graph, app, log, DependencyKind::Dev);
+        edge(&mut graph, serde, log, DependencyKind::Normal);
+        graph.edges[0].features = vec!["std".to_string(), "derive".to_string()];
+        for tool in ["cargo", "rustc", "cargo-audit"] {
+            graph.metadata.tool_versions.insert(tool.to_string(), "1.0.0".to_string());
+        }
+        
+        let mut shuffled = graph.clone();
+        shuffled.root_packages.reverse();
+        shuffled.edges.reverse();
+        
+        graph.sort_canonical();
+        shuffled.sort_canonical();
+        let names: Vec<&str> = graph.root_packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "log", "serde"]);
+        assert_eq!((graph.edges[0].to, graph.edges[1].to), (log, serde));
+        assert_eq!(graph.edges[1].features, vec!["derive".to_string(), "std".to_string()]);
+        assert_eq!(serde_json::to_string(&graph).unwrap(), serde_json::to_string(&shuffled).unwrap());
+    }
+    
+    #[test]
+    fn test_depths_and_depth_limited_graph() {
+        let mut graph = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        let app = package(&mut graph, "app");
+        let serde = package(&mut graph, "serde");
+        let derive = package(&mut graph, "serde_derive");
+        let orphan = package(&mut graph, "orphan");
+        edge(&mut graph, app, serde, DependencyKind::Normal);
+        edge(&mut graph, serde, derive, DependencyKind::Normal);
+        edge(&mut graph, app, derive, DependencyKind::Build);
+        graph.root_packages[0].set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::Value::Bool(true));
+        
+        graph.annotate_depths();
+        let depth = |id: PackageId| graph.find_package_by_id(&id).unwrap().depth();
+        assert_eq!((depth(app), depth(serde), depth(derive), depth(orphan)), (Some(0), Some(1), Some(1), None));
+        
+        let limited = graph.within_depth(0);
+        assert_eq!(limited.root_packages.len(), 1);
+        assert!(limited.edges.is_empty());
+        assert_eq!(graph.within_depth(1).edges.len(), 3);
+    }
+    
+    #[test]
+    fn test_graph_scoped_to_dependency_kinds() {
+        let mut graph = DependencyGraph::new("repo".to_string(), "rust".to_string());
+        let app = package(&mut graph, "app");
+        let serde = package(&mut graph, "serde");
+        let cc = package(&mut graph, "cc");
+        let proptest = package(&mut graph, "proptest");
+        let regex = package(&mut graph, "regex");
+        edge(&mut graph, app, serde, DependencyKind::Normal);
+        edge(&mut graph, app, cc, DependencyKind::Build);
+        edge(&mut graph, app, proptest, DependencyKind::Dev);
+        edge(&mut graph, proptest, regex, DependencyKind::Normal);
+        edge(&mut graph, cc, serde, DependencyKind::Normal);
+        graph.root_packages[0].set_annotation(annotation_keys::WORKSPACE_MEMBER, serde_json::Value::Bool(true));
+        
+        let names = |scoped: &DependencyGraph| scoped.root_packages.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        let normal = graph.through_kinds(&[DependencyKind::Normal]);
+        assert_eq!(names(&normal), vec!["app", "serde"]);
+        assert_eq!(normal.edges.len(), 1);
+        
+        // Packages below a dev-dependency follow it in or out of scope
+        let build = graph.through_kinds(&[DependencyKind::Normal, DependencyKind::Build]);
+        assert_eq!(names(&build), vec!["app", "serde", "cc"]);
+        assert_eq!(build.edges.len(), 3);
+        assert_eq!(graph.through_kinds(&[DependencyKind::Normal, DependencyKind::Build, DependencyKind::Dev]).root_packages.len(), 5);
+    }
+}
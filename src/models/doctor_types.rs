@@ -0,0 +1,99 @@
+//! Environment doctor types
+//!
+//! This module defines the result of checking, before any analysis runs,
+//! that the tools, configuration and project files the adapter relies on
+//! are in place.
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one doctor check
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorStatus {
+    /// Everything the check covers is in place
+    Pass,
+    /// Not applicable to this project or configuration
+    Skip,
+    /// Works, but needs attention
+    Warn,
+    /// Operations relying on it will fail
+    Fail,
+}
+
+impl DoctorStatus {
+    /// Status as printed in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Skip => "skip",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+        }
+    }
+}
+
+/// Result of one doctor check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DoctorCheck {
+    /// Check name (e.g., `tool.cargo-audit`, `lockfile`)
+    pub name: String,
+    /// Check outcome
+    pub status: DoctorStatus,
+    /// What was found
+    pub message: String,
+    /// Error code of the underlying adapter error, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Steps to resolve a warning or failure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guidance: Vec<String>,
+}
+
+/// Result of a doctor run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DoctorReport {
+    /// Whether no check failed
+    pub healthy: bool,
+    /// Time the checks ran
+    pub checked_at: String,
+    /// Project root checked
+    pub project_root: String,
+    /// Checks in the order they ran
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorCheck {
+    /// Check with no guidance
+    pub fn new(name: &str, status: DoctorStatus, message: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message,
+            error_code: None,
+            guidance: vec![],
+        }
+    }
+    
+    /// Add a step to resolve the check
+    pub fn with_guidance(mut self, guidance: String) -> Self {
+        self.guidance.push(guidance);
+        self
+    }
+}
+
+impl DoctorReport {
+    /// Report over `checks`
+    pub fn new(project_root: String, checks: Vec<DoctorCheck>) -> Self {
+        Self {
+            healthy: checks.iter().all(|check| check.status != DoctorStatus::Fail),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            project_root,
+            checks,
+        }
+    }
+    
+    /// Number of checks with `status`
+    pub fn count(&self, status: DoctorStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+}
@@ -4,14 +4,100 @@
 //! between epochs, including change classification and priority levels.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use super::dependency_graph::*;
+use super::vendor_types::VendorManifestEntry;
+use crate::utils::{NamePattern, PatternOverrides};
+
+/// Group of drifts not pulled in by any known workspace member
+pub const UNATTRIBUTED_DRIFT_GROUP: &str = "(unattributed)";
+
+/// Group of drifts not owned by any team
+pub const UNOWNED_DRIFT_GROUP: &str = "(unowned)";
+
+/// Version of the drift report format
+///
+/// The major version changes when fields are removed or change meaning;
+/// readers accept any report of the same major version.
+pub const DRIFT_REPORT_SCHEMA_VERSION: &str = "1.0";
+
+/// File holding acknowledged drift items, in the project's epochs directory
+pub const DRIFT_ACKNOWLEDGEMENTS_FILE: &str = "drift-acks.json";
+
+/// Approved dependency state that drift is detected against
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Epoch {
+    /// Epoch identifier
+    pub id: String,
+    /// Project the epoch belongs to
+    pub project_id: String,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+    /// Descriptive metadata
+    #[serde(default)]
+    pub metadata: EpochMetadata,
+    /// Pinned dependency state
+    #[serde(default)]
+    pub dependencies: EpochDependencies,
+    /// Security state at approval time
+    #[serde(default)]
+    pub security: EpochSecurity,
+    /// Approval record
+    #[serde(default)]
+    pub governance: EpochGovernance,
+}
+
+/// Descriptive metadata of an epoch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EpochMetadata {
+    /// Free-form description
+    pub description: Option<String>,
+    /// Additional properties
+    #[serde(default)]
+    pub properties: HashMap<String, serde_json::Value>,
+}
+
+/// Dependency state pinned by an epoch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EpochDependencies {
+    /// Canonical Cargo.lock fingerprint
+    pub lockfile_hash: String,
+    /// SHA-256 of the SBOM generated for the epoch
+    pub sbom_hash: String,
+    /// Pinned versions of TCS packages, keyed by package name
+    #[serde(default)]
+    pub tcs_pins: BTreeMap<String, String>,
+}
+
+/// Security state of an epoch at approval time
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EpochSecurity {
+    /// Advisories open against the pinned dependencies
+    #[serde(default)]
+    pub open_advisories: Vec<String>,
+}
+
+/// Approval record of an epoch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EpochGovernance {
+    /// Reviewer who approved the epoch
+    pub approved_by: Option<String>,
+    /// Approval timestamp (RFC 3339)
+    pub approved_at: Option<String>,
+}
 
 /// Comprehensive drift detection report
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DriftReport {
+    /// Report format version (see `DRIFT_REPORT_SCHEMA_VERSION`)
+    #[serde(default)]
+    pub schema_version: String,
     /// Epoch being compared against
     pub expected_epoch_id: String,
+    /// Canonical Cargo.lock fingerprint of the analyzed dependency state
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
     /// Current analysis timestamp
     pub analysis_timestamp: String,
     /// Detected drift items
@@ -20,11 +106,24 @@ pub struct DriftReport {
     pub summary: DriftSummary,
     /// Impact assessment
     pub impact: DriftImpact,
+    /// Drift items left out because they were acknowledged
+    #[serde(default)]
+    pub acknowledged_drifts: usize,
+    /// Drift items left out because their package lies deeper than the
+    /// transitive depth limit
+    #[serde(default)]
+    pub depth_suppressed_drifts: usize,
+    /// Model the impact was assessed with
+    #[serde(default)]
+    pub impact_model: ImpactModel,
 }
 
 /// Individual drift item detected
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DriftItem {
+    /// Identifier of the change (see `DriftItem::change_id`)
+    #[serde(default)]
+    pub id: String,
     /// Package name
     pub package_name: String,
     /// Previous version (if applicable)
@@ -45,6 +144,12 @@ pub struct DriftItem {
     pub is_high_risk_source_change: bool,
     /// Additional details about the drift
     pub details: Option<String>,
+    /// Workspace members depending on the package
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
+    /// Teams owning the drift
+    #[serde(default)]
+    pub owners: Vec<String>,
 }
 
 /// Type of change detected
@@ -62,6 +167,18 @@ pub enum ChangeType {
     MultipleChanges,
 }
 
+impl std::fmt::Display for ChangeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeType::Addition => write!(f, "addition"),
+            ChangeType::Removal => write!(f, "removal"),
+            ChangeType::VersionChange => write!(f, "version change"),
+            ChangeType::SourceChange => write!(f, "source change"),
+            ChangeType::MultipleChanges => write!(f, "multiple changes"),
+        }
+    }
+}
+
 /// Priority level for drift items
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Priority {
@@ -75,8 +192,34 @@ pub enum Priority {
     Low,
 }
 
-/// Drift summary statistics
+/// Drift items reviewers acknowledged, suppressed from later reports
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DriftAcknowledgements {
+    /// Last update timestamp
+    pub updated_at: String,
+    /// Acknowledged items keyed by drift item ID
+    pub items: BTreeMap<String, DriftAcknowledgement>,
+}
+
+/// An acknowledged drift item
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DriftAcknowledgement {
+    /// Package name
+    pub package_name: String,
+    /// Type of change
+    pub change_type: ChangeType,
+    /// Previous version (if applicable)
+    pub previous_version: Option<String>,
+    /// Current version (if applicable)
+    pub current_version: Option<String>,
+    /// Epoch the acknowledged report compared against
+    pub expected_epoch_id: String,
+    /// Acknowledgement timestamp
+    pub acknowledged_at: String,
+}
+
+/// Drift summary statistics
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct DriftSummary {
     /// Total number of drift items
     pub total_drifts: usize,
@@ -131,7 +274,7 @@ pub enum ImpactLevel {
 }
 
 /// Security impact assessment
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SecurityImpact {
     /// Whether security posture is affected
     pub affected: bool,
@@ -176,7 +319,7 @@ pub enum PerformanceImpact {
 }
 
 /// Compliance impact assessment
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ComplianceImpact {
     /// Whether compliance requirements are affected
     pub compliance_affected: bool,
@@ -207,19 +350,63 @@ pub enum RecommendedTimeline {
 
 /// Epoch comparison configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct DriftDetectionConfig {
     /// Whether to ignore version updates for Mechanical components
     pub ignore_mechanical_version_updates: bool,
     /// Whether to flag source changes as high risk by default
     pub flag_source_changes_high_risk: bool,
-    /// Custom priority overrides for specific packages
-    pub priority_overrides: HashMap<String, Priority>,
+    /// Priority overrides keyed by package name, glob or `/regex/`
+    pub priority_overrides: PatternOverrides<Priority>,
     /// Whether to include dev dependencies in drift detection
     pub include_dev_dependencies: bool,
     /// Whether to include build dependencies in drift detection
     pub include_build_dependencies: bool,
     /// Maximum transitive depth to analyze
     pub max_transitive_depth: Option<usize>,
+    /// Model assessing the impact of the detected drift
+    #[serde(default)]
+    pub impact_model: ImpactModel,
+}
+
+/// Weighted model for assessing drift impact
+///
+/// Each drift scores its classification weight times its change type
+/// weight. Critical and high priority drifts, source changes and TCS drifts
+/// set the overall impact regardless of score; otherwise the total score
+/// is compared with the thresholds. With the default model every drift
+/// scores 1, so thresholds count drifts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ImpactModel {
+    /// Weight of drift in TCS packages
+    pub tcs_weight: f64,
+    /// Weight of drift in Mechanical packages
+    pub mechanical_weight: f64,
+    /// Weight of drift in unclassified packages
+    pub unknown_weight: f64,
+    /// Weight of added packages
+    pub addition_weight: f64,
+    /// Weight of removed packages
+    pub removal_weight: f64,
+    /// Weight of version changes
+    pub version_change_weight: f64,
+    /// Weight of source changes
+    pub source_change_weight: f64,
+    /// Weight of combined version and source changes
+    pub multiple_changes_weight: f64,
+    /// Score above which the overall impact is at least Minor
+    pub minor_threshold: f64,
+    /// Score above which the overall impact is at least Moderate
+    pub moderate_threshold: Option<f64>,
+    /// Score above which the overall impact is at least Major
+    pub major_threshold: Option<f64>,
+    /// Score above which a minor performance impact is expected
+    pub performance_minor_threshold: f64,
+    /// Score above which a moderate performance impact is expected
+    pub performance_moderate_threshold: f64,
+    /// Score above which a significant performance impact is expected
+    pub performance_significant_threshold: f64,
 }
 
 /// Drift detection context
@@ -237,16 +424,324 @@ pub struct DriftDetectionContext {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// Packages pinned by one point of a drift timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochPackageState {
+    /// Epoch ID, or `None` for the current Cargo.lock
+    pub epoch_id: Option<String>,
+    /// Epoch snapshot creation timestamp
+    pub created_at: Option<String>,
+    /// Pinned packages keyed by "name@version"
+    pub packages: BTreeMap<String, VendorManifestEntry>,
+}
+
+/// Drift across a sequence of epochs, from a baseline epoch to Cargo.lock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DriftTimeline {
+    /// Baseline epoch the timeline starts from
+    pub since_epoch_id: String,
+    /// Canonical Cargo.lock fingerprint of the current dependency state
+    pub lockfile_fingerprint: String,
+    /// Timeline generation timestamp
+    pub generated_at: String,
+    /// Points compared against their predecessor, oldest first
+    pub epochs: Vec<TimelineEpoch>,
+    /// Changes of each package, oldest first
+    pub packages: BTreeMap<String, Vec<TimelineChange>>,
+    /// Cumulative statistics over the whole window
+    pub statistics: TimelineStatistics,
+}
+
+/// One point of a drift timeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineEpoch {
+    /// Epoch ID, or `None` for the current Cargo.lock
+    pub epoch_id: Option<String>,
+    /// Epoch snapshot creation timestamp
+    pub created_at: Option<String>,
+    /// Point this one is compared against
+    pub previous_epoch_id: String,
+    /// Number of pinned packages
+    pub total_packages: usize,
+    /// Number of changes relative to the previous point
+    pub changes: usize,
+}
+
+/// Change of a package between two consecutive timeline points
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineChange {
+    /// Epoch that approved the change, or `None` if not yet approved
+    pub approved_in: Option<String>,
+    /// Epoch the change is relative to
+    pub previous_epoch_id: String,
+    /// Type of change
+    pub change_type: ChangeType,
+    /// Previous version (if applicable)
+    pub previous_version: Option<String>,
+    /// New version (if applicable)
+    pub current_version: Option<String>,
+    /// Previous Cargo.lock source string (if applicable)
+    pub previous_source: Option<String>,
+    /// New Cargo.lock source string (if applicable)
+    pub current_source: Option<String>,
+    /// Priority based on the package's current classification
+    pub priority: Priority,
+}
+
+/// Cumulative drift statistics for a timeline
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TimelineStatistics {
+    /// Number of epochs in the window, baseline included
+    pub epochs_covered: usize,
+    /// Total number of changes
+    pub total_changes: usize,
+    /// Number of additions
+    pub additions: usize,
+    /// Number of removals
+    pub removals: usize,
+    /// Number of version changes
+    pub version_changes: usize,
+    /// Number of source changes
+    pub source_changes: usize,
+    /// Number of distinct packages that changed
+    pub packages_changed: usize,
+    /// Number of packages that changed in more than one epoch
+    pub packages_changed_repeatedly: usize,
+    /// Changes in Cargo.lock not approved by any epoch
+    pub unapproved_changes: usize,
+    /// Critical priority changes
+    pub critical_priority: usize,
+}
+
+/// Update proposed in a what-if simulation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProposedUpdate {
+    /// Crate to update
+    pub name: String,
+    /// Exact version to update to
+    pub version: String,
+}
+
+/// Dependency state a set of proposed updates would produce
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpdateSimulation {
+    /// Updates applied
+    pub updates: Vec<ProposedUpdate>,
+    /// Canonical fingerprint of the project's current Cargo.lock
+    pub base_lockfile_fingerprint: String,
+    /// Dependency graph after the updates
+    pub graph: DependencyGraph,
+    /// Drift of the updated Cargo.lock from the current one
+    pub drift_report: DriftReport,
+}
+
+/// Selection of drift items by priority, change, classification and package
+///
+/// Each non-empty criterion must match; within a criterion any entry may.
+#[derive(Debug, Clone, Default)]
+pub struct DriftQuery {
+    /// Priorities to keep
+    pub priorities: Vec<Priority>,
+    /// Change types to keep
+    pub change_types: Vec<ChangeType>,
+    /// Classifications to keep
+    pub classifications: Vec<DriftClassification>,
+    /// Package name patterns to keep (names, globs or `/regex/`)
+    pub packages: Vec<NamePattern>,
+}
+
+/// Classification of a drift item, without its category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftClassification {
+    /// Trust-Critical Software
+    Tcs,
+    /// Mechanical component
+    Mechanical,
+    /// Not classified
+    Unknown,
+}
+
+/// One page of the drift items a query selects
+///
+/// Items are ordered by priority, most urgent first, then canonically, so
+/// the first page always holds the most critical matches. Summary and
+/// impact describe the whole report. In summary-only pages `drifts` is
+/// left out.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DriftReportPage {
+    /// Report format version (see `DRIFT_REPORT_SCHEMA_VERSION`)
+    pub schema_version: String,
+    /// Epoch being compared against
+    pub expected_epoch_id: String,
+    /// Canonical Cargo.lock fingerprint of the analyzed dependency state
+    pub lockfile_fingerprint: String,
+    /// Analysis timestamp
+    pub analysis_timestamp: String,
+    /// Summary statistics of the whole report
+    pub summary: DriftSummary,
+    /// Impact assessment of the whole report
+    pub impact: DriftImpact,
+    /// Drift items left out because they were acknowledged
+    pub acknowledged_drifts: usize,
+    /// Drift items left out because of the transitive depth limit
+    #[serde(default)]
+    pub depth_suppressed_drifts: usize,
+    /// Number of items the query matched
+    pub matching_drifts: usize,
+    /// Index of the first item of the page among the matches
+    pub offset: usize,
+    /// Maximum number of items per page, if paginated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Offset of the next page, if there are more matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+    /// Items of the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drifts: Option<Vec<DriftItem>>,
+}
+
+impl DriftQuery {
+    /// Whether the query selects every drift item
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty() && self.change_types.is_empty() && self.classifications.is_empty() && self.packages.is_empty()
+    }
+    
+    /// Check whether a drift item matches every criterion
+    pub fn matches(&self, drift: &DriftItem) -> bool {
+        (self.priorities.is_empty() || self.priorities.contains(&drift.priority))
+            && (self.change_types.is_empty() || self.change_types.contains(&drift.change_type))
+            && (self.classifications.is_empty() || self.classifications.contains(&DriftClassification::of(&drift.classification)))
+            && (self.packages.is_empty() || self.packages.iter().any(|pattern| pattern.matches(&drift.package_name)))
+    }
+}
+
+impl DriftClassification {
+    /// Kind of a package classification
+    pub fn of(classification: &Classification) -> Self {
+        match classification {
+            Classification::TCS { .. } => Self::Tcs,
+            Classification::Mechanical { .. } => Self::Mechanical,
+            Classification::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl std::str::FromStr for DriftClassification {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tcs" => Ok(Self::Tcs),
+            "mechanical" => Ok(Self::Mechanical),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("Unknown classification '{}'. Valid classifications: tcs, mechanical, unknown", s)),
+        }
+    }
+}
+
+impl std::str::FromStr for Priority {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "critical" => Ok(Self::Critical),
+            "high" => Ok(Self::High),
+            "medium" => Ok(Self::Medium),
+            "low" => Ok(Self::Low),
+            _ => Err(format!("Unknown priority '{}'. Valid priorities: critical, high, medium, low", s)),
+        }
+    }
+}
+
+impl std::str::FromStr for ChangeType {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "addition" | "added" => Ok(Self::Addition),
+            "removal" | "removed" => Ok(Self::Removal),
+            "version-change" | "version" => Ok(Self::VersionChange),
+            "source-change" | "source" => Ok(Self::SourceChange),
+            "multiple-changes" | "multiple" => Ok(Self::MultipleChanges),
+            _ => Err(format!(
+                "Unknown change type '{}'. Valid change types: addition, removal, version-change, source-change, multiple-changes",
+                s
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for ProposedUpdate {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('@') {
+            Some((name, version)) if !name.is_empty() && !version.is_empty() => Ok(Self {
+                name: name.to_string(),
+                version: version.to_string(),
+            }),
+            _ => Err(format!("Invalid update '{}', expected <crate>@<version>", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ProposedUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
 impl DriftReport {
     /// Create new drift report
     pub fn new(expected_epoch_id: String) -> Self {
         Self {
+            schema_version: DRIFT_REPORT_SCHEMA_VERSION.to_string(),
             expected_epoch_id,
+            lockfile_fingerprint: String::new(),
             analysis_timestamp: chrono::Utc::now().to_rfc3339(),
             drifts: Vec::new(),
             summary: DriftSummary::default(),
             impact: DriftImpact::default(),
+            acknowledged_drifts: 0,
+            depth_suppressed_drifts: 0,
+            impact_model: ImpactModel::default(),
+        }
+    }
+    
+    /// Read a report serialized by this or a compatible version
+    ///
+    /// Reports written before the format was versioned have no schema
+    /// version and are read as version 1.
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let report: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let major = |version: &str| version.split('.').next().map(str::to_string);
+        if !report.schema_version.is_empty() && major(&report.schema_version) != major(DRIFT_REPORT_SCHEMA_VERSION) {
+            return Err(format!(
+                "unsupported drift report schema version '{}' (expected {})",
+                report.schema_version, DRIFT_REPORT_SCHEMA_VERSION,
+            ));
         }
+        Ok(report)
+    }
+    
+    /// Set the ID of every drift item from its change
+    pub fn assign_ids(&mut self) {
+        for drift in &mut self.drifts {
+            drift.id = drift.change_id();
+        }
+    }
+    
+    /// Leave out acknowledged items, then recompute summary and impact
+    ///
+    /// An item stays suppressed only while its change is the one that was
+    /// acknowledged: a different version or source gives it a new ID.
+    pub fn suppress_acknowledged(&mut self, acknowledgements: &DriftAcknowledgements) {
+        let before = self.drifts.len();
+        self.drifts.retain(|drift| !acknowledgements.items.contains_key(&drift.id));
+        self.acknowledged_drifts += before - self.drifts.len();
+        self.calculate_summary();
+        self.assess_impact();
     }
     
     /// Add drift item to report
@@ -254,6 +749,16 @@ impl DriftReport {
         self.drifts.push(drift);
     }
     
+    /// Sort drifts by package name, then previous and current version
+    pub fn sort_canonical(&mut self) {
+        self.drifts.sort_by_cached_key(|drift| (
+            drift.package_name.clone(),
+            drift.previous_version.clone(),
+            drift.current_version.clone(),
+            format!("{:?}", drift.change_type),
+        ));
+    }
+    
     /// Calculate summary statistics
     pub fn calculate_summary(&mut self) {
         let mut summary = DriftSummary::default();
@@ -289,7 +794,7 @@ impl DriftReport {
     
     /// Assess impact of detected drift
     pub fn assess_impact(&mut self) {
-        self.impact = DriftImpact::from_drifts(&self.drifts, &self.summary);
+        self.impact = DriftImpact::assess(&self.drifts, &self.summary, &self.impact_model);
     }
     
     /// Get critical drift items
@@ -313,6 +818,74 @@ impl DriftReport {
             .collect()
     }
     
+    /// Group drifts by the workspace members pulling in each package
+    ///
+    /// A drift pulled in by several members is listed under each of them;
+    /// drifts without a known member are grouped under
+    /// [`UNATTRIBUTED_DRIFT_GROUP`].
+    pub fn by_workspace_member(&self) -> BTreeMap<String, Vec<&DriftItem>> {
+        Self::group_by(&self.drifts, |drift| &drift.workspace_members, UNATTRIBUTED_DRIFT_GROUP)
+    }
+    
+    /// Group drifts by owning team
+    ///
+    /// Drifts owned by no team are grouped under [`UNOWNED_DRIFT_GROUP`].
+    pub fn by_team(&self) -> BTreeMap<String, Vec<&DriftItem>> {
+        Self::group_by(&self.drifts, |drift| &drift.owners, UNOWNED_DRIFT_GROUP)
+    }
+    
+    /// Group drifts under each of their keys, or under a fallback group
+    fn group_by<'a>(
+        drifts: &'a [DriftItem],
+        keys: impl Fn(&DriftItem) -> &Vec<String>,
+        fallback: &str,
+    ) -> BTreeMap<String, Vec<&'a DriftItem>> {
+        let mut groups: BTreeMap<String, Vec<&DriftItem>> = BTreeMap::new();
+        for drift in drifts {
+            let keys = keys(drift);
+            if keys.is_empty() {
+                groups.entry(fallback.to_string()).or_default().push(drift);
+            }
+            for key in keys {
+                groups.entry(key.clone()).or_default().push(drift);
+            }
+        }
+        groups
+    }
+    
+    /// Drift items matching `query`, most urgent first
+    pub fn query(&self, query: &DriftQuery) -> Vec<&DriftItem> {
+        let mut drifts: Vec<&DriftItem> = self.drifts.iter().filter(|drift| query.matches(drift)).collect();
+        drifts.sort_by(|a, b| a.priority.cmp(&b.priority));
+        drifts
+    }
+    
+    /// One page of the items matching `query`
+    ///
+    /// Returns up to `limit` matches starting at `offset`, or all from
+    /// `offset` on without a limit. With `summary_only` the page carries
+    /// the counts but no items.
+    pub fn page(&self, query: &DriftQuery, offset: usize, limit: Option<usize>, summary_only: bool) -> DriftReportPage {
+        let matches = self.query(query);
+        let end = limit.map_or(matches.len(), |limit| offset.saturating_add(limit).min(matches.len()));
+        let start = offset.min(end);
+        DriftReportPage {
+            schema_version: self.schema_version.clone(),
+            expected_epoch_id: self.expected_epoch_id.clone(),
+            lockfile_fingerprint: self.lockfile_fingerprint.clone(),
+            analysis_timestamp: self.analysis_timestamp.clone(),
+            summary: self.summary.clone(),
+            impact: self.impact.clone(),
+            acknowledged_drifts: self.acknowledged_drifts,
+            depth_suppressed_drifts: self.depth_suppressed_drifts,
+            matching_drifts: matches.len(),
+            offset,
+            limit,
+            next_offset: (end < matches.len()).then_some(end),
+            drifts: (!summary_only).then(|| matches[start..end].iter().map(|drift| (*drift).clone()).collect()),
+        }
+    }
+    
     /// Check if report has critical issues
     pub fn has_critical_issues(&self) -> bool {
         !self.critical_drifts().is_empty() ||
@@ -320,22 +893,6 @@ impl DriftReport {
     }
 }
 
-impl Default for DriftSummary {
-    fn default() -> Self {
-        Self {
-            total_drifts: 0,
-            additions: 0,
-            removals: 0,
-            version_changes: 0,
-            source_changes: 0,
-            critical_priority: 0,
-            high_priority: 0,
-            tcs_drifts: 0,
-            mechanical_drifts: 0,
-        }
-    }
-}
-
 impl Default for DriftImpact {
     fn default() -> Self {
         Self {
@@ -353,6 +910,7 @@ impl DriftItem {
     /// Create new drift item
     pub fn new(package_name: String, change_type: ChangeType, priority: Priority) -> Self {
         Self {
+            id: String::new(),
             package_name,
             previous_version: None,
             current_version: None,
@@ -363,6 +921,8 @@ impl DriftItem {
             classification: Classification::Unknown,
             is_high_risk_source_change: false,
             details: None,
+            workspace_members: Vec::new(),
+            owners: Vec::new(),
         }
     }
     
@@ -398,6 +958,27 @@ impl DriftItem {
         self
     }
     
+    /// Stable identifier of the change this item describes
+    ///
+    /// Derived from the package, the kind of change and the versions and
+    /// sources on both sides, so the same change gets the same ID in every
+    /// run and any other change of the package gets a different one.
+    pub fn change_id(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            self.package_name.clone(),
+            format!("{:?}", self.change_type),
+            format!("{:?}", self.previous_version),
+            format!("{:?}", self.current_version),
+            serde_json::to_string(&self.previous_source).unwrap_or_default(),
+            serde_json::to_string(&self.current_source).unwrap_or_default(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())[..16].to_string()
+    }
+    
     /// Check if this is a TCS drift
     pub fn is_tcs_drift(&self) -> bool {
         matches!(self.classification, Classification::TCS { .. })
@@ -407,14 +988,57 @@ impl DriftItem {
     pub fn affects_security(&self) -> bool {
         self.is_tcs_drift() || self.is_high_risk_source_change
     }
+    
+    /// Whether the drift is reported however deep its package lies
+    ///
+    /// TCS, high-risk source and critical or high priority drifts are
+    /// never left out for the transitive depth limit.
+    pub fn exempt_from_depth_limit(&self) -> bool {
+        self.affects_security() || matches!(self.priority, Priority::Critical | Priority::High)
+    }
+}
+
+impl DriftAcknowledgements {
+    /// Acknowledge items of a report by ID
+    ///
+    /// Fails without acknowledging anything if an ID is not in the report.
+    pub fn acknowledge(&mut self, report: &DriftReport, ids: &[String]) -> Result<(), String> {
+        let unknown: Vec<&str> = ids.iter()
+            .filter(|id| !report.drifts.iter().any(|drift| &drift.id == *id))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(format!("Drift items not in the report: {}", unknown.join(", ")));
+        }
+        
+        let now = chrono::Utc::now().to_rfc3339();
+        for drift in report.drifts.iter().filter(|drift| ids.contains(&drift.id)) {
+            self.items.insert(drift.id.clone(), DriftAcknowledgement {
+                package_name: drift.package_name.clone(),
+                change_type: drift.change_type.clone(),
+                previous_version: drift.previous_version.clone(),
+                current_version: drift.current_version.clone(),
+                expected_epoch_id: report.expected_epoch_id.clone(),
+                acknowledged_at: now.clone(),
+            });
+        }
+        self.updated_at = now;
+        Ok(())
+    }
 }
 
 impl DriftImpact {
-    /// Create impact assessment from drift items
+    /// Create impact assessment from drift items with the default model
     pub fn from_drifts(drifts: &[DriftItem], summary: &DriftSummary) -> Self {
-        let overall_impact = Self::assess_overall_impact(summary);
+        Self::assess(drifts, summary, &ImpactModel::default())
+    }
+    
+    /// Create impact assessment from drift items with `model`
+    pub fn assess(drifts: &[DriftItem], summary: &DriftSummary, model: &ImpactModel) -> Self {
+        let score = model.score(drifts);
+        let overall_impact = Self::assess_overall_impact(summary, score, model);
         let security_impact = SecurityImpact::from_drifts(drifts);
-        let operational_impact = OperationalImpact::from_drifts(drifts);
+        let operational_impact = OperationalImpact::assess(drifts, score, model);
         let compliance_impact = ComplianceImpact::from_drifts(drifts);
         let recommended_timeline = Self::recommend_timeline(&overall_impact, &security_impact);
         let recommendations = Self::generate_recommendations(&overall_impact, &security_impact, &operational_impact);
@@ -430,20 +1054,22 @@ impl DriftImpact {
     }
     
     /// Assess overall impact level
-    fn assess_overall_impact(summary: &DriftSummary) -> ImpactLevel {
+    fn assess_overall_impact(summary: &DriftSummary, score: f64, model: &ImpactModel) -> ImpactLevel {
+        let exceeds = |threshold: Option<f64>| threshold.is_some_and(|threshold| score > threshold);
+        
         if summary.critical_priority > 0 {
             return ImpactLevel::Critical;
         }
         
-        if summary.high_priority > 0 || summary.source_changes > 0 {
+        if summary.high_priority > 0 || summary.source_changes > 0 || exceeds(model.major_threshold) {
             return ImpactLevel::Major;
         }
         
-        if summary.tcs_drifts > 0 {
+        if summary.tcs_drifts > 0 || exceeds(model.moderate_threshold) {
             return ImpactLevel::Moderate;
         }
         
-        if summary.total_drifts > 10 {
+        if exceeds(Some(model.minor_threshold)) {
             return ImpactLevel::Minor;
         }
         
@@ -495,18 +1121,6 @@ impl DriftImpact {
     }
 }
 
-impl Default for SecurityImpact {
-    fn default() -> Self {
-        Self {
-            affected: false,
-            tcs_components_affected: 0,
-            high_risk_source_changes: 0,
-            attack_vectors: Vec::new(),
-            security_recommendations: Vec::new(),
-        }
-    }
-}
-
 impl SecurityImpact {
     /// Create security impact from drift items
     pub fn from_drifts(drifts: &[DriftItem]) -> Self {
@@ -561,8 +1175,14 @@ impl Default for OperationalImpact {
 }
 
 impl OperationalImpact {
-    /// Create operational impact from drift items
+    /// Create operational impact from drift items with the default model
     pub fn from_drifts(drifts: &[DriftItem]) -> Self {
+        let model = ImpactModel::default();
+        Self::assess(drifts, model.score(drifts), &model)
+    }
+    
+    /// Create operational impact from drift items scoring `score` under `model`
+    pub fn assess(drifts: &[DriftItem], score: f64, model: &ImpactModel) -> Self {
         let version_changes = drifts.iter().any(|d| 
             matches!(d.change_type, ChangeType::VersionChange | ChangeType::MultipleChanges)
         );
@@ -575,11 +1195,11 @@ impl OperationalImpact {
         let runtime_affected = version_changes;
         let compatibility_affected = version_changes;
         
-        let performance_impact = if drifts.len() > 20 {
+        let performance_impact = if score > model.performance_significant_threshold {
             PerformanceImpact::Significant
-        } else if drifts.len() > 10 {
+        } else if score > model.performance_moderate_threshold {
             PerformanceImpact::Moderate
-        } else if drifts.len() > 5 {
+        } else if score > model.performance_minor_threshold {
             PerformanceImpact::Minor
         } else {
             PerformanceImpact::None
@@ -606,18 +1226,6 @@ impl OperationalImpact {
     }
 }
 
-impl Default for ComplianceImpact {
-    fn default() -> Self {
-        Self {
-            compliance_affected: false,
-            affected_frameworks: Vec::new(),
-            license_issues: Vec::new(),
-            audit_implications: Vec::new(),
-            compliance_recommendations: Vec::new(),
-        }
-    }
-}
-
 impl ComplianceImpact {
     /// Create compliance impact from drift items
     pub fn from_drifts(_drifts: &[DriftItem]) -> Self {
@@ -638,10 +1246,122 @@ impl Default for DriftDetectionConfig {
         Self {
             ignore_mechanical_version_updates: false,
             flag_source_changes_high_risk: true,
-            priority_overrides: HashMap::new(),
+            priority_overrides: PatternOverrides::default(),
             include_dev_dependencies: false,
             include_build_dependencies: true,
             max_transitive_depth: Some(10),
+            impact_model: ImpactModel::default(),
+        }
+    }
+}
+
+impl Default for ImpactModel {
+    fn default() -> Self {
+        Self {
+            tcs_weight: 1.0,
+            mechanical_weight: 1.0,
+            unknown_weight: 1.0,
+            addition_weight: 1.0,
+            removal_weight: 1.0,
+            version_change_weight: 1.0,
+            source_change_weight: 1.0,
+            multiple_changes_weight: 1.0,
+            minor_threshold: 10.0,
+            moderate_threshold: None,
+            major_threshold: None,
+            performance_minor_threshold: 5.0,
+            performance_moderate_threshold: 10.0,
+            performance_significant_threshold: 20.0,
         }
     }
 }
+
+impl ImpactModel {
+    /// Score of one drift item
+    pub fn weight(&self, drift: &DriftItem) -> f64 {
+        let classification = match drift.classification {
+            Classification::TCS { .. } => self.tcs_weight,
+            Classification::Mechanical { .. } => self.mechanical_weight,
+            Classification::Unknown => self.unknown_weight,
+        };
+        let change_type = match drift.change_type {
+            ChangeType::Addition => self.addition_weight,
+            ChangeType::Removal => self.removal_weight,
+            ChangeType::VersionChange => self.version_change_weight,
+            ChangeType::SourceChange => self.source_change_weight,
+            ChangeType::MultipleChanges => self.multiple_changes_weight,
+        };
+        classification * change_type
+    }
+    
+    /// Total score of drift items
+    pub fn score(&self, drifts: &[DriftItem]) -> f64 {
+        drifts.iter().map(|drift| self.weight(drift)).sum()
+    }
+    
+    /// Check that weights are non-negative and thresholds ascend
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        let weights = [
+            ("tcs_weight", self.tcs_weight),
+            ("mechanical_weight", self.mechanical_weight),
+            ("unknown_weight", self.unknown_weight),
+            ("addition_weight", self.addition_weight),
+            ("removal_weight", self.removal_weight),
+            ("version_change_weight", self.version_change_weight),
+            ("source_change_weight", self.source_change_weight),
+            ("multiple_changes_weight", self.multiple_changes_weight),
+        ];
+        if let Some((name, _)) = weights.iter().find(|(_, weight)| weight.is_nan() || *weight < 0.0) {
+            return Err(format!("{} must be a non-negative number", name));
+        }
+        
+        let levels: Vec<f64> = [Some(self.minor_threshold), self.moderate_threshold, self.major_threshold]
+            .into_iter().flatten().collect();
+        if levels.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err("Impact thresholds must ascend from minor to major".to_string());
+        }
+        if self.performance_minor_threshold > self.performance_moderate_threshold
+            || self.performance_moderate_threshold > self.performance_significant_threshold {
+            return Err("Performance thresholds must ascend from minor to significant".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl TimelineStatistics {
+    /// Compute cumulative statistics from per-package changes
+    pub fn from_changes(epochs_covered: usize, packages: &BTreeMap<String, Vec<TimelineChange>>) -> Self {
+        let mut statistics = Self {
+            epochs_covered,
+            packages_changed: packages.len(),
+            ..Self::default()
+        };
+        
+        for changes in packages.values() {
+            let mut epochs: Vec<&Option<String>> = changes.iter().map(|c| &c.approved_in).collect();
+            epochs.dedup();
+            if epochs.len() > 1 {
+                statistics.packages_changed_repeatedly += 1;
+            }
+            
+            for change in changes {
+                statistics.total_changes += 1;
+                match change.change_type {
+                    ChangeType::Addition => statistics.additions += 1,
+                    ChangeType::Removal => statistics.removals += 1,
+                    ChangeType::VersionChange => statistics.version_changes += 1,
+                    ChangeType::SourceChange => statistics.source_changes += 1,
+                    ChangeType::MultipleChanges => {},
+                }
+                if change.approved_in.is_none() {
+                    statistics.unapproved_changes += 1;
+                }
+                if change.priority == Priority::Critical {
+                    statistics.critical_priority += 1;
+                }
+            }
+        }
+        
+        statistics
+    }
+}
@@ -0,0 +1,92 @@
+//! Progress event types
+//!
+//! This module defines the events streamed as NDJSON while a command
+//! runs, so orchestrators such as the Control Plane can show live status
+//! instead of waiting for the final report. Each line is one
+//! [`EventRecord`]; the `event` field names its kind.
+
+use serde::{Deserialize, Serialize};
+use super::audit_types::Severity;
+use super::drift_types::{ChangeType, Priority};
+
+/// One line of the events stream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventRecord {
+    /// Emission timestamp
+    pub timestamp: String,
+    /// Event payload
+    #[serde(flatten)]
+    pub event: AdapterEvent,
+}
+
+/// Progress or result of a running command
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AdapterEvent {
+    /// An operation (`parse`, `audit`, `sbom`, ...) started
+    StageStarted {
+        /// Operation name
+        stage: String,
+    },
+    /// An operation finished
+    StageFinished {
+        /// Operation name
+        stage: String,
+        /// `ok` or the error code of the failure
+        outcome: String,
+        /// Duration in milliseconds
+        duration_ms: u64,
+    },
+    /// The audit reported a finding
+    FindingEmitted {
+        /// Advisory ID
+        id: String,
+        /// Affected package
+        package_name: String,
+        /// Locked version of the affected package, if known
+        package_version: Option<String>,
+        /// Finding severity
+        severity: Severity,
+    },
+    /// Drift from the approved epoch was detected
+    DriftDetected {
+        /// Drifted package
+        package_name: String,
+        /// Kind of change
+        change_type: ChangeType,
+        /// Drift priority
+        priority: Priority,
+        /// Version in the epoch
+        previous_version: Option<String>,
+        /// Version in Cargo.lock
+        current_version: Option<String>,
+    },
+    /// A vendored package or artifact failed verification
+    VerificationFailed {
+        /// Package or artifact path that failed
+        subject: String,
+        /// Why verification failed
+        reason: String,
+    },
+    /// The command finished; always the last event
+    Result {
+        /// Command name, as typed on the command line
+        command: String,
+        /// Whether the command succeeded
+        success: bool,
+        /// Error message of a failed command
+        error: Option<String>,
+        /// Files the command wrote
+        artifacts: Vec<String>,
+    },
+}
+
+impl EventRecord {
+    /// Stamp an event with the current time
+    pub fn now(event: AdapterEvent) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event,
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Policy facts types
+//!
+//! This module defines a flat, policy-neutral view of everything the
+//! adapter knows about a project: packages, edges, classification, audit
+//! findings and vendor state. The document is shaped to be consumed as
+//! `input` by external policy engines such as OPA/Rego or CUE; it carries
+//! facts only and no verdicts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use super::audit_types::AdvisoryApplicability;
+
+/// Version of the policy facts document layout
+pub const POLICY_FACTS_SCHEMA_VERSION: &str = "1.0";
+
+/// Policy facts document for a single project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolicyFacts {
+    /// Document layout version
+    pub schema_version: String,
+    /// Generation timestamp
+    pub generated_at: String,
+    /// Project-level facts
+    pub project: ProjectFacts,
+    /// Packages keyed by `name@version`
+    pub packages: BTreeMap<String, PackageFacts>,
+    /// Dependency edges between package keys
+    pub edges: Vec<EdgeFacts>,
+    /// Security findings
+    pub findings: Vec<FindingFacts>,
+    /// Audit execution facts (absent when no audit was run)
+    pub audit: Option<AuditRunFacts>,
+    /// Vendor snapshot facts (absent when no epoch was requested)
+    pub vendor: Option<VendorFacts>,
+    /// TCS packages missing cargo-vet criteria the project policy requires
+    #[serde(default)]
+    pub audit_criteria_gaps: Vec<AuditCriteriaGapFacts>,
+    /// Version pinning of TCS dependencies declared by workspace packages
+    #[serde(default)]
+    pub tcs_pinning: Vec<PinningFacts>,
+    /// Pre-computed counts for simple policies
+    pub summary: FactsSummary,
+}
+
+/// Project-level facts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectFacts {
+    /// Project identifier
+    pub id: String,
+    /// Ecosystem name
+    pub ecosystem: String,
+    /// Canonical Cargo.lock fingerprint
+    pub lockfile_fingerprint: String,
+    /// Whether the facts were gathered in offline mode
+    pub offline_mode: bool,
+}
+
+/// Facts about a single package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageFacts {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Source kind ("registry", "git", "local")
+    pub source: String,
+    /// Registry or repository URL, or local path
+    pub source_location: String,
+    /// Git revision for git sources
+    pub source_rev: Option<String>,
+    /// Package checksum
+    pub checksum: String,
+    /// Classification ("tcs", "mechanical", "unknown")
+    pub classification: String,
+    /// Classification category, if classified
+    pub category: Option<String>,
+    /// Origin ("first_party" for workspace members, "third_party")
+    #[serde(default)]
+    pub origin: String,
+    /// Audit status ("audited", "exempted", "unaudited")
+    pub audit_status: String,
+    /// Audit criteria for cargo-vet audits
+    pub audit_criteria: Option<String>,
+    /// Exemption expiry for exempted packages
+    pub exemption_expires: Option<String>,
+    /// Whether the package is a procedural macro
+    pub proc_macro: bool,
+    /// Keys of the packages this package depends on
+    pub dependencies: Vec<String>,
+    /// Identifiers of findings affecting this package
+    pub findings: Vec<String>,
+    /// Rust annotations by key
+    pub annotations: BTreeMap<String, serde_json::Value>,
+}
+
+/// A dependency edge between two package keys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EdgeFacts {
+    /// Dependent package key
+    pub from: String,
+    /// Dependency package key
+    pub to: String,
+    /// Dependency kind ("normal", "build", "dev")
+    pub kind: String,
+    /// Target-specific dependency (if applicable)
+    pub target: Option<String>,
+    /// Whether the dependency is optional
+    pub optional: bool,
+}
+
+/// A security finding
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FindingFacts {
+    /// Finding identifier
+    pub id: String,
+    /// Affected package name
+    pub package: String,
+    /// Keys (`name@version`) of the graph's packages the finding applies to
+    #[serde(default)]
+    pub packages: Vec<String>,
+    /// Severity ("critical", "high", "medium", "low", "info")
+    pub severity: String,
+    /// CVSS score (if available)
+    pub cvss_score: Option<f64>,
+    /// Patched versions
+    pub patched_versions: Vec<String>,
+    /// Whether the finding applies to the locked versions ("applicable", "not_applicable", "unknown")
+    #[serde(default)]
+    pub applicability: AdvisoryApplicability,
+    /// Smallest patched version clearing the finding
+    #[serde(default)]
+    pub minimal_safe_version: Option<String>,
+    /// Finding source
+    pub source: String,
+    /// Whether the finding affects a TCS package
+    pub affects_tcs: bool,
+}
+
+/// Facts about the audit run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditRunFacts {
+    /// Audit timestamp
+    pub timestamp: String,
+    /// Tool versions used
+    pub tool_versions: BTreeMap<String, String>,
+    /// Exit codes from tools
+    pub exit_codes: BTreeMap<String, i32>,
+    /// Warning types raised during execution
+    pub warnings: Vec<String>,
+}
+
+/// Facts about the vendor snapshot pinned to an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorFacts {
+    /// Epoch ID
+    pub epoch_id: String,
+    /// Whether the snapshot verified against the vendored sources
+    pub verified: bool,
+    /// Verification failure, if any
+    pub error: Option<String>,
+    /// Whether the snapshot was taken from the current Cargo.lock
+    pub lockfile_matches: bool,
+    /// Number of vendored packages
+    pub total_packages: usize,
+    /// Content digest of the vendor directory
+    pub content_digest: String,
+    /// Snapshot creation timestamp
+    pub created_at: Option<String>,
+}
+
+/// Cargo-vet criteria a TCS package lacks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditCriteriaGapFacts {
+    /// Package key (`name@version`)
+    pub package: String,
+    /// TCS category the requirement applies to
+    pub category: String,
+    /// Criteria required by the project policy
+    pub required: Vec<String>,
+    /// Criteria met by the package's audit, including implied ones
+    pub met: Vec<String>,
+    /// Required criteria not met
+    pub missing: Vec<String>,
+}
+
+/// How tightly a version requirement pins a dependency
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionPinning {
+    /// A single version (`=1.2.3`)
+    Exact,
+    /// Patch updates only (`~1.2.3`)
+    Tilde,
+    /// Semver-compatible updates (`1.2.3`, `^1.2.3`)
+    Caret,
+    /// Any other bounded range (`>=1.2, <1.5`, `=1.2`)
+    Range,
+    /// Any version, or any version of a major or minor (`*`, `1.*`)
+    Wildcard,
+}
+
+/// Version pinning of a TCS dependency declared by a workspace package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PinningFacts {
+    /// Depended-on crate
+    pub crate_name: String,
+    /// Workspace package declaring the dependency
+    pub declared_by: String,
+    /// Declared requirement (`*` when none is declared)
+    pub requirement: String,
+    /// How tightly the requirement pins the version
+    pub pinning: VersionPinning,
+    /// Key of the locked package satisfying the requirement
+    pub locked: Option<String>,
+    /// Whether `cargo update` would move the lock to a newer release
+    /// (absent when the crate's releases are unknown)
+    pub update_available: Option<bool>,
+    /// Newest release the requirement admits, if newer than the locked one
+    pub update_version: Option<String>,
+}
+
+/// Pre-computed counts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FactsSummary {
+    /// Number of packages
+    pub total_packages: usize,
+    /// Number of first-party workspace packages
+    #[serde(default)]
+    pub first_party_packages: usize,
+    /// Number of TCS packages held to audit requirements
+    pub tcs_packages: usize,
+    /// Number of unaudited TCS packages held to audit requirements
+    pub unaudited_tcs_packages: usize,
+    /// Number of TCS packages missing required audit criteria
+    #[serde(default)]
+    pub criteria_gap_packages: usize,
+    /// Number of TCS dependency declarations with wildcard requirements
+    #[serde(default)]
+    pub wildcard_tcs_dependencies: usize,
+    /// Number of TCS dependency declarations `cargo update` would move
+    #[serde(default)]
+    pub updatable_tcs_dependencies: usize,
+    /// Number of packages from git sources
+    pub git_packages: usize,
+    /// Number of findings by severity
+    pub findings_by_severity: BTreeMap<String, usize>,
+}
+
+impl PolicyFacts {
+    /// Key used for a package in `packages` and `edges`
+    pub fn package_key(name: &str, version: &str) -> String {
+        format!("{}@{}", name, version)
+    }
+}
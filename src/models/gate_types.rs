@@ -0,0 +1,37 @@
+//! Gate evaluation types
+//!
+//! This module defines the pass/fail verdict of evaluating the configured
+//! gating thresholds against previously generated reports.
+
+use serde::{Deserialize, Serialize};
+
+/// Verdict of a gate evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GateReport {
+    /// Whether every check passed
+    pub passed: bool,
+    /// Evaluation timestamp
+    pub evaluated_at: String,
+    /// Canonical Cargo.lock fingerprint of the reports evaluated
+    pub lockfile_fingerprint: Option<String>,
+    /// Checks of the configured thresholds, in configuration order
+    pub checks: Vec<GateCheck>,
+}
+
+/// Result of checking one threshold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GateCheck {
+    /// Configuration key of the threshold (e.g., `max_findings.critical`)
+    pub rule: String,
+    /// Whether the threshold was met
+    pub passed: bool,
+    /// Why the check passed or failed
+    pub reason: String,
+}
+
+impl GateReport {
+    /// Checks that failed
+    pub fn failures(&self) -> Vec<&GateCheck> {
+        self.checks.iter().filter(|check| !check.passed).collect()
+    }
+}
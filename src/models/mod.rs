@@ -12,6 +12,22 @@ pub mod sbom_types;
 pub mod drift_types;
 pub mod config_types;
 pub mod project_types;
+pub mod risk_types;
+pub mod facts_types;
+pub mod build_execution_types;
+pub mod version_skew_types;
+pub mod badge_types;
+pub mod universal_epoch_types;
+pub mod org_types;
+pub mod review_types;
+pub mod gate_types;
+pub mod attribution_types;
+pub mod artifact_types;
+pub mod classifier_bench_types;
+pub mod event_types;
+pub mod doctor_types;
+pub mod bundle_types;
+pub(crate) mod ordering;
 
 // Re-export commonly used types
 pub use dependency_graph::*;
@@ -21,4 +37,19 @@ pub use vendor_types::*;
 pub use sbom_types::*;
 pub use drift_types::*;
 pub use config_types::*;
-pub use project_types::*;
\ No newline at end of file
+pub use project_types::*;
+pub use risk_types::*;
+pub use facts_types::*;
+pub use build_execution_types::*;
+pub use version_skew_types::*;
+pub use badge_types::*;
+pub use universal_epoch_types::*;
+pub use org_types::*;
+pub use review_types::*;
+pub use gate_types::*;
+pub use attribution_types::*;
+pub use artifact_types::*;
+pub use classifier_bench_types::*;
+pub use event_types::*;
+pub use doctor_types::*;
+pub use bundle_types::*;
\ No newline at end of file
@@ -0,0 +1,45 @@
+//! Canonical ordering of emitted collections
+//!
+//! Graphs, reports and SBOMs are sorted before they are returned so that
+//! two runs over the same inputs serialize identically. Entities sort by
+//! name, version and identifier. Relations (edges, SBOM relationships)
+//! sort by the positions of the entities they connect, since their
+//! identifiers are random and would reorder them on every run.
+
+use serde::{Serialize, Serializer};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Positions of identifiers in a sorted entity list
+pub(crate) struct Ranks<K>(HashMap<K, usize>);
+
+impl<K: Hash + Eq> Ranks<K> {
+    /// Ranks in iteration order; a repeated identifier keeps its first position
+    pub(crate) fn new(ids: impl IntoIterator<Item = K>) -> Self {
+        let mut ranks = HashMap::new();
+        for (index, id) in ids.into_iter().enumerate() {
+            ranks.entry(id).or_insert(index);
+        }
+        Self(ranks)
+    }
+    
+    /// Position of an identifier; unknown identifiers rank after all known ones
+    pub(crate) fn get<Q>(&self, id: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.get(id).copied().unwrap_or(usize::MAX)
+    }
+}
+
+/// Serialize a map with its keys in sorted order
+pub(crate) fn sorted_map<S, K, V>(map: &HashMap<K, V>, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
@@ -0,0 +1,92 @@
+//! Organization rollup types
+//!
+//! This module defines the organization-level report aggregating the
+//! dependency graphs and audit findings of several projects.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use super::audit_types::Severity;
+use super::dependency_graph::TcsCategory;
+
+/// Rollup report across the projects of an organization
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgRollupReport {
+    /// Organization name
+    pub organization: String,
+    /// Report generation timestamp
+    pub generated_at: String,
+    /// Per-project summaries, in configuration order
+    pub projects: Vec<OrgProjectSummary>,
+    /// Third-party dependencies of all projects, once per name and version
+    pub dependencies: Vec<OrgDependency>,
+    /// TCS components used by more than one project
+    pub shared_tcs_components: Vec<OrgDependency>,
+    /// Audit findings with the projects they were reported for, most severe first
+    pub findings: Vec<OrgFinding>,
+    /// Aggregate statistics
+    pub statistics: OrgRollupStatistics,
+}
+
+/// Summary of one project in the rollup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgProjectSummary {
+    /// Project identifier
+    pub id: String,
+    /// Project name
+    pub name: String,
+    /// Canonical Cargo.lock fingerprint the project was analyzed at
+    pub lockfile_fingerprint: Option<String>,
+    /// Number of third-party dependencies
+    pub dependencies: usize,
+    /// Number of TCS dependencies
+    pub tcs_dependencies: usize,
+    /// Number of audit findings by severity
+    pub findings_by_severity: BTreeMap<String, usize>,
+    /// Why the project could not be analyzed, if it failed
+    pub error: Option<String>,
+}
+
+/// A dependency (name and version) and the projects using it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgDependency {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// TCS category, when classified as TCS
+    pub tcs_category: Option<TcsCategory>,
+    /// Identifiers of the projects using the dependency
+    pub projects: Vec<String>,
+}
+
+/// An audit finding and the projects it was reported for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgFinding {
+    /// Finding identifier (CVE, RUSTSEC, GHSA)
+    pub id: String,
+    /// Package name affected
+    pub package_name: String,
+    /// Severity level
+    pub severity: Severity,
+    /// Whether the finding affects TCS components
+    pub affects_tcs: bool,
+    /// Identifiers of the affected projects
+    pub projects: Vec<String>,
+}
+
+/// Aggregate statistics of a rollup
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OrgRollupStatistics {
+    /// Number of projects analyzed successfully
+    pub projects_analyzed: usize,
+    /// Number of projects that failed to analyze
+    pub projects_failed: usize,
+    /// Number of unique third-party dependencies (name and version)
+    pub unique_dependencies: usize,
+    /// Number of unique TCS dependencies
+    pub unique_tcs_dependencies: usize,
+    /// Number of unique findings
+    pub unique_findings: usize,
+    /// Number of findings reported for more than one project
+    pub shared_findings: usize,
+}
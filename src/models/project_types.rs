@@ -4,8 +4,10 @@
 //! project configuration, and project-specific settings.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use super::cargo_types::UpdateLag;
+use super::dependency_graph::{Classification, DependencyGraph, TcsCategory};
 
 /// Project representation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -32,6 +34,9 @@ pub struct Project {
     pub alerting: ProjectAlerting,
     /// Project metadata
     pub metadata: ProjectMetadata,
+    /// Team ownership of workspace members and crates
+    #[serde(default)]
+    pub owners: ProjectOwners,
 }
 
 /// Project paths configuration
@@ -69,7 +74,7 @@ pub struct ProjectSecurity {
 }
 
 /// Project TCS configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ProjectTcs {
     /// Cryptographic components
     pub crypto: Vec<String>,
@@ -94,6 +99,9 @@ pub struct ProjectTcs {
 pub struct ProjectPolicy {
     /// Whether TCS components require audit
     pub tcs_requires_audit: bool,
+    /// Cargo-vet criteria required of TCS components, keyed by TCS category (e.g., `crypto`)
+    #[serde(default)]
+    pub audit_criteria: BTreeMap<String, Vec<String>>,
     /// Whether mechanical components require scan
     pub mechanical_requires_scan: bool,
     /// Whether git dependencies are allowed
@@ -107,7 +115,7 @@ pub struct ProjectPolicy {
 }
 
 /// Project alerting configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ProjectAlerting {
     /// Critical CVE alert recipients
     pub critical_cve_to: Vec<String>,
@@ -125,8 +133,30 @@ pub struct ProjectAlerting {
     pub verification_failure_to: Vec<String>,
 }
 
+/// Team ownership configured under `[project.owners.<team>]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct ProjectOwners {
+    /// Ownership entries keyed by team name
+    pub teams: BTreeMap<String, TeamOwnership>,
+}
+
+/// Workspace members and crates owned by one team
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TeamOwnership {
+    /// Workspace members owned by the team (names, globs or `/regex/`)
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// Dependencies owned by the team whichever member pulls them in
+    #[serde(default)]
+    pub crates: Vec<String>,
+    /// Reviewers the team's drift is routed to
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
 /// Project metadata
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ProjectMetadata {
     /// Project description
     pub description: Option<String>,
@@ -226,12 +256,32 @@ pub struct ProjectAnalysis {
     pub git_dependencies: usize,
     /// Local dependencies found
     pub local_dependencies: usize,
+    /// Update lag statistics per classification (`tcs`, `mechanical`, `unknown`)
+    #[serde(default)]
+    pub update_lag: BTreeMap<String, UpdateLagStats>,
     /// Analysis metadata
     pub metadata: AnalysisMetadata,
 }
 
+/// Aggregated update lag of the packages of one classification
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UpdateLagStats {
+    /// Packages with known update lag
+    pub packages: usize,
+    /// Packages with a newer release
+    pub outdated: usize,
+    /// Packages at least one major version behind
+    pub major_behind: usize,
+    /// Mean number of newer releases
+    pub mean_releases_behind: f64,
+    /// Mean days behind the latest release, over packages with release dates
+    pub mean_days_behind: Option<f64>,
+    /// Largest number of days behind the latest release
+    pub max_days_behind: Option<i64>,
+}
+
 /// Analysis metadata
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct AnalysisMetadata {
     /// Tool versions used
     pub tool_versions: HashMap<String, String>,
@@ -286,6 +336,7 @@ impl Project {
             policy: ProjectPolicy::default(),
             alerting: ProjectAlerting::default(),
             metadata: ProjectMetadata::default(),
+            owners: ProjectOwners::default(),
         }
     }
     
@@ -384,18 +435,35 @@ impl Default for ProjectSecurity {
     }
 }
 
-impl Default for ProjectTcs {
-    fn default() -> Self {
-        Self {
-            crypto: Vec::new(),
-            auth: Vec::new(),
-            serialization: Vec::new(),
-            transport: Vec::new(),
-            database: Vec::new(),
-            random: Vec::new(),
-            build_time_execution: Vec::new(),
-            custom: HashMap::new(),
+impl ProjectTcs {
+    /// Find the TCS category a crate is listed under, with the list it came from
+    ///
+    /// Built-in lists are checked first; custom lists are checked in name
+    /// order so a crate listed under several custom categories resolves
+    /// deterministically.
+    pub fn category_for(&self, crate_name: &str) -> Option<(TcsCategory, String)> {
+        let builtin = [
+            ("crypto", &self.crypto, TcsCategory::Cryptography),
+            ("auth", &self.auth, TcsCategory::Authentication),
+            ("serialization", &self.serialization, TcsCategory::Serialization),
+            ("transport", &self.transport, TcsCategory::Transport),
+            ("database", &self.database, TcsCategory::Database),
+            ("random", &self.random, TcsCategory::Random),
+            ("build_time_execution", &self.build_time_execution, TcsCategory::BuildTimeExecution),
+        ];
+        
+        for (list, crates, category) in builtin {
+            if crates.iter().any(|c| c == crate_name) {
+                return Some((category, format!("tcs.{}", list)));
+            }
         }
+        
+        let mut custom: Vec<_> = self.custom.iter().collect();
+        custom.sort_by(|a, b| a.0.cmp(b.0));
+        
+        custom.into_iter()
+            .find(|(_, crates)| crates.iter().any(|c| c == crate_name))
+            .map(|(name, _)| (TcsCategory::Custom(name.clone()), format!("tcs.custom.{}", name)))
     }
 }
 
@@ -403,6 +471,7 @@ impl Default for ProjectPolicy {
     fn default() -> Self {
         Self {
             tcs_requires_audit: true,
+            audit_criteria: BTreeMap::new(),
             mechanical_requires_scan: true,
             allow_git_dependencies: false,
             max_transitive_depth: Some(10),
@@ -412,35 +481,6 @@ impl Default for ProjectPolicy {
     }
 }
 
-impl Default for ProjectAlerting {
-    fn default() -> Self {
-        Self {
-            critical_cve_to: Vec::new(),
-            high_cve_to: Vec::new(),
-            medium_cve_to: Vec::new(),
-            low_cve_to: Vec::new(),
-            drift_detected_to: Vec::new(),
-            audit_failure_to: Vec::new(),
-            verification_failure_to: Vec::new(),
-        }
-    }
-}
-
-impl Default for ProjectMetadata {
-    fn default() -> Self {
-        Self {
-            description: None,
-            version: None,
-            homepage: None,
-            documentation: None,
-            tags: Vec::new(),
-            language: None,
-            license: None,
-            properties: HashMap::new(),
-        }
-    }
-}
-
 impl ProjectAnalysis {
     /// Create new project analysis
     pub fn new(project: Project) -> Self {
@@ -452,10 +492,31 @@ impl ProjectAnalysis {
             mechanical_dependencies: 0,
             git_dependencies: 0,
             local_dependencies: 0,
+            update_lag: BTreeMap::new(),
             metadata: AnalysisMetadata::default(),
         }
     }
     
+    /// Aggregate the update lag annotated on a graph's packages
+    pub fn record_update_lag(&mut self, graph: &DependencyGraph) {
+        let mut lags: BTreeMap<String, Vec<UpdateLag>> = BTreeMap::new();
+        for package in &graph.root_packages {
+            let Some(lag) = UpdateLag::from_package(package) else {
+                continue;
+            };
+            let classification = match package.classification {
+                Classification::TCS { .. } => "tcs",
+                Classification::Mechanical { .. } => "mechanical",
+                Classification::Unknown => "unknown",
+            };
+            lags.entry(classification.to_string()).or_default().push(lag);
+        }
+        
+        self.update_lag = lags.into_iter()
+            .map(|(classification, lags)| (classification, UpdateLagStats::from_lags(&lags)))
+            .collect();
+    }
+    
     /// Get dependency statistics
     pub fn dependency_stats(&self) -> DependencyStats {
         DependencyStats {
@@ -493,17 +554,6 @@ impl Default for ConfigFileMetadata {
     }
 }
 
-impl Default for AnalysisMetadata {
-    fn default() -> Self {
-        Self {
-            tool_versions: HashMap::new(),
-            analysis_duration_ms: 0,
-            offline_mode: false,
-            warnings: Vec::new(),
-        }
-    }
-}
-
 impl AnalysisWarning {
     /// Create new analysis warning
     pub fn new(warning_type: String, message: String, severity: WarningSeverity) -> Self {
@@ -522,6 +572,25 @@ impl AnalysisWarning {
     }
 }
 
+impl UpdateLagStats {
+    /// Aggregate the lag of a set of packages
+    pub fn from_lags(lags: &[UpdateLag]) -> Self {
+        if lags.is_empty() {
+            return Self::default();
+        }
+        
+        let days: Vec<i64> = lags.iter().filter_map(|l| l.days_behind_latest).collect();
+        Self {
+            packages: lags.len(),
+            outdated: lags.iter().filter(|l| l.is_outdated()).count(),
+            major_behind: lags.iter().filter(|l| l.major_behind > 0).count(),
+            mean_releases_behind: lags.iter().map(|l| l.releases_behind as f64).sum::<f64>() / lags.len() as f64,
+            mean_days_behind: (!days.is_empty()).then(|| days.iter().sum::<i64>() as f64 / days.len() as f64),
+            max_days_behind: days.iter().max().copied(),
+        }
+    }
+}
+
 /// Dependency statistics
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DependencyStats {
@@ -0,0 +1,118 @@
+//! Review decision types
+//!
+//! This module defines the decisions reviewers record for drift items and
+//! audit findings before the vendor directory is pinned to an epoch.
+//! Decisions are tied to the Cargo.lock they were made against;
+//! `vendor snapshot` ignores decisions made against another Cargo.lock.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// File holding review decisions, in the project's epochs directory
+pub const REVIEW_DECISIONS_FILE: &str = "review.json";
+
+/// Review decisions for the current Cargo.lock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewDecisions {
+    /// Canonical Cargo.lock fingerprint the decisions were made against
+    pub lockfile_fingerprint: String,
+    /// Last update timestamp
+    pub updated_at: String,
+    /// Decisions keyed by item key (see `ReviewDecisions::drift_key` and siblings)
+    pub decisions: BTreeMap<String, ReviewDecision>,
+}
+
+/// Decision on a single review item
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReviewDecision {
+    /// Kind of item reviewed
+    pub kind: ReviewItemKind,
+    /// Outcome of the review
+    pub status: ReviewStatus,
+    /// Reviewer identity
+    pub reviewer: String,
+    /// Decision timestamp
+    pub decided_at: String,
+    /// Free-form note
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Kind of review item
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewItemKind {
+    /// Change of a package since the base epoch
+    Drift,
+    /// Security finding
+    Finding,
+}
+
+/// Outcome of a review
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    /// Reviewed and accepted
+    Approved,
+    /// Reviewed and not accepted; blocks the epoch snapshot
+    Rejected,
+}
+
+impl ReviewDecisions {
+    /// Create empty decisions for a Cargo.lock
+    pub fn new(lockfile_fingerprint: String) -> Self {
+        Self {
+            lockfile_fingerprint,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            decisions: BTreeMap::new(),
+        }
+    }
+    
+    /// Key of a drift item
+    pub fn drift_key(package_name: &str) -> String {
+        format!("drift:{}", package_name)
+    }
+    
+    /// Key of a finding item
+    pub fn finding_key(finding_id: &str, package_name: &str) -> String {
+        format!("finding:{}:{}", finding_id, package_name)
+    }
+    
+    /// Record a decision, replacing any earlier one for the item
+    pub fn decide(&mut self, key: String, kind: ReviewItemKind, status: ReviewStatus, reviewer: &str) {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.decisions.insert(key, ReviewDecision {
+            kind,
+            status,
+            reviewer: reviewer.to_string(),
+            decided_at: now.clone(),
+            note: None,
+        });
+        self.updated_at = now;
+    }
+    
+    /// Withdraw the decision on an item
+    pub fn clear(&mut self, key: &str) {
+        if self.decisions.remove(key).is_some() {
+            self.updated_at = chrono::Utc::now().to_rfc3339();
+        }
+    }
+    
+    /// Decision status of an item, if decided
+    pub fn status(&self, key: &str) -> Option<ReviewStatus> {
+        self.decisions.get(key).map(|decision| decision.status)
+    }
+    
+    /// Keys of rejected items
+    pub fn rejected(&self) -> Vec<&str> {
+        self.decisions.iter()
+            .filter(|(_, decision)| decision.status == ReviewStatus::Rejected)
+            .map(|(key, _)| key.as_str())
+            .collect()
+    }
+    
+    /// Number of approved items
+    pub fn approved(&self) -> usize {
+        self.decisions.values().filter(|decision| decision.status == ReviewStatus::Approved).count()
+    }
+}
@@ -0,0 +1,107 @@
+//! Risk indicator types
+//!
+//! This module defines informational per-package risk scores and
+//! their aggregation. Scores summarize gathered facts only; they are
+//! not policy decisions, which remain with the Control Plane.
+
+use serde::{Deserialize, Serialize};
+
+/// Notice attached to every risk score and summary
+pub const RISK_INFORMATIONAL_NOTICE: &str =
+    "Informational risk indicator derived from collected facts; not a policy decision";
+
+/// Informational risk score for a single package
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RiskScore {
+    /// Aggregate score from 0.0 (lowest) to 100.0 (highest)
+    pub score: f64,
+    /// Contributing factors
+    pub factors: Vec<RiskFactor>,
+    /// Always true; marks the score as informational
+    pub informational: bool,
+}
+
+/// A single fact contributing to a risk score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RiskFactor {
+    /// Factor name (e.g., "classification", "source")
+    pub name: String,
+    /// Observed value
+    pub value: String,
+    /// Points contributed to the score
+    pub contribution: f64,
+}
+
+/// Risk score entry for a package in a summary
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageRisk {
+    /// Package name
+    pub package_name: String,
+    /// Package version
+    pub package_version: String,
+    /// Aggregate score
+    pub score: f64,
+}
+
+/// Summary of risk scores across a dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RiskSummary {
+    /// Informational notice
+    pub notice: String,
+    /// Number of packages scored
+    pub package_count: usize,
+    /// Average score across packages
+    pub average_score: f64,
+    /// Highest score across packages
+    pub max_score: f64,
+    /// Highest-scoring packages, in descending order
+    pub highest: Vec<PackageRisk>,
+}
+
+impl RiskScore {
+    /// Create a score from contributing factors, capped at 100
+    pub fn from_factors(factors: Vec<RiskFactor>) -> Self {
+        let score = factors.iter().map(|f| f.contribution).sum::<f64>().min(100.0);
+        
+        Self {
+            score,
+            factors,
+            informational: true,
+        }
+    }
+}
+
+impl RiskFactor {
+    /// Create new risk factor
+    pub fn new(name: &str, value: String, contribution: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            contribution,
+        }
+    }
+}
+
+impl RiskSummary {
+    /// Summarize package scores, keeping the `limit` highest entries
+    pub fn from_scores(mut scores: Vec<PackageRisk>, limit: usize) -> Self {
+        let package_count = scores.len();
+        let total: f64 = scores.iter().map(|s| s.score).sum();
+        let max_score = scores.iter().map(|s| s.score).fold(0.0, f64::max);
+        
+        scores.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.package_name.cmp(&b.package_name))
+        });
+        scores.truncate(limit);
+        
+        Self {
+            notice: RISK_INFORMATIONAL_NOTICE.to_string(),
+            package_count,
+            average_score: if package_count == 0 { 0.0 } else { total / package_count as f64 },
+            max_score,
+            highest: scores,
+        }
+    }
+}
@@ -6,16 +6,163 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use super::dependency_graph::*;
+use super::ordering::Ranks;
 
 /// SBOM format options
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SbomFormat {
-    /// SPDX 2.3 JSON format
+    /// SPDX JSON format (version selected by `spdx_version`)
     SpdxJson,
-    /// CycloneDX 1.4 JSON format
+    /// CycloneDX JSON format (version selected by `cyclonedx_version`)
     CycloneDxJson,
 }
 
+/// CycloneDX specification version
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum CycloneDxVersion {
+    /// CycloneDX 1.4
+    #[default]
+    #[serde(rename = "1.4")]
+    V1_4,
+    /// CycloneDX 1.5 (adds lifecycles and formulation)
+    #[serde(rename = "1.5")]
+    V1_5,
+    /// CycloneDX 1.6
+    #[serde(rename = "1.6")]
+    V1_6,
+}
+
+impl CycloneDxVersion {
+    /// Version as written in `specVersion`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V1_4 => "1.4",
+            Self::V1_5 => "1.5",
+            Self::V1_6 => "1.6",
+        }
+    }
+}
+
+/// SPDX specification version
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum SpdxVersion {
+    /// SPDX 2.3
+    #[default]
+    #[serde(rename = "2.3")]
+    V2_3,
+    /// SPDX 3.0 (JSON-LD element graph)
+    #[serde(rename = "3.0")]
+    V3_0,
+}
+
+impl SpdxVersion {
+    /// Version as written in `spdxVersion`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V2_3 => "SPDX-2.3",
+            Self::V3_0 => "SPDX-3.0",
+        }
+    }
+}
+
+/// Named SBOM scope profile
+///
+/// A scope selects the dependency edges the SBOM follows from the
+/// workspace members; packages reached only through other edges are left
+/// out of the document.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SbomScope {
+    /// Normal dependencies only: what release artifacts ship
+    Distribution,
+    /// Normal and build dependencies: what building from source runs
+    Development,
+    /// Everything reachable, including dev-dependencies
+    Full,
+}
+
+impl SbomScope {
+    /// Dependency edges followed in this scope
+    pub fn dependency_kinds(&self) -> &'static [DependencyKind] {
+        match self {
+            Self::Distribution => &[DependencyKind::Normal],
+            Self::Development => &[DependencyKind::Normal, DependencyKind::Build],
+            Self::Full => &[DependencyKind::Normal, DependencyKind::Build, DependencyKind::Dev],
+        }
+    }
+    
+    /// Scope name as used in configuration and on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Distribution => "distribution",
+            Self::Development => "development",
+            Self::Full => "full",
+        }
+    }
+}
+
+impl std::str::FromStr for SbomScope {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "distribution" => Ok(Self::Distribution),
+            "development" => Ok(Self::Development),
+            "full" => Ok(Self::Full),
+            _ => Err(format!("Unknown SBOM scope '{}'. Valid scopes: distribution, development, full", s)),
+        }
+    }
+}
+
+/// Byte encoding of a written SBOM document
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SbomEncoding {
+    /// JSON, available for every format
+    #[default]
+    Json,
+    /// XML, available for CycloneDX
+    Xml,
+    /// Tag-value, available for SPDX 2.3
+    TagValue,
+}
+
+impl SbomEncoding {
+    /// Encoding implied by a file name (`.xml`, `.spdx`), JSON otherwise
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_ascii_lowercase()).unwrap_or_default();
+        if name.ends_with(".xml") {
+            Self::Xml
+        } else if name.ends_with(".spdx") {
+            Self::TagValue
+        } else {
+            Self::Json
+        }
+    }
+
+    /// Encoding name as used in configuration and on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Xml => "xml",
+            Self::TagValue => "tag-value",
+        }
+    }
+}
+
+impl std::str::FromStr for SbomEncoding {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "xml" => Ok(Self::Xml),
+            "tag-value" | "tv" => Ok(Self::TagValue),
+            _ => Err(format!("Unknown SBOM encoding '{}'. Valid encodings: json, xml, tag-value", s)),
+        }
+    }
+}
+
 /// SBOM generation configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SbomConfig {
@@ -35,16 +182,35 @@ pub struct SbomConfig {
     pub author: String,
     /// Document creation timestamp
     pub created_at: String,
+    /// CycloneDX specification version to emit
+    #[serde(default)]
+    pub cyclonedx_version: CycloneDxVersion,
+    /// SPDX specification version to emit
+    #[serde(default)]
+    pub spdx_version: SpdxVersion,
+    /// Scope profile; when unset, the include flags filter packages instead
+    #[serde(default)]
+    pub scope: Option<SbomScope>,
+    /// Embed TCS classification and audit status of packages, and their
+    /// counts, as properties (off keeps the SBOM policy-neutral)
+    #[serde(default)]
+    pub include_security_posture: bool,
+    /// CPE vendor of each crate, keyed by crate name; CPE identifiers are
+    /// only emitted for crates listed here
+    #[serde(default)]
+    pub cpe_vendors: HashMap<String, String>,
 }
 
 /// SPDX document structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxDocument {
     /// SPDX version
     pub spdx_version: String,
     /// Data license
     pub data_license: String,
     /// SPDX identifier
+    #[serde(rename = "SPDXID")]
     pub spdx_id: String,
     /// Document name
     pub name: String,
@@ -60,6 +226,7 @@ pub struct SpdxDocument {
 
 /// SPDX creation information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxCreationInfo {
     /// Creation timestamp
     pub created: String,
@@ -67,43 +234,67 @@ pub struct SpdxCreationInfo {
     pub creators: Vec<String>,
     /// License list version
     pub license_list_version: String,
+    /// Creation comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 /// SPDX package information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxPackage {
     /// SPDX identifier
+    #[serde(rename = "SPDXID")]
     pub spdx_id: String,
     /// Package name
     pub name: String,
     /// Package version
+    #[serde(rename = "versionInfo")]
     pub version: String,
-    /// Package download location
+    /// Package download location (`NOASSERTION` when unknown)
+    #[serde(serialize_with = "serialize_noassertion")]
     pub download_location: Option<String>,
     /// Files analyzed flag
     pub files_analyzed: bool,
     /// License conclusions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub license_concluded: Option<String>,
     /// License declared
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub license_declared: Option<String>,
     /// License comments
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub license_comments: Option<String>,
     /// Copyright text
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub copyright_text: Option<String>,
     /// Package summary
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
     /// Package description
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Package homepage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// Package supplier (e.g., "Person: Jane Doe")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<String>,
     /// Source information
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub source_info: Option<String>,
     /// Package checksums
     pub checksums: Vec<SpdxChecksum>,
     /// External references
     pub external_refs: Vec<SpdxExternalReference>,
+    /// Package comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
 }
 
 /// SPDX checksum information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxChecksum {
     /// Checksum algorithm
     pub algorithm: String,
@@ -113,6 +304,7 @@ pub struct SpdxChecksum {
 
 /// SPDX external reference
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxExternalReference {
     /// Reference category
     pub reference_category: String,
@@ -121,11 +313,13 @@ pub struct SpdxExternalReference {
     /// Reference locator
     pub reference_locator: String,
     /// Reference comments
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 }
 
 /// SPDX relationship
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct SpdxRelationship {
     /// SPDX element ID
     pub spdx_element_id: String,
@@ -134,11 +328,13 @@ pub struct SpdxRelationship {
     /// Relationship type
     pub relationship_type: String,
     /// Relationship comment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 }
 
 /// CycloneDX document structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CycloneDxDocument {
     /// BOM format version
     pub bom_format: String,
@@ -152,40 +348,120 @@ pub struct CycloneDxDocument {
     pub components: Vec<CycloneDxComponent>,
     /// Dependencies
     pub dependencies: Vec<CycloneDxDependency>,
+    /// How the described components were produced (1.5+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formulation: Option<Vec<CycloneDxFormula>>,
 }
 
 /// CycloneDX metadata
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CycloneDxMetadata {
     /// Component information
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub component: Option<CycloneDxComponent>,
     /// BOM timestamp
     pub timestamp: String,
     /// Tools used
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<CycloneDxTool>>,
     /// Authors
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub authors: Option<Vec<CycloneDxAuthor>>,
+    /// BOM-level properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<CycloneDxProperty>>,
+    /// Product lifecycle phases the BOM describes (1.5+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifecycles: Option<Vec<CycloneDxLifecycle>>,
+}
+
+/// CycloneDX lifecycle phase (1.5+)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CycloneDxLifecycle {
+    /// Phase name (e.g., "build")
+    pub phase: String,
+}
+
+/// CycloneDX formula describing how components were produced (1.5+)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CycloneDxFormula {
+    /// Formula reference
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    /// Workflows of the formula
+    pub workflows: Vec<CycloneDxWorkflow>,
+}
+
+/// CycloneDX workflow (1.5+)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxWorkflow {
+    /// Workflow reference
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    /// Unique identifier of the workflow instance
+    pub uid: String,
+    /// Workflow name
+    pub name: String,
+    /// Task types performed (e.g., "build")
+    pub task_types: Vec<String>,
+    /// Workflow properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Vec<CycloneDxProperty>>,
 }
 
 /// CycloneDX component
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CycloneDxComponent {
     /// Component type
     pub r#type: String,
+    /// Component reference used by the dependency graph
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    pub bom_ref: Option<String>,
     /// Component name
     pub name: String,
     /// Component version
     pub version: String,
+    /// Package URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    /// Component description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Component supplier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supplier: Option<CycloneDxOrganizationalEntity>,
+    /// Common Platform Enumeration identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpe: Option<String>,
     /// Component scope
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<String>,
     /// Component hashes
     pub hashes: Vec<CycloneDxHash>,
     /// Component licenses
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub licenses: Option<Vec<CycloneDxLicenseChoice>>,
     /// Component external references
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub external_references: Option<Vec<CycloneDxExternalReference>>,
     /// Component properties
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<Vec<CycloneDxProperty>>,
+    /// Subcomponents, e.g. the first-party crates of the project root component
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<CycloneDxComponent>>,
+}
+
+/// CycloneDX organizational entity (supplier, manufacturer)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CycloneDxOrganizationalEntity {
+    /// Entity name
+    pub name: String,
+    /// Entity URLs
+    pub url: Vec<String>,
 }
 
 /// CycloneDX hash
@@ -202,7 +478,7 @@ pub struct CycloneDxHash {
 #[serde(untagged)]
 pub enum CycloneDxLicenseChoice {
     /// License expression
-    Expression(String),
+    Expression { expression: String },
     /// License with ID
     License { license: CycloneDxLicense },
 }
@@ -211,12 +487,16 @@ pub enum CycloneDxLicenseChoice {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CycloneDxLicense {
     /// License ID
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     /// License name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// License text
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
     /// License URL
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
@@ -228,6 +508,7 @@ pub struct CycloneDxExternalReference {
     /// Reference URL
     pub url: String,
     /// Reference comment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
 }
 
@@ -242,9 +523,10 @@ pub struct CycloneDxProperty {
 
 /// CycloneDX dependency
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct CycloneDxDependency {
     /// Dependency reference
-    pub ref: String,
+    pub r#ref: String,
     /// Dependency depends on
     pub depends_on: Vec<String>,
 }
@@ -253,12 +535,14 @@ pub struct CycloneDxDependency {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CycloneDxTool {
     /// Tool vendor
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vendor: Option<String>,
     /// Tool name
     pub name: String,
     /// Tool version
     pub version: String,
     /// Tool hashes
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hashes: Option<Vec<CycloneDxHash>>,
 }
 
@@ -268,6 +552,7 @@ pub struct CycloneDxAuthor {
     /// Author name
     pub name: String,
     /// Author email
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
 }
 
@@ -295,6 +580,11 @@ impl Default for SbomConfig {
             namespace: None,
             author: "Rust Ecosystem Adapter".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            cyclonedx_version: CycloneDxVersion::default(),
+            spdx_version: SpdxVersion::default(),
+            scope: None,
+            include_security_posture: false,
+            cpe_vendors: HashMap::new(),
         }
     }
 }
@@ -323,6 +613,393 @@ impl SpdxDocument {
     pub fn add_relationship(&mut self, relationship: SpdxRelationship) {
         self.relationships.push(relationship);
     }
+    
+    /// Sort packages by name, version and SPDX ID, and relationships by the packages they relate
+    pub fn sort_canonical(&mut self) {
+        self.packages.sort_by(|a, b| (&a.name, &a.version, &a.spdx_id).cmp(&(&b.name, &b.version, &b.spdx_id)));
+        let ranks = Ranks::new(self.packages.iter().map(|package| package.spdx_id.clone()));
+        self.relationships.sort_by_cached_key(|relationship| (
+            ranks.get(relationship.spdx_element_id.as_str()),
+            relationship.spdx_element_id.clone(),
+            ranks.get(relationship.related_spdx_element.as_str()),
+            relationship.related_spdx_element.clone(),
+            relationship.relationship_type.clone(),
+        ));
+    }
+
+    /// Render the document as an SPDX 3.0 JSON-LD element graph
+    ///
+    /// Identifiers become IRIs under the document namespace, creators and
+    /// suppliers become agents, and licenses become license expression
+    /// elements linked by relationships. SPDX 2.3 relationship types are
+    /// mapped to their 3.0 names, flipping inverse types; types without an
+    /// equivalent become `other` with the original type in the comment.
+    pub fn to_spdx3(&self) -> serde_json::Value {
+        use serde_json::{json, Value};
+
+        let iri = |id: &str| format!("{}#{}", self.document_namespace, id);
+        let mut graph = Vec::new();
+        let mut elements = Vec::new();
+        let mut agents = HashMap::new();
+        let mut licenses = HashMap::new();
+        // (from, type, comment) -> targets
+        type RelationshipKey<'a> = (String, &'a str, Option<String>);
+        let mut relationships: Vec<(RelationshipKey, Vec<String>)> = Vec::new();
+        let mut relate = |from: String, relationship_type: &'static str, to: String, comment: Option<String>| {
+            let key = (from, relationship_type, comment);
+            match relationships.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, targets)) => targets.push(to),
+                None => relationships.push((key, vec![to])),
+            }
+        };
+
+        let mut created_by = Vec::new();
+        let mut created_using = Vec::new();
+        for creator in &self.creation_info.creators {
+            if let Some((kind, id)) = spdx3_agent(&self.document_namespace, creator, &mut graph, &mut agents) {
+                if kind == "Tool" { created_using.push(id) } else { created_by.push(id) }
+            }
+        }
+        let created = chrono::DateTime::parse_from_rfc3339(&self.creation_info.created)
+            .map(|created| created.with_timezone(&chrono::Utc).format("%Y-%m-%dT%H:%M:%SZ").to_string())
+            .unwrap_or_else(|_| self.creation_info.created.clone());
+        let mut creation_info = json!({
+            "type": "CreationInfo",
+            "@id": SPDX3_CREATION_INFO,
+            "specVersion": SPDX3_SPEC_VERSION,
+            "created": created,
+            "createdBy": created_by,
+        });
+        if !created_using.is_empty() {
+            creation_info["createdUsing"] = json!(created_using);
+        }
+        if let Some(comment) = &self.creation_info.comment {
+            creation_info["comment"] = json!(comment);
+        }
+        graph.insert(0, creation_info);
+
+        for package in &self.packages {
+            let id = iri(&package.spdx_id);
+            let mut element = json!({
+                "type": "software_Package",
+                "spdxId": id,
+                "creationInfo": SPDX3_CREATION_INFO,
+                "name": package.name,
+                "software_packageVersion": package.version,
+            });
+            let optional = [
+                ("software_downloadLocation", &package.download_location),
+                ("software_homePage", &package.homepage),
+                ("software_copyrightText", &package.copyright_text),
+                ("software_sourceInfo", &package.source_info),
+                ("summary", &package.summary),
+                ("description", &package.description),
+                ("comment", &package.comment),
+            ];
+            for (key, value) in optional {
+                if let Some(value) = value.as_deref().filter(|v| !matches!(*v, "NOASSERTION" | "NONE")) {
+                    element[key] = json!(value);
+                }
+            }
+
+            if let Some(supplier) = &package.supplier {
+                if let Some((_, agent)) = spdx3_agent(&self.document_namespace, supplier, &mut graph, &mut agents) {
+                    element["suppliedBy"] = json!(agent);
+                }
+            }
+
+            if !package.checksums.is_empty() {
+                element["verifiedUsing"] = package.checksums.iter()
+                    .map(|checksum| json!({
+                        "type": "Hash",
+                        "algorithm": checksum.algorithm.to_lowercase().replace("sha3-", "sha3_").replace('-', ""),
+                        "hashValue": checksum.checksum_value,
+                    }))
+                    .collect();
+            }
+
+            let (identifiers, references): (Vec<_>, Vec<_>) = package.external_refs.iter()
+                .partition(|r| matches!(r.reference_type.as_str(), "cpe23Type" | "purl"));
+            if !identifiers.is_empty() {
+                element["externalIdentifier"] = identifiers.iter()
+                    .map(|r| json!({
+                        "type": "ExternalIdentifier",
+                        "externalIdentifierType": if r.reference_type == "purl" { "packageUrl" } else { "cpe23" },
+                        "identifier": r.reference_locator,
+                    }))
+                    .collect();
+            }
+            if !references.is_empty() {
+                element["externalRef"] = references.iter()
+                    .map(|r| {
+                        let reference_type = if matches!(r.reference_type.as_str(), "git" | "repository" | "vcs") { "vcs" } else { "other" };
+                        let mut reference = json!({ "type": "ExternalRef", "externalRefType": reference_type, "locator": [r.reference_locator] });
+                        if let Some(comment) = &r.comment {
+                            reference["comment"] = json!(comment);
+                        }
+                        reference
+                    })
+                    .collect();
+            }
+
+            for (relationship_type, expression) in [("hasDeclaredLicense", &package.license_declared), ("hasConcludedLicense", &package.license_concluded)] {
+                if let Some(expression) = expression.as_deref().filter(|e| !matches!(*e, "NOASSERTION" | "NONE")) {
+                    let license = spdx3_license(&self.document_namespace, expression, &mut graph, &mut licenses);
+                    relate(id.clone(), relationship_type, license, None);
+                }
+            }
+
+            elements.push(id);
+            graph.push(element);
+        }
+
+        let document_id = iri(&self.spdx_id);
+        let mut root_elements = Vec::new();
+        for relationship in &self.relationships {
+            let (relationship_type, reversed, comment) = match SPDX3_RELATIONSHIP_TYPES.iter().find(|(name, _, _)| *name == relationship.relationship_type) {
+                Some((_, relationship_type, reversed)) => (*relationship_type, *reversed, relationship.comment.clone()),
+                None => ("other", false, Some(match &relationship.comment {
+                    Some(comment) => format!("SPDX 2.3 relationship {}: {}", relationship.relationship_type, comment),
+                    None => format!("SPDX 2.3 relationship {}", relationship.relationship_type),
+                })),
+            };
+            let (from, to) = match reversed {
+                false => (iri(&relationship.spdx_element_id), iri(&relationship.related_spdx_element)),
+                true => (iri(&relationship.related_spdx_element), iri(&relationship.spdx_element_id)),
+            };
+            if from == document_id && relationship_type == "describes" {
+                root_elements.push(to.clone());
+            }
+            relate(from, relationship_type, to, comment);
+        }
+
+        for (index, ((from, relationship_type, comment), to)) in relationships.into_iter().enumerate() {
+            let id = iri(&format!("SPDXRef-Relationship-{}", index + 1));
+            let mut element = json!({
+                "type": "Relationship",
+                "spdxId": id,
+                "creationInfo": SPDX3_CREATION_INFO,
+                "from": from,
+                "relationshipType": relationship_type,
+                "to": to,
+            });
+            if let Some(comment) = comment {
+                element["comment"] = json!(comment);
+            }
+            elements.push(id);
+            graph.push(element);
+        }
+        let data_license = spdx3_license(&self.document_namespace, &self.data_license, &mut graph, &mut licenses);
+        elements.extend(licenses.into_values().filter(|id| *id != data_license));
+
+        let mut document = json!({
+            "type": "SpdxDocument",
+            "spdxId": document_id,
+            "creationInfo": SPDX3_CREATION_INFO,
+            "name": self.name,
+            "dataLicense": data_license,
+            "profileConformance": ["core", "software", "simpleLicensing"],
+            "element": elements,
+        });
+        if !root_elements.is_empty() {
+            document["rootElement"] = json!(root_elements);
+        }
+        graph.push(document);
+
+        json!({
+            "@context": SPDX3_CONTEXT,
+            "@graph": Value::Array(graph),
+        })
+    }
+
+    /// Render the document in the SPDX 2.3 tag-value format
+    ///
+    /// The document header comes first, then each package with its
+    /// checksums and external references, then the relationships.
+    /// Multi-line values are wrapped in `<text>` tags.
+    pub fn to_tag_value(&self) -> String {
+        let mut out = String::new();
+        push_tag(&mut out, "SPDXVersion", &self.spdx_version);
+        push_tag(&mut out, "DataLicense", &self.data_license);
+        push_tag(&mut out, "SPDXID", &self.spdx_id);
+        push_tag(&mut out, "DocumentName", &self.name);
+        push_tag(&mut out, "DocumentNamespace", &self.document_namespace);
+        for creator in &self.creation_info.creators {
+            push_tag(&mut out, "Creator", creator);
+        }
+        push_tag(&mut out, "Created", &self.creation_info.created);
+        push_tag(&mut out, "LicenseListVersion", &self.creation_info.license_list_version);
+        if let Some(comment) = &self.creation_info.comment {
+            push_tag(&mut out, "CreatorComment", comment);
+        }
+
+        for package in &self.packages {
+            out.push('\n');
+            push_tag(&mut out, "PackageName", &package.name);
+            push_tag(&mut out, "SPDXID", &package.spdx_id);
+            push_tag(&mut out, "PackageVersion", &package.version);
+            if let Some(supplier) = &package.supplier {
+                push_tag(&mut out, "PackageSupplier", supplier);
+            }
+            push_tag(&mut out, "PackageDownloadLocation", package.download_location.as_deref().unwrap_or("NOASSERTION"));
+            push_tag(&mut out, "FilesAnalyzed", if package.files_analyzed { "true" } else { "false" });
+            for checksum in &package.checksums {
+                push_tag(&mut out, "PackageChecksum", &format!("{}: {}", checksum.algorithm, checksum.checksum_value));
+            }
+            let optional_tags = [
+                ("PackageHomePage", &package.homepage),
+                ("PackageSourceInfo", &package.source_info),
+                ("PackageLicenseConcluded", &package.license_concluded),
+                ("PackageLicenseDeclared", &package.license_declared),
+                ("PackageLicenseComments", &package.license_comments),
+                ("PackageCopyrightText", &package.copyright_text),
+                ("PackageSummary", &package.summary),
+                ("PackageDescription", &package.description),
+                ("PackageComment", &package.comment),
+            ];
+            for (tag, value) in optional_tags {
+                if let Some(value) = value {
+                    push_tag(&mut out, tag, value);
+                }
+            }
+            for reference in &package.external_refs {
+                push_tag(&mut out, "ExternalRef", &format!(
+                    "{} {} {}",
+                    reference.reference_category, reference.reference_type, reference.reference_locator,
+                ));
+                if let Some(comment) = &reference.comment {
+                    push_tag(&mut out, "ExternalRefComment", comment);
+                }
+            }
+        }
+
+        if !self.relationships.is_empty() {
+            out.push('\n');
+        }
+        for relationship in &self.relationships {
+            push_tag(&mut out, "Relationship", &format!(
+                "{} {} {}",
+                relationship.spdx_element_id, relationship.relationship_type, relationship.related_spdx_element,
+            ));
+            if let Some(comment) = &relationship.comment {
+                push_tag(&mut out, "RelationshipComment", comment);
+            }
+        }
+        out
+    }
+}
+
+/// JSON-LD context of SPDX 3.0 documents
+const SPDX3_CONTEXT: &str = "https://spdx.org/rdf/3.0.1/spdx-context.jsonld";
+
+/// SPDX 3.0 release written in `specVersion`
+const SPDX3_SPEC_VERSION: &str = "3.0.1";
+
+/// Blank node shared by every element's creation information
+const SPDX3_CREATION_INFO: &str = "_:creationinfo";
+
+/// SPDX 2.3 relationship types with their 3.0 name and whether the direction flips
+const SPDX3_RELATIONSHIP_TYPES: &[(&str, &str, bool)] = &[
+    ("DESCRIBES", "describes", false),
+    ("DESCRIBED_BY", "describes", true),
+    ("CONTAINS", "contains", false),
+    ("CONTAINED_BY", "contains", true),
+    ("DEPENDS_ON", "dependsOn", false),
+    ("DEPENDENCY_OF", "dependsOn", true),
+    ("BUILD_DEPENDENCY_OF", "dependsOn", true),
+    ("DEV_DEPENDENCY_OF", "dependsOn", true),
+    ("RUNTIME_DEPENDENCY_OF", "dependsOn", true),
+    ("TEST_DEPENDENCY_OF", "dependsOn", true),
+    ("OPTIONAL_DEPENDENCY_OF", "hasOptionalDependency", true),
+    ("PROVIDED_DEPENDENCY_OF", "hasProvidedDependency", true),
+    ("DEPENDENCY_MANIFEST_OF", "hasDependencyManifest", true),
+    ("DYNAMIC_LINK", "hasDynamicLink", false),
+    ("STATIC_LINK", "hasStaticLink", false),
+    ("GENERATES", "generates", false),
+    ("GENERATED_FROM", "generates", true),
+    ("ANCESTOR_OF", "ancestorOf", false),
+    ("DESCENDANT_OF", "descendantOf", false),
+    ("VARIANT_OF", "hasVariant", true),
+    ("PATCH_FOR", "patchedBy", true),
+    ("DOCUMENTATION_OF", "hasDocumentation", true),
+    ("TEST_OF", "hasTest", true),
+    ("DATA_FILE_OF", "hasDataFile", true),
+    ("OPTIONAL_COMPONENT_OF", "hasOptionalComponent", true),
+    ("HAS_PREREQUISITE", "hasPrerequisite", false),
+    ("PREREQUISITE_FOR", "hasPrerequisite", true),
+    ("DISTRIBUTION_ARTIFACT_OF", "hasDistributionArtifact", true),
+    ("EXPANDED_FROM_ARCHIVE", "expandsTo", true),
+    ("COPY_OF", "copiedTo", true),
+];
+
+/// Agent element for an SPDX 2.3 creator or supplier (e.g., "Person: Jane Doe")
+///
+/// Returns the agent type and IRI, adding the element to the graph the
+/// first time a declaration is seen.
+fn spdx3_agent(
+    namespace: &str,
+    declaration: &str,
+    graph: &mut Vec<serde_json::Value>,
+    agents: &mut HashMap<String, (String, String)>,
+) -> Option<(String, String)> {
+    if let Some(agent) = agents.get(declaration) {
+        return Some(agent.clone());
+    }
+
+    let (kind, name) = declaration.split_once(':')?;
+    let kind = match kind.trim() {
+        "Tool" => "Tool",
+        "Organization" => "Organization",
+        "Person" => "Person",
+        _ => return None,
+    };
+    let id = format!("{}#SPDXRef-Agent-{}", namespace, agents.len() + 1);
+    graph.push(serde_json::json!({
+        "type": kind,
+        "spdxId": id,
+        "creationInfo": SPDX3_CREATION_INFO,
+        "name": name.trim(),
+    }));
+
+    let agent = (kind.to_string(), id);
+    agents.insert(declaration.to_string(), agent.clone());
+    Some(agent)
+}
+
+/// License expression element, added to the graph the first time an expression is seen
+fn spdx3_license(
+    namespace: &str,
+    expression: &str,
+    graph: &mut Vec<serde_json::Value>,
+    licenses: &mut HashMap<String, String>,
+) -> String {
+    if let Some(id) = licenses.get(expression) {
+        return id.clone();
+    }
+
+    let id = format!("{}#SPDXRef-License-{}", namespace, licenses.len() + 1);
+    graph.push(serde_json::json!({
+        "type": "simplelicensing_LicenseExpression",
+        "spdxId": id,
+        "creationInfo": SPDX3_CREATION_INFO,
+        "simplelicensing_licenseExpression": expression,
+    }));
+    licenses.insert(expression.to_string(), id.clone());
+    id
+}
+
+/// Append an SPDX tag-value line, wrapping multi-line values in `<text>`
+fn push_tag(out: &mut String, tag: &str, value: &str) {
+    if value.contains('\n') {
+        out.push_str(&format!("{}: <text>{}</text>\n", tag, value));
+    } else {
+        out.push_str(&format!("{}: {}\n", tag, value));
+    }
+}
+
+/// Serialize an unknown SPDX value as `NOASSERTION`
+fn serialize_noassertion<S: serde::Serializer>(value: &Option<String>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.as_deref().unwrap_or("NOASSERTION"))
 }
 
 impl Default for SpdxCreationInfo {
@@ -333,6 +1010,7 @@ impl Default for SpdxCreationInfo {
                 "Tool: rust-ecosystem-adapter".to_string(),
             ],
             license_list_version: "3.20".to_string(),
+            comment: None,
         }
     }
 }
@@ -352,9 +1030,12 @@ impl SpdxPackage {
             copyright_text: None,
             summary: None,
             description: None,
+            homepage: None,
+            supplier: None,
             source_info: None,
             checksums: Vec::new(),
             external_refs: Vec::new(),
+            comment: None,
         }
     }
     
@@ -375,7 +1056,7 @@ impl SpdxPackage {
     
     /// Set license information
     pub fn with_license(mut self, license_declared: String) -> Self {
-        self.license_declared = Some(license_declared);
+        self.license_declared = Some(license_declared.clone());
         self.license_concluded = Some(license_declared);
         self
     }
@@ -385,6 +1066,30 @@ impl SpdxPackage {
         self.download_location = Some(location);
         self
     }
+    
+    /// Set package description
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+    
+    /// Set package homepage
+    pub fn with_homepage(mut self, homepage: String) -> Self {
+        self.homepage = Some(homepage);
+        self
+    }
+    
+    /// Set package supplier
+    pub fn with_supplier(mut self, supplier: String) -> Self {
+        self.supplier = Some(supplier);
+        self
+    }
+}
+
+impl Default for CycloneDxDocument {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CycloneDxDocument {
@@ -397,6 +1102,7 @@ impl CycloneDxDocument {
             metadata: CycloneDxMetadata::default(),
             components: Vec::new(),
             dependencies: Vec::new(),
+            formulation: None,
         }
     }
     
@@ -409,6 +1115,287 @@ impl CycloneDxDocument {
     pub fn add_dependency(&mut self, dependency: CycloneDxDependency) {
         self.dependencies.push(dependency);
     }
+    
+    /// The root component, its subcomponents and the top-level components
+    pub fn all_components(&self) -> impl Iterator<Item = &CycloneDxComponent> {
+        let root = self.metadata.component.iter();
+        let subcomponents = self.metadata.component.iter().flat_map(|root| root.components.iter().flatten());
+        root.chain(subcomponents).chain(self.components.iter())
+    }
+    
+    /// Sort components by name, version and reference, and dependencies by the components they connect
+    ///
+    /// Subcomponents of the root component rank before top-level components.
+    pub fn sort_canonical(&mut self) {
+        let order = |a: &CycloneDxComponent, b: &CycloneDxComponent| (&a.name, &a.version, &a.bom_ref).cmp(&(&b.name, &b.version, &b.bom_ref));
+        if let Some(subcomponents) = self.metadata.component.as_mut().and_then(|root| root.components.as_mut()) {
+            subcomponents.sort_by(order);
+        }
+        self.components.sort_by(order);
+        let subcomponents = self.metadata.component.iter().flat_map(|root| root.components.iter().flatten());
+        let ranks = Ranks::new(subcomponents.chain(self.components.iter()).filter_map(|component| component.bom_ref.clone()));
+        for dependency in &mut self.dependencies {
+            dependency.depends_on.sort_by_cached_key(|reference| (ranks.get(reference.as_str()), reference.clone()));
+        }
+        self.dependencies.sort_by_cached_key(|dependency| (
+            ranks.get(dependency.r#ref.as_str()),
+            dependency.r#ref.clone(),
+            dependency.depends_on.iter().map(|reference| ranks.get(reference.as_str())).collect::<Vec<_>>(),
+            dependency.depends_on.clone(),
+        ));
+    }
+
+    /// Render the document in the CycloneDX XML format
+    ///
+    /// Elements follow the order of the CycloneDX XML schema for the
+    /// document's `specVersion`; `pretty` indents nested elements.
+    pub fn to_xml(&self, pretty: bool) -> String {
+        let mut xml = XmlWriter::new(pretty);
+        xml.line("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        let namespace = format!("http://cyclonedx.org/schema/bom/{}", self.spec_version);
+        xml.open("bom", &[("xmlns", &namespace), ("serialNumber", &self.serial_number), ("version", "1")]);
+
+        let metadata = &self.metadata;
+        xml.open("metadata", &[]);
+        xml.element("timestamp", &[], &metadata.timestamp);
+        if let Some(lifecycles) = &metadata.lifecycles {
+            xml.open("lifecycles", &[]);
+            for lifecycle in lifecycles {
+                xml.open("lifecycle", &[]);
+                xml.element("phase", &[], &lifecycle.phase);
+                xml.close("lifecycle");
+            }
+            xml.close("lifecycles");
+        }
+        if let Some(tools) = &metadata.tools {
+            xml.open("tools", &[]);
+            for tool in tools {
+                xml.open("tool", &[]);
+                xml.optional("vendor", tool.vendor.as_deref());
+                xml.element("name", &[], &tool.name);
+                xml.element("version", &[], &tool.version);
+                xml.hashes(tool.hashes.as_deref().unwrap_or_default());
+                xml.close("tool");
+            }
+            xml.close("tools");
+        }
+        if let Some(authors) = &metadata.authors {
+            xml.open("authors", &[]);
+            for author in authors {
+                xml.open("author", &[]);
+                xml.element("name", &[], &author.name);
+                xml.optional("email", author.email.as_deref());
+                xml.close("author");
+            }
+            xml.close("authors");
+        }
+        if let Some(component) = &metadata.component {
+            xml.component(component);
+        }
+        xml.properties(metadata.properties.as_deref().unwrap_or_default());
+        xml.close("metadata");
+
+        xml.open("components", &[]);
+        for component in &self.components {
+            xml.component(component);
+        }
+        xml.close("components");
+
+        xml.open("dependencies", &[]);
+        for dependency in &self.dependencies {
+            if dependency.depends_on.is_empty() {
+                xml.empty("dependency", &[("ref", &dependency.r#ref)]);
+                continue;
+            }
+            xml.open("dependency", &[("ref", &dependency.r#ref)]);
+            for reference in &dependency.depends_on {
+                xml.empty("dependency", &[("ref", reference)]);
+            }
+            xml.close("dependency");
+        }
+        xml.close("dependencies");
+
+        if let Some(formulation) = &self.formulation {
+            xml.open("formulation", &[]);
+            for formula in formulation {
+                xml.open("formula", &[("bom-ref", &formula.bom_ref)]);
+                xml.open("workflows", &[]);
+                for workflow in &formula.workflows {
+                    xml.open("workflow", &[("bom-ref", &workflow.bom_ref)]);
+                    xml.element("uid", &[], &workflow.uid);
+                    xml.element("name", &[], &workflow.name);
+                    xml.open("taskTypes", &[]);
+                    for task_type in &workflow.task_types {
+                        xml.element("taskType", &[], task_type);
+                    }
+                    xml.close("taskTypes");
+                    xml.properties(workflow.properties.as_deref().unwrap_or_default());
+                    xml.close("workflow");
+                }
+                xml.close("workflows");
+                xml.close("formula");
+            }
+            xml.close("formulation");
+        }
+
+        xml.close("bom");
+        xml.finish()
+    }
+}
+
+/// Writer of the CycloneDX XML encoding
+struct XmlWriter {
+    /// Rendered document
+    out: String,
+    /// Nesting depth of the next element
+    depth: usize,
+    /// Whether elements are indented, one per line
+    pretty: bool,
+}
+
+impl XmlWriter {
+    fn new(pretty: bool) -> Self {
+        Self { out: String::new(), depth: 0, pretty }
+    }
+
+    /// Append markup at the current depth
+    fn line(&mut self, markup: &str) {
+        if self.pretty {
+            self.out.push_str(&"  ".repeat(self.depth));
+        }
+        self.out.push_str(markup);
+        if self.pretty {
+            self.out.push('\n');
+        }
+    }
+
+    fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.line(&format!("<{}{}>", name, xml_attributes(attributes)));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", name));
+    }
+
+    fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.line(&format!("<{}{}/>", name, xml_attributes(attributes)));
+    }
+
+    fn element(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.line(&format!("<{0}{1}>{2}</{0}>", name, xml_attributes(attributes), xml_escape(text)));
+    }
+
+    fn optional(&mut self, name: &str, text: Option<&str>) {
+        if let Some(text) = text {
+            self.element(name, &[], text);
+        }
+    }
+
+    fn hashes(&mut self, hashes: &[CycloneDxHash]) {
+        if hashes.is_empty() {
+            return;
+        }
+        self.open("hashes", &[]);
+        for hash in hashes {
+            self.element("hash", &[("alg", &hash.alg)], &hash.content);
+        }
+        self.close("hashes");
+    }
+
+    fn properties(&mut self, properties: &[CycloneDxProperty]) {
+        if properties.is_empty() {
+            return;
+        }
+        self.open("properties", &[]);
+        for property in properties {
+            self.element("property", &[("name", &property.name)], &property.value);
+        }
+        self.close("properties");
+    }
+
+    fn component(&mut self, component: &CycloneDxComponent) {
+        let mut attributes = vec![("type", component.r#type.as_str())];
+        if let Some(bom_ref) = &component.bom_ref {
+            attributes.push(("bom-ref", bom_ref));
+        }
+        self.open("component", &attributes);
+        if let Some(supplier) = &component.supplier {
+            self.open("supplier", &[]);
+            self.element("name", &[], &supplier.name);
+            for url in &supplier.url {
+                self.element("url", &[], url);
+            }
+            self.close("supplier");
+        }
+        self.element("name", &[], &component.name);
+        self.element("version", &[], &component.version);
+        self.optional("description", component.description.as_deref());
+        self.optional("scope", component.scope.as_deref());
+        self.hashes(&component.hashes);
+        if let Some(licenses) = component.licenses.as_ref().filter(|licenses| !licenses.is_empty()) {
+            self.open("licenses", &[]);
+            for choice in licenses {
+                match choice {
+                    CycloneDxLicenseChoice::Expression { expression } => self.element("expression", &[], expression),
+                    CycloneDxLicenseChoice::License { license } => {
+                        self.open("license", &[]);
+                        self.optional("id", license.id.as_deref());
+                        self.optional("name", license.name.as_deref());
+                        self.optional("text", license.text.as_deref());
+                        self.optional("url", license.url.as_deref());
+                        self.close("license");
+                    },
+                }
+            }
+            self.close("licenses");
+        }
+        self.optional("cpe", component.cpe.as_deref());
+        self.optional("purl", component.purl.as_deref());
+        if let Some(references) = component.external_references.as_ref().filter(|references| !references.is_empty()) {
+            self.open("externalReferences", &[]);
+            for reference in references {
+                self.open("reference", &[("type", &reference.r#type)]);
+                self.element("url", &[], &reference.url);
+                self.optional("comment", reference.comment.as_deref());
+                self.close("reference");
+            }
+            self.close("externalReferences");
+        }
+        self.properties(component.properties.as_deref().unwrap_or_default());
+        if let Some(subcomponents) = component.components.as_ref().filter(|subcomponents| !subcomponents.is_empty()) {
+            self.open("components", &[]);
+            for subcomponent in subcomponents {
+                self.component(subcomponent);
+            }
+            self.close("components");
+        }
+        self.close("component");
+    }
+
+    /// The rendered document, newline-terminated
+    fn finish(mut self) -> String {
+        if !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.out
+    }
+}
+
+/// Render XML attributes, each preceded by a space
+fn xml_attributes(attributes: &[(&str, &str)]) -> String {
+    attributes.iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, xml_escape(value)))
+        .collect()
+}
+
+/// Escape text for XML content and attribute values
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 impl Default for CycloneDxMetadata {
@@ -418,6 +1405,8 @@ impl Default for CycloneDxMetadata {
             timestamp: chrono::Utc::now().to_rfc3339(),
             tools: Some(vec![CycloneDxTool::default()]),
             authors: None,
+            properties: None,
+            lifecycles: None,
         }
     }
 }
@@ -427,16 +1416,40 @@ impl CycloneDxComponent {
     pub fn new(name: String, version: String) -> Self {
         Self {
             r#type: "library".to_string(),
+            bom_ref: None,
             name,
             version,
+            purl: None,
+            description: None,
+            supplier: None,
+            cpe: None,
             scope: None,
             hashes: Vec::new(),
             licenses: None,
             external_references: None,
             properties: None,
+            components: None,
         }
     }
     
+    /// Set component type (e.g., "library", "application", "file")
+    pub fn with_type(mut self, component_type: String) -> Self {
+        self.r#type = component_type;
+        self
+    }
+    
+    /// Set component BOM reference
+    pub fn with_bom_ref(mut self, bom_ref: String) -> Self {
+        self.bom_ref = Some(bom_ref);
+        self
+    }
+    
+    /// Set component package URL
+    pub fn with_purl(mut self, purl: String) -> Self {
+        self.purl = Some(purl);
+        self
+    }
+    
     /// Set component scope
     pub fn with_scope(mut self, scope: String) -> Self {
         self.scope = Some(scope);
@@ -458,6 +1471,30 @@ impl CycloneDxComponent {
         self
     }
     
+    /// Set component description
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+    
+    /// Set component supplier
+    pub fn with_supplier(mut self, supplier: CycloneDxOrganizationalEntity) -> Self {
+        self.supplier = Some(supplier);
+        self
+    }
+    
+    /// Set component CPE identifier
+    pub fn with_cpe(mut self, cpe: String) -> Self {
+        self.cpe = Some(cpe);
+        self
+    }
+    
+    /// Add external reference to component
+    pub fn add_external_reference(mut self, external_ref: CycloneDxExternalReference) -> Self {
+        self.external_references.get_or_insert_with(Vec::new).push(external_ref);
+        self
+    }
+    
     /// Add property to component
     pub fn add_property(mut self, name: String, value: String) -> Self {
         if self.properties.is_none() {
@@ -481,6 +1518,12 @@ impl Default for CycloneDxTool {
     }
 }
 
+impl Default for LicenseInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LicenseInfo {
     /// Create new license info
     pub fn new() -> Self {
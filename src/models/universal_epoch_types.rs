@@ -0,0 +1,238 @@
+//! Universal epoch types
+//!
+//! This module defines the language-agnostic epoch document consumed by
+//! the Control Plane. An epoch is described by the packages it pins, their
+//! classification and audit status, and the dependency edges between them,
+//! using plain strings instead of Rust-specific enums so that every
+//! ecosystem adapter produces the same layout.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Version of the universal epoch document layout
+///
+/// Documents with the same major version can be imported.
+pub const UNIVERSAL_EPOCH_SCHEMA_VERSION: &str = "1.0";
+
+/// Source kinds of the universal schema
+pub const UNIVERSAL_SOURCE_KINDS: &[&str] = &["registry", "git", "local"];
+
+/// Classification kinds of the universal schema
+pub const UNIVERSAL_CLASSIFICATION_KINDS: &[&str] = &["tcs", "mechanical", "unknown"];
+
+/// Audit statuses of the universal schema
+pub const UNIVERSAL_AUDIT_STATUSES: &[&str] = &["audited", "exempted", "unaudited"];
+
+/// Dependency kinds of the universal schema
+pub const UNIVERSAL_DEPENDENCY_KINDS: &[&str] = &["normal", "build", "dev"];
+
+/// Epoch in the Control Plane's universal format
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalEpoch {
+    /// Document layout version
+    pub schema_version: String,
+    /// Ecosystem the epoch belongs to
+    pub ecosystem: String,
+    /// Epoch ID
+    pub epoch_id: String,
+    /// Epoch creation timestamp
+    pub created_at: String,
+    /// Project identifier
+    pub project_id: String,
+    /// Fingerprint of the lockfile the epoch pins
+    pub lockfile_fingerprint: String,
+    /// Vendored sources pinned with the epoch (absent when not vendored)
+    pub vendor: Option<UniversalVendorState>,
+    /// Pinned packages, sorted by key
+    pub packages: Vec<UniversalPackage>,
+    /// Dependency edges between package keys
+    pub edges: Vec<UniversalEdge>,
+}
+
+/// Vendored sources pinned with an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalVendorState {
+    /// Content digest of the vendored sources
+    pub content_digest: String,
+    /// Number of vendored packages
+    pub total_packages: usize,
+    /// Total size in bytes
+    pub total_size_bytes: u64,
+    /// Last verification timestamp
+    pub verified_at: Option<String>,
+}
+
+/// Package pinned by an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalPackage {
+    /// Package key (`name@version`)
+    pub key: String,
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Package source
+    pub source: UniversalSource,
+    /// Package checksum
+    pub checksum: String,
+    /// Package classification
+    pub classification: UniversalClassification,
+    /// Package audit status
+    pub audit: UniversalAudit,
+    /// Ecosystem-specific attributes, passed through unchanged
+    #[serde(default)]
+    pub attributes: BTreeMap<String, serde_json::Value>,
+}
+
+/// Package source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalSource {
+    /// Source kind ("registry", "git", "local")
+    pub kind: String,
+    /// Registry or repository URL, or local path
+    pub location: String,
+    /// Revision for git sources
+    pub revision: Option<String>,
+}
+
+/// Package classification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalClassification {
+    /// Classification kind ("tcs", "mechanical", "unknown")
+    pub kind: String,
+    /// Category, required unless the kind is "unknown"
+    pub category: Option<String>,
+    /// Rationale for TCS classifications
+    pub rationale: Option<String>,
+}
+
+/// Package audit status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalAudit {
+    /// Audit status ("audited", "exempted", "unaudited")
+    pub status: String,
+    /// How the package was audited (ecosystem-specific, e.g. "cargo-vet")
+    pub method: Option<String>,
+    /// Method detail, such as audit criteria or a decision record reference
+    pub reference: Option<String>,
+    /// Auditor identity
+    pub auditor: Option<String>,
+    /// Audit date
+    pub date: Option<String>,
+    /// Exemption reason
+    pub reason: Option<String>,
+    /// Exemption expiry
+    pub expires: Option<String>,
+}
+
+/// Dependency edge between two package keys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UniversalEdge {
+    /// Dependent package key
+    pub from: String,
+    /// Dependency package key
+    pub to: String,
+    /// Dependency kind ("normal", "build", "dev")
+    pub kind: String,
+    /// Target-specific dependency (if applicable)
+    pub target: Option<String>,
+    /// Whether the dependency is optional
+    pub optional: bool,
+    /// Features enabled by the dependency
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl UniversalEpoch {
+    /// Key used for a package in `packages` and `edges`
+    pub fn package_key(name: &str, version: &str) -> String {
+        format!("{}@{}", name, version)
+    }
+    
+    /// Whether a document of `schema_version` can be read by this version
+    pub fn is_supported_version(schema_version: &str) -> bool {
+        let major = |version: &str| version.split('.').next().map(str::to_string);
+        major(schema_version).is_some() && major(schema_version) == major(UNIVERSAL_EPOCH_SCHEMA_VERSION)
+    }
+    
+    /// Check the document against the universal schema
+    ///
+    /// Returns every violation found, each prefixed with the path of the
+    /// offending field; an empty list means the document is valid.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if !Self::is_supported_version(&self.schema_version) {
+            errors.push(format!(
+                "schema_version: unsupported version '{}' (expected {})",
+                self.schema_version, UNIVERSAL_EPOCH_SCHEMA_VERSION,
+            ));
+        }
+        for (field, value) in [("ecosystem", &self.ecosystem), ("epoch_id", &self.epoch_id), ("project_id", &self.project_id)] {
+            if value.trim().is_empty() {
+                errors.push(format!("{}: must not be empty", field));
+            }
+        }
+        
+        let mut keys = HashSet::new();
+        for (index, package) in self.packages.iter().enumerate() {
+            let path = format!("packages[{}]", index);
+            if package.name.is_empty() || package.version.is_empty() {
+                errors.push(format!("{}: name and version must not be empty", path));
+            }
+            if package.key != Self::package_key(&package.name, &package.version) {
+                errors.push(format!("{}.key: '{}' does not match name and version", path, package.key));
+            }
+            if !keys.insert(package.key.as_str()) {
+                errors.push(format!("{}.key: duplicate package '{}'", path, package.key));
+            }
+            errors.extend(package.validate().into_iter().map(|error| format!("{}.{}", path, error)));
+        }
+        
+        for (index, edge) in self.edges.iter().enumerate() {
+            let path = format!("edges[{}]", index);
+            for (field, key) in [("from", &edge.from), ("to", &edge.to)] {
+                if !keys.contains(key.as_str()) {
+                    errors.push(format!("{}.{}: unknown package '{}'", path, field, key));
+                }
+            }
+            if !UNIVERSAL_DEPENDENCY_KINDS.contains(&edge.kind.as_str()) {
+                errors.push(format!("{}.kind: unknown dependency kind '{}'", path, edge.kind));
+            }
+        }
+        
+        errors
+    }
+}
+
+impl UniversalPackage {
+    /// Schema violations in the source, classification and audit fields
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        
+        if !UNIVERSAL_SOURCE_KINDS.contains(&self.source.kind.as_str()) {
+            errors.push(format!("source.kind: unknown source kind '{}'", self.source.kind));
+        }
+        if self.source.kind == "git" && self.source.revision.is_none() {
+            errors.push("source.revision: required for git sources".to_string());
+        }
+        
+        let classification = &self.classification;
+        if !UNIVERSAL_CLASSIFICATION_KINDS.contains(&classification.kind.as_str()) {
+            errors.push(format!("classification.kind: unknown classification '{}'", classification.kind));
+        } else if classification.kind != "unknown" && classification.category.is_none() {
+            errors.push(format!("classification.category: required for {} packages", classification.kind));
+        }
+        
+        let audit = &self.audit;
+        match audit.status.as_str() {
+            "audited" if audit.method.is_none() => errors.push("audit.method: required for audited packages".to_string()),
+            "exempted" if audit.reason.is_none() => errors.push("audit.reason: required for exempted packages".to_string()),
+            status if !UNIVERSAL_AUDIT_STATUSES.contains(&status) => {
+                errors.push(format!("audit.status: unknown audit status '{}'", status));
+            },
+            _ => {},
+        }
+        
+        errors
+    }
+}
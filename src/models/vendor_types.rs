@@ -4,9 +4,10 @@
 //! including vendor information, verification reports, and strategies.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use super::dependency_graph::*;
+use crate::utils::DigestConfig;
 
 /// Vendor operation information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -17,6 +18,9 @@ pub struct VendorInfo {
     pub total_packages: usize,
     /// Cryptographic digest of vendor directory
     pub vendor_digest: String,
+    /// Canonical Cargo.lock fingerprint the vendor directory was built from
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
     /// Whether vendor is ready for offline builds
     pub offline_ready: bool,
     /// Vendoring operation metadata
@@ -65,6 +69,19 @@ pub struct VendorPackageInfo {
     pub verified_at: Option<String>,
 }
 
+/// Vendored size of the packages in one classification
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct VendorSizeStats {
+    /// Number of vendored packages
+    pub packages: usize,
+    /// Total size of the packages in bytes
+    pub total_size_bytes: u64,
+    /// Fraction of the whole vendor directory (0.0 - 1.0)
+    pub share: f64,
+    /// Largest package ("name@version")
+    pub largest: Option<String>,
+}
+
 /// Vendor verification report
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VerificationReport {
@@ -84,6 +101,12 @@ pub struct VerificationReport {
     pub verified_at: String,
     /// Verification duration in milliseconds
     pub verification_duration_ms: u64,
+    /// Secrets found in vendored sources (empty unless the scan is enabled)
+    #[serde(default)]
+    pub secret_findings: Vec<SecretFinding>,
+    /// Path dependencies that failed verification
+    #[serde(default)]
+    pub local_dependency_issues: Vec<LocalDependencyIssue>,
     /// Additional verification details
     pub details: HashMap<String, serde_json::Value>,
 }
@@ -101,6 +124,23 @@ pub enum VerificationResult {
     Incomplete,
 }
 
+/// Secret embedded in a vendored source file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SecretFinding {
+    /// Vendored package directory
+    pub package: String,
+    /// File path, relative to the vendor directory
+    pub path: String,
+    /// Line number (1-based)
+    pub line: usize,
+    /// Rule that matched (e.g. `aws-access-key-id`)
+    pub rule: String,
+    /// Matched value with all but its first characters masked
+    pub redacted: String,
+    /// Shannon entropy of the value, for entropy-checked rules
+    pub entropy: Option<f64>,
+}
+
 /// Checksum mismatch information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ChecksumMismatch {
@@ -116,6 +156,32 @@ pub struct ChecksumMismatch {
     pub details: Option<String>,
 }
 
+/// Path dependency of Cargo.lock located in the repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalDependency {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Crate directory relative to the repository root
+    pub path: PathBuf,
+    /// SHA256 over the crate's files, excluding nested crates and `target`
+    pub content_hash: String,
+}
+
+/// Path dependency of Cargo.lock that failed verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalDependencyIssue {
+    /// Package name
+    pub package_name: String,
+    /// Locked version
+    pub version: String,
+    /// Declared crate directory, if any manifest declares one
+    pub path: Option<PathBuf>,
+    /// Why verification failed
+    pub reason: String,
+}
+
 /// Vendor strategy configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VendorStrategy {
@@ -194,6 +260,12 @@ pub struct VendorSnapshot {
     pub total_size_bytes: u64,
     /// Checksums file path
     pub checksums_file: PathBuf,
+    /// Canonical fingerprint of the Cargo.lock the snapshot was taken from
+    #[serde(default)]
+    pub lockfile_fingerprint: String,
+    /// Content digest of the vendor directory (see `VendorArchiveManifest`)
+    #[serde(default)]
+    pub content_digest: String,
     /// Snapshot creation timestamp
     pub created_at: String,
     /// Last verification timestamp
@@ -202,6 +274,288 @@ pub struct VendorSnapshot {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// Checksums manifest recorded in the vendor directory after vendoring
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorChecksumManifest {
+    /// Manifest generation timestamp
+    pub generated_at: String,
+    /// Vendored packages keyed by "name@version"
+    pub packages: BTreeMap<String, VendorManifestEntry>,
+}
+
+/// Vendored package recorded in the checksums manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorManifestEntry {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Cargo.lock source string
+    pub source: Option<String>,
+    /// Cargo.lock checksum (registry packages only)
+    pub checksum: Option<String>,
+    /// Directory relative to the vendor root
+    pub path: PathBuf,
+}
+
+/// Sources an artifact mirror must pre-fetch to rebuild an epoch
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MirrorManifest {
+    /// Epoch the packages are pinned by
+    pub epoch_id: String,
+    /// Canonical fingerprint of the Cargo.lock the epoch was taken from
+    pub lockfile_fingerprint: String,
+    /// Manifest generation timestamp
+    pub generated_at: String,
+    /// Packages to mirror, ordered by name and version
+    pub packages: Vec<MirrorEntry>,
+    /// Packages whose source cannot be mirrored ("name@version: source")
+    pub unsupported: Vec<String>,
+}
+
+/// Package to mirror
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MirrorEntry {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Where to fetch the package from
+    pub source: MirrorSource,
+}
+
+/// Fetchable package source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MirrorSource {
+    /// Registry crate archive
+    Registry {
+        /// Registry index URL
+        index: String,
+        /// Exact `.crate` download URL (absent for registries without a
+        /// configured download template)
+        download_url: Option<String>,
+        /// SHA-256 checksum of the `.crate` file
+        checksum: Option<String>,
+    },
+    /// Git repository at a commit
+    Git {
+        /// Repository URL
+        url: String,
+        /// Locked commit
+        rev: String,
+        /// Branch, tag or revision the dependency was declared with
+        reference: Option<String>,
+    },
+}
+
+/// Outcome of a delta vendoring operation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeltaVendorReport {
+    /// Whether a full `cargo vendor` was performed instead of a delta
+    pub full_vendor: bool,
+    /// Why the delta was abandoned, if it was
+    pub fallback_reason: Option<String>,
+    /// Packages vendored that were not present before ("name@version")
+    pub added: Vec<String>,
+    /// Packages re-vendored because their checksum changed ("name@version")
+    pub changed: Vec<String>,
+    /// Packages removed from the vendor directory ("name@version")
+    pub removed: Vec<String>,
+    /// Number of packages left untouched
+    pub unchanged: usize,
+}
+
+impl DeltaVendorReport {
+    /// Report for a full vendoring run
+    pub fn full(reason: Option<String>) -> Self {
+        Self {
+            full_vendor: true,
+            fallback_reason: reason,
+            added: Vec::new(),
+            changed: Vec::new(),
+            removed: Vec::new(),
+            unchanged: 0,
+        }
+    }
+}
+
+/// Changes a mutating operation would make, reported instead of applied
+/// in dry-run mode
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChangePlan {
+    /// Operation being planned
+    pub operation: String,
+    /// Files or directories that would be written
+    pub files_written: Vec<PathBuf>,
+    /// Files or directories that would be removed
+    pub files_removed: Vec<PathBuf>,
+    /// Packages that would be vendored ("name@version")
+    pub packages_vendored: Vec<String>,
+    /// Packages that would be removed from the vendor directory ("name@version")
+    pub packages_removed: Vec<String>,
+    /// Epochs that would no longer be the latest
+    pub epochs_superseded: Vec<String>,
+    /// Further details of the planned change
+    pub notes: Vec<String>,
+}
+
+impl ChangePlan {
+    /// Empty plan for an operation
+    pub fn new(operation: impl Into<String>) -> Self {
+        Self {
+            operation: operation.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the operation would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.files_written.is_empty()
+            && self.files_removed.is_empty()
+            && self.packages_vendored.is_empty()
+            && self.packages_removed.is_empty()
+            && self.epochs_superseded.is_empty()
+    }
+}
+
+/// Manifest stored as the first entry of a vendor archive
+///
+/// Contains no timestamps so that archiving the same vendor directory
+/// always produces the same bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorArchiveManifest {
+    /// Archive format version
+    pub format_version: u32,
+    /// Epoch the vendor snapshot belongs to
+    pub epoch_id: Option<String>,
+    /// Number of files in the archive
+    pub total_files: usize,
+    /// Total uncompressed size in bytes
+    pub total_size_bytes: u64,
+    /// Digest over the sorted file list (path, size and checksum)
+    pub content_digest: String,
+    /// Algorithm and encoding of the file checksums and content digest;
+    /// SHA-256 in hex for manifests written before it was recorded
+    #[serde(default)]
+    pub digest: DigestConfig,
+    /// Archived files, sorted by path
+    pub files: Vec<VendorArchiveFile>,
+}
+
+/// File recorded in a vendor archive manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorArchiveFile {
+    /// Path relative to the vendor directory, '/'-separated
+    pub path: String,
+    /// File size in bytes
+    pub size_bytes: u64,
+    /// Digest of file contents, as the manifest's `digest` describes
+    #[serde(alias = "sha256")]
+    pub checksum: String,
+    /// Whether the file is executable
+    pub executable: bool,
+}
+
+/// Result of creating a vendor archive
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorArchiveInfo {
+    /// Archive file path
+    pub archive_path: PathBuf,
+    /// Hex digest of the archive file, with the manifest's algorithm
+    pub archive_digest: String,
+    /// Path of the digest file written next to the archive
+    pub digest_path: PathBuf,
+    /// Archive manifest
+    pub manifest: VendorArchiveManifest,
+}
+
+impl VendorArchiveManifest {
+    /// Current archive format version
+    pub const FORMAT_VERSION: u32 = 1;
+    
+    /// Create manifest from files, sorting them and computing the content digest
+    ///
+    /// File checksums must have been calculated with `digest`.
+    pub fn new(epoch_id: Option<String>, mut files: Vec<VendorArchiveFile>, digest: DigestConfig) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        
+        let mut manifest = Self {
+            format_version: Self::FORMAT_VERSION,
+            epoch_id,
+            total_files: files.len(),
+            total_size_bytes: files.iter().map(|f| f.size_bytes).sum(),
+            content_digest: String::new(),
+            digest,
+            files,
+        };
+        manifest.content_digest = manifest.compute_content_digest();
+        manifest
+    }
+    
+    /// Compute the content digest over the sorted file list
+    pub fn compute_content_digest(&self) -> String {
+        let mut hasher = self.digest.hasher();
+        for file in &self.files {
+            hasher.update(format!("{}  {}  {}\n", file.checksum, file.size_bytes, file.path).as_bytes());
+        }
+        self.digest.finish(hasher)
+    }
+}
+
+/// File-level differences of vendored sources between two epochs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorDiffReport {
+    /// Epoch compared from
+    pub epoch_a: String,
+    /// Epoch compared to
+    pub epoch_b: String,
+    /// Package the comparison was restricted to
+    pub package: Option<String>,
+    /// Changed files, sorted by path
+    pub changes: Vec<VendorFileChange>,
+    /// Number of files identical in both epochs
+    pub unchanged: usize,
+}
+
+/// Vendored file that differs between two epochs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VendorFileChange {
+    /// Path relative to the vendor directory, '/'-separated
+    pub path: String,
+    /// Kind of change
+    pub kind: VendorFileChangeKind,
+    /// Checksum in the first epoch
+    pub sha256_a: Option<String>,
+    /// Checksum in the second epoch
+    pub sha256_b: Option<String>,
+    /// Unified diff of the contents, when requested and both sides are available
+    pub unified_diff: Option<String>,
+}
+
+/// Kind of change to a vendored file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum VendorFileChangeKind {
+    /// File only present in the second epoch
+    Added,
+    /// File only present in the first epoch
+    Removed,
+    /// File present in both epochs with different contents or mode
+    Modified,
+}
+
+impl VendorDiffReport {
+    /// Number of changes of a given kind
+    pub fn count(&self, kind: VendorFileChangeKind) -> usize {
+        self.changes.iter().filter(|change| change.kind == kind).count()
+    }
+    
+    /// Whether the two epochs vendor identical sources
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
 /// Cargo configuration for vendor operation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CargoVendorConfig {
@@ -246,6 +600,7 @@ impl VendorInfo {
             vendor_path,
             total_packages: 0,
             vendor_digest: String::new(),
+            lockfile_fingerprint: String::new(),
             offline_ready: false,
             metadata: VendorMetadata::default(),
             packages: HashMap::new(),
@@ -253,14 +608,60 @@ impl VendorInfo {
     }
     
     /// Add package information
+    ///
+    /// Packages are keyed by "name@version" so that several vendored
+    /// versions of a crate are all kept.
     pub fn add_package(&mut self, package_info: VendorPackageInfo) {
         self.total_packages += 1;
-        self.packages.insert(package_info.name.clone(), package_info);
+        self.metadata.total_size_bytes += package_info.size_bytes;
+        self.packages.insert(format!("{}@{}", package_info.name, package_info.version), package_info);
     }
     
-    /// Get package information by name
+    /// Get package information by name, or by "name@version"
     pub fn get_package(&self, name: &str) -> Option<&VendorPackageInfo> {
         self.packages.get(name)
+            .or_else(|| self.packages.values().find(|package| package.name == name))
+    }
+    
+    /// Packages ordered by size, largest first
+    pub fn packages_by_size(&self) -> Vec<&VendorPackageInfo> {
+        let mut packages: Vec<_> = self.packages.values().collect();
+        packages.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes)
+            .then_with(|| (&a.name, &a.version).cmp(&(&b.name, &b.version))));
+        packages
+    }
+    
+    /// Aggregate vendored sizes by the classification of each package in a graph
+    ///
+    /// Packages missing from the graph are counted as "unknown".
+    pub fn size_by_classification(&self, graph: &DependencyGraph) -> BTreeMap<String, VendorSizeStats> {
+        let classifications: HashMap<(&str, &str), &str> = graph.root_packages.iter()
+            .map(|package| {
+                let classification = match package.classification {
+                    Classification::TCS { .. } => "tcs",
+                    Classification::Mechanical { .. } => "mechanical",
+                    Classification::Unknown => "unknown",
+                };
+                ((package.name.as_str(), package.version.as_str()), classification)
+            })
+            .collect();
+        
+        let mut stats: BTreeMap<String, VendorSizeStats> = BTreeMap::new();
+        for package in self.packages_by_size() {
+            let classification = classifications.get(&(package.name.as_str(), package.version.as_str())).copied().unwrap_or("unknown");
+            let entry = stats.entry(classification.to_string()).or_default();
+            entry.packages += 1;
+            entry.total_size_bytes += package.size_bytes;
+            if entry.largest.is_none() {
+                entry.largest = Some(format!("{}@{}", package.name, package.version));
+            }
+        }
+        
+        let total = self.metadata.total_size_bytes.max(1) as f64;
+        for entry in stats.values_mut() {
+            entry.share = entry.total_size_bytes as f64 / total;
+        }
+        stats
     }
     
     /// Check if all packages are verified
@@ -288,6 +689,12 @@ impl Default for VendorMetadata {
     }
 }
 
+impl Default for VerificationReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl VerificationReport {
     /// Create new verification report
     pub fn new() -> Self {
@@ -300,6 +707,8 @@ impl VerificationReport {
             epoch_valid: false,
             verified_at: chrono::Utc::now().to_rfc3339(),
             verification_duration_ms: 0,
+            secret_findings: Vec::new(),
+            local_dependency_issues: Vec::new(),
             details: HashMap::new(),
         }
     }
@@ -323,6 +732,7 @@ impl VerificationReport {
     pub fn has_critical_issues(&self) -> bool {
         !self.checksum_mismatches.is_empty() ||
         !self.missing_dependencies.is_empty() ||
+        !self.local_dependency_issues.is_empty() ||
         !self.structure_valid ||
         !self.config_valid
     }
@@ -331,7 +741,9 @@ impl VerificationReport {
     pub fn determine_result(&mut self) {
         self.result = if self.has_critical_issues() {
             VerificationResult::Failed
-        } else if self.checksum_mismatches.iter().any(|m| matches!(m.severity, ErrorSeverity::High)) {
+        } else if self.checksum_mismatches.iter().any(|m| matches!(m.severity, ErrorSeverity::High))
+            || !self.secret_findings.is_empty()
+        {
             VerificationResult::Warning
         } else {
             VerificationResult::Success
@@ -436,6 +848,8 @@ impl VendorSnapshot {
             total_packages: 0,
             total_size_bytes: 0,
             checksums_file: PathBuf::from("checksums.txt"),
+            lockfile_fingerprint: String::new(),
+            content_digest: String::new(),
             created_at: chrono::Utc::now().to_rfc3339(),
             verified_at: None,
             metadata: HashMap::new(),
@@ -0,0 +1,60 @@
+//! Version skew types
+//!
+//! This module defines findings about mismatches between the version
+//! requirements declared in Cargo.toml and the versions resolved in
+//! Cargo.lock.
+
+use serde::{Deserialize, Serialize};
+use super::audit_types::Severity;
+
+/// Version skew report for a project
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionSkewReport {
+    /// Report generation timestamp
+    pub generated_at: String,
+    /// Canonical Cargo.lock fingerprint the report was computed against
+    pub lockfile_fingerprint: String,
+    /// Number of declarations checked
+    pub declarations_checked: usize,
+    /// Detected skew findings, most severe first
+    pub findings: Vec<VersionSkewFinding>,
+}
+
+/// A declared requirement that is at odds with the resolved version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionSkewFinding {
+    /// Kind of skew
+    pub kind: VersionSkewKind,
+    /// Severity level
+    pub severity: Severity,
+    /// Name of the depended-on crate
+    pub crate_name: String,
+    /// Workspace package declaring the requirement
+    pub declared_by: String,
+    /// Declared version requirement
+    pub requirement: String,
+    /// Versions of the crate resolved in Cargo.lock
+    pub resolved_versions: Vec<String>,
+    /// Whether the crate is classified as TCS
+    pub tcs: bool,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Kind of version skew
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VersionSkewKind {
+    /// No resolved version satisfies the requirement
+    Unsatisfied,
+    /// The resolved version is the lowest the requirement admits
+    BarelySatisfied,
+    /// A TCS crate is declared with a wildcard requirement
+    WildcardTcs,
+}
+
+impl VersionSkewReport {
+    /// Number of findings of a kind
+    pub fn count(&self, kind: VersionSkewKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+}
@@ -0,0 +1,20 @@
+//! Fixtures shared by the unit tests
+
+use crate::models::{Classification, PackageNode, PackageSource};
+use crate::utils::lockfile::CRATES_IO_SOURCE;
+
+/// crates.io source without a checksum
+pub(crate) fn crates_io() -> PackageSource {
+    PackageSource::Registry {
+        url: CRATES_IO_SOURCE.trim_start_matches("registry+").to_string(),
+        checksum: String::new(),
+    }
+}
+
+/// Unaudited crates.io package with the given classification
+///
+/// Override other fields with struct update syntax, e.g.
+/// `PackageNode { source, ..package("app", "0.1.0", Classification::Unknown) }`.
+pub(crate) fn package(name: &str, version: &str, classification: Classification) -> PackageNode {
+    PackageNode { classification, ..PackageNode::new(name.to_string(), version.to_string(), crates_io()) }
+}
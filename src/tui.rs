@@ -0,0 +1,543 @@
+//! Interactive review of drift and audit findings
+//!
+//! `rust-adapter tui` opens a terminal UI with three tabs: the dependency
+//! tree, the packages that changed since the base epoch, and the audit
+//! findings. Reviewers approve or reject drift items and findings; every
+//! decision is written straight to the project's review decisions file,
+//! which `vendor snapshot` checks before pinning the vendor directory to
+//! an epoch. The vendored sources of a changed package can be diffed
+//! between the base epoch and a later one.
+
+use crate::adapter::rust_adapter::EcosystemAdapter;
+use crate::error::{AdapterError, Result};
+use crate::models::*;
+use crate::utils::{GraphFilter, GraphView};
+use crate::RustAdapter;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+/// Key bindings shown in the footer
+const HELP: &str = "Tab: switch  ↑/↓: move  Enter: details  d: vendored diff  a: approve  r: reject  u: undo  q: quit";
+
+/// Tabs of the review UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewTab {
+    /// Dependency tree (browse only)
+    Tree,
+    /// Packages changed since the base epoch
+    Drift,
+    /// Audit findings
+    Findings,
+}
+
+impl ReviewTab {
+    /// Tabs in display order
+    const ALL: [ReviewTab; 3] = [ReviewTab::Tree, ReviewTab::Drift, ReviewTab::Findings];
+    
+    /// Position in display order
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|tab| *tab == self).unwrap_or_default()
+    }
+}
+
+/// Package changed since the base epoch
+#[derive(Debug, Clone)]
+pub struct DriftItem {
+    /// Package name
+    pub package_name: String,
+    /// Most recent change of the package
+    pub change: TimelineChange,
+}
+
+/// State of a review session
+#[derive(Debug)]
+pub struct ReviewSession {
+    /// Rendered dependency tree lines
+    tree: Vec<String>,
+    /// Drift items
+    drift: Vec<DriftItem>,
+    /// Audit findings
+    findings: Vec<AuditFinding>,
+    /// Decisions made so far
+    decisions: ReviewDecisions,
+    /// Reviewer recorded with decisions
+    reviewer: String,
+    /// Active tab
+    tab: ReviewTab,
+    /// Selected row of each tab
+    selected: [usize; 3],
+    /// Details of the selected item, when open
+    detail: Option<Vec<String>>,
+    /// Scroll offset of the details
+    detail_scroll: u16,
+    /// Last status message
+    status: Option<String>,
+}
+
+impl ReviewSession {
+    /// Create a session over a graph, its drift and its findings
+    pub fn new(
+        graph: &DependencyGraph,
+        drift: Vec<DriftItem>,
+        findings: Vec<AuditFinding>,
+        decisions: ReviewDecisions,
+        reviewer: &str,
+    ) -> Self {
+        let tree = GraphView::new(graph, GraphFilter::default())
+            .render_tree()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        
+        Self {
+            tree,
+            drift,
+            findings,
+            decisions,
+            reviewer: reviewer.to_string(),
+            tab: ReviewTab::Drift,
+            selected: [0; 3],
+            detail: None,
+            detail_scroll: 0,
+            status: None,
+        }
+    }
+    
+    /// Decisions made so far
+    pub fn decisions(&self) -> &ReviewDecisions {
+        &self.decisions
+    }
+    
+    /// Active tab
+    pub fn tab(&self) -> ReviewTab {
+        self.tab
+    }
+    
+    /// Switch to the next (or previous) tab
+    pub fn switch_tab(&mut self, forward: bool) {
+        let count = ReviewTab::ALL.len();
+        let offset = if forward { 1 } else { count - 1 };
+        self.tab = ReviewTab::ALL[(self.tab.index() + offset) % count];
+        self.detail = None;
+    }
+    
+    /// Move the selection, or scroll the details when they are open
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.detail.is_some() {
+            self.detail_scroll = self.detail_scroll.saturating_add_signed(delta as i16);
+            return;
+        }
+        
+        let len = self.len();
+        let selected = &mut self.selected[self.tab.index()];
+        *selected = selected.saturating_add_signed(delta).min(len.saturating_sub(1));
+    }
+    
+    /// Selected drift item, on the drift tab
+    pub fn selected_drift(&self) -> Option<&DriftItem> {
+        match self.tab {
+            ReviewTab::Drift => self.drift.get(self.selected[self.tab.index()]),
+            _ => None,
+        }
+    }
+    
+    /// Record (or, with `None`, withdraw) a decision on the selected item
+    ///
+    /// Returns whether the decisions changed.
+    pub fn decide(&mut self, status: Option<ReviewStatus>) -> bool {
+        let selected = self.selected[self.tab.index()];
+        let (key, kind) = match self.tab {
+            ReviewTab::Drift => match self.drift.get(selected) {
+                Some(item) => (ReviewDecisions::drift_key(&item.package_name), ReviewItemKind::Drift),
+                None => return false,
+            },
+            ReviewTab::Findings => match self.findings.get(selected) {
+                Some(finding) => (ReviewDecisions::finding_key(&finding.id, &finding.package_name), ReviewItemKind::Finding),
+                None => return false,
+            },
+            ReviewTab::Tree => {
+                self.status = Some("Dependency tree entries are not reviewed individually".to_string());
+                return false;
+            },
+        };
+        
+        match status {
+            Some(status) => self.decisions.decide(key, kind, status, &self.reviewer),
+            None => self.decisions.clear(&key),
+        }
+        self.move_selection(1);
+        true
+    }
+    
+    /// Show or hide the details of the selected item
+    pub fn toggle_detail(&mut self) {
+        if self.detail.take().is_some() {
+            return;
+        }
+        
+        let selected = self.selected[self.tab.index()];
+        let lines = match self.tab {
+            ReviewTab::Drift => self.drift.get(selected).map(Self::drift_detail),
+            ReviewTab::Findings => self.findings.get(selected).map(Self::finding_detail),
+            ReviewTab::Tree => None,
+        };
+        if let Some(lines) = lines {
+            self.show_detail(lines);
+        }
+    }
+    
+    /// Show lines in the details pane
+    pub fn show_detail(&mut self, lines: Vec<String>) {
+        self.detail = Some(lines);
+        self.detail_scroll = 0;
+    }
+    
+    /// Hide the details pane; returns whether it was open
+    pub fn close_detail(&mut self) -> bool {
+        self.detail.take().is_some()
+    }
+    
+    /// Show a status message in the footer
+    pub fn set_status(&mut self, message: String) {
+        self.status = Some(message);
+    }
+    
+    /// Draw the session
+    pub fn render(&self, frame: &mut Frame) {
+        let [tabs_area, body_area, footer_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ]).areas(frame.area());
+        
+        let titles = [
+            "Tree".to_string(),
+            format!("Drift ({})", self.drift.len()),
+            format!("Findings ({})", self.findings.len()),
+        ];
+        let tabs = Tabs::new(titles)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "Review ({} approved, {} rejected)",
+                self.decisions.approved(),
+                self.decisions.rejected().len(),
+            )))
+            .select(self.tab.index())
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow));
+        frame.render_widget(tabs, tabs_area);
+        
+        match &self.detail {
+            Some(lines) => {
+                let detail = Paragraph::new(lines.join("\n"))
+                    .block(Block::default().borders(Borders::ALL).title("Details (Enter/Esc to close)"))
+                    .wrap(Wrap { trim: false })
+                    .scroll((self.detail_scroll, 0));
+                frame.render_widget(detail, body_area);
+            },
+            None => {
+                let list = List::new(self.rows())
+                    .block(Block::default().borders(Borders::ALL))
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                let mut state = ListState::default().with_selected(Some(self.selected[self.tab.index()]));
+                frame.render_stateful_widget(list, body_area, &mut state);
+            },
+        }
+        
+        frame.render_widget(Paragraph::new(self.status.as_deref().unwrap_or(HELP)), footer_area);
+    }
+    
+    /// Number of rows of the active tab
+    fn len(&self) -> usize {
+        match self.tab {
+            ReviewTab::Tree => self.tree.len(),
+            ReviewTab::Drift => self.drift.len(),
+            ReviewTab::Findings => self.findings.len(),
+        }
+    }
+    
+    /// Rows of the active tab
+    fn rows(&self) -> Vec<ListItem<'static>> {
+        match self.tab {
+            ReviewTab::Tree => self.tree.iter().map(|line| ListItem::new(line.clone())).collect(),
+            ReviewTab::Drift => self.drift.iter()
+                .map(|item| {
+                    let change = &item.change;
+                    ListItem::new(format!(
+                        "{} {} {:?} {} -> {} ({:?})",
+                        self.marker(&ReviewDecisions::drift_key(&item.package_name)),
+                        item.package_name,
+                        change.change_type,
+                        change.previous_version.as_deref().unwrap_or("-"),
+                        change.current_version.as_deref().unwrap_or("-"),
+                        change.priority,
+                    ))
+                })
+                .collect(),
+            ReviewTab::Findings => self.findings.iter()
+                .map(|finding| {
+                    ListItem::new(format!(
+                        "{} {} {} [{}]{}",
+                        self.marker(&ReviewDecisions::finding_key(&finding.id, &finding.package_name)),
+                        finding.id,
+                        finding.package_name,
+                        finding.severity.as_str(),
+                        if finding.affects_tcs { " TCS" } else { "" },
+                    ))
+                })
+                .collect(),
+        }
+    }
+    
+    /// Decision marker of an item
+    fn marker(&self, key: &str) -> &'static str {
+        match self.decisions.status(key) {
+            Some(ReviewStatus::Approved) => "[✓]",
+            Some(ReviewStatus::Rejected) => "[✗]",
+            None => "[ ]",
+        }
+    }
+    
+    /// Detail lines of a drift item
+    fn drift_detail(item: &DriftItem) -> Vec<String> {
+        let change = &item.change;
+        vec![
+            format!("Package: {}", item.package_name),
+            format!("Change: {:?} ({:?} priority)", change.change_type, change.priority),
+            format!("Previous: {} {}", change.previous_version.as_deref().unwrap_or("-"), change.previous_source.as_deref().unwrap_or("")),
+            format!("Current: {} {}", change.current_version.as_deref().unwrap_or("-"), change.current_source.as_deref().unwrap_or("")),
+            format!("Since epoch: {}", change.previous_epoch_id),
+            format!("Approved in: {}", change.approved_in.as_deref().unwrap_or("not approved by any epoch")),
+        ]
+    }
+    
+    /// Detail lines of a finding
+    fn finding_detail(finding: &AuditFinding) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} ({})", finding.id, finding.severity.as_str()),
+            format!("Package: {} {}", finding.package_name, finding.package_version.as_deref().unwrap_or("")),
+            format!("Affected: {}", finding.affected_versions),
+            format!("Patched: {}", finding.patched_versions.join(", ")),
+        ];
+        if let Some(version) = &finding.minimal_safe_version {
+            lines.push(format!("Minimal safe version: {}", version));
+        }
+        lines.push(String::new());
+        lines.push(finding.description.clone());
+        if !finding.dependency_paths.is_empty() {
+            lines.push(String::new());
+            lines.push("Dependency paths:".to_string());
+            lines.extend(finding.dependency_paths.iter().map(|path| format!("  {}", path.join(" -> "))));
+        }
+        if !finding.references.is_empty() {
+            lines.push(String::new());
+            lines.extend(finding.references.iter().cloned());
+        }
+        lines
+    }
+}
+
+/// Run an interactive review session for a project
+///
+/// Drift is computed since `since` (the most recent epoch by default).
+/// Vendored diffs compare `since` with `diff_to` (the most recent epoch by
+/// default). Decisions made earlier against the same Cargo.lock are
+/// loaded and can be changed.
+pub async fn run(
+    adapter: &RustAdapter,
+    project: &Project,
+    since: Option<&str>,
+    diff_to: Option<&str>,
+    reviewer: &str,
+) -> Result<()> {
+    let graph = adapter.parse_dependencies(project).await?;
+    let audit_report = adapter.run_audit(project).await?;
+    
+    let epochs = adapter.vendor_manager().list_epochs(project)?;
+    let latest_epoch = epochs.last().map(|snapshot| snapshot.epoch_id.clone());
+    let since = since.map(str::to_string).or_else(|| latest_epoch.clone());
+    let diff_to = diff_to.map(str::to_string).or(latest_epoch).filter(|to| Some(to) != since.as_ref());
+    
+    let drift = match &since {
+        Some(since) => adapter.drift_timeline(project, since).await?
+            .packages
+            .into_iter()
+            .filter_map(|(package_name, changes)| {
+                changes.into_iter().last().map(|change| DriftItem { package_name, change })
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    
+    let decisions = adapter.vendor_manager().load_review_decisions(project)?
+        .filter(|decisions| decisions.lockfile_fingerprint == graph.metadata.lockfile_fingerprint)
+        .unwrap_or_else(|| ReviewDecisions::new(graph.metadata.lockfile_fingerprint.clone()));
+    
+    let mut session = ReviewSession::new(&graph, drift, audit_report.findings, decisions, reviewer);
+    if since.is_none() {
+        session.set_status("No epochs yet: drift is unavailable".to_string());
+    }
+    
+    let mut terminal = ratatui::try_init().map_err(terminal_error)?;
+    let result = event_loop(&mut terminal, &mut session, adapter, project, since.as_deref(), diff_to.as_deref());
+    ratatui::restore();
+    result
+}
+
+/// Handle key presses until the reviewer quits
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    session: &mut ReviewSession,
+    adapter: &RustAdapter,
+    project: &Project,
+    since: Option<&str>,
+    diff_to: Option<&str>,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| session.render(frame)).map_err(terminal_error)?;
+        let key = match event::read().map_err(terminal_error)? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        
+        let changed = match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Esc => {
+                if !session.close_detail() {
+                    return Ok(());
+                }
+                false
+            },
+            KeyCode::Tab | KeyCode::Right => { session.switch_tab(true); false },
+            KeyCode::BackTab | KeyCode::Left => { session.switch_tab(false); false },
+            KeyCode::Down | KeyCode::Char('j') => { session.move_selection(1); false },
+            KeyCode::Up | KeyCode::Char('k') => { session.move_selection(-1); false },
+            KeyCode::PageDown => { session.move_selection(20); false },
+            KeyCode::PageUp => { session.move_selection(-20); false },
+            KeyCode::Enter => { session.toggle_detail(); false },
+            KeyCode::Char('a') => session.decide(Some(ReviewStatus::Approved)),
+            KeyCode::Char('r') => session.decide(Some(ReviewStatus::Rejected)),
+            KeyCode::Char('u') => session.decide(None),
+            KeyCode::Char('d') => {
+                show_vendored_diff(session, adapter, project, since, diff_to);
+                false
+            },
+            _ => false,
+        };
+        
+        if changed {
+            adapter.vendor_manager().save_review_decisions(project, session.decisions())?;
+            session.set_status(format!("Saved {} decision(s)", session.decisions().decisions.len()));
+        }
+    }
+}
+
+/// Show the vendored source diff of the selected drift item
+fn show_vendored_diff(
+    session: &mut ReviewSession,
+    adapter: &RustAdapter,
+    project: &Project,
+    since: Option<&str>,
+    diff_to: Option<&str>,
+) {
+    let Some(item) = session.selected_drift() else {
+        session.set_status("Vendored diffs are available for drift items".to_string());
+        return;
+    };
+    let (Some(since), Some(diff_to)) = (since, diff_to) else {
+        session.set_status("No later epoch to diff against (pass --to)".to_string());
+        return;
+    };
+    
+    match adapter.vendor_manager().diff_epochs(project, since, diff_to, Some(&item.package_name), true) {
+        Ok(report) => {
+            let mut lines = vec![format!("{}: {} -> {}", item.package_name, report.epoch_a, report.epoch_b)];
+            if report.changes.is_empty() {
+                lines.push("No vendored file changes".to_string());
+            }
+            for change in &report.changes {
+                lines.push(format!("{:?} {}", change.kind, change.path));
+                if let Some(diff) = &change.unified_diff {
+                    lines.extend(diff.lines().map(str::to_string));
+                }
+            }
+            session.show_detail(lines);
+        },
+        Err(e) => session.set_status(format!("Failed to diff vendored sources: {}", e)),
+    }
+}
+
+/// Wrap a terminal I/O error
+fn terminal_error(error: std::io::Error) -> AdapterError {
+    AdapterError::Internal {
+        message: "Terminal I/O failed".to_string(),
+        source: error.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    
+    #[test]
+    fn test_session_records_decisions() {
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_package(PackageNode {
+            id: uuid::Uuid::new_v4(),
+            name: "itoa".to_string(),
+            version: "1.0.10".to_string(),
+            source: PackageSource::Registry {
+                url: "https://github.com/rust-lang/crates.io-index".to_string(),
+                checksum: String::new(),
+            },
+            checksum: String::new(),
+            classification: Classification::Unknown,
+            audit_status: AuditStatus::Unaudited,
+            annotations: Vec::new(),
+        });
+        let drift = vec![DriftItem {
+            package_name: "itoa".to_string(),
+            change: TimelineChange {
+                approved_in: None,
+                previous_epoch_id: "2025-Q4-001".to_string(),
+                change_type: ChangeType::VersionChange,
+                previous_version: Some("1.0.9".to_string()),
+                current_version: Some("1.0.10".to_string()),
+                previous_source: None,
+                current_source: None,
+                priority: Priority::Low,
+            },
+        }];
+        let findings = vec![AuditFinding::new(
+            "RUSTSEC-2025-0001".to_string(),
+            "itoa".to_string(),
+            "<1.0.10".to_string(),
+            Severity::High,
+            "Example".to_string(),
+        )];
+        let mut session = ReviewSession::new(&graph, drift, findings, ReviewDecisions::new("fingerprint".to_string()), "alice");
+        
+        assert_eq!(session.tab(), ReviewTab::Drift);
+        assert!(session.decide(Some(ReviewStatus::Approved)));
+        session.switch_tab(true);
+        assert!(session.decide(Some(ReviewStatus::Rejected)));
+        session.switch_tab(true);
+        assert_eq!(session.tab(), ReviewTab::Tree);
+        assert!(!session.decide(Some(ReviewStatus::Approved)));
+        
+        let decisions = session.decisions();
+        assert_eq!(decisions.status("drift:itoa"), Some(ReviewStatus::Approved));
+        assert_eq!(decisions.status("finding:RUSTSEC-2025-0001:itoa"), Some(ReviewStatus::Rejected));
+        assert_eq!(decisions.decisions["drift:itoa"].reviewer, "alice");
+        
+        session.switch_tab(true);
+        let mut terminal = Terminal::new(TestBackend::new(100, 10)).unwrap();
+        terminal.draw(|frame| session.render(frame)).unwrap();
+        let screen: String = terminal.backend().buffer().content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Review (1 approved, 1 rejected)"));
+        assert!(screen.contains("[✓] itoa VersionChange 1.0.9 -> 1.0.10 (Low)"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Checksummed artifacts and run manifests
+//!
+//! Every artifact is written atomically together with a checksum file
+//! named after the digest algorithm (`<name>.sha256`, `<name>.blake3`) in
+//! `sha256sum` format, so a single file can be checked with `sha256sum -c`
+//! or `b3sum -c`. A run manifest lists the artifacts with sizes and
+//! digests; `verify_manifest` checks a whole transfer at once. Paths in
+//! the manifest are relative to its directory when the artifacts are
+//! below it, so the manifest stays valid when the directory is moved.
+
+use crate::error::{AdapterError, Result};
+use crate::models::{ArtifactEntry, ArtifactVerification, RunManifest};
+use super::atomic_write::{recover_partial_writes, write_atomic};
+use super::checksum::DigestConfig;
+use std::path::{Path, PathBuf};
+
+/// Path of the checksum file of an artifact
+pub fn checksum_path(path: &Path, digest: &DigestConfig) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(digest.algorithm.as_str());
+    path.with_file_name(name)
+}
+
+/// Write an artifact and its SHA-256 checksum file
+pub fn write_artifact<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<ArtifactEntry> {
+    write_artifact_with(path, contents, &DigestConfig::default())
+}
+
+/// Write an artifact and its checksum file with the given digest
+pub fn write_artifact_with<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C, digest: &DigestConfig) -> Result<ArtifactEntry> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+    write_atomic(path, contents)?;
+    
+    let checksum = digest.digest(contents);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    write_atomic(checksum_path(path, digest), format!("{}  {}\n", checksum, name))?;
+    
+    Ok(ArtifactEntry {
+        path: path.to_string_lossy().into_owned(),
+        size: contents.len() as u64,
+        checksum,
+        digest: *digest,
+    })
+}
+
+/// Add artifacts to the run manifest at `manifest_path`
+///
+/// Entries of an existing manifest are kept unless an artifact of the
+/// same path replaces them, so consecutive runs can share one manifest.
+/// The manifest is itself written with a checksum file of `digest`.
+pub fn update_manifest(manifest_path: &Path, artifacts: &[ArtifactEntry], digest: &DigestConfig) -> Result<RunManifest> {
+    let mut manifest = match manifest_path.exists() {
+        true => load_manifest(manifest_path)?,
+        false => RunManifest::new(),
+    };
+    
+    let base_dir = absolute(manifest_path.parent().unwrap_or(Path::new("")));
+    for artifact in artifacts {
+        let artifact_path = absolute(Path::new(&artifact.path));
+        let path = match artifact_path.strip_prefix(&base_dir) {
+            Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+            Err(_) => artifact_path.to_string_lossy().into_owned(),
+        };
+        manifest.record(ArtifactEntry { path, ..artifact.clone() });
+    }
+    
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| AdapterError::Internal {
+            message: "Failed to serialize run manifest".to_string(),
+            source: anyhow::anyhow!("{}", e),
+        })?;
+    write_artifact_with(manifest_path, content, digest)?;
+    
+    Ok(manifest)
+}
+
+/// Verify the artifacts listed in a run manifest
+///
+/// An artifact fails when it is missing, when its size or digest differs
+/// from the manifest, or when its checksum file disagrees with the manifest.
+pub fn verify_manifest(manifest_path: &Path) -> Result<Vec<ArtifactVerification>> {
+    let manifest = load_manifest(manifest_path)?;
+    let base_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    
+    let verifications = manifest.artifacts.iter().map(|artifact| {
+        let path = base_dir.join(&artifact.path);
+        let reason = match std::fs::read(&path) {
+            Err(e) => Some(format!("Cannot read {}: {}", path.display(), e)),
+            Ok(contents) if contents.len() as u64 != artifact.size => {
+                Some(format!("Size is {} bytes, manifest lists {}", contents.len(), artifact.size))
+            },
+            Ok(contents) => {
+                let checksum = artifact.digest.digest(&contents);
+                if checksum != artifact.checksum {
+                    Some(format!("{} is {}, manifest lists {}", artifact.digest.label(), checksum, artifact.checksum))
+                } else {
+                    match std::fs::read_to_string(checksum_path(&path, &artifact.digest)) {
+                        Ok(sidecar) if sidecar.split_whitespace().next() != Some(checksum.as_str()) => {
+                            Some("Checksum file disagrees with the manifest".to_string())
+                        },
+                        _ => None,
+                    }
+                }
+            },
+        };
+        ArtifactVerification { path: artifact.path.clone(), passed: reason.is_none(), reason }
+    }).collect();
+    
+    Ok(verifications)
+}
+
+/// Read a run manifest
+fn load_manifest(manifest_path: &Path) -> Result<RunManifest> {
+    recover_partial_writes(manifest_path);
+    let content = std::fs::read_to_string(manifest_path)
+        .map_err(|_| AdapterError::file_not_found(manifest_path, "reading run manifest"))?;
+    serde_json::from_str(&content)
+        .map_err(|e| AdapterError::MetadataParseError {
+            field: "run_manifest".to_string(),
+            value: manifest_path.display().to_string(),
+            source: anyhow::anyhow!("{}", e),
+        })
+}
+
+/// Absolute form of a path, without resolving symlinks
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_manifest_verifies_artifacts_after_transfer() {
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("out");
+        std::fs::create_dir_all(&out).unwrap();
+        
+        let sbom = write_artifact(out.join("sbom.json"), "{\"spdxVersion\":\"SPDX-2.3\"}").unwrap();
+        let drift = write_artifact(out.join("drift.json"), "{}").unwrap();
+        assert_eq!(std::fs::read_to_string(out.join("sbom.json.sha256")).unwrap(),
+            format!("{}  sbom.json\n", sbom.checksum));
+        
+        let digest = DigestConfig::default();
+        let manifest = update_manifest(&out.join("manifest.json"), &[sbom], &digest).unwrap();
+        assert_eq!(manifest.artifacts[0].path, "sbom.json");
+        let manifest = update_manifest(&out.join("manifest.json"), &[drift], &digest).unwrap();
+        assert_eq!(manifest.artifacts.len(), 2);
+        assert!(out.join("manifest.json.sha256").is_file());
+        
+        // Manifest paths are relative, so the directory can be moved
+        let moved = temp.path().join("moved");
+        std::fs::rename(&out, &moved).unwrap();
+        assert!(verify_manifest(&moved.join("manifest.json")).unwrap().iter().all(|v| v.passed));
+        
+        std::fs::write(moved.join("drift.json"), "{\"x\":1}").unwrap();
+        let verifications = verify_manifest(&moved.join("manifest.json")).unwrap();
+        let failed: Vec<&str> = verifications.iter().filter(|v| !v.passed).map(|v| v.path.as_str()).collect();
+        assert_eq!(failed, vec!["drift.json"]);
+    }
+}
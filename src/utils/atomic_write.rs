@@ -0,0 +1,232 @@
+//! Crash-safe artifact writes
+//!
+//! Artifacts are written to a temporary file next to the destination,
+//! flushed to disk, and renamed over the destination. A crash leaves either
+//! the previous file or the complete new one, never a truncated mix.
+//! Temporary files orphaned by a crash are recognised by name and removed
+//! by `recover_partial_writes` when the artifact is next loaded.
+
+use crate::error::{AdapterError, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Marker separating the artifact name from the unique suffix of its temporary file
+pub const PARTIAL_WRITE_MARKER: &str = ".partial-";
+
+/// Age after which an orphaned temporary file is assumed to belong to a crashed writer
+pub const STALE_PARTIAL_WRITE_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// File that replaces its destination atomically on `commit`
+///
+/// Dropping an uncommitted `AtomicFile` removes the temporary file and
+/// leaves the destination untouched.
+#[derive(Debug)]
+pub struct AtomicFile {
+    /// Final destination
+    path: PathBuf,
+    /// Temporary file being written
+    temp_path: PathBuf,
+    /// Open handle, `None` once committed
+    file: Option<File>,
+}
+
+impl AtomicFile {
+    /// Start writing a replacement for `path`
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let temp_path = partial_write_path(&path);
+        let file = File::create(&temp_path)
+            .map_err(|_| AdapterError::permission_denied(&temp_path, "creating temporary file"))?;
+        
+        Ok(Self {
+            path,
+            temp_path,
+            file: Some(file),
+        })
+    }
+    
+    /// Destination the file is committed to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Flush the contents to disk and move them over the destination
+    pub fn commit(mut self) -> Result<()> {
+        let mut file = match self.file.take() {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        
+        file.flush().and_then(|_| file.sync_all())
+            .map_err(|_| AdapterError::permission_denied(&self.temp_path, "flushing temporary file"))?;
+        drop(file);
+        
+        if std::fs::rename(&self.temp_path, &self.path).is_err() {
+            let _ = std::fs::remove_file(&self.temp_path);
+            return Err(AdapterError::permission_denied(&self.path, "replacing file"));
+        }
+        
+        sync_parent_dir(&self.path);
+        Ok(())
+    }
+    
+    fn file_mut(&mut self) -> std::io::Result<&mut File> {
+        self.file.as_mut()
+            .ok_or_else(|| std::io::Error::other("atomic file already committed"))
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file_mut()?.write(buf)
+    }
+    
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file_mut()?.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Write `contents` to `path` atomically
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents.as_ref())
+        .map_err(|_| AdapterError::permission_denied(&file.temp_path, "writing temporary file"))?;
+    file.commit()
+}
+
+/// Whether `path` is a temporary file of an atomic write
+pub fn is_partial_write(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') && name.contains(PARTIAL_WRITE_MARKER))
+}
+
+/// Remove temporary files left behind by interrupted writes of `path`
+///
+/// Only files older than `STALE_PARTIAL_WRITE_AGE` are removed, so a write
+/// still in progress in another process is not disturbed. Returns the
+/// removed paths. The destination itself is never touched: after a crash it
+/// still holds the last complete version, if any.
+pub fn recover_partial_writes(path: &Path) -> Vec<PathBuf> {
+    let (dir, prefix) = match (path.parent(), path.file_name().and_then(|name| name.to_str())) {
+        (Some(dir), Some(name)) => (dir, format!(".{}{}", name, PARTIAL_WRITE_MARKER)),
+        _ => return Vec::new(),
+    };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    
+    let now = SystemTime::now();
+    let mut removed = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.file_name().to_str().is_some_and(|name| name.starts_with(&prefix)) {
+            continue;
+        }
+        
+        let age = entry.metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        match age {
+            Some(age) if age >= STALE_PARTIAL_WRITE_AGE => {},
+            _ => continue,
+        }
+        
+        if std::fs::remove_file(entry.path()).is_ok() {
+            tracing::warn!(path = %entry.path().display(), "Removed partial write left by an interrupted run");
+            removed.push(entry.path());
+        }
+    }
+    
+    removed
+}
+
+/// Temporary file used while writing `path`
+fn partial_write_path(path: &Path) -> PathBuf {
+    let name = path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}{}{}", name, PARTIAL_WRITE_MARKER, uuid::Uuid::new_v4().simple()))
+}
+
+/// Persist the rename itself by syncing the containing directory
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("sbom.json");
+        
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+    
+    #[test]
+    fn test_uncommitted_file_leaves_destination_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.json");
+        write_atomic(&path, "complete").unwrap();
+        
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"trunc").unwrap();
+        drop(file);
+        
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "complete");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+    
+    #[test]
+    fn test_recover_partial_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("manifest.json");
+        let stale = temp_dir.path().join(format!(".manifest.json{}crashed", PARTIAL_WRITE_MARKER));
+        let fresh = temp_dir.path().join(format!(".manifest.json{}running", PARTIAL_WRITE_MARKER));
+        let other = temp_dir.path().join(format!(".other.json{}crashed", PARTIAL_WRITE_MARKER));
+        for file in [&stale, &fresh, &other] {
+            std::fs::write(file, "partial").unwrap();
+        }
+        
+        let old = SystemTime::now() - STALE_PARTIAL_WRITE_AGE - Duration::from_secs(60);
+        for file in [&stale, &other] {
+            File::options().write(true).open(file).unwrap().set_modified(old).unwrap();
+        }
+        
+        assert!(is_partial_write(&stale));
+        assert!(!is_partial_write(&path));
+        assert_eq!(recover_partial_writes(&path), vec![stale.clone()]);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(other.exists());
+    }
+}
@@ -2,42 +2,287 @@
 //! 
 //! This module provides utilities for calculating
 //! various types of checksums for integrity verification.
+//!
+//! The digest written into artifact checksum files, run manifests and
+//! vendor archive manifests is set by [`DigestConfig`]. Each of those
+//! records its algorithm and encoding, so files written with one setting
+//! still verify after it changes. Cargo.lock checksums are always SHA-256.
 
 use crate::error::{AdapterError, Result};
-use sha2::{Digest, Sha256};
+use crate::utils::lockfile::CanonicalLockfile;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
+/// Algorithm of the checksums Cargo.lock records for registry packages
+pub const LOCKFILE_CHECKSUM_ALGORITHM: ChecksumAlgorithm = ChecksumAlgorithm::Sha256;
+
 /// Checksum calculator for various algorithms
 #[derive(Debug, Clone)]
 pub struct ChecksumCalculator {
     /// Default algorithm to use
     default_algorithm: ChecksumAlgorithm,
+    /// Encoding of calculated checksums
+    encoding: DigestEncoding,
 }
 
 /// Supported checksum algorithms
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
 pub enum ChecksumAlgorithm {
     /// SHA-256
+    #[default]
     Sha256,
     /// SHA-512
     Sha512,
     /// MD5 (legacy, not recommended for security)
     Md5,
+    /// BLAKE3, much faster than SHA-2 on large directories
+    Blake3,
+}
+
+/// Text encoding of digests
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestEncoding {
+    /// Lowercase hexadecimal
+    #[default]
+    Hex,
+    /// URL-safe base64 without padding, usable in file names
+    Base64,
+}
+
+/// Digest algorithm and encoding of written checksums
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Hash algorithm
+    pub algorithm: ChecksumAlgorithm,
+    /// Text encoding of the hash
+    pub encoding: DigestEncoding,
+}
+
+/// Incremental hasher of any supported algorithm
+#[derive(Debug, Clone)]
+pub enum Hasher {
+    /// SHA-256 state
+    Sha256(Sha256),
+    /// SHA-512 state
+    Sha512(Sha512),
+    /// MD5 state
+    Md5(md5::Md5),
+    /// BLAKE3 state
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl ChecksumAlgorithm {
+    /// Every supported algorithm
+    pub const ALL: [Self; 4] = [Self::Sha256, Self::Sha512, Self::Md5, Self::Blake3];
+    
+    /// Algorithm name as used in configuration and checksum file extensions
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+            Self::Md5 => "md5",
+            Self::Blake3 => "blake3",
+        }
+    }
+    
+    /// Label of the algorithm in SPDX 2.x checksums
+    pub fn spdx_label(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "SHA256",
+            Self::Sha512 => "SHA512",
+            Self::Md5 => "MD5",
+            Self::Blake3 => "BLAKE3",
+        }
+    }
+    
+    /// Label of the algorithm in CycloneDX hashes
+    pub fn cyclonedx_label(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "SHA-256",
+            Self::Sha512 => "SHA-512",
+            Self::Md5 => "MD5",
+            Self::Blake3 => "BLAKE3",
+        }
+    }
+    
+    /// Whether the algorithm is broken for integrity checks
+    ///
+    /// MD5 collisions are cheap to produce, so MD5 digests only identify
+    /// content and must not be trusted to verify it.
+    pub fn is_weak(&self) -> bool {
+        matches!(self, Self::Md5)
+    }
+    
+    /// Length of the raw digest in bytes
+    pub fn digest_len(&self) -> usize {
+        match self {
+            Self::Sha256 | Self::Blake3 => 32,
+            Self::Sha512 => 64,
+            Self::Md5 => 16,
+        }
+    }
+}
+
+impl std::str::FromStr for ChecksumAlgorithm {
+    type Err = String;
+    
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(Self::Sha256),
+            "sha512" => Ok(Self::Sha512),
+            "md5" => Ok(Self::Md5),
+            "blake3" => Ok(Self::Blake3),
+            _ => Err(format!("Unknown checksum algorithm '{}'. Valid algorithms: sha256, sha512, md5, blake3", s)),
+        }
+    }
+}
+
+impl DigestEncoding {
+    /// Encode a raw digest
+    pub fn encode(&self, digest: &[u8]) -> String {
+        match self {
+            Self::Hex => digest.iter().map(|b| format!("{:02x}", b)).collect(),
+            Self::Base64 => base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest),
+        }
+    }
+}
+
+impl DigestConfig {
+    /// SHA-256 in hex, the digest of files written before it was configurable
+    pub const SHA256_HEX: Self = Self { algorithm: ChecksumAlgorithm::Sha256, encoding: DigestEncoding::Hex };
+    
+    /// Digest of `algorithm` in hex
+    pub fn hex(algorithm: ChecksumAlgorithm) -> Self {
+        Self { algorithm, encoding: DigestEncoding::Hex }
+    }
+    
+    /// Start an incremental digest
+    pub fn hasher(&self) -> Hasher {
+        Hasher::new(self.algorithm)
+    }
+    
+    /// Encoded digest of a finished hasher
+    pub fn finish(&self, hasher: Hasher) -> String {
+        self.encoding.encode(&hasher.finalize())
+    }
+    
+    /// Encoded digest of `data`
+    pub fn digest(&self, data: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        self.finish(hasher)
+    }
+    
+    /// Encoded digest of everything `reader` yields, read `buffer_size` bytes at a time
+    pub fn digest_reader<R: Read>(&self, mut reader: R, buffer_size: usize) -> std::io::Result<String> {
+        let mut hasher = self.hasher();
+        let mut buffer = vec![0u8; buffer_size.max(1)];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(self.finish(hasher))
+    }
+    
+    /// Whether `digest` has the length and characters of this digest
+    ///
+    /// Digests passing this check are safe to use as file names.
+    pub fn is_well_formed(&self, digest: &str) -> bool {
+        let len = self.algorithm.digest_len();
+        match self.encoding {
+            DigestEncoding::Hex => digest.len() == len * 2 && digest.bytes().all(|b| b.is_ascii_hexdigit()),
+            DigestEncoding::Base64 => {
+                digest.len() == (len * 4).div_ceil(3)
+                    && digest.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+            },
+        }
+    }
+    
+    /// Check that the digest can be trusted to verify content
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.algorithm.is_weak() {
+            return Err(format!("{} is not collision resistant; use sha256, sha512 or blake3", self.algorithm.as_str()));
+        }
+        Ok(())
+    }
+    
+    /// Short name of the digest, e.g. `sha256` or `blake3-base64`
+    pub fn label(&self) -> String {
+        match self.encoding {
+            DigestEncoding::Hex => self.algorithm.as_str().to_string(),
+            DigestEncoding::Base64 => format!("{}-base64", self.algorithm.as_str()),
+        }
+    }
+}
+
+impl Hasher {
+    /// Start a digest with `algorithm`
+    pub fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+            ChecksumAlgorithm::Md5 => Self::Md5(md5::Md5::new()),
+            ChecksumAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+    
+    /// Feed data into the digest
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha512(hasher) => hasher.update(data),
+            Self::Md5(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            },
+        }
+    }
+    
+    /// Raw digest of the data fed so far
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Sha512(hasher) => hasher.finalize().to_vec(),
+            Self::Md5(hasher) => hasher.finalize().to_vec(),
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        }
+    }
 }
 
 impl ChecksumCalculator {
     /// Create new checksum calculator
     pub fn new() -> Self {
-        Self {
-            default_algorithm: ChecksumAlgorithm::Sha256,
-        }
+        Self::with_config(DigestConfig::default())
     }
     
     /// Create checksum calculator with specific algorithm
     pub fn with_algorithm(algorithm: ChecksumAlgorithm) -> Self {
+        Self::with_config(DigestConfig::hex(algorithm))
+    }
+    
+    /// Create checksum calculator with a configured algorithm and encoding
+    pub fn with_config(config: DigestConfig) -> Self {
         Self {
-            default_algorithm: algorithm,
+            default_algorithm: config.algorithm,
+            encoding: config.encoding,
+        }
+    }
+    
+    /// Digest settings for `algorithm`, or the default one
+    fn digest_config(&self, algorithm: Option<ChecksumAlgorithm>) -> DigestConfig {
+        DigestConfig {
+            algorithm: algorithm.unwrap_or(self.default_algorithm),
+            encoding: self.encoding,
         }
     }
     
@@ -46,31 +291,12 @@ impl ChecksumCalculator {
     where
         P: AsRef<Path>,
     {
-        let algorithm = algorithm.unwrap_or_else(|| self.default_algorithm.clone());
         let path = path.as_ref();
+        let file = fs::File::open(path)
+            .map_err(|_| AdapterError::permission_denied(path, "reading file for checksum"))?;
         
-        let content = fs::read(path)
-            .map_err(|e| AdapterError::permission_denied(path, "reading file for checksum"))?;
-        
-        match algorithm {
-            ChecksumAlgorithm::Sha256 => {
-                let mut hasher = Sha256::new();
-                hasher.update(&content);
-                Ok(format!("{:x}", hasher.finalize()))
-            },
-            ChecksumAlgorithm::Sha512 => {
-                use sha2::Sha512;
-                let mut hasher = Sha512::new();
-                hasher.update(&content);
-                Ok(format!("{:x}", hasher.finalize()))
-            },
-            ChecksumAlgorithm::Md5 => {
-                use md5::Md5;
-                let mut hasher = Md5::new();
-                hasher.update(&content);
-                Ok(format!("{:x}", hasher.finalize()))
-            },
-        }
+        self.digest_config(algorithm).digest_reader(file, 64 * 1024)
+            .map_err(|_| AdapterError::permission_denied(path, "reading file for checksum"))
     }
     
     /// Calculate checksum for directory recursively
@@ -78,51 +304,17 @@ impl ChecksumCalculator {
     where
         P: AsRef<Path>,
     {
-        let algorithm = algorithm.unwrap_or_else(|| self.default_algorithm.clone());
+        let config = self.digest_config(algorithm);
         let path = path.as_ref();
         
-        let mut hasher = match algorithm {
-            ChecksumAlgorithm::Sha256 => {
-                let mut h = Sha256::new();
-                // Update with directory path for deterministic ordering
-                h.update(path.to_string_lossy().as_bytes());
-                h
-            },
-            ChecksumAlgorithm::Sha512 => {
-                use sha2::Sha512;
-                let mut h = Sha512::new();
-                h.update(path.to_string_lossy().as_bytes());
-                h
-            },
-            ChecksumAlgorithm::Md5 => {
-                use md5::Md5;
-                let mut h = Md5::new();
-                h.update(path.to_string_lossy().as_bytes());
-                h
-            },
-        };
+        let mut hasher = config.hasher();
+        // Update with directory path for deterministic ordering
+        hasher.update(path.to_string_lossy().as_bytes());
         
         // Walk directory and hash all files
         self.walk_and_hash_directory(path, &mut hasher)?;
         
-        let checksum = match algorithm {
-            ChecksumAlgorithm::Sha256 => {
-                let h: Sha256 = hasher;
-                format!("{:x}", h.finalize())
-            },
-            ChecksumAlgorithm::Sha512 => {
-                use sha2::Sha512;
-                let h: Sha512 = hasher;
-                format!("{:x}", h.finalize())
-            },
-            ChecksumAlgorithm::Md5 => {
-                use md5::Md5;
-                let h: Md5 = hasher;
-                format!("{:x}", h.finalize())
-            },
-        };
-        
-        Ok(checksum)
+        Ok(config.finish(hasher))
     }
     
     /// Verify file checksum
@@ -134,10 +326,33 @@ impl ChecksumCalculator {
         Ok(actual == expected)
     }
     
+    /// Calculate the canonical fingerprint of a Cargo.lock file
+    pub fn calculate_lockfile_fingerprint<P>(&self, path: P) -> Result<String>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        
+        let content = fs::read_to_string(path)
+            .map_err(|_e| AdapterError::file_not_found(path, "reading Cargo.lock for fingerprint"))?;
+        
+        Self::lockfile_fingerprint(&content)
+    }
+    
+    /// Calculate the canonical fingerprint of Cargo.lock content
+    ///
+    /// SHA-256 over the canonical lockfile (see [`CanonicalLockfile`]).
+    /// Comments, formatting, entry order, the lockfile format version and
+    /// the spelling of source URLs do not affect the result, so equal
+    /// fingerprints mean equal dependency state.
+    pub fn lockfile_fingerprint(content: &str) -> Result<String> {
+        Ok(CanonicalLockfile::parse(content)?.fingerprint())
+    }
+    
     /// Walk directory and update hasher
-    fn walk_and_hash_directory(&self, path: &Path, hasher: &mut dyn digest::Digest) -> Result<()> {
+    fn walk_and_hash_directory(&self, path: &Path, hasher: &mut Hasher) -> Result<()> {
         let entries = fs::read_dir(path)
-            .map_err(|e| AdapterError::permission_denied(path, "reading directory"))?;
+            .map_err(|_| AdapterError::permission_denied(path, "reading directory"))?;
         
         let mut file_paths = Vec::new();
         
@@ -148,7 +363,7 @@ impl ChecksumCalculator {
                 file_paths.push(entry_path);
             } else if entry_path.is_dir() {
                 // Recursively process subdirectories
-                self.walk_and_hash_directory(entry_path, hasher)?;
+                self.walk_and_hash_directory(&entry_path, hasher)?;
             }
         }
         
@@ -158,7 +373,7 @@ impl ChecksumCalculator {
         // Hash each file
         for file_path in file_paths {
             let content = fs::read(&file_path)
-                .map_err(|e| AdapterError::permission_denied(&file_path, "reading file for checksum"))?;
+                .map_err(|_| AdapterError::permission_denied(&file_path, "reading file for checksum"))?;
             
             hasher.update(file_path.to_string_lossy().as_bytes());
             hasher.update(&content);
@@ -180,6 +395,51 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
     
+    #[test]
+    fn test_lockfile_fingerprint_is_canonical() -> Result<()> {
+        let lockfile = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "serde",
+ "itoa",
+]
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "af150ab688ff2122fcef229be89cb50dd66af9e01a4ff320cc137eecc9bacc38"
+"#;
+        
+        let reordered = r#"
+# This file is automatically @generated by Cargo.
+version = 4
+
+[[package]]
+name = "itoa"
+checksum = "af150ab688ff2122fcef229be89cb50dd66af9e01a4ff320cc137eecc9bacc38"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+version = "1.0.9"
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["itoa", "serde"]
+"#;
+        
+        let fingerprint = ChecksumCalculator::lockfile_fingerprint(lockfile)?;
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(fingerprint, ChecksumCalculator::lockfile_fingerprint(reordered)?);
+        assert_ne!(fingerprint, ChecksumCalculator::lockfile_fingerprint(&lockfile.replace("1.0.9", "1.0.10"))?);
+        assert!(ChecksumCalculator::lockfile_fingerprint("[[package").is_err());
+        
+        Ok(())
+    }
+    
     #[test]
     fn test_checksum_calculator_creation() {
         let calculator = ChecksumCalculator::new();
@@ -190,7 +450,7 @@ mod tests {
     }
     
     #[test]
-    fn test_file_checksum() -> Result<()> {
+    fn test_file_checksum() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let calculator = ChecksumCalculator::new();
         
         // Create temporary file
@@ -209,7 +469,7 @@ mod tests {
     }
     
     #[test]
-    fn test_file_checksum_verification() -> Result<()> {
+    fn test_file_checksum_verification() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let calculator = ChecksumCalculator::new();
         
         // Create temporary file
@@ -230,7 +490,7 @@ mod tests {
     }
     
     #[test]
-    fn test_directory_checksum() -> Result<()> {
+    fn test_directory_checksum() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let calculator = ChecksumCalculator::new();
         
         let temp_dir = tempfile::tempdir()?;
@@ -258,7 +518,7 @@ mod tests {
     }
     
     #[test]
-    fn test_different_algorithms() -> Result<()> {
+    fn test_different_algorithms() -> std::result::Result<(), Box<dyn std::error::Error>> {
         let sha256_calculator = ChecksumCalculator::with_algorithm(ChecksumAlgorithm::Sha256);
         let md5_calculator = ChecksumCalculator::with_algorithm(ChecksumAlgorithm::Md5);
         
@@ -280,4 +540,27 @@ mod tests {
         
         Ok(())
     }
+    
+    #[test]
+    fn test_digest_config() {
+        let blake3 = DigestConfig::hex(ChecksumAlgorithm::Blake3);
+        let digest = blake3.digest(b"Hello, world!");
+        assert_eq!(digest, "ede5c0b10f2ec4979c69b52f61e42ff5b413519ce09be0f14d098dcfe5f6f98d");
+        assert_eq!(blake3.digest_reader(&b"Hello, world!"[..], 3).unwrap(), digest);
+        assert!(blake3.is_well_formed(&digest));
+        
+        let base64 = DigestConfig { algorithm: ChecksumAlgorithm::Sha256, encoding: DigestEncoding::Base64 };
+        let digest = base64.digest(b"Hello, world!");
+        assert_eq!(digest, "MV9b23bQeMQ7isAGTkoBZGErH853yGk0W_yUx1iU7dM");
+        assert!(base64.is_well_formed(&digest));
+        assert!(!base64.is_well_formed("../../etc/passwd"));
+        
+        let config: DigestConfig = serde_json::from_str(r#"{"algorithm": "blake3"}"#).unwrap();
+        assert_eq!(config, blake3);
+        assert_eq!("SHA-512".parse::<ChecksumAlgorithm>(), Ok(ChecksumAlgorithm::Sha512));
+        assert_eq!(ChecksumAlgorithm::Blake3.cyclonedx_label(), "BLAKE3");
+        
+        assert!(blake3.validate().is_ok());
+        assert!(DigestConfig::hex(ChecksumAlgorithm::Md5).validate().unwrap_err().contains("md5"));
+    }
 }
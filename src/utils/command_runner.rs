@@ -1,12 +1,23 @@
 //! Command runner utility
 //! 
 //! This module provides utilities for running external commands
-//! with proper timeout handling and error management.
+//! with proper timeout handling, cancellation and error management.
 
 use crate::error::{AdapterError, Result};
-use std::process::{Command, Output, Stdio};
+use crate::utils::resolve_program;
+use std::process::{Output, Stdio};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command as AsyncCommand;
+use tokio_util::sync::CancellationToken;
+
+/// Why a running command was stopped before it exited
+enum Stopped {
+    TimedOut,
+    Cancelled,
+    OutputLimit,
+}
 
 /// Command runner for external tool execution
 #[derive(Debug, Clone)]
@@ -15,6 +26,8 @@ pub struct CommandRunner {
     default_timeout: Duration,
     /// Whether to run in offline mode
     offline_mode: bool,
+    /// Token that stops running commands when cancelled
+    cancel: CancellationToken,
 }
 
 impl CommandRunner {
@@ -23,9 +36,16 @@ impl CommandRunner {
         Self {
             default_timeout,
             offline_mode,
+            cancel: CancellationToken::new(),
         }
     }
     
+    /// Stop running commands when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
     /// Run command with default timeout
     pub async fn run(&self, command: &str, args: &[&str]) -> Result<Output> {
         self.run_with_timeout(command, args, self.default_timeout).await
@@ -41,25 +61,12 @@ impl CommandRunner {
             });
         }
         
-        let mut cmd = AsyncCommand::new(command);
+        let mut cmd = AsyncCommand::new(resolve_program(command));
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
         
-        // Add timeout
-        let output = tokio::time::timeout(timeout, cmd.output()).await
-            .map_err(|_| AdapterError::ToolTimeout {
-                tool: command.to_string(),
-                timeout,
-                source: anyhow::anyhow!("Command timed out"),
-            })?;
-        
-        let output = output.map_err(|e| AdapterError::ToolExecutionFailed {
-            tool: command.to_string(),
-            exit_code: -1,
-            stderr: e.to_string(),
-            source: anyhow::anyhow!("Failed to execute command"),
-        })?;
+        let output = Self::execute(cmd, command, Some(timeout), &self.cancel).await?;
         
         if !output.status.success() {
             return Err(AdapterError::ToolExecutionFailed {
@@ -73,6 +80,152 @@ impl CommandRunner {
         Ok(output)
     }
     
+    /// Run a prepared command until it exits, times out or is cancelled
+    ///
+    /// Piped stdout and stderr are drained while the command runs. On timeout
+    /// or cancellation the child is killed and reaped before returning, and a
+    /// `ToolTimeout` error carries the stdout produced so far. The exit status
+    /// is not checked.
+    pub async fn execute(
+        cmd: AsyncCommand,
+        tool: &str,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> Result<Output> {
+        Self::execute_bounded(cmd, tool, timeout, cancel, None).await?
+            .ok_or_else(|| AdapterError::Internal {
+                message: format!("{} stopped without an output limit", tool),
+                source: anyhow::anyhow!("Unbounded command reported an output overflow"),
+            })
+    }
+    
+    /// Run a prepared command, killing it once stdout grows past `stdout_limit` bytes
+    ///
+    /// Behaves like [`CommandRunner::execute`] but never buffers more than
+    /// `stdout_limit` bytes of stdout. Returns `Ok(None)` when the child was
+    /// killed because the limit was exceeded.
+    pub async fn execute_with_limit(
+        cmd: AsyncCommand,
+        tool: &str,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+        stdout_limit: u64,
+    ) -> Result<Option<Output>> {
+        Self::execute_bounded(cmd, tool, timeout, cancel, Some(stdout_limit)).await
+    }
+    
+    /// Shared body of `execute` and `execute_with_limit`
+    async fn execute_bounded(
+        mut cmd: AsyncCommand,
+        tool: &str,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+        stdout_limit: Option<u64>,
+    ) -> Result<Option<Output>> {
+        if cancel.is_cancelled() {
+            return Err(AdapterError::cancelled(tool));
+        }
+        
+        let program = cmd.as_std().get_program().to_string_lossy().to_string();
+        let mut child = cmd.kill_on_drop(true).spawn()
+            .map_err(|_| AdapterError::tool_not_found(&program))?;
+        
+        let stdout = Arc::new(Mutex::new(Vec::new()));
+        let stderr = Arc::new(Mutex::new(Vec::new()));
+        let overflow = CancellationToken::new();
+        let stdout_bound = stdout_limit.map(|limit| (limit, overflow.clone()));
+        let stdout_reader = tokio::spawn(Self::drain(child.stdout.take(), stdout.clone(), stdout_bound));
+        let stderr_reader = tokio::spawn(Self::drain(child.stderr.take(), stderr.clone(), None));
+        
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        
+        let stopped = tokio::select! {
+            status = child.wait() => {
+                let status = status.map_err(|e| AdapterError::ToolExecutionFailed {
+                    tool: tool.to_string(),
+                    exit_code: -1,
+                    stderr: e.to_string(),
+                    source: anyhow::anyhow!("Failed to execute command"),
+                })?;
+                let _ = tokio::join!(stdout_reader, stderr_reader);
+                if overflow.is_cancelled() {
+                    return Ok(None);
+                }
+                return Ok(Some(Output {
+                    status,
+                    stdout: Self::take_buffer(&stdout),
+                    stderr: Self::take_buffer(&stderr),
+                }));
+            },
+            _ = deadline => Stopped::TimedOut,
+            _ = cancel.cancelled() => Stopped::Cancelled,
+            _ = overflow.cancelled() => Stopped::OutputLimit,
+        };
+        
+        // Kill and reap the child so it does not outlive the operation
+        if let Err(e) = child.kill().await {
+            tracing::warn!(tool, error = %e, "Failed to kill child process");
+        }
+        stdout_reader.abort();
+        stderr_reader.abort();
+        
+        match stopped {
+            Stopped::OutputLimit => Ok(None),
+            Stopped::Cancelled => Err(AdapterError::cancelled(tool)),
+            Stopped::TimedOut => Err(AdapterError::ToolTimeout {
+                tool: tool.to_string(),
+                timeout: timeout.unwrap_or_default(),
+                partial_output: String::from_utf8_lossy(&Self::take_buffer(&stdout)).to_string(),
+                source: anyhow::anyhow!("Command timed out"),
+            }),
+        }
+    }
+    
+    /// Copy a child pipe into a shared buffer until it closes
+    ///
+    /// With a `bound`, reading stops and the token is cancelled as soon as
+    /// the buffer would grow past the limit.
+    async fn drain<R: AsyncRead + Unpin>(
+        reader: Option<R>,
+        buffer: Arc<Mutex<Vec<u8>>>,
+        bound: Option<(u64, CancellationToken)>,
+    ) {
+        let mut reader = match reader {
+            Some(reader) => reader,
+            None => return,
+        };
+        
+        let mut total = 0u64;
+        let mut chunk = [0u8; 8192];
+        loop {
+            match reader.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    total += read as u64;
+                    if let Some((limit, overflow)) = &bound {
+                        if total > *limit {
+                            overflow.cancel();
+                            break;
+                        }
+                    }
+                    if let Ok(mut buffer) = buffer.lock() {
+                        buffer.extend_from_slice(&chunk[..read]);
+                    }
+                },
+            }
+        }
+    }
+    
+    /// Take the bytes collected from a child pipe
+    fn take_buffer(buffer: &Mutex<Vec<u8>>) -> Vec<u8> {
+        buffer.lock().map(|mut buffer| std::mem::take(&mut *buffer)).unwrap_or_default()
+    }
+    
     /// Check if command is a network operation
     fn is_network_command(&self, command: &str) -> bool {
         match command {
@@ -185,4 +338,52 @@ mod tests {
             _ => panic!("Expected ToolTimeout error"),
         }
     }
+    
+    #[tokio::test]
+    async fn test_timeout_keeps_partial_output() {
+        let mut cmd = AsyncCommand::new("sh");
+        cmd.args(["-c", "echo partial; sleep 5"]).stdout(Stdio::piped());
+        
+        let result = CommandRunner::execute(cmd, "sh", Some(Duration::from_millis(500)), &CancellationToken::new()).await;
+        match result.unwrap_err() {
+            AdapterError::ToolTimeout { partial_output, .. } => assert_eq!(partial_output.trim(), "partial"),
+            other => panic!("Expected ToolTimeout error, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_output_limit_kills_command() {
+        let mut cmd = AsyncCommand::new("sh");
+        cmd.args(["-c", "yes; sleep 30"]).stdout(Stdio::piped());
+        
+        let started = std::time::Instant::now();
+        let result = CommandRunner::execute_with_limit(cmd, "sh", Some(Duration::from_secs(30)), &CancellationToken::new(), 64 * 1024).await;
+        assert!(matches!(result, Ok(None)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        
+        let mut cmd = AsyncCommand::new("echo");
+        cmd.arg("small").stdout(Stdio::piped());
+        let output = CommandRunner::execute_with_limit(cmd, "echo", None, &CancellationToken::new(), 64 * 1024).await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.unwrap().stdout).trim(), "small");
+    }
+    
+    #[tokio::test]
+    async fn test_cancellation_kills_command() {
+        let cancel = CancellationToken::new();
+        let runner = CommandRunner::new(Duration::from_secs(30), false).with_cancellation_token(cancel.clone());
+        
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            trigger.cancel();
+        });
+        
+        let started = std::time::Instant::now();
+        let result = runner.run("sleep", &["30"]).await;
+        assert!(matches!(result, Err(AdapterError::Cancelled { .. })));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        
+        // Already cancelled tokens refuse to start new commands
+        assert!(matches!(runner.run("echo", &["late"]).await, Err(AdapterError::Cancelled { .. })));
+    }
 }
@@ -0,0 +1,156 @@
+//! Per-operation deadline budgets
+//!
+//! [`enforce`] runs an operation within its budget from `deadline_config`.
+//! While it runs, components report how far they got with [`set_stage`],
+//! [`set_total`] and [`advance`], and long synchronous loops call [`check`]
+//! to stop as soon as the budget is spent. An operation that runs out of
+//! time fails with [`AdapterError::DeadlineExceeded`] carrying the progress
+//! reported so far.
+//!
+//! Outside an enforced operation the reporting functions do nothing.
+
+use crate::error::{AdapterError, Result};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    /// Deadline of the operation running on the current task
+    static CURRENT: Arc<DeadlineState>;
+}
+
+/// Budget and progress of one running operation
+#[derive(Debug)]
+struct DeadlineState {
+    operation: &'static str,
+    budget: Duration,
+    started: Instant,
+    stage: Mutex<String>,
+    completed: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl DeadlineState {
+    fn new(operation: &'static str, budget: Duration) -> Self {
+        Self {
+            operation,
+            budget,
+            started: Instant::now(),
+            stage: Mutex::new("starting".to_string()),
+            completed: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+        }
+    }
+    
+    fn is_expired(&self) -> bool {
+        self.started.elapsed() >= self.budget
+    }
+    
+    /// Error describing how far the operation got
+    fn exceeded(&self) -> AdapterError {
+        let stage = self.stage.lock().map(|stage| stage.clone()).unwrap_or_default();
+        AdapterError::deadline_exceeded(
+            self.operation,
+            self.budget,
+            &stage,
+            self.completed.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Run `future` as `operation`, failing once `budget` has elapsed
+///
+/// Without a budget the future runs unbounded, but progress reporting
+/// still works the same way.
+pub async fn enforce<T, F>(operation: &'static str, budget: Option<Duration>, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let Some(budget) = budget else {
+        return future.await;
+    };
+    
+    let state = Arc::new(DeadlineState::new(operation, budget));
+    match tokio::time::timeout(budget, CURRENT.scope(state.clone(), future)).await {
+        Ok(result) => result,
+        Err(_) => {
+            let error = state.exceeded();
+            tracing::warn!(operation, budget = ?budget, "{}", error);
+            Err(error)
+        },
+    }
+}
+
+/// Fail if the current operation has used up its budget
+///
+/// Called between units of synchronous work, which the timer in
+/// [`enforce`] cannot interrupt.
+pub fn check() -> Result<()> {
+    CURRENT.try_with(|state| match state.is_expired() {
+        true => Err(state.exceeded()),
+        false => Ok(()),
+    }).unwrap_or(Ok(()))
+}
+
+/// Time left in the current operation's budget
+///
+/// `None` outside an enforced operation.
+pub fn remaining() -> Option<Duration> {
+    CURRENT.try_with(|state| state.budget.saturating_sub(state.started.elapsed())).ok()
+}
+
+/// Name the step the current operation is in
+pub fn set_stage(stage: &str) {
+    let _ = CURRENT.try_with(|state| {
+        if let Ok(mut current) = state.stage.lock() {
+            *current = stage.to_string();
+        }
+        state.completed.store(0, Ordering::Relaxed);
+        state.total.store(0, Ordering::Relaxed);
+    });
+}
+
+/// Set the number of items the current step processes
+pub fn set_total(total: usize) {
+    let _ = CURRENT.try_with(|state| state.total.store(total, Ordering::Relaxed));
+}
+
+/// Record `count` more items processed by the current step
+pub fn advance(count: usize) {
+    let _ = CURRENT.try_with(|state| state.completed.fetch_add(count, Ordering::Relaxed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_deadline_reports_progress() {
+        let result: Result<()> = enforce("verify", Some(Duration::from_millis(50)), async {
+            set_stage("checksums");
+            set_total(10);
+            advance(4);
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        }).await;
+        
+        match result {
+            Err(AdapterError::DeadlineExceeded { operation, stage, completed, total, .. }) => {
+                assert_eq!(operation, "verify");
+                assert_eq!(stage, "checksums");
+                assert_eq!((completed, total), (4, 10));
+            },
+            other => panic!("expected deadline error, got {:?}", other),
+        }
+        
+        // Synchronous work stops at the next check once the budget is spent
+        let result: Result<()> = enforce("classify", Some(Duration::ZERO), async { check() }).await;
+        assert!(matches!(result, Err(AdapterError::DeadlineExceeded { .. })));
+        
+        // Without a budget, or outside an operation, nothing is enforced
+        assert!(enforce("parse", None, async { check() }).await.is_ok());
+        assert!(check().is_ok());
+    }
+}
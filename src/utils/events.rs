@@ -0,0 +1,135 @@
+//! NDJSON progress events
+//!
+//! An [`EventSink`] opened on a stream is handed to the components that
+//! report progress (see `RustAdapter::with_event_sink`), which write
+//! through [`EventSink::emit`] and [`EventSink::stage`]: one JSON object
+//! per line, flushed immediately so a reader tailing the stream sees each
+//! event as it happens. A disabled sink, the default, does nothing.
+
+use crate::error::{AdapterError, Result};
+use crate::models::{AdapterEvent, EventRecord};
+use std::future::Future;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Stream of NDJSON events, shared by the clones of a sink
+#[derive(Clone, Default)]
+pub struct EventSink {
+    writer: Option<Arc<Mutex<Box<dyn Write + Send>>>>,
+}
+
+impl std::fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSink").field("enabled", &self.is_enabled()).finish()
+    }
+}
+
+impl EventSink {
+    /// Sink discarding every event
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+    
+    /// Stream events to a new file at `target`
+    pub fn create(target: &Path) -> Result<Self> {
+        let file = std::fs::File::create(target)
+            .map_err(|_| AdapterError::permission_denied(target, "creating events stream"))?;
+        Ok(Self::from_writer(Box::new(file)))
+    }
+    
+    /// Stream events to `writer`
+    pub fn from_writer(writer: Box<dyn Write + Send>) -> Self {
+        Self { writer: Some(Arc::new(Mutex::new(writer))) }
+    }
+    
+    /// Whether events are written anywhere
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+    
+    /// Write one event to the stream
+    ///
+    /// A failed write is logged and does not fail the running command.
+    pub fn emit(&self, event: AdapterEvent) {
+        let Some(writer) = &self.writer else { return };
+        let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let result = serde_json::to_string(&EventRecord::now(event))
+            .map_err(std::io::Error::from)
+            .and_then(|line| {
+                writeln!(writer, "{}", line)?;
+                writer.flush()
+            });
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "Failed to write event");
+        }
+    }
+    
+    /// Run `future` as `stage`, emitting its start and its outcome
+    pub async fn stage<T, F>(&self, stage: &'static str, future: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.emit(AdapterEvent::StageStarted { stage: stage.to_string() });
+        let started = Instant::now();
+        let result = future.await;
+        self.emit(AdapterEvent::StageFinished {
+            stage: stage.to_string(),
+            outcome: match &result {
+                Ok(_) => "ok".to_string(),
+                Err(e) => e.error_code().to_string(),
+            },
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Writer appending to a buffer the test keeps a handle to
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+    
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_events_are_streamed_as_ndjson() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let sink = EventSink::from_writer(Box::new(SharedBuffer(buffer.clone())));
+        assert!(sink.is_enabled());
+        assert!(!EventSink::disabled().is_enabled());
+        
+        let result: Result<()> = sink.stage("events-test", async {
+            sink.clone().emit(AdapterEvent::VerificationFailed { subject: "serde@1.0.0".to_string(), reason: "Checksum mismatch".to_string() });
+            Err(AdapterError::cancelled("audit"))
+        }).await;
+        assert!(result.is_err());
+        
+        // Dropping every handle closes the stream
+        drop(sink);
+        let output = String::from_utf8(Arc::try_unwrap(buffer).unwrap().into_inner().unwrap()).unwrap();
+        let events: Vec<serde_json::Value> = output.lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["event"], "stage_started");
+        assert_eq!(events[1]["event"], "verification_failed");
+        assert_eq!(events[1]["subject"], "serde@1.0.0");
+        assert_eq!(events[2]["event"], "stage_finished");
+        assert_eq!(events[2]["outcome"], AdapterError::cancelled("audit").error_code());
+        assert!(events[2]["timestamp"].is_string());
+    }
+}
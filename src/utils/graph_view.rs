@@ -0,0 +1,340 @@
+//! Terminal views of a dependency graph
+//!
+//! Renders a `DependencyGraph` as a `cargo tree`-style tree or as a flat
+//! table. Filters select the packages of interest; the tree keeps the
+//! dependency paths leading to a selected package so that it is shown in
+//! context. Packages whose dependencies were already listed elsewhere in
+//! the tree are marked with `(*)` and not expanded again.
+
+use crate::models::*;
+use std::collections::{HashMap, HashSet};
+
+/// Classification selected by a graph filter
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassificationFilter {
+    /// TCS packages of one category
+    Tcs(TcsCategory),
+    /// Mechanical packages
+    Mechanical,
+    /// Packages not yet classified
+    Unknown,
+}
+
+/// Package selection for graph views
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    /// Only select TCS packages
+    pub only_tcs: bool,
+    /// Only select packages fetched from git
+    pub only_git: bool,
+    /// Only select packages with this classification
+    pub classification: Option<ClassificationFilter>,
+    /// Maximum depth below the roots (roots are at depth 0)
+    pub max_depth: Option<usize>,
+}
+
+/// Filtered view of a dependency graph
+#[derive(Debug)]
+pub struct GraphView<'a> {
+    /// Graph being rendered
+    graph: &'a DependencyGraph,
+    /// Package selection
+    filter: GraphFilter,
+    /// Direct dependencies of each package, sorted by name and version
+    dependencies: HashMap<PackageId, Vec<(&'a PackageNode, DependencyKind)>>,
+}
+
+/// Tree expansion state shared across roots
+#[derive(Default)]
+struct TreeState {
+    /// Whether each expanded package has a selected package in its subtree
+    expanded: HashMap<PackageId, bool>,
+}
+
+impl std::str::FromStr for ClassificationFilter {
+    type Err = String;
+    
+    /// Parse `mechanical`, `unknown` or a TCS category name
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mechanical" => Ok(ClassificationFilter::Mechanical),
+            "unknown" => Ok(ClassificationFilter::Unknown),
+            _ => s.parse().map(ClassificationFilter::Tcs)
+                .map_err(|e| format!("{} (or mechanical, unknown)", e)),
+        }
+    }
+}
+
+impl ClassificationFilter {
+    /// Check whether a classification matches
+    pub fn matches(&self, classification: &Classification) -> bool {
+        match (self, classification) {
+            (ClassificationFilter::Tcs(wanted), Classification::TCS { category, .. }) => wanted == category,
+            (ClassificationFilter::Mechanical, Classification::Mechanical { .. }) => true,
+            (ClassificationFilter::Unknown, Classification::Unknown) => true,
+            _ => false,
+        }
+    }
+}
+
+impl GraphFilter {
+    /// Check whether a package is selected, ignoring depth
+    pub fn selects(&self, package: &PackageNode) -> bool {
+        if self.only_tcs && !matches!(package.classification, Classification::TCS { .. }) {
+            return false;
+        }
+        if self.only_git && !matches!(package.source, PackageSource::Git { .. }) {
+            return false;
+        }
+        self.classification.as_ref().is_none_or(|wanted| wanted.matches(&package.classification))
+    }
+    
+    /// Whether any package-level filter is set
+    fn is_selective(&self) -> bool {
+        self.only_tcs || self.only_git || self.classification.is_some()
+    }
+}
+
+impl<'a> GraphView<'a> {
+    /// Create a view of a graph
+    pub fn new(graph: &'a DependencyGraph, filter: GraphFilter) -> Self {
+        let mut dependencies: HashMap<PackageId, Vec<(&PackageNode, DependencyKind)>> = HashMap::new();
+        for edge in &graph.edges {
+            let Some(dependency) = graph.find_package_by_id(&edge.to) else { continue };
+            let children = dependencies.entry(edge.from).or_default();
+            if !children.iter().any(|(child, _)| child.id == dependency.id) {
+                children.push((dependency, edge.kind.clone()));
+            }
+        }
+        for children in dependencies.values_mut() {
+            children.sort_by(|(a, _), (b, _)| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        }
+        
+        Self { graph, filter, dependencies }
+    }
+    
+    /// Packages the tree starts from
+    ///
+    /// Workspace members if the graph records them, otherwise packages no
+    /// other package depends on.
+    pub fn roots(&self) -> Vec<&'a PackageNode> {
+        let mut roots: Vec<_> = self.graph.root_packages.iter()
+            .filter(|p| p.is_workspace_member())
+            .collect();
+        
+        if roots.is_empty() {
+            let dependencies: HashSet<_> = self.graph.edges.iter().map(|e| e.to).collect();
+            roots = self.graph.root_packages.iter()
+                .filter(|p| !dependencies.contains(&p.id))
+                .collect();
+        }
+        
+        roots.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        roots
+    }
+    
+    /// Render the dependency tree, one block per root
+    pub fn render_tree(&self) -> String {
+        let mut state = TreeState::default();
+        let blocks: Vec<String> = self.roots().into_iter()
+            .filter_map(|root| self.render_node(root, None, 0, &mut state))
+            .map(|lines| lines.join("\n"))
+            .collect();
+        
+        blocks.join("\n\n")
+    }
+    
+    /// Render selected packages as a table sorted by name and version
+    ///
+    /// Depth is the shortest distance from a root. Without a depth limit,
+    /// packages unreachable from any root are listed too.
+    pub fn render_table(&self) -> String {
+        let depths = self.depths();
+        let mut packages: Vec<_> = self.graph.root_packages.iter()
+            .filter(|p| self.filter.selects(p))
+            .filter(|p| match (self.filter.max_depth, depths.get(&p.id)) {
+                (Some(max_depth), Some(depth)) => *depth <= max_depth,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .collect();
+        packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        
+        let header = ["NAME", "VERSION", "CLASSIFICATION", "DEPTH", "SOURCE"].map(str::to_string);
+        let rows: Vec<[String; 5]> = packages.iter()
+            .map(|p| [
+                p.name.clone(),
+                p.version.clone(),
+                p.classification.to_string(),
+                depths.get(&p.id).map_or_else(|| "-".to_string(), |d| d.to_string()),
+                Self::source_label(&p.source),
+            ])
+            .collect();
+        
+        let mut widths = header.clone().map(|h| h.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        
+        std::iter::once(&header).chain(&rows)
+            .map(|row| {
+                let cells: Vec<_> = row.iter().zip(widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    
+    /// Render a package and its subtree, or `None` if nothing in it is selected
+    fn render_node(
+        &self,
+        package: &PackageNode,
+        kind: Option<&DependencyKind>,
+        depth: usize,
+        state: &mut TreeState,
+    ) -> Option<Vec<String>> {
+        let selected = self.filter.selects(package);
+        let mut label = self.label(package, kind);
+        
+        if let Some(&has_selection) = state.expanded.get(&package.id) {
+            label.push_str(" (*)");
+            return has_selection.then(|| vec![label]);
+        }
+        
+        let children = self.dependencies.get(&package.id).map(Vec::as_slice).unwrap_or_default();
+        let expand = self.filter.max_depth.is_none_or(|max_depth| depth < max_depth);
+        if !expand || children.is_empty() {
+            return selected.then(|| vec![label]);
+        }
+        
+        // Recorded before descending so that cycles terminate
+        state.expanded.insert(package.id, selected);
+        let blocks: Vec<Vec<String>> = children.iter()
+            .filter_map(|(child, kind)| self.render_node(child, Some(kind), depth + 1, state))
+            .collect();
+        let has_selection = selected || !blocks.is_empty();
+        state.expanded.insert(package.id, has_selection);
+        
+        if !has_selection && (depth > 0 || self.filter.is_selective()) {
+            return None;
+        }
+        
+        let mut lines = vec![label];
+        let last = blocks.len().saturating_sub(1);
+        for (index, block) in blocks.into_iter().enumerate() {
+            let (first_prefix, rest_prefix) = if index == last { ("└── ", "    ") } else { ("├── ", "│   ") };
+            for (line_index, line) in block.into_iter().enumerate() {
+                let prefix = if line_index == 0 { first_prefix } else { rest_prefix };
+                lines.push(format!("{}{}", prefix, line));
+            }
+        }
+        
+        Some(lines)
+    }
+    
+    /// Tree line for a package
+    fn label(&self, package: &PackageNode, kind: Option<&DependencyKind>) -> String {
+        let mut label = format!("{} v{} [{}]", package.name, package.version, package.classification);
+        match kind {
+            Some(DependencyKind::Build) => label.push_str(" (build)"),
+            Some(DependencyKind::Dev) => label.push_str(" (dev)"),
+            _ => {},
+        }
+        if !matches!(package.source, PackageSource::Registry { .. }) {
+            label.push_str(&format!(" ({})", Self::source_label(&package.source)));
+        }
+        label
+    }
+    
+    /// Short description of a package source
+    fn source_label(source: &PackageSource) -> String {
+        match source {
+            PackageSource::Registry { url, .. } => format!("registry {}", url),
+            PackageSource::Git { url, rev, .. } => {
+                format!("git {}#{}", url, rev.get(..8).unwrap_or(rev))
+            },
+            PackageSource::Local { path } => format!("path {}", path),
+        }
+    }
+    
+    /// Shortest distance of each reachable package from a root
+    fn depths(&self) -> HashMap<PackageId, usize> {
+        self.graph.package_depths()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::package;
+    
+    fn edge(from: &PackageNode, to: &PackageNode) -> DependencyEdge {
+        DependencyEdge {
+            from: from.id,
+            to: to.id,
+            kind: DependencyKind::Normal,
+            target: None,
+            optional: false,
+            features: vec![],
+        }
+    }
+    
+    fn sample_graph() -> DependencyGraph {
+        let mechanical = || Classification::Mechanical { category: MechanicalCategory::Utility };
+        let crypto = Classification::TCS { category: TcsCategory::Cryptography, rationale: String::new() };
+        
+        let app = PackageNode { source: PackageSource::Local { path: ".".to_string() }, ..package("app", "1.0.0", mechanical()) };
+        let http = package("http-client", "1.0.0", mechanical());
+        let ring = package("ring", "1.0.0", crypto);
+        let patched = PackageNode {
+            source: PackageSource::Git {
+                url: "https://example.com/patched".to_string(),
+                rev: "0123456789abcdef".to_string(),
+                checksum: String::new(),
+            },
+            ..package("patched", "1.0.0", mechanical())
+        };
+        
+        let mut graph = DependencyGraph::new("test".to_string(), "rust".to_string());
+        graph.add_edge(edge(&app, &http));
+        graph.add_edge(edge(&app, &ring));
+        graph.add_edge(edge(&http, &ring));
+        graph.add_edge(edge(&http, &patched));
+        for package in [app, http, ring, patched] {
+            graph.add_package(package);
+        }
+        graph
+    }
+    
+    #[test]
+    fn test_tree_rendering_and_filters() {
+        let graph = sample_graph();
+        
+        let tree = GraphView::new(&graph, GraphFilter::default()).render_tree();
+        assert_eq!(tree, [
+            "app v1.0.0 [Mechanical (Utility)] (path .)",
+            "├── http-client v1.0.0 [Mechanical (Utility)]",
+            "│   ├── patched v1.0.0 [Mechanical (Utility)] (git https://example.com/patched#01234567)",
+            "│   └── ring v1.0.0 [TCS (Cryptography)]",
+            "└── ring v1.0.0 [TCS (Cryptography)]",
+        ].join("\n"));
+        
+        let only_git = GraphFilter { only_git: true, ..Default::default() };
+        let tree = GraphView::new(&graph, only_git).render_tree();
+        assert_eq!(tree.lines().count(), 3);
+        assert!(!tree.contains("ring"));
+        
+        let shallow = GraphFilter { max_depth: Some(1), ..Default::default() };
+        let tree = GraphView::new(&graph, shallow).render_tree();
+        assert!(!tree.contains("patched"));
+        
+        let crypto = GraphFilter { classification: Some("crypto".parse().unwrap()), ..Default::default() };
+        let table = GraphView::new(&graph, crypto).render_table();
+        assert_eq!(table.lines().count(), 2);
+        assert!(table.lines().nth(1).unwrap().starts_with("ring  1.0.0    TCS (Cryptography)  1"));
+    }
+}
@@ -0,0 +1,292 @@
+//! Shared HTTP client of online operations
+//!
+//! Components that talk to the network (the crates.io API, index and
+//! advisory fetches, provenance lookups) go through [`HttpClient`] rather
+//! than building clients of their own. A client and its clones share one
+//! connection pool and start requests at least `min_interval` apart.
+//! Connection errors, timeouts and 429/5xx responses are retried with
+//! exponential backoff, or after the delay a `Retry-After` header asks
+//! for. A `Retry-After` delay is never shortened; when it would outlast
+//! the operation's deadline the request fails instead. Proxy and CA
+//! settings come from `http_config`.
+//!
+//! In offline mode every request is refused before anything is sent.
+
+use crate::error::{AdapterError, Result};
+use crate::models::HttpConfig;
+use crate::utils::deadline;
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// Pooled, rate-limited and retrying HTTP client
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    /// Underlying client; clones share its connection pool
+    client: reqwest::Client,
+    /// Whether requests are refused
+    offline_mode: bool,
+    /// Retries of a failed request
+    max_retries: u32,
+    /// Delay before the first retry
+    initial_backoff: Duration,
+    /// Longest computed backoff; `Retry-After` delays are not capped
+    max_backoff: Duration,
+    /// Minimum delay between the starts of two requests
+    min_interval: Duration,
+    /// When the last request was started, shared by clones
+    last_request: Arc<Mutex<Option<Instant>>>,
+    /// Token that aborts requests and retry delays when cancelled
+    cancel: CancellationToken,
+}
+
+impl HttpClient {
+    /// Create a client with the settings of `http_config`
+    ///
+    /// Fails if the proxy URL or the CA bundle is invalid.
+    pub fn new(config: &HttpConfig, offline_mode: bool) -> Result<Self> {
+        let mut builder = reqwest::Client::builder()
+            .user_agent(concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(config.timeout))
+            .pool_max_idle_per_host(config.max_idle_per_host);
+        
+        if let Some(proxy) = &config.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| Self::invalid("http_config.proxy", proxy, e))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = &config.ca_bundle {
+            let pem = std::fs::read(path)
+                .map_err(|_| AdapterError::file_not_found(path, "reading CA bundle"))?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| Self::invalid("http_config.ca_bundle", &path.display().to_string(), e))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        
+        let client = builder.build()
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to build HTTP client".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        
+        Ok(Self {
+            client,
+            offline_mode,
+            max_retries: config.max_retries,
+            initial_backoff: Duration::from_millis(config.initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            min_interval: Duration::ZERO,
+            last_request: Arc::new(Mutex::new(None)),
+            cancel: CancellationToken::new(),
+        })
+    }
+    
+    /// Start requests at least `interval` apart, e.g. to honour a crawler policy
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+    
+    /// Abort requests and retry delays when `token` is cancelled
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+    
+    /// Issue a GET request, retrying transient failures
+    ///
+    /// Returns the first successful response. Other client errors (4xx
+    /// except 429) are not retried.
+    pub async fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Response> {
+        let operation = format!("GET {}", url);
+        if self.offline_mode {
+            return Err(AdapterError::NetworkTimeout {
+                operation,
+                source: anyhow::anyhow!("Network operations disabled in offline mode"),
+            });
+        }
+        
+        let mut attempt = 0;
+        loop {
+            self.wait_turn(&operation).await?;
+            let sent = tokio::select! {
+                sent = self.client.get(url).query(query).send() => sent,
+                _ = self.cancel.cancelled() => return Err(AdapterError::cancelled(&operation)),
+            };
+            
+            let (retry_after, error) = match sent {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) if Self::is_transient(response.status()) => (
+                    Self::retry_after(&response),
+                    Self::unavailable(url, anyhow::anyhow!("HTTP status {}", response.status())),
+                ),
+                Ok(response) => {
+                    return Err(Self::unavailable(url, anyhow::anyhow!("HTTP status {}", response.status())));
+                },
+                Err(e) if e.is_timeout() || e.is_connect() => (None, Self::failed(url, e)),
+                Err(e) => return Err(Self::failed(url, e)),
+            };
+            
+            if attempt >= self.max_retries {
+                return Err(error);
+            }
+            let delay = match retry_after {
+                Some(retry_after) => retry_after,
+                None => self.backoff(attempt).min(self.max_backoff),
+            };
+            if deadline::remaining().is_some_and(|remaining| delay >= remaining) {
+                return Err(Self::unavailable(url, anyhow::anyhow!("{}; retrying after {:?} would exceed the deadline", error, delay)));
+            }
+            tracing::debug!(url, attempt = attempt + 1, delay_ms = delay.as_millis() as u64, error = %error, "Retrying HTTP request");
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {},
+                _ = self.cancel.cancelled() => return Err(AdapterError::cancelled(&operation)),
+            }
+            attempt += 1;
+        }
+    }
+    
+    /// Issue a GET request and decode the JSON body
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str, query: &[(&str, String)]) -> Result<T> {
+        self.get(url, query).await?
+            .json()
+            .await
+            .map_err(|e| Self::failed(url, e))
+    }
+    
+    /// Wait until `min_interval` has passed since the last request started
+    async fn wait_turn(&self, operation: &str) -> Result<()> {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            tokio::select! {
+                _ = tokio::time::sleep_until(last + self.min_interval) => {},
+                _ = self.cancel.cancelled() => return Err(AdapterError::cancelled(operation)),
+            }
+        }
+        *last_request = Some(Instant::now());
+        Ok(())
+    }
+    
+    /// Delay before retry number `attempt + 1`
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt))
+    }
+    
+    /// Whether a response status is worth retrying
+    fn is_transient(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+    
+    /// Delay requested by a `Retry-After` header given in seconds
+    fn retry_after(response: &Response) -> Option<Duration> {
+        response.headers().get(reqwest::header::RETRY_AFTER)?
+            .to_str().ok()?
+            .trim()
+            .parse()
+            .ok()
+            .map(Duration::from_secs)
+    }
+    
+    fn failed(url: &str, error: reqwest::Error) -> AdapterError {
+        if error.is_timeout() {
+            AdapterError::NetworkTimeout {
+                operation: format!("GET {}", url),
+                source: anyhow::anyhow!("{}", error),
+            }
+        } else {
+            Self::unavailable(url, anyhow::anyhow!("{}", error))
+        }
+    }
+    
+    fn unavailable(url: &str, source: anyhow::Error) -> AdapterError {
+        AdapterError::RegistryUnavailable {
+            url: url.to_string(),
+            source,
+        }
+    }
+    
+    fn invalid(field: &str, value: &str, error: reqwest::Error) -> AdapterError {
+        AdapterError::ConfigurationInvalid {
+            field: field.to_string(),
+            value: value.to_string(),
+            reason: error.to_string(),
+            source: anyhow::anyhow!("Invalid HTTP client settings"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    
+    /// Serve `responses` in order, one per connection, returning the server's URL
+    async fn serve(responses: Vec<&'static str>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/crates", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+    
+    #[tokio::test]
+    async fn test_get_json_retries_transient_failures() {
+        let config = HttpConfig {
+            initial_backoff_ms: 10,
+            ..HttpConfig::default()
+        };
+        let url = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: 11\r\n\r\n{\"ok\":true}",
+            "HTTP/1.1 404 Not Found\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+        ]).await;
+        
+        let client = HttpClient::new(&config, false).unwrap()
+            .with_min_interval(Duration::from_millis(5));
+        let body: serde_json::Value = client.get_json(&url, &[]).await.unwrap();
+        assert_eq!(body["ok"], true);
+        
+        // Client errors are not retried
+        assert!(matches!(client.get(&url, &[]).await, Err(AdapterError::RegistryUnavailable { .. })));
+        
+        // Offline, nothing is sent
+        let offline = HttpClient::new(&config, true).unwrap();
+        assert!(matches!(offline.get(&url, &[]).await, Err(AdapterError::NetworkTimeout { .. })));
+    }
+    
+    #[tokio::test]
+    async fn test_retry_after_is_honoured() {
+        let config = HttpConfig {
+            max_backoff_ms: 10,
+            ..HttpConfig::default()
+        };
+        let client = HttpClient::new(&config, false).unwrap();
+        let url = serve(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 1\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+        ]).await;
+        let started = Instant::now();
+        client.get(&url, &[]).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        
+        // A delay beyond the deadline fails the request instead of being shortened
+        let url = serve(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 60\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+        ]).await;
+        let result = deadline::enforce("fetch", Some(Duration::from_secs(5)), client.get(&url, &[])).await;
+        assert!(matches!(result, Err(AdapterError::RegistryUnavailable { .. })));
+    }
+}
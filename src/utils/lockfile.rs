@@ -0,0 +1,310 @@
+//! Cargo.lock canonicalization
+//!
+//! Cargo versions write the same resolution differently: lockfile format
+//! versions reference dependencies with more or less detail, v1 keeps
+//! checksums in a `[metadata]` table, and crates.io may appear under its
+//! git or sparse index URL. [`CanonicalLockfile`] reduces a lockfile to a
+//! form in which only the resolved packages matter, so that fingerprints
+//! and lockfile comparisons do not report formatting noise as drift.
+
+use crate::error::{AdapterError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Source string cargo writes for crates.io
+pub const CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+/// Other spellings of the crates.io source
+const CRATES_IO_ALIASES: &[&str] = &[
+    "registry+https://crates.io",
+    "sparse+https://index.crates.io",
+    "registry+https://index.crates.io",
+    "registry+https://github.com/rust-lang/crates.io-index.git",
+];
+
+/// Package entry of a canonical lockfile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CanonicalPackage {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Normalized source, absent for workspace members and path dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Registry checksum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Dependencies in cargo's shortest unambiguous form, sorted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+/// Cargo.lock with stable package order and normalized sources
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CanonicalLockfile {
+    /// Packages sorted by name, version and source
+    #[serde(rename = "package", default)]
+    pub packages: Vec<CanonicalPackage>,
+}
+
+/// Cargo.lock as written by any cargo version
+#[derive(Deserialize)]
+struct RawLockfile {
+    #[serde(default)]
+    package: Vec<RawPackage>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// Package entry as written by any cargo version
+#[derive(Deserialize)]
+struct RawPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    checksum: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+impl CanonicalLockfile {
+    /// Canonicalize Cargo.lock content
+    pub fn parse(content: &str) -> Result<Self> {
+        let raw: RawLockfile = toml::from_str(content)
+            .map_err(|e| AdapterError::MetadataParseError {
+                field: "Cargo.lock".to_string(),
+                value: String::new(),
+                source: anyhow::anyhow!("Invalid Cargo.lock: {}", e),
+            })?;
+        
+        let mut packages: Vec<CanonicalPackage> = raw.package.iter()
+            .map(|package| {
+                let source = package.source.as_deref().map(normalize_source);
+                let checksum = package.checksum.clone().or_else(|| {
+                    v1_metadata_checksum(&raw.metadata, &package.name, &package.version, package.source.as_deref())
+                });
+                CanonicalPackage {
+                    name: package.name.clone(),
+                    version: package.version.clone(),
+                    source,
+                    checksum,
+                    dependencies: Vec::new(),
+                }
+            })
+            .collect();
+        
+        // Indexed by name so that resolving references stays linear in the lockfile size
+        let mut by_name: HashMap<&str, Vec<&CanonicalPackage>> = HashMap::new();
+        for package in &packages {
+            by_name.entry(package.name.as_str()).or_default().push(package);
+        }
+        let dependencies: Vec<Vec<String>> = raw.package.iter()
+            .map(|package| {
+                let mut dependencies: Vec<String> = package.dependencies.iter()
+                    .map(|dependency| Self::shortest_reference(&by_name, dependency))
+                    .collect();
+                dependencies.sort_unstable();
+                dependencies
+            })
+            .collect();
+        for (package, dependencies) in packages.iter_mut().zip(dependencies) {
+            package.dependencies = dependencies;
+        }
+        packages.sort();
+        
+        Ok(Self { packages })
+    }
+    
+    /// Canonical lockfile as TOML, suitable for line-based diffs
+    pub fn render(&self) -> Result<String> {
+        toml::to_string(self)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to render canonical Cargo.lock".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })
+    }
+    
+    /// SHA-256 over the canonical packages
+    ///
+    /// Each package contributes its name, version, source, checksum and
+    /// sorted dependency list, in package order.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for package in &self.packages {
+            let entry = format!(
+                "name={}\nversion={}\nsource={}\nchecksum={}\ndependencies={}\n",
+                package.name,
+                package.version,
+                package.source.as_deref().unwrap_or_default(),
+                package.checksum.as_deref().unwrap_or_default(),
+                package.dependencies.join(","),
+            );
+            hasher.update(entry.as_bytes());
+            hasher.update(b"\n");
+        }
+        
+        format!("{:x}", hasher.finalize())
+    }
+    
+    /// Rewrite a dependency reference in cargo's shortest unambiguous form
+    ///
+    /// The version is kept only when several versions of the crate are
+    /// locked, and the source only when the version is locked from several
+    /// sources. References that match no package are kept as written.
+    fn shortest_reference(by_name: &HashMap<&str, Vec<&CanonicalPackage>>, reference: &str) -> String {
+        let (head, source) = match reference.split_once(" (") {
+            Some((head, source)) => (head, source.strip_suffix(')').map(normalize_source)),
+            None => (reference, None),
+        };
+        let mut parts = head.split_whitespace();
+        let (Some(name), version) = (parts.next(), parts.next()) else {
+            return reference.to_string();
+        };
+        
+        let same_name = by_name.get(name).map(Vec::as_slice).unwrap_or_default();
+        let matches: Vec<&&CanonicalPackage> = same_name.iter()
+            .filter(|p| version.is_none_or(|version| p.version == version))
+            .filter(|p| source.is_none() || p.source == source)
+            .collect();
+        let [target] = matches.as_slice() else {
+            return reference.to_string();
+        };
+        
+        if same_name.len() == 1 {
+            return target.name.clone();
+        }
+        if same_name.iter().filter(|p| p.version == target.version).count() == 1 {
+            return format!("{} {}", target.name, target.version);
+        }
+        format!("{} {} ({})", target.name, target.version, target.source.as_deref().unwrap_or_default())
+    }
+}
+
+/// Checksum a format v1 lockfile keeps in its `[metadata]` table
+///
+/// Format v1 writes checksums as `"checksum <name> <version> (<source>)"`
+/// metadata entries instead of on the package, with `<none>` for packages
+/// without one.
+pub fn v1_metadata_checksum(metadata: &HashMap<String, String>, name: &str, version: &str, source: Option<&str>) -> Option<String> {
+    let key = format!("checksum {} {} ({})", name, version, source.unwrap_or_default());
+    metadata.get(&key).filter(|checksum| checksum.as_str() != "<none>").cloned()
+}
+
+/// Normalize a Cargo.lock source string
+///
+/// crates.io is always written as [`CRATES_IO_SOURCE`]; other registry
+/// URLs lose a trailing slash, and git URLs lose a trailing slash or
+/// `.git` suffix and get a lowercase host. Git queries and the locked
+/// revision are kept.
+pub fn normalize_source(source: &str) -> String {
+    let source = source.trim();
+    let Some((kind, location)) = source.split_once('+') else {
+        return source.to_string();
+    };
+    
+    match kind {
+        "git" => {
+            let (url, fragment) = match location.split_once('#') {
+                Some((url, fragment)) => (url, Some(fragment)),
+                None => (location, None),
+            };
+            let (url, query) = match url.split_once('?') {
+                Some((url, query)) => (url, Some(query)),
+                None => (url, None),
+            };
+            
+            let url = url.trim_end_matches('/');
+            let url = url.strip_suffix(".git").unwrap_or(url);
+            let url = match url.split_once("://") {
+                Some((scheme, rest)) => {
+                    let (host, path) = rest.split_once('/').map_or((rest, ""), |(host, path)| (host, path));
+                    let separator = if path.is_empty() { "" } else { "/" };
+                    format!("{}://{}{}{}", scheme.to_ascii_lowercase(), host.to_ascii_lowercase(), separator, path)
+                },
+                None => url.to_string(),
+            };
+            
+            let mut normalized = format!("git+{}", url);
+            if let Some(query) = query {
+                normalized.push('?');
+                normalized.push_str(query);
+            }
+            if let Some(fragment) = fragment {
+                normalized.push('#');
+                normalized.push_str(fragment);
+            }
+            normalized
+        },
+        "registry" | "sparse" => {
+            let trimmed = source.trim_end_matches('/');
+            if trimmed == CRATES_IO_SOURCE || CRATES_IO_ALIASES.contains(&trimmed) {
+                CRATES_IO_SOURCE.to_string()
+            } else {
+                format!("{}+{}", kind, location.trim_end_matches('/'))
+            }
+        },
+        _ => source.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_canonicalization_removes_format_noise() {
+        let v1 = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "dep 0.1.0 (git+https://GitHub.com/example/dep.git#abc123)",
+ "itoa 1.0.9 (registry+https://github.com/rust-lang/crates.io-index)",
+]
+
+[[package]]
+name = "dep"
+version = "0.1.0"
+source = "git+https://GitHub.com/example/dep.git#abc123"
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[metadata]
+"checksum itoa 1.0.9 (registry+https://github.com/rust-lang/crates.io-index)" = "af15"
+"#;
+        
+        let v4 = r#"
+version = 4
+
+[[package]]
+name = "itoa"
+version = "1.0.9"
+source = "sparse+https://index.crates.io/"
+checksum = "af15"
+
+[[package]]
+name = "dep"
+version = "0.1.0"
+source = "git+https://github.com/example/dep#abc123"
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["itoa", "dep"]
+"#;
+        
+        let canonical = CanonicalLockfile::parse(v1).unwrap();
+        assert_eq!(canonical, CanonicalLockfile::parse(v4).unwrap());
+        assert_eq!(canonical.packages[0].dependencies, vec!["dep".to_string(), "itoa".to_string()]);
+        assert_eq!(canonical.packages[2].checksum.as_deref(), Some("af15"));
+        assert!(canonical.render().unwrap().contains(CRATES_IO_SOURCE));
+        
+        assert_eq!(normalize_source("git+https://github.com/example/dep?branch=main#abc123"), "git+https://github.com/example/dep?branch=main#abc123");
+        assert_eq!(normalize_source("registry+https://example.com/index/"), "registry+https://example.com/index");
+    }
+}
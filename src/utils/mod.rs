@@ -3,9 +3,38 @@
 //! This module provides utility functions and helpers
 //! used across the adapter implementation.
 
+#[cfg(not(target_arch = "wasm32"))]
 pub mod command_runner;
 pub mod checksum;
+pub mod atomic_write;
+pub mod artifact;
+pub mod name_pattern;
+pub mod graph_view;
+pub mod shared_cache;
+pub mod lockfile;
+pub mod tool_invoker;
+pub mod telemetry;
+pub mod deadline;
+pub mod platform;
+pub mod events;
+pub mod report_template;
+#[cfg(feature = "online")]
+pub mod http;
 
 // Re-export commonly used utilities
+#[cfg(not(target_arch = "wasm32"))]
 pub use command_runner::CommandRunner;
-pub use checksum::ChecksumCalculator;
+pub use checksum::{ChecksumAlgorithm, ChecksumCalculator, DigestConfig, DigestEncoding, Hasher, LOCKFILE_CHECKSUM_ALGORITHM};
+pub use atomic_write::{recover_partial_writes, write_atomic, AtomicFile};
+pub use artifact::{update_manifest, verify_manifest, write_artifact, write_artifact_with};
+pub use name_pattern::{NamePattern, PatternOverrides};
+pub use graph_view::{ClassificationFilter, GraphFilter, GraphView};
+pub use shared_cache::{CacheEntryStatus, CacheLock, SharedCache};
+pub use lockfile::{normalize_source, v1_metadata_checksum, CanonicalLockfile, CRATES_IO_SOURCE};
+pub use report_template::ReportTemplate;
+pub use events::EventSink;
+#[cfg(feature = "online")]
+pub use http::HttpClient;
+pub use platform::{config_path, resolve_program, simplify_path};
+pub use tool_invoker::{ProcessInvoker, RecordingInvoker, ReplayInvoker, ToolInvocation, ToolInvoker, ToolOutput};
+pub use tokio_util::sync::CancellationToken;
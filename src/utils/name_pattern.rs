@@ -0,0 +1,232 @@
+//! Package name patterns for override tables
+//!
+//! Override tables such as `explicit_tcs_overrides` and
+//! `drift_detection_config.priority_overrides` are keyed on package names.
+//! A key may also be a glob (`*` matches any run of characters, `?` a
+//! single character) or a regular expression written as `/regex/`. Globs
+//! and regular expressions must match the whole name.
+//!
+//! When several keys match a package, the first of these wins:
+//! 1. the key equal to the package name;
+//! 2. the matching glob with the most literal (non-wildcard) characters,
+//!    ties broken alphabetically;
+//! 3. the matching regular expression that sorts first alphabetically.
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+/// Parsed override table key
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    /// Exact package name
+    Exact(String),
+    /// Glob, compiled to an anchored regular expression
+    Glob {
+        /// Glob as written in the configuration
+        glob: String,
+        /// Compiled matcher
+        regex: Regex,
+        /// Number of literal characters
+        literals: usize,
+    },
+    /// Regular expression, anchored to the whole name
+    Regex {
+        /// Expression as written in the configuration, without slashes
+        source: String,
+        /// Compiled matcher
+        regex: Regex,
+    },
+}
+
+/// Override table resolving package names through patterns
+///
+/// Serialized as the table written in the configuration; deserializing
+/// rejects keys that are not valid patterns.
+#[derive(Debug, Clone)]
+pub struct PatternOverrides<V> {
+    /// Exact-name entries
+    exact: HashMap<String, V>,
+    /// Glob and regex entries in precedence order, with their keys
+    patterns: Vec<(String, NamePattern, V)>,
+}
+
+impl NamePattern {
+    /// Parse an override table key
+    pub fn parse(key: &str) -> Result<Self, String> {
+        if let Some(source) = key.strip_prefix('/').and_then(|rest| rest.strip_suffix('/')) {
+            let regex = Regex::new(&format!("^(?:{})$", source))
+                .map_err(|e| format!("Invalid regular expression: {}", e))?;
+            return Ok(Self::Regex { source: source.to_string(), regex });
+        }
+        
+        if !key.contains(['*', '?']) {
+            return Ok(Self::Exact(key.to_string()));
+        }
+        
+        let mut expression = String::from("^");
+        let mut literals = 0;
+        for c in key.chars() {
+            match c {
+                '*' => expression.push_str(".*"),
+                '?' => expression.push('.'),
+                c => {
+                    expression.push_str(&regex::escape(&c.to_string()));
+                    literals += 1;
+                },
+            }
+        }
+        expression.push('$');
+        
+        let regex = Regex::new(&expression).map_err(|e| format!("Invalid glob: {}", e))?;
+        Ok(Self::Glob { glob: key.to_string(), regex, literals })
+    }
+    
+    /// Check whether a package name matches
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(exact) => exact == name,
+            Self::Glob { regex, .. } | Self::Regex { regex, .. } => regex.is_match(name),
+        }
+    }
+}
+
+impl<V> PatternOverrides<V> {
+    /// Build an override table from configuration entries
+    ///
+    /// Keys that fail to parse are skipped with a warning; configuration
+    /// validation reports them as errors.
+    pub fn new<'a, I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a String, &'a V)>,
+        V: Clone + 'a,
+    {
+        let mut exact = HashMap::new();
+        let mut patterns = Vec::new();
+        
+        for (key, value) in entries {
+            match NamePattern::parse(key) {
+                Ok(NamePattern::Exact(name)) => {
+                    exact.insert(name, value.clone());
+                },
+                Ok(pattern) => patterns.push((key.clone(), pattern, value.clone())),
+                Err(reason) => tracing::warn!(key = %key, reason = %reason, "Ignoring invalid override pattern"),
+            }
+        }
+        
+        patterns.sort_by(|(a_key, a, _), (b_key, b, _)| {
+            Self::precedence(a).cmp(&Self::precedence(b)).then_with(|| a_key.cmp(b_key))
+        });
+        
+        Self { exact, patterns }
+    }
+    
+    /// Look up the override for a package name
+    pub fn get(&self, name: &str) -> Option<&V> {
+        self.get_with_key(name).map(|(_, value)| value)
+    }
+    
+    /// Look up the override for a package name, with the key that matched
+    pub fn get_with_key(&self, name: &str) -> Option<(&str, &V)> {
+        if let Some((key, value)) = self.exact.get_key_value(name) {
+            return Some((key.as_str(), value));
+        }
+        
+        self.patterns.iter()
+            .find(|(_, pattern, _)| pattern.matches(name))
+            .map(|(key, _, value)| (key.as_str(), value))
+    }
+    
+    /// Whether the table has no entries
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.patterns.is_empty()
+    }
+    
+    /// Entries as written in the configuration, sorted by key
+    pub fn entries(&self) -> BTreeMap<&str, &V> {
+        self.exact.iter()
+            .map(|(key, value)| (key.as_str(), value))
+            .chain(self.patterns.iter().map(|(key, _, value)| (key.as_str(), value)))
+            .collect()
+    }
+    
+    /// Sort key of a non-exact pattern, lowest first
+    fn precedence(pattern: &NamePattern) -> (u8, std::cmp::Reverse<usize>) {
+        match pattern {
+            NamePattern::Exact(_) => (0, std::cmp::Reverse(0)),
+            NamePattern::Glob { literals, .. } => (1, std::cmp::Reverse(*literals)),
+            NamePattern::Regex { .. } => (2, std::cmp::Reverse(0)),
+        }
+    }
+}
+
+impl<V> Default for PatternOverrides<V> {
+    fn default() -> Self {
+        Self {
+            exact: HashMap::new(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl<V: PartialEq> PartialEq for PatternOverrides<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries() == other.entries()
+    }
+}
+
+impl<V: Serialize> Serialize for PatternOverrides<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries().serialize(serializer)
+    }
+}
+
+impl<'de, V: Deserialize<'de> + Clone> Deserialize<'de> for PatternOverrides<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = HashMap::<String, V>::deserialize(deserializer)?;
+        for key in entries.keys() {
+            NamePattern::parse(key)
+                .map_err(|reason| serde::de::Error::custom(format!("invalid override pattern `{}`: {}", key, reason)))?;
+        }
+        Ok(Self::new(&entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pattern_precedence() {
+        let entries: HashMap<String, &str> = [
+            ("openssl*", "glob-short"),
+            ("openssl-s?s", "glob-long"),
+            ("openssl", "exact"),
+            ("*-sys", "suffix"),
+            ("/ring|rustls.*/", "regex"),
+        ].into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+        let overrides = PatternOverrides::new(&entries);
+        
+        assert_eq!(overrides.get("openssl"), Some(&"exact"));
+        assert_eq!(overrides.get_with_key("openssl-sys"), Some(("openssl-s?s", &"glob-long")));
+        assert_eq!(overrides.get("openssl-probe"), Some(&"glob-short"));
+        assert_eq!(overrides.get("libz-sys"), Some(&"suffix"));
+        assert_eq!(overrides.get("rustls-webpki"), Some(&"regex"));
+        assert_eq!(overrides.get("ring"), Some(&"regex"));
+        assert_eq!(overrides.get("ring-compat"), None);
+        assert_eq!(overrides.get("serde"), None);
+    }
+    
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(NamePattern::parse("/(unclosed/").is_err());
+        assert!(matches!(NamePattern::parse("a.b"), Ok(NamePattern::Exact(_))));
+        assert!(NamePattern::parse("a.b*").unwrap().matches("a.bc"));
+        assert!(!NamePattern::parse("a.b*").unwrap().matches("axbc"));
+        
+        let overrides: PatternOverrides<u8> = serde_json::from_str(r#"{"*-sys": 1, "ring": 2}"#).unwrap();
+        assert_eq!(overrides.get("libz-sys"), Some(&1));
+        assert_eq!(serde_json::to_string(&overrides).unwrap(), r#"{"*-sys":1,"ring":2}"#);
+        assert!(serde_json::from_str::<PatternOverrides<u8>>(r#"{"/(ring/": 1}"#).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Platform-specific tool discovery and path handling
+//!
+//! Tools are found on `PATH` the way the platform's shell finds them: on
+//! Windows every `PATHEXT` extension is tried, so `cargo` resolves to
+//! `cargo.exe` and wrapper scripts such as `cargo-audit.cmd` are found too.
+//! Paths written into Cargo configuration use forward slashes and never
+//! the `\\?\` verbatim prefix `canonicalize` adds on Windows, which Cargo
+//! and many build scripts reject. UNC shares are kept as `//server/share`.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried on Windows when `PATHEXT` is not set
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Prefix of verbatim paths
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// Prefix of verbatim UNC paths
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// Find a program on `PATH`
+pub fn find_program(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let extensions = if cfg!(windows) {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+        pathext.split(';').filter(|ext| !ext.is_empty()).map(str::to_string).collect()
+    } else {
+        Vec::new()
+    };
+    find_program_in(name, &path, &extensions)
+}
+
+/// Find a program in the directories of a `PATH`-style list
+///
+/// Each extension is tried after the bare name unless `name` already ends
+/// with one of them. A name containing a directory is only checked as is.
+pub fn find_program_in(name: &str, path: &OsStr, extensions: &[String]) -> Option<PathBuf> {
+    let has_extension = extensions.iter().any(|ext| name.to_ascii_lowercase().ends_with(&ext.to_ascii_lowercase()));
+    let candidates = |dir: &Path| -> Vec<PathBuf> {
+        let mut candidates = vec![dir.join(name)];
+        if !has_extension {
+            candidates.extend(extensions.iter().map(|ext| dir.join(format!("{}{}", name, ext))));
+        }
+        candidates
+    };
+    
+    if Path::new(name).components().count() > 1 {
+        return candidates(Path::new("")).into_iter().find(|candidate| candidate.is_file());
+    }
+    
+    std::env::split_paths(path)
+        .flat_map(|dir| candidates(&dir))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Program to spawn for a tool name, found on `PATH` when possible
+///
+/// Falls back to the bare name so the spawn error names the missing tool.
+pub fn resolve_program(name: &str) -> PathBuf {
+    find_program(name).unwrap_or_else(|| PathBuf::from(name))
+}
+
+/// Path without the verbatim prefix `canonicalize` adds on Windows
+///
+/// `\\?\C:\work` becomes `C:\work` and `\\?\UNC\server\share` becomes
+/// `\\server\share`. Other verbatim forms and other paths are unchanged.
+pub fn simplify_path(path: &Path) -> PathBuf {
+    let text = path.to_string_lossy();
+    if let Some(rest) = text.strip_prefix(VERBATIM_UNC_PREFIX) {
+        return PathBuf::from(format!(r"\\{}", rest));
+    }
+    match text.strip_prefix(VERBATIM_PREFIX) {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') && rest.as_bytes()[0].is_ascii_alphabetic() => PathBuf::from(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Path as written into Cargo configuration files
+///
+/// Simplified and with forward slashes, so it needs no escaping in TOML
+/// and reads the same on every platform.
+pub fn config_path(path: &Path) -> String {
+    simplify_path(path).to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_find_program_tries_pathext() {
+        let temp = TempDir::new().unwrap();
+        let (first, second) = (temp.path().join("first"), temp.path().join("second"));
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(first.join("cargo-audit.cmd"), "").unwrap();
+        std::fs::write(second.join("cargo.exe"), "").unwrap();
+        let path = std::env::join_paths([&first, &second]).unwrap();
+        let extensions = vec![".exe".to_string(), ".cmd".to_string()];
+        
+        assert_eq!(find_program_in("cargo", &path, &extensions), Some(second.join("cargo.exe")));
+        assert_eq!(find_program_in("cargo.exe", &path, &extensions), Some(second.join("cargo.exe")));
+        assert_eq!(find_program_in("cargo-audit", &path, &extensions), Some(first.join("cargo-audit.cmd")));
+        assert_eq!(find_program_in("cargo", &path, &[]), None);
+        assert_eq!(find_program_in("cargo-vet", &path, &extensions), None);
+    }
+    
+    #[test]
+    fn test_config_path_strips_verbatim_prefix() {
+        assert_eq!(config_path(Path::new(r"\\?\C:\work\vendor")), "C:/work/vendor");
+        assert_eq!(config_path(Path::new(r"\\?\UNC\build01\share\vendor")), "//build01/share/vendor");
+        assert_eq!(config_path(Path::new(r"\\build01\share\vendor")), "//build01/share/vendor");
+        assert_eq!(config_path(Path::new("/work/vendor")), "/work/vendor");
+        assert_eq!(simplify_path(Path::new(r"\\?\Volume{1234}\vendor")), PathBuf::from(r"\\?\Volume{1234}\vendor"));
+    }
+}
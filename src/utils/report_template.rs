@@ -0,0 +1,103 @@
+//! Rendering of reports through user-supplied templates
+//!
+//! Reports that must follow an organization's own layout, such as a
+//! corporate security review form, are rendered through a
+//! [MiniJinja](https://docs.rs/minijinja) template instead of the built-in
+//! JSON. The template sees every field of the serialized report at the top
+//! level, and the whole report as `report`:
+//!
+//! ```text
+//! Drift review for epoch {{ expected_epoch }}
+//! {% for drift in drifts %}
+//! - {{ drift.package_name }}: {{ drift.change_type }} ({{ drift.priority }})
+//! {% endfor %}
+//! ```
+//!
+//! Output of templates named `*.html`, `*.htm` or `*.xml` is HTML-escaped.
+//! Referencing a field the report does not have is an error rather than
+//! empty output, so typos surface when the template is first used.
+
+use crate::error::{AdapterError, Result};
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Report template loaded from a file
+#[derive(Debug, Clone)]
+pub struct ReportTemplate {
+    /// Template file
+    path: PathBuf,
+    /// Template source
+    source: String,
+}
+
+impl ReportTemplate {
+    /// Load a template file
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path).map_err(|e| AdapterError::FileNotFound {
+            path: path.to_path_buf(),
+            context: "Report template".to_string(),
+            source: e.into(),
+        })?;
+        Ok(Self { path: path.to_path_buf(), source })
+    }
+    
+    /// Template from source, named like a file to select escaping
+    pub fn from_source(name: &str, source: &str) -> Self {
+        Self { path: PathBuf::from(name), source: source.to_string() }
+    }
+    
+    /// Render `report` through the template
+    pub fn render<T: Serialize>(&self, report: &T) -> Result<String> {
+        let mut context = serde_json::to_value(report).map_err(|e| self.error(e.to_string(), e.into()))?;
+        if let serde_json::Value::Object(fields) = &mut context {
+            let whole = serde_json::Value::Object(fields.clone());
+            fields.entry("report").or_insert(whole);
+        }
+        
+        let mut env = Environment::new();
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        let name = self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        env.render_named_str(&name, &self.source, context).map_err(|e| {
+            let reason = match e.detail() {
+                Some(detail) => format!("{}: {}", e, detail),
+                None => e.to_string(),
+            };
+            self.error(reason, e.into())
+        })
+    }
+    
+    fn error(&self, reason: String, source: anyhow::Error) -> AdapterError {
+        AdapterError::ConfigurationInvalid {
+            field: "template".to_string(),
+            value: self.path.display().to_string(),
+            reason,
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[derive(Serialize)]
+    struct Report {
+        title: String,
+        items: Vec<String>,
+    }
+    
+    #[test]
+    fn test_render_exposes_report_fields() {
+        let report = Report { title: "Review <Q3>".to_string(), items: vec!["serde".to_string(), "tokio".to_string()] };
+        
+        let text = ReportTemplate::from_source("review.txt", "{{ title }}:{% for i in report.items %} {{ i }}{% endfor %}");
+        assert_eq!(text.render(&report).unwrap(), "Review <Q3>: serde tokio");
+        
+        let html = ReportTemplate::from_source("review.html", "<h1>{{ title }}</h1>");
+        assert_eq!(html.render(&report).unwrap(), "<h1>Review &lt;Q3&gt;</h1>");
+        
+        let typo = ReportTemplate::from_source("review.txt", "{{ tittle }}");
+        assert!(matches!(typo.render(&report), Err(AdapterError::ConfigurationInvalid { .. })));
+    }
+}
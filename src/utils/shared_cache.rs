@@ -0,0 +1,319 @@
+//! Cache directory shared by concurrent adapter runs
+//!
+//! Several CI jobs on one machine may use the same cache at once. Each
+//! top-level entry (`advisory-db`, `classification`, ...) is guarded by an
+//! advisory file lock in `.locks/`: readers hold it shared, writers
+//! exclusive. Entries that cannot be updated file by file are rebuilt in a
+//! staging directory and swapped in while the exclusive lock is held, so a
+//! reader never sees a half-synced entry. Staging directories left behind
+//! by a crashed writer are removed by `clean`.
+
+use crate::config::RustAdapterConfig;
+use crate::error::{AdapterError, Result};
+use crate::utils::atomic_write::PARTIAL_WRITE_MARKER;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Cache directory below the user cache directory
+const DEFAULT_CACHE_SUBDIR: &str = "rust-adapter";
+
+/// Directory holding the entry lock files
+const LOCK_DIR: &str = ".locks";
+
+/// Interval between attempts to take a contended lock
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shared cache directory
+#[derive(Debug, Clone)]
+pub struct SharedCache {
+    /// Cache root
+    root: PathBuf,
+    /// Maximum time to wait for a lock held by another process
+    lock_timeout: Duration,
+}
+
+/// Lock on a cache entry, released on drop
+#[derive(Debug)]
+pub struct CacheLock {
+    /// Locked file
+    _file: File,
+}
+
+/// Usage of one cache entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheEntryStatus {
+    /// Entry name
+    pub name: String,
+    /// Entry directory
+    pub path: PathBuf,
+    /// Total size of the entry's files (bytes)
+    pub size_bytes: u64,
+    /// Number of files
+    pub file_count: usize,
+    /// Last modification time (RFC 3339)
+    pub modified: Option<String>,
+    /// Whether another process holds the entry's lock exclusively
+    pub locked: bool,
+}
+
+impl SharedCache {
+    /// Create a cache rooted at `root`
+    pub fn new(root: PathBuf, lock_timeout: Duration) -> Self {
+        Self { root, lock_timeout }
+    }
+    
+    /// Cache configured for the adapter, `None` if no location is known
+    pub fn from_config(config: &RustAdapterConfig) -> Option<Self> {
+        Self::configured(config.cache_config.shared_dir.clone(), config.cache_config.lock_timeout)
+    }
+    
+    /// Cache at `shared_dir`, or the default root, with a lock timeout in seconds
+    pub fn configured(shared_dir: Option<PathBuf>, lock_timeout: u64) -> Option<Self> {
+        let root = shared_dir.or_else(Self::default_root)?;
+        Some(Self::new(root, Duration::from_secs(lock_timeout)))
+    }
+    
+    /// Default cache root shared by all projects of the current user
+    ///
+    /// Uses `$XDG_CACHE_HOME`, falling back to `$HOME/.cache`.
+    pub fn default_root() -> Option<PathBuf> {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .map(|dir| dir.join(DEFAULT_CACHE_SUBDIR))
+    }
+    
+    /// Cache root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    
+    /// Directory of an entry
+    pub fn entry_path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+    
+    /// Lock an entry for reading
+    ///
+    /// Blocks for up to the lock timeout while a writer holds the entry.
+    pub fn lock_shared(&self, name: &str) -> Result<CacheLock> {
+        self.lock(name, false)
+    }
+    
+    /// Lock an entry for writing
+    ///
+    /// Blocks for up to the lock timeout while any other process holds the entry.
+    pub fn lock_exclusive(&self, name: &str) -> Result<CacheLock> {
+        self.lock(name, true)
+    }
+    
+    /// Whether an entry exists and was modified less than `max_age` ago
+    pub fn is_fresh(&self, name: &str, max_age: Duration) -> bool {
+        std::fs::metadata(self.entry_path(name))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < max_age)
+    }
+    
+    /// Create an empty staging directory for rebuilding an entry
+    ///
+    /// The caller must hold the entry's exclusive lock until the staging
+    /// directory is committed with [`SharedCache::commit_staging`].
+    pub fn create_staging(&self, name: &str) -> Result<PathBuf> {
+        let staging = self.root.join(format!("{}{}{}", name, PARTIAL_WRITE_MARKER, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&staging)
+            .map_err(|_| AdapterError::permission_denied(&staging, "creating cache staging directory"))?;
+        Ok(staging)
+    }
+    
+    /// Replace an entry with a populated staging directory
+    pub fn commit_staging(&self, name: &str, staging: &Path) -> Result<()> {
+        let current = self.entry_path(name);
+        let retired = self.root.join(format!("{}{}{}", name, PARTIAL_WRITE_MARKER, uuid::Uuid::new_v4()));
+        
+        if current.exists() {
+            std::fs::rename(&current, &retired)
+                .map_err(|_| AdapterError::permission_denied(&current, "retiring cache entry"))?;
+        }
+        std::fs::rename(staging, &current)
+            .map_err(|_| AdapterError::permission_denied(&current, "committing cache entry"))?;
+        
+        if retired.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&retired) {
+                tracing::debug!(path = %retired.display(), error = %e, "Failed to remove retired cache entry");
+            }
+        }
+        Ok(())
+    }
+    
+    /// Usage of every entry, sorted by name
+    pub fn status(&self) -> Vec<CacheEntryStatus> {
+        let mut entries: Vec<_> = self.entry_names().into_iter()
+            .map(|name| {
+                let path = self.entry_path(&name);
+                let (size_bytes, file_count) = walkdir::WalkDir::new(&path).into_iter()
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_file())
+                    .fold((0, 0), |(size, count), entry| {
+                        (size + entry.metadata().map(|m| m.len()).unwrap_or(0), count + 1)
+                    });
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()
+                    .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339());
+                let locked = self.try_lock(&name, false).ok().flatten().is_none();
+                
+                CacheEntryStatus { name, path, size_bytes, file_count, modified, locked }
+            })
+            .collect();
+        
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+    
+    /// Remove an entry, or every entry, returning the names removed
+    ///
+    /// Staging directories of the removed entries are removed too. Each
+    /// entry is removed under its exclusive lock.
+    pub fn clean(&self, name: Option<&str>) -> Result<Vec<String>> {
+        let names = match name {
+            Some(name) => vec![name.to_string()],
+            None => self.entry_names(),
+        };
+        
+        let mut removed = Vec::new();
+        for name in names {
+            let _lock = self.lock_exclusive(&name)?;
+            let paths = self.entry_dirs(&name);
+            for path in &paths {
+                std::fs::remove_dir_all(path)
+                    .map_err(|_| AdapterError::permission_denied(path, "removing cache entry"))?;
+            }
+            if !paths.is_empty() {
+                removed.push(name);
+            }
+        }
+        
+        Ok(removed)
+    }
+    
+    /// Directories `clean` would remove, without removing them
+    pub fn plan_clean(&self, name: Option<&str>) -> Vec<PathBuf> {
+        let names = match name {
+            Some(name) => vec![name.to_string()],
+            None => self.entry_names(),
+        };
+        
+        names.iter().flat_map(|name| self.entry_dirs(name)).collect()
+    }
+    
+    /// Entry directory and staging directories of an entry that exist
+    fn entry_dirs(&self, name: &str) -> Vec<PathBuf> {
+        let staging_prefix = format!("{}{}", name, PARTIAL_WRITE_MARKER);
+        let leftovers = std::fs::read_dir(&self.root).into_iter().flatten().flatten()
+            .filter(|entry| entry.file_name().to_str().is_some_and(|n| n.starts_with(&staging_prefix)))
+            .map(|entry| entry.path());
+        
+        std::iter::once(self.entry_path(name))
+            .chain(leftovers)
+            .filter(|path| path.is_dir())
+            .collect()
+    }
+    
+    /// Names of the entries present, including those only left as staging directories
+    fn entry_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(&self.root).into_iter().flatten().flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| name != LOCK_DIR)
+            .map(|name| match name.find(PARTIAL_WRITE_MARKER) {
+                Some(index) => name[..index].to_string(),
+                None => name,
+            })
+            .collect();
+        
+        names.sort();
+        names.dedup();
+        names
+    }
+    
+    /// Take an entry lock, waiting up to the lock timeout
+    fn lock(&self, name: &str, exclusive: bool) -> Result<CacheLock> {
+        let started = Instant::now();
+        loop {
+            if let Some(lock) = self.try_lock(name, exclusive)? {
+                return Ok(lock);
+            }
+            if started.elapsed() >= self.lock_timeout {
+                return Err(AdapterError::CacheLocked {
+                    path: self.entry_path(name),
+                    timeout: self.lock_timeout,
+                    source: anyhow::anyhow!("Cache entry '{}' is locked by another process", name),
+                });
+            }
+            std::thread::sleep(LOCK_POLL_INTERVAL);
+        }
+    }
+    
+    /// Take an entry lock if it is free
+    fn try_lock(&self, name: &str, exclusive: bool) -> Result<Option<CacheLock>> {
+        let lock_dir = self.root.join(LOCK_DIR);
+        std::fs::create_dir_all(&lock_dir)
+            .map_err(|_| AdapterError::permission_denied(&lock_dir, "creating cache lock directory"))?;
+        
+        let path = lock_dir.join(format!("{}.lock", name));
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path)
+            .map_err(|_| AdapterError::permission_denied(&path, "opening cache lock"))?;
+        
+        let attempt = if exclusive { file.try_lock() } else { file.try_lock_shared() };
+        match attempt {
+            Ok(()) => Ok(Some(CacheLock { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(_)) => Err(AdapterError::permission_denied(&path, "locking cache entry")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    
+    #[test]
+    fn test_locking_staging_and_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SharedCache::new(temp_dir.path().to_path_buf(), Duration::from_millis(200));
+        
+        // Writers exclude readers and other writers; readers share
+        let shared = cache.lock_shared("advisory-db").unwrap();
+        let second_reader = cache.lock_shared("advisory-db").unwrap();
+        assert!(matches!(cache.lock_exclusive("advisory-db"), Err(AdapterError::CacheLocked { .. })));
+        drop((shared, second_reader));
+        
+        let lock = cache.lock_exclusive("advisory-db").unwrap();
+        for content in ["old", "new"] {
+            let staging = cache.create_staging("advisory-db").unwrap();
+            std::fs::write(staging.join("db.json"), content).unwrap();
+            cache.commit_staging("advisory-db", &staging).unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(cache.entry_path("advisory-db").join("db.json")).unwrap(), "new");
+        assert!(cache.is_fresh("advisory-db", Duration::from_secs(60)));
+        
+        // A staging directory left by a crashed writer
+        cache.create_staging("advisory-db").unwrap();
+        let status = cache.status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].name, "advisory-db");
+        assert_eq!(status[0].file_count, 1);
+        assert_eq!(status[0].size_bytes, 3);
+        drop(lock);
+        
+        assert_eq!(cache.plan_clean(None).len(), 2);
+        assert!(cache.entry_path("advisory-db").exists());
+        assert_eq!(cache.clean(None).unwrap(), vec!["advisory-db".to_string()]);
+        assert!(cache.status().is_empty());
+        assert!(!cache.is_fresh("advisory-db", Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,286 @@
+//! OpenTelemetry metrics and traces
+//!
+//! Components report operation durations, package and finding counts and
+//! cache accesses through the functions in this module. In builds with the
+//! `otel` feature they are recorded on the global OpenTelemetry meter and
+//! exported over OTLP/HTTP once [`init`] has installed an exporter; without
+//! the feature, or when telemetry is disabled, they do nothing.
+//!
+//! Exported metrics:
+//!
+//! - `adapter.operation.duration` (histogram, seconds), by `operation` and `outcome`
+//! - `adapter.operation.packages` (histogram), packages handled, by `operation`
+//! - `adapter.audit.findings` (counter), by `severity`
+//! - `adapter.cache.requests` (counter), by `cache` and `result` (`hit` or `miss`)
+//!
+//! The cache hit rate is `result="hit"` over all requests of a cache.
+
+use crate::config::TelemetryConfig;
+use crate::error::Result;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Instrumentation scope of all metrics and spans
+#[cfg(feature = "otel")]
+const SCOPE: &str = "rust-ecosystem-adapter";
+
+/// Exporters installed by [`init`]
+///
+/// Dropping the guard flushes pending metrics and spans and shuts the
+/// exporters down, so it must live until the process is done.
+#[derive(Debug)]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    meter_provider: opentelemetry_sdk::metrics::SdkMeterProvider,
+    #[cfg(feature = "otel")]
+    tracer_provider: opentelemetry_sdk::trace::SdkTracerProvider,
+}
+
+impl TelemetryGuard {
+    /// Layer exporting `tracing` spans as OpenTelemetry traces
+    pub fn tracing_layer<S>(&self) -> Option<Box<dyn Layer<S> + Send + Sync>>
+    where
+        S: Subscriber + Send + Sync + for<'a> LookupSpan<'a>,
+    {
+        #[cfg(feature = "otel")]
+        {
+            use opentelemetry::trace::TracerProvider;
+            let tracer = self.tracer_provider.tracer(SCOPE);
+            Some(Box::new(tracing_opentelemetry::layer().with_tracer(tracer)))
+        }
+        #[cfg(not(feature = "otel"))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.tracer_provider.shutdown() {
+            tracing::warn!(error = %e, "Failed to flush traces");
+        }
+        if let Err(e) = self.meter_provider.shutdown() {
+            tracing::warn!(error = %e, "Failed to flush metrics");
+        }
+    }
+}
+
+/// Build the log subscriber without installing it
+///
+/// Log lines go to `writer`, spans additionally to the exporters of
+/// `telemetry`. The CLI installs the subscriber process-wide; embedders
+/// serving several tenants can scope one per request with
+/// `tracing::subscriber::with_default` instead.
+pub fn subscriber<W>(filter: EnvFilter, telemetry: Option<&TelemetryGuard>, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let otel_layer = telemetry.and_then(|guard| guard.tracing_layer());
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(otel_layer)
+        .with(tracing_subscriber::fmt::layer().with_writer(writer).with_target(false).without_time().compact())
+}
+
+/// Install the OTLP exporters described by `config`
+///
+/// Returns `None` when telemetry is disabled. Enabling it in a build
+/// without the `otel` feature is a configuration error.
+pub fn init(config: &TelemetryConfig) -> Result<Option<TelemetryGuard>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    
+    #[cfg(feature = "otel")]
+    {
+        otel::install(config).map(Some)
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        Err(crate::error::AdapterError::ConfigurationInvalid {
+            field: "telemetry_config.enabled".to_string(),
+            value: "true".to_string(),
+            reason: "Telemetry export requires building with the `otel` feature".to_string(),
+            source: anyhow::anyhow!("otel feature not enabled"),
+        })
+    }
+}
+
+/// Time `operation`, recording its duration and outcome
+///
+/// The outcome is `ok` or the error code of the failure.
+pub async fn measure<T, F>(operation: &'static str, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let started = Instant::now();
+    let result = future.await;
+    let outcome = match &result {
+        Ok(_) => "ok",
+        Err(e) => e.error_code(),
+    };
+    record_duration(operation, outcome, started.elapsed());
+    result
+}
+
+/// Record how long an operation took
+pub fn record_duration(operation: &'static str, outcome: &'static str, duration: Duration) {
+    tracing::debug!(operation, outcome, duration_ms = duration.as_millis() as u64, "Operation finished");
+    #[cfg(feature = "otel")]
+    otel::instruments().duration.record(
+        duration.as_secs_f64(),
+        &[otel::attribute("operation", operation), otel::attribute("outcome", outcome)],
+    );
+}
+
+/// Record the number of packages an operation handled
+pub fn record_packages(operation: &'static str, count: usize) {
+    #[cfg(feature = "otel")]
+    otel::instruments().packages.record(count as u64, &[otel::attribute("operation", operation)]);
+    #[cfg(not(feature = "otel"))]
+    let _ = (operation, count);
+}
+
+/// Record audit findings of one severity
+pub fn record_findings(severity: &'static str, count: usize) {
+    #[cfg(feature = "otel")]
+    otel::instruments().findings.add(count as u64, &[otel::attribute("severity", severity)]);
+    #[cfg(not(feature = "otel"))]
+    let _ = (severity, count);
+}
+
+/// Record a lookup in a cache
+pub fn record_cache_access(cache: &'static str, hit: bool) {
+    #[cfg(feature = "otel")]
+    otel::instruments().cache_requests.add(1, &[
+        otel::attribute("cache", cache),
+        otel::attribute("result", if hit { "hit" } else { "miss" }),
+    ]);
+    #[cfg(not(feature = "otel"))]
+    let _ = (cache, hit);
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use super::{TelemetryGuard, SCOPE};
+    use crate::config::TelemetryConfig;
+    use crate::error::{AdapterError, Result};
+    use opentelemetry::metrics::{Counter, Histogram};
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+    
+    /// Metric instruments, created on first use from the global meter
+    pub(super) struct Instruments {
+        pub duration: Histogram<f64>,
+        pub packages: Histogram<u64>,
+        pub findings: Counter<u64>,
+        pub cache_requests: Counter<u64>,
+    }
+    
+    /// Instruments of the meter provider installed by [`install`]
+    ///
+    /// Instruments created before the provider is installed stay no-ops,
+    /// so [`install`] creates them right after installing it.
+    pub(super) fn instruments() -> &'static Instruments {
+        static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+        INSTRUMENTS.get_or_init(|| {
+            let meter = opentelemetry::global::meter(SCOPE);
+            Instruments {
+                duration: meter.f64_histogram("adapter.operation.duration")
+                    .with_unit("s")
+                    .with_description("Duration of adapter operations")
+                    .build(),
+                packages: meter.u64_histogram("adapter.operation.packages")
+                    .with_description("Packages handled by an adapter operation")
+                    .build(),
+                findings: meter.u64_counter("adapter.audit.findings")
+                    .with_description("Security audit findings")
+                    .build(),
+                cache_requests: meter.u64_counter("adapter.cache.requests")
+                    .with_description("Cache lookups by result")
+                    .build(),
+            }
+        })
+    }
+    
+    pub(super) fn attribute(key: &'static str, value: &'static str) -> KeyValue {
+        KeyValue::new(key, value)
+    }
+    
+    /// Build the OTLP exporters and install them as global providers
+    pub(super) fn install(config: &TelemetryConfig) -> Result<TelemetryGuard> {
+        let resource = Resource::builder()
+            .with_service_name(config.service_name.clone())
+            .build();
+        
+        let mut metric_exporter = MetricExporter::builder().with_http();
+        let mut span_exporter = SpanExporter::builder().with_http();
+        if let Some(endpoint) = &config.otlp_endpoint {
+            let endpoint = endpoint.trim_end_matches('/');
+            metric_exporter = metric_exporter.with_endpoint(format!("{}/v1/metrics", endpoint));
+            span_exporter = span_exporter.with_endpoint(format!("{}/v1/traces", endpoint));
+        }
+        let metric_exporter = metric_exporter.build().map_err(|e| exporter_error(config, e))?;
+        let span_exporter = span_exporter.build().map_err(|e| exporter_error(config, e))?;
+        
+        let reader = PeriodicReader::builder(metric_exporter)
+            .with_interval(Duration::from_secs(config.export_interval.max(1)))
+            .build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(reader)
+            .with_resource(resource.clone())
+            .build();
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(resource)
+            .build();
+        
+        opentelemetry::global::set_meter_provider(meter_provider.clone());
+        opentelemetry::global::set_tracer_provider(tracer_provider.clone());
+        instruments();
+        
+        Ok(TelemetryGuard { meter_provider, tracer_provider })
+    }
+    
+    fn exporter_error(config: &TelemetryConfig, error: impl std::fmt::Display) -> AdapterError {
+        AdapterError::ConfigurationInvalid {
+            field: "telemetry_config.otlp_endpoint".to_string(),
+            value: config.otlp_endpoint.clone().unwrap_or_default(),
+            reason: "Failed to create OTLP exporter".to_string(),
+            source: anyhow::anyhow!("{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AdapterError;
+    
+    #[tokio::test]
+    async fn test_measure_passes_result_through() {
+        assert!(init(&TelemetryConfig::default()).unwrap().is_none());
+        
+        let ok = measure("parse", async { Ok(3) }).await;
+        assert_eq!(ok.unwrap(), 3);
+        
+        let err: Result<()> = measure("audit", async { Err(AdapterError::tool_not_found("cargo-audit")) }).await;
+        assert!(matches!(err, Err(AdapterError::ToolNotFound { .. })));
+        
+        record_packages("parse", 12);
+        record_findings("high", 2);
+        record_cache_access("registry", true);
+    }
+}
@@ -0,0 +1,364 @@
+//! External tool invocation
+//!
+//! Components run cargo, cargo-audit and cargo-vet through a
+//! [`ToolInvoker`] instead of spawning processes directly. The default
+//! [`ProcessInvoker`] runs the real tools; [`RecordingInvoker`] captures
+//! their output as fixtures and [`ReplayInvoker`] plays fixtures back, so
+//! tests do not need the tools installed.
+
+use crate::error::{AdapterError, Result};
+use crate::utils::write_atomic;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::{resolve_program, CommandRunner};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// External command to run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolInvocation {
+    /// Tool name used in errors and logs, e.g. "cargo-audit"
+    pub tool: String,
+    /// Program to execute
+    pub program: String,
+    /// Program arguments
+    pub args: Vec<String>,
+    /// Working directory
+    pub current_dir: Option<PathBuf>,
+    /// Additional environment variables
+    pub env: Vec<(String, String)>,
+    /// Time limit, `None` for no limit
+    pub timeout: Option<Duration>,
+    /// Whether stdout and stderr are captured rather than passed to the terminal
+    pub capture_output: bool,
+    /// Most bytes of stdout captured before the tool is killed, `None` for no limit
+    pub stdout_limit: Option<u64>,
+}
+
+impl ToolInvocation {
+    /// Invocation of `program` reported as `tool`, with captured output
+    pub fn new(tool: &str, program: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            program: program.to_string(),
+            args: Vec::new(),
+            current_dir: None,
+            env: Vec::new(),
+            timeout: None,
+            capture_output: true,
+            stdout_limit: None,
+        }
+    }
+    
+    /// Append one argument
+    pub fn arg(mut self, arg: impl AsRef<std::ffi::OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_string_lossy().to_string());
+        self
+    }
+    
+    /// Append arguments
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        self.args.extend(args.into_iter().map(|arg| arg.as_ref().to_string_lossy().to_string()));
+        self
+    }
+    
+    /// Set the working directory
+    pub fn current_dir(mut self, dir: &Path) -> Self {
+        self.current_dir = Some(dir.to_path_buf());
+        self
+    }
+    
+    /// Set an environment variable
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.env.push((key.to_string(), value.to_string()));
+        self
+    }
+    
+    /// Set the time limit
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    
+    /// Kill the tool once its stdout grows past `limit` bytes
+    pub fn stdout_limit(mut self, limit: u64) -> Self {
+        self.stdout_limit = Some(limit);
+        self
+    }
+    
+    /// Pass stdout and stderr through to the terminal
+    pub fn inherit_output(mut self) -> Self {
+        self.capture_output = false;
+        self
+    }
+    
+    /// Program and arguments, the key fixtures are matched by
+    ///
+    /// The working directory is left out, since it differs between the
+    /// recording and the replaying machine.
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Captured result of a finished tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ToolOutput {
+    /// Exit code, -1 if the tool was terminated by a signal
+    pub exit_code: i32,
+    /// Captured stdout
+    pub stdout: String,
+    /// Captured stderr
+    pub stderr: String,
+    /// Whether the tool was killed for exceeding the invocation's stdout limit
+    #[serde(default)]
+    pub stdout_limit_exceeded: bool,
+}
+
+impl ToolOutput {
+    /// Output of a tool that exited successfully
+    pub fn success(stdout: &str) -> Self {
+        Self {
+            exit_code: 0,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            stdout_limit_exceeded: false,
+        }
+    }
+    
+    /// Output of a tool that failed
+    pub fn failure(exit_code: i32, stderr: &str) -> Self {
+        Self {
+            exit_code,
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            stdout_limit_exceeded: false,
+        }
+    }
+    
+    /// Whether the tool exited with code 0
+    pub fn is_success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+/// Runs external tools
+///
+/// Implementations return the output of tools that ran to completion,
+/// whatever their exit code; errors are reserved for tools that could not
+/// be started, timed out or were cancelled.
+#[async_trait]
+pub trait ToolInvoker: std::fmt::Debug + Send + Sync {
+    /// Run a tool until it exits, times out or `cancel` is cancelled
+    async fn invoke(&self, invocation: &ToolInvocation, cancel: &CancellationToken) -> Result<ToolOutput>;
+}
+
+/// Invoker running the real tools as child processes
+///
+/// On wasm32, where no process can be spawned, every tool counts as not
+/// installed.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessInvoker;
+
+impl ProcessInvoker {
+    /// Shared process invoker, the default for all components
+    pub fn shared() -> Arc<dyn ToolInvoker> {
+        Arc::new(Self)
+    }
+}
+
+#[async_trait]
+impl ToolInvoker for ProcessInvoker {
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn invoke(&self, invocation: &ToolInvocation, cancel: &CancellationToken) -> Result<ToolOutput> {
+        let mut cmd = tokio::process::Command::new(resolve_program(&invocation.program));
+        cmd.args(&invocation.args);
+        if let Some(dir) = &invocation.current_dir {
+            cmd.current_dir(dir);
+        }
+        for (key, value) in &invocation.env {
+            cmd.env(key, value);
+        }
+        if invocation.capture_output {
+            cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+        
+        let output = match invocation.stdout_limit {
+            Some(limit) => CommandRunner::execute_with_limit(cmd, &invocation.tool, invocation.timeout, cancel, limit).await?,
+            None => Some(CommandRunner::execute(cmd, &invocation.tool, invocation.timeout, cancel).await?),
+        };
+        let Some(output) = output else {
+            return Ok(ToolOutput { exit_code: -1, stdout_limit_exceeded: true, ..ToolOutput::default() });
+        };
+        Ok(ToolOutput {
+            exit_code: output.status.code().unwrap_or(-1),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            stdout_limit_exceeded: false,
+        })
+    }
+    
+    #[cfg(target_arch = "wasm32")]
+    async fn invoke(&self, invocation: &ToolInvocation, _cancel: &CancellationToken) -> Result<ToolOutput> {
+        Err(AdapterError::tool_not_found(&invocation.program))
+    }
+}
+
+/// Tool output captured for one command line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolFixture {
+    /// Program and arguments, see [`ToolInvocation::command_line`]
+    pub command: String,
+    /// Recorded output
+    pub output: ToolOutput,
+}
+
+/// Invoker that runs tools through another invoker and saves their output as fixtures
+///
+/// Each command line is written to its own JSON file in the fixture
+/// directory, replacing an earlier recording of the same command.
+#[derive(Debug)]
+pub struct RecordingInvoker {
+    /// Invoker running the tools
+    inner: Arc<dyn ToolInvoker>,
+    /// Directory fixtures are written to
+    dir: PathBuf,
+}
+
+impl RecordingInvoker {
+    /// Record the tools run by `inner` into `dir`
+    pub fn new(inner: Arc<dyn ToolInvoker>, dir: &Path) -> Self {
+        Self {
+            inner,
+            dir: dir.to_path_buf(),
+        }
+    }
+    
+    /// Fixture file name for a command line
+    fn fixture_name(command: &str) -> String {
+        let slug: String = command.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        format!("{}.json", slug.trim_matches('_'))
+    }
+}
+
+#[async_trait]
+impl ToolInvoker for RecordingInvoker {
+    async fn invoke(&self, invocation: &ToolInvocation, cancel: &CancellationToken) -> Result<ToolOutput> {
+        let output = self.inner.invoke(invocation, cancel).await?;
+        
+        let command = invocation.command_line();
+        let fixture = ToolFixture { command: command.clone(), output: output.clone() };
+        let content = serde_json::to_string_pretty(&fixture)
+            .map_err(|e| AdapterError::Internal {
+                message: "Failed to serialize tool fixture".to_string(),
+                source: anyhow::anyhow!("{}", e),
+            })?;
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|_| AdapterError::permission_denied(&self.dir, "creating tool fixture directory"))?;
+        write_atomic(self.dir.join(Self::fixture_name(&command)), content.as_bytes())?;
+        
+        Ok(output)
+    }
+}
+
+/// Invoker answering from recorded fixtures without running anything
+///
+/// Commands without a fixture fail as if the tool were not installed.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayInvoker {
+    /// Recorded output keyed by command line
+    fixtures: HashMap<String, ToolOutput>,
+}
+
+impl ReplayInvoker {
+    /// Invoker without fixtures
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Load all fixtures written by [`RecordingInvoker`] to `dir`
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|_| AdapterError::file_not_found(dir, "reading tool fixtures"))?;
+        
+        let mut replay = Self::new();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .map_err(|_| AdapterError::file_not_found(&path, "reading tool fixture"))?;
+            let fixture: ToolFixture = serde_json::from_str(&content)
+                .map_err(|e| AdapterError::MetadataParseError {
+                    field: "tool fixture".to_string(),
+                    value: path.display().to_string(),
+                    source: anyhow::anyhow!("Invalid tool fixture: {}", e),
+                })?;
+            replay.fixtures.insert(fixture.command, fixture.output);
+        }
+        
+        Ok(replay)
+    }
+    
+    /// Answer `command` (program and arguments) with `output`
+    pub fn with_output(mut self, command: &str, output: ToolOutput) -> Self {
+        self.fixtures.insert(command.to_string(), output);
+        self
+    }
+}
+
+#[async_trait]
+impl ToolInvoker for ReplayInvoker {
+    async fn invoke(&self, invocation: &ToolInvocation, cancel: &CancellationToken) -> Result<ToolOutput> {
+        if cancel.is_cancelled() {
+            return Err(AdapterError::cancelled(&invocation.tool));
+        }
+        
+        let command = invocation.command_line();
+        self.fixtures.get(&command)
+            .cloned()
+            .ok_or_else(|| {
+                tracing::debug!(command = %command, "No tool fixture recorded");
+                AdapterError::tool_not_found(&invocation.tool)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cancel = CancellationToken::new();
+        let invocation = ToolInvocation::new("echo", "echo").args(["audit", "--json"]);
+        
+        let recorder = RecordingInvoker::new(ProcessInvoker::shared(), temp_dir.path());
+        let recorded = recorder.invoke(&invocation, &cancel).await.unwrap();
+        assert!(recorded.is_success());
+        assert_eq!(recorded.stdout.trim(), "audit --json");
+        
+        let replay = ReplayInvoker::from_dir(temp_dir.path()).unwrap();
+        assert_eq!(replay.invoke(&invocation, &cancel).await.unwrap(), recorded);
+        
+        // Unrecorded commands behave like missing tools
+        let other = ToolInvocation::new("cargo-vet", "cargo").args(["vet", "dump"]);
+        assert!(matches!(replay.invoke(&other, &cancel).await, Err(AdapterError::ToolNotFound { .. })));
+    }
+}
@@ -0,0 +1,106 @@
+//! WebAssembly bindings
+//!
+//! Exposes Cargo.lock parsing and TCS classification to JavaScript so the
+//! Control Plane UI can preview an uploaded lockfile without a server
+//! round trip. Only the pure parts of the adapter are bound: nothing here
+//! reads files, spawns processes or touches the network, and the
+//! classification result cache is always disabled.
+//!
+//! The package is not built as a `cdylib` by default, so build the module
+//! and its JavaScript glue with:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/rust_ecosystem_adapter.wasm
+//! ```
+//!
+//! The `online` feature is not available on wasm32.
+
+use crate::adapter::dependency_parser::DependencyParser;
+use crate::adapter::tcs_classifier::TcsClassifier;
+use crate::config::RustAdapterConfig;
+use crate::models::*;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Project ID recorded in graphs parsed from uploaded lockfiles
+const PREVIEW_PROJECT_ID: &str = "preview";
+
+/// Parse Cargo.lock text into an unclassified dependency graph
+#[wasm_bindgen(js_name = parseLockfile)]
+pub fn parse_lockfile(lockfile: &str) -> Result<JsValue, JsError> {
+    let config = preview_config(None)?;
+    let graph = parse_graph(&config, lockfile)?;
+    to_js(&graph)
+}
+
+/// Parse Cargo.lock text and classify every package
+///
+/// `config_toml` uses the `rust-adapter.toml` format; defaults apply when
+/// it is omitted. `project_tcs` optionally carries the project's TCS lists
+/// in the same shape as `Project.tcs`.
+#[wasm_bindgen(js_name = classifyLockfile)]
+pub fn classify_lockfile(
+    lockfile: &str,
+    config_toml: Option<String>,
+    project_tcs: JsValue,
+) -> Result<JsValue, JsError> {
+    let config = preview_config(config_toml.as_deref())?;
+    let mut project = preview_project();
+    if !project_tcs.is_undefined() && !project_tcs.is_null() {
+        project.tcs = serde_wasm_bindgen::from_value(project_tcs)
+            .map_err(|e| JsError::new(&format!("Invalid project TCS lists: {}", e)))?;
+    }
+    
+    let mut graph = parse_graph(&config, lockfile)?;
+    TcsClassifier::new(&config).classify_graph(&project, &mut graph)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    to_js(&graph)
+}
+
+/// Adapter configuration for in-browser use
+fn preview_config(config_toml: Option<&str>) -> Result<RustAdapterConfig, JsError> {
+    let mut config = match config_toml {
+        Some(text) => {
+            let config: RustAdapterConfig = toml::from_str(text)
+                .map_err(|e| JsError::new(&format!("Invalid configuration: {}", e)))?;
+            if config.schema_validation {
+                config.validate().map_err(|e| JsError::new(&e.to_string()))?;
+            }
+            config
+        },
+        None => RustAdapterConfig::default(),
+    };
+    
+    config.offline_mode = true;
+    config.classification_config.disable_result_cache = true;
+    Ok(config)
+}
+
+/// Project standing in for the uploaded lockfile
+fn preview_project() -> Project {
+    Project::new(
+        PREVIEW_PROJECT_ID.to_string(),
+        "Lockfile preview".to_string(),
+        "rust".to_string(),
+        std::path::PathBuf::from("."),
+    )
+}
+
+/// Build the base graph from lockfile text
+fn parse_graph(config: &RustAdapterConfig, lockfile: &str) -> Result<DependencyGraph, JsError> {
+    let mut graph = DependencyParser::new(config)
+        .parse_lockfile_content(&preview_project(), lockfile)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    graph.metadata.offline_mode = true;
+    Ok(graph)
+}
+
+/// Convert a graph to a plain JavaScript object
+fn to_js(graph: &DependencyGraph) -> Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    graph.serialize(&serializer)
+        .map_err(|e| JsError::new(&format!("Failed to convert dependency graph: {}", e)))
+}
@@ -0,0 +1,283 @@
+//! Golden-file tests over the projects in `tests/fixtures`
+//!
+//! Each fixture directory is a small project: `Cargo.toml` and `Cargo.lock`,
+//! optionally `next/Cargo.lock` (the lockfile after a change, for drift) and
+//! `vendor/` (a vendor directory with its checksums manifest). The outputs
+//! generated from it are compared with the JSON files in `expected/`:
+//!
+//! - `graph.json`: dependency graph parsed from `Cargo.lock`
+//! - `sbom.json`: SBOM generated from that graph
+//! - `drift.json`: drift from `Cargo.lock` to `next/Cargo.lock`
+//! - `vendor.json`: packages and sizes of `vendor/`
+//!
+//! Outputs are normalized before comparison: package ids become
+//! `name@version`, other UUIDs and timestamps are masked, and the fixture
+//! path is replaced by `.`. A failing step is recorded as its error code.
+//!
+//! After an intended behaviour change, rewrite the expected files with
+//!
+//! ```text
+//! cargo test --test golden -- --bless
+//! ```
+//!
+//! (or `BLESS=1 cargo test --test golden`) and review the diff. Other
+//! arguments select fixtures by name.
+
+use regex::Regex;
+use rust_ecosystem_adapter::adapter::dependency_parser::DependencyParser;
+use rust_ecosystem_adapter::adapter::drift_detector::DriftDetector;
+use rust_ecosystem_adapter::adapter::sbom_generator::SbomGenerator;
+use rust_ecosystem_adapter::adapter::vendor_manager::VendorManager;
+use rust_ecosystem_adapter::models::{DependencyGraph, EpochPackageState};
+use rust_ecosystem_adapter::{AdapterError, Project, RustAdapterConfig};
+use serde_json::{json, Value};
+use similar::TextDiff;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Keys whose values change on every run
+const VOLATILE_KEYS: &[&str] = &[
+    "analysis_timestamp",
+    "created",
+    "generated_at",
+    "serialNumber",
+    "timestamp",
+    "verified_at",
+];
+
+/// Drift report keys left out of `drift.json`; the impact assessment is derived from the drifts
+const DRIFT_OMITTED_KEYS: &[&str] = &["impact"];
+
+fn main() -> ExitCode {
+    let mut bless = std::env::var_os("BLESS").is_some();
+    let mut filters = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            // Flags cargo passes to every test binary
+            arg if arg.starts_with('-') => {},
+            arg => filters.push(arg.to_string()),
+        }
+    }
+    
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    
+    for case in fixture_cases(&fixtures) {
+        let name = case.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if !filters.is_empty() && !filters.iter().any(|filter| name.contains(filter.as_str())) {
+            continue;
+        }
+        
+        for (output, value) in runtime.block_on(render_case(&case)) {
+            let path = case.join("expected").join(format!("{}.json", output));
+            let actual = serde_json::to_string_pretty(&value).expect("golden output serializes") + "\n";
+            checked += 1;
+            
+            if bless {
+                fs::create_dir_all(path.parent().unwrap()).expect("create expected directory");
+                fs::write(&path, &actual).expect("write golden file");
+                println!("blessed {}/{}", name, output);
+                continue;
+            }
+            
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => println!("ok      {}/{}", name, output),
+                Ok(expected) => {
+                    let diff = TextDiff::from_lines(&expected, &actual)
+                        .unified_diff()
+                        .header("expected", "actual")
+                        .to_string();
+                    failures.push(format!("{}/{} differs from {}:\n{}", name, output, path.display(), diff));
+                },
+                Err(_) => failures.push(format!("{}/{}: {} is missing", name, output, path.display())),
+            }
+        }
+    }
+    
+    if failures.is_empty() {
+        println!("\n{} golden outputs {}", checked, if bless { "blessed" } else { "match" });
+        return ExitCode::SUCCESS;
+    }
+    
+    for failure in &failures {
+        eprintln!("{}\n", failure);
+    }
+    eprintln!("{} of {} golden outputs failed; rerun with `-- --bless` to accept the changes", failures.len(), checked);
+    ExitCode::FAILURE
+}
+
+/// Fixture directories, sorted by name
+fn fixture_cases(fixtures: &Path) -> Vec<PathBuf> {
+    let mut cases: Vec<PathBuf> = fs::read_dir(fixtures)
+        .expect("tests/fixtures exists")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("Cargo.lock").is_file())
+        .collect();
+    cases.sort();
+    cases
+}
+
+/// Normalized outputs of one fixture, keyed by golden file name
+async fn render_case(case: &Path) -> Vec<(&'static str, Value)> {
+    let config = RustAdapterConfig::default();
+    let project = fixture_project(case);
+    let lockfile = fs::read_to_string(project.lockfile_path()).expect("fixture Cargo.lock");
+    let mut outputs = Vec::new();
+    
+    let parser = DependencyParser::new(&config);
+    let graph = parser.parse_lockfile_content(&project, &lockfile);
+    let mut labels = match &graph {
+        Ok(graph) => package_labels(graph),
+        Err(_) => HashMap::new(),
+    };
+    outputs.push(("graph", match &graph {
+        Ok(graph) => to_value(graph),
+        Err(e) => error_value(e),
+    }));
+    
+    let sbom = match &graph {
+        Ok(graph) => match SbomGenerator::new(&config).generate_sbom(&project, graph).await {
+            Ok(sbom) => to_value(&sbom),
+            Err(e) => error_value(&e),
+        },
+        Err(e) => error_value(e),
+    };
+    outputs.push(("sbom", sbom));
+    
+    let next = fixture_project(&case.join("next"));
+    if next.lockfile_path().is_file() {
+        outputs.push(("drift", drift_value(&config, &project, &next, graph.as_ref().ok(), &mut labels)));
+    }
+    
+    let vendor_dir = case.join("vendor");
+    if vendor_dir.is_dir() {
+        outputs.push(("vendor", match VendorManager::new(&config).vendor_info(&project, &vendor_dir) {
+            Ok(info) => vendor_value(&info),
+            Err(e) => error_value(&e),
+        }));
+    }
+    
+    outputs.into_iter()
+        .map(|(name, value)| (name, normalize(value, &labels, case)))
+        .collect()
+}
+
+/// Project rooted at a fixture directory
+fn fixture_project(root: &Path) -> Project {
+    Project::new("golden".to_string(), "Golden Fixture".to_string(), "rust".to_string(), root.to_path_buf())
+}
+
+/// Drift from the fixture lockfile to `next/Cargo.lock`
+///
+/// Lockfiles the parser rejects are compared with empty graphs, so drift
+/// is still reported from the locked packages alone.
+fn drift_value(
+    config: &RustAdapterConfig,
+    project: &Project,
+    next: &Project,
+    graph: Option<&DependencyGraph>,
+    labels: &mut HashMap<String, String>,
+) -> Value {
+    let vendor_manager = VendorManager::new(config);
+    let state = |project: &Project| vendor_manager.locked_packages(project).map(|manifest| EpochPackageState {
+        epoch_id: None,
+        created_at: None,
+        packages: manifest.packages,
+    });
+    let (before, after) = match (state(project), state(next)) {
+        (Ok(before), Ok(after)) => (before, after),
+        (Err(e), _) | (_, Err(e)) => return error_value(&e),
+    };
+    
+    let empty = DependencyGraph::new(project.id.clone(), project.ecosystem.clone());
+    let lockfile = fs::read_to_string(next.lockfile_path()).unwrap_or_default();
+    let next_graph = DependencyParser::new(config).parse_lockfile_content(next, &lockfile).ok();
+    if let Some(next_graph) = &next_graph {
+        labels.extend(package_labels(next_graph));
+    }
+    
+    let report = DriftDetector::new(config).projected_drift(
+        &before,
+        &after,
+        graph.unwrap_or(&empty),
+        next_graph.as_ref().unwrap_or(&empty),
+    );
+    let mut value = to_value(&report);
+    if let Value::Object(map) = &mut value {
+        map.retain(|key, _| !DRIFT_OMITTED_KEYS.contains(&key.as_str()));
+    }
+    value
+}
+
+/// Vendored packages, largest first
+fn vendor_value(info: &rust_ecosystem_adapter::VendorInfo) -> Value {
+    let packages: Vec<Value> = info.packages_by_size().into_iter()
+        .map(|package| json!({
+            "package": format!("{}@{}", package.name, package.version),
+            "source": package.source,
+            "path": package.path,
+            "size_bytes": package.size_bytes,
+        }))
+        .collect();
+    
+    json!({
+        "lockfile_fingerprint": info.lockfile_fingerprint,
+        "offline_ready": info.offline_ready,
+        "total_packages": info.total_packages,
+        "total_size_bytes": info.metadata.total_size_bytes,
+        "packages": packages,
+    })
+}
+
+/// `name@version` label of every package id in a graph
+fn package_labels(graph: &DependencyGraph) -> HashMap<String, String> {
+    graph.root_packages.iter()
+        .map(|package| (package.id.to_string(), format!("{}@{}", package.name, package.version)))
+        .collect()
+}
+
+fn to_value<T: serde::Serialize>(output: &T) -> Value {
+    serde_json::to_value(output).expect("output serializes to JSON")
+}
+
+fn error_value(error: &AdapterError) -> Value {
+    json!({ "error": error.error_code() })
+}
+
+/// Remove run-specific values from an output
+fn normalize(value: Value, labels: &HashMap<String, String>, case: &Path) -> Value {
+    let uuid = Regex::new(r"[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}").unwrap();
+    let case_path = case.to_string_lossy().to_string();
+    
+    fn walk(value: Value, rewrite: &dyn Fn(&str) -> String) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(map.into_iter()
+                .map(|(key, value)| {
+                    let value = if VOLATILE_KEYS.contains(&key.as_str()) {
+                        Value::String("<volatile>".to_string())
+                    } else {
+                        walk(value, rewrite)
+                    };
+                    (rewrite(&key), value)
+                })
+                .collect()),
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| walk(item, rewrite)).collect()),
+            Value::String(text) => Value::String(rewrite(&text)),
+            other => other,
+        }
+    }
+    
+    let rewrite = |text: &str| {
+        let text = text.replace(&case_path, ".");
+        uuid.replace_all(&text, |captures: &regex::Captures| {
+            labels.get(&captures[0]).cloned().unwrap_or_else(|| "<uuid>".to_string())
+        }).to_string()
+    };
+    walk(value, &rewrite)
+}
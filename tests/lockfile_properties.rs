@@ -0,0 +1,394 @@
+//! Property tests of the Cargo.lock parsers
+//!
+//! Cargo.lock is the first untrusted input the adapter reads, so every
+//! parser of lockfile text must turn malformed input into an error rather
+//! than panic, however large or strange the input is. Well-formed
+//! lockfiles are generated in the shapes cargo writes them and checked
+//! against the canonicalization invariants:
+//!
+//! - the same resolution written as format v1 and v3, in any package and
+//!   dependency order, has one fingerprint
+//! - a rendered canonical lockfile parses back to itself
+//! - every spelling of a source normalizes to the same string, and
+//!   normalizing is idempotent
+//!
+//! `tests/fixtures/lockfile-corpus` holds lockfiles in every format cargo
+//! has written (`valid/`) and malformed ones (`invalid/`); together with
+//! this repository's own `Cargo.lock` they pin which inputs parse and seed
+//! the mutation tests.
+//!
+//! proptest records shrunk failures in
+//! `tests/lockfile_properties.proptest-regressions`; commit that file so
+//! they are replayed on every run. Run more cases with
+//!
+//! ```text
+//! PROPTEST_CASES=10000 cargo test --test lockfile_properties
+//! ```
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use rust_ecosystem_adapter::adapter::dependency_parser::DependencyParser;
+use rust_ecosystem_adapter::utils::lockfile::CRATES_IO_SOURCE;
+use rust_ecosystem_adapter::utils::{normalize_source, CanonicalLockfile};
+use rust_ecosystem_adapter::RustAdapterConfig;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Spellings cargo has used for crates.io
+const CRATES_IO_SPELLINGS: &[&str] = &[
+    CRATES_IO_SOURCE,
+    "registry+https://github.com/rust-lang/crates.io-index.git",
+    "sparse+https://index.crates.io/",
+];
+
+/// Fragments of lockfile syntax and characters that trip up parsers
+const LOCKFILE_TOKENS: &[&str] = &[
+    "[[package]]\n", "[metadata]\n", "version = 3\n", "version = 4\n", "name = ", "version = ",
+    "source = ", "checksum = ", "dependencies = [", "]", "[", "\"", "\"\"\"", "'", ",", "=", "\n",
+    "{", "}", "#", "\\", "\\u0000", " (", ")", "registry+", "git+", "sparse+", "https://", "?", "#rev",
+    "\u{0}", "\u{feff}", "\u{202e}", "\u{200b}", "\r\n", "🦀", "é",
+];
+
+/// Package of a generated lockfile
+#[derive(Debug, Clone)]
+struct GenPackage {
+    name: String,
+    version: String,
+    /// Two spellings of the source that normalize to the same string
+    source: Option<[String; 2]>,
+    checksum: Option<String>,
+    /// Indices of the packages depended on
+    dependencies: Vec<usize>,
+}
+
+/// Edit applied to a corpus lockfile
+#[derive(Debug, Clone)]
+enum Mutation {
+    Truncate(Index),
+    DeleteLine(Index),
+    DuplicateLine(Index),
+    Insert(Index, String),
+    ReplaceByte(Index, u8),
+}
+
+fn crate_name() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_-]{0,11}"
+}
+
+fn version() -> impl Strategy<Value = String> {
+    (0..3u32, 0..30u32, 0..30u32, prop::option::of("(alpha|beta|rc)\\.[0-9]"))
+        .prop_map(|(major, minor, patch, pre)| match pre {
+            Some(pre) => format!("{}.{}.{}-{}", major, minor, patch, pre),
+            None => format!("{}.{}.{}", major, minor, patch),
+        })
+}
+
+/// Git source spelling; the host's case and the `.git` or `/` suffix vary
+fn git_spelling(org: &str, repo: &str, query: &Option<String>, rev: &str, host: &str, suffix: &str) -> String {
+    let query = query.as_ref().map(|q| format!("?{}", q)).unwrap_or_default();
+    format!("git+https://{}/{}/{}{}{}#{}", host, org, repo, suffix, query, rev)
+}
+
+fn source() -> impl Strategy<Value = Option<[String; 2]>> {
+    let crates_io = (prop::sample::select(CRATES_IO_SPELLINGS), prop::sample::select(CRATES_IO_SPELLINGS))
+        .prop_map(|(a, b)| Some([a.to_string(), b.to_string()]));
+    let git = (
+        "[a-z]{1,8}",
+        "[a-z][a-z0-9-]{0,10}",
+        prop::option::of("(branch|tag)=[a-z0-9]{1,6}"),
+        "[0-9a-f]{40}",
+        prop::array::uniform2(prop::sample::select(&["github.com", "GitHub.com", "GITHUB.COM"][..])),
+        prop::array::uniform2(prop::sample::select(&["", ".git", "/"][..])),
+    ).prop_map(|(org, repo, query, rev, hosts, suffixes)| Some([
+        git_spelling(&org, &repo, &query, &rev, hosts[0], suffixes[0]),
+        git_spelling(&org, &repo, &query, &rev, hosts[1], suffixes[1]),
+    ]));
+    prop_oneof![Just(None), crates_io, git]
+}
+
+/// Packages with unique names and versions and dependencies between them
+fn packages() -> impl Strategy<Value = Vec<GenPackage>> {
+    prop::collection::btree_map((crate_name(), version()), (source(), "[0-9a-f]{64}"), 1..24)
+        .prop_flat_map(|entries| {
+            let dependencies = prop::collection::vec(prop::collection::vec(any::<Index>(), 0..4), entries.len());
+            (Just(entries), dependencies)
+        })
+        .prop_map(|(entries, dependencies)| {
+            let count = entries.len();
+            entries.into_iter().zip(dependencies).enumerate()
+                .map(|(index, (((name, version), (source, checksum)), dependencies))| {
+                    let mut dependencies: Vec<usize> = dependencies.iter()
+                        .map(|dependency| dependency.index(count))
+                        .filter(|&dependency| dependency != index)
+                        .collect();
+                    dependencies.sort_unstable();
+                    dependencies.dedup();
+                    let is_registry = source.as_ref().is_some_and(|s| !s[0].starts_with("git+"));
+                    GenPackage { name, version, source, checksum: is_registry.then_some(checksum), dependencies }
+                })
+                .collect()
+        })
+}
+
+/// Write packages in lockfile format v1: full references, checksums in `[metadata]`
+fn write_v1(packages: &[GenPackage]) -> String {
+    let mut out = String::new();
+    let mut metadata = String::new();
+    for package in packages {
+        writeln!(out, "[[package]]\nname = \"{}\"\nversion = \"{}\"", package.name, package.version).unwrap();
+        if let Some(source) = &package.source {
+            writeln!(out, "source = \"{}\"", source[0]).unwrap();
+            if let Some(checksum) = &package.checksum {
+                writeln!(metadata, "\"checksum {} {} ({})\" = \"{}\"", package.name, package.version, source[0], checksum).unwrap();
+            }
+        }
+        if !package.dependencies.is_empty() {
+            out.push_str("dependencies = [\n");
+            for &dependency in &package.dependencies {
+                let target = &packages[dependency];
+                match &target.source {
+                    Some(source) => writeln!(out, " \"{} {} ({})\",", target.name, target.version, source[0]).unwrap(),
+                    None => writeln!(out, " \"{} {}\",", target.name, target.version).unwrap(),
+                }
+            }
+            out.push_str("]\n");
+        }
+        out.push('\n');
+    }
+    if !metadata.is_empty() {
+        out.push_str("[metadata]\n");
+        out.push_str(&metadata);
+    }
+    out
+}
+
+/// Write packages in lockfile format v3 in `order`, with inline checksums
+/// and dependencies listed in reverse
+fn write_v3(packages: &[GenPackage], order: &[usize]) -> String {
+    let mut out = String::from("# This file is automatically @generated by Cargo.\nversion = 3\n\n");
+    for &index in order {
+        let package = &packages[index];
+        writeln!(out, "[[package]]\nname = \"{}\"\nversion = \"{}\"", package.name, package.version).unwrap();
+        if let Some(source) = &package.source {
+            writeln!(out, "source = \"{}\"", source[1]).unwrap();
+        }
+        if let Some(checksum) = &package.checksum {
+            writeln!(out, "checksum = \"{}\"", checksum).unwrap();
+        }
+        if !package.dependencies.is_empty() {
+            out.push_str("dependencies = [\n");
+            for &dependency in package.dependencies.iter().rev() {
+                writeln!(out, " \"{} {}\",", packages[dependency].name, packages[dependency].version).unwrap();
+            }
+            out.push_str("]\n");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Lockfile-like text: syntax fragments mixed with arbitrary characters
+fn lockfile_soup() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            3 => prop::sample::select(LOCKFILE_TOKENS).prop_map(str::to_string),
+            1 => "\\PC{0,12}",
+            1 => any::<char>().prop_map(String::from),
+        ],
+        0..200,
+    ).prop_map(|parts| parts.concat())
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        any::<Index>().prop_map(Mutation::Truncate),
+        any::<Index>().prop_map(Mutation::DeleteLine),
+        any::<Index>().prop_map(Mutation::DuplicateLine),
+        (any::<Index>(), prop_oneof![prop::sample::select(LOCKFILE_TOKENS).prop_map(str::to_string), "\\PC{1,4}"])
+            .prop_map(|(at, text)| Mutation::Insert(at, text)),
+        (any::<Index>(), any::<u8>()).prop_map(|(at, byte)| Mutation::ReplaceByte(at, byte)),
+    ]
+}
+
+/// Apply mutations to lockfile bytes, replacing invalid UTF-8 like a lossy reader would
+fn mutate(content: &str, mutations: &[Mutation]) -> String {
+    let mut bytes = content.as_bytes().to_vec();
+    for mutation in mutations {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(bytes.iter().enumerate().filter(|(_, &b)| b == b'\n').map(|(i, _)| i + 1))
+            .filter(|&start| start < bytes.len())
+            .collect();
+        let line = |at: &Index| -> std::ops::Range<usize> {
+            let start = line_starts[at.index(line_starts.len())];
+            let end = bytes[start..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |i| start + i + 1);
+            start..end
+        };
+        match mutation {
+            _ if bytes.is_empty() => {},
+            Mutation::Truncate(at) => bytes.truncate(at.index(bytes.len())),
+            Mutation::DeleteLine(at) => {
+                let range = line(at);
+                bytes.drain(range);
+            },
+            Mutation::DuplicateLine(at) => {
+                let range = line(at);
+                let copy = bytes[range.clone()].to_vec();
+                bytes.splice(range.end..range.end, copy);
+            },
+            Mutation::Insert(at, text) => {
+                let at = at.index(bytes.len() + 1);
+                bytes.splice(at..at, text.bytes());
+            },
+            Mutation::ReplaceByte(at, byte) => {
+                let at = at.index(bytes.len());
+                bytes[at] = *byte;
+            },
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join("lockfile-corpus")
+}
+
+/// Lockfiles in a corpus directory, sorted by path
+fn read_corpus(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("reading {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lock"))
+        .map(|path| {
+            let content = std::fs::read_to_string(&path).unwrap();
+            (path, content)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Lockfiles that must parse: the valid corpus and the real lockfiles in this repository
+fn valid_lockfiles() -> &'static [(PathBuf, String)] {
+    static VALID: OnceLock<Vec<(PathBuf, String)>> = OnceLock::new();
+    VALID.get_or_init(|| {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut files = read_corpus(&corpus_dir().join("valid"));
+        for path in [
+            root.join("Cargo.lock"),
+            root.join("tests/fixtures/registry-to-git/Cargo.lock"),
+            root.join("tests/fixtures/registry-to-git/next/Cargo.lock"),
+        ] {
+            let content = std::fs::read_to_string(&path).unwrap();
+            files.push((path, content));
+        }
+        files
+    })
+}
+
+/// Run every lockfile parser over `content`; the results do not matter, only that they return
+fn parse_all(content: &str) {
+    static PARSER: OnceLock<DependencyParser> = OnceLock::new();
+    let parser = PARSER.get_or_init(|| DependencyParser::new(&RustAdapterConfig::default()));
+    
+    if let Ok(lockfile) = CanonicalLockfile::parse(content) {
+        let _ = lockfile.fingerprint();
+        let _ = lockfile.render();
+    }
+    let _ = parser.parse_lockfile_str(content);
+}
+
+#[test]
+fn test_corpus_parses_or_is_rejected() {
+    for (path, content) in valid_lockfiles() {
+        let lockfile = CanonicalLockfile::parse(content)
+            .unwrap_or_else(|e| panic!("{} should parse: {}", path.display(), e));
+        let rendered = lockfile.render().unwrap();
+        assert_eq!(CanonicalLockfile::parse(&rendered).unwrap(), lockfile, "{} round-trips", path.display());
+        parse_all(content);
+    }
+    
+    let invalid = read_corpus(&corpus_dir().join("invalid"));
+    assert!(!invalid.is_empty());
+    for (path, content) in &invalid {
+        assert!(CanonicalLockfile::parse(content).is_err(), "{} should be rejected", path.display());
+        parse_all(content);
+    }
+}
+
+#[test]
+fn test_huge_inputs_do_not_panic() {
+    let packages: Vec<GenPackage> = (0..20_000)
+        .map(|i| GenPackage {
+            name: format!("crate-{}", i % 5_000),
+            version: format!("1.0.{}", i / 5_000),
+            source: Some([CRATES_IO_SOURCE.to_string(), CRATES_IO_SOURCE.to_string()]),
+            checksum: Some(format!("{:064x}", i)),
+            dependencies: (1..4).map(|d| (i + d * 7_919) % 20_000).collect(),
+        })
+        .collect();
+    let order: Vec<usize> = (0..packages.len()).collect();
+    let lockfile = CanonicalLockfile::parse(&write_v3(&packages, &order)).unwrap();
+    assert_eq!(lockfile.packages.len(), 20_000);
+    
+    let long_name = "a".repeat(4 << 20);
+    parse_all(&format!("version = 3\n[[package]]\nname = \"{}\"\nversion = \"1.0.0\"\n", long_name));
+    
+    let depth = 100_000;
+    parse_all(&format!("version = 3\n[[package]]\nname = \"a\"\nversion = \"1.0.0\"\ndependencies = {}{}\n", "[".repeat(depth), "]".repeat(depth)));
+    parse_all(&format!("version = 3\n[[package]]\nname = \"a\"\nversion = \"1.0.0\"\nsource = {}\n", "{ a = ".repeat(depth)));
+    parse_all(&"[[package]]\n".repeat(depth));
+}
+
+proptest! {
+    #[test]
+    fn prop_arbitrary_text_does_not_panic(content in any::<String>()) {
+        parse_all(&content);
+    }
+    
+    #[test]
+    fn prop_lockfile_soup_does_not_panic(content in lockfile_soup()) {
+        parse_all(&content);
+    }
+    
+    #[test]
+    fn prop_mutated_corpus_does_not_panic(file in any::<Index>(), mutations in prop::collection::vec(mutation(), 1..8)) {
+        let lockfiles = valid_lockfiles();
+        let (_, content) = &lockfiles[file.index(lockfiles.len())];
+        parse_all(&mutate(content, &mutations));
+    }
+    
+    #[test]
+    fn prop_format_and_order_do_not_change_fingerprint(
+        (packages, order) in packages().prop_flat_map(|packages| {
+            let order = Just((0..packages.len()).collect::<Vec<_>>()).prop_shuffle();
+            (Just(packages), order)
+        })
+    ) {
+        let v1 = CanonicalLockfile::parse(&write_v1(&packages)).unwrap();
+        let v3 = CanonicalLockfile::parse(&write_v3(&packages, &order)).unwrap();
+        prop_assert_eq!(v1.packages.len(), packages.len());
+        prop_assert_eq!(&v1, &v3);
+        prop_assert_eq!(v1.fingerprint(), v3.fingerprint());
+    }
+    
+    #[test]
+    fn prop_canonical_lockfile_round_trips(packages in packages()) {
+        let lockfile = CanonicalLockfile::parse(&write_v1(&packages)).unwrap();
+        let reparsed = CanonicalLockfile::parse(&lockfile.render().unwrap()).unwrap();
+        prop_assert_eq!(reparsed, lockfile);
+    }
+    
+    #[test]
+    fn prop_source_spellings_normalize_alike(source in source().prop_filter("has a source", Option::is_some)) {
+        let [a, b] = source.unwrap();
+        let normalized = normalize_source(&a);
+        prop_assert_eq!(&normalized, &normalize_source(&b));
+        prop_assert_eq!(&normalize_source(&normalized), &normalized);
+    }
+    
+    #[test]
+    fn prop_normalize_source_does_not_panic(source in "\\PC{0,64}|(git|registry|sparse)\\+\\PC{0,64}") {
+        let _ = normalize_source(&source);
+    }
+}